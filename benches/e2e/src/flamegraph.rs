@@ -0,0 +1,33 @@
+//! Sampling profiles of the workloads, rendered as flamegraphs.
+//!
+//! Profiling runs separately from [`measure`](crate::measure), so the sampling doesn't skew the
+//! reported timings.
+
+use std::{error::Error, fs::File, path::Path, path::PathBuf};
+
+use crate::Workload;
+
+/// Samples per second taken by the profiler. It's prime to avoid sampling in lockstep with
+/// periodic work.
+const FREQUENCY: i32 = 997;
+
+/// Runs `workload` `iterations` times under a sampling profiler, and writes the flamegraph of
+/// the samples to `<directory>/<name>-<log_size>.svg`. Returns the path of the flamegraph.
+pub fn profile(
+    workload: &dyn Workload,
+    iterations: usize,
+    directory: &Path,
+) -> Result<PathBuf, Box<dyn Error>> {
+    let guard = pprof::ProfilerGuardBuilder::default()
+        .frequency(FREQUENCY)
+        .blocklist(&["libc", "libgcc", "pthread", "vdso"])
+        .build()?;
+    for _ in 0..iterations {
+        workload.run();
+    }
+    let report = guard.report().build()?;
+
+    let path = directory.join(format!("{}-{}.svg", workload.name(), workload.log_size()));
+    report.flamegraph(File::create(&path)?)?;
+    Ok(path)
+}
@@ -0,0 +1,105 @@
+//! Reproducible end-to-end workloads for lambdaworks: STARK and PLONK proofs, multi-scalar
+//! multiplications and FFTs, timed as a whole and reported as JSON lines.
+//!
+//! Inputs are generated from a fixed seed, so two runs of the same workload on the same commit
+//! prove the same statements over the same data.
+
+use std::time::{Duration, Instant};
+
+use serde::Serialize;
+
+#[cfg(feature = "flamegraph")]
+pub mod flamegraph;
+pub mod workloads;
+
+/// A workload whose inputs are built when it's created, so that only [`Workload::run`] is
+/// timed.
+pub trait Workload {
+    /// Name of the workload in the reports, such as `plonk`.
+    fn name(&self) -> &'static str;
+
+    /// Base 2 logarithm of the size of the workload: the trace length, the number of gates,
+    /// the number of points or the number of coefficients.
+    fn log_size(&self) -> u32;
+
+    /// Runs the workload once.
+    fn run(&self);
+}
+
+/// Timings of a workload. Each one is printed as a line of JSON.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct Measurement {
+    pub workload: &'static str,
+    pub log_size: u32,
+    pub iterations: usize,
+    pub mean_ns: u128,
+    pub median_ns: u128,
+    pub min_ns: u128,
+    pub max_ns: u128,
+}
+
+impl Measurement {
+    /// Summarizes the duration of each iteration of a workload.
+    ///
+    /// Panics if `samples` is empty.
+    pub fn new(workload: &'static str, log_size: u32, samples: &[Duration]) -> Self {
+        let mut nanos: Vec<u128> = samples.iter().map(Duration::as_nanos).collect();
+        nanos.sort_unstable();
+
+        Self {
+            workload,
+            log_size,
+            iterations: nanos.len(),
+            mean_ns: nanos.iter().sum::<u128>() / nanos.len() as u128,
+            median_ns: nanos[nanos.len() / 2],
+            min_ns: nanos[0],
+            max_ns: nanos[nanos.len() - 1],
+        }
+    }
+}
+
+/// Runs `workload` once to warm up, and then `iterations` times, timing each run.
+///
+/// Panics if `iterations` is zero.
+pub fn measure(workload: &dyn Workload, iterations: usize) -> Measurement {
+    assert!(iterations > 0, "at least one iteration is needed");
+
+    workload.run();
+    let samples: Vec<Duration> = (0..iterations)
+        .map(|_| {
+            let start = Instant::now();
+            workload.run();
+            start.elapsed()
+        })
+        .collect();
+
+    Measurement::new(workload.name(), workload.log_size(), &samples)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn measurement_summarizes_the_samples() {
+        let samples = [30, 10, 20, 40].map(Duration::from_nanos);
+        let measurement = Measurement::new("fft", 4, &samples);
+
+        assert_eq!(
+            measurement,
+            Measurement {
+                workload: "fft",
+                log_size: 4,
+                iterations: 4,
+                mean_ns: 25,
+                median_ns: 30,
+                min_ns: 10,
+                max_ns: 40,
+            }
+        );
+        assert_eq!(
+            serde_json::to_string(&measurement).unwrap(),
+            r#"{"workload":"fft","log_size":4,"iterations":4,"mean_ns":25,"median_ns":30,"min_ns":10,"max_ns":40}"#
+        );
+    }
+}
@@ -0,0 +1,75 @@
+use std::{
+    fs::File,
+    io::{self, BufWriter, Write},
+    path::PathBuf,
+};
+
+use clap::{builder::PossibleValuesParser, Parser};
+use lambdaworks_bench::{
+    measure,
+    workloads::{workload, SUITE, WORKLOADS},
+};
+
+#[derive(Parser, Debug)]
+#[command(about = "Runs end-to-end lambdaworks workloads and prints their timings as JSON lines")]
+struct BenchArgs {
+    /// Workload to run. The whole suite runs if none is given.
+    #[arg(short, long, value_parser = PossibleValuesParser::new(WORKLOADS), requires = "log_sizes")]
+    workload: Option<String>,
+    /// Comma separated base 2 logarithms of the sizes to run the workload with.
+    #[arg(short, long, value_delimiter = ',', requires = "workload")]
+    log_sizes: Vec<u32>,
+    /// Timed runs of each workload, after a warm-up run.
+    #[arg(short, long, default_value_t = 10, value_parser = clap::value_parser!(u64).range(1..))]
+    iterations: u64,
+    /// File to write the measurements to, instead of the standard output.
+    #[arg(short, long)]
+    output: Option<PathBuf>,
+    /// Directory to write a flamegraph of each workload to.
+    #[cfg(feature = "flamegraph")]
+    #[arg(long)]
+    flamegraph: Option<PathBuf>,
+}
+
+fn run(args: BenchArgs) -> Result<(), Box<dyn std::error::Error>> {
+    let runs: Vec<(&str, Vec<u32>)> = match &args.workload {
+        Some(name) => vec![(name.as_str(), args.log_sizes.clone())],
+        None => SUITE
+            .iter()
+            .map(|(name, log_sizes)| (*name, log_sizes.to_vec()))
+            .collect(),
+    };
+    let mut output: Box<dyn Write> = match &args.output {
+        Some(path) => Box::new(BufWriter::new(File::create(path)?)),
+        None => Box::new(io::stdout().lock()),
+    };
+    let iterations = args.iterations as usize;
+
+    for (name, log_sizes) in runs {
+        for log_size in log_sizes {
+            let workload = workload(name, log_size).ok_or("unknown workload")?;
+            let measurement = measure(workload.as_ref(), iterations);
+            serde_json::to_writer(&mut output, &measurement)?;
+            writeln!(output)?;
+            output.flush()?;
+
+            #[cfg(feature = "flamegraph")]
+            if let Some(directory) = &args.flamegraph {
+                let path = lambdaworks_bench::flamegraph::profile(
+                    workload.as_ref(),
+                    iterations,
+                    directory,
+                )?;
+                eprintln!("Saved flamegraph to {}", path.display());
+            }
+        }
+    }
+    Ok(())
+}
+
+fn main() {
+    if let Err(e) = run(BenchArgs::parse()) {
+        eprintln!("Error while running the benchmarks: {e}");
+        std::process::exit(1);
+    }
+}
@@ -0,0 +1,302 @@
+use std::{collections::HashMap, hint::black_box};
+
+use lambdaworks_crypto::commitments::kzg::StructuredReferenceString;
+use lambdaworks_math::{
+    cyclic_group::IsGroup,
+    elliptic_curve::{
+        short_weierstrass::curves::bls12_381::{
+            curve::BLS12381Curve,
+            default_types::{FrElement, FrField},
+            twist::BLS12381TwistCurve,
+        },
+        traits::IsEllipticCurve,
+    },
+    field::{
+        element::FieldElement, fields::fft_friendly::stark_252_prime_field::Stark252PrimeField,
+    },
+    msm::pippenger,
+    polynomial::Polynomial,
+    unsigned_integer::element::UnsignedInteger,
+};
+use lambdaworks_plonk::{
+    constraint_system::ConstraintSystem,
+    prover::Prover as PlonkProver,
+    setup::{setup, CommonPreprocessedInput, VerificationKey, Witness},
+    test_utils::utils::{
+        G1Point, G2Point, TestRandomFieldGenerator, KZG, ORDER_R_MINUS_1_ROOT_UNITY,
+    },
+};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use stark_platinum_prover::{
+    examples::simple_fibonacci::{self, FibonacciAIR, FibonacciPublicInputs},
+    proof::options::{ProofOptions, SecurityLevel},
+    prover::{IsStarkProver, Prover as StarkProver},
+    trace::TraceTable,
+    transcript::StoneProverTranscript,
+    Felt252,
+};
+
+use crate::Workload;
+
+/// Seed of the random inputs of the workloads.
+pub const SEED: u64 = 42;
+
+/// Names of the workloads, as accepted by [`workload`].
+pub const WORKLOADS: [&str; 4] = ["stark-fibonacci", "plonk", "msm", "fft"];
+
+/// Workloads run when none is requested, with the base 2 logarithms of their sizes.
+pub const SUITE: [(&str, &[u32]); 4] = [
+    ("stark-fibonacci", &[12, 14, 16, 18]),
+    ("plonk", &[20]),
+    ("msm", &[10, 12, 14, 16]),
+    ("fft", &[16, 18, 20, 22]),
+];
+
+/// Builds the workload named `name` with size `2^log_size`, or returns `None` if there's no
+/// workload with that name.
+pub fn workload(name: &str, log_size: u32) -> Option<Box<dyn Workload>> {
+    match name {
+        "stark-fibonacci" => Some(Box::new(StarkFibonacci::new(log_size))),
+        "plonk" => Some(Box::new(PlonkCircuit::new(log_size))),
+        "msm" => Some(Box::new(Msm::new(log_size))),
+        "fft" => Some(Box::new(Fft::new(log_size))),
+        _ => None,
+    }
+}
+
+/// A STARK proof of a Fibonacci trace, with the options of 100 bits of conjectured security.
+pub struct StarkFibonacci {
+    log_trace_length: u32,
+    trace: TraceTable<Stark252PrimeField>,
+    pub_inputs: FibonacciPublicInputs<Stark252PrimeField>,
+    proof_options: ProofOptions,
+}
+
+impl StarkFibonacci {
+    pub fn new(log_trace_length: u32) -> Self {
+        let pub_inputs = FibonacciPublicInputs {
+            a0: Felt252::one(),
+            a1: Felt252::one(),
+        };
+        let trace = simple_fibonacci::fibonacci_trace(
+            [pub_inputs.a0.clone(), pub_inputs.a1.clone()],
+            1 << log_trace_length,
+        );
+
+        Self {
+            log_trace_length,
+            trace,
+            pub_inputs,
+            proof_options: ProofOptions::new_secure(SecurityLevel::Conjecturable100Bits, 3),
+        }
+    }
+}
+
+impl Workload for StarkFibonacci {
+    fn name(&self) -> &'static str {
+        "stark-fibonacci"
+    }
+
+    fn log_size(&self) -> u32 {
+        self.log_trace_length
+    }
+
+    fn run(&self) {
+        let proof = StarkProver::<FibonacciAIR<Stark252PrimeField>>::prove(
+            &self.trace,
+            &self.pub_inputs,
+            &self.proof_options,
+            StoneProverTranscript::new(&[]),
+        )
+        .unwrap();
+        black_box(proof);
+    }
+}
+
+/// A PLONK proof, over BLS12-381, of a circuit that squares a public input repeatedly, with
+/// as many squarings as fit in `2^log_gates` gates.
+pub struct PlonkCircuit {
+    log_gates: u32,
+    prover: PlonkProver<FrField, KZG, TestRandomFieldGenerator>,
+    witness: Witness<FrField>,
+    public_inputs: Vec<FrElement>,
+    common_preprocessed_input: CommonPreprocessedInput<FrField>,
+    verifying_key: VerificationKey<G1Point>,
+}
+
+impl PlonkCircuit {
+    pub fn new(log_gates: u32) -> Self {
+        let system = &mut ConstraintSystem::<FrField>::new();
+        // The public input takes one gate, and each squaring another one.
+        let x = system.new_public_input();
+        let mut y = x;
+        for _ in 1..1 << log_gates {
+            y = system.mul(&y, &y);
+        }
+
+        let common_preprocessed_input =
+            CommonPreprocessedInput::from_constraint_system(system, &ORDER_R_MINUS_1_ROOT_UNITY);
+        let kzg = KZG::new(srs(common_preprocessed_input.n));
+        let verifying_key = setup(&common_preprocessed_input, &kzg);
+
+        let assignments = system
+            .solve(HashMap::from([(x, FrElement::from(3))]))
+            .unwrap();
+        let public_inputs = system.public_input_values(&assignments);
+
+        Self {
+            log_gates,
+            prover: PlonkProver::new(kzg, TestRandomFieldGenerator {}),
+            witness: Witness::new(assignments, system),
+            public_inputs,
+            common_preprocessed_input,
+            verifying_key,
+        }
+    }
+}
+
+impl Workload for PlonkCircuit {
+    fn name(&self) -> &'static str {
+        "plonk"
+    }
+
+    fn log_size(&self) -> u32 {
+        self.log_gates
+    }
+
+    fn run(&self) {
+        let proof = self.prover.prove(
+            &self.witness,
+            &self.public_inputs,
+            &self.common_preprocessed_input,
+            &self.verifying_key,
+        );
+        black_box(proof);
+    }
+}
+
+/// An SRS for `n` gates with toxic waste `s = 2`, like the one of `test_srs`. Its powers of `s`
+/// are computed by doubling, which is much faster than a scalar multiplication per power for
+/// large circuits. It's only sound for benchmarks.
+fn srs(n: usize) -> StructuredReferenceString<G1Point, G2Point> {
+    let powers_main_group: Vec<G1Point> =
+        std::iter::successors(Some(BLS12381Curve::generator()), |power| {
+            Some(power.double())
+        })
+        .take(n + 3)
+        .collect();
+    let g2 = BLS12381TwistCurve::generator();
+    let powers_secondary_group = [g2.clone(), g2.double()];
+
+    StructuredReferenceString::new(&powers_main_group, &powers_secondary_group)
+}
+
+/// A multi-scalar multiplication of `2^log_size` random points of BLS12-381 with Pippenger's
+/// algorithm.
+pub struct Msm {
+    log_size: u32,
+    scalars: Vec<UnsignedInteger<4>>,
+    points: Vec<G1Point>,
+}
+
+impl Msm {
+    pub fn new(log_size: u32) -> Self {
+        let mut rng = StdRng::seed_from_u64(SEED);
+        let g = BLS12381Curve::generator();
+        let mut random_scalar =
+            || FrElement::new(UnsignedInteger { limbs: rng.gen() }).representative();
+
+        let scalars = (0..1 << log_size).map(|_| random_scalar()).collect();
+        let points = (0..1 << log_size)
+            .map(|_| g.operate_with_self(random_scalar()))
+            .collect();
+
+        Self {
+            log_size,
+            scalars,
+            points,
+        }
+    }
+}
+
+impl Workload for Msm {
+    fn name(&self) -> &'static str {
+        "msm"
+    }
+
+    fn log_size(&self) -> u32 {
+        self.log_size
+    }
+
+    fn run(&self) {
+        black_box(pippenger::msm(&self.scalars, &self.points).unwrap());
+    }
+}
+
+/// An evaluation with the FFT of a random polynomial with `2^log_size` coefficients over the
+/// Stark252 field.
+pub struct Fft {
+    log_size: u32,
+    poly: Polynomial<FieldElement<Stark252PrimeField>>,
+}
+
+impl Fft {
+    pub fn new(log_size: u32) -> Self {
+        let mut rng = StdRng::seed_from_u64(SEED);
+        let coefficients: Vec<_> = (0..1 << log_size)
+            .map(|_| FieldElement::new(UnsignedInteger { limbs: rng.gen() }))
+            .collect();
+
+        Self {
+            log_size,
+            poly: Polynomial::new(&coefficients),
+        }
+    }
+}
+
+impl Workload for Fft {
+    fn name(&self) -> &'static str {
+        "fft"
+    }
+
+    fn log_size(&self) -> u32 {
+        self.log_size
+    }
+
+    fn run(&self) {
+        black_box(Polynomial::evaluate_fft::<Stark252PrimeField>(&self.poly, 1, None).unwrap());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_workload_runs_at_a_small_size() {
+        for name in WORKLOADS {
+            let workload = workload(name, 4).unwrap();
+            assert_eq!(workload.name(), name);
+            assert_eq!(workload.log_size(), 4);
+            workload.run();
+        }
+    }
+
+    #[test]
+    fn plonk_circuit_fills_the_requested_number_of_gates() {
+        assert_eq!(PlonkCircuit::new(5).common_preprocessed_input.n, 1 << 5);
+    }
+
+    #[test]
+    fn srs_matches_the_test_srs() {
+        let srs = srs(6);
+        let test_srs = lambdaworks_plonk::test_utils::utils::test_srs(6);
+        assert_eq!(srs.powers_main_group, test_srs.powers_main_group);
+        assert_eq!(srs.powers_secondary_group, test_srs.powers_secondary_group);
+    }
+
+    #[test]
+    fn unknown_workloads_are_rejected() {
+        assert!(workload("cairo", 4).is_none());
+    }
+}
@@ -0,0 +1,385 @@
+//! Openings of several polynomials at several points with a single pair of KZG proofs,
+//! following [fflonk](https://eprint.iacr.org/2021/1167) and
+//! [shplonk](https://eprint.iacr.org/2020/081).
+//!
+//! fflonk commits to `t` polynomials `f_0, ..., f_{t-1}` at once through the combination
+//! `g(X) = Σ fᵢ(Xᵗ) Xⁱ`. Opening every `fᵢ` at `z = xᵗ` is the same as opening `g` at the `t`
+//! points `x ωʲ`, where `ω` is a primitive `t`-th root of unity, since on those points
+//! `g(X) = r(X) = Σ fᵢ(z) Xⁱ`. Openings of several combined polynomials, each at its own set of
+//! points, are then batched with shplonk into two group elements, verified with two pairings
+//! whatever the number of polynomials and points. The prover pays with larger commitments
+//! (degree `t` times higher) in exchange for a verifier with fewer group operations.
+
+use alloc::vec::Vec;
+use core::marker::PhantomData;
+
+use lambdaworks_math::{
+    cyclic_group::IsGroup,
+    elliptic_curve::traits::IsPairing,
+    field::{element::FieldElement, traits::IsPrimeField},
+    msm::pippenger::msm,
+    polynomial::Polynomial,
+    traits::AsBytes,
+    unsigned_integer::element::UnsignedInteger,
+};
+
+use super::kzg::{KateZaveruchaGoldberg, StructuredReferenceString};
+use crate::fiat_shamir::is_transcript::IsTranscript;
+
+/// Returns `g(X) = Σ fᵢ(Xᵗ) Xⁱ`, where `t` is the number of polynomials.
+pub fn combine_polynomials<F: IsPrimeField>(
+    polynomials: &[Polynomial<FieldElement<F>>],
+) -> Polynomial<FieldElement<F>> {
+    let t = polynomials.len();
+    let max_len = polynomials
+        .iter()
+        .map(|p| p.coefficients.len())
+        .max()
+        .unwrap_or(0);
+    let mut coefficients = vec![FieldElement::zero(); t * max_len];
+    for (i, polynomial) in polynomials.iter().enumerate() {
+        for (j, coefficient) in polynomial.coefficients.iter().enumerate() {
+            coefficients[j * t + i] = coefficient.clone();
+        }
+    }
+    Polynomial::new(&coefficients)
+}
+
+/// Polynomials committed together with [`Fflonk::commit`], to be opened at `z = xᵗ`.
+pub struct OpeningSet<'a, F: IsPrimeField> {
+    pub polynomials: &'a [Polynomial<FieldElement<F>>],
+    pub x: FieldElement<F>,
+}
+
+/// What the verifier knows about an [`OpeningSet`]: the commitment to the combined
+/// polynomial and the claimed evaluations `fᵢ(xᵗ)`.
+pub struct OpeningClaim<'a, F: IsPrimeField, Commitment> {
+    pub commitment: &'a Commitment,
+    pub x: FieldElement<F>,
+    pub evaluations: &'a [FieldElement<F>],
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FflonkProof<Commitment> {
+    /// Commitment to `W(X) = Σ γᵏ (gₖ(X) - rₖ(X)) / Z_{Sₖ}(X)`.
+    pub w: Commitment,
+    /// Commitment to the quotient of the linearized check by `X - ρ`.
+    pub w_prime: Commitment,
+}
+
+#[derive(Clone)]
+pub struct Fflonk<F: IsPrimeField, P: IsPairing> {
+    srs: StructuredReferenceString<P::G1Point, P::G2Point>,
+    phantom: PhantomData<F>,
+}
+
+impl<const N: usize, F, P> Fflonk<F, P>
+where
+    F: IsPrimeField<RepresentativeType = UnsignedInteger<N>>,
+    P: IsPairing,
+    P::G1Point: AsBytes,
+{
+    pub fn new(srs: StructuredReferenceString<P::G1Point, P::G2Point>) -> Self {
+        Self {
+            srs,
+            phantom: PhantomData,
+        }
+    }
+
+    /// Commits to `polynomials` with a single group element, the commitment to
+    /// [`combine_polynomials`].
+    pub fn commit(&self, polynomials: &[Polynomial<FieldElement<F>>]) -> P::G1Point {
+        self.commit_polynomial(&combine_polynomials(polynomials))
+    }
+
+    fn commit_polynomial(&self, p: &Polynomial<FieldElement<F>>) -> P::G1Point {
+        let coefficients: Vec<_> = p
+            .coefficients
+            .iter()
+            .map(|coefficient| coefficient.representative())
+            .collect();
+        msm(
+            &coefficients,
+            &self.srs.powers_main_group[..coefficients.len()],
+        )
+        .expect("`points` is sliced by `cs`'s length")
+    }
+
+    /// Opens every polynomial of every set at the point `xᵗ` of its set. Returns the
+    /// evaluations, in the order of the sets and of the polynomials, and the proof.
+    /// The commitments are expected to be already in the transcript.
+    pub fn open<T: IsTranscript<F>>(
+        &self,
+        sets: &[OpeningSet<F>],
+        transcript: &mut T,
+    ) -> (Vec<Vec<FieldElement<F>>>, FflonkProof<P::G1Point>) {
+        let combined: Vec<_> = sets
+            .iter()
+            .map(|set| combine_polynomials(set.polynomials))
+            .collect();
+        let evaluations: Vec<Vec<_>> = sets
+            .iter()
+            .map(|set| {
+                let z = set.x.pow(set.polynomials.len());
+                set.polynomials.iter().map(|p| p.evaluate(&z)).collect()
+            })
+            .collect();
+        for value in evaluations.iter().flatten() {
+            transcript.append_field_element(value);
+        }
+        let gamma = transcript.sample_field_element();
+
+        // W(X) = Σ γᵏ (gₖ(X) - rₖ(X)) / (Xᵗ - xₖᵗ)
+        let remainders: Vec<_> = evaluations.iter().map(|e| Polynomial::new(e)).collect();
+        let mut w = Polynomial::zero();
+        let mut gamma_k = FieldElement::<F>::one();
+        for ((set, g), r) in sets.iter().zip(&combined).zip(&remainders) {
+            let t = set.polynomials.len();
+            let quotient = divide_by_binomial(&(g - r), t, &set.x.pow(t));
+            w = w + quotient * &gamma_k;
+            gamma_k *= &gamma;
+        }
+        let w_1 = self.commit_polynomial(&w);
+        transcript.append_bytes(&w_1.as_bytes());
+        let rho = transcript.sample_field_element();
+
+        // L(X) = Σ γᵏ Z_{T∖Sₖ}(ρ) (gₖ(X) - rₖ(ρ)) - Z_T(ρ) W(X) vanishes at ρ
+        let vanishing = vanishing_at(sets.iter().map(|s| (&s.x, s.polynomials.len())), &rho);
+        let mut l = w * -vanishing.total;
+        let mut gamma_k = FieldElement::<F>::one();
+        for ((g, r), complement) in combined.iter().zip(&remainders).zip(&vanishing.complements) {
+            let r_rho = r.evaluate(&rho);
+            l = l + (g - r_rho) * (&gamma_k * complement);
+            gamma_k *= &gamma;
+        }
+        let w_prime = l.ruffini_division(&rho);
+
+        let proof = FflonkProof {
+            w: w_1,
+            w_prime: self.commit_polynomial(&w_prime),
+        };
+        (evaluations, proof)
+    }
+
+    /// Checks an opening produced by [`Fflonk::open`] for the given claims.
+    pub fn verify<T: IsTranscript<F>>(
+        &self,
+        claims: &[OpeningClaim<F, P::G1Point>],
+        proof: &FflonkProof<P::G1Point>,
+        transcript: &mut T,
+    ) -> bool {
+        for value in claims.iter().flat_map(|claim| claim.evaluations) {
+            transcript.append_field_element(value);
+        }
+        let gamma = transcript.sample_field_element();
+        transcript.append_bytes(&proof.w.as_bytes());
+        let rho = transcript.sample_field_element();
+
+        // [L] = Σ γᵏ Z_{T∖Sₖ}(ρ) ([gₖ] - rₖ(ρ) [1]) - Z_T(ρ) [W]
+        let vanishing = vanishing_at(claims.iter().map(|c| (&c.x, c.evaluations.len())), &rho);
+        let mut scalar_g1 = FieldElement::<F>::zero();
+        let mut l = proof
+            .w
+            .operate_with_self(vanishing.total.representative())
+            .neg();
+        let mut gamma_k = FieldElement::<F>::one();
+        for (claim, complement) in claims.iter().zip(&vanishing.complements) {
+            let factor = &gamma_k * complement;
+            let r_rho = Polynomial::new(claim.evaluations).evaluate(&rho);
+            l = l.operate_with(&claim.commitment.operate_with_self(factor.representative()));
+            scalar_g1 += factor * r_rho;
+            gamma_k *= &gamma;
+        }
+        let g1 = &self.srs.powers_main_group[0];
+        l = l.operate_with(&g1.operate_with_self(scalar_g1.representative()).neg());
+
+        // e([L] + ρ [W'], [1]) = e([W'], [τ])
+        let g2 = &self.srs.powers_secondary_group[0];
+        let tau_g2 = &self.srs.powers_secondary_group[1];
+        let lhs = l.operate_with(&proof.w_prime.operate_with_self(rho.representative()));
+        let e = P::compute_batch(&[(&lhs, g2), (&proof.w_prime.neg(), tau_g2)]);
+        e == Ok(FieldElement::one())
+    }
+}
+
+/// A single polynomial is committed as with KZG, so commitments made with
+/// [`KateZaveruchaGoldberg`] can be opened with the [`Fflonk`] built from the same SRS.
+impl<F: IsPrimeField, P: IsPairing> From<&KateZaveruchaGoldberg<F, P>> for Fflonk<F, P> {
+    fn from(kzg: &KateZaveruchaGoldberg<F, P>) -> Self {
+        Self {
+            srs: kzg.srs.clone(),
+            phantom: PhantomData,
+        }
+    }
+}
+
+/// Values at `ρ` of `Z_T = Π Z_{Sₖ}` and of `Z_{T∖Sₖ} = Π_{j≠k} Z_{Sⱼ}`, with
+/// `Z_{Sₖ}(X) = Xᵗ - xₖᵗ`.
+struct VanishingValues<F: IsPrimeField> {
+    total: FieldElement<F>,
+    complements: Vec<FieldElement<F>>,
+}
+
+fn vanishing_at<'a, F: IsPrimeField + 'a>(
+    sets: impl Iterator<Item = (&'a FieldElement<F>, usize)>,
+    rho: &FieldElement<F>,
+) -> VanishingValues<F> {
+    let values: Vec<_> = sets.map(|(x, t)| rho.pow(t) - x.pow(t)).collect();
+    let complements = (0..values.len())
+        .map(|k| {
+            values
+                .iter()
+                .enumerate()
+                .filter(|(j, _)| *j != k)
+                .fold(FieldElement::one(), |acc, (_, value)| acc * value)
+        })
+        .collect();
+    VanishingValues {
+        total: values.iter().fold(FieldElement::one(), |acc, v| acc * v),
+        complements,
+    }
+}
+
+/// Returns the quotient of `p` by `Xᵗ - z`, discarding the remainder.
+fn divide_by_binomial<F: IsPrimeField>(
+    p: &Polynomial<FieldElement<F>>,
+    t: usize,
+    z: &FieldElement<F>,
+) -> Polynomial<FieldElement<F>> {
+    let coefficients = &p.coefficients;
+    if coefficients.len() <= t {
+        return Polynomial::zero();
+    }
+    // p = q (Xᵗ - z) + r, so pᵢ₊ₜ = qᵢ - z qᵢ₊ₜ
+    let mut quotient = vec![FieldElement::zero(); coefficients.len() - t];
+    for i in (0..quotient.len()).rev() {
+        quotient[i] = match quotient.get(i + t) {
+            Some(next) => &coefficients[i + t] + z * next,
+            None => coefficients[i + t].clone(),
+        };
+    }
+    Polynomial::new(&quotient)
+}
+
+#[cfg(test)]
+mod tests {
+    use lambdaworks_math::elliptic_curve::{
+        short_weierstrass::curves::bls12_381::{
+            curve::BLS12381Curve,
+            default_types::{FrElement, FrField},
+            pairing::BLS12381AtePairing,
+            twist::BLS12381TwistCurve,
+        },
+        traits::IsEllipticCurve,
+    };
+
+    use super::*;
+    use crate::fiat_shamir::default_transcript::DefaultTranscript;
+
+    type TestFflonk = Fflonk<FrField, BLS12381AtePairing>;
+
+    fn test_fflonk() -> TestFflonk {
+        let toxic_waste = FrElement::from(0x1234_5678_9abc_u64);
+        let g1 = BLS12381Curve::generator();
+        let g2 = BLS12381TwistCurve::generator();
+        let powers_main_group: Vec<_> = (0..64)
+            .map(|exponent| g1.operate_with_self(toxic_waste.pow(exponent as u64).representative()))
+            .collect();
+        let powers_secondary_group = [
+            g2.clone(),
+            g2.operate_with_self(toxic_waste.representative()),
+        ];
+        Fflonk::new(StructuredReferenceString::new(
+            &powers_main_group,
+            &powers_secondary_group,
+        ))
+    }
+
+    fn polynomials(coefficients: &[&[u64]]) -> Vec<Polynomial<FrElement>> {
+        coefficients
+            .iter()
+            .map(|c| Polynomial::new(&c.iter().map(|v| FrElement::from(*v)).collect::<Vec<_>>()))
+            .collect()
+    }
+
+    #[test]
+    fn combined_polynomial_interleaves_coefficients() {
+        let g = combine_polynomials(&polynomials(&[&[1, 2], &[3], &[4, 5, 6]]));
+        let expected = polynomials(&[&[1, 3, 4, 2, 0, 5, 0, 0, 6]]);
+        assert_eq!(g, expected[0]);
+    }
+
+    #[test]
+    fn divide_by_binomial_is_exact_on_multiples() {
+        let q = &polynomials(&[&[3, 1, 4, 1, 5]])[0];
+        let z = FrElement::from(9);
+        let binomial = Polynomial::new_monomial(FrElement::one(), 3) - z.clone();
+        let p = q.mul_with_ref(&binomial);
+        assert_eq!(divide_by_binomial(&p, 3, &z), *q);
+    }
+
+    #[test]
+    fn open_and_verify_two_sets() {
+        let fflonk = test_fflonk();
+        let first = polynomials(&[&[1, 2, 3, 4], &[5, 6], &[7, 8, 9]]);
+        let second = polynomials(&[&[10, 11, 12], &[13, 14, 15, 16]]);
+        let first_commitment = fflonk.commit(&first);
+        let second_commitment = fflonk.commit(&second);
+
+        // The first set is opened at two points, the second one at a single point
+        let x = FrElement::from(17);
+        let omega_x = FrElement::from(19);
+        let sets = [
+            OpeningSet {
+                polynomials: &first,
+                x: x.clone(),
+            },
+            OpeningSet {
+                polynomials: &first,
+                x: omega_x.clone(),
+            },
+            OpeningSet {
+                polynomials: &second,
+                x: x.clone(),
+            },
+        ];
+        let (evaluations, proof) = fflonk.open(&sets, &mut DefaultTranscript::default());
+        let z = x.pow(3_u64);
+        assert_eq!(evaluations[0][1], first[1].evaluate(&z));
+
+        let claims = |evaluations: &[Vec<FrElement>]| {
+            [
+                (&first_commitment, x.clone()),
+                (&first_commitment, omega_x.clone()),
+                (&second_commitment, x.clone()),
+            ]
+            .into_iter()
+            .zip(evaluations.to_vec())
+            .map(|((commitment, x), evaluations)| (commitment, x, evaluations))
+            .collect::<Vec<_>>()
+        };
+        let verify = |evaluations: &[Vec<FrElement>], proof: &FflonkProof<_>| {
+            let claims = claims(evaluations);
+            let claims: Vec<_> = claims
+                .iter()
+                .map(|(commitment, x, evaluations)| OpeningClaim {
+                    commitment: *commitment,
+                    x: x.clone(),
+                    evaluations,
+                })
+                .collect();
+            fflonk.verify(&claims, proof, &mut DefaultTranscript::default())
+        };
+        assert!(verify(&evaluations, &proof));
+
+        let mut wrong_evaluations = evaluations.clone();
+        wrong_evaluations[2][0] = &wrong_evaluations[2][0] + FrElement::one();
+        assert!(!verify(&wrong_evaluations, &proof));
+
+        let wrong_proof = FflonkProof {
+            w: proof.w_prime.clone(),
+            w_prime: proof.w.clone(),
+        };
+        assert!(!verify(&evaluations, &wrong_proof));
+    }
+}
@@ -0,0 +1,298 @@
+//! Pedersen commitments to multilinear polynomials, opened with the inner product argument
+//! of [Bulletproofs](https://eprint.iacr.org/2017/1066).
+//!
+//! The commitment to the evaluations `a` is `<a, G>`. The evaluation at `r` is `<a, eq(r, ·)>`,
+//! proven with `log n` rounds halving both vectors. The scheme is transparent: the generators
+//! only need to have no known discrete logarithm relation, which holds when they are obtained by
+//! hashing to the curve with [`generators_from_transcript`]. Proofs have `2 log n` group
+//! elements, and verification takes time linear in `n`.
+
+use alloc::vec::Vec;
+use core::marker::PhantomData;
+
+use lambdaworks_math::{
+    cyclic_group::IsGroup,
+    elliptic_curve::{
+        short_weierstrass::{point::ShortWeierstrassProjectivePoint, traits::IsShortWeierstrass},
+        traits::FromAffine,
+    },
+    field::{element::FieldElement, traits::IsPrimeField},
+    msm::pippenger::msm,
+    traits::AsBytes,
+    unsigned_integer::element::UnsignedInteger,
+};
+
+use super::traits::IsMultilinearCommitmentScheme;
+use crate::fiat_shamir::is_transcript::IsTranscript;
+
+/// Samples `n` points of a short Weierstrass curve of cofactor one, by taking x coordinates from
+/// the transcript until they are on the curve.
+pub fn generators_from_transcript<E, T>(
+    transcript: &mut T,
+    n: usize,
+) -> Vec<ShortWeierstrassProjectivePoint<E>>
+where
+    E: IsShortWeierstrass,
+    E::BaseField: IsPrimeField,
+    T: IsTranscript<E::BaseField>,
+{
+    let mut generators = Vec::with_capacity(n);
+    while generators.len() < n {
+        let x = transcript.sample_field_element();
+        let y_squared = x.square() * &x + E::a() * &x + E::b();
+        if let Some((y, _)) = y_squared.sqrt() {
+            if let Ok(point) = ShortWeierstrassProjectivePoint::from_affine(x, y) {
+                generators.push(point);
+            }
+        }
+    }
+    generators
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct IpaProof<F: IsPrimeField, G> {
+    pub l: Vec<G>,
+    pub r: Vec<G>,
+    /// The single remaining entry of the folded evaluations.
+    pub a: FieldElement<F>,
+}
+
+#[derive(Clone)]
+pub struct InnerProductArgument<F: IsPrimeField, G> {
+    generators: Vec<G>,
+    u: G,
+    phantom: PhantomData<F>,
+}
+
+impl<F: IsPrimeField, G> InnerProductArgument<F, G> {
+    /// Creates the scheme for polynomials with up to `generators.len()` evaluations. `u` is an
+    /// extra generator binding the inner product.
+    pub fn new(generators: Vec<G>, u: G) -> Self {
+        Self {
+            generators,
+            u,
+            phantom: PhantomData,
+        }
+    }
+}
+
+impl<const N: usize, F, G> IsMultilinearCommitmentScheme<F> for InnerProductArgument<F, G>
+where
+    F: IsPrimeField<RepresentativeType = UnsignedInteger<N>>,
+    G: IsGroup + AsBytes,
+{
+    type Commitment = G;
+    type Proof = IpaProof<F, G>;
+
+    fn commit(&self, evals: &[FieldElement<F>]) -> G {
+        multiscalar_mul(evals, &self.generators[..evals.len()])
+    }
+
+    fn open<T: IsTranscript<F>>(
+        &self,
+        evals: &[FieldElement<F>],
+        point: &[FieldElement<F>],
+        transcript: &mut T,
+    ) -> IpaProof<F, G> {
+        assert_eq!(evals.len(), 1 << point.len());
+        let mut a = evals.to_vec();
+        let mut b = eq_evals(point);
+        let mut generators = self.generators[..a.len()].to_vec();
+        transcript.append_field_element(&inner_product(&a, &b));
+        let q = self
+            .u
+            .operate_with_self(transcript.sample_field_element().representative());
+
+        let (mut l, mut r) = (Vec::new(), Vec::new());
+        while a.len() > 1 {
+            let half = a.len() / 2;
+            let (a_lo, a_hi) = a.split_at(half);
+            let (b_lo, b_hi) = b.split_at(half);
+            let (g_lo, g_hi) = generators.split_at(half);
+            let l_i = multiscalar_mul(a_lo, g_hi)
+                .operate_with(&q.operate_with_self(inner_product(a_lo, b_hi).representative()));
+            let r_i = multiscalar_mul(a_hi, g_lo)
+                .operate_with(&q.operate_with_self(inner_product(a_hi, b_lo).representative()));
+            transcript.append_bytes(&l_i.as_bytes());
+            transcript.append_bytes(&r_i.as_bytes());
+            let x = transcript.sample_field_element();
+            let x_inv = x.inv().unwrap();
+
+            a = fold_scalars(&a, &x, &x_inv);
+            b = fold_scalars(&b, &x_inv, &x);
+            generators = fold_points(&generators, &x_inv, &x);
+            l.push(l_i);
+            r.push(r_i);
+        }
+        IpaProof {
+            l,
+            r,
+            a: a[0].clone(),
+        }
+    }
+
+    fn verify<T: IsTranscript<F>>(
+        &self,
+        commitment: &G,
+        point: &[FieldElement<F>],
+        value: &FieldElement<F>,
+        proof: &IpaProof<F, G>,
+        transcript: &mut T,
+    ) -> bool {
+        let n = 1 << point.len();
+        if proof.l.len() != point.len() || proof.r.len() != point.len() || n > self.generators.len()
+        {
+            return false;
+        }
+        transcript.append_field_element(value);
+        let q = self
+            .u
+            .operate_with_self(transcript.sample_field_element().representative());
+
+        // P = C + v Q, folded as P' = x² L + P + x⁻² R
+        let mut p = commitment.operate_with(&q.operate_with_self(value.representative()));
+        let mut b = eq_evals(point);
+        let mut generators = self.generators[..n].to_vec();
+        for (l_i, r_i) in proof.l.iter().zip(&proof.r) {
+            transcript.append_bytes(&l_i.as_bytes());
+            transcript.append_bytes(&r_i.as_bytes());
+            let x = transcript.sample_field_element();
+            let x_inv = match x.inv() {
+                Ok(x_inv) => x_inv,
+                Err(_) => return false,
+            };
+            p = l_i
+                .operate_with_self(x.square().representative())
+                .operate_with(&p)
+                .operate_with(&r_i.operate_with_self(x_inv.square().representative()));
+            b = fold_scalars(&b, &x_inv, &x);
+            generators = fold_points(&generators, &x_inv, &x);
+        }
+
+        let expected = generators[0]
+            .operate_with_self(proof.a.representative())
+            .operate_with(&q.operate_with_self((&proof.a * &b[0]).representative()));
+        p == expected
+    }
+}
+
+fn multiscalar_mul<const N: usize, F, G>(scalars: &[FieldElement<F>], points: &[G]) -> G
+where
+    F: IsPrimeField<RepresentativeType = UnsignedInteger<N>>,
+    G: IsGroup,
+{
+    let scalars: Vec<_> = scalars.iter().map(|s| s.representative()).collect();
+    msm(&scalars, points).expect("`points` is sliced by `cs`'s length")
+}
+
+fn inner_product<F: IsPrimeField>(a: &[FieldElement<F>], b: &[FieldElement<F>]) -> FieldElement<F> {
+    a.iter()
+        .zip(b)
+        .fold(FieldElement::zero(), |acc, (a_i, b_i)| acc + a_i * b_i)
+}
+
+/// Returns `lo · x_lo + hi · x_hi`, for the low and high halves of `v`.
+fn fold_scalars<F: IsPrimeField>(
+    v: &[FieldElement<F>],
+    x_lo: &FieldElement<F>,
+    x_hi: &FieldElement<F>,
+) -> Vec<FieldElement<F>> {
+    let (lo, hi) = v.split_at(v.len() / 2);
+    lo.iter()
+        .zip(hi)
+        .map(|(lo, hi)| lo * x_lo + hi * x_hi)
+        .collect()
+}
+
+fn fold_points<F: IsPrimeField, G: IsGroup>(
+    v: &[G],
+    x_lo: &FieldElement<F>,
+    x_hi: &FieldElement<F>,
+) -> Vec<G> {
+    let (lo, hi) = v.split_at(v.len() / 2);
+    lo.iter()
+        .zip(hi)
+        .map(|(lo, hi)| {
+            lo.operate_with_self(x_lo.representative())
+                .operate_with(&hi.operate_with_self(x_hi.representative()))
+        })
+        .collect()
+}
+
+/// Evaluations of `eq(r, x)` over the boolean hypercube, `r[0]` being the most significant bit.
+fn eq_evals<F: IsPrimeField>(r: &[FieldElement<F>]) -> Vec<FieldElement<F>> {
+    let mut evals = vec![FieldElement::one()];
+    for r_i in r {
+        evals = evals
+            .iter()
+            .flat_map(|e| {
+                let high = e * r_i;
+                [e - &high, high]
+            })
+            .collect();
+    }
+    evals
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fiat_shamir::default_transcript::DefaultTranscript;
+    use lambdaworks_math::{
+        elliptic_curve::{
+            short_weierstrass::curves::pallas::curve::PallasCurve, traits::IsEllipticCurve,
+        },
+        field::fields::vesta_field::Vesta255PrimeField,
+        polynomial::dense_multilinear_poly::DenseMultilinearPolynomial,
+    };
+
+    type FE = FieldElement<Vesta255PrimeField>;
+    type PallasPoint = ShortWeierstrassProjectivePoint<PallasCurve>;
+    type Ipa = InnerProductArgument<Vesta255PrimeField, PallasPoint>;
+
+    fn test_ipa(n: usize) -> Ipa {
+        let mut transcript = DefaultTranscript::new(b"ipa test generators");
+        let mut generators = generators_from_transcript::<PallasCurve, _>(&mut transcript, n + 1);
+        let u = generators.pop().unwrap();
+        InnerProductArgument::new(generators, u)
+    }
+
+    #[test]
+    fn generators_are_on_the_curve() {
+        for generator in test_ipa(4).generators {
+            let [x, y, _] = generator.to_affine().coordinates().clone();
+            assert!(PallasCurve::create_point_from_affine(x, y).is_ok());
+        }
+    }
+
+    #[test]
+    fn evaluation_is_opened_and_verified() {
+        let ipa = test_ipa(8);
+        let evals: Vec<FE> = [3, 1, 4, 1, 5, 9, 2, 6].map(FE::from).to_vec();
+        let point: Vec<FE> = [7, 11, 13].map(FE::from).to_vec();
+        let value = DenseMultilinearPolynomial::new(evals.clone())
+            .evaluate(point.clone())
+            .unwrap();
+
+        let commitment = ipa.commit(&evals);
+        let proof = ipa.open(
+            &evals,
+            &point,
+            &mut DefaultTranscript::<Vesta255PrimeField>::default(),
+        );
+        assert!(ipa.verify(
+            &commitment,
+            &point,
+            &value,
+            &proof,
+            &mut DefaultTranscript::default()
+        ));
+        assert!(!ipa.verify(
+            &commitment,
+            &point,
+            &(value + FE::one()),
+            &proof,
+            &mut DefaultTranscript::default()
+        ));
+    }
+}
@@ -1,4 +1,6 @@
 use super::traits::IsCommitmentScheme;
+#[cfg(feature = "std")]
+use crate::setup_file::{point_size, SetupFileReader, SetupFileWriter, SRS_KIND};
 use alloc::{borrow::ToOwned, vec::Vec};
 use core::{marker::PhantomData, mem};
 use lambdaworks_math::{
@@ -43,6 +45,49 @@ where
     }
 }
 
+#[cfg(feature = "std")]
+impl<G1Point, G2Point> StructuredReferenceString<G1Point, G2Point>
+where
+    G1Point: IsGroup + AsBytes + Deserializable,
+    G2Point: IsGroup + AsBytes + Deserializable,
+{
+    const MAIN_GROUP_SECTION: u32 = 1;
+    const SECONDARY_GROUP_SECTION: u32 = 2;
+
+    /// Writes the SRS as a [`crate::setup_file`], one point at a time.
+    pub fn write_to<W: std::io::Write>(
+        &self,
+        writer: W,
+    ) -> Result<(), crate::errors::SetupFileError> {
+        let mut file = SetupFileWriter::new(writer, SRS_KIND, 2)?;
+        file.write_section(Self::MAIN_GROUP_SECTION, &self.powers_main_group)?;
+        file.write_section(Self::SECONDARY_GROUP_SECTION, &self.powers_secondary_group)?;
+        file.finish()?;
+        Ok(())
+    }
+
+    /// Reads an SRS written by [`Self::write_to`]. When `max_powers` is given only that many
+    /// powers of the main group are read and the rest are skipped, so the SRS of a large
+    /// ceremony can be used for smaller circuits without loading all of it.
+    pub fn read_from<R: std::io::Read>(
+        reader: R,
+        max_powers: Option<usize>,
+    ) -> Result<Self, crate::errors::SetupFileError> {
+        let mut file = SetupFileReader::new(reader, SRS_KIND)?;
+        let mut section = file.section(Self::MAIN_GROUP_SECTION, point_size::<G1Point>())?;
+        let powers_main_group = match max_powers {
+            Some(max_powers) => section.read_chunk(max_powers)?,
+            None => section.read_all()?,
+        };
+        let mut section = file.section(Self::SECONDARY_GROUP_SECTION, point_size::<G2Point>())?;
+        let powers_secondary_group = [section.read()?, section.read()?];
+        Ok(Self {
+            powers_main_group,
+            powers_secondary_group,
+        })
+    }
+}
+
 impl<G1Point, G2Point> AsBytes for StructuredReferenceString<G1Point, G2Point>
 where
     G1Point: IsGroup + AsBytes,
@@ -137,7 +182,7 @@ where
 
 #[derive(Clone)]
 pub struct KateZaveruchaGoldberg<F: IsPrimeField, P: IsPairing> {
-    srs: StructuredReferenceString<P::G1Point, P::G2Point>,
+    pub(crate) srs: StructuredReferenceString<P::G1Point, P::G2Point>,
     phantom: PhantomData<F>,
 }
 
@@ -413,6 +458,24 @@ mod tests {
         assert_eq!(srs, deserialized);
     }
 
+    #[test]
+    #[cfg(feature = "std")]
+    fn write_and_read_srs_setup_file() {
+        let srs = create_srs();
+        let mut bytes = Vec::new();
+        srs.write_to(&mut bytes).unwrap();
+
+        let read = StructuredReferenceString::read_from(bytes.as_slice(), None).unwrap();
+        assert_eq!(srs, read);
+
+        let prefix: StructuredReferenceString<
+            ShortWeierstrassProjectivePoint<BLS12381Curve>,
+            ShortWeierstrassProjectivePoint<BLS12381TwistCurve>,
+        > = StructuredReferenceString::read_from(bytes.as_slice(), Some(10)).unwrap();
+        assert_eq!(prefix.powers_main_group, srs.powers_main_group[..10]);
+        assert_eq!(prefix.powers_secondary_group, srs.powers_secondary_group);
+    }
+
     #[test]
     #[cfg(feature = "std")]
     fn load_srs_from_file() {
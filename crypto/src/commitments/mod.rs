@@ -1,2 +1,5 @@
+pub mod fflonk;
+pub mod ipa;
 pub mod kzg;
+pub mod srs_update;
 pub mod traits;
@@ -0,0 +1,269 @@
+//! Updates of a KZG [`StructuredReferenceString`], to run powers-of-tau ceremonies where each
+//! participant re-randomizes the string produced by the previous one.
+//!
+//! A participant with secret `s` turns the powers of `τ` into the powers of `τ' = τ s`, and
+//! publishes `[s]₂` together with `[τ']₁`. Anyone can then check that `e([τ']₁, g₂) =
+//! e([τ]₁, [s]₂)`, so that the update was a multiplication of `τ` by a known-to-the-participant
+//! factor, and that the new string is still made of consecutive powers of a single secret. The
+//! final string is secure as long as one participant of the chain discarded its secret.
+
+use alloc::vec::Vec;
+
+use lambdaworks_math::{
+    cyclic_group::IsGroup,
+    elliptic_curve::traits::IsPairing,
+    field::{element::FieldElement, traits::IsPrimeField},
+    msm::pippenger::msm,
+    traits::AsBytes,
+    unsigned_integer::element::UnsignedInteger,
+};
+
+use super::kzg::StructuredReferenceString;
+use crate::fiat_shamir::is_transcript::IsTranscript;
+
+type Srs<P> = StructuredReferenceString<<P as IsPairing>::G1Point, <P as IsPairing>::G2Point>;
+
+/// What a participant publishes along with the updated string.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct UpdateProof<G1Point, G2Point> {
+    /// `[τ s]₁`, the first power of the updated string.
+    pub tau_g1: G1Point,
+    /// `[s]₂`, the contribution of the participant.
+    pub secret_g2: G2Point,
+}
+
+/// Multiplies the secret of `srs` by `secret`. The caller must sample `secret` at random and
+/// discard it afterwards.
+pub fn update<const N: usize, F, P>(
+    srs: &Srs<P>,
+    secret: &FieldElement<F>,
+) -> (Srs<P>, UpdateProof<P::G1Point, P::G2Point>)
+where
+    F: IsPrimeField<RepresentativeType = UnsignedInteger<N>>,
+    P: IsPairing,
+{
+    assert!(srs.powers_main_group.len() >= 2);
+    let mut power = FieldElement::<F>::one();
+    let powers_main_group: Vec<_> = srs
+        .powers_main_group
+        .iter()
+        .map(|point| {
+            let updated = point.operate_with_self(power.representative());
+            power *= secret;
+            updated
+        })
+        .collect();
+    let [g2, tau_g2] = &srs.powers_secondary_group;
+    let proof = UpdateProof {
+        tau_g1: powers_main_group[1].clone(),
+        secret_g2: g2.operate_with_self(secret.representative()),
+    };
+    let updated = StructuredReferenceString {
+        powers_main_group,
+        powers_secondary_group: [
+            g2.clone(),
+            tau_g2.operate_with_self(secret.representative()),
+        ],
+    };
+    (updated, proof)
+}
+
+/// Checks that `after` is `before` updated by the contribution of `proof`.
+pub fn verify_update<const N: usize, F, P, T>(
+    before: &Srs<P>,
+    after: &Srs<P>,
+    proof: &UpdateProof<P::G1Point, P::G2Point>,
+    transcript: &mut T,
+) -> bool
+where
+    F: IsPrimeField<RepresentativeType = UnsignedInteger<N>>,
+    P: IsPairing,
+    P::G1Point: AsBytes,
+    P::G2Point: AsBytes,
+    T: IsTranscript<F>,
+{
+    verify_update_chain::<N, F, P, T>(before, core::slice::from_ref(proof), after, transcript)
+}
+
+/// Checks that `last` is `first` updated by each contribution of `proofs` in turn. The
+/// intermediate strings are not needed.
+pub fn verify_update_chain<const N: usize, F, P, T>(
+    first: &Srs<P>,
+    proofs: &[UpdateProof<P::G1Point, P::G2Point>],
+    last: &Srs<P>,
+    transcript: &mut T,
+) -> bool
+where
+    F: IsPrimeField<RepresentativeType = UnsignedInteger<N>>,
+    P: IsPairing,
+    P::G1Point: AsBytes,
+    P::G2Point: AsBytes,
+    T: IsTranscript<F>,
+{
+    let g2 = &first.powers_secondary_group[0];
+    if first.powers_main_group.len() < 2
+        || last.powers_main_group.len() != first.powers_main_group.len()
+        || last.powers_main_group[0] != first.powers_main_group[0]
+        || &last.powers_secondary_group[0] != g2
+    {
+        return false;
+    }
+
+    // [τₖ]₁ = sₖ [τₖ₋₁]₁ for every contribution
+    let mut tau_g1 = &first.powers_main_group[1];
+    for proof in proofs {
+        if proof.secret_g2 == P::G2Point::neutral_element()
+            || !pairings_match::<P>(&proof.tau_g1, g2, tau_g1, &proof.secret_g2)
+        {
+            return false;
+        }
+        tau_g1 = &proof.tau_g1;
+    }
+    tau_g1 == &last.powers_main_group[1] && is_well_formed::<N, F, P, T>(last, transcript)
+}
+
+/// Checks that the elements of `srs` are `[τⁱ]₁` and `[τ]₂` for a single `τ`, with a random
+/// linear combination of the ratios of consecutive powers: `e(Σ ρⁱ [τⁱ]₁, [τ]₂) =
+/// e(Σ ρⁱ [τⁱ⁺¹]₁, g₂)`.
+pub fn is_well_formed<const N: usize, F, P, T>(srs: &Srs<P>, transcript: &mut T) -> bool
+where
+    F: IsPrimeField<RepresentativeType = UnsignedInteger<N>>,
+    P: IsPairing,
+    P::G1Point: AsBytes,
+    P::G2Point: AsBytes,
+    T: IsTranscript<F>,
+{
+    let powers = &srs.powers_main_group;
+    let [g2, tau_g2] = &srs.powers_secondary_group;
+    if powers.len() < 2
+        || powers[1] == P::G1Point::neutral_element()
+        || *g2 == P::G2Point::neutral_element()
+    {
+        return false;
+    }
+    for point in powers {
+        transcript.append_bytes(&point.as_bytes());
+    }
+    transcript.append_bytes(&g2.as_bytes());
+    transcript.append_bytes(&tau_g2.as_bytes());
+    let rho: FieldElement<F> = transcript.sample_field_element();
+
+    let mut coefficient = FieldElement::<F>::one();
+    let coefficients: Vec<_> = (1..powers.len())
+        .map(|_| {
+            let current = coefficient.representative();
+            coefficient *= &rho;
+            current
+        })
+        .collect();
+    let (Some(lower), Some(upper)) = (
+        msm(&coefficients, &powers[..powers.len() - 1]).ok(),
+        msm(&coefficients, &powers[1..]).ok(),
+    ) else {
+        return false;
+    };
+    pairings_match::<P>(&lower, tau_g2, &upper, g2)
+}
+
+/// `e(a₁, a₂) = e(b₁, b₂)`
+fn pairings_match<P: IsPairing>(
+    a_1: &P::G1Point,
+    a_2: &P::G2Point,
+    b_1: &P::G1Point,
+    b_2: &P::G2Point,
+) -> bool {
+    P::compute_batch(&[(a_1, a_2), (&b_1.neg(), b_2)]) == Ok(FieldElement::one())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        commitments::{kzg::KateZaveruchaGoldberg, traits::IsCommitmentScheme},
+        fiat_shamir::default_transcript::DefaultTranscript,
+    };
+    use lambdaworks_math::{
+        elliptic_curve::{
+            short_weierstrass::curves::bls12_381::{
+                curve::BLS12381Curve,
+                default_types::{FrElement, FrField},
+                pairing::BLS12381AtePairing,
+                twist::BLS12381TwistCurve,
+            },
+            traits::IsEllipticCurve,
+        },
+        polynomial::Polynomial,
+    };
+
+    type TestSrs = Srs<BLS12381AtePairing>;
+    type Proof = UpdateProof<
+        <BLS12381AtePairing as IsPairing>::G1Point,
+        <BLS12381AtePairing as IsPairing>::G2Point,
+    >;
+
+    /// The string every ceremony starts from, with `τ = 1`.
+    fn initial_srs(size: usize) -> TestSrs {
+        let g1 = BLS12381Curve::generator();
+        let g2 = BLS12381TwistCurve::generator();
+        StructuredReferenceString::new(&vec![g1; size], &[g2.clone(), g2])
+    }
+
+    fn contribute(srs: &TestSrs, secret: u64) -> (TestSrs, Proof) {
+        update::<4, FrField, BLS12381AtePairing>(srs, &FrElement::from(secret))
+    }
+
+    fn verify_chain(first: &TestSrs, proofs: &[Proof], last: &TestSrs) -> bool {
+        verify_update_chain::<4, FrField, BLS12381AtePairing, _>(
+            first,
+            proofs,
+            last,
+            &mut DefaultTranscript::<FrField>::default(),
+        )
+    }
+
+    #[test]
+    fn chain_of_updates_is_verified_and_usable() {
+        let initial = initial_srs(8);
+        let mut srs = initial.clone();
+        let mut proofs = Vec::new();
+        for secret in [3, 5, 7] {
+            let (next, proof) = contribute(&srs, secret);
+            assert!(verify_update::<4, FrField, BLS12381AtePairing, _>(
+                &srs,
+                &next,
+                &proof,
+                &mut DefaultTranscript::default()
+            ));
+            srs = next;
+            proofs.push(proof);
+        }
+        assert!(verify_chain(&initial, &proofs, &srs));
+        assert!(!verify_chain(&initial, &proofs[1..], &srs));
+
+        let kzg = KateZaveruchaGoldberg::<FrField, BLS12381AtePairing>::new(srs);
+        let p = Polynomial::new(&[FrElement::from(1), FrElement::from(2), FrElement::from(3)]);
+        let x = FrElement::from(9);
+        let y = p.evaluate(&x);
+        let commitment = kzg.commit(&p);
+        let proof = kzg.open(&x, &y, &p);
+        assert!(kzg.verify(&x, &y, &commitment, &proof));
+    }
+
+    #[test]
+    fn forged_update_is_rejected() {
+        let initial = initial_srs(4);
+        let (mut updated, proof) = contribute(&initial, 3);
+
+        // A proof claiming a different contribution
+        let (_, other_proof) = contribute(&initial, 4);
+        let forged = UpdateProof {
+            tau_g1: proof.tau_g1.clone(),
+            secret_g2: other_proof.secret_g2,
+        };
+        assert!(!verify_chain(&initial, &[forged], &updated));
+
+        // A string whose powers are not consecutive powers of the same secret
+        updated.powers_main_group[3] = updated.powers_main_group[2].clone();
+        assert!(!verify_chain(&initial, &[proof], &updated));
+    }
+}
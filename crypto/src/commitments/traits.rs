@@ -1,3 +1,4 @@
+use crate::fiat_shamir::is_transcript::IsTranscript;
 use lambdaworks_math::{
     field::{element::FieldElement, traits::IsField},
     polynomial::Polynomial,
@@ -39,3 +40,31 @@ pub trait IsCommitmentScheme<F: IsField> {
         upsilon: &FieldElement<F>,
     ) -> bool;
 }
+
+/// A commitment scheme for multilinear polynomials, given by their evaluations over the boolean
+/// hypercube. The first coordinate of an evaluation point is the most significant bit of the
+/// evaluation index, as in `DenseMultilinearPolynomial`.
+pub trait IsMultilinearCommitmentScheme<F: IsField> {
+    type Commitment;
+    type Proof;
+
+    fn commit(&self, evals: &[FieldElement<F>]) -> Self::Commitment;
+
+    /// Proves the evaluation of the polynomial at `point`. The commitment is expected to be
+    /// already in the transcript.
+    fn open<T: IsTranscript<F>>(
+        &self,
+        evals: &[FieldElement<F>],
+        point: &[FieldElement<F>],
+        transcript: &mut T,
+    ) -> Self::Proof;
+
+    fn verify<T: IsTranscript<F>>(
+        &self,
+        commitment: &Self::Commitment,
+        point: &[FieldElement<F>],
+        value: &FieldElement<F>,
+        proof: &Self::Proof,
+        transcript: &mut T,
+    ) -> bool;
+}
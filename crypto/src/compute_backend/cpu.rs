@@ -0,0 +1,74 @@
+use crate::{
+    compute_backend::{ComputeBackend, IsBackendMsmCurve},
+    hash::byte_hash::IsGpuByteHash,
+};
+use alloc::vec::Vec;
+use lambdaworks_math::{
+    elliptic_curve::short_weierstrass::point::ShortWeierstrassProjectivePoint,
+    fft::{
+        errors::FFTError,
+        polynomial::{evaluate_fft_cpu, interpolate_fft_cpu},
+    },
+    field::{
+        element::FieldElement,
+        traits::{IsFFTField, IsField, IsSubFieldOf},
+    },
+    msm::{naive::MSMError, pippenger},
+    polynomial::Polynomial,
+    unsigned_integer::element::UnsignedInteger,
+};
+
+/// Computes everything in the CPU. It's the reference of the other [`ComputeBackend`]s, and
+/// the one they fall back to.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct CpuBackend;
+
+impl ComputeBackend for CpuBackend {
+    fn name(&self) -> &'static str {
+        "cpu"
+    }
+
+    fn evaluate_fft<F, E>(
+        &self,
+        coefficients: &[FieldElement<E>],
+    ) -> Result<Vec<FieldElement<E>>, FFTError>
+    where
+        F: IsFFTField + IsSubFieldOf<E>,
+        E: IsField,
+    {
+        evaluate_fft_cpu::<F, E>(coefficients)
+    }
+
+    fn interpolate_fft<F, E>(
+        &self,
+        evaluations: &[FieldElement<E>],
+    ) -> Result<Polynomial<FieldElement<E>>, FFTError>
+    where
+        F: IsFFTField + IsSubFieldOf<E>,
+        E: IsField,
+    {
+        interpolate_fft_cpu::<F, E>(evaluations)
+    }
+
+    fn msm<const NUM_LIMBS: usize, const SCALAR_LIMBS: usize, E>(
+        &self,
+        cs: &[UnsignedInteger<SCALAR_LIMBS>],
+        points: &[ShortWeierstrassProjectivePoint<E>],
+    ) -> Result<ShortWeierstrassProjectivePoint<E>, MSMError>
+    where
+        E: IsBackendMsmCurve,
+        E::BaseField: IsField<BaseType = UnsignedInteger<NUM_LIMBS>>,
+    {
+        pippenger::msm(cs, points)
+    }
+
+    fn hash_rows<D: IsGpuByteHash>(&self, rows: &[Vec<u8>]) -> Vec<[u8; 32]> {
+        rows.iter()
+            .map(|row| {
+                let mut digest = [0; 32];
+                digest.copy_from_slice(&D::digest(row));
+                digest
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,124 @@
+use crate::{
+    compute_backend::{ComputeBackend, CpuBackend, IsBackendMsmCurve},
+    hash::byte_hash::{gpu::cuda::CudaByteHashState, IsGpuByteHash},
+};
+use alloc::vec::Vec;
+use lambdaworks_gpu::cuda::abstractions::errors::CudaError;
+use lambdaworks_math::{
+    elliptic_curve::short_weierstrass::point::ShortWeierstrassProjectivePoint,
+    fft::{
+        errors::FFTError,
+        gpu::cuda::{four_step::four_step_fft, polynomial::is_cuda_field, state::CudaState},
+    },
+    field::{
+        element::FieldElement,
+        traits::{IsFFTField, IsField, IsSubFieldOf},
+    },
+    gpu::cuda::memory::DeviceMemoryPool,
+    msm::{
+        gpu::cuda::{ops, state::CudaMsmState},
+        naive::MSMError,
+    },
+    polynomial::Polynomial,
+    unsigned_integer::element::UnsignedInteger,
+};
+
+/// Runs the FFTs, MSMs and hashes in a CUDA device, with the states of each of them loaded
+/// once. Falls back to [`CpuBackend`] for the fields without kernels, or if a launch fails.
+pub struct CudaBackend {
+    fft: CudaState,
+    msm: CudaMsmState,
+    byte_hash: CudaByteHashState,
+}
+
+impl CudaBackend {
+    /// Creates a new backend with the first GPU, whose states take their buffers from
+    /// [`DeviceMemoryPool::global`].
+    pub fn new() -> Result<Self, CudaError> {
+        Self::with_pool(DeviceMemoryPool::global()?)
+    }
+
+    /// Creates a new backend with the device of `pool`, whose states take their buffers from
+    /// it.
+    pub fn with_pool(pool: DeviceMemoryPool) -> Result<Self, CudaError> {
+        Ok(Self {
+            fft: CudaState::with_pool(pool.clone())?,
+            msm: CudaMsmState::with_pool(pool.clone())?,
+            byte_hash: CudaByteHashState::with_pool(pool)?,
+        })
+    }
+
+    fn fft<F, E>(
+        &self,
+        input: &[FieldElement<E>],
+        inverse: bool,
+    ) -> Result<Vec<FieldElement<E>>, CudaError>
+    where
+        F: IsFFTField + IsSubFieldOf<E>,
+        E: IsField,
+    {
+        if !is_cuda_field::<F, E>() {
+            return Err(CudaError::FunctionError(format!(
+                "FFTs over {}",
+                core::any::type_name::<E>()
+            )));
+        }
+        if !input.len().is_power_of_two() {
+            return Err(CudaError::InvalidOrder(input.len()));
+        }
+        four_step_fft::<F, E>(input, inverse, &self.fft)
+    }
+}
+
+impl ComputeBackend for CudaBackend {
+    fn name(&self) -> &'static str {
+        "cuda"
+    }
+
+    fn evaluate_fft<F, E>(
+        &self,
+        coefficients: &[FieldElement<E>],
+    ) -> Result<Vec<FieldElement<E>>, FFTError>
+    where
+        F: IsFFTField + IsSubFieldOf<E>,
+        E: IsField,
+    {
+        self.fft::<F, E>(coefficients, false)
+            .or_else(|_| CpuBackend.evaluate_fft::<F, E>(coefficients))
+    }
+
+    fn interpolate_fft<F, E>(
+        &self,
+        evaluations: &[FieldElement<E>],
+    ) -> Result<Polynomial<FieldElement<E>>, FFTError>
+    where
+        F: IsFFTField + IsSubFieldOf<E>,
+        E: IsField,
+    {
+        match self.fft::<F, E>(evaluations, true) {
+            Ok(coefficients) => {
+                let scale_factor = FieldElement::from(evaluations.len() as u64).inv().unwrap();
+                Ok(Polynomial::new(&coefficients).scale_coeffs(&scale_factor))
+            }
+            Err(_) => CpuBackend.interpolate_fft::<F, E>(evaluations),
+        }
+    }
+
+    fn msm<const NUM_LIMBS: usize, const SCALAR_LIMBS: usize, E>(
+        &self,
+        cs: &[UnsignedInteger<SCALAR_LIMBS>],
+        points: &[ShortWeierstrassProjectivePoint<E>],
+    ) -> Result<ShortWeierstrassProjectivePoint<E>, MSMError>
+    where
+        E: IsBackendMsmCurve,
+        E::BaseField: IsField<BaseType = UnsignedInteger<NUM_LIMBS>>,
+    {
+        ops::msm(cs, points, &self.msm).or_else(|_| CpuBackend.msm(cs, points))
+    }
+
+    fn hash_rows<D: IsGpuByteHash>(&self, rows: &[Vec<u8>]) -> Vec<[u8; 32]> {
+        self.byte_hash
+            .hash_rows(D::BYTE_HASH, rows)
+            .unwrap_or_else(|_| CpuBackend.hash_rows::<D>(rows))
+    }
+}
@@ -0,0 +1,110 @@
+use crate::{
+    compute_backend::{ComputeBackend, CpuBackend, IsBackendMsmCurve},
+    hash::byte_hash::{gpu::metal::MetalByteHashState, IsGpuByteHash},
+};
+use alloc::vec::Vec;
+use lambdaworks_gpu::metal::abstractions::{errors::MetalError, state::MetalState};
+use lambdaworks_math::{
+    elliptic_curve::short_weierstrass::point::ShortWeierstrassProjectivePoint,
+    fft::{
+        errors::FFTError,
+        gpu::metal::ops::{fft, gen_twiddles},
+    },
+    field::{
+        element::FieldElement,
+        traits::{IsFFTField, IsField, IsSubFieldOf, RootsConfig},
+    },
+    msm::{gpu::metal::ops, naive::MSMError},
+    polynomial::Polynomial,
+    unsigned_integer::element::UnsignedInteger,
+};
+
+/// Runs the FFTs, MSMs and hashes in a Metal device, with its libraries loaded once. Falls back
+/// to [`CpuBackend`] for the fields without kernels, or if a command fails.
+pub struct MetalBackend {
+    state: MetalState,
+    byte_hash: MetalByteHashState,
+}
+
+impl MetalBackend {
+    /// Creates a new backend with an optional `device`. If `None` is passed then it will use the
+    /// system's default.
+    pub fn new(device: Option<metal::Device>) -> Result<Self, MetalError> {
+        let state = MetalState::new(device)?;
+        let byte_hash = MetalByteHashState::new(Some(state.device.clone()))?;
+        Ok(Self { state, byte_hash })
+    }
+
+    fn fft<F, E>(
+        &self,
+        input: &[FieldElement<E>],
+        config: RootsConfig,
+    ) -> Result<Vec<FieldElement<E>>, MetalError>
+    where
+        F: IsFFTField + IsSubFieldOf<E>,
+        E: IsField,
+    {
+        if F::field_name().is_empty() {
+            return Err(MetalError::FunctionError(format!(
+                "FFTs over {}",
+                core::any::type_name::<F>()
+            )));
+        }
+        let order = input.len().trailing_zeros();
+        let twiddles = gen_twiddles::<F>(order.into(), config, &self.state)?;
+        fft(input, &twiddles, &self.state)
+    }
+}
+
+impl ComputeBackend for MetalBackend {
+    fn name(&self) -> &'static str {
+        "metal"
+    }
+
+    fn evaluate_fft<F, E>(
+        &self,
+        coefficients: &[FieldElement<E>],
+    ) -> Result<Vec<FieldElement<E>>, FFTError>
+    where
+        F: IsFFTField + IsSubFieldOf<E>,
+        E: IsField,
+    {
+        self.fft::<F, E>(coefficients, RootsConfig::BitReverse)
+            .or_else(|_| CpuBackend.evaluate_fft::<F, E>(coefficients))
+    }
+
+    fn interpolate_fft<F, E>(
+        &self,
+        evaluations: &[FieldElement<E>],
+    ) -> Result<Polynomial<FieldElement<E>>, FFTError>
+    where
+        F: IsFFTField + IsSubFieldOf<E>,
+        E: IsField,
+    {
+        match self.fft::<F, E>(evaluations, RootsConfig::BitReverseInversed) {
+            Ok(coefficients) => {
+                let scale_factor = FieldElement::from(evaluations.len() as u64).inv().unwrap();
+                Ok(Polynomial::new(&coefficients).scale_coeffs(&scale_factor))
+            }
+            Err(_) => CpuBackend.interpolate_fft::<F, E>(evaluations),
+        }
+    }
+
+    fn msm<const NUM_LIMBS: usize, const SCALAR_LIMBS: usize, E>(
+        &self,
+        cs: &[UnsignedInteger<SCALAR_LIMBS>],
+        points: &[ShortWeierstrassProjectivePoint<E>],
+    ) -> Result<ShortWeierstrassProjectivePoint<E>, MSMError>
+    where
+        E: IsBackendMsmCurve,
+        E::BaseField: IsField<BaseType = UnsignedInteger<NUM_LIMBS>>,
+    {
+        ops::msm(cs, points, &self.state).or_else(|_| CpuBackend.msm(cs, points))
+    }
+
+    fn hash_rows<D: IsGpuByteHash>(&self, rows: &[Vec<u8>]) -> Vec<[u8; 32]> {
+        self.byte_hash
+            .hash_rows(D::BYTE_HASH, rows)
+            .unwrap_or_else(|_| CpuBackend.hash_rows::<D>(rows))
+    }
+}
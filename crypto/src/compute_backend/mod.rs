@@ -0,0 +1,240 @@
+use crate::hash::byte_hash::IsGpuByteHash;
+use alloc::vec::Vec;
+use lambdaworks_math::{
+    elliptic_curve::short_weierstrass::{
+        curves::{bls12_381::curve::BLS12381Curve, bn_254::curve::BN254Curve},
+        point::ShortWeierstrassProjectivePoint,
+    },
+    fft::errors::FFTError,
+    field::{
+        element::FieldElement,
+        traits::{IsFFTField, IsField, IsSubFieldOf},
+    },
+    msm::naive::MSMError,
+    polynomial::Polynomial,
+    unsigned_integer::element::UnsignedInteger,
+};
+
+pub mod cpu;
+#[cfg(feature = "cuda")]
+pub mod cuda;
+#[cfg(feature = "metal")]
+pub mod metal;
+
+pub use cpu::CpuBackend;
+#[cfg(feature = "cuda")]
+pub use cuda::CudaBackend;
+#[cfg(feature = "metal")]
+pub use metal::MetalBackend;
+
+#[cfg(not(any(feature = "cuda", feature = "metal")))]
+use lambdaworks_math::elliptic_curve::short_weierstrass::traits::IsShortWeierstrass as IsGpuMsmCurve;
+#[cfg(feature = "cuda")]
+use lambdaworks_math::msm::gpu::cuda::state::IsCudaMsmCurve as IsGpuMsmCurve;
+#[cfg(feature = "metal")]
+use lambdaworks_math::msm::gpu::metal::ops::IsMetalMsmCurve as IsGpuMsmCurve;
+
+/// Curves whose MSMs every [`ComputeBackend`] can compute, which are the ones with GPU
+/// kernels.
+pub trait IsBackendMsmCurve: IsGpuMsmCurve {}
+
+impl IsBackendMsmCurve for BN254Curve {}
+
+impl IsBackendMsmCurve for BLS12381Curve {}
+
+/// The operations that provers offload to a device: FFTs, multi-scalar multiplications and
+/// batches of hashes. Every implementation computes the same results as [`CpuBackend`], and
+/// the GPU ones fall back to it for the inputs their kernels don't support or if the device
+/// fails.
+pub trait ComputeBackend {
+    /// Name of the device the backend runs on.
+    fn name(&self) -> &'static str;
+
+    /// Returns the evaluations of the polynomial with `coefficients` over the roots of unity of
+    /// `F`, as in [`Polynomial::evaluate_fft`]. The number of coefficients must be a power of
+    /// two.
+    fn evaluate_fft<F, E>(
+        &self,
+        coefficients: &[FieldElement<E>],
+    ) -> Result<Vec<FieldElement<E>>, FFTError>
+    where
+        F: IsFFTField + IsSubFieldOf<E>,
+        E: IsField;
+
+    /// Returns the polynomial that interpolates `evaluations` over the roots of unity of `F`.
+    /// This is the inverse operation of [`Self::evaluate_fft`].
+    fn interpolate_fft<F, E>(
+        &self,
+        evaluations: &[FieldElement<E>],
+    ) -> Result<Polynomial<FieldElement<E>>, FFTError>
+    where
+        F: IsFFTField + IsSubFieldOf<E>,
+        E: IsField;
+
+    /// Computes the multi-scalar multiplication `k_1 * g_1 + ... + k_n * g_n`.
+    fn msm<const NUM_LIMBS: usize, const SCALAR_LIMBS: usize, E>(
+        &self,
+        cs: &[UnsignedInteger<SCALAR_LIMBS>],
+        points: &[ShortWeierstrassProjectivePoint<E>],
+    ) -> Result<ShortWeierstrassProjectivePoint<E>, MSMError>
+    where
+        E: IsBackendMsmCurve,
+        E::BaseField: IsField<BaseType = UnsignedInteger<NUM_LIMBS>>;
+
+    /// Returns the `D` hash of each of the `rows`.
+    fn hash_rows<D: IsGpuByteHash>(&self, rows: &[Vec<u8>]) -> Vec<[u8; 32]>;
+}
+
+/// A [`ComputeBackend`] chosen at runtime among the ones the crate was built with, so that
+/// the same binary runs in machines with and without a GPU.
+pub enum Backend {
+    Cpu(CpuBackend),
+    #[cfg(feature = "cuda")]
+    Cuda(CudaBackend),
+    #[cfg(feature = "metal")]
+    Metal(MetalBackend),
+}
+
+impl Backend {
+    /// Returns the GPU backend if there is a device it can use, and the CPU one otherwise.
+    pub fn detect() -> Self {
+        #[cfg(feature = "cuda")]
+        if let Ok(backend) = CudaBackend::new() {
+            return Backend::Cuda(backend);
+        }
+        #[cfg(feature = "metal")]
+        if let Ok(backend) = MetalBackend::new(None) {
+            return Backend::Metal(backend);
+        }
+        Backend::Cpu(CpuBackend)
+    }
+}
+
+impl Default for Backend {
+    fn default() -> Self {
+        Self::detect()
+    }
+}
+
+/// Calls `$method` on the backend held by `$backend`.
+macro_rules! dispatch {
+    ($backend:expr, $method:ident $(::<$($generic:ty),*>)?($($arg:expr),*)) => {
+        match $backend {
+            Backend::Cpu(backend) => backend.$method$(::<$($generic),*>)?($($arg),*),
+            #[cfg(feature = "cuda")]
+            Backend::Cuda(backend) => backend.$method$(::<$($generic),*>)?($($arg),*),
+            #[cfg(feature = "metal")]
+            Backend::Metal(backend) => backend.$method$(::<$($generic),*>)?($($arg),*),
+        }
+    };
+}
+
+impl ComputeBackend for Backend {
+    fn name(&self) -> &'static str {
+        dispatch!(self, name())
+    }
+
+    fn evaluate_fft<F, E>(
+        &self,
+        coefficients: &[FieldElement<E>],
+    ) -> Result<Vec<FieldElement<E>>, FFTError>
+    where
+        F: IsFFTField + IsSubFieldOf<E>,
+        E: IsField,
+    {
+        dispatch!(self, evaluate_fft::<F, E>(coefficients))
+    }
+
+    fn interpolate_fft<F, E>(
+        &self,
+        evaluations: &[FieldElement<E>],
+    ) -> Result<Polynomial<FieldElement<E>>, FFTError>
+    where
+        F: IsFFTField + IsSubFieldOf<E>,
+        E: IsField,
+    {
+        dispatch!(self, interpolate_fft::<F, E>(evaluations))
+    }
+
+    fn msm<const NUM_LIMBS: usize, const SCALAR_LIMBS: usize, E>(
+        &self,
+        cs: &[UnsignedInteger<SCALAR_LIMBS>],
+        points: &[ShortWeierstrassProjectivePoint<E>],
+    ) -> Result<ShortWeierstrassProjectivePoint<E>, MSMError>
+    where
+        E: IsBackendMsmCurve,
+        E::BaseField: IsField<BaseType = UnsignedInteger<NUM_LIMBS>>,
+    {
+        dispatch!(self, msm(cs, points))
+    }
+
+    fn hash_rows<D: IsGpuByteHash>(&self, rows: &[Vec<u8>]) -> Vec<[u8; 32]> {
+        dispatch!(self, hash_rows::<D>(rows))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use blake2::Blake2s256;
+    use lambdaworks_math::{
+        cyclic_group::IsGroup, elliptic_curve::traits::IsEllipticCurve,
+        field::fields::fft_friendly::stark_252_prime_field::Stark252PrimeField,
+    };
+    use sha3::Keccak256;
+
+    type F = Stark252PrimeField;
+    type FE = FieldElement<F>;
+
+    #[test]
+    fn detected_backend_matches_cpu_fft() {
+        let backend = Backend::detect();
+        let coefficients: Vec<FE> = (0..1_u64 << 10).map(|i| FE::from(i * i + 3)).collect();
+
+        let evaluations = backend.evaluate_fft::<F, F>(&coefficients).unwrap();
+        assert_eq!(
+            evaluations,
+            CpuBackend.evaluate_fft::<F, F>(&coefficients).unwrap()
+        );
+        assert_eq!(
+            backend.interpolate_fft::<F, F>(&evaluations).unwrap(),
+            Polynomial::new(&coefficients)
+        );
+    }
+
+    #[test]
+    fn detected_backend_matches_cpu_msm() {
+        let backend = Backend::detect();
+        let g = BLS12381Curve::generator();
+        let points: Vec<_> = (1..200_u64).map(|i| g.operate_with_self(i)).collect();
+        let cs: Vec<_> = (1..200_u64)
+            .map(|i| UnsignedInteger::<4>::from_u64(i * 7919))
+            .collect();
+
+        assert_eq!(
+            backend.msm(&cs, &points).unwrap(),
+            CpuBackend.msm(&cs, &points).unwrap()
+        );
+        assert!(matches!(
+            backend.msm(&cs[1..], &points),
+            Err(MSMError::LengthMismatch(198, 199))
+        ));
+    }
+
+    #[test]
+    fn detected_backend_matches_cpu_hashes() {
+        let backend = Backend::detect();
+        let rows: Vec<Vec<u8>> = (0..100_u8)
+            .map(|i| (0..70).map(|j| i ^ j).collect())
+            .collect();
+
+        assert_eq!(
+            backend.hash_rows::<Keccak256>(&rows),
+            CpuBackend.hash_rows::<Keccak256>(&rows)
+        );
+        assert_eq!(
+            backend.hash_rows::<Blake2s256>(&rows),
+            CpuBackend.hash_rows::<Blake2s256>(&rows)
+        );
+    }
+}
@@ -35,3 +35,32 @@ impl From<std::io::Error> for SrsFromFileError {
         SrsFromFileError::FileError(err)
     }
 }
+
+/// Errors of the setup artifacts container, see [`crate::setup_file`].
+#[derive(Debug)]
+pub enum SetupFileError {
+    Io(io::Error),
+    InvalidMagic,
+    UnsupportedVersion(u32),
+    /// The file holds a different kind of artifact.
+    UnexpectedKind(u32),
+    UnexpectedSection(u32),
+    MissingSection,
+    UnexpectedEndOfSection,
+    InvalidElementSize,
+    /// The section does not fit in memory on this architecture.
+    SectionTooLarge,
+    Deserialization(DeserializationError),
+}
+
+impl From<io::Error> for SetupFileError {
+    fn from(err: io::Error) -> SetupFileError {
+        SetupFileError::Io(err)
+    }
+}
+
+impl From<DeserializationError> for SetupFileError {
+    fn from(err: DeserializationError) -> SetupFileError {
+        SetupFileError::Deserialization(err)
+    }
+}
@@ -66,7 +66,7 @@ where
     }
 
     fn sample_u64(&mut self, upper_bound: u64) -> u64 {
-        u64::from_be_bytes(self.state()[..8].try_into().unwrap()) % upper_bound
+        u64::from_be_bytes(self.sample()[..8].try_into().unwrap()) % upper_bound
     }
 }
 
@@ -75,7 +75,7 @@ mod tests {
     use super::*;
 
     extern crate alloc;
-    use alloc::vec::Vec;
+    use alloc::{collections::BTreeSet, vec::Vec};
     use lambdaworks_math::elliptic_curve::short_weierstrass::curves::bls12_381::default_types::FrField;
 
     #[test]
@@ -115,4 +115,12 @@ mod tests {
             ]
         );
     }
+
+    #[test]
+    fn query_indexes_sampled_one_after_the_other_differ() {
+        let mut transcript = DefaultTranscript::<FrField>::new(&[]);
+        let indexes: BTreeSet<u64> = (0..30).map(|_| transcript.sample_u64(1 << 12)).collect();
+
+        assert!(indexes.len() > 1);
+    }
 }
@@ -0,0 +1,122 @@
+use super::is_transcript::IsTranscript;
+use alloc::vec::Vec;
+use core::marker::PhantomData;
+use lambdaworks_math::field::{
+    element::FieldElement,
+    traits::{IsField, IsSubFieldOf},
+};
+
+/// A transcript over an extension `E` of the field `F` of the transcript `T`. Elements of `E`
+/// are appended as their coordinates over `F`, and sampled from as many elements of `F`, so they
+/// are uniform in `E`.
+///
+/// Proofs over small fields, such as Goldilocks or BabyBear, sample their challenges in an
+/// extension to be sound, while hashing with a transcript over the base field.
+pub struct ExtensionTranscript<F, E, T> {
+    transcript: T,
+    phantom: PhantomData<(F, E)>,
+}
+
+impl<F, E, T> ExtensionTranscript<F, E, T>
+where
+    F: IsSubFieldOf<E>,
+    E: IsField,
+    T: IsTranscript<F>,
+{
+    pub fn new(transcript: T) -> Self {
+        Self {
+            transcript,
+            phantom: PhantomData,
+        }
+    }
+
+    /// The transcript over the base field.
+    pub fn into_inner(self) -> T {
+        self.transcript
+    }
+
+    /// The degree of `E` over `F`.
+    fn degree() -> usize {
+        F::to_subfield_vec(FieldElement::<E>::one().value().clone()).len()
+    }
+}
+
+impl<F, E, T> IsTranscript<E> for ExtensionTranscript<F, E, T>
+where
+    F: IsSubFieldOf<E>,
+    E: IsField,
+    T: IsTranscript<F>,
+{
+    fn append_field_element(&mut self, element: &FieldElement<E>) {
+        for coordinate in element.clone().to_subfield_vec::<F>() {
+            self.transcript.append_field_element(&coordinate);
+        }
+    }
+
+    fn append_bytes(&mut self, new_bytes: &[u8]) {
+        self.transcript.append_bytes(new_bytes);
+    }
+
+    fn state(&self) -> [u8; 32] {
+        self.transcript.state()
+    }
+
+    fn sample_field_element(&mut self) -> FieldElement<E> {
+        let coordinates: Vec<FieldElement<F>> = (0..Self::degree())
+            .map(|_| self.transcript.sample_field_element())
+            .collect();
+        FieldElement::from_subfield_slice(&coordinates)
+    }
+
+    fn sample_u64(&mut self, upper_bound: u64) -> u64 {
+        self.transcript.sample_u64(upper_bound)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fiat_shamir::default_transcript::DefaultTranscript;
+    use lambdaworks_math::field::{
+        extensions::quadratic::{HasQuadraticNonResidue, QuadraticExtensionField},
+        fields::u64_prime_field::U64PrimeField,
+    };
+
+    type F = U64PrimeField<17>;
+    type FE = FieldElement<F>;
+
+    #[derive(Debug, Clone)]
+    struct NonResidue;
+    impl HasQuadraticNonResidue<F> for NonResidue {
+        fn residue() -> FE {
+            FE::from(3)
+        }
+    }
+
+    type E = QuadraticExtensionField<F, NonResidue>;
+
+    #[test]
+    fn samples_are_the_coordinates_sampled_from_the_base_field() {
+        let mut base = DefaultTranscript::<F>::new(&[1, 2, 3]);
+        let mut extension = ExtensionTranscript::<F, E, _>::new(DefaultTranscript::new(&[1, 2, 3]));
+
+        let coordinates = [base.sample_field_element(), base.sample_field_element()];
+        assert_eq!(
+            extension.sample_field_element(),
+            FieldElement::<E>::new(coordinates)
+        );
+        assert_eq!(extension.state(), base.state());
+    }
+
+    #[test]
+    fn elements_are_appended_as_their_coordinates() {
+        let mut base = DefaultTranscript::<F>::default();
+        let mut extension = ExtensionTranscript::<F, E, _>::new(DefaultTranscript::default());
+
+        base.append_field_element(&FE::from(5));
+        base.append_field_element(&FE::from(7));
+        extension.append_field_element(&FieldElement::new([FE::from(5), FE::from(7)]));
+
+        assert_eq!(extension.into_inner().state(), base.state());
+    }
+}
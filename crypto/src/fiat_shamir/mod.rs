@@ -1,4 +1,6 @@
 pub mod default_transcript;
+pub mod extension_transcript;
 pub mod is_transcript;
+pub mod poseidon_transcript;
 #[cfg(feature = "test_fiat_shamir")]
 pub mod test_transcript;
@@ -0,0 +1,175 @@
+use super::is_transcript::IsTranscript;
+use crate::hash::poseidon::Poseidon;
+use alloc::vec::Vec;
+use lambdaworks_math::{
+    field::{element::FieldElement, traits::IsPrimeField},
+    traits::ByteConversion,
+};
+
+/// A transcript over the field of the Poseidon permutation `P`, as a duplex sponge. Field
+/// elements are absorbed as they are, and bytes are packed in elements, so verifiers of proofs
+/// over the same field can run it inside a circuit with a few permutations.
+///
+/// The elements appended between two samples are absorbed, padded like in
+/// [`Poseidon::hash_many`], before squeezing the rate of the state.
+pub struct PoseidonTranscript<P: Poseidon> {
+    state: Vec<FieldElement<P::F>>,
+    /// The elements appended since the last permutation.
+    pending: Vec<FieldElement<P::F>>,
+    /// The elements of the rate left from the last permutation, in reverse order.
+    squeezed: Vec<FieldElement<P::F>>,
+}
+
+impl<P: Poseidon> PoseidonTranscript<P>
+where
+    FieldElement<P::F>: ByteConversion,
+{
+    pub fn new(data: &[u8]) -> Self {
+        let mut res = Self {
+            state: vec![FieldElement::zero(); P::STATE_SIZE],
+            pending: Vec::new(),
+            squeezed: Vec::new(),
+        };
+        res.append_bytes(data);
+        res
+    }
+
+    /// The number of bytes packed in each element, as many as fit below the modulus.
+    fn bytes_per_element() -> usize {
+        (P::F::field_bit_size() - 1) / 8
+    }
+
+    /// Absorbs the pending elements and permutes the state, leaving its rate to be squeezed.
+    fn duplex(&mut self) {
+        let mut values = core::mem::take(&mut self.pending);
+        values.push(FieldElement::one());
+        values.resize(
+            values.len().div_ceil(P::RATE) * P::RATE,
+            FieldElement::zero(),
+        );
+        for block in values.chunks(P::RATE) {
+            for (state, value) in self.state.iter_mut().zip(block) {
+                *state = &*state + value;
+            }
+            P::hades_permutation(&mut self.state);
+        }
+        self.squeezed = self.state[..P::RATE].iter().rev().cloned().collect();
+    }
+}
+
+impl<P: Poseidon> Default for PoseidonTranscript<P>
+where
+    FieldElement<P::F>: ByteConversion,
+{
+    fn default() -> Self {
+        Self::new(&[])
+    }
+}
+
+impl<P: Poseidon> IsTranscript<P::F> for PoseidonTranscript<P>
+where
+    FieldElement<P::F>: ByteConversion,
+{
+    fn append_field_element(&mut self, element: &FieldElement<P::F>) {
+        self.pending.push(element.clone());
+    }
+
+    /// Appends the number of bytes, followed by the bytes packed in big-endian elements.
+    fn append_bytes(&mut self, new_bytes: &[u8]) {
+        let byte = FieldElement::<P::F>::from(256);
+        self.pending
+            .push(FieldElement::from(new_bytes.len() as u64));
+        self.pending
+            .extend(new_bytes.chunks(Self::bytes_per_element()).map(|chunk| {
+                chunk.iter().fold(FieldElement::zero(), |acc, b| {
+                    acc * &byte + FieldElement::from(*b as u64)
+                })
+            }));
+    }
+
+    /// The last 32 bytes of the next element the transcript would sample.
+    fn state(&self) -> [u8; 32] {
+        let mut transcript = Self {
+            state: self.state.clone(),
+            pending: self.pending.clone(),
+            squeezed: self.squeezed.clone(),
+        };
+        let bytes = transcript.sample_field_element().to_bytes_be();
+        let len = bytes.len().min(32);
+        let mut state = [0; 32];
+        state[32 - len..].copy_from_slice(&bytes[bytes.len() - len..]);
+        state
+    }
+
+    fn sample_field_element(&mut self) -> FieldElement<P::F> {
+        if !self.pending.is_empty() || self.squeezed.is_empty() {
+            self.duplex();
+        }
+        self.squeezed.pop().unwrap()
+    }
+
+    fn sample_u64(&mut self, upper_bound: u64) -> u64 {
+        let bytes = self.sample_field_element().to_bytes_be();
+        let mut value = [0; 8];
+        value.copy_from_slice(&bytes[bytes.len() - 8..]);
+        u64::from_be_bytes(value) % upper_bound
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hash::poseidon::starknet::PoseidonCairoStark252;
+    use lambdaworks_math::field::fields::fft_friendly::stark_252_prime_field::Stark252PrimeField;
+
+    type FE = FieldElement<Stark252PrimeField>;
+
+    #[test]
+    fn samples_depend_on_everything_appended() {
+        let sample = |elements: &[FE], bytes: &[u8]| {
+            let mut transcript = PoseidonTranscript::<PoseidonCairoStark252>::default();
+            for element in elements {
+                transcript.append_field_element(element);
+            }
+            transcript.append_bytes(bytes);
+            transcript.sample_field_element()
+        };
+
+        let first = sample(&[FE::from(1), FE::from(2)], &[3; 40]);
+        assert_eq!(first, sample(&[FE::from(1), FE::from(2)], &[3; 40]));
+        assert_ne!(first, sample(&[FE::from(2), FE::from(1)], &[3; 40]));
+        assert_ne!(first, sample(&[FE::from(1), FE::from(2)], &[3; 41]));
+        assert_ne!(
+            first,
+            sample(&[FE::from(1), FE::from(2), FE::zero()], &[3; 40])
+        );
+    }
+
+    #[test]
+    fn consecutive_samples_are_different() {
+        let mut transcript = PoseidonTranscript::<PoseidonCairoStark252>::new(&[1, 2, 3]);
+        let state = transcript.state();
+        let samples: Vec<FE> = (0..5).map(|_| transcript.sample_field_element()).collect();
+
+        assert_eq!(state, samples[0].to_bytes_be().as_slice());
+        for (i, sample) in samples.iter().enumerate() {
+            assert!(samples[i + 1..].iter().all(|other| other != sample));
+        }
+    }
+
+    #[test]
+    fn samples_are_the_rate_of_the_permuted_padded_input() {
+        let mut transcript = PoseidonTranscript::<PoseidonCairoStark252>::default();
+        transcript.append_field_element(&FE::from(7));
+
+        // The empty initial data is absorbed as its length, zero, and the padding takes another
+        // block.
+        let mut state = vec![FE::zero(), FE::from(7), FE::zero()];
+        PoseidonCairoStark252::hades_permutation(&mut state);
+        state[0] = &state[0] + FE::one();
+        PoseidonCairoStark252::hades_permutation(&mut state);
+
+        assert_eq!(transcript.sample_field_element(), state[0]);
+        assert_eq!(transcript.sample_field_element(), state[1]);
+    }
+}
@@ -0,0 +1,219 @@
+use crate::hash::byte_hash::ByteHash;
+use alloc::vec::Vec;
+use cudarc::{
+    driver::{LaunchAsync, LaunchConfig},
+    nvrtc::safe::Ptx,
+};
+use lambdaworks_gpu::cuda::abstractions::errors::CudaError;
+use lambdaworks_math::gpu::cuda::memory::{DeviceMemoryPool, PooledSlice};
+
+const BYTES_PTX: &str = include_str!("../../../../../math/src/gpu/cuda/shaders/hash/bytes.ptx");
+const MODULE_NAME: &str = "byte_hash";
+const BYTE_HASH_FUNCTIONS: [&str; 4] = [
+    "keccak256_hash_rows",
+    "keccak256_merkle_level",
+    "blake2s256_hash_rows",
+    "blake2s256_merkle_level",
+];
+const WARP_SIZE: usize = 32; // the implementation will spawn threadblocks of this size.
+const DIGEST_LEN: usize = 32;
+
+/// Computes batches of [`ByteHash`] hashes of byte rows, and the Merkle trees built with them,
+/// in CUDA.
+pub struct CudaByteHashState {
+    pool: DeviceMemoryPool,
+}
+
+impl CudaByteHashState {
+    /// Creates a new CUDA state with the first GPU, which takes its buffers from
+    /// [`DeviceMemoryPool::global`].
+    pub fn new() -> Result<Self, CudaError> {
+        Self::with_pool(DeviceMemoryPool::global()?)
+    }
+
+    /// Creates a new CUDA state with the device of `pool`, which takes its buffers from it.
+    pub fn with_pool(pool: DeviceMemoryPool) -> Result<Self, CudaError> {
+        // Load PTX libraries, once per device
+        if pool
+            .device()
+            .get_func(MODULE_NAME, BYTE_HASH_FUNCTIONS[0])
+            .is_none()
+        {
+            pool.device()
+                .load_ptx(Ptx::from_src(BYTES_PTX), MODULE_NAME, &BYTE_HASH_FUNCTIONS)
+                .map_err(|err| CudaError::PtxError(err.to_string()))?;
+        }
+
+        Ok(Self { pool })
+    }
+
+    /// Returns the `hash` of each of the `rows`. All the rows must have the same length.
+    pub fn hash_rows(&self, hash: ByteHash, rows: &[Vec<u8>]) -> Result<Vec<[u8; 32]>, CudaError> {
+        let Some(row_len) = rows.first().map(Vec::len) else {
+            return Ok(Vec::new());
+        };
+        if let Some(row) = rows.iter().find(|row| row.len() != row_len) {
+            return Err(CudaError::LengthMismatch(row_len, row.len()));
+        }
+        let function_name = format!("{}_hash_rows", hash.kernel_prefix());
+        let function = self
+            .pool
+            .device()
+            .get_func(MODULE_NAME, &function_name)
+            .ok_or(CudaError::FunctionError(function_name))?;
+        let mut flat_rows: Vec<u8> = rows.concat();
+        if flat_rows.is_empty() {
+            // Device buffers can't be empty
+            flat_rows.push(0);
+        }
+        let input = self.pool.upload(&flat_rows)?;
+        let mut output = self.pool.alloc::<u8>(rows.len() * DIGEST_LEN)?;
+
+        unsafe {
+            function.launch(
+                launch_config(rows.len()),
+                (&*input, &mut *output, row_len as u32, rows.len() as u32),
+            )
+        }
+        .map_err(|err| CudaError::Launch(err.to_string()))?;
+
+        dtoh(&self.pool, &output)
+    }
+
+    /// Computes the inner nodes of a Merkle tree whose parents are the `hash` of their children
+    /// one after the other. `nodes` holds the tree in breadth-first order, with its `leaves_len`
+    /// leaves at the end, as in [`MerkleTree`](crate::merkle_tree::merkle::MerkleTree).
+    /// `leaves_len` must be a power of two.
+    ///
+    /// The whole tree stays in the device until all its levels are built.
+    pub fn build_merkle_tree(
+        &self,
+        hash: ByteHash,
+        nodes: &mut [[u8; 32]],
+        leaves_len: usize,
+    ) -> Result<(), CudaError> {
+        if nodes.len() != 2 * leaves_len - 1 {
+            return Err(CudaError::LengthMismatch(2 * leaves_len - 1, nodes.len()));
+        }
+        if leaves_len == 1 {
+            return Ok(());
+        }
+        let function_name = format!("{}_merkle_level", hash.kernel_prefix());
+        let function = self
+            .pool
+            .device()
+            .get_func(MODULE_NAME, &function_name)
+            .ok_or(CudaError::FunctionError(function_name))?;
+        let mut device_nodes = self.pool.upload(&nodes.concat())?;
+
+        // Levels are launched on the same stream, so each one sees the nodes of the previous one
+        let mut level_begin = leaves_len - 1;
+        while level_begin > 0 {
+            let parents_begin = level_begin / 2;
+            let count = level_begin - parents_begin;
+            unsafe {
+                function.clone().launch(
+                    launch_config(count),
+                    (&mut *device_nodes, parents_begin as u32, count as u32),
+                )
+            }
+            .map_err(|err| CudaError::Launch(err.to_string()))?;
+            level_begin = parents_begin;
+        }
+
+        let built_nodes = dtoh(&self.pool, &device_nodes)?;
+        nodes[..leaves_len - 1].copy_from_slice(&built_nodes[..leaves_len - 1]);
+        Ok(())
+    }
+}
+
+fn launch_config(threads: usize) -> LaunchConfig {
+    let block_size = WARP_SIZE;
+    let block_count = threads.div_ceil(block_size);
+
+    LaunchConfig {
+        grid_dim: (block_count as u32, 1, 1), // in blocks
+        block_dim: (block_size as u32, 1, 1),
+        shared_mem_bytes: 0,
+    }
+}
+
+fn dtoh(pool: &DeviceMemoryPool, buffer: &PooledSlice<u8>) -> Result<Vec<[u8; 32]>, CudaError> {
+    let bytes = pool
+        .device()
+        .dtoh_sync_copy(&**buffer)
+        .map_err(|err| CudaError::RetrieveMemory(err.to_string()))?;
+    Ok(bytes
+        .chunks_exact(DIGEST_LEN)
+        .map(|digest| {
+            let mut node = [0; DIGEST_LEN];
+            node.copy_from_slice(digest);
+            node
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use blake2::Blake2s256;
+    use sha3::{Digest, Keccak256};
+
+    fn digest(hash: ByteHash, data: &[u8]) -> [u8; 32] {
+        match hash {
+            ByteHash::Keccak256 => Keccak256::digest(data).into(),
+            ByteHash::Blake2s256 => Blake2s256::digest(data).into(),
+        }
+    }
+
+    fn rows(count: usize, row_len: usize) -> Vec<Vec<u8>> {
+        (0..count)
+            .map(|i| (0..row_len).map(|j| (i * 31 + j * 7) as u8).collect())
+            .collect()
+    }
+
+    #[test]
+    fn cuda_hash_rows_matches_cpu() {
+        let state = CudaByteHashState::new().unwrap();
+        for hash in [ByteHash::Keccak256, ByteHash::Blake2s256] {
+            // Rows shorter and longer than a block of both hashes
+            for row_len in [0, 1, 32, 64, 65, 136, 137, 300] {
+                let rows = rows(50, row_len);
+                let expected: Vec<_> = rows.iter().map(|row| digest(hash, row)).collect();
+
+                assert_eq!(state.hash_rows(hash, &rows).unwrap(), expected);
+            }
+        }
+    }
+
+    #[test]
+    fn cuda_hash_rows_of_different_lengths_should_fail() {
+        let state = CudaByteHashState::new().unwrap();
+        let rows = vec![vec![1, 2, 3], vec![4, 5]];
+
+        assert!(matches!(
+            state.hash_rows(ByteHash::Keccak256, &rows),
+            Err(CudaError::LengthMismatch(3, 2))
+        ));
+    }
+
+    #[test]
+    fn cuda_merkle_tree_matches_cpu() {
+        let state = CudaByteHashState::new().unwrap();
+        for hash in [ByteHash::Keccak256, ByteHash::Blake2s256] {
+            let leaves_len = 64;
+            let mut nodes = vec![[0; 32]; leaves_len - 1];
+            nodes.extend(rows(leaves_len, 32).iter().map(|row| digest(hash, row)));
+            let mut expected = nodes.clone();
+            for parent in (0..leaves_len - 1).rev() {
+                let children = [expected[2 * parent + 1], expected[2 * parent + 2]].concat();
+                expected[parent] = digest(hash, &children);
+            }
+
+            state
+                .build_merkle_tree(hash, &mut nodes, leaves_len)
+                .unwrap();
+            assert_eq!(nodes, expected);
+        }
+    }
+}
@@ -0,0 +1,209 @@
+use crate::hash::byte_hash::ByteHash;
+use alloc::vec::Vec;
+use lambdaworks_gpu::metal::abstractions::{errors::MetalError, state::MetalState};
+use metal::{Buffer, ComputeCommandEncoderRef, ComputePipelineState, MTLSize};
+
+use core::mem;
+
+/// Computes batches of [`ByteHash`] hashes of byte rows, and the Merkle trees built with them,
+/// in Metal.
+pub struct MetalByteHashState {
+    state: MetalState,
+}
+
+impl MetalByteHashState {
+    /// Creates a new state with an optional `device`. If `None` is passed then it will use the
+    /// system's default.
+    pub fn new(device: Option<metal::Device>) -> Result<Self, MetalError> {
+        Ok(Self {
+            state: MetalState::new(device)?,
+        })
+    }
+
+    /// Returns the `hash` of each of the `rows`. All the rows must have the same length.
+    pub fn hash_rows(&self, hash: ByteHash, rows: &[Vec<u8>]) -> Result<Vec<[u8; 32]>, MetalError> {
+        let Some(row_len) = rows.first().map(Vec::len) else {
+            return Ok(Vec::new());
+        };
+        if let Some(row) = rows.iter().find(|row| row.len() != row_len) {
+            return Err(MetalError::LengthMismatch(row_len, row.len()));
+        }
+        let pipeline = self
+            .state
+            .setup_pipeline(&format!("{}_hash_rows", hash.kernel_prefix()))?;
+        let mut flat_rows: Vec<u8> = rows.concat();
+        if flat_rows.is_empty() {
+            // Metal buffers can't be empty
+            flat_rows.push(0);
+        }
+        let input_buffer = self.state.alloc_buffer_data(&flat_rows);
+        let output_buffer = self.state.alloc_buffer::<[u8; 32]>(rows.len());
+
+        self.run(|command_encoder| {
+            dispatch(
+                command_encoder,
+                &pipeline,
+                &[&input_buffer, &output_buffer],
+                &[row_len as u32],
+                rows.len(),
+            );
+        });
+
+        Ok(MetalState::retrieve_contents(&output_buffer))
+    }
+
+    /// Computes the inner nodes of a Merkle tree whose parents are the `hash` of their children
+    /// one after the other. `nodes` holds the tree in breadth-first order, with its `leaves_len`
+    /// leaves at the end, as in [`MerkleTree`](crate::merkle_tree::merkle::MerkleTree).
+    /// `leaves_len` must be a power of two.
+    ///
+    /// All the levels are encoded in a single command buffer.
+    pub fn build_merkle_tree(
+        &self,
+        hash: ByteHash,
+        nodes: &mut [[u8; 32]],
+        leaves_len: usize,
+    ) -> Result<(), MetalError> {
+        if nodes.len() != 2 * leaves_len - 1 {
+            return Err(MetalError::LengthMismatch(2 * leaves_len - 1, nodes.len()));
+        }
+        if leaves_len == 1 {
+            return Ok(());
+        }
+        let pipeline = self
+            .state
+            .setup_pipeline(&format!("{}_merkle_level", hash.kernel_prefix()))?;
+        let nodes_buffer = self.state.alloc_buffer_data(nodes);
+
+        self.run(|command_encoder| {
+            // Dispatches of the same encoder run one after the other, so each level sees the
+            // nodes of the previous one.
+            let mut level_begin = leaves_len - 1;
+            while level_begin > 0 {
+                let parents_begin = level_begin / 2;
+                dispatch(
+                    command_encoder,
+                    &pipeline,
+                    &[&nodes_buffer],
+                    &[parents_begin as u32],
+                    level_begin - parents_begin,
+                );
+                level_begin = parents_begin;
+            }
+        });
+
+        let built_nodes: Vec<[u8; 32]> = MetalState::retrieve_contents(&nodes_buffer);
+        nodes[..leaves_len - 1].copy_from_slice(&built_nodes[..leaves_len - 1]);
+        Ok(())
+    }
+
+    /// Encodes the dispatches of `encode` in a command buffer and waits for it to complete.
+    fn run(&self, encode: impl FnOnce(&ComputeCommandEncoderRef)) {
+        objc::rc::autoreleasepool(|| {
+            let command_buffer = self.state.queue.new_command_buffer();
+            let command_encoder = command_buffer.new_compute_command_encoder();
+            encode(command_encoder);
+            command_encoder.end_encoding();
+
+            command_buffer.commit();
+            command_buffer.wait_until_completed();
+        });
+    }
+}
+
+/// Queues `threads` threads of `pipeline`, with `buffers` bound from index 0 and the
+/// `constants` after them.
+fn dispatch(
+    command_encoder: &ComputeCommandEncoderRef,
+    pipeline: &ComputePipelineState,
+    buffers: &[&Buffer],
+    constants: &[u32],
+    threads: usize,
+) {
+    command_encoder.set_compute_pipeline_state(pipeline);
+    for (i, buffer) in buffers.iter().enumerate() {
+        command_encoder.set_buffer(i as u64, Some(buffer), 0);
+    }
+    for (i, constant) in constants.iter().enumerate() {
+        command_encoder.set_bytes(
+            (buffers.len() + i) as u64,
+            mem::size_of_val(constant) as u64,
+            void_ptr(constant),
+        );
+    }
+
+    let grid_size = MTLSize::new(threads as u64, 1, 1);
+    let threadgroup_size = MTLSize::new(pipeline.thread_execution_width(), 1, 1);
+
+    // WARN: Device should support non-uniform threadgroups (Metal3 and Apple4 or latter).
+    command_encoder.dispatch_threads(grid_size, threadgroup_size);
+}
+
+fn void_ptr<T>(v: &T) -> *const core::ffi::c_void {
+    v as *const T as *const core::ffi::c_void
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use blake2::Blake2s256;
+    use sha3::{Digest, Keccak256};
+
+    fn digest(hash: ByteHash, data: &[u8]) -> [u8; 32] {
+        match hash {
+            ByteHash::Keccak256 => Keccak256::digest(data).into(),
+            ByteHash::Blake2s256 => Blake2s256::digest(data).into(),
+        }
+    }
+
+    fn rows(count: usize, row_len: usize) -> Vec<Vec<u8>> {
+        (0..count)
+            .map(|i| (0..row_len).map(|j| (i * 31 + j * 7) as u8).collect())
+            .collect()
+    }
+
+    #[test]
+    fn metal_hash_rows_matches_cpu() {
+        let state = MetalByteHashState::new(None).unwrap();
+        for hash in [ByteHash::Keccak256, ByteHash::Blake2s256] {
+            // Rows shorter and longer than a block of both hashes
+            for row_len in [0, 1, 32, 64, 65, 136, 137, 300] {
+                let rows = rows(50, row_len);
+                let expected: Vec<_> = rows.iter().map(|row| digest(hash, row)).collect();
+
+                assert_eq!(state.hash_rows(hash, &rows).unwrap(), expected);
+            }
+        }
+    }
+
+    #[test]
+    fn metal_hash_rows_of_different_lengths_should_fail() {
+        let state = MetalByteHashState::new(None).unwrap();
+        let rows = vec![vec![1, 2, 3], vec![4, 5]];
+
+        assert!(matches!(
+            state.hash_rows(ByteHash::Keccak256, &rows),
+            Err(MetalError::LengthMismatch(3, 2))
+        ));
+    }
+
+    #[test]
+    fn metal_merkle_tree_matches_cpu() {
+        let state = MetalByteHashState::new(None).unwrap();
+        for hash in [ByteHash::Keccak256, ByteHash::Blake2s256] {
+            let leaves_len = 64;
+            let mut nodes = vec![[0; 32]; leaves_len - 1];
+            nodes.extend(rows(leaves_len, 32).iter().map(|row| digest(hash, row)));
+            let mut expected = nodes.clone();
+            for parent in (0..leaves_len - 1).rev() {
+                let children = [expected[2 * parent + 1], expected[2 * parent + 2]].concat();
+                expected[parent] = digest(hash, &children);
+            }
+
+            state
+                .build_merkle_tree(hash, &mut nodes, leaves_len)
+                .unwrap();
+            assert_eq!(nodes, expected);
+        }
+    }
+}
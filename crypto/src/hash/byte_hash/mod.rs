@@ -0,0 +1,36 @@
+use blake2::Blake2s256;
+use sha3::{Digest, Keccak256};
+
+#[cfg(any(feature = "cuda", feature = "metal"))]
+pub mod gpu;
+
+/// Byte-oriented hashes with 32 bytes digests that have GPU kernels for hashing Merkle leaves
+/// and nodes, as used by Stone-compatible commitments.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ByteHash {
+    Keccak256,
+    Blake2s256,
+}
+
+impl ByteHash {
+    /// Prefix of the names of the kernels that compute this hash.
+    pub fn kernel_prefix(&self) -> &'static str {
+        match self {
+            ByteHash::Keccak256 => "keccak256",
+            ByteHash::Blake2s256 => "blake2s256",
+        }
+    }
+}
+
+/// A [`Digest`] that the GPU kernels of [`ByteHash`] can compute.
+pub trait IsGpuByteHash: Digest {
+    const BYTE_HASH: ByteHash;
+}
+
+impl IsGpuByteHash for Keccak256 {
+    const BYTE_HASH: ByteHash = ByteHash::Keccak256;
+}
+
+impl IsGpuByteHash for Blake2s256 {
+    const BYTE_HASH: ByteHash = ByteHash::Blake2s256;
+}
@@ -1,5 +1,7 @@
+pub mod byte_hash;
 pub mod hash_to_field;
 pub mod monolith;
 pub mod pedersen;
 pub mod poseidon;
+pub mod poseidon2;
 pub mod sha3;
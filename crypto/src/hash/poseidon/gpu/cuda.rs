@@ -0,0 +1,292 @@
+use super::IsGpuPoseidon;
+use crate::hash::poseidon::starknet::PoseidonCairoStark252;
+use alloc::{format, string::String, vec::Vec};
+use core::marker::PhantomData;
+use cudarc::{
+    driver::{CudaFunction, LaunchAsync, LaunchConfig},
+    nvrtc::safe::Ptx,
+};
+use lambdaworks_gpu::cuda::abstractions::errors::CudaError;
+use lambdaworks_math::{
+    field::{
+        element::FieldElement, fields::fft_friendly::stark_252_prime_field::Stark252PrimeField,
+    },
+    gpu::cuda::memory::{DeviceMemoryPool, PooledSlice},
+    unsigned_integer::element::UnsignedInteger,
+};
+
+type FE = FieldElement<Stark252PrimeField>;
+
+const STARK256_PTX: &str =
+    include_str!("../../../../../math/src/gpu/cuda/shaders/field/stark256.ptx");
+const WARP_SIZE: usize = 32; // the implementation will spawn threadblocks of this size.
+const LIMBS: usize = 4;
+
+/// Structure for abstracting basic calls to a CUDA device and saving the state. Used for
+/// computing batches of hashes with `P`, such as [`PoseidonCairoStark252`], and the Merkle trees
+/// built with them.
+///
+/// The round constants are copied to the device once, when the state is created.
+pub struct CudaPoseidonState<P: IsGpuPoseidon = PoseidonCairoStark252> {
+    pool: DeviceMemoryPool,
+    round_constants: PooledSlice<u64>,
+    one: PooledSlice<u64>,
+    module_name: String,
+    phantom: PhantomData<P>,
+}
+
+impl<P: IsGpuPoseidon> CudaPoseidonState<P> {
+    /// Creates a new CUDA state with the first GPU, which takes its buffers from
+    /// [`DeviceMemoryPool::global`], like the FFTs of the LDE.
+    pub fn new() -> Result<Self, CudaError> {
+        Self::with_pool(DeviceMemoryPool::global()?)
+    }
+
+    /// Creates a new CUDA state with the device of `pool`, which takes its buffers from it.
+    pub fn with_pool(pool: DeviceMemoryPool) -> Result<Self, CudaError> {
+        // Load PTX libraries, once per device
+        let module_name = format!("{}_stark252", P::NAME);
+        if pool
+            .device()
+            .get_func(&module_name, P::KERNELS[0])
+            .is_none()
+        {
+            pool.device()
+                .load_ptx(Ptx::from_src(STARK256_PTX), &module_name, &P::KERNELS)
+                .map_err(|err| CudaError::PtxError(err.to_string()))?;
+        }
+
+        let round_constants = htod(&pool, P::round_constants())?;
+        let one = htod(&pool, &[FE::one()])?;
+
+        Ok(Self {
+            pool,
+            round_constants,
+            one,
+            module_name,
+            phantom: PhantomData,
+        })
+    }
+
+    fn get_function(&self, func_name: &str) -> Result<CudaFunction, CudaError> {
+        self.pool
+            .device()
+            .get_func(&self.module_name, func_name)
+            .ok_or_else(|| CudaError::FunctionError(func_name.to_string()))
+    }
+
+    /// Returns the hash of each of the `inputs` with [`IsGpuPoseidon::hash_element`].
+    pub fn hash_single(&self, inputs: &[FE]) -> Result<Vec<FE>, CudaError> {
+        if inputs.is_empty() {
+            return Ok(Vec::new());
+        }
+        let function = self.get_function(P::KERNELS[0])?;
+        let input = htod(&self.pool, inputs)?;
+        let mut output = self.alloc(inputs.len())?;
+
+        unsafe {
+            function.launch(
+                launch_config(inputs.len()),
+                (
+                    &*input,
+                    &mut *output,
+                    &*self.round_constants,
+                    &*self.one,
+                    inputs.len() as u32,
+                ),
+            )
+        }
+        .map_err(|err| CudaError::Launch(err.to_string()))?;
+
+        dtoh(&self.pool, &output)
+    }
+
+    /// Returns the hash of each of the `rows` with [`IsGpuPoseidon::hash_row`].
+    /// All the rows must have the same length.
+    pub fn hash_rows(&self, rows: &[Vec<FE>]) -> Result<Vec<FE>, CudaError> {
+        let Some(row_len) = rows.first().map(Vec::len) else {
+            return Ok(Vec::new());
+        };
+        if let Some(row) = rows.iter().find(|row| row.len() != row_len) {
+            return Err(CudaError::LengthMismatch(row_len, row.len()));
+        }
+        let function = self.get_function(P::KERNELS[1])?;
+        let mut flat_rows: Vec<FE> = rows.iter().flatten().cloned().collect();
+        if flat_rows.is_empty() {
+            // Device buffers can't be empty
+            flat_rows.push(FE::zero());
+        }
+        let input = htod(&self.pool, &flat_rows)?;
+        let mut output = self.alloc(rows.len())?;
+
+        unsafe {
+            function.launch(
+                launch_config(rows.len()),
+                (
+                    &*input,
+                    &mut *output,
+                    &*self.round_constants,
+                    &*self.one,
+                    row_len as u32,
+                    rows.len() as u32,
+                ),
+            )
+        }
+        .map_err(|err| CudaError::Launch(err.to_string()))?;
+
+        dtoh(&self.pool, &output)
+    }
+
+    /// Computes the inner nodes of a Merkle tree whose parents are built with
+    /// [`IsGpuPoseidon::hash_pair`]. `nodes` holds the tree in
+    /// breadth-first order, with its `leaves_len` leaves at the end, as in
+    /// [`MerkleTree`](crate::merkle_tree::merkle::MerkleTree). `leaves_len` must be a power of two.
+    ///
+    /// The whole tree stays in the device until all its levels are built.
+    pub fn build_merkle_tree(&self, nodes: &mut [FE], leaves_len: usize) -> Result<(), CudaError> {
+        if nodes.len() != 2 * leaves_len - 1 {
+            return Err(CudaError::LengthMismatch(2 * leaves_len - 1, nodes.len()));
+        }
+        if leaves_len == 1 {
+            return Ok(());
+        }
+        let function = self.get_function(P::KERNELS[2])?;
+        let mut device_nodes = htod(&self.pool, nodes)?;
+
+        // Levels are launched on the same stream, so each one sees the nodes of the previous one
+        let mut level_begin = leaves_len - 1;
+        while level_begin > 0 {
+            let parents_begin = level_begin / 2;
+            let count = level_begin - parents_begin;
+            unsafe {
+                function.clone().launch(
+                    launch_config(count),
+                    (
+                        &mut *device_nodes,
+                        &*self.round_constants,
+                        &*self.one,
+                        parents_begin as u32,
+                        count as u32,
+                    ),
+                )
+            }
+            .map_err(|err| CudaError::Launch(err.to_string()))?;
+            level_begin = parents_begin;
+        }
+
+        let built_nodes = dtoh(&self.pool, &device_nodes)?;
+        nodes[..leaves_len - 1].clone_from_slice(&built_nodes[..leaves_len - 1]);
+        Ok(())
+    }
+
+    fn alloc(&self, len: usize) -> Result<PooledSlice<u64>, CudaError> {
+        self.pool.alloc(len * LIMBS)
+    }
+}
+
+fn launch_config(threads: usize) -> LaunchConfig {
+    let block_size = WARP_SIZE;
+    let block_count = threads.div_ceil(block_size);
+
+    LaunchConfig {
+        grid_dim: (block_count as u32, 1, 1), // in blocks
+        block_dim: (block_size as u32, 1, 1),
+        shared_mem_bytes: 0,
+    }
+}
+
+/// Copies `elements` to a buffer of `pool`, as the limbs of their Montgomery representation.
+fn htod(pool: &DeviceMemoryPool, elements: &[FE]) -> Result<PooledSlice<u64>, CudaError> {
+    let limbs: Vec<u64> = elements
+        .iter()
+        .flat_map(|element| element.value().limbs)
+        .collect();
+    pool.upload(&limbs)
+}
+
+fn dtoh(pool: &DeviceMemoryPool, buffer: &PooledSlice<u64>) -> Result<Vec<FE>, CudaError> {
+    let limbs = pool
+        .device()
+        .dtoh_sync_copy(&**buffer)
+        .map_err(|err| CudaError::RetrieveMemory(err.to_string()))?;
+    Ok(limbs
+        .chunks_exact(LIMBS)
+        .map(|limbs| {
+            let mut value = [0; LIMBS];
+            value.copy_from_slice(limbs);
+            FE::from_raw(UnsignedInteger::from_limbs(value))
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hash::poseidon2::Poseidon2Stark252;
+
+    fn inputs(len: u64) -> Vec<FE> {
+        (0..len).map(|i| FE::from(i * i + 5).pow(7_u64)).collect()
+    }
+
+    fn assert_hash_single_matches_cpu<P: IsGpuPoseidon>() {
+        let state = CudaPoseidonState::<P>::new().unwrap();
+        let inputs = inputs(100);
+        let expected: Vec<_> = inputs.iter().map(P::hash_element).collect();
+
+        assert_eq!(state.hash_single(&inputs).unwrap(), expected);
+    }
+
+    fn assert_hash_rows_matches_cpu<P: IsGpuPoseidon>() {
+        let state = CudaPoseidonState::<P>::new().unwrap();
+        for row_len in 1..6 {
+            let rows: Vec<Vec<FE>> = inputs(40).chunks(row_len).map(<[FE]>::to_vec).collect();
+            let rows = &rows[..40 / row_len];
+            let expected: Vec<_> = rows.iter().map(|row| P::hash_row(row)).collect();
+
+            assert_eq!(state.hash_rows(rows).unwrap(), expected);
+        }
+    }
+
+    fn assert_merkle_tree_matches_cpu<P: IsGpuPoseidon>() {
+        let state = CudaPoseidonState::<P>::new().unwrap();
+        let leaves_len = 64;
+        let mut nodes = vec![FE::zero(); leaves_len - 1];
+        nodes.extend(inputs(leaves_len as u64));
+        let mut expected = nodes.clone();
+        for parent in (0..leaves_len - 1).rev() {
+            expected[parent] = P::hash_pair(&expected[2 * parent + 1], &expected[2 * parent + 2]);
+        }
+
+        state.build_merkle_tree(&mut nodes, leaves_len).unwrap();
+        assert_eq!(nodes, expected);
+    }
+
+    #[test]
+    fn cuda_hash_single_matches_cpu() {
+        assert_hash_single_matches_cpu::<PoseidonCairoStark252>();
+        assert_hash_single_matches_cpu::<Poseidon2Stark252>();
+    }
+
+    #[test]
+    fn cuda_hash_rows_matches_cpu() {
+        assert_hash_rows_matches_cpu::<PoseidonCairoStark252>();
+        assert_hash_rows_matches_cpu::<Poseidon2Stark252>();
+    }
+
+    #[test]
+    fn cuda_hash_rows_of_different_lengths_should_fail() {
+        let state = CudaPoseidonState::<PoseidonCairoStark252>::new().unwrap();
+        let rows = vec![inputs(3), inputs(2)];
+
+        assert!(matches!(
+            state.hash_rows(&rows),
+            Err(CudaError::LengthMismatch(3, 2))
+        ));
+    }
+
+    #[test]
+    fn cuda_merkle_tree_matches_cpu() {
+        assert_merkle_tree_matches_cpu::<PoseidonCairoStark252>();
+        assert_merkle_tree_matches_cpu::<Poseidon2Stark252>();
+    }
+}
@@ -0,0 +1,274 @@
+use super::IsGpuPoseidon;
+use crate::hash::poseidon::starknet::PoseidonCairoStark252;
+use alloc::{format, vec::Vec};
+use lambdaworks_gpu::metal::abstractions::{errors::MetalError, state::MetalState};
+use lambdaworks_math::{
+    field::{
+        element::FieldElement, fields::fft_friendly::stark_252_prime_field::Stark252PrimeField,
+    },
+    unsigned_integer::element::UnsignedInteger,
+};
+use metal::{Buffer, ComputeCommandEncoderRef, ComputePipelineState, MTLSize};
+
+use core::{marker::PhantomData, mem};
+
+type FE = FieldElement<Stark252PrimeField>;
+
+/// Computes batches of hashes with `P`, such as [`PoseidonCairoStark252`], and the Merkle trees
+/// built with them, in Metal.
+///
+/// The round constants are copied to the device once, when the state is created.
+pub struct MetalPoseidonState<P: IsGpuPoseidon = PoseidonCairoStark252> {
+    state: MetalState,
+    round_constants: Buffer,
+    one: Buffer,
+    phantom: PhantomData<P>,
+}
+
+impl<P: IsGpuPoseidon> MetalPoseidonState<P> {
+    /// Creates a new state with an optional `device`. If `None` is passed then it will use the
+    /// system's default.
+    pub fn new(device: Option<metal::Device>) -> Result<Self, MetalError> {
+        let state = MetalState::new(device)?;
+        let round_constants = state.alloc_buffer_data(P::round_constants());
+        let one = state.alloc_buffer_data(&[FE::one()]);
+
+        Ok(Self {
+            state,
+            round_constants,
+            one,
+            phantom: PhantomData,
+        })
+    }
+
+    /// Returns the hash of each of the `inputs` with [`IsGpuPoseidon::hash_element`].
+    pub fn hash_single(&self, inputs: &[FE]) -> Result<Vec<FE>, MetalError> {
+        if inputs.is_empty() {
+            return Ok(Vec::new());
+        }
+        let pipeline = self.setup_pipeline("hash_single")?;
+        let input_buffer = self.state.alloc_buffer_data(inputs);
+        let output_buffer = self.state.alloc_buffer::<FE>(inputs.len());
+
+        self.run(|command_encoder| {
+            dispatch(
+                command_encoder,
+                &pipeline,
+                &[
+                    &input_buffer,
+                    &output_buffer,
+                    &self.round_constants,
+                    &self.one,
+                ],
+                &[],
+                inputs.len(),
+            );
+        });
+
+        Ok(retrieve_elements(&output_buffer))
+    }
+
+    /// Returns the hash of each of the `rows` with [`IsGpuPoseidon::hash_row`].
+    /// All the rows must have the same length.
+    pub fn hash_rows(&self, rows: &[Vec<FE>]) -> Result<Vec<FE>, MetalError> {
+        let Some(row_len) = rows.first().map(Vec::len) else {
+            return Ok(Vec::new());
+        };
+        if let Some(row) = rows.iter().find(|row| row.len() != row_len) {
+            return Err(MetalError::LengthMismatch(row_len, row.len()));
+        }
+        let pipeline = self.setup_pipeline("hash_rows")?;
+        let mut flat_rows: Vec<FE> = rows.iter().flatten().cloned().collect();
+        if flat_rows.is_empty() {
+            // Metal buffers can't be empty
+            flat_rows.push(FE::zero());
+        }
+        let input_buffer = self.state.alloc_buffer_data(&flat_rows);
+        let output_buffer = self.state.alloc_buffer::<FE>(rows.len());
+
+        self.run(|command_encoder| {
+            dispatch(
+                command_encoder,
+                &pipeline,
+                &[
+                    &input_buffer,
+                    &output_buffer,
+                    &self.round_constants,
+                    &self.one,
+                ],
+                &[row_len as u32],
+                rows.len(),
+            );
+        });
+
+        Ok(retrieve_elements(&output_buffer))
+    }
+
+    /// Computes the inner nodes of a Merkle tree whose parents are built with
+    /// [`IsGpuPoseidon::hash_pair`]. `nodes` holds the tree in
+    /// breadth-first order, with its `leaves_len` leaves at the end, as in
+    /// [`MerkleTree`](crate::merkle_tree::merkle::MerkleTree). `leaves_len` must be a power of two.
+    ///
+    /// All the levels are encoded in a single command buffer.
+    pub fn build_merkle_tree(&self, nodes: &mut [FE], leaves_len: usize) -> Result<(), MetalError> {
+        if nodes.len() != 2 * leaves_len - 1 {
+            return Err(MetalError::LengthMismatch(2 * leaves_len - 1, nodes.len()));
+        }
+        if leaves_len == 1 {
+            return Ok(());
+        }
+        let pipeline = self.setup_pipeline("merkle_level")?;
+        let nodes_buffer = self.state.alloc_buffer_data(nodes);
+
+        self.run(|command_encoder| {
+            // Dispatches of the same encoder run one after the other, so each level sees the
+            // nodes of the previous one.
+            let mut level_begin = leaves_len - 1;
+            while level_begin > 0 {
+                let parents_begin = level_begin / 2;
+                dispatch(
+                    command_encoder,
+                    &pipeline,
+                    &[&nodes_buffer, &self.round_constants, &self.one],
+                    &[parents_begin as u32],
+                    level_begin - parents_begin,
+                );
+                level_begin = parents_begin;
+            }
+        });
+
+        let built_nodes = retrieve_elements(&nodes_buffer);
+        nodes[..leaves_len - 1].clone_from_slice(&built_nodes[..leaves_len - 1]);
+        Ok(())
+    }
+
+    /// Returns the pipeline of the `kernel` of `P` over the Stark252 field.
+    fn setup_pipeline(&self, kernel: &str) -> Result<ComputePipelineState, MetalError> {
+        self.state
+            .setup_pipeline(&format!("{}_{kernel}_stark256", P::NAME))
+    }
+
+    /// Encodes the dispatches of `encode` in a command buffer and waits for it to complete.
+    fn run(&self, encode: impl FnOnce(&ComputeCommandEncoderRef)) {
+        objc::rc::autoreleasepool(|| {
+            let command_buffer = self.state.queue.new_command_buffer();
+            let command_encoder = command_buffer.new_compute_command_encoder();
+            encode(command_encoder);
+            command_encoder.end_encoding();
+
+            command_buffer.commit();
+            command_buffer.wait_until_completed();
+        });
+    }
+}
+
+/// Queues `threads` threads of `pipeline`, with `buffers` bound from index 0 and the
+/// `constants` after them.
+fn dispatch(
+    command_encoder: &ComputeCommandEncoderRef,
+    pipeline: &ComputePipelineState,
+    buffers: &[&Buffer],
+    constants: &[u32],
+    threads: usize,
+) {
+    command_encoder.set_compute_pipeline_state(pipeline);
+    for (i, buffer) in buffers.iter().enumerate() {
+        command_encoder.set_buffer(i as u64, Some(buffer), 0);
+    }
+    for (i, constant) in constants.iter().enumerate() {
+        command_encoder.set_bytes(
+            (buffers.len() + i) as u64,
+            mem::size_of_val(constant) as u64,
+            void_ptr(constant),
+        );
+    }
+
+    let grid_size = MTLSize::new(threads as u64, 1, 1);
+    let threadgroup_size = MTLSize::new(pipeline.thread_execution_width(), 1, 1);
+
+    // WARN: Device should support non-uniform threadgroups (Metal3 and Apple4 or latter).
+    command_encoder.dispatch_threads(grid_size, threadgroup_size);
+}
+
+fn void_ptr<T>(v: &T) -> *const core::ffi::c_void {
+    v as *const T as *const core::ffi::c_void
+}
+
+fn retrieve_elements(buffer: &Buffer) -> Vec<FE> {
+    MetalState::retrieve_contents::<UnsignedInteger<4>>(buffer)
+        .into_iter()
+        .map(FE::from_raw)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hash::poseidon2::Poseidon2Stark252;
+
+    fn inputs(len: u64) -> Vec<FE> {
+        (0..len).map(|i| FE::from(i * i + 5).pow(7_u64)).collect()
+    }
+
+    fn assert_hash_single_matches_cpu<P: IsGpuPoseidon>() {
+        let state = MetalPoseidonState::<P>::new(None).unwrap();
+        let inputs = inputs(100);
+        let expected: Vec<_> = inputs.iter().map(P::hash_element).collect();
+
+        assert_eq!(state.hash_single(&inputs).unwrap(), expected);
+    }
+
+    fn assert_hash_rows_matches_cpu<P: IsGpuPoseidon>() {
+        let state = MetalPoseidonState::<P>::new(None).unwrap();
+        for row_len in 1..6 {
+            let rows: Vec<Vec<FE>> = inputs(40).chunks(row_len).map(<[FE]>::to_vec).collect();
+            let rows = &rows[..40 / row_len];
+            let expected: Vec<_> = rows.iter().map(|row| P::hash_row(row)).collect();
+
+            assert_eq!(state.hash_rows(rows).unwrap(), expected);
+        }
+    }
+
+    fn assert_merkle_tree_matches_cpu<P: IsGpuPoseidon>() {
+        let state = MetalPoseidonState::<P>::new(None).unwrap();
+        let leaves_len = 64;
+        let mut nodes = vec![FE::zero(); leaves_len - 1];
+        nodes.extend(inputs(leaves_len as u64));
+        let mut expected = nodes.clone();
+        for parent in (0..leaves_len - 1).rev() {
+            expected[parent] = P::hash_pair(&expected[2 * parent + 1], &expected[2 * parent + 2]);
+        }
+
+        state.build_merkle_tree(&mut nodes, leaves_len).unwrap();
+        assert_eq!(nodes, expected);
+    }
+
+    #[test]
+    fn metal_hash_single_matches_cpu() {
+        assert_hash_single_matches_cpu::<PoseidonCairoStark252>();
+        assert_hash_single_matches_cpu::<Poseidon2Stark252>();
+    }
+
+    #[test]
+    fn metal_hash_rows_matches_cpu() {
+        assert_hash_rows_matches_cpu::<PoseidonCairoStark252>();
+        assert_hash_rows_matches_cpu::<Poseidon2Stark252>();
+    }
+
+    #[test]
+    fn metal_hash_rows_of_different_lengths_should_fail() {
+        let state = MetalPoseidonState::<PoseidonCairoStark252>::new(None).unwrap();
+        let rows = vec![inputs(3), inputs(2)];
+
+        assert!(matches!(
+            state.hash_rows(&rows),
+            Err(MetalError::LengthMismatch(3, 2))
+        ));
+    }
+
+    #[test]
+    fn metal_merkle_tree_matches_cpu() {
+        assert_merkle_tree_matches_cpu::<PoseidonCairoStark252>();
+        assert_merkle_tree_matches_cpu::<Poseidon2Stark252>();
+    }
+}
@@ -0,0 +1,83 @@
+use crate::hash::{
+    poseidon::{parameters::PermutationParameters, starknet::PoseidonCairoStark252, Poseidon},
+    poseidon2::Poseidon2Stark252,
+};
+use lambdaworks_math::field::{
+    element::FieldElement, fields::fft_friendly::stark_252_prime_field::Stark252PrimeField,
+};
+
+#[cfg(feature = "cuda")]
+pub mod cuda;
+#[cfg(feature = "metal")]
+pub mod metal;
+#[cfg(feature = "wgpu")]
+pub mod wgpu;
+
+type FE = FieldElement<Stark252PrimeField>;
+
+/// A hash over the Stark252 field with CUDA and Metal kernels, whose permutation takes three
+/// round constants per full round and one per partial round. The GPU states and the Merkle tree
+/// backends built on them are generic over it, and use its CPU functions as a fallback, so code
+/// that picks the hash can be generic over it with or without the GPU features.
+pub trait IsGpuPoseidon: Clone + Default {
+    /// Name of the permutation. Its kernels are `<name>_hash_single`, `<name>_hash_rows` and
+    /// `<name>_merkle_level`.
+    const NAME: &'static str;
+    /// The kernels, as they are named in the CUDA module.
+    const KERNELS: [&'static str; 3];
+
+    fn round_constants() -> &'static [FE];
+    fn hash_pair(x: &FE, y: &FE) -> FE;
+    fn hash_element(x: &FE) -> FE;
+    fn hash_row(inputs: &[FE]) -> FE;
+}
+
+impl IsGpuPoseidon for PoseidonCairoStark252 {
+    const NAME: &'static str = "poseidon";
+    const KERNELS: [&'static str; 3] = [
+        "poseidon_hash_single",
+        "poseidon_hash_rows",
+        "poseidon_merkle_level",
+    ];
+
+    fn round_constants() -> &'static [FE] {
+        <Self as PermutationParameters>::ROUND_CONSTANTS
+    }
+
+    fn hash_pair(x: &FE, y: &FE) -> FE {
+        <Self as Poseidon>::hash(x, y)
+    }
+
+    fn hash_element(x: &FE) -> FE {
+        <Self as Poseidon>::hash_single(x)
+    }
+
+    fn hash_row(inputs: &[FE]) -> FE {
+        <Self as Poseidon>::hash_many(inputs)
+    }
+}
+
+impl IsGpuPoseidon for Poseidon2Stark252 {
+    const NAME: &'static str = "poseidon2";
+    const KERNELS: [&'static str; 3] = [
+        "poseidon2_hash_single",
+        "poseidon2_hash_rows",
+        "poseidon2_merkle_level",
+    ];
+
+    fn round_constants() -> &'static [FE] {
+        Self::ROUND_CONSTANTS
+    }
+
+    fn hash_pair(x: &FE, y: &FE) -> FE {
+        Self::hash(x, y)
+    }
+
+    fn hash_element(x: &FE) -> FE {
+        Self::hash_single(x)
+    }
+
+    fn hash_row(inputs: &[FE]) -> FE {
+        Self::hash_many(inputs)
+    }
+}
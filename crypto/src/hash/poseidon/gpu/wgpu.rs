@@ -0,0 +1,242 @@
+use crate::hash::poseidon::{parameters::PermutationParameters, starknet::PoseidonCairoStark252};
+use alloc::vec::Vec;
+use lambdaworks_gpu::wgpu::abstractions::{errors::WgpuError, state::WgpuState};
+use lambdaworks_math::{
+    field::{
+        element::FieldElement, fields::fft_friendly::stark_252_prime_field::Stark252PrimeField,
+    },
+    gpu::wgpu::POSEIDON_STARK256_SHADER,
+    unsigned_integer::element::UnsignedInteger,
+};
+use wgpu::{Buffer, ShaderModule};
+
+type FE = FieldElement<Stark252PrimeField>;
+
+/// Computes batches of [`PoseidonCairoStark252`] hashes, and the Merkle trees built with them,
+/// in WebGPU.
+///
+/// The shader is compiled and the round constants are copied to the device once, when the state
+/// is created.
+pub struct WgpuPoseidonState {
+    state: WgpuState,
+    module: ShaderModule,
+    round_constants: Buffer,
+    one: Buffer,
+}
+
+impl WgpuPoseidonState {
+    /// Creates a new state with the system's default adapter.
+    pub fn new() -> Result<Self, WgpuError> {
+        let state = WgpuState::new()?;
+        let module = state.load_shader(POSEIDON_STARK256_SHADER)?;
+        let round_constants = state.alloc_buffer_data(PoseidonCairoStark252::ROUND_CONSTANTS);
+        let one = state.alloc_buffer_data(&[FE::one()]);
+
+        Ok(Self {
+            state,
+            module,
+            round_constants,
+            one,
+        })
+    }
+
+    /// Returns the hash of each of the `inputs` with [`Poseidon::hash_single`](crate::hash::poseidon::Poseidon::hash_single).
+    pub fn hash_single(&self, inputs: &[FE]) -> Result<Vec<FE>, WgpuError> {
+        if inputs.is_empty() {
+            return Ok(Vec::new());
+        }
+        let pipeline = self
+            .state
+            .setup_pipeline(&self.module, "poseidon_hash_single")?;
+        let input_buffer = self.state.alloc_buffer_data(inputs);
+        let output_buffer = self.state.alloc_buffer::<FE>(inputs.len());
+        let params = self.state.alloc_buffer_data(&[inputs.len() as u32, 0]);
+
+        let mut encoder = self.state.setup_command();
+        self.state.dispatch(
+            &mut encoder,
+            &pipeline,
+            &[
+                (0, &input_buffer),
+                (1, &output_buffer),
+                (2, &self.round_constants),
+                (3, &self.one),
+                (4, &params),
+            ],
+            inputs.len(),
+        );
+        self.state.submit(encoder);
+
+        self.retrieve_elements(&output_buffer)
+    }
+
+    /// Returns the hash of each of the `rows` with [`Poseidon::hash_many`](crate::hash::poseidon::Poseidon::hash_many).
+    /// All the rows must have the same length.
+    pub fn hash_rows(&self, rows: &[Vec<FE>]) -> Result<Vec<FE>, WgpuError> {
+        let Some(row_len) = rows.first().map(Vec::len) else {
+            return Ok(Vec::new());
+        };
+        if let Some(row) = rows.iter().find(|row| row.len() != row_len) {
+            return Err(WgpuError::LengthMismatch(row_len, row.len()));
+        }
+        let pipeline = self
+            .state
+            .setup_pipeline(&self.module, "poseidon_hash_rows")?;
+        let mut flat_rows: Vec<FE> = rows.iter().flatten().cloned().collect();
+        if flat_rows.is_empty() {
+            // Bound buffers can't be empty
+            flat_rows.push(FE::zero());
+        }
+        let input_buffer = self.state.alloc_buffer_data(&flat_rows);
+        let output_buffer = self.state.alloc_buffer::<FE>(rows.len());
+        let params = self
+            .state
+            .alloc_buffer_data(&[rows.len() as u32, row_len as u32]);
+
+        let mut encoder = self.state.setup_command();
+        self.state.dispatch(
+            &mut encoder,
+            &pipeline,
+            &[
+                (0, &input_buffer),
+                (1, &output_buffer),
+                (2, &self.round_constants),
+                (3, &self.one),
+                (4, &params),
+            ],
+            rows.len(),
+        );
+        self.state.submit(encoder);
+
+        self.retrieve_elements(&output_buffer)
+    }
+
+    /// Computes the inner nodes of a Merkle tree whose parents are built with
+    /// [`Poseidon::hash`](crate::hash::poseidon::Poseidon::hash). `nodes` holds the tree in
+    /// breadth-first order, with its `leaves_len` leaves at the end, as in
+    /// [`MerkleTree`](crate::merkle_tree::merkle::MerkleTree). `leaves_len` must be a power of two.
+    ///
+    /// All the levels are recorded in a single command encoder.
+    pub fn build_merkle_tree(&self, nodes: &mut [FE], leaves_len: usize) -> Result<(), WgpuError> {
+        if nodes.len() != 2 * leaves_len - 1 {
+            return Err(WgpuError::LengthMismatch(2 * leaves_len - 1, nodes.len()));
+        }
+        if leaves_len == 1 {
+            return Ok(());
+        }
+        let pipeline = self
+            .state
+            .setup_pipeline(&self.module, "poseidon_merkle_level")?;
+        let nodes_buffer = self.state.alloc_buffer_data(nodes);
+
+        let mut levels = Vec::new();
+        let mut level_begin = leaves_len - 1;
+        while level_begin > 0 {
+            let parents_begin = level_begin / 2;
+            levels.push((parents_begin, level_begin - parents_begin));
+            level_begin = parents_begin;
+        }
+        let level_params: Vec<Buffer> = levels
+            .iter()
+            .map(|&(parents_begin, count)| {
+                self.state
+                    .alloc_buffer_data(&[count as u32, parents_begin as u32])
+            })
+            .collect();
+
+        // Dispatches of the same encoder run one after the other, so each level sees the nodes
+        // of the previous one.
+        let mut encoder = self.state.setup_command();
+        for (&(_, count), params) in levels.iter().zip(&level_params) {
+            self.state.dispatch(
+                &mut encoder,
+                &pipeline,
+                &[
+                    (0, &nodes_buffer),
+                    (2, &self.round_constants),
+                    (3, &self.one),
+                    (4, params),
+                ],
+                count,
+            );
+        }
+        self.state.submit(encoder);
+
+        let built_nodes = self.retrieve_elements(&nodes_buffer)?;
+        nodes[..leaves_len - 1].clone_from_slice(&built_nodes[..leaves_len - 1]);
+        Ok(())
+    }
+
+    fn retrieve_elements(&self, buffer: &Buffer) -> Result<Vec<FE>, WgpuError> {
+        Ok(self
+            .state
+            .retrieve_contents::<UnsignedInteger<4>>(buffer)?
+            .into_iter()
+            .map(FE::from_raw)
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hash::poseidon::Poseidon;
+
+    fn inputs(len: u64) -> Vec<FE> {
+        (0..len).map(|i| FE::from(i * i + 5).pow(7_u64)).collect()
+    }
+
+    #[test]
+    fn wgpu_hash_single_matches_cpu() {
+        let state = WgpuPoseidonState::new().unwrap();
+        let inputs = inputs(100);
+        let expected: Vec<_> = inputs
+            .iter()
+            .map(PoseidonCairoStark252::hash_single)
+            .collect();
+
+        assert_eq!(state.hash_single(&inputs).unwrap(), expected);
+    }
+
+    #[test]
+    fn wgpu_hash_rows_matches_cpu() {
+        let state = WgpuPoseidonState::new().unwrap();
+        for row_len in 1..6 {
+            let rows: Vec<Vec<FE>> = inputs(40).chunks(row_len).map(<[FE]>::to_vec).collect();
+            let rows = &rows[..40 / row_len];
+            let expected: Vec<_> = rows
+                .iter()
+                .map(|row| PoseidonCairoStark252::hash_many(row))
+                .collect();
+
+            assert_eq!(state.hash_rows(rows).unwrap(), expected);
+        }
+    }
+
+    #[test]
+    fn wgpu_hash_rows_of_different_lengths_should_fail() {
+        let state = WgpuPoseidonState::new().unwrap();
+        let rows = vec![inputs(3), inputs(2)];
+
+        assert!(matches!(
+            state.hash_rows(&rows),
+            Err(WgpuError::LengthMismatch(3, 2))
+        ));
+    }
+
+    #[test]
+    fn wgpu_merkle_tree_matches_cpu() {
+        let state = WgpuPoseidonState::new().unwrap();
+        let leaves_len = 64;
+        let mut nodes = vec![FE::zero(); leaves_len - 1];
+        nodes.extend(inputs(leaves_len as u64));
+        let mut expected = nodes.clone();
+        for parent in (0..leaves_len - 1).rev() {
+            expected[parent] =
+                PoseidonCairoStark252::hash(&expected[2 * parent + 1], &expected[2 * parent + 2]);
+        }
+
+        state.build_merkle_tree(&mut nodes, leaves_len).unwrap();
+        assert_eq!(nodes, expected);
+    }
+}
@@ -1,6 +1,7 @@
 use alloc::{borrow::ToOwned, vec::Vec};
 use lambdaworks_math::field::element::FieldElement as FE;
 
+pub mod gpu;
 pub mod parameters;
 pub mod starknet;
 
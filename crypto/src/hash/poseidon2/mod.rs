@@ -0,0 +1,216 @@
+//! [Poseidon2](https://eprint.iacr.org/2023/323) over the Stark252 field.
+//!
+//! The state has three elements and the S-box is `x³`, as in
+//! [`PoseidonCairoStark252`](crate::hash::poseidon::starknet::PoseidonCairoStark252), with 8 full
+//! rounds and 83 partial rounds. The external linear layer is the matrix `circ(2, 1, 1)`, which
+//! is also applied once before the first round, and the internal one is `J + diag(1, 1, 2)`,
+//! where `J` is the matrix of ones. Partial rounds add their constant to, and apply the S-box
+//! on, the first element of the state.
+//!
+//! The round constants are drawn from the Grain LFSR of the Poseidon reference implementation,
+//! as in the instances of the Poseidon2 reference implementation. There is no reference instance
+//! over this field, so the test vectors are the ones of this instance.
+//!
+//! [`Poseidon2Stark252::hash`], [`Poseidon2Stark252::hash_single`] and
+//! [`Poseidon2Stark252::hash_many`] pad their inputs and separate domains like the ones of
+//! [`Poseidon`](crate::hash::poseidon::Poseidon) do.
+use alloc::vec::Vec;
+use lambdaworks_math::field::{
+    element::FieldElement, fields::fft_friendly::stark_252_prime_field::Stark252PrimeField,
+};
+
+mod parameters;
+
+type FE = FieldElement<Stark252PrimeField>;
+
+#[derive(Clone, Default)]
+pub struct Poseidon2Stark252;
+
+impl Poseidon2Stark252 {
+    pub const N_FULL_ROUNDS: usize = 8;
+    pub const N_PARTIAL_ROUNDS: usize = 83;
+    pub const RATE: usize = 2;
+    /// Three constants per full round and one per partial round, in the order they are used.
+    pub const ROUND_CONSTANTS: &'static [FE] = &parameters::ROUND_CONSTANTS;
+
+    /// Multiplies the state by `circ(2, 1, 1)`.
+    #[inline(always)]
+    fn external_mix(state: &mut [FE; 3]) {
+        let t = &state[0] + &state[1] + &state[2];
+        for value in state.iter_mut() {
+            *value = &*value + &t;
+        }
+    }
+
+    /// Multiplies the state by `J + diag(1, 1, 2)`.
+    #[inline(always)]
+    fn internal_mix(state: &mut [FE; 3]) {
+        let t = &state[0] + &state[1] + &state[2];
+        state[0] = &state[0] + &t;
+        state[1] = &state[1] + &t;
+        state[2] = state[2].double() + t;
+    }
+
+    #[inline]
+    fn full_round(state: &mut [FE; 3], constants: &[FE]) {
+        for (value, constant) in state.iter_mut().zip(constants) {
+            let x = &*value + constant;
+            *value = x.square() * x;
+        }
+        Self::external_mix(state);
+    }
+
+    pub fn permutation(state: &mut [FE; 3]) {
+        let (first_full, rest) = Self::ROUND_CONSTANTS.split_at(3 * Self::N_FULL_ROUNDS / 2);
+        let (partial, last_full) = rest.split_at(Self::N_PARTIAL_ROUNDS);
+
+        Self::external_mix(state);
+        for constants in first_full.chunks(3) {
+            Self::full_round(state, constants);
+        }
+        for constant in partial {
+            let x = &state[0] + constant;
+            state[0] = x.square() * x;
+            Self::internal_mix(state);
+        }
+        for constants in last_full.chunks(3) {
+            Self::full_round(state, constants);
+        }
+    }
+
+    pub fn hash(x: &FE, y: &FE) -> FE {
+        let mut state = [*x, *y, FE::from(2)];
+        Self::permutation(&mut state);
+        state[0]
+    }
+
+    pub fn hash_single(x: &FE) -> FE {
+        let mut state = [*x, FE::zero(), FE::one()];
+        Self::permutation(&mut state);
+        state[0]
+    }
+
+    /// Sponge with rate 2 over `inputs` padded with a 1 and then zeros.
+    pub fn hash_many(inputs: &[FE]) -> FE {
+        let mut values: Vec<FE> = inputs.to_vec();
+        values.push(FE::one());
+        values.resize(values.len().div_ceil(Self::RATE) * Self::RATE, FE::zero());
+
+        let mut state = [FE::zero(), FE::zero(), FE::zero()];
+        for block in values.chunks(Self::RATE) {
+            state[0] = &state[0] + &block[0];
+            state[1] = &state[1] + &block[1];
+            Self::permutation(&mut state);
+        }
+        state[0]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use lambdaworks_math::{
+        field::traits::IsPrimeField, unsigned_integer::element::UnsignedInteger,
+    };
+
+    fn fe(hex: &str) -> FE {
+        FE::from_hex(hex).unwrap()
+    }
+
+    /// The Grain LFSR of the Poseidon reference implementation, for a prime field of `n` bits
+    /// and the S-box `x^α`.
+    struct Grain(Vec<bool>);
+
+    impl Grain {
+        fn new(n: u64, t: u64, full_rounds: u64, partial_rounds: u64) -> Self {
+            let mut bits = Vec::new();
+            for (value, len) in [
+                (1, 2),
+                (0, 4),
+                (n, 12),
+                (t, 12),
+                (full_rounds, 10),
+                (partial_rounds, 10),
+            ] {
+                bits.extend((0..len).rev().map(|i| (value >> i) & 1 == 1));
+            }
+            bits.extend([true; 30]);
+            let mut grain = Grain(bits);
+            for _ in 0..160 {
+                grain.step();
+            }
+            grain
+        }
+
+        fn step(&mut self) -> bool {
+            let b = &self.0;
+            let bit = b[62] ^ b[51] ^ b[38] ^ b[23] ^ b[13] ^ b[0];
+            self.0.remove(0);
+            self.0.push(bit);
+            bit
+        }
+
+        fn next_bit(&mut self) -> bool {
+            loop {
+                let (keep, bit) = (self.step(), self.step());
+                if keep {
+                    return bit;
+                }
+            }
+        }
+
+        fn next_element(&mut self) -> FE {
+            loop {
+                let value = (0..252).fold(UnsignedInteger::<4>::from_u64(0), |acc, _| {
+                    (acc << 1) + UnsignedInteger::from_u64(self.next_bit() as u64)
+                });
+                if value <= Stark252PrimeField::modulus_minus_one() {
+                    return FE::new(value);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn round_constants_are_the_ones_of_the_grain_lfsr() {
+        let mut grain = Grain::new(252, 3, 8, 83);
+        let constants: Vec<FE> = (0..107).map(|_| grain.next_element()).collect();
+        assert_eq!(constants, Poseidon2Stark252::ROUND_CONSTANTS);
+    }
+
+    #[test]
+    fn test_permutation() {
+        let mut state = [FE::from(0), FE::from(1), FE::from(2)];
+        Poseidon2Stark252::permutation(&mut state);
+
+        assert_eq!(
+            state,
+            [
+                fe("0x5d436af7030617ccc8ec0a81239d82559d567cb13c7ea0cf7f87c0c69a7d755"),
+                fe("0x6c046556ccc26b926140a0986f4f30f9177076b7b383f91fafd578ae5232a9b"),
+                fe("0x75175a1bc06b8d204eb36827b7b8a7845cc3e200fa4ff4158b58fb2772e6952"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_hashes() {
+        assert_eq!(
+            Poseidon2Stark252::hash(&fe("0x123456"), &fe("0x789101")),
+            fe("0x36b81f0cd3951ed92110732221341e5d5072a17803e84b276fd48fa515ef274")
+        );
+        assert_eq!(
+            Poseidon2Stark252::hash_single(&FE::from(9)),
+            fe("0x77319726c8375de5e44aa49ff784f85ca12d223c1e325898ee3b29f9c0f6544")
+        );
+        let inputs: Vec<FE> = (1..=6).map(FE::from).collect();
+        assert_eq!(
+            Poseidon2Stark252::hash_many(&inputs),
+            fe("0x1013164e990bb3dadb27a049b42eacd2bd07bae30955fed3935072fc3a2058a")
+        );
+        assert_eq!(
+            Poseidon2Stark252::hash_many(&inputs[..1]),
+            fe("0x744d2df049e6b807b02feedc0ab8a2974abeae039ebb70cd92014fa8ed5797e")
+        );
+    }
+}
@@ -0,0 +1,117 @@
+use lambdaworks_math::field::{
+    element::FieldElement as FE, fields::fft_friendly::stark_252_prime_field::Stark252PrimeField,
+};
+
+/// Round constants of [`Poseidon2Stark252`](super::Poseidon2Stark252): three for each of the
+/// first four full rounds, one for each partial round and three for each of the last four full
+/// rounds, in the order they are drawn from the Grain LFSR of the reference implementation
+/// initialized with `n = 252`, `t = 3`, `R_F = 8` and `R_P = 83`.
+pub(super) const ROUND_CONSTANTS: [FE<Stark252PrimeField>; 107] = [
+    FE::from_hex_unchecked("1caafe19abc51e89d9e19e96658904fed74eb9e5769b70e3f9b786afb30dc0a"),
+    FE::from_hex_unchecked("6a49348a28b19f4c27358c56b807ada552c834c555fb0039e59af9de62c2019"),
+    FE::from_hex_unchecked("5228a48d4fc955881700a9c8d791626098428056657435212fe5e9f844b06d"),
+    FE::from_hex_unchecked("3ba61c6bd818a9f8226c750ee48812ca090a6b1e61c4c403d59018076e4bc8d"),
+    FE::from_hex_unchecked("6e2a2531c5b77e40fa42f804978db49ec3128dfc512eda0322e76ba7192c1e8"),
+    FE::from_hex_unchecked("46401bacd635a7dd2d291a4029da3c669881e860c0b6ac438f32d720cc5080e"),
+    FE::from_hex_unchecked("64ff3a8bd01c025e3121cea2ba866f3f5bf7ec9f670443c93dc560a8d412387"),
+    FE::from_hex_unchecked("55bfd0d83713139db5d42260cbe94f3e4c1cf21ecd5af4ea9ec8dc1d7ea2096"),
+    FE::from_hex_unchecked("c49838bac6734c9e84cfdebbfce7761c8dff31bd112aa068f3393ac6922f03"),
+    FE::from_hex_unchecked("4cdf3f1c45550cf9903fe83ff089dc57fde71f21b766cbacaa3e6c578357216"),
+    FE::from_hex_unchecked("78ca37a2a066d3cb56e4d7ca4ea372ee2e9bbd19775a196f5f879ae13fa5ab2"),
+    FE::from_hex_unchecked("69739e32d9ab7cdaa0b0bd066ead0664e8d96bb7f4a42f7d587b5de6944d329"),
+    FE::from_hex_unchecked("673fd4917a99e14cb199ca240a313958995e9eb1c47a913be7ebfbfa5d2a7d2"),
+    FE::from_hex_unchecked("30baa62b25de6a89bbc71fb99c9dc870a63d023937fe61f43b27c57617fdd80"),
+    FE::from_hex_unchecked("64886a5ef665b774177d5b4b440ab04b7a5c1a89612951b59da0d02ead8ff0d"),
+    FE::from_hex_unchecked("7d1eb7b032165794cd31827245d187ffd8f9abbdc81af7ec1cb18856a2eb4de"),
+    FE::from_hex_unchecked("2b5a99a6b751957843aabf081ea79c3c9f5de2c31262ccf3734f80fcd2762ab"),
+    FE::from_hex_unchecked("1255793b24f0f860522e3f75b6ebc07cdb7e6ff8210c16e7dc5bf7c7c71639c"),
+    FE::from_hex_unchecked("4ecddac96630b0cb9becfeb0d7dcebb50dc750362351b28888247179a8d628"),
+    FE::from_hex_unchecked("79893ab13a1e627462497ac166ba177a956ca1e3c2e8bec31c3a3d8ef89d85d"),
+    FE::from_hex_unchecked("537edab27f234a11cfbbeb3d88034ea9be6d15906806af79e83d88c7e4ccb0b"),
+    FE::from_hex_unchecked("7430f75cdc58f7263447bf5c3274918b526abd57bf1bc3242480bb1412ebd09"),
+    FE::from_hex_unchecked("65a5821b2a99f38d220b0b82f71bb614be03f4eae7cb61bfb3567765757a64f"),
+    FE::from_hex_unchecked("1fef33ca367918040276992b8e2afe308a72d58966aa187db6d6d96d0143c5c"),
+    FE::from_hex_unchecked("417e2859a8d763d9579a2de1c5b34b1f60b8abcaac63b68c3ee29460838c0c2"),
+    FE::from_hex_unchecked("659fa303e5a1b91a233b43ad73b4ab43a161d045aff0f462a3619c8c504659b"),
+    FE::from_hex_unchecked("58a5ee378a9f49acd2a79373c382573beae03a057bc40016f9847dc274ee873"),
+    FE::from_hex_unchecked("480e72fdc7c17178576f277cd3e34a78f58ee64ba969ca24c6a67e52f399d0a"),
+    FE::from_hex_unchecked("4ff6b8383cfa589064600cccba02175d4488440e2d78154c5f2064688521e9a"),
+    FE::from_hex_unchecked("1d55afec1a523ad72d6d2599fa37c9860843ad37949e273e1a71049bec721eb"),
+    FE::from_hex_unchecked("533e1fd0e8a47ac2d434c2158f2898ef5c05d76dbec11df13d3a96b77cd1f03"),
+    FE::from_hex_unchecked("1f75036b4d208493a66f0693794c3b06ea52ddbc92f938b038587a07fa1cf74"),
+    FE::from_hex_unchecked("f27c19da832f5f96835a024d153cf0a55ac70cb6850926e5c3565cce0f098"),
+    FE::from_hex_unchecked("fa6dad9c5d5fc88dd1c65bd87abab4eabb9034966c00f10a9abfbdff7721f4"),
+    FE::from_hex_unchecked("1525a9da8d1ee034362e5d750cd94c72ea2231cbf2f4bdb3a2a8dbf356c517f"),
+    FE::from_hex_unchecked("5ea0f5cd18645f5dbf6ffd7de437a7f28d756b0da77e26908e70a2582258c53"),
+    FE::from_hex_unchecked("14730fe1334bdf46740b0c47aa11b9d13a2051abc71318135a9d41651bd2ba6"),
+    FE::from_hex_unchecked("691f607600e792ecfcfd03dd9d1049486bca2aa0a1b5304ad5dac77cadfeeb4"),
+    FE::from_hex_unchecked("700810ac7be2633ff68f5ba8a7832675b4528d7d372daf74654c5dc5f76a44e"),
+    FE::from_hex_unchecked("1b9367599c0850cbd1b2ad107a7a0cf82a5fa873121ea5c97f06197144c5da1"),
+    FE::from_hex_unchecked("510d4b04f2c26e18846477272d500c929275c497e690d3ffdf4edf1d843fe8b"),
+    FE::from_hex_unchecked("2d0b0edfd9483002bb58b93b9ec72b77a323cc270d522b67e27f70856355c11"),
+    FE::from_hex_unchecked("18651665380759d056a47dd537a065b9927e26e4e08e66be57eb889617076a2"),
+    FE::from_hex_unchecked("46fe1ddfdad9e163a88677680f9805af9db4a7304f1e3da42671813120e1f9d"),
+    FE::from_hex_unchecked("686745b1f6d0f97e5e78f3b5319bf20f488f4aa8cf3654ca27642e27caba0d3"),
+    FE::from_hex_unchecked("3670f90ff89791db3c80a7cc4e4ccf84a6f986718e4b44acf05b78c1e4e445"),
+    FE::from_hex_unchecked("206310fdf701f1286e498c637e6a49003054923754908b11c8b733407308b25"),
+    FE::from_hex_unchecked("679fe3addfab71e90be3157923e14cfcdc8cf022e0c93d0a3f823e7e4b0ddc9"),
+    FE::from_hex_unchecked("3efec7fc8b364d48c4b740591411d5921f32fdf3dd1b4162564d3ed7a447918"),
+    FE::from_hex_unchecked("2519db11ea2efcbf153a26e364bf5c91260913be3ce159d26119d09868eafe8"),
+    FE::from_hex_unchecked("2359738c04c88a78e93588b994b8aa800b9a458b6a71c7689fb1c8bd647e1a4"),
+    FE::from_hex_unchecked("3dc42a576a8d93f29ccaaf9641e2481e8a552181c09836415dcf4f41ce41e1"),
+    FE::from_hex_unchecked("88b2ebf0d77df7961cf95c1712a11438f258d6c3d2e014113e3d9aef7aaa6c"),
+    FE::from_hex_unchecked("431df2e4aa221ae184afbbec3b8a06fe9ddc3ad1a24bcd98946ed1269f7fba0"),
+    FE::from_hex_unchecked("2085926430c0c3a93f8bbe276008a73413d85aadeb87c35bca9c42d05a66c34"),
+    FE::from_hex_unchecked("6ee17a022415361e687000e8cd1bfc7ec46ace8f2de29b0893e4a0778f10d85"),
+    FE::from_hex_unchecked("6b690f00074215603edab594f98465e4de28fecd90f5ba2b2cafd6a7aaa9759"),
+    FE::from_hex_unchecked("769d18543a2369458dbe0c9b7628fd2042720f8941cfe1a44358091f47a69aa"),
+    FE::from_hex_unchecked("78f414a24db5f2c6d08451073649e356c4edbd8ca66a5fd212eef588d4706da"),
+    FE::from_hex_unchecked("75a859ba682bdb6c2967300d5151dfc32e593a6c66a5f416a44466adc18ab93"),
+    FE::from_hex_unchecked("6d3b2cc9b371e8feb31d30105f13bd901fb68d66b90959248378c3cf7bb7f73"),
+    FE::from_hex_unchecked("150bd3a290bb6a4a8818d2a69aff52d5b6ce4747381f71ab996e23138ec72e6"),
+    FE::from_hex_unchecked("60e7d9da09840d7c8615afc8b77d8dbc66408514effd708aac8bd728cedc3d8"),
+    FE::from_hex_unchecked("34ddde2b9fddab828de31956a6995885cfda7f16748dd9c73c6c3e19850dbb3"),
+    FE::from_hex_unchecked("7cd1821a677861bd5e1772e138616c62e149b03972a79c902af2f21e8da7c7a"),
+    FE::from_hex_unchecked("7c6a80fb6b4c8268f0165d904f861df60ca04f2a24357a2d4bd00915492197d"),
+    FE::from_hex_unchecked("52ac8cac9cb8bea5abad085f9bdccdb1f4cc903248db7f9be69a21f65d248ca"),
+    FE::from_hex_unchecked("e9f66065c1fcde646aeeb6cdf2a9c923f9e6300e1a97965a0723cd2e038423"),
+    FE::from_hex_unchecked("572f25fccd60c87d7308a66a7fcddf580e849a6b84a9a743bd434150a9c32ec"),
+    FE::from_hex_unchecked("523f4914b37a6e6a93ebb32f11a2b676f64235f71d18881b7c79d8a9399cc8f"),
+    FE::from_hex_unchecked("48c368a4c21b1e46256bcb3c110e6ffcd418e5c33bc6bc82e18885f82eafe1f"),
+    FE::from_hex_unchecked("7f7b9dde8d7294927d73df6ed2e70fc4dfec7b1a402dca5abf8c0c648586724"),
+    FE::from_hex_unchecked("670ff50da1a477ae34d44e27a3c0e87e267322843a2f04e86c8088934fcdf57"),
+    FE::from_hex_unchecked("47d2e3788a6ce634c59af265553549586deb4c10038e6e6513ef64c26475f7b"),
+    FE::from_hex_unchecked("4096c37cc4770dc8049b4ab483818f5746ffb05cc4916a1479096113fc95720"),
+    FE::from_hex_unchecked("6322f2251894cf8934d5974be5a1018258acd2978fd99e0669ea61ce63944be"),
+    FE::from_hex_unchecked("5fcaf134497a35fd68e39a5faa41939908d1b6cfba0e1d10764cadc5892f3df"),
+    FE::from_hex_unchecked("27e8c046ca5751dd329fe0f499150bd40a7859f6616a31de35c0b73fc14e54e"),
+    FE::from_hex_unchecked("1ad15273316815c320ec79d42e535070e2b1f67e5fe6d0d54bfc1336f176189"),
+    FE::from_hex_unchecked("4c601a0d1098288096584faa8736bf4ff967e4d3a13389e2dbe2f7fba8b1616"),
+    FE::from_hex_unchecked("32c0df48c19587bf6176c7bb18fff5a4c5e6025f07f3bad827fdc8b1da682f5"),
+    FE::from_hex_unchecked("6bf016bcfe1b346dce014fd0683b65ff06c5deb8a311261ec5f163499a4f506"),
+    FE::from_hex_unchecked("68f22e49430097a2fd3446160f6900a106d6c84f7e2267123c26394187cd0e9"),
+    FE::from_hex_unchecked("28943ef3ae18150e46230d933e4799e98b1c9a8eed1b62c13ff182ca75a8497"),
+    FE::from_hex_unchecked("51939bd6cf778aae328f9812517e6f860c4b9dbe771a2755b7815a40d12f31"),
+    FE::from_hex_unchecked("72f0931ee56e22b129a42c51535b583ffc61859c08be04e0f144760ed0808bb"),
+    FE::from_hex_unchecked("36f52567bf4fa74a79ca7b625bcb7eb57103c525b7aa28dd08aa659cbdd5650"),
+    FE::from_hex_unchecked("635b5b1847b2a7fc7687c1b35f0d76fafbf1ecd26595e83c3025d96a535675a"),
+    FE::from_hex_unchecked("62357f8f54bd53cd32087d324aa13b14355702304205feb23e5a50ab611281"),
+    FE::from_hex_unchecked("6ef2b3a77797f7bace6a9866b02cf1d98f48f8c719061b6a07c698dc18d3ced"),
+    FE::from_hex_unchecked("28fcdfc38b8059df3534a0bb29699eae6a7ccd2dd6f1a28d791c95780e924f8"),
+    FE::from_hex_unchecked("6540dc764d1ef62efbacb347f79780a7b0526e6930426fb2f698364c4563120"),
+    FE::from_hex_unchecked("19c2eb9ecae2f62c644bbf6d8f0161281308851f95202d7c4b6b7741a54eee"),
+    FE::from_hex_unchecked("1dbbcb555d2b9ebe59b13c5d230539e05a1355cd45912c05e6751adb66155ef"),
+    FE::from_hex_unchecked("455a2fd9e3c2d8034d550c80e10736d99682733b35ded2775185a24012109a2"),
+    FE::from_hex_unchecked("534757d0cf17a80a60130345f66ceee6ccfd0eddb0b9e1740c71cf99f0d0dc0"),
+    FE::from_hex_unchecked("6facad21e991ed6a3871491dd8786dfb6b2b1a6ac149b6735ed249a8c9f3abf"),
+    FE::from_hex_unchecked("7dcc26d042cdb987f73227a56df5524c5b9aadfa0714a52de109c71d98bba03"),
+    FE::from_hex_unchecked("5592f68cb8c1003b35476b2b3a78c4c619d3196bd90d79412a209b1c51ae9e1"),
+    FE::from_hex_unchecked("204601234388ac5ce77bca83703b923512c87de9cd8a9191ef9f749540becaf"),
+    FE::from_hex_unchecked("5f359edc9ec808c9e1585d191796834690c8dd7c4f9b20d1fd4b34ad5ba76b8"),
+    FE::from_hex_unchecked("5c6dcffa770d4a505fc55da6a90cfdfd5b62062310e876cb3806a4db1768c98"),
+    FE::from_hex_unchecked("20e8945ed0f5c1ff4576d28bb683b859ba545a812ff20861bc73cb7c94a94db"),
+    FE::from_hex_unchecked("5e1bb202bd0ffbe37c7246abc67f34269e66be12c88b3b734ea52acd3652987"),
+    FE::from_hex_unchecked("426cd1b02689f178a17ea7858f8379ce10b2354320a7a4a2e579bf2c79832ea"),
+    FE::from_hex_unchecked("6c44f485087cd57554c72dcb55d962e0b3210228825e996de0fba726c2f3a"),
+    FE::from_hex_unchecked("6f8b831ecded2b6fd51fa94dc117dfd2e7a5990fb0ca4bb7171920ab38c17b1"),
+];
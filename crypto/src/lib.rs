@@ -4,8 +4,11 @@
 extern crate alloc;
 
 pub mod commitments;
+pub mod compute_backend;
 #[cfg(feature = "std")]
 pub mod errors;
 pub mod fiat_shamir;
 pub mod hash;
 pub mod merkle_tree;
+#[cfg(feature = "std")]
+pub mod setup_file;
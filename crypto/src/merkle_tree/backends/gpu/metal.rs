@@ -0,0 +1,315 @@
+use crate::{
+    hash::{
+        byte_hash::{gpu::metal::MetalByteHashState, IsGpuByteHash},
+        poseidon::{
+            gpu::{metal::MetalPoseidonState, IsGpuPoseidon},
+            starknet::PoseidonCairoStark252,
+        },
+    },
+    merkle_tree::{
+        backends::{
+            field_element::FieldElementBackend, field_element_vector::FieldElementVectorBackend,
+        },
+        traits::IsMerkleTreeBackend,
+        utils::build,
+    },
+};
+use alloc::vec::Vec;
+use blake2::Blake2s256;
+use core::marker::PhantomData;
+use lambdaworks_math::{
+    field::{
+        element::FieldElement, fields::fft_friendly::stark_252_prime_field::Stark252PrimeField,
+        traits::IsField,
+    },
+    traits::AsBytes,
+};
+use sha3::{
+    digest::{generic_array::GenericArray, OutputSizeUser},
+    Keccak256,
+};
+
+type FE = FieldElement<Stark252PrimeField>;
+
+/// Same tree as [`TreePoseidon`] with [`PoseidonCairoStark252`], or any other hash `P` with GPU
+/// kernels, with the leaves hashed and the inner nodes built in Metal. Falls back to the CPU if
+/// the device can't be used.
+///
+/// [`TreePoseidon`]: crate::merkle_tree::backends::field_element::TreePoseidon
+#[derive(Clone)]
+pub struct MetalTreePoseidon<P: IsGpuPoseidon = PoseidonCairoStark252> {
+    phantom: PhantomData<P>,
+}
+
+impl<P: IsGpuPoseidon> Default for MetalTreePoseidon<P> {
+    fn default() -> Self {
+        Self {
+            phantom: PhantomData,
+        }
+    }
+}
+
+impl<P: IsGpuPoseidon> IsMerkleTreeBackend for MetalTreePoseidon<P> {
+    type Node = FE;
+    type Data = FE;
+
+    fn hash_data(input: &FE) -> FE {
+        P::hash_element(input)
+    }
+
+    fn hash_leaves(unhashed_leaves: &[FE]) -> Vec<FE> {
+        MetalPoseidonState::<P>::new(None)
+            .and_then(|state| state.hash_single(unhashed_leaves))
+            .unwrap_or_else(|_| unhashed_leaves.iter().map(P::hash_element).collect())
+    }
+
+    fn hash_new_parent(left: &FE, right: &FE) -> FE {
+        P::hash_pair(left, right)
+    }
+
+    fn build_inner_nodes(nodes: &mut [FE], leaves_len: usize) {
+        build_inner_nodes_in_metal::<Self, P>(nodes, leaves_len);
+    }
+}
+
+/// Same tree as [`BatchPoseidonTree`] with [`PoseidonCairoStark252`], or any other hash `P` with
+/// GPU kernels, with the leaves hashed and the inner nodes built in Metal. Falls back to the CPU
+/// if the device can't be used, or if the leaves have different lengths.
+///
+/// [`BatchPoseidonTree`]: crate::merkle_tree::backends::field_element_vector::BatchPoseidonTree
+#[derive(Clone)]
+pub struct MetalBatchPoseidonTree<P: IsGpuPoseidon = PoseidonCairoStark252> {
+    phantom: PhantomData<P>,
+}
+
+impl<P: IsGpuPoseidon> Default for MetalBatchPoseidonTree<P> {
+    fn default() -> Self {
+        Self {
+            phantom: PhantomData,
+        }
+    }
+}
+
+impl<P: IsGpuPoseidon> IsMerkleTreeBackend for MetalBatchPoseidonTree<P> {
+    type Node = FE;
+    type Data = Vec<FE>;
+
+    fn hash_data(input: &Vec<FE>) -> FE {
+        P::hash_row(input)
+    }
+
+    fn hash_leaves(unhashed_leaves: &[Vec<FE>]) -> Vec<FE> {
+        MetalPoseidonState::<P>::new(None)
+            .and_then(|state| state.hash_rows(unhashed_leaves))
+            .unwrap_or_else(|_| unhashed_leaves.iter().map(|row| P::hash_row(row)).collect())
+    }
+
+    fn hash_new_parent(left: &FE, right: &FE) -> FE {
+        P::hash_pair(left, right)
+    }
+
+    fn build_inner_nodes(nodes: &mut [FE], leaves_len: usize) {
+        build_inner_nodes_in_metal::<Self, P>(nodes, leaves_len);
+    }
+}
+
+fn build_inner_nodes_in_metal<B, P>(nodes: &mut [FE], leaves_len: usize)
+where
+    B: IsMerkleTreeBackend<Node = FE>,
+    P: IsGpuPoseidon,
+{
+    let built = MetalPoseidonState::<P>::new(None)
+        .and_then(|state| state.build_merkle_tree(nodes, leaves_len))
+        .is_ok();
+    // `nodes` is only written when all the levels were built
+    if !built {
+        build::<B>(nodes, leaves_len);
+    }
+}
+
+/// Same tree as [`FieldElementBackend`] with a hash `D` that has GPU kernels, with the leaves
+/// hashed and the inner nodes built in Metal. Leaves are hashed as their bytes. Falls back to
+/// the CPU if the device can't be used, or if the leaves have different lengths in bytes.
+#[derive(Clone)]
+pub struct MetalFieldElementBackend<F, D: IsGpuByteHash> {
+    phantom1: PhantomData<F>,
+    phantom2: PhantomData<D>,
+}
+
+impl<F, D: IsGpuByteHash> Default for MetalFieldElementBackend<F, D> {
+    fn default() -> Self {
+        Self {
+            phantom1: PhantomData,
+            phantom2: PhantomData,
+        }
+    }
+}
+
+impl<F, D: IsGpuByteHash> IsMerkleTreeBackend for MetalFieldElementBackend<F, D>
+where
+    F: IsField,
+    FieldElement<F>: AsBytes + Sync + Send,
+    [u8; 32]: From<GenericArray<u8, <D as OutputSizeUser>::OutputSize>>,
+{
+    type Node = [u8; 32];
+    type Data = FieldElement<F>;
+
+    fn hash_data(input: &FieldElement<F>) -> [u8; 32] {
+        FieldElementBackend::<F, D, 32>::hash_data(input)
+    }
+
+    fn hash_leaves(unhashed_leaves: &[FieldElement<F>]) -> Vec<[u8; 32]> {
+        let rows: Vec<Vec<u8>> = unhashed_leaves.iter().map(AsBytes::as_bytes).collect();
+        MetalByteHashState::new(None)
+            .and_then(|state| state.hash_rows(D::BYTE_HASH, &rows))
+            .unwrap_or_else(|_| FieldElementBackend::<F, D, 32>::hash_leaves(unhashed_leaves))
+    }
+
+    fn hash_new_parent(left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
+        FieldElementBackend::<F, D, 32>::hash_new_parent(left, right)
+    }
+
+    fn build_inner_nodes(nodes: &mut [[u8; 32]], leaves_len: usize) {
+        build_byte_hash_inner_nodes_in_metal::<Self, D>(nodes, leaves_len);
+    }
+}
+
+/// Same tree as [`FieldElementVectorBackend`] with a hash `D` that has GPU kernels, with the
+/// leaves hashed and the inner nodes built in Metal. Leaves are hashed as the bytes of their
+/// elements one after the other. Falls back to the CPU if the device can't be used, or if the
+/// leaves have different lengths in bytes.
+#[derive(Clone)]
+pub struct MetalFieldElementVectorBackend<F, D: IsGpuByteHash> {
+    phantom1: PhantomData<F>,
+    phantom2: PhantomData<D>,
+}
+
+impl<F, D: IsGpuByteHash> Default for MetalFieldElementVectorBackend<F, D> {
+    fn default() -> Self {
+        Self {
+            phantom1: PhantomData,
+            phantom2: PhantomData,
+        }
+    }
+}
+
+impl<F, D: IsGpuByteHash> IsMerkleTreeBackend for MetalFieldElementVectorBackend<F, D>
+where
+    F: IsField,
+    FieldElement<F>: AsBytes,
+    [u8; 32]: From<GenericArray<u8, <D as OutputSizeUser>::OutputSize>>,
+    Vec<FieldElement<F>>: Sync + Send,
+{
+    type Node = [u8; 32];
+    type Data = Vec<FieldElement<F>>;
+
+    fn hash_data(input: &Vec<FieldElement<F>>) -> [u8; 32] {
+        FieldElementVectorBackend::<F, D, 32>::hash_data(input)
+    }
+
+    fn hash_leaves(unhashed_leaves: &[Vec<FieldElement<F>>]) -> Vec<[u8; 32]> {
+        let rows: Vec<Vec<u8>> = unhashed_leaves
+            .iter()
+            .map(|leaf| leaf.iter().flat_map(AsBytes::as_bytes).collect())
+            .collect();
+        MetalByteHashState::new(None)
+            .and_then(|state| state.hash_rows(D::BYTE_HASH, &rows))
+            .unwrap_or_else(|_| FieldElementVectorBackend::<F, D, 32>::hash_leaves(unhashed_leaves))
+    }
+
+    fn hash_new_parent(left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
+        FieldElementVectorBackend::<F, D, 32>::hash_new_parent(left, right)
+    }
+
+    fn build_inner_nodes(nodes: &mut [[u8; 32]], leaves_len: usize) {
+        build_byte_hash_inner_nodes_in_metal::<Self, D>(nodes, leaves_len);
+    }
+}
+
+pub type MetalKeccak256Backend<F> = MetalFieldElementBackend<F, Keccak256>;
+pub type MetalBlake2s256Backend<F> = MetalFieldElementBackend<F, Blake2s256>;
+pub type MetalBatchKeccak256Backend<F> = MetalFieldElementVectorBackend<F, Keccak256>;
+pub type MetalBatchBlake2s256Backend<F> = MetalFieldElementVectorBackend<F, Blake2s256>;
+
+fn build_byte_hash_inner_nodes_in_metal<B, D>(nodes: &mut [[u8; 32]], leaves_len: usize)
+where
+    B: IsMerkleTreeBackend<Node = [u8; 32]>,
+    D: IsGpuByteHash,
+{
+    let built = MetalByteHashState::new(None)
+        .and_then(|state| state.build_merkle_tree(D::BYTE_HASH, nodes, leaves_len))
+        .is_ok();
+    // `nodes` is only written when all the levels were built
+    if !built {
+        build::<B>(nodes, leaves_len);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::merkle_tree::{
+        backends::{
+            field_element::TreePoseidon,
+            field_element_vector::BatchPoseidonTree,
+            types::{BatchKeccak256Backend, Blake2s256Backend},
+        },
+        merkle::MerkleTree,
+    };
+
+    #[test]
+    fn metal_poseidon_tree_matches_cpu_tree() {
+        let values: Vec<FE> = (1..1000_u64).map(FE::from).collect();
+        let cpu_tree = MerkleTree::<TreePoseidon<PoseidonCairoStark252>>::build(&values).unwrap();
+        let metal_tree = MerkleTree::<MetalTreePoseidon>::build(&values).unwrap();
+
+        assert_eq!(metal_tree.root, cpu_tree.root);
+        let proof = metal_tree.get_proof_by_pos(17).unwrap();
+        assert!(proof.verify::<TreePoseidon<PoseidonCairoStark252>>(
+            &cpu_tree.root,
+            17,
+            &values[17]
+        ));
+    }
+
+    #[test]
+    fn metal_batch_poseidon_tree_matches_cpu_tree() {
+        let values: Vec<Vec<FE>> = (1..300_u64)
+            .map(|i| vec![FE::from(i), FE::from(2 * i), FE::from(3 * i)])
+            .collect();
+        let cpu_tree =
+            MerkleTree::<BatchPoseidonTree<PoseidonCairoStark252>>::build(&values).unwrap();
+        let metal_tree = MerkleTree::<MetalBatchPoseidonTree>::build(&values).unwrap();
+
+        assert_eq!(metal_tree.root, cpu_tree.root);
+    }
+
+    #[test]
+    fn metal_batch_keccak256_tree_matches_cpu_tree() {
+        let values: Vec<Vec<FE>> = (1..300_u64)
+            .map(|i| vec![FE::from(i), FE::from(2 * i), FE::from(3 * i)])
+            .collect();
+        let cpu_tree =
+            MerkleTree::<BatchKeccak256Backend<Stark252PrimeField>>::build(&values).unwrap();
+        let metal_tree =
+            MerkleTree::<MetalBatchKeccak256Backend<Stark252PrimeField>>::build(&values).unwrap();
+
+        assert_eq!(metal_tree.root, cpu_tree.root);
+    }
+
+    #[test]
+    fn metal_blake2s256_tree_matches_cpu_tree() {
+        let values: Vec<FE> = (1..1000_u64).map(FE::from).collect();
+        let cpu_tree = MerkleTree::<Blake2s256Backend<Stark252PrimeField>>::build(&values).unwrap();
+        let metal_tree =
+            MerkleTree::<MetalBlake2s256Backend<Stark252PrimeField>>::build(&values).unwrap();
+
+        assert_eq!(metal_tree.root, cpu_tree.root);
+        let proof = metal_tree.get_proof_by_pos(17).unwrap();
+        assert!(proof.verify::<Blake2s256Backend<Stark252PrimeField>>(
+            &cpu_tree.root,
+            17,
+            &values[17]
+        ));
+    }
+}
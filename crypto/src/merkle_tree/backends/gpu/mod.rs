@@ -0,0 +1,6 @@
+#[cfg(feature = "cuda")]
+pub mod cuda;
+#[cfg(feature = "metal")]
+pub mod metal;
+#[cfg(feature = "wgpu")]
+pub mod wgpu;
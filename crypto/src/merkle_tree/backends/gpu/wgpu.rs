@@ -0,0 +1,115 @@
+use crate::{
+    hash::poseidon::{gpu::wgpu::WgpuPoseidonState, starknet::PoseidonCairoStark252, Poseidon},
+    merkle_tree::{
+        backends::{field_element::TreePoseidon, field_element_vector::BatchPoseidonTree},
+        traits::IsMerkleTreeBackend,
+    },
+};
+use alloc::vec::Vec;
+use lambdaworks_math::field::{
+    element::FieldElement, fields::fft_friendly::stark_252_prime_field::Stark252PrimeField,
+};
+
+type FE = FieldElement<Stark252PrimeField>;
+
+/// Same tree as [`TreePoseidon`] with [`PoseidonCairoStark252`], with the leaves hashed and the
+/// inner nodes built in WebGPU. Falls back to the CPU if the device can't be used.
+#[derive(Clone, Default)]
+pub struct WgpuTreePoseidon;
+
+impl IsMerkleTreeBackend for WgpuTreePoseidon {
+    type Node = FE;
+    type Data = FE;
+
+    fn hash_data(input: &FE) -> FE {
+        PoseidonCairoStark252::hash_single(input)
+    }
+
+    fn hash_leaves(unhashed_leaves: &[FE]) -> Vec<FE> {
+        WgpuPoseidonState::new()
+            .and_then(|state| state.hash_single(unhashed_leaves))
+            .unwrap_or_else(|_| TreePoseidon::<PoseidonCairoStark252>::hash_leaves(unhashed_leaves))
+    }
+
+    fn hash_new_parent(left: &FE, right: &FE) -> FE {
+        PoseidonCairoStark252::hash(left, right)
+    }
+
+    fn build_inner_nodes(nodes: &mut [FE], leaves_len: usize) {
+        build_inner_nodes_in_wgpu(nodes, leaves_len);
+    }
+}
+
+/// Same tree as [`BatchPoseidonTree`] with [`PoseidonCairoStark252`], with the leaves hashed and
+/// the inner nodes built in WebGPU. Falls back to the CPU if the device can't be used, or if the
+/// leaves have different lengths.
+#[derive(Clone, Default)]
+pub struct WgpuBatchPoseidonTree;
+
+impl IsMerkleTreeBackend for WgpuBatchPoseidonTree {
+    type Node = FE;
+    type Data = Vec<FE>;
+
+    fn hash_data(input: &Vec<FE>) -> FE {
+        PoseidonCairoStark252::hash_many(input)
+    }
+
+    fn hash_leaves(unhashed_leaves: &[Vec<FE>]) -> Vec<FE> {
+        WgpuPoseidonState::new()
+            .and_then(|state| state.hash_rows(unhashed_leaves))
+            .unwrap_or_else(|_| {
+                BatchPoseidonTree::<PoseidonCairoStark252>::hash_leaves(unhashed_leaves)
+            })
+    }
+
+    fn hash_new_parent(left: &FE, right: &FE) -> FE {
+        PoseidonCairoStark252::hash(left, right)
+    }
+
+    fn build_inner_nodes(nodes: &mut [FE], leaves_len: usize) {
+        build_inner_nodes_in_wgpu(nodes, leaves_len);
+    }
+}
+
+fn build_inner_nodes_in_wgpu(nodes: &mut [FE], leaves_len: usize) {
+    let built = WgpuPoseidonState::new()
+        .and_then(|state| state.build_merkle_tree(nodes, leaves_len))
+        .is_ok();
+    // `nodes` is only written when all the levels were built
+    if !built {
+        TreePoseidon::<PoseidonCairoStark252>::build_inner_nodes(nodes, leaves_len);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::merkle_tree::merkle::MerkleTree;
+
+    #[test]
+    fn wgpu_poseidon_tree_matches_cpu_tree() {
+        let values: Vec<FE> = (1..1000_u64).map(FE::from).collect();
+        let cpu_tree = MerkleTree::<TreePoseidon<PoseidonCairoStark252>>::build(&values).unwrap();
+        let wgpu_tree = MerkleTree::<WgpuTreePoseidon>::build(&values).unwrap();
+
+        assert_eq!(wgpu_tree.root, cpu_tree.root);
+        let proof = wgpu_tree.get_proof_by_pos(17).unwrap();
+        assert!(proof.verify::<TreePoseidon<PoseidonCairoStark252>>(
+            &cpu_tree.root,
+            17,
+            &values[17]
+        ));
+    }
+
+    #[test]
+    fn wgpu_batch_poseidon_tree_matches_cpu_tree() {
+        let values: Vec<Vec<FE>> = (1..300_u64)
+            .map(|i| vec![FE::from(i), FE::from(2 * i), FE::from(3 * i)])
+            .collect();
+        let cpu_tree =
+            MerkleTree::<BatchPoseidonTree<PoseidonCairoStark252>>::build(&values).unwrap();
+        let wgpu_tree = MerkleTree::<WgpuBatchPoseidonTree>::build(&values).unwrap();
+
+        assert_eq!(wgpu_tree.root, cpu_tree.root);
+    }
+}
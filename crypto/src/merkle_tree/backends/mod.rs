@@ -1,5 +1,7 @@
 pub mod field_element;
 pub mod field_element_vector;
+#[cfg(any(feature = "cuda", feature = "metal", feature = "wgpu"))]
+pub mod gpu;
 /// Configurations for merkle trees
 /// Setting generics to some value
 pub mod types;
@@ -1,3 +1,4 @@
+use blake2::Blake2s256;
 use sha2::{Sha256, Sha512};
 use sha3::{Keccak256, Keccak512, Sha3_256, Sha3_512};
 
@@ -9,6 +10,7 @@ use super::{field_element::FieldElementBackend, field_element_vector::FieldEleme
 pub type Sha3_256Backend<F> = FieldElementBackend<F, Sha3_256, 32>;
 pub type Keccak256Backend<F> = FieldElementBackend<F, Keccak256, 32>;
 pub type Sha2_256Backend<F> = FieldElementBackend<F, Sha256, 32>;
+pub type Blake2s256Backend<F> = FieldElementBackend<F, Blake2s256, 32>;
 
 // - With 512 bit
 pub type Sha3_512Backend<F> = FieldElementBackend<F, Sha3_512, 64>;
@@ -21,6 +23,7 @@ pub type Sha2_512Backend<F> = FieldElementBackend<F, Sha512, 64>;
 pub type BatchSha3_256Backend<F> = FieldElementVectorBackend<F, Sha3_256, 32>;
 pub type BatchKeccak256Backend<F> = FieldElementVectorBackend<F, Keccak256, 32>;
 pub type BatchSha2_256Backend<F> = FieldElementVectorBackend<F, Sha256, 32>;
+pub type BatchBlake2s256Backend<F> = FieldElementVectorBackend<F, Blake2s256, 32>;
 
 // - With 512 bit
 pub type BatchSha3_512Backend<F> = FieldElementVectorBackend<F, Sha3_512, 64>;
@@ -1,6 +1,13 @@
-use core::fmt::Display;
+use core::fmt::{Debug, Display};
 
 use alloc::vec::Vec;
+#[cfg(feature = "proptest")]
+use proptest::{
+    arbitrary::Arbitrary,
+    collection::{self, SizeRange},
+    prelude::any,
+    strategy::{BoxedStrategy, Strategy},
+};
 
 use super::{proof::Proof, traits::IsMerkleTreeBackend, utils::*};
 
@@ -35,7 +42,16 @@ where
             return None;
         }
 
-        let hashed_leaves: Vec<B::Node> = B::hash_leaves(unhashed_leaves);
+        Self::build_from_hashed_leaves(B::hash_leaves(unhashed_leaves))
+    }
+
+    /// Builds the tree whose leaves have the hashes `hashed_leaves`, for leaves hashed in several
+    /// batches with [`IsMerkleTreeBackend::hash_leaves`]. The tree is the same as the one built
+    /// from the leaves.
+    pub fn build_from_hashed_leaves(hashed_leaves: Vec<B::Node>) -> Option<Self> {
+        if hashed_leaves.is_empty() {
+            return None;
+        }
 
         //The leaf must be a power of 2 set
         let hashed_leaves = complete_until_power_of_two(hashed_leaves);
@@ -47,7 +63,7 @@ where
         nodes.extend(hashed_leaves);
 
         //Build the inner nodes of the tree
-        build::<B>(&mut nodes, leaves_len);
+        B::build_inner_nodes(&mut nodes, leaves_len);
 
         Some(MerkleTree {
             root: nodes[ROOT].clone(),
@@ -85,6 +101,42 @@ where
         Ok(merkle_path)
     }
 }
+
+impl<B> Debug for MerkleTree<B>
+where
+    B: IsMerkleTreeBackend,
+    B::Node: Debug,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("MerkleTree")
+            .field("root", &self.root)
+            .field("leaves", &((self.nodes.len() + 1) / 2))
+            .finish()
+    }
+}
+
+/// Generates trees with random leaves. The parameter is the range of the number of leaves, and
+/// empty ranges of leaves are discarded.
+#[cfg(feature = "proptest")]
+impl<B> Arbitrary for MerkleTree<B>
+where
+    B: IsMerkleTreeBackend + 'static,
+    B::Node: Debug,
+    B::Data: Arbitrary,
+{
+    type Parameters = SizeRange;
+
+    fn arbitrary_with(num_leaves: Self::Parameters) -> Self::Strategy {
+        collection::vec(any::<B::Data>(), num_leaves)
+            .prop_filter_map("a tree needs at least one leaf", |leaves| {
+                MerkleTree::build(&leaves)
+            })
+            .boxed()
+    }
+
+    type Strategy = BoxedStrategy<Self>;
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -126,8 +178,36 @@ mod tests {
         assert_eq!(merkle_tree.root, FE::new(2)); // Adjusted expected value
     }
 
+    #[test]
+    fn trees_built_from_hashed_leaves_are_the_same() {
+        let values: Vec<FE> = (1..6).map(FE::new).collect();
+        let merkle_tree = MerkleTree::<TestBackend<U64PF>>::build(&values).unwrap();
+        let hashed_leaves = TestBackend::<U64PF>::hash_leaves(&values);
+        let rebuilt = MerkleTree::<TestBackend<U64PF>>::build_from_hashed_leaves(hashed_leaves);
+
+        assert_eq!(rebuilt.unwrap().nodes, merkle_tree.nodes);
+    }
+
     #[test]
     fn build_empty_tree_should_not_panic() {
         assert!(MerkleTree::<TestBackend<U64PF>>::build(&[]).is_none());
     }
+
+    #[cfg(feature = "proptest")]
+    proptest::proptest! {
+        #[test]
+        fn every_leaf_has_a_proof_with_a_node_per_level(
+            merkle_tree in proptest::prelude::any::<MerkleTree<TestBackend<U64PF>>>()
+        ) {
+            let leaves_len = (merkle_tree.nodes.len() + 1) / 2;
+            for pos in 0..leaves_len {
+                let proof = merkle_tree.get_proof_by_pos(pos).unwrap();
+                proptest::prop_assert_eq!(
+                    proof.merkle_path.len(),
+                    leaves_len.trailing_zeros() as usize
+                );
+            }
+            proptest::prop_assert!(merkle_tree.get_proof_by_pos(leaves_len).is_none());
+        }
+    }
 }
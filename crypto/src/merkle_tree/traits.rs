@@ -1,3 +1,4 @@
+use super::utils::build;
 use alloc::vec::Vec;
 #[cfg(feature = "parallel")]
 use rayon::prelude::{IntoParallelRefIterator, ParallelIterator};
@@ -26,4 +27,10 @@ pub trait IsMerkleTreeBackend: Default {
     /// This function takes to children nodes and builds a new parent node.
     /// It will be used in the construction of the Merkle tree.
     fn hash_new_parent(child_1: &Self::Node, child_2: &Self::Node) -> Self::Node;
+
+    /// This function takes the nodes of the tree, in breadth-first order and with the
+    /// `leaves_len` leaf nodes at the end, and builds the inner nodes level by level.
+    fn build_inner_nodes(nodes: &mut [Self::Node], leaves_len: usize) {
+        build::<Self>(nodes, leaves_len);
+    }
 }
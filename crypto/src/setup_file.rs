@@ -0,0 +1,512 @@
+//! Binary container for trusted setup artifacts (structured reference strings, proving
+//! keys and verification keys), proofs and public inputs. It is the canonical encoding of
+//! these artifacts across the provers of the workspace.
+//!
+//! magic `lwsf` | version (u32) | artifact kind (u32) | number of sections (u32) | sections
+//!
+//! where every section is: id (u32) | element size (u32) | number of elements (u64) | elements.
+//! All integers are little endian. Elements of a section share their size, so sections can be
+//! written and read one element (or one chunk of elements) at a time and the prefix of a section
+//! can be read without touching the rest. Large setups never need to be held in memory as a
+//! single buffer.
+//!
+//! Field elements are written as their little endian canonical representatives, curve points
+//! and commitments with their [`AsBytes`] implementation. Lists of variable length elements,
+//! like Merkle paths, are written as a section with the lengths (u64) followed by a section
+//! with all the elements.
+//!
+//! Readers give the size of the elements they expect when they open a section, so that a
+//! corrupted or malicious header can't make them allocate more than an element of the expected
+//! encoding.
+//!
+//! Readers accept files of every version up to [`SETUP_FILE_VERSION`], so that artifacts
+//! written by previous releases can still be read. Changes to the layout of a kind of artifact
+//! bump the version.
+
+use std::io::{self, Read, Write};
+
+use lambdaworks_math::{
+    cyclic_group::IsGroup,
+    errors::DeserializationError,
+    field::{element::FieldElement, traits::IsField},
+    traits::{AsBytes, ByteConversion, Deserializable},
+};
+
+use crate::errors::SetupFileError;
+
+pub const SETUP_FILE_MAGIC: &[u8; 4] = b"lwsf";
+/// Version written by [`SetupFileWriter`]. Readers accept files up to this version.
+pub const SETUP_FILE_VERSION: u32 = 1;
+
+/// Kinds of artifacts, so that a file is not mistaken for another one.
+pub const SRS_KIND: u32 = 1;
+pub const GROTH16_PROVING_KEY_KIND: u32 = 2;
+pub const GROTH16_VERIFYING_KEY_KIND: u32 = 3;
+pub const PLONK_COMMON_PREPROCESSED_INPUT_KIND: u32 = 4;
+pub const PLONK_VERIFICATION_KEY_KIND: u32 = 5;
+pub const PLONK_PROOF_KIND: u32 = 6;
+pub const STARK_PROOF_KIND: u32 = 7;
+pub const PUBLIC_INPUTS_KIND: u32 = 8;
+
+const PUBLIC_INPUTS_SECTION: u32 = 1;
+
+/// Size of the elements of sections of u64s, as the lengths of lists are written.
+pub const U64_SIZE: usize = 8;
+
+/// Writes the sections of a setup file, in order.
+pub struct SetupFileWriter<W: Write> {
+    writer: W,
+    remaining_sections: u32,
+    remaining_elements: u64,
+    element_size: u32,
+}
+
+impl<W: Write> SetupFileWriter<W> {
+    /// Writes the header of a file of the given `kind` with `num_sections` sections.
+    pub fn new(mut writer: W, kind: u32, num_sections: u32) -> Result<Self, SetupFileError> {
+        writer.write_all(SETUP_FILE_MAGIC)?;
+        writer.write_all(&SETUP_FILE_VERSION.to_le_bytes())?;
+        writer.write_all(&kind.to_le_bytes())?;
+        writer.write_all(&num_sections.to_le_bytes())?;
+        Ok(Self {
+            writer,
+            remaining_sections: num_sections,
+            remaining_elements: 0,
+            element_size: 0,
+        })
+    }
+
+    /// Starts a section of `len` elements of `element_size` bytes each, to be written with
+    /// [`Self::write_element`].
+    pub fn begin_section(
+        &mut self,
+        id: u32,
+        element_size: u32,
+        len: u64,
+    ) -> Result<(), SetupFileError> {
+        if self.remaining_elements != 0 || self.remaining_sections == 0 {
+            return Err(SetupFileError::UnexpectedSection(id));
+        }
+        self.writer.write_all(&id.to_le_bytes())?;
+        self.writer.write_all(&element_size.to_le_bytes())?;
+        self.writer.write_all(&len.to_le_bytes())?;
+        self.remaining_sections -= 1;
+        self.remaining_elements = len;
+        self.element_size = element_size;
+        Ok(())
+    }
+
+    pub fn write_element(&mut self, bytes: &[u8]) -> Result<(), SetupFileError> {
+        if self.remaining_elements == 0 || bytes.len() != self.element_size as usize {
+            return Err(SetupFileError::InvalidElementSize);
+        }
+        self.writer.write_all(bytes)?;
+        self.remaining_elements -= 1;
+        Ok(())
+    }
+
+    /// Writes a whole section. All elements must serialize to the same number of bytes.
+    pub fn write_section<'a, T, I>(&mut self, id: u32, elements: I) -> Result<(), SetupFileError>
+    where
+        T: AsBytes + 'a,
+        I: IntoIterator<Item = &'a T>,
+        I::IntoIter: ExactSizeIterator,
+    {
+        self.write_section_with(id, elements, T::as_bytes)
+    }
+
+    /// Writes a whole section, serializing every element with `encode`.
+    pub fn write_section_with<'a, T, I>(
+        &mut self,
+        id: u32,
+        elements: I,
+        encode: impl Fn(&T) -> Vec<u8>,
+    ) -> Result<(), SetupFileError>
+    where
+        T: 'a,
+        I: IntoIterator<Item = &'a T>,
+        I::IntoIter: ExactSizeIterator,
+    {
+        let mut elements = elements.into_iter().peekable();
+        let len = elements.len() as u64;
+        let element_size = elements.peek().map_or(0, |element| encode(element).len());
+        self.begin_section(id, element_size as u32, len)?;
+        for element in elements {
+            self.write_element(&encode(element))?;
+        }
+        Ok(())
+    }
+
+    /// Checks that every declared section was written and returns the inner writer.
+    pub fn finish(mut self) -> Result<W, SetupFileError> {
+        if self.remaining_sections != 0 || self.remaining_elements != 0 {
+            return Err(SetupFileError::MissingSection);
+        }
+        self.writer.flush()?;
+        Ok(self.writer)
+    }
+}
+
+/// Reads the sections of a setup file, in order.
+pub struct SetupFileReader<R: Read> {
+    reader: R,
+    pub version: u32,
+    pub kind: u32,
+    remaining_sections: u32,
+    /// Elements of the current section that have not been read yet.
+    remaining_elements: u64,
+    element_size: usize,
+}
+
+impl<R: Read> SetupFileReader<R> {
+    /// Reads the header of a file, which must be of the given `kind`.
+    pub fn new(mut reader: R, kind: u32) -> Result<Self, SetupFileError> {
+        let mut magic = [0u8; 4];
+        reader.read_exact(&mut magic)?;
+        if &magic != SETUP_FILE_MAGIC {
+            return Err(SetupFileError::InvalidMagic);
+        }
+        let version = read_u32(&mut reader)?;
+        if version == 0 || version > SETUP_FILE_VERSION {
+            return Err(SetupFileError::UnsupportedVersion(version));
+        }
+        let file_kind = read_u32(&mut reader)?;
+        if file_kind != kind {
+            return Err(SetupFileError::UnexpectedKind(file_kind));
+        }
+        let remaining_sections = read_u32(&mut reader)?;
+        Ok(Self {
+            reader,
+            version,
+            kind,
+            remaining_sections,
+            remaining_elements: 0,
+            element_size: 0,
+        })
+    }
+
+    /// Returns the next section, which must have the given `id` and hold elements of
+    /// `element_size` bytes, unless it's empty. The elements of the previous section that were
+    /// not read are skipped.
+    pub fn section(
+        &mut self,
+        id: u32,
+        element_size: usize,
+    ) -> Result<SectionReader<'_, R>, SetupFileError> {
+        self.skip_remaining_elements()?;
+        if self.remaining_sections == 0 {
+            return Err(SetupFileError::MissingSection);
+        }
+        let section_id = read_u32(&mut self.reader)?;
+        if section_id != id {
+            return Err(SetupFileError::UnexpectedSection(section_id));
+        }
+        let section_element_size = read_u32(&mut self.reader)? as usize;
+        let remaining_elements = read_u64(&mut self.reader)?;
+        if remaining_elements != 0 && section_element_size != element_size {
+            return Err(SetupFileError::InvalidElementSize);
+        }
+        self.element_size = element_size;
+        self.remaining_elements = remaining_elements;
+        self.remaining_sections -= 1;
+        Ok(SectionReader { file: self })
+    }
+
+    fn skip_remaining_elements(&mut self) -> Result<(), SetupFileError> {
+        let len = self
+            .remaining_elements
+            .checked_mul(self.element_size as u64)
+            .ok_or(SetupFileError::SectionTooLarge)?;
+        let skipped = io::copy(&mut (&mut self.reader).take(len), &mut io::sink())?;
+        if skipped != len {
+            return Err(io::Error::from(io::ErrorKind::UnexpectedEof).into());
+        }
+        self.remaining_elements = 0;
+        Ok(())
+    }
+}
+
+/// Reads the elements of a section.
+pub struct SectionReader<'a, R: Read> {
+    file: &'a mut SetupFileReader<R>,
+}
+
+impl<'a, R: Read> SectionReader<'a, R> {
+    /// Number of elements left in the section.
+    pub fn remaining(&self) -> u64 {
+        self.file.remaining_elements
+    }
+
+    pub fn element_size(&self) -> usize {
+        self.file.element_size
+    }
+
+    /// Reads the next element and decodes it with `decode`.
+    pub fn read_with<T>(
+        &mut self,
+        decode: impl Fn(&[u8]) -> Result<T, DeserializationError>,
+    ) -> Result<T, SetupFileError> {
+        if self.file.remaining_elements == 0 {
+            return Err(SetupFileError::UnexpectedEndOfSection);
+        }
+        let mut bytes = vec![0u8; self.file.element_size];
+        self.file.reader.read_exact(&mut bytes)?;
+        self.file.remaining_elements -= 1;
+        Ok(decode(&bytes)?)
+    }
+
+    pub fn read<T: Deserializable>(&mut self) -> Result<T, SetupFileError> {
+        self.read_with(T::deserialize)
+    }
+
+    /// Reads up to `max_len` elements. Returns an empty vector once the section is exhausted.
+    pub fn read_chunk<T: Deserializable>(
+        &mut self,
+        max_len: usize,
+    ) -> Result<Vec<T>, SetupFileError> {
+        let len = self.remaining().min(max_len as u64) as usize;
+        (0..len).map(|_| self.read()).collect()
+    }
+
+    /// Reads the remaining elements of the section.
+    pub fn read_all<T: Deserializable>(&mut self) -> Result<Vec<T>, SetupFileError> {
+        self.read_all_with(T::deserialize)
+    }
+
+    pub fn read_all_with<T>(
+        &mut self,
+        decode: impl Fn(&[u8]) -> Result<T, DeserializationError>,
+    ) -> Result<Vec<T>, SetupFileError> {
+        let len = usize::try_from(self.remaining()).map_err(|_| SetupFileError::SectionTooLarge)?;
+        (0..len).map(|_| self.read_with(&decode)).collect()
+    }
+}
+
+/// Writes the public inputs of a proof as a file of a single section of field elements.
+pub fn write_public_inputs<F, W>(
+    writer: W,
+    public_inputs: &[FieldElement<F>],
+) -> Result<(), SetupFileError>
+where
+    F: IsField,
+    FieldElement<F>: ByteConversion,
+    W: Write,
+{
+    let mut file = SetupFileWriter::new(writer, PUBLIC_INPUTS_KIND, 1)?;
+    file.write_section_with(
+        PUBLIC_INPUTS_SECTION,
+        public_inputs,
+        FieldElement::<F>::to_bytes_le,
+    )?;
+    file.finish()?;
+    Ok(())
+}
+
+/// Reads public inputs written by [`write_public_inputs`].
+pub fn read_public_inputs<F, R>(reader: R) -> Result<Vec<FieldElement<F>>, SetupFileError>
+where
+    F: IsField,
+    FieldElement<F>: ByteConversion,
+    R: Read,
+{
+    let mut file = SetupFileReader::new(reader, PUBLIC_INPUTS_KIND)?;
+    file.section(PUBLIC_INPUTS_SECTION, field_element_size::<F>())?
+        .read_all_with(field_element_from_bytes_le)
+}
+
+/// Size of the elements of sections of field elements of `F`.
+pub fn field_element_size<F>() -> usize
+where
+    F: IsField,
+    FieldElement<F>: ByteConversion,
+{
+    FieldElement::<F>::zero().to_bytes_le().len()
+}
+
+/// Size of the elements of sections of points of `P`, which are all written with as many bytes
+/// as the neutral element.
+pub fn point_size<P: IsGroup + AsBytes>() -> usize {
+    P::neutral_element().as_bytes().len()
+}
+
+/// Decodes the little endian representation of a field element, as written in setup files.
+pub fn field_element_from_bytes_le<F>(bytes: &[u8]) -> Result<FieldElement<F>, DeserializationError>
+where
+    F: IsField,
+    FieldElement<F>: ByteConversion,
+{
+    Ok(FieldElement::from_bytes_le(bytes)?)
+}
+
+/// Decodes a u64 element, as the lengths of lists are written.
+pub fn u64_from_bytes(bytes: &[u8]) -> Result<u64, DeserializationError> {
+    let bytes = bytes
+        .try_into()
+        .map_err(|_| DeserializationError::InvalidAmountOfBytes)?;
+    Ok(u64::from_le_bytes(bytes))
+}
+
+fn read_u32<R: Read>(reader: &mut R) -> Result<u32, SetupFileError> {
+    let mut bytes = [0u8; 4];
+    reader.read_exact(&mut bytes)?;
+    Ok(u32::from_le_bytes(bytes))
+}
+
+fn read_u64<R: Read>(reader: &mut R) -> Result<u64, SetupFileError> {
+    let mut bytes = [0u8; 8];
+    reader.read_exact(&mut bytes)?;
+    Ok(u64::from_le_bytes(bytes))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use lambdaworks_math::field::fields::fft_friendly::stark_252_prime_field::Stark252PrimeField;
+
+    const KIND: u32 = 100;
+
+    fn two_sections() -> Vec<u8> {
+        let mut writer = SetupFileWriter::new(Vec::new(), KIND, 2).unwrap();
+        writer.write_section(1, &[1u64, 2, 3, 4, 5]).unwrap();
+        writer.write_section(2, &[6u32]).unwrap();
+        writer.finish().unwrap()
+    }
+
+    #[test]
+    fn sections_are_read_element_by_element() {
+        let bytes = two_sections();
+        let mut reader = SetupFileReader::new(bytes.as_slice(), KIND).unwrap();
+        assert_eq!(reader.version, SETUP_FILE_VERSION);
+
+        let mut section = reader.section(1, U64_SIZE).unwrap();
+        assert_eq!(section.remaining(), 5);
+        assert_eq!(section.element_size(), 8);
+        assert_eq!(section.read_with(u64_from_bytes).unwrap(), 1);
+        assert_eq!(
+            section.read_all_with(u64_from_bytes).unwrap(),
+            vec![2, 3, 4, 5]
+        );
+        assert!(matches!(
+            section.read_with(u64_from_bytes),
+            Err(SetupFileError::UnexpectedEndOfSection)
+        ));
+    }
+
+    #[test]
+    fn sections_can_be_skipped() {
+        let bytes = two_sections();
+        let mut reader = SetupFileReader::new(bytes.as_slice(), KIND).unwrap();
+
+        let mut section = reader.section(1, U64_SIZE).unwrap();
+        section.read_with(u64_from_bytes).unwrap();
+        let section = reader.section(2, 4).unwrap();
+        assert_eq!(section.element_size(), 4);
+        assert_eq!(section.remaining(), 1);
+        assert!(matches!(
+            reader.section(3, 4).err(),
+            Some(SetupFileError::MissingSection)
+        ));
+    }
+
+    #[test]
+    fn invalid_headers_are_rejected() {
+        let bytes = two_sections();
+        assert!(matches!(
+            SetupFileReader::new(bytes.as_slice(), KIND + 1).err(),
+            Some(SetupFileError::UnexpectedKind(KIND))
+        ));
+        let mut reader = SetupFileReader::new(bytes.as_slice(), KIND).unwrap();
+        assert!(matches!(
+            reader.section(2, 4).err(),
+            Some(SetupFileError::UnexpectedSection(1))
+        ));
+
+        let mut newer_version = bytes.clone();
+        newer_version[4..8].copy_from_slice(&(SETUP_FILE_VERSION + 1).to_le_bytes());
+        assert!(matches!(
+            SetupFileReader::new(newer_version.as_slice(), KIND).err(),
+            Some(SetupFileError::UnsupportedVersion(version)) if version == SETUP_FILE_VERSION + 1
+        ));
+
+        let mut wrong_magic = bytes;
+        wrong_magic[0] = b'x';
+        assert!(matches!(
+            SetupFileReader::new(wrong_magic.as_slice(), KIND).err(),
+            Some(SetupFileError::InvalidMagic)
+        ));
+    }
+
+    #[test]
+    fn sections_of_another_element_size_are_rejected() {
+        let bytes = two_sections();
+        let mut reader = SetupFileReader::new(bytes.as_slice(), KIND).unwrap();
+        assert!(matches!(
+            reader.section(1, 4).err(),
+            Some(SetupFileError::InvalidElementSize)
+        ));
+
+        // A header claiming elements of 4 GiB is rejected before any of them is allocated.
+        let mut huge_elements = bytes;
+        huge_elements[20..24].copy_from_slice(&u32::MAX.to_le_bytes());
+        let mut reader = SetupFileReader::new(huge_elements.as_slice(), KIND).unwrap();
+        assert!(matches!(
+            reader.section(1, U64_SIZE).err(),
+            Some(SetupFileError::InvalidElementSize)
+        ));
+    }
+
+    #[test]
+    fn skipping_a_section_too_large_for_its_length_fails() {
+        let mut writer = SetupFileWriter::new(Vec::new(), KIND, 2).unwrap();
+        writer.begin_section(1, 8, u64::MAX).unwrap();
+        writer.write_element(&[0u8; 8]).unwrap();
+        let bytes = writer.writer;
+
+        let mut reader = SetupFileReader::new(bytes.as_slice(), KIND).unwrap();
+        reader.section(1, U64_SIZE).unwrap();
+        assert!(matches!(
+            reader.section(2, U64_SIZE).err(),
+            Some(SetupFileError::SectionTooLarge)
+        ));
+    }
+
+    #[test]
+    fn writer_checks_declared_sections() {
+        let mut writer = SetupFileWriter::new(Vec::new(), KIND, 2).unwrap();
+        writer.write_section(1, &[1u64]).unwrap();
+        assert!(matches!(
+            writer.finish().err(),
+            Some(SetupFileError::MissingSection)
+        ));
+
+        let mut writer = SetupFileWriter::new(Vec::new(), KIND, 1).unwrap();
+        writer.begin_section(1, 8, 2).unwrap();
+        assert!(matches!(
+            writer.write_element(&[0u8; 4]),
+            Err(SetupFileError::InvalidElementSize)
+        ));
+    }
+
+    #[test]
+    fn public_inputs_have_a_stable_encoding() {
+        type FE = FieldElement<Stark252PrimeField>;
+        let public_inputs = [FE::from(1), FE::from(0x0203)];
+
+        let mut bytes = Vec::new();
+        write_public_inputs(&mut bytes, &public_inputs).unwrap();
+
+        // Files written by version 1 must keep being readable as they are.
+        let mut expected = b"lwsf".to_vec();
+        expected.extend([1, 0, 0, 0, 8, 0, 0, 0, 1, 0, 0, 0]);
+        expected.extend([1, 0, 0, 0, 32, 0, 0, 0, 2, 0, 0, 0, 0, 0, 0, 0]);
+        expected.extend([1]);
+        expected.extend([0; 31]);
+        expected.extend([3, 2]);
+        expected.extend([0; 30]);
+        assert_eq!(bytes, expected);
+        assert_eq!(
+            read_public_inputs::<Stark252PrimeField, _>(expected.as_slice()).unwrap(),
+            public_inputs
+        );
+    }
+}
@@ -13,7 +13,7 @@ fn main() {
         &fs::read_to_string(format!("{TEST_DIR}witness.json")).expect("Error reading the file");
 
     println!("\nConverting to Lambdaworks-compatible QAP and witness assignments");
-    let (qap, w) = circom_to_lambda(r1cs_file_content, witness_file_content);
+    let (qap, w) = circom_to_lambda::<common::Pairing>(r1cs_file_content, witness_file_content);
 
     println!("\nPerforming trusted setup");
     let (pk, vk) = setup(&qap);
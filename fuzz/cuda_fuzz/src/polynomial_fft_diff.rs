@@ -10,6 +10,8 @@ use lambdaworks_math::{
     }
 };
 
+type F = Stark252PrimeField;
+
 fn main() {
     loop {
         fuzz!(|data: Vec<u64>| {
@@ -22,10 +24,10 @@ fn main() {
 
             for i in 0..inputs_raw.len() {
                 let input_value = format!("{:x}", inputs_raw[i]);
-                inputs.push(FieldElement::<Stark252PrimeField>::from_hex_unchecked(&input_value))
+                inputs.push(FieldElement::<F>::from_hex_unchecked(&input_value))
             }
 
-            let (fft_eval_cuda, fft_eval_cpu) = match (evaluate_fft_cuda(&inputs), evaluate_fft_cpu(&inputs)) {
+            let (fft_eval_cuda, fft_eval_cpu) = match (evaluate_fft_cuda::<F, F>(&inputs), evaluate_fft_cpu::<F, F>(&inputs)) {
                 (Ok(fft_eval_cuda), Ok(fft_eval_cpu)) => {
                     assert_eq!(fft_eval_cuda, fft_eval_cpu);
                     (fft_eval_cuda.clone(), fft_eval_cpu.clone())
@@ -36,7 +38,7 @@ fn main() {
                 (cuda, cpu) => panic!("Evaluate results didn't match. cuda.is_err(): {}, cpu.is_err(): {}", cuda.is_err(), cpu.is_err())
             };
 
-            match (interpolate_fft_cuda(&fft_eval_cuda), interpolate_fft_cpu(&fft_eval_cpu)) {
+            match (interpolate_fft_cuda::<F, F>(&fft_eval_cuda), interpolate_fft_cpu::<F, F>(&fft_eval_cpu)) {
                 (Ok(interpolated_cuda), Ok(interpolated_cpu)) => {
                     assert_eq!(interpolated_cuda, interpolated_cpu);
                 },
@@ -0,0 +1,24 @@
+#![no_main]
+use libfuzzer_sys::fuzz_target;
+use lambdaworks_math::{
+    elliptic_curve::short_weierstrass::curves::bls12_381::{
+        curve::BLS12381Curve, field_extension::BLS12381PrimeField, twist::BLS12381TwistCurve,
+    },
+    elliptic_curve::short_weierstrass::point::ShortWeierstrassProjectivePoint,
+    field::element::FieldElement,
+    traits::CanonicalByteConversion,
+};
+
+// Canonical encodings are unique, so every value that decodes must encode back to the same
+// bytes.
+fuzz_target!(|data: &[u8]| {
+    if let Ok(element) = FieldElement::<BLS12381PrimeField>::from_bytes_be_canonical(data) {
+        assert_eq!(element.to_bytes_be_array().as_ref(), data);
+    }
+    if let Ok(point) = ShortWeierstrassProjectivePoint::<BLS12381Curve>::from_bytes_be_canonical(data) {
+        assert_eq!(point.to_bytes_be_array().as_ref(), data);
+    }
+    if let Ok(point) = ShortWeierstrassProjectivePoint::<BLS12381TwistCurve>::from_bytes_le_canonical(data) {
+        assert_eq!(point.to_bytes_le_array().as_ref(), data);
+    }
+});
@@ -0,0 +1,18 @@
+#![no_main]
+use libfuzzer_sys::fuzz_target;
+use lambdaworks_math::elliptic_curve::short_weierstrass::curves::bls12_381::default_types::FrField;
+use lambdaworks_plonk::{
+    prover::Proof,
+    setup::{CommonPreprocessedInput, VerificationKey},
+    test_utils::utils::{G1Point, KZG},
+};
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(proof) = Proof::<FrField, KZG>::read_from(data) {
+        let mut bytes = Vec::new();
+        proof.write_to(&mut bytes).unwrap();
+        assert!(Proof::<FrField, KZG>::read_from(bytes.as_slice()).is_ok());
+    }
+    let _ = VerificationKey::<G1Point>::read_from(data);
+    let _ = CommonPreprocessedInput::<FrField>::read_from(data);
+});
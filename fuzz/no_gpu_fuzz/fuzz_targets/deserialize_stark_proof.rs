@@ -2,11 +2,13 @@
 use libfuzzer_sys::fuzz_target;
 use stark_platinum_prover::proof::stark::StarkProof;
 use lambdaworks_math::field::fields::fft_friendly::stark_252_prime_field::Stark252PrimeField;
-use lambdaworks_math::traits::Deserializable;
-
-
-fuzz_target!(|data: Vec<u8>| {
-    
-    let _proof = StarkProof::<Stark252PrimeField>::deserialize(&data);
 
+fuzz_target!(|data: &[u8]| {
+    if let Ok(proof) = StarkProof::<Stark252PrimeField, Stark252PrimeField>::read_from(data) {
+        let mut bytes = Vec::new();
+        proof.write_to(&mut bytes).unwrap();
+        let decoded = StarkProof::<Stark252PrimeField, Stark252PrimeField>::read_from(bytes.as_slice()).unwrap();
+        assert_eq!(decoded.trace_length, proof.trace_length);
+        assert_eq!(decoded.fri_last_layer_coefficients, proof.fri_last_layer_coefficients);
+    }
 });
@@ -0,0 +1,27 @@
+#![no_main]
+use libfuzzer_sys::fuzz_target;
+use stark_platinum_prover::{
+    examples::simple_fibonacci::{FibonacciAIR, FibonacciPublicInputs},
+    proof::{options::ProofOptions, stark::StarkProof},
+    transcript::StoneProverTranscript,
+    verifier::{IsStarkVerifier, Verifier},
+    Felt252, PrimeField,
+};
+
+// The verifier must reject malformed proofs without panicking.
+fuzz_target!(|data: &[u8]| {
+    let Ok(proof) = StarkProof::<PrimeField, PrimeField>::read_from(data) else {
+        return;
+    };
+    let pub_inputs = FibonacciPublicInputs {
+        a0: Felt252::one(),
+        a1: Felt252::one(),
+    };
+
+    let _ = Verifier::<FibonacciAIR<PrimeField>>::verify(
+        &proof,
+        &pub_inputs,
+        &ProofOptions::default_test_options(),
+        StoneProverTranscript::new(&[]),
+    );
+});
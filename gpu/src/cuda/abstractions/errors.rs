@@ -16,6 +16,8 @@ pub enum CudaError {
     RetrieveMemory(String),
     #[error("Couldn't launch CUDA function: {0}")]
     Launch(String),
+    #[error("Scalars and points must be of the same length. Got: {0} and {1}")]
+    LengthMismatch(usize, usize),
     #[error("Index out of bounds: {0}. Length of buffer is {0}")]
     IndexOutOfBounds(usize, usize),
 }
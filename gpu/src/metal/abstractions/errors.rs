@@ -14,4 +14,6 @@ pub enum MetalError {
     RootOfUnityError(String, u64),
     #[error("Input length is {0}, which is not a power of two")]
     InputError(usize),
+    #[error("Scalars and points must be of the same length. Got: {0} and {1}")]
+    LengthMismatch(usize, usize),
 }
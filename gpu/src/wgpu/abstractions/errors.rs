@@ -0,0 +1,21 @@
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum WgpuError {
+    #[error("Couldn't find a WebGPU adapter")]
+    DeviceNotFound(),
+    #[error("Couldn't open a WebGPU device: {0}")]
+    RequestDevice(String),
+    #[error("Couldn't create a new WebGPU shader module: {0}")]
+    ShaderError(String),
+    #[error("There is no WebGPU kernel for {0}")]
+    FunctionError(String),
+    #[error("Couldn't create a new WebGPU compute pipeline: {0}")]
+    PipelineError(String),
+    #[error("Input length is {0}, which is not a power of two")]
+    InputError(usize),
+    #[error("Scalars and points must be of the same length. Got: {0} and {1}")]
+    LengthMismatch(usize, usize),
+    #[error("Couldn't retrieve information from GPU: {0}")]
+    RetrieveMemory(String),
+}
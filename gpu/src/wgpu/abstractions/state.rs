@@ -0,0 +1,209 @@
+use wgpu::{
+    util::{BufferInitDescriptor, DeviceExt},
+    Buffer, BufferDescriptor, BufferUsages, CommandEncoder, ComputePipeline, Device, ErrorFilter,
+    Queue, ShaderModule,
+};
+
+use super::errors::WgpuError;
+
+use core::mem;
+use std::{borrow::Cow, sync::mpsc};
+
+/// Threads of each workgroup, which must match `WORKGROUP_SIZE` of the shaders.
+pub const WORKGROUP_SIZE: u32 = 64;
+
+/// Structure for abstracting basic calls to a WebGPU device and saving the state. Used for
+/// implementing GPU parallel computations with any of the native backends of `wgpu` (Vulkan,
+/// Metal, DX12) or in the browser, without vendor-specific toolchains.
+pub struct WgpuState {
+    pub device: Device,
+    pub queue: Queue,
+}
+
+impl WgpuState {
+    /// Creates a new WebGPU state with the system's default high performance adapter (GPU).
+    pub fn new() -> Result<Self, WgpuError> {
+        let instance = wgpu::Instance::default();
+        let adapter = pollster::block_on(instance.request_adapter(&wgpu::RequestAdapterOptions {
+            power_preference: wgpu::PowerPreference::HighPerformance,
+            ..Default::default()
+        }))
+        .ok_or(WgpuError::DeviceNotFound())?;
+
+        let (device, queue) = pollster::block_on(adapter.request_device(
+            &wgpu::DeviceDescriptor {
+                label: None,
+                required_features: wgpu::Features::empty(),
+                // Big buffers need the largest storage bindings the adapter can give
+                required_limits: adapter.limits(),
+            },
+            None,
+        ))
+        .map_err(|err| WgpuError::RequestDevice(err.to_string()))?;
+
+        Ok(Self { device, queue })
+    }
+
+    /// Compiles the WGSL `source` into a shader module, whose entry points are used to create
+    /// pipelines.
+    pub fn load_shader(&self, source: &str) -> Result<ShaderModule, WgpuError> {
+        self.device.push_error_scope(ErrorFilter::Validation);
+        let module = self
+            .device
+            .create_shader_module(wgpu::ShaderModuleDescriptor {
+                label: None,
+                source: wgpu::ShaderSource::Wgsl(Cow::Borrowed(source)),
+            });
+        match pollster::block_on(self.device.pop_error_scope()) {
+            Some(err) => Err(WgpuError::ShaderError(err.to_string())),
+            None => Ok(module),
+        }
+    }
+
+    /// Creates a pipeline based on the compute function `entry_point` of `module`. Its bind
+    /// group only has the bindings that the function uses.
+    pub fn setup_pipeline(
+        &self,
+        module: &ShaderModule,
+        entry_point: &str,
+    ) -> Result<ComputePipeline, WgpuError> {
+        self.device.push_error_scope(ErrorFilter::Validation);
+        let pipeline = self
+            .device
+            .create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+                label: Some(entry_point),
+                layout: None,
+                module,
+                entry_point,
+            });
+        match pollster::block_on(self.device.pop_error_scope()) {
+            Some(err) => Err(WgpuError::PipelineError(err.to_string())),
+            None => Ok(pipeline),
+        }
+    }
+
+    /// Allocates a storage buffer of `length` elements of `T`, which can be copied from and to.
+    pub fn alloc_buffer<T>(&self, length: usize) -> Buffer {
+        self.device.create_buffer(&BufferDescriptor {
+            label: None,
+            size: (length * mem::size_of::<T>()) as u64,
+            usage: BufferUsages::STORAGE | BufferUsages::COPY_SRC | BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        })
+    }
+
+    /// Allocates `data` in a storage buffer, which can be copied from and to.
+    pub fn alloc_buffer_data<T>(&self, data: &[T]) -> Buffer {
+        // SAFETY: the bytes are only copied to the device
+        let bytes = unsafe {
+            core::slice::from_raw_parts(data.as_ptr() as *const u8, mem::size_of_val(data))
+        };
+        self.device.create_buffer_init(&BufferInitDescriptor {
+            label: None,
+            contents: bytes,
+            usage: BufferUsages::STORAGE | BufferUsages::COPY_SRC | BufferUsages::COPY_DST,
+        })
+    }
+
+    /// Creates a command encoder, where dispatches are recorded until it's submitted with
+    /// [`Self::submit`].
+    pub fn setup_command(&self) -> CommandEncoder {
+        self.device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor { label: None })
+    }
+
+    /// Records `threads` threads of `pipeline` in `encoder`, with `buffers` bound to their
+    /// indices of group 0.
+    ///
+    /// Threads are grouped in workgroups of [`WORKGROUP_SIZE`], laid out in two dimensions since
+    /// each one has a limited size, so the last workgroups may have threads past `threads`.
+    /// Kernels flatten their position and return early if it's out of bounds.
+    ///
+    /// Dispatches of the same encoder run one after the other, each one seeing the writes of the
+    /// previous ones.
+    pub fn dispatch(
+        &self,
+        encoder: &mut CommandEncoder,
+        pipeline: &ComputePipeline,
+        buffers: &[(u32, &Buffer)],
+        threads: usize,
+    ) {
+        let entries: Vec<_> = buffers
+            .iter()
+            .map(|(binding, buffer)| wgpu::BindGroupEntry {
+                binding: *binding,
+                resource: buffer.as_entire_binding(),
+            })
+            .collect();
+        let bind_group = self.device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: None,
+            layout: &pipeline.get_bind_group_layout(0),
+            entries: &entries,
+        });
+
+        let groups = (threads as u32).div_ceil(WORKGROUP_SIZE).max(1);
+        let groups_x = groups.min(self.device.limits().max_compute_workgroups_per_dimension);
+        let groups_y = groups.div_ceil(groups_x);
+
+        let mut pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
+            label: None,
+            timestamp_writes: None,
+        });
+        pass.set_pipeline(pipeline);
+        pass.set_bind_group(0, &bind_group, &[]);
+        pass.dispatch_workgroups(groups_x, groups_y, 1);
+    }
+
+    /// Submits the commands of `encoder` to the queue.
+    pub fn submit(&self, encoder: CommandEncoder) {
+        self.queue.submit(Some(encoder.finish()));
+    }
+
+    /// Waits for the submitted commands to complete and returns a copy of the data that `buffer`
+    /// holds, interpreting it into a specific type `T`.
+    ///
+    /// SAFETY: this function uses an unsafe function for retrieveing the data, if the buffer's
+    /// contents don't match the specified `T`, expect undefined behaviour. Always make sure the
+    /// buffer you are retreiving from holds data of type `T`.
+    pub fn retrieve_contents<T: Clone>(&self, buffer: &Buffer) -> Result<Vec<T>, WgpuError> {
+        // Storage buffers can't be mapped, so the data goes through a staging buffer
+        let staging_buffer = self.device.create_buffer(&BufferDescriptor {
+            label: None,
+            size: buffer.size(),
+            usage: BufferUsages::MAP_READ | BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+        let mut encoder = self.setup_command();
+        encoder.copy_buffer_to_buffer(buffer, 0, &staging_buffer, 0, buffer.size());
+        self.submit(encoder);
+
+        let slice = staging_buffer.slice(..);
+        let (sender, receiver) = mpsc::channel();
+        slice.map_async(wgpu::MapMode::Read, move |result| {
+            let _ = sender.send(result);
+        });
+        self.device.poll(wgpu::Maintain::Wait).panic_on_timeout();
+        receiver
+            .recv()
+            .map_err(|err| WgpuError::RetrieveMemory(err.to_string()))?
+            .map_err(|err| WgpuError::RetrieveMemory(err.to_string()))?;
+
+        let contents = {
+            let bytes = slice.get_mapped_range();
+            let ptr = bytes.as_ptr() as *const T;
+            let len = bytes.len() / mem::size_of::<T>();
+            let mut contents = Vec::with_capacity(len);
+            for i in 0..len {
+                // 1. Read possibly unaligned data producing a bitwise copy
+                let val = unsafe { ptr.add(i).read_unaligned() };
+                // 2. Clone into the vector to avoid both `contents` and `bytes` dropping it
+                contents.push(val.clone());
+                // 3. Forget the bitwise copy to avoid both `val` and `bytes` dropping it
+                core::mem::forget(val);
+            }
+            contents
+        };
+        staging_buffer.unmap();
+        Ok(contents)
+    }
+}
@@ -0,0 +1,278 @@
+//! Conversions between the field elements and curve points of lambdaworks and the ones of
+//! arkworks, for BN254, BLS12-381 and the Pasta curves.
+//!
+//! Field elements convert with [`From`] in both directions, since both libraries use the same
+//! moduli. Points convert into arkworks with [`From`], and from arkworks with [`TryFrom`],
+//! which checks that the point is on the curve, as [`FromAffine::from_affine`] does.
+
+use ark_ec::CurveGroup;
+use ark_ff::{BigInt, PrimeField};
+
+use crate::{
+    cyclic_group::IsGroup,
+    elliptic_curve::{
+        short_weierstrass::{
+            curves::{
+                bls12_381::{
+                    curve::BLS12381Curve,
+                    default_types::FrField as BLS12381FrField,
+                    field_extension::{
+                        BLS12381PrimeField, Degree2ExtensionField as BLS12381Degree2ExtensionField,
+                    },
+                    twist::BLS12381TwistCurve,
+                },
+                bn_254::{
+                    curve::BN254Curve,
+                    default_types::FrField as BN254FrField,
+                    field_extension::{
+                        BN254PrimeField, Degree2ExtensionField as BN254Degree2ExtensionField,
+                    },
+                    twist::BN254TwistCurve,
+                },
+                pallas::curve::PallasCurve,
+                vesta::curve::VestaCurve,
+            },
+            point::ShortWeierstrassProjectivePoint,
+        },
+        traits::{EllipticCurveError, FromAffine},
+    },
+    field::{
+        element::FieldElement,
+        fields::{pallas_field::Pallas255PrimeField, vesta_field::Vesta255PrimeField},
+    },
+    unsigned_integer::element::UnsignedInteger,
+};
+
+/// Implements the conversions between the elements of a prime field and the ones of its
+/// arkworks counterpart. Limbs of lambdaworks integers are stored from the most significant to
+/// the least significant, and the ones of arkworks integers the other way around.
+macro_rules! impl_prime_field_conversions {
+    ($field:ty, $ark_field:ty) => {
+        impl From<$ark_field> for FieldElement<$field> {
+            fn from(value: $ark_field) -> Self {
+                let mut limbs = value.into_bigint().0;
+                limbs.reverse();
+                FieldElement::new(UnsignedInteger::from_limbs(limbs))
+            }
+        }
+
+        impl From<&FieldElement<$field>> for $ark_field {
+            fn from(value: &FieldElement<$field>) -> Self {
+                let mut limbs = value.representative().limbs;
+                limbs.reverse();
+                <$ark_field>::new(BigInt(limbs))
+            }
+        }
+
+        impl From<FieldElement<$field>> for $ark_field {
+            fn from(value: FieldElement<$field>) -> Self {
+                Self::from(&value)
+            }
+        }
+    };
+}
+
+/// Implements the conversions between the elements of a quadratic extension `c0 + c1 * u` and
+/// the ones of its arkworks counterpart.
+macro_rules! impl_quadratic_extension_conversions {
+    ($field:ty, $ark_field:ty) => {
+        impl From<$ark_field> for FieldElement<$field> {
+            fn from(value: $ark_field) -> Self {
+                FieldElement::new([value.c0.into(), value.c1.into()])
+            }
+        }
+
+        impl From<&FieldElement<$field>> for $ark_field {
+            fn from(value: &FieldElement<$field>) -> Self {
+                let [c0, c1] = value.value();
+                <$ark_field>::new(c0.into(), c1.into())
+            }
+        }
+
+        impl From<FieldElement<$field>> for $ark_field {
+            fn from(value: FieldElement<$field>) -> Self {
+                Self::from(&value)
+            }
+        }
+    };
+}
+
+/// Implements the conversions between the points of a short Weierstrass curve and the affine
+/// and projective points of its arkworks counterpart.
+macro_rules! impl_curve_conversions {
+    ($curve:ty, $ark_affine:ty, $ark_projective:ty) => {
+        impl From<&ShortWeierstrassProjectivePoint<$curve>> for $ark_affine {
+            fn from(point: &ShortWeierstrassProjectivePoint<$curve>) -> Self {
+                if point.is_neutral_element() {
+                    return <$ark_affine>::identity();
+                }
+                let point = point.to_affine();
+                <$ark_affine>::new_unchecked(point.x().into(), point.y().into())
+            }
+        }
+
+        impl From<ShortWeierstrassProjectivePoint<$curve>> for $ark_affine {
+            fn from(point: ShortWeierstrassProjectivePoint<$curve>) -> Self {
+                Self::from(&point)
+            }
+        }
+
+        impl From<&ShortWeierstrassProjectivePoint<$curve>> for $ark_projective {
+            fn from(point: &ShortWeierstrassProjectivePoint<$curve>) -> Self {
+                <$ark_affine>::from(point).into()
+            }
+        }
+
+        impl From<ShortWeierstrassProjectivePoint<$curve>> for $ark_projective {
+            fn from(point: ShortWeierstrassProjectivePoint<$curve>) -> Self {
+                Self::from(&point)
+            }
+        }
+
+        impl TryFrom<$ark_affine> for ShortWeierstrassProjectivePoint<$curve> {
+            type Error = EllipticCurveError;
+
+            fn try_from(point: $ark_affine) -> Result<Self, Self::Error> {
+                if point.infinity {
+                    return Ok(Self::neutral_element());
+                }
+                Self::from_affine(point.x.into(), point.y.into())
+            }
+        }
+
+        impl TryFrom<$ark_projective> for ShortWeierstrassProjectivePoint<$curve> {
+            type Error = EllipticCurveError;
+
+            fn try_from(point: $ark_projective) -> Result<Self, Self::Error> {
+                point.into_affine().try_into()
+            }
+        }
+    };
+}
+
+impl_prime_field_conversions!(BN254PrimeField, ark_bn254::Fq);
+impl_prime_field_conversions!(BN254FrField, ark_bn254::Fr);
+impl_quadratic_extension_conversions!(BN254Degree2ExtensionField, ark_bn254::Fq2);
+impl_curve_conversions!(BN254Curve, ark_bn254::G1Affine, ark_bn254::G1Projective);
+impl_curve_conversions!(
+    BN254TwistCurve,
+    ark_bn254::G2Affine,
+    ark_bn254::G2Projective
+);
+
+impl_prime_field_conversions!(BLS12381PrimeField, ark_bls12_381::Fq);
+impl_prime_field_conversions!(BLS12381FrField, ark_bls12_381::Fr);
+impl_quadratic_extension_conversions!(BLS12381Degree2ExtensionField, ark_bls12_381::Fq2);
+impl_curve_conversions!(
+    BLS12381Curve,
+    ark_bls12_381::G1Affine,
+    ark_bls12_381::G1Projective
+);
+impl_curve_conversions!(
+    BLS12381TwistCurve,
+    ark_bls12_381::G2Affine,
+    ark_bls12_381::G2Projective
+);
+
+// The scalar field of each Pasta curve is the base field of the other one.
+impl_prime_field_conversions!(Pallas255PrimeField, ark_pallas::Fq);
+impl_prime_field_conversions!(Vesta255PrimeField, ark_vesta::Fq);
+impl_curve_conversions!(PallasCurve, ark_pallas::Affine, ark_pallas::Projective);
+impl_curve_conversions!(VestaCurve, ark_vesta::Affine, ark_vesta::Projective);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::elliptic_curve::traits::IsEllipticCurve;
+    use ark_ec::AffineRepr;
+
+    type BN254FrElement = FieldElement<BN254FrField>;
+
+    #[test]
+    fn field_elements_convert_to_and_from_arkworks() {
+        let a = BN254FrElement::from(0x1234_5678_9abc_def0);
+        assert_eq!(
+            ark_bn254::Fr::from(&a),
+            ark_bn254::Fr::from(0x1234_5678_9abc_def0_u64)
+        );
+        assert_eq!(BN254FrElement::from(ark_bn254::Fr::from(&a)), a);
+
+        let minus_one = -FieldElement::<BLS12381PrimeField>::one();
+        assert_eq!(
+            ark_bls12_381::Fq::from(&minus_one),
+            -ark_bls12_381::Fq::from(1u64)
+        );
+        assert_eq!(
+            FieldElement::<BLS12381PrimeField>::from(-ark_bls12_381::Fq::from(1u64)),
+            minus_one
+        );
+
+        let u = FieldElement::<BN254Degree2ExtensionField>::new([
+            FieldElement::from(3),
+            FieldElement::from(5),
+        ]);
+        let ark_u = ark_bn254::Fq2::from(&u);
+        assert_eq!(ark_u, ark_bn254::Fq2::new(3u64.into(), 5u64.into()));
+        assert_eq!(FieldElement::<BN254Degree2ExtensionField>::from(ark_u), u);
+    }
+
+    #[test]
+    fn generators_match_the_arkworks_ones() {
+        assert_eq!(
+            ark_bn254::G1Affine::from(BN254Curve::generator()),
+            ark_bn254::G1Affine::generator()
+        );
+        assert_eq!(
+            ark_bn254::G2Affine::from(BN254TwistCurve::generator()),
+            ark_bn254::G2Affine::generator()
+        );
+        assert_eq!(
+            ark_bls12_381::G1Affine::from(BLS12381Curve::generator()),
+            ark_bls12_381::G1Affine::generator()
+        );
+        assert_eq!(
+            ark_bls12_381::G2Affine::from(BLS12381TwistCurve::generator()),
+            ark_bls12_381::G2Affine::generator()
+        );
+        assert_eq!(
+            ark_pallas::Affine::from(PallasCurve::generator()),
+            ark_pallas::Affine::generator()
+        );
+        assert_eq!(
+            ark_vesta::Affine::from(VestaCurve::generator()),
+            ark_vesta::Affine::generator()
+        );
+    }
+
+    #[test]
+    fn points_convert_to_and_from_arkworks() {
+        let g = BLS12381Curve::generator();
+        let p = g.operate_with_self(1234_u64);
+        let ark_p = ark_bls12_381::G1Projective::from(&p);
+        assert_eq!(
+            ark_p,
+            ark_bls12_381::G1Affine::generator() * ark_bls12_381::Fr::from(1234u64)
+        );
+        assert_eq!(
+            ShortWeierstrassProjectivePoint::<BLS12381Curve>::try_from(ark_p),
+            Ok(p)
+        );
+
+        let neutral = ShortWeierstrassProjectivePoint::<BN254TwistCurve>::neutral_element();
+        let ark_neutral = ark_bn254::G2Affine::from(&neutral);
+        assert!(ark_neutral.is_zero());
+        assert_eq!(
+            ShortWeierstrassProjectivePoint::<BN254TwistCurve>::try_from(ark_neutral),
+            Ok(neutral)
+        );
+    }
+
+    #[test]
+    fn points_not_on_the_curve_are_rejected() {
+        let point = ark_pallas::Affine::new_unchecked(1u64.into(), 1u64.into());
+        assert_eq!(
+            ShortWeierstrassProjectivePoint::<PallasCurve>::try_from(point),
+            Err(EllipticCurveError::InvalidPoint)
+        );
+    }
+}
@@ -0,0 +1,247 @@
+//! Interpolation and evaluation of circle polynomials over [`CircleDomain`]s.
+//!
+//! The coefficients of a polynomial of `2^n` coefficients are in the basis of the products
+//! `b_j = yʲ⁰ · v₁(x)ʲ¹ ··· vₙ₋₁(x)ʲⁿ⁻¹`, where `j₀, ..., jₙ₋₁` are the bits of `j` from the least
+//! significant one, `v₁(x) = x` and `vₖ₊₁(x) = 2vₖ(x)² - 1`. Then a polynomial `f` splits as
+//! `f(x, y) = f₀(x) + y f₁(x)`, with the coefficients at the even and odd indexes, and each
+//! polynomial `g` in `x` splits as `g(x) = g₀(2x² - 1) + x g₁(2x² - 1)`, which the FFT uses to fold
+//! the evaluations of each polynomial in half.
+//!
+//! The coefficients are over an extension `E` of Mersenne31, to interpolate and evaluate the
+//! polynomials of the proofs, whose challenges are sampled in an extension.
+use super::{
+    domain::{half_coset_points, CircleDomain},
+    point::CirclePoint,
+};
+use crate::field::{
+    element::FieldElement,
+    fields::mersenne31::field::Mersenne31Field,
+    traits::{IsField, IsSubFieldOf},
+};
+use alloc::vec::Vec;
+
+type FpE = FieldElement<Mersenne31Field>;
+type Point = CirclePoint<Mersenne31Field>;
+
+/// Returns the `domain.size()` coefficients of the polynomial with `evaluations` on the points
+/// of `domain`.
+///
+/// # Panics
+///
+/// If there aren't as many evaluations as points in the domain.
+pub fn interpolate<E>(
+    evaluations: &[FieldElement<E>],
+    domain: &CircleDomain,
+) -> Vec<FieldElement<E>>
+where
+    E: IsField,
+    Mersenne31Field: IsSubFieldOf<E>,
+{
+    assert_eq!(evaluations.len(), domain.size());
+    let half_size = domain.size() / 2;
+    let q = domain.generator();
+    let half_inverse = FpE::from(2).inv().unwrap();
+
+    let (mut even, mut odd) = (Vec::with_capacity(half_size), Vec::with_capacity(half_size));
+    for (i, point) in half_coset_points(&q, half_size).iter().enumerate() {
+        let (value, conjugate_value) = (&evaluations[i], &evaluations[i + half_size]);
+        even.push(half_inverse * (value + conjugate_value));
+        odd.push(point.y.double().inv().unwrap() * (value - conjugate_value));
+    }
+
+    interleave(interpolate_line(&even, &q), interpolate_line(&odd, &q))
+}
+
+/// Returns the evaluations of the polynomial with `coefficients` on the points of `domain`.
+/// The coefficients are padded with zeros to the size of the domain, so a polynomial is
+/// extended to a larger domain by evaluating it on it.
+///
+/// # Panics
+///
+/// If there are more coefficients than points in the domain.
+pub fn evaluate<E>(coefficients: &[FieldElement<E>], domain: &CircleDomain) -> Vec<FieldElement<E>>
+where
+    E: IsField,
+    Mersenne31Field: IsSubFieldOf<E>,
+{
+    assert!(coefficients.len() <= domain.size());
+    let mut coefficients = coefficients.to_vec();
+    coefficients.resize(domain.size(), FieldElement::zero());
+
+    let half_size = domain.size() / 2;
+    let q = domain.generator();
+    let (even, odd) = deinterleave(&coefficients);
+    let (even, odd) = (evaluate_line(&even, &q), evaluate_line(&odd, &q));
+
+    let mut evaluations = vec![FieldElement::zero(); domain.size()];
+    for (i, point) in half_coset_points(&q, half_size).iter().enumerate() {
+        let y_odd = point.y * &odd[i];
+        evaluations[i] = &even[i] + &y_odd;
+        evaluations[i + half_size] = &even[i] - y_odd;
+    }
+    evaluations
+}
+
+/// Evaluates the polynomial with `coefficients` at `point`, which can be outside of any domain.
+///
+/// # Panics
+///
+/// If the number of coefficients isn't a power of two.
+pub fn evaluate_at_point<E>(coefficients: &[FieldElement<E>], point: &Point) -> FieldElement<E>
+where
+    E: IsField,
+    Mersenne31Field: IsSubFieldOf<E>,
+{
+    assert!(coefficients.len().is_power_of_two());
+    if coefficients.len() == 1 {
+        return coefficients[0].clone();
+    }
+    let (even, odd) = deinterleave(coefficients);
+    evaluate_line_at(&even, &point.x) + point.y * evaluate_line_at(&odd, &point.x)
+}
+
+/// The coefficients of the polynomial in `x` with `values` on the `x` coordinates of the first
+/// `values.len()` odd multiples of `q`, where `q` has order `4 * values.len()`. The point at
+/// `i` and the one at `values.len() - 1 - i` have opposite `x` coordinates.
+fn interpolate_line<E>(values: &[FieldElement<E>], q: &Point) -> Vec<FieldElement<E>>
+where
+    E: IsField,
+    Mersenne31Field: IsSubFieldOf<E>,
+{
+    let len = values.len();
+    if len == 1 {
+        return values.to_vec();
+    }
+    let half_len = len / 2;
+    let half_inverse = FpE::from(2).inv().unwrap();
+
+    let (mut even, mut odd) = (Vec::with_capacity(half_len), Vec::with_capacity(half_len));
+    for (i, point) in half_coset_points(q, half_len).iter().enumerate() {
+        let (value, opposite_value) = (&values[i], &values[len - 1 - i]);
+        even.push(half_inverse * (value + opposite_value));
+        odd.push(point.x.double().inv().unwrap() * (value - opposite_value));
+    }
+
+    let q = q.double();
+    interleave(interpolate_line(&even, &q), interpolate_line(&odd, &q))
+}
+
+/// The values of the polynomial in `x` with `coefficients` on the points of
+/// [`interpolate_line`].
+fn evaluate_line<E>(coefficients: &[FieldElement<E>], q: &Point) -> Vec<FieldElement<E>>
+where
+    E: IsField,
+    Mersenne31Field: IsSubFieldOf<E>,
+{
+    let len = coefficients.len();
+    if len == 1 {
+        return coefficients.to_vec();
+    }
+    let half_len = len / 2;
+    let (even, odd) = deinterleave(coefficients);
+    let double_q = q.double();
+    let (even, odd) = (
+        evaluate_line(&even, &double_q),
+        evaluate_line(&odd, &double_q),
+    );
+
+    let mut values = vec![FieldElement::zero(); len];
+    for (i, point) in half_coset_points(q, half_len).iter().enumerate() {
+        let x_odd = point.x * &odd[i];
+        values[i] = &even[i] + &x_odd;
+        values[len - 1 - i] = &even[i] - x_odd;
+    }
+    values
+}
+
+fn evaluate_line_at<E>(coefficients: &[FieldElement<E>], x: &FpE) -> FieldElement<E>
+where
+    E: IsField,
+    Mersenne31Field: IsSubFieldOf<E>,
+{
+    if coefficients.len() == 1 {
+        return coefficients[0].clone();
+    }
+    let (even, odd) = deinterleave(coefficients);
+    let double_x = x.square().double() - FpE::one();
+    evaluate_line_at(&even, &double_x) + x * evaluate_line_at(&odd, &double_x)
+}
+
+fn interleave<T: Clone>(even: Vec<T>, odd: Vec<T>) -> Vec<T> {
+    even.into_iter()
+        .zip(odd)
+        .flat_map(|(even, odd)| [even, odd])
+        .collect()
+}
+
+fn deinterleave<T: Clone>(values: &[T]) -> (Vec<T>, Vec<T>) {
+    let even = values.iter().step_by(2).cloned().collect();
+    let odd = values.iter().skip(1).step_by(2).cloned().collect();
+    (even, odd)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::field::fields::mersenne31::extensions::Degree4ExtensionField;
+
+    type Fp4E = FieldElement<Degree4ExtensionField>;
+
+    fn coefficients(len: u64) -> Vec<FpE> {
+        (0..len).map(|i| FpE::from(i * i + 7)).collect()
+    }
+
+    #[test]
+    fn evaluations_are_the_evaluations_at_each_point() {
+        let domain = CircleDomain::new(4);
+        let coefficients = coefficients(16);
+        let evaluations = evaluate(&coefficients, &domain);
+        for (evaluation, point) in evaluations.iter().zip(domain.points()) {
+            assert_eq!(evaluation, &evaluate_at_point(&coefficients, &point));
+        }
+    }
+
+    #[test]
+    fn interpolate_is_the_inverse_of_evaluate() {
+        for log_size in 1..8 {
+            let domain = CircleDomain::new(log_size);
+            let coefficients = coefficients(1 << log_size);
+            assert_eq!(
+                interpolate(&evaluate(&coefficients, &domain), &domain),
+                coefficients
+            );
+        }
+    }
+
+    #[test]
+    fn extension_to_a_larger_domain_interpolates_to_the_same_coefficients() {
+        let coefficients = coefficients(8);
+        let lde_domain = CircleDomain::new(5);
+        let lde_coefficients = interpolate(&evaluate(&coefficients, &lde_domain), &lde_domain);
+        assert_eq!(&lde_coefficients[..8], &coefficients[..]);
+        assert!(lde_coefficients[8..].iter().all(|c| c == &FpE::zero()));
+    }
+
+    #[test]
+    fn basis_has_the_product_of_y_and_x_at_index_three() {
+        let mut coefficients = vec![FpE::zero(); 4];
+        coefficients[3] = FpE::one();
+        let point = CirclePoint::<Mersenne31Field>::generator();
+        assert_eq!(evaluate_at_point(&coefficients, &point), point.x * point.y);
+    }
+
+    #[test]
+    fn polynomials_over_the_extension_are_evaluated_by_coordinates() {
+        let domain = CircleDomain::new(3);
+        let coefficients: Vec<Fp4E> = coefficients(8)
+            .into_iter()
+            .map(|c| c.to_extension())
+            .collect();
+        let evaluations = evaluate(&coefficients, &domain);
+        assert_eq!(interpolate(&evaluations, &domain), coefficients);
+        let base_evaluations = evaluate(&self::coefficients(8), &domain);
+        for (evaluation, base_evaluation) in evaluations.into_iter().zip(base_evaluations) {
+            assert_eq!(evaluation, base_evaluation.to_extension());
+        }
+    }
+}
@@ -0,0 +1,120 @@
+use super::point::CirclePoint;
+use crate::{cyclic_group::IsGroup, field::fields::mersenne31::field::Mersenne31Field};
+use alloc::vec::Vec;
+
+type Point = CirclePoint<Mersenne31Field>;
+
+/// The circle domain of `2^log_size` points, the odd multiples of a generator `q` of the subgroup
+/// of order `2^(log_size + 1)`. Its first half are the points `(2i + 1)q`, and its second half
+/// their conjugates in the same order, so the conjugate of the point at `i` is at
+/// `i + 2^(log_size - 1)`.
+///
+/// The domain is closed under conjugation and under the antipode, and doubling its points maps
+/// it to the domain of half its size. Domains of different sizes are disjoint, so the domain of
+/// a trace doesn't meet the domains of its low degree extensions.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CircleDomain {
+    log_size: u32,
+}
+
+impl CircleDomain {
+    /// The largest domain, of `2³⁰` points.
+    pub const MAX_LOG_SIZE: u32 = Point::LOG_ORDER - 1;
+
+    /// The domain of `2^log_size` points.
+    ///
+    /// # Panics
+    ///
+    /// If `log_size` is zero or larger than [`Self::MAX_LOG_SIZE`].
+    pub fn new(log_size: u32) -> Self {
+        assert!((1..=Self::MAX_LOG_SIZE).contains(&log_size));
+        Self { log_size }
+    }
+
+    pub fn log_size(&self) -> u32 {
+        self.log_size
+    }
+
+    pub fn size(&self) -> usize {
+        1 << self.log_size
+    }
+
+    /// The generator `q` of the subgroup of order `2^(log_size + 1)`.
+    pub fn generator(&self) -> Point {
+        Point::subgroup_generator(self.log_size + 1)
+    }
+
+    /// The point at `index`.
+    pub fn point(&self, index: usize) -> Point {
+        let half_size = self.size() / 2;
+        let point = self
+            .generator()
+            .operate_with_self(2 * (index % half_size) as u64 + 1);
+        if index < half_size {
+            point
+        } else {
+            point.conjugate()
+        }
+    }
+
+    /// The points of the domain, in order.
+    pub fn points(&self) -> Vec<Point> {
+        let half = half_coset_points(&self.generator(), self.size() / 2);
+        let conjugates: Vec<Point> = half.iter().map(Point::conjugate).collect();
+        [half, conjugates].concat()
+    }
+}
+
+/// The first `len` odd multiples `(2i + 1)q` of `q`.
+pub fn half_coset_points(q: &Point, len: usize) -> Vec<Point> {
+    let step = q.double();
+    let mut points = Vec::with_capacity(len);
+    let mut point = q.clone();
+    for _ in 0..len {
+        let next = point.operate_with(&step);
+        points.push(point);
+        point = next;
+    }
+    points
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn points_are_the_points_at_each_index() {
+        let domain = CircleDomain::new(4);
+        let points = domain.points();
+        assert_eq!(points.len(), 16);
+        for (index, point) in points.iter().enumerate() {
+            assert_eq!(point, &domain.point(index));
+        }
+    }
+
+    #[test]
+    fn points_are_distinct_and_closed_under_conjugation_and_antipode() {
+        let points = CircleDomain::new(5).points();
+        for (index, point) in points.iter().enumerate() {
+            assert!(!points[..index].contains(point));
+            assert!(points.contains(&point.conjugate()));
+            assert!(points.contains(&point.antipode()));
+        }
+    }
+
+    #[test]
+    fn doubling_maps_the_domain_to_the_domain_of_half_its_size() {
+        let points = CircleDomain::new(5).points();
+        let half_points = CircleDomain::new(4).points();
+        for point in points {
+            assert!(half_points.contains(&point.double()));
+        }
+    }
+
+    #[test]
+    fn domains_of_different_sizes_are_disjoint() {
+        let trace_points = CircleDomain::new(3).points();
+        let lde_points = CircleDomain::new(5).points();
+        assert!(trace_points.iter().all(|point| !lde_points.contains(point)));
+    }
+}
@@ -0,0 +1,9 @@
+//! The circle group of Mersenne31 and the FFT over its domains, from
+//! [Circle STARKs](https://eprint.iacr.org/2024/278).
+//!
+//! Mersenne31 has no large multiplicative subgroups of power of two order, but its circle
+//! `x² + y² = 1` is a group of order `p + 1 = 2³¹`. Polynomials are evaluated on cosets of its
+//! subgroups, and interpolated with an FFT that folds the evaluations on `y` and then on `x`.
+pub mod cfft;
+pub mod domain;
+pub mod point;
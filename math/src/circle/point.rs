@@ -0,0 +1,153 @@
+use crate::{
+    cyclic_group::IsGroup,
+    field::{element::FieldElement, fields::mersenne31::field::Mersenne31Field, traits::IsField},
+};
+
+/// A point `(x, y)` of the circle `x² + y² = 1` over `F`. The points are a group with the
+/// product of the complex numbers `x + iy`, which is written additively, so the neutral element
+/// is `(1, 0)` and the inverse of a point is its conjugate.
+#[derive(Debug, Clone)]
+pub struct CirclePoint<F: IsField> {
+    pub x: FieldElement<F>,
+    pub y: FieldElement<F>,
+}
+
+impl<F: IsField> CirclePoint<F> {
+    /// Returns the point `(x, y)`, or `None` if it isn't in the circle.
+    pub fn new(x: FieldElement<F>, y: FieldElement<F>) -> Option<Self> {
+        if x.square() + y.square() == FieldElement::one() {
+            Some(Self { x, y })
+        } else {
+            None
+        }
+    }
+
+    /// The point `(2x² - 1, 2xy)`. Its `x` coordinate only depends on `x`, so the map
+    /// `x ↦ 2x² - 1` halves the `x` coordinates of a domain.
+    pub fn double(&self) -> Self {
+        Self {
+            x: self.x.square().double() - FieldElement::one(),
+            y: (&self.x * &self.y).double(),
+        }
+    }
+
+    /// The point doubled `n` times.
+    pub fn repeated_double(&self, n: u32) -> Self {
+        (0..n).fold(self.clone(), |point, _| point.double())
+    }
+
+    /// The point `(x, -y)`.
+    pub fn conjugate(&self) -> Self {
+        Self {
+            x: self.x.clone(),
+            y: -&self.y,
+        }
+    }
+
+    /// The point `(-x, -y)`, the sum of the point and the point of order 2.
+    pub fn antipode(&self) -> Self {
+        Self {
+            x: -&self.x,
+            y: -&self.y,
+        }
+    }
+}
+
+impl<F: IsField> PartialEq for CirclePoint<F> {
+    fn eq(&self, other: &Self) -> bool {
+        self.x == other.x && self.y == other.y
+    }
+}
+
+impl<F: IsField> Eq for CirclePoint<F> {}
+
+impl<F: IsField> IsGroup for CirclePoint<F> {
+    fn neutral_element() -> Self {
+        Self {
+            x: FieldElement::one(),
+            y: FieldElement::zero(),
+        }
+    }
+
+    fn operate_with(&self, other: &Self) -> Self {
+        Self {
+            x: &self.x * &other.x - &self.y * &other.y,
+            y: &self.x * &other.y + &self.y * &other.x,
+        }
+    }
+
+    fn neg(&self) -> Self {
+        self.conjugate()
+    }
+}
+
+impl CirclePoint<Mersenne31Field> {
+    /// The logarithm of the order of the circle group of Mersenne31.
+    pub const LOG_ORDER: u32 = 31;
+
+    /// A generator of the circle group of Mersenne31.
+    pub fn generator() -> Self {
+        Self {
+            x: FieldElement::from(2),
+            y: FieldElement::from(1268011823),
+        }
+    }
+
+    /// A generator of the subgroup of order `2^log_order`.
+    ///
+    /// # Panics
+    ///
+    /// If `log_order` is larger than [`Self::LOG_ORDER`].
+    pub fn subgroup_generator(log_order: u32) -> Self {
+        assert!(log_order <= Self::LOG_ORDER);
+        Self::generator().repeated_double(Self::LOG_ORDER - log_order)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    type Point = CirclePoint<Mersenne31Field>;
+    type FE = FieldElement<Mersenne31Field>;
+
+    #[test]
+    fn generator_is_in_the_circle() {
+        let generator = Point::generator();
+        assert_eq!(Point::new(generator.x, generator.y), Some(generator));
+    }
+
+    #[test]
+    fn points_not_in_the_circle_are_rejected() {
+        assert_eq!(Point::new(FE::from(2), FE::from(2)), None);
+    }
+
+    #[test]
+    fn generator_has_order_two_to_the_thirty_one() {
+        let half_order = Point::generator().repeated_double(Point::LOG_ORDER - 1);
+        assert_eq!(half_order, Point::neutral_element().antipode());
+        assert_eq!(half_order.double(), Point::neutral_element());
+    }
+
+    #[test]
+    fn double_is_the_sum_of_the_point_with_itself() {
+        let point = Point::generator();
+        assert_eq!(point.double(), point.operate_with(&point));
+    }
+
+    #[test]
+    fn sum_of_a_point_and_its_conjugate_is_the_neutral_element() {
+        let point = Point::subgroup_generator(10);
+        assert!(point.operate_with(&point.neg()).is_neutral_element());
+    }
+
+    #[test]
+    fn subgroup_generator_has_the_order_of_the_subgroup() {
+        let generator = Point::subgroup_generator(5);
+        assert_eq!(
+            generator.operate_with_self(16_u64),
+            Point::neutral_element().antipode()
+        );
+        assert!(generator.operate_with_self(32_u64).is_neutral_element());
+    }
+}
@@ -3,7 +3,9 @@ use super::{
     twist::BLS12377TwistCurve,
 };
 use crate::cyclic_group::IsGroup;
-use crate::elliptic_curve::short_weierstrass::point::ShortWeierstrassProjectivePoint;
+use crate::elliptic_curve::short_weierstrass::point::{
+    impl_canonical_point_byte_conversion, ShortWeierstrassProjectivePoint,
+};
 use crate::elliptic_curve::traits::IsEllipticCurve;
 use crate::unsigned_integer::element::U256;
 
@@ -47,6 +49,8 @@ impl IsShortWeierstrass for BLS12377Curve {
     }
 }
 
+impl_canonical_point_byte_conversion!(BLS12377Curve, 96);
+
 /// This is equal to the frobenius trace of the BLS12 377 curve minus one or seed value z.
 pub const MILLER_LOOP_CONSTANT: u64 = 0x8508c00000000001;
 
@@ -1,3 +1,4 @@
+use crate::errors::ByteConversionError;
 use crate::field::{
     element::FieldElement,
     errors::FieldError,
@@ -8,7 +9,7 @@ use crate::field::{
     fields::montgomery_backed_prime_fields::{IsModulus, MontgomeryBackendPrimeField},
     traits::{IsField, IsSubFieldOf},
 };
-use crate::traits::ByteConversion;
+use crate::traits::{ByteConversion, CanonicalByteConversion};
 use crate::unsigned_integer::element::U384;
 
 pub const BLS12377_PRIME_FIELD_ORDER: U384 = U384::from_hex_unchecked("1ae3a4617c510eac63b05c06ca1493b1a22d9f300f5138f1ef3622fba094800170b5d44300000008508c00000000001");
@@ -150,6 +151,10 @@ impl IsSubFieldOf<Degree2ExtensionField> for BLS12377PrimeField {
     ) -> alloc::vec::Vec<Self::BaseType> {
         b.into_iter().map(|x| x.to_raw()).collect()
     }
+
+    fn from_subfield_slice(b: &[Self::BaseType]) -> <Degree2ExtensionField as IsField>::BaseType {
+        [FieldElement::from_raw(b[0]), FieldElement::from_raw(b[1])]
+    }
 }
 
 impl ByteConversion for FieldElement<Degree2ExtensionField> {
@@ -188,6 +193,37 @@ impl ByteConversion for FieldElement<Degree2ExtensionField> {
     }
 }
 
+/// Elements `c0 + c1 * u` are encoded as the encoding of `c0` followed by the one of `c1`.
+impl CanonicalByteConversion for FieldElement<Degree2ExtensionField> {
+    type Bytes = [u8; 96];
+
+    fn to_bytes_be_array(&self) -> Self::Bytes {
+        let mut bytes = [0; 96];
+        bytes[..48].copy_from_slice(&self.value()[0].to_bytes_be_array());
+        bytes[48..].copy_from_slice(&self.value()[1].to_bytes_be_array());
+        bytes
+    }
+
+    fn to_bytes_le_array(&self) -> Self::Bytes {
+        let mut bytes = [0; 96];
+        bytes[..48].copy_from_slice(&self.value()[0].to_bytes_le_array());
+        bytes[48..].copy_from_slice(&self.value()[1].to_bytes_le_array());
+        bytes
+    }
+
+    fn from_bytes_be_array(bytes: &Self::Bytes) -> Result<Self, ByteConversionError> {
+        let c0 = FieldElement::from_bytes_be_canonical(&bytes[..48])?;
+        let c1 = FieldElement::from_bytes_be_canonical(&bytes[48..])?;
+        Ok(Self::new([c0, c1]))
+    }
+
+    fn from_bytes_le_array(bytes: &Self::Bytes) -> Result<Self, ByteConversionError> {
+        let c0 = FieldElement::from_bytes_le_canonical(&bytes[..48])?;
+        let c1 = FieldElement::from_bytes_le_canonical(&bytes[48..])?;
+        Ok(Self::new([c0, c1]))
+    }
+}
+
 impl FieldElement<Degree2ExtensionField> {
     pub fn new_base(a_hex: &str) -> Self {
         Self::new([FieldElement::new(U384::from(a_hex)), FieldElement::zero()])
@@ -1,5 +1,7 @@
 use super::field_extension::Degree2ExtensionField;
-use crate::elliptic_curve::short_weierstrass::point::ShortWeierstrassProjectivePoint;
+use crate::elliptic_curve::short_weierstrass::point::{
+    impl_canonical_point_byte_conversion, ShortWeierstrassProjectivePoint,
+};
 use crate::elliptic_curve::traits::IsEllipticCurve;
 use crate::unsigned_integer::element::U384;
 use crate::{
@@ -45,6 +47,8 @@ impl IsShortWeierstrass for BLS12377TwistCurve {
     }
 }
 
+impl_canonical_point_byte_conversion!(BLS12377TwistCurve, 192);
+
 #[cfg(test)]
 mod tests {
     use crate::{
@@ -3,7 +3,9 @@ use super::{
     twist::BLS12381TwistCurve,
 };
 use crate::cyclic_group::IsGroup;
-use crate::elliptic_curve::short_weierstrass::point::ShortWeierstrassProjectivePoint;
+use crate::elliptic_curve::short_weierstrass::point::{
+    impl_canonical_point_byte_conversion, ShortWeierstrassProjectivePoint,
+};
 use crate::elliptic_curve::traits::IsEllipticCurve;
 use crate::unsigned_integer::element::U256;
 use crate::{
@@ -45,6 +47,8 @@ impl IsShortWeierstrass for BLS12381Curve {
     }
 }
 
+impl_canonical_point_byte_conversion!(BLS12381Curve, 96);
+
 /// This is equal to the frobenius trace of the BLS12 381 curve minus one or seed value z.
 pub const MILLER_LOOP_CONSTANT: u64 = 0xd201000000010000;
 
@@ -7,3 +7,5 @@ pub mod twist;
 
 #[cfg(feature = "alloc")]
 pub mod pairing;
+#[cfg(feature = "alloc")]
+pub mod precompiles;
@@ -0,0 +1,327 @@
+//! Encodings and semantics of the BLS12-381 precompiles of the EVM from
+//! [EIP-2537](https://eips.ethereum.org/EIPS/eip-2537): `BLS12_G1ADD`, `BLS12_G1MSM`,
+//! `BLS12_G2ADD`, `BLS12_G2MSM` and `BLS12_PAIRING_CHECK`. The precompiles that map field
+//! elements to the curve are not implemented.
+//!
+//! Field elements are 64 byte big endian integers smaller than the modulus, so their top 16
+//! bytes are always zero. Points of G1 are encoded as `x || y`. Elements `a + b * u` of the
+//! quadratic extension are encoded as `a || b`, so points of G2 are
+//! `x.c0 || x.c1 || y.c0 || y.c1`. The point at infinity is encoded with zeros. Scalars are 32
+//! byte big endian integers.
+
+use alloc::vec::Vec;
+
+use super::{
+    curve::BLS12381Curve,
+    field_extension::{BLS12381PrimeField, Degree2ExtensionField},
+    pairing::BLS12381AtePairing,
+    twist::BLS12381TwistCurve,
+};
+use crate::{
+    cyclic_group::IsGroup,
+    elliptic_curve::{
+        short_weierstrass::point::ShortWeierstrassProjectivePoint,
+        traits::{FromAffine, IsPairing},
+    },
+    errors::PrecompileError,
+    field::element::FieldElement,
+    msm::pippenger,
+    traits::{ByteConversion, CanonicalByteConversion},
+    unsigned_integer::element::U256,
+};
+
+type G1Point = ShortWeierstrassProjectivePoint<BLS12381Curve>;
+type G2Point = ShortWeierstrassProjectivePoint<BLS12381TwistCurve>;
+type FpE = FieldElement<BLS12381PrimeField>;
+type Fp2E = FieldElement<Degree2ExtensionField>;
+
+pub const FIELD_ELEMENT_SIZE: usize = 64;
+pub const SCALAR_SIZE: usize = 32;
+pub const G1_POINT_SIZE: usize = 2 * FIELD_ELEMENT_SIZE;
+pub const G2_POINT_SIZE: usize = 4 * FIELD_ELEMENT_SIZE;
+/// Size of each pair of a point and a scalar of the input of `BLS12_G1MSM`.
+pub const G1_MSM_PAIR_SIZE: usize = G1_POINT_SIZE + SCALAR_SIZE;
+/// Size of each pair of a point and a scalar of the input of `BLS12_G2MSM`.
+pub const G2_MSM_PAIR_SIZE: usize = G2_POINT_SIZE + SCALAR_SIZE;
+/// Size of each pair of points of the input of `BLS12_PAIRING_CHECK`.
+pub const PAIR_SIZE: usize = G1_POINT_SIZE + G2_POINT_SIZE;
+
+/// Bytes of padding before each field element.
+const PADDING_SIZE: usize = 16;
+
+pub fn encode_g1(point: &G1Point) -> [u8; G1_POINT_SIZE] {
+    let mut bytes = [0; G1_POINT_SIZE];
+    if !point.is_neutral_element() {
+        let point = point.to_affine();
+        encode_fp(point.x(), &mut bytes[..64]);
+        encode_fp(point.y(), &mut bytes[64..]);
+    }
+    bytes
+}
+
+/// Decodes a point of G1. Fails if a coordinate is not a valid field element, or if the point
+/// is not on the curve. Doesn't check that the point is in the subgroup of order r.
+pub fn decode_g1(bytes: &[u8; G1_POINT_SIZE]) -> Result<G1Point, PrecompileError> {
+    if bytes.iter().all(|byte| *byte == 0) {
+        return Ok(G1Point::neutral_element());
+    }
+    let x = decode_fp(&bytes[..64])?;
+    let y = decode_fp(&bytes[64..])?;
+    G1Point::from_affine(x, y).map_err(|_| PrecompileError::PointNotOnCurve)
+}
+
+pub fn encode_g2(point: &G2Point) -> [u8; G2_POINT_SIZE] {
+    let mut bytes = [0; G2_POINT_SIZE];
+    if !point.is_neutral_element() {
+        let point = point.to_affine();
+        encode_fp2(point.x(), &mut bytes[..128]);
+        encode_fp2(point.y(), &mut bytes[128..]);
+    }
+    bytes
+}
+
+/// Decodes a point of G2. Fails if a coordinate is not a valid field element, or if the point
+/// is not on the twist. Doesn't check that the point is in the subgroup of order r.
+pub fn decode_g2(bytes: &[u8; G2_POINT_SIZE]) -> Result<G2Point, PrecompileError> {
+    if bytes.iter().all(|byte| *byte == 0) {
+        return Ok(G2Point::neutral_element());
+    }
+    let x = decode_fp2(&bytes[..128])?;
+    let y = decode_fp2(&bytes[128..])?;
+    G2Point::from_affine(x, y).map_err(|_| PrecompileError::PointNotOnCurve)
+}
+
+/// Encodes pairs of points as the input of `BLS12_PAIRING_CHECK`.
+pub fn encode_pairing_input(pairs: &[(G1Point, G2Point)]) -> Vec<u8> {
+    pairs
+        .iter()
+        .flat_map(|(p, q)| encode_g1(p).into_iter().chain(encode_g2(q)))
+        .collect()
+}
+
+/// `BLS12_G1ADD`: adds two points of G1. The input is `p || q`, and must be exactly 256 bytes.
+/// The points don't need to be in the subgroup of order r. Returns the encoding of `p + q`.
+pub fn g1_add(input: &[u8]) -> Result<[u8; G1_POINT_SIZE], PrecompileError> {
+    if input.len() != 2 * G1_POINT_SIZE {
+        return Err(PrecompileError::InvalidInputLength);
+    }
+    let p = decode_g1(input[..G1_POINT_SIZE].try_into().unwrap())?;
+    let q = decode_g1(input[G1_POINT_SIZE..].try_into().unwrap())?;
+    Ok(encode_g1(&p.operate_with(&q)))
+}
+
+/// `BLS12_G2ADD`: adds two points of G2. The input is `p || q`, and must be exactly 512 bytes.
+/// The points don't need to be in the subgroup of order r. Returns the encoding of `p + q`.
+pub fn g2_add(input: &[u8]) -> Result<[u8; G2_POINT_SIZE], PrecompileError> {
+    if input.len() != 2 * G2_POINT_SIZE {
+        return Err(PrecompileError::InvalidInputLength);
+    }
+    let p = decode_g2(input[..G2_POINT_SIZE].try_into().unwrap())?;
+    let q = decode_g2(input[G2_POINT_SIZE..].try_into().unwrap())?;
+    Ok(encode_g2(&p.operate_with(&q)))
+}
+
+/// `BLS12_G1MSM`: computes the sum of `s_i * p_i` for a non-empty concatenation of pairs
+/// `p_i || s_i`. Every point must be in the subgroup of order r.
+pub fn g1_msm(input: &[u8]) -> Result<[u8; G1_POINT_SIZE], PrecompileError> {
+    let (scalars, points) = decode_msm_input(input, G1_POINT_SIZE, |bytes| {
+        let point = decode_g1(bytes.try_into().unwrap())?;
+        if !point.is_in_subgroup() {
+            return Err(PrecompileError::PointNotInSubgroup);
+        }
+        Ok(point)
+    })?;
+    let result =
+        pippenger::msm(&scalars, &points).map_err(|_| PrecompileError::InvalidInputLength)?;
+    Ok(encode_g1(&result))
+}
+
+/// `BLS12_G2MSM`: computes the sum of `s_i * p_i` for a non-empty concatenation of pairs
+/// `p_i || s_i`. Every point must be in the subgroup of order r.
+pub fn g2_msm(input: &[u8]) -> Result<[u8; G2_POINT_SIZE], PrecompileError> {
+    let (scalars, points) = decode_msm_input(input, G2_POINT_SIZE, |bytes| {
+        let point = decode_g2(bytes.try_into().unwrap())?;
+        if !point.is_in_subgroup() {
+            return Err(PrecompileError::PointNotInSubgroup);
+        }
+        Ok(point)
+    })?;
+    let result =
+        pippenger::msm(&scalars, &points).map_err(|_| PrecompileError::InvalidInputLength)?;
+    Ok(encode_g2(&result))
+}
+
+/// `BLS12_PAIRING_CHECK`: checks whether the product of the pairings of the pairs of points of
+/// the input is one. The input is a non-empty concatenation of pairs `p || q` with `p` in G1
+/// and `q` in G2. Returns a 32 byte big endian one if the check passes, and zero otherwise.
+pub fn pairing_check(input: &[u8]) -> Result<[u8; 32], PrecompileError> {
+    if input.is_empty() || input.len() % PAIR_SIZE != 0 {
+        return Err(PrecompileError::InvalidInputLength);
+    }
+    let pairs = input
+        .chunks_exact(PAIR_SIZE)
+        .map(|pair| {
+            let p = decode_g1(pair[..G1_POINT_SIZE].try_into().unwrap())?;
+            let q = decode_g2(pair[G1_POINT_SIZE..].try_into().unwrap())?;
+            Ok((p, q))
+        })
+        .collect::<Result<Vec<_>, PrecompileError>>()?;
+    let pairs: Vec<_> = pairs.iter().map(|(p, q)| (p, q)).collect();
+
+    let result = BLS12381AtePairing::compute_batch(&pairs)
+        .map_err(|_| PrecompileError::PointNotInSubgroup)?;
+    Ok(encode_bool(result == FieldElement::one()))
+}
+
+fn decode_msm_input<G>(
+    input: &[u8],
+    point_size: usize,
+    decode_point: impl Fn(&[u8]) -> Result<G, PrecompileError>,
+) -> Result<(Vec<U256>, Vec<G>), PrecompileError> {
+    let pair_size = point_size + SCALAR_SIZE;
+    if input.is_empty() || input.len() % pair_size != 0 {
+        return Err(PrecompileError::InvalidInputLength);
+    }
+    input
+        .chunks_exact(pair_size)
+        .map(|pair| {
+            let point = decode_point(&pair[..point_size])?;
+            // Every 32 bytes are a valid `U256`.
+            let scalar = U256::from_bytes_be(&pair[point_size..]).unwrap();
+            Ok((scalar, point))
+        })
+        .collect()
+}
+
+fn encode_fp(element: &FpE, bytes: &mut [u8]) {
+    bytes[PADDING_SIZE..].copy_from_slice(&element.to_bytes_be_array());
+}
+
+fn decode_fp(bytes: &[u8]) -> Result<FpE, PrecompileError> {
+    if bytes[..PADDING_SIZE].iter().any(|byte| *byte != 0) {
+        return Err(PrecompileError::InvalidFieldElement);
+    }
+    FpE::from_bytes_be_canonical(&bytes[PADDING_SIZE..])
+        .map_err(|_| PrecompileError::InvalidFieldElement)
+}
+
+fn encode_fp2(element: &Fp2E, bytes: &mut [u8]) {
+    let [c0, c1] = element.value();
+    encode_fp(c0, &mut bytes[..64]);
+    encode_fp(c1, &mut bytes[64..]);
+}
+
+fn decode_fp2(bytes: &[u8]) -> Result<Fp2E, PrecompileError> {
+    let c0 = decode_fp(&bytes[..64])?;
+    let c1 = decode_fp(&bytes[64..])?;
+    Ok(Fp2E::new([c0, c1]))
+}
+
+fn encode_bool(value: bool) -> [u8; 32] {
+    let mut bytes = [0; 32];
+    bytes[31] = value as u8;
+    bytes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::elliptic_curve::traits::IsEllipticCurve;
+
+    fn msm_input<G, const N: usize>(pairs: &[(G, u64)], encode: impl Fn(&G) -> [u8; N]) -> Vec<u8> {
+        pairs
+            .iter()
+            .flat_map(|(point, scalar)| {
+                encode(point)
+                    .into_iter()
+                    .chain(U256::from_u64(*scalar).to_bytes_be())
+            })
+            .collect()
+    }
+
+    #[test]
+    fn points_round_trip() {
+        let p = BLS12381Curve::generator().operate_with_self(1234_u64);
+        let q = BLS12381TwistCurve::generator().operate_with_self(5678_u64);
+        assert_eq!(decode_g1(&encode_g1(&p)), Ok(p));
+        assert_eq!(decode_g2(&encode_g2(&q)), Ok(q));
+        assert_eq!(decode_g1(&[0; 128]), Ok(G1Point::neutral_element()));
+        assert_eq!(encode_g2(&G2Point::neutral_element()), [0; 256]);
+    }
+
+    #[test]
+    fn field_elements_are_padded_to_64_bytes() {
+        let g = BLS12381Curve::generator().to_affine();
+        let bytes = encode_g1(&g);
+        assert_eq!(bytes[..16], [0; 16]);
+        assert_eq!(bytes[16..64], g.x().to_bytes_be_array());
+
+        let mut invalid = bytes;
+        invalid[0] = 1;
+        assert_eq!(
+            decode_g1(&invalid),
+            Err(PrecompileError::InvalidFieldElement)
+        );
+    }
+
+    #[test]
+    fn g1_add_adds_points() {
+        let g = BLS12381Curve::generator();
+        let mut input = encode_g1(&g).to_vec();
+        input.extend(encode_g1(&g.operate_with_self(2_u64)));
+        assert_eq!(g1_add(&input), Ok(encode_g1(&g.operate_with_self(3_u64))));
+        assert_eq!(
+            g1_add(&input[1..]),
+            Err(PrecompileError::InvalidInputLength)
+        );
+    }
+
+    #[test]
+    fn g2_add_adds_points() {
+        let g = BLS12381TwistCurve::generator();
+        let mut input = encode_g2(&g).to_vec();
+        input.extend(encode_g2(&g.neg()));
+        assert_eq!(g2_add(&input), Ok([0; 256]));
+    }
+
+    #[test]
+    fn g1_msm_computes_the_sum_of_the_products() {
+        let g = BLS12381Curve::generator();
+        let input = msm_input(
+            &[(g.clone(), 3), (g.operate_with_self(5_u64), 7)],
+            encode_g1,
+        );
+        assert_eq!(g1_msm(&input), Ok(encode_g1(&g.operate_with_self(38_u64))));
+        assert_eq!(g1_msm(&[]), Err(PrecompileError::InvalidInputLength));
+    }
+
+    #[test]
+    fn g2_msm_computes_the_sum_of_the_products() {
+        let g = BLS12381TwistCurve::generator();
+        let input = msm_input(&[(g.clone(), 2), (g.clone(), 9)], encode_g2);
+        assert_eq!(g2_msm(&input), Ok(encode_g2(&g.operate_with_self(11_u64))));
+    }
+
+    #[test]
+    fn g1_msm_rejects_points_outside_the_subgroup() {
+        // (0, 2) is on the curve, but the curve has a cofactor.
+        let p = G1Point::from_affine(FpE::zero(), FpE::from(2)).unwrap();
+        assert!(!p.is_in_subgroup());
+        let input = msm_input(&[(p, 1)], encode_g1);
+        assert_eq!(g1_msm(&input), Err(PrecompileError::PointNotInSubgroup));
+    }
+
+    #[test]
+    fn pairing_check_checks_the_product_of_pairings() {
+        let p = BLS12381Curve::generator().operate_with_self(3_u64);
+        let q = BLS12381TwistCurve::generator().operate_with_self(5_u64);
+
+        let balanced = encode_pairing_input(&[(p.clone(), q.clone()), (p.neg(), q.clone())]);
+        assert_eq!(pairing_check(&balanced), Ok(encode_bool(true)));
+
+        let unbalanced = encode_pairing_input(&[(p.clone(), q.clone()), (p, q)]);
+        assert_eq!(pairing_check(&unbalanced), Ok(encode_bool(false)));
+
+        assert_eq!(pairing_check(&[]), Err(PrecompileError::InvalidInputLength));
+    }
+}
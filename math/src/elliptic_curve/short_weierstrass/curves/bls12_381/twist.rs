@@ -1,5 +1,7 @@
 use crate::cyclic_group::IsGroup;
-use crate::elliptic_curve::short_weierstrass::point::ShortWeierstrassProjectivePoint;
+use crate::elliptic_curve::short_weierstrass::point::{
+    impl_canonical_point_byte_conversion, ShortWeierstrassProjectivePoint,
+};
 use crate::elliptic_curve::traits::IsEllipticCurve;
 use crate::unsigned_integer::element::U384;
 use crate::{
@@ -46,6 +48,8 @@ impl IsShortWeierstrass for BLS12381TwistCurve {
     }
 }
 
+impl_canonical_point_byte_conversion!(BLS12381TwistCurve, 192);
+
 impl ShortWeierstrassProjectivePoint<BLS12381TwistCurve> {
     /// This function is related to the map ψ: E_twist(𝔽p²) -> E(𝔽p¹²).
     /// Given an affine point G in E_twist(𝔽p²) returns x, y such that
@@ -4,7 +4,9 @@ use super::{
     twist::BN254TwistCurve,
 };
 use crate::cyclic_group::IsGroup;
-use crate::elliptic_curve::short_weierstrass::point::ShortWeierstrassProjectivePoint;
+use crate::elliptic_curve::short_weierstrass::point::{
+    impl_canonical_point_byte_conversion, ShortWeierstrassProjectivePoint,
+};
 use crate::elliptic_curve::traits::IsEllipticCurve;
 use crate::{
     elliptic_curve::short_weierstrass::traits::IsShortWeierstrass, field::element::FieldElement,
@@ -39,6 +41,8 @@ impl IsShortWeierstrass for BN254Curve {
     }
 }
 
+impl_canonical_point_byte_conversion!(BN254Curve, 64);
+
 impl ShortWeierstrassProjectivePoint<BN254Curve> {
     pub fn is_in_subgroup(&self) -> bool {
         true
@@ -2,8 +2,9 @@ use crate::{
     field::{
         element::FieldElement,
         fields::montgomery_backed_prime_fields::{IsModulus, MontgomeryBackendPrimeField},
+        traits::IsFFTField,
     },
-    unsigned_integer::element::U256,
+    unsigned_integer::element::{UnsignedInteger, U256},
 };
 
 #[derive(Clone, Debug)]
@@ -20,3 +21,10 @@ impl IsModulus<U256> for FrConfig {
 pub type FrField = MontgomeryBackendPrimeField<FrConfig, 4>;
 /// FrElement using MontgomeryBackend for Bn254
 pub type FrElement = FieldElement<FrField>;
+
+impl IsFFTField for FrField {
+    const TWO_ADICITY: u64 = 28;
+    const TWO_ADIC_PRIMITVE_ROOT_OF_UNITY: Self::BaseType = UnsignedInteger::from_hex_unchecked(
+        "2a3c09f0a58a7e8500e0a7eb8ef62abc402d111e41112ed49bd61b6e725b19f0",
+    );
+}
@@ -11,6 +11,7 @@ use crate::field::{
 #[cfg(feature = "alloc")]
 use crate::traits::ByteConversion;
 use crate::unsigned_integer::element::U256;
+use crate::{errors::ByteConversionError, traits::CanonicalByteConversion};
 
 pub const BN254_PRIME_FIELD_ORDER: U256 =
     U256::from_hex_unchecked("30644e72e131a029b85045b68181585d97816a916871ca8d3c208c16d87cfd47");
@@ -152,6 +153,10 @@ impl IsSubFieldOf<Degree2ExtensionField> for BN254PrimeField {
     ) -> alloc::vec::Vec<Self::BaseType> {
         b.into_iter().map(|x| x.to_raw()).collect()
     }
+
+    fn from_subfield_slice(b: &[Self::BaseType]) -> <Degree2ExtensionField as IsField>::BaseType {
+        [FieldElement::from_raw(b[0]), FieldElement::from_raw(b[1])]
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -201,6 +206,37 @@ impl ByteConversion for FieldElement<Degree2ExtensionField> {
     }
 }
 
+/// Elements `c0 + c1 * u` are encoded as the encoding of `c0` followed by the one of `c1`.
+impl CanonicalByteConversion for FieldElement<Degree2ExtensionField> {
+    type Bytes = [u8; 64];
+
+    fn to_bytes_be_array(&self) -> Self::Bytes {
+        let mut bytes = [0; 64];
+        bytes[..32].copy_from_slice(&self.value()[0].to_bytes_be_array());
+        bytes[32..].copy_from_slice(&self.value()[1].to_bytes_be_array());
+        bytes
+    }
+
+    fn to_bytes_le_array(&self) -> Self::Bytes {
+        let mut bytes = [0; 64];
+        bytes[..32].copy_from_slice(&self.value()[0].to_bytes_le_array());
+        bytes[32..].copy_from_slice(&self.value()[1].to_bytes_le_array());
+        bytes
+    }
+
+    fn from_bytes_be_array(bytes: &Self::Bytes) -> Result<Self, ByteConversionError> {
+        let c0 = FieldElement::from_bytes_be_canonical(&bytes[..32])?;
+        let c1 = FieldElement::from_bytes_be_canonical(&bytes[32..])?;
+        Ok(Self::new([c0, c1]))
+    }
+
+    fn from_bytes_le_array(bytes: &Self::Bytes) -> Result<Self, ByteConversionError> {
+        let c0 = FieldElement::from_bytes_le_canonical(&bytes[..32])?;
+        let c1 = FieldElement::from_bytes_le_canonical(&bytes[32..])?;
+        Ok(Self::new([c0, c1]))
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct LevelTwoResidue;
 
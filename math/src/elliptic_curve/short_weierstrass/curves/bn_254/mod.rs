@@ -3,5 +3,7 @@ pub mod curve;
 pub mod default_types;
 pub mod field_extension;
 pub mod pairing;
+#[cfg(feature = "alloc")]
+pub mod precompiles;
 pub mod sqrt;
 pub mod twist;
@@ -0,0 +1,258 @@
+//! Encodings and semantics of the BN254 precompiles of the EVM: `ECADD` (0x06) and `ECMUL`
+//! (0x07) from [EIP-196](https://eips.ethereum.org/EIPS/eip-196), and `ECPAIRING` (0x08) from
+//! [EIP-197](https://eips.ethereum.org/EIPS/eip-197).
+//!
+//! Field elements are 32 byte big endian integers smaller than the modulus. Points of G1 are
+//! encoded as `x || y`, and the point at infinity as `(0, 0)`. Elements `a + b * i` of the
+//! quadratic extension are encoded as `b || a`, with the imaginary part first, so points of G2
+//! are `x.c1 || x.c0 || y.c1 || y.c0`.
+
+use alloc::vec::Vec;
+
+use super::{
+    curve::BN254Curve,
+    field_extension::{BN254PrimeField, Degree2ExtensionField},
+    pairing::BN254AtePairing,
+    twist::BN254TwistCurve,
+};
+use crate::{
+    cyclic_group::IsGroup,
+    elliptic_curve::{
+        short_weierstrass::point::ShortWeierstrassProjectivePoint,
+        traits::{FromAffine, IsPairing},
+    },
+    errors::PrecompileError,
+    field::element::FieldElement,
+    traits::{ByteConversion, CanonicalByteConversion},
+    unsigned_integer::element::U256,
+};
+
+type G1Point = ShortWeierstrassProjectivePoint<BN254Curve>;
+type G2Point = ShortWeierstrassProjectivePoint<BN254TwistCurve>;
+type FpE = FieldElement<BN254PrimeField>;
+type Fp2E = FieldElement<Degree2ExtensionField>;
+
+pub const FIELD_ELEMENT_SIZE: usize = 32;
+pub const SCALAR_SIZE: usize = 32;
+pub const G1_POINT_SIZE: usize = 2 * FIELD_ELEMENT_SIZE;
+pub const G2_POINT_SIZE: usize = 4 * FIELD_ELEMENT_SIZE;
+/// Size of each pair of points of the input of `ECPAIRING`.
+pub const PAIR_SIZE: usize = G1_POINT_SIZE + G2_POINT_SIZE;
+
+pub fn encode_g1(point: &G1Point) -> [u8; G1_POINT_SIZE] {
+    let mut bytes = [0; G1_POINT_SIZE];
+    if !point.is_neutral_element() {
+        let point = point.to_affine();
+        bytes[..32].copy_from_slice(&point.x().to_bytes_be_array());
+        bytes[32..].copy_from_slice(&point.y().to_bytes_be_array());
+    }
+    bytes
+}
+
+/// Decodes a point of G1. Fails if a coordinate is not smaller than the modulus, or if the
+/// point is not on the curve.
+pub fn decode_g1(bytes: &[u8; G1_POINT_SIZE]) -> Result<G1Point, PrecompileError> {
+    let x = decode_fp(&bytes[..32])?;
+    let y = decode_fp(&bytes[32..])?;
+    if x == FpE::zero() && y == FpE::zero() {
+        return Ok(G1Point::neutral_element());
+    }
+    G1Point::from_affine(x, y).map_err(|_| PrecompileError::PointNotOnCurve)
+}
+
+pub fn encode_g2(point: &G2Point) -> [u8; G2_POINT_SIZE] {
+    let mut bytes = [0; G2_POINT_SIZE];
+    if !point.is_neutral_element() {
+        let point = point.to_affine();
+        encode_fp2(point.x(), &mut bytes[..64]);
+        encode_fp2(point.y(), &mut bytes[64..]);
+    }
+    bytes
+}
+
+/// Decodes a point of G2. Fails if a coordinate is not smaller than the modulus, if the point
+/// is not on the twist, or if it's not in the subgroup of order r.
+pub fn decode_g2(bytes: &[u8; G2_POINT_SIZE]) -> Result<G2Point, PrecompileError> {
+    let x = decode_fp2(&bytes[..64])?;
+    let y = decode_fp2(&bytes[64..])?;
+    if x == Fp2E::zero() && y == Fp2E::zero() {
+        return Ok(G2Point::neutral_element());
+    }
+    let point = G2Point::from_affine(x, y).map_err(|_| PrecompileError::PointNotOnCurve)?;
+    if !point.is_in_subgroup() {
+        return Err(PrecompileError::PointNotInSubgroup);
+    }
+    Ok(point)
+}
+
+/// Encodes pairs of points as the input of `ECPAIRING`.
+pub fn encode_pairing_input(pairs: &[(G1Point, G2Point)]) -> Vec<u8> {
+    pairs
+        .iter()
+        .flat_map(|(p, q)| encode_g1(p).into_iter().chain(encode_g2(q)))
+        .collect()
+}
+
+/// `ECADD`: adds two points of G1. The input is `p || q`, right padded with zeros or truncated
+/// to 128 bytes. Returns the encoding of `p + q`.
+pub fn ec_add(input: &[u8]) -> Result<[u8; G1_POINT_SIZE], PrecompileError> {
+    let input: [u8; 2 * G1_POINT_SIZE] = padded(input);
+    let p = decode_g1(input[..64].try_into().unwrap())?;
+    let q = decode_g1(input[64..].try_into().unwrap())?;
+    Ok(encode_g1(&p.operate_with(&q)))
+}
+
+/// `ECMUL`: multiplies a point of G1 by a scalar. The input is `p || s`, right padded with
+/// zeros or truncated to 96 bytes, where `s` is any 256 bit big endian integer. Returns the
+/// encoding of `s * p`.
+pub fn ec_mul(input: &[u8]) -> Result<[u8; G1_POINT_SIZE], PrecompileError> {
+    let input: [u8; G1_POINT_SIZE + SCALAR_SIZE] = padded(input);
+    let p = decode_g1(input[..64].try_into().unwrap())?;
+    let scalar = decode_scalar(&input[64..]);
+    Ok(encode_g1(&p.operate_with_self(scalar)))
+}
+
+/// `ECPAIRING`: checks whether the product of the pairings of the pairs of points of the input
+/// is one. The input is a concatenation of pairs `p || q` with `p` in G1 and `q` in G2, and
+/// can be empty. Returns a 32 byte big endian one if the check passes, and zero otherwise.
+pub fn ec_pairing(input: &[u8]) -> Result<[u8; 32], PrecompileError> {
+    if input.len() % PAIR_SIZE != 0 {
+        return Err(PrecompileError::InvalidInputLength);
+    }
+    let pairs = input
+        .chunks_exact(PAIR_SIZE)
+        .map(|pair| {
+            let p = decode_g1(pair[..G1_POINT_SIZE].try_into().unwrap())?;
+            let q = decode_g2(pair[G1_POINT_SIZE..].try_into().unwrap())?;
+            Ok((p, q))
+        })
+        .collect::<Result<Vec<_>, PrecompileError>>()?;
+    let pairs: Vec<_> = pairs.iter().map(|(p, q)| (p, q)).collect();
+
+    let result =
+        BN254AtePairing::compute_batch(&pairs).map_err(|_| PrecompileError::PointNotInSubgroup)?;
+    Ok(encode_bool(result == FieldElement::one()))
+}
+
+/// Copies `input` into an array of `N` bytes, padding it with zeros at the end or truncating
+/// it, as the EVM does with the input of the precompiles.
+fn padded<const N: usize>(input: &[u8]) -> [u8; N] {
+    let mut bytes = [0; N];
+    let len = input.len().min(N);
+    bytes[..len].copy_from_slice(&input[..len]);
+    bytes
+}
+
+fn decode_scalar(bytes: &[u8]) -> U256 {
+    // Every 32 bytes are a valid `U256`.
+    U256::from_bytes_be(bytes).unwrap()
+}
+
+fn decode_fp(bytes: &[u8]) -> Result<FpE, PrecompileError> {
+    FpE::from_bytes_be_canonical(bytes).map_err(|_| PrecompileError::InvalidFieldElement)
+}
+
+fn encode_fp2(element: &Fp2E, bytes: &mut [u8]) {
+    let [c0, c1] = element.value();
+    bytes[..32].copy_from_slice(&c1.to_bytes_be_array());
+    bytes[32..].copy_from_slice(&c0.to_bytes_be_array());
+}
+
+fn decode_fp2(bytes: &[u8]) -> Result<Fp2E, PrecompileError> {
+    let c1 = decode_fp(&bytes[..32])?;
+    let c0 = decode_fp(&bytes[32..])?;
+    Ok(Fp2E::new([c0, c1]))
+}
+
+fn encode_bool(value: bool) -> [u8; 32] {
+    let mut bytes = [0; 32];
+    bytes[31] = value as u8;
+    bytes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::elliptic_curve::traits::IsEllipticCurve;
+
+    #[test]
+    fn generator_is_encoded_as_one_and_two() {
+        let mut expected = [0; 64];
+        expected[31] = 1;
+        expected[63] = 2;
+        assert_eq!(encode_g1(&BN254Curve::generator()), expected);
+        assert_eq!(decode_g1(&expected), Ok(BN254Curve::generator()));
+    }
+
+    #[test]
+    fn points_round_trip() {
+        let p = BN254Curve::generator().operate_with_self(1234_u64);
+        let q = BN254TwistCurve::generator().operate_with_self(5678_u64);
+        assert_eq!(decode_g1(&encode_g1(&p)), Ok(p));
+        assert_eq!(decode_g2(&encode_g2(&q)), Ok(q));
+        assert_eq!(encode_g1(&G1Point::neutral_element()), [0; 64]);
+        assert_eq!(decode_g2(&[0; 128]), Ok(G2Point::neutral_element()));
+    }
+
+    #[test]
+    fn g2_coordinates_are_encoded_with_the_imaginary_part_first() {
+        let q = BN254TwistCurve::generator().to_affine();
+        let bytes = encode_g2(&q);
+        assert_eq!(&bytes[..32], &q.x().value()[1].to_bytes_be_array());
+        assert_eq!(&bytes[32..64], &q.x().value()[0].to_bytes_be_array());
+    }
+
+    #[test]
+    fn ec_add_adds_points() {
+        let g = BN254Curve::generator();
+        let mut input = encode_g1(&g).to_vec();
+        input.extend(encode_g1(&g.operate_with_self(2_u64)));
+        assert_eq!(ec_add(&input), Ok(encode_g1(&g.operate_with_self(3_u64))));
+    }
+
+    #[test]
+    fn ec_add_pads_short_inputs_with_zeros() {
+        let g = BN254Curve::generator();
+        assert_eq!(ec_add(&encode_g1(&g)), Ok(encode_g1(&g)));
+        assert_eq!(ec_add(&[]), Ok([0; 64]));
+    }
+
+    #[test]
+    fn ec_mul_multiplies_by_any_256_bit_scalar() {
+        let g = BN254Curve::generator();
+        let mut input = encode_g1(&g).to_vec();
+        input.extend([0xff; 32]);
+        let scalar = U256::from_hex_unchecked(&"f".repeat(64));
+        assert_eq!(ec_mul(&input), Ok(encode_g1(&g.operate_with_self(scalar))));
+    }
+
+    #[test]
+    fn invalid_points_are_rejected() {
+        let mut not_on_curve = [0; 64];
+        not_on_curve[31] = 1;
+        not_on_curve[63] = 1;
+        assert_eq!(ec_add(&not_on_curve), Err(PrecompileError::PointNotOnCurve));
+
+        let mut too_big = [0xff; 64];
+        too_big[32..].fill(0);
+        assert_eq!(ec_mul(&too_big), Err(PrecompileError::InvalidFieldElement));
+    }
+
+    #[test]
+    fn ec_pairing_checks_the_product_of_pairings() {
+        let p = BN254Curve::generator().operate_with_self(3_u64);
+        let q = BN254TwistCurve::generator().operate_with_self(5_u64);
+
+        let balanced = encode_pairing_input(&[(p.clone(), q.clone()), (p.neg(), q.clone())]);
+        assert_eq!(ec_pairing(&balanced), Ok(encode_bool(true)));
+
+        let unbalanced = encode_pairing_input(&[(p.clone(), q.clone()), (p, q)]);
+        assert_eq!(ec_pairing(&unbalanced), Ok(encode_bool(false)));
+
+        assert_eq!(ec_pairing(&[]), Ok(encode_bool(true)));
+        assert_eq!(
+            ec_pairing(&balanced[1..]),
+            Err(PrecompileError::InvalidInputLength)
+        );
+    }
+}
@@ -1,4 +1,6 @@
-use crate::elliptic_curve::short_weierstrass::point::ShortWeierstrassProjectivePoint;
+use crate::elliptic_curve::short_weierstrass::point::{
+    impl_canonical_point_byte_conversion, ShortWeierstrassProjectivePoint,
+};
 use crate::elliptic_curve::traits::IsEllipticCurve;
 use crate::unsigned_integer::element::U256;
 use crate::{
@@ -60,6 +62,8 @@ impl IsShortWeierstrass for BN254TwistCurve {
     }
 }
 
+impl_canonical_point_byte_conversion!(BN254TwistCurve, 128);
+
 #[cfg(test)]
 mod tests {
     use crate::{
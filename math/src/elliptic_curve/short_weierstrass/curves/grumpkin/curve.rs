@@ -1,4 +1,6 @@
-use crate::elliptic_curve::short_weierstrass::point::ShortWeierstrassProjectivePoint;
+use crate::elliptic_curve::short_weierstrass::point::{
+    impl_canonical_point_byte_conversion, ShortWeierstrassProjectivePoint,
+};
 use crate::elliptic_curve::traits::IsEllipticCurve;
 use crate::field::fields::montgomery_backed_prime_fields::{
     IsModulus, MontgomeryBackendPrimeField,
@@ -45,6 +47,8 @@ impl IsShortWeierstrass for GrumpkinCurve {
     }
 }
 
+impl_canonical_point_byte_conversion!(GrumpkinCurve, 64);
+
 // Grumpkin Fp
 // p = 21888242871839275222246405745257275088548364400416034343698204186575808495617
 // Equal tp BN254 Fr
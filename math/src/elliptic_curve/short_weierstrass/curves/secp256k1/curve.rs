@@ -1,4 +1,6 @@
-use crate::elliptic_curve::short_weierstrass::point::ShortWeierstrassProjectivePoint;
+use crate::elliptic_curve::short_weierstrass::point::{
+    impl_canonical_point_byte_conversion, ShortWeierstrassProjectivePoint,
+};
 use crate::elliptic_curve::traits::IsEllipticCurve;
 use crate::field::fields::secp256k1_field::Secp256k1PrimeField;
 use crate::{
@@ -36,6 +38,8 @@ impl IsShortWeierstrass for Secp256k1Curve {
     }
 }
 
+impl_canonical_point_byte_conversion!(Secp256k1Curve, 64);
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1,4 +1,6 @@
-use crate::elliptic_curve::short_weierstrass::point::ShortWeierstrassProjectivePoint;
+use crate::elliptic_curve::short_weierstrass::point::{
+    impl_canonical_point_byte_conversion, ShortWeierstrassProjectivePoint,
+};
 use crate::elliptic_curve::traits::IsEllipticCurve;
 use crate::field::fields::secp256r1_field::Secp256r1PrimeField;
 use crate::{
@@ -39,6 +41,8 @@ impl IsShortWeierstrass for Secp256r1Curve {
     }
 }
 
+impl_canonical_point_byte_conversion!(Secp256r1Curve, 64);
+
 #[cfg(test)]
 mod tests {
     use super::*;
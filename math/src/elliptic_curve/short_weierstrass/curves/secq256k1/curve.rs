@@ -1,4 +1,6 @@
-use crate::elliptic_curve::short_weierstrass::point::ShortWeierstrassProjectivePoint;
+use crate::elliptic_curve::short_weierstrass::point::{
+    impl_canonical_point_byte_conversion, ShortWeierstrassProjectivePoint,
+};
 use crate::elliptic_curve::traits::IsEllipticCurve;
 use crate::field::fields::secp256k1_scalarfield::Secp256k1ScalarField;
 use crate::{
@@ -35,6 +37,8 @@ impl IsShortWeierstrass for Secq256k1Curve {
     }
 }
 
+impl_canonical_point_byte_conversion!(Secq256k1Curve, 64);
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1,6 +1,9 @@
 use crate::{
     elliptic_curve::{
-        short_weierstrass::{point::ShortWeierstrassProjectivePoint, traits::IsShortWeierstrass},
+        short_weierstrass::{
+            point::{impl_canonical_point_byte_conversion, ShortWeierstrassProjectivePoint},
+            traits::IsShortWeierstrass,
+        },
         traits::IsEllipticCurve,
     },
     field::{
@@ -52,3 +55,5 @@ impl IsShortWeierstrass for StarkCurve {
         )
     }
 }
+
+impl_canonical_point_byte_conversion!(StarkCurve, 64);
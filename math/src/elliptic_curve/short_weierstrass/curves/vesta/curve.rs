@@ -1,4 +1,6 @@
-use crate::elliptic_curve::short_weierstrass::point::ShortWeierstrassProjectivePoint;
+use crate::elliptic_curve::short_weierstrass::point::{
+    impl_canonical_point_byte_conversion, ShortWeierstrassProjectivePoint,
+};
 use crate::elliptic_curve::traits::IsEllipticCurve;
 use crate::field::fields::vesta_field::Vesta255PrimeField;
 use crate::{
@@ -31,6 +33,8 @@ impl IsShortWeierstrass for VestaCurve {
     }
 }
 
+impl_canonical_point_byte_conversion!(VestaCurve, 64);
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -4,9 +4,9 @@ use crate::{
         point::{JacobianPoint, ProjectivePoint},
         traits::{EllipticCurveError, FromAffine, IsEllipticCurve},
     },
-    errors::DeserializationError,
+    errors::{ByteConversionError, DeserializationError},
     field::element::FieldElement,
-    traits::{ByteConversion, Deserializable},
+    traits::{ByteConversion, CanonicalByteConversion, Deserializable},
 };
 
 use super::traits::IsShortWeierstrass;
@@ -344,6 +344,107 @@ where
     }
 }
 
+impl<E> ShortWeierstrassProjectivePoint<E>
+where
+    E: IsShortWeierstrass,
+    FieldElement<E::BaseField>: CanonicalByteConversion,
+{
+    /// Writes the canonical encodings of the affine coordinates `x` and `y` of the point, one
+    /// after the other, to `bytes`. The neutral element, which has no affine coordinates, is
+    /// written as zeros, which are not the coordinates of any point of curves with `b != 0`.
+    pub(crate) fn write_affine_bytes(&self, bytes: &mut [u8], endianness: Endianness) {
+        if self.is_neutral_element() {
+            bytes.fill(0);
+            return;
+        }
+        let point = self.to_affine();
+        let (x_bytes, y_bytes) = bytes.split_at_mut(bytes.len() / 2);
+        if endianness == Endianness::BigEndian {
+            x_bytes.copy_from_slice(point.x().to_bytes_be_array().as_ref());
+            y_bytes.copy_from_slice(point.y().to_bytes_be_array().as_ref());
+        } else {
+            x_bytes.copy_from_slice(point.x().to_bytes_le_array().as_ref());
+            y_bytes.copy_from_slice(point.y().to_bytes_le_array().as_ref());
+        }
+    }
+
+    /// Reads a point written by [`Self::write_affine_bytes`]. Fails if the coordinates are not
+    /// canonical or not the ones of a point of the curve. Subgroup membership is not checked.
+    pub(crate) fn read_affine_bytes(
+        bytes: &[u8],
+        endianness: Endianness,
+    ) -> Result<Self, ByteConversionError> {
+        if bytes.iter().all(|byte| *byte == 0) {
+            return Ok(Self::neutral_element());
+        }
+        let (x_bytes, y_bytes) = bytes.split_at(bytes.len() / 2);
+        let (x, y) = if endianness == Endianness::BigEndian {
+            (
+                FieldElement::from_bytes_be_canonical(x_bytes)?,
+                FieldElement::from_bytes_be_canonical(y_bytes)?,
+            )
+        } else {
+            (
+                FieldElement::from_bytes_le_canonical(x_bytes)?,
+                FieldElement::from_bytes_le_canonical(y_bytes)?,
+            )
+        };
+        Self::from_affine(x, y).map_err(|_| ByteConversionError::InvalidValue)
+    }
+}
+
+/// Implements [`CanonicalByteConversion`] for the points of `$curve`, whose affine coordinates
+/// encode to `$num_bytes` bytes together, as described in
+/// [`ShortWeierstrassProjectivePoint::write_affine_bytes`].
+macro_rules! impl_canonical_point_byte_conversion {
+    ($curve:ty, $num_bytes:literal) => {
+        impl $crate::traits::CanonicalByteConversion
+            for $crate::elliptic_curve::short_weierstrass::point::ShortWeierstrassProjectivePoint<
+                $curve,
+            >
+        {
+            type Bytes = [u8; $num_bytes];
+
+            fn to_bytes_be_array(&self) -> Self::Bytes {
+                let mut bytes = [0; $num_bytes];
+                self.write_affine_bytes(
+                    &mut bytes,
+                    $crate::elliptic_curve::short_weierstrass::point::Endianness::BigEndian,
+                );
+                bytes
+            }
+
+            fn to_bytes_le_array(&self) -> Self::Bytes {
+                let mut bytes = [0; $num_bytes];
+                self.write_affine_bytes(
+                    &mut bytes,
+                    $crate::elliptic_curve::short_weierstrass::point::Endianness::LittleEndian,
+                );
+                bytes
+            }
+
+            fn from_bytes_be_array(
+                bytes: &Self::Bytes,
+            ) -> Result<Self, $crate::errors::ByteConversionError> {
+                Self::read_affine_bytes(
+                    bytes,
+                    $crate::elliptic_curve::short_weierstrass::point::Endianness::BigEndian,
+                )
+            }
+
+            fn from_bytes_le_array(
+                bytes: &Self::Bytes,
+            ) -> Result<Self, $crate::errors::ByteConversionError> {
+                Self::read_affine_bytes(
+                    bytes,
+                    $crate::elliptic_curve::short_weierstrass::point::Endianness::LittleEndian,
+                )
+            }
+        }
+    };
+}
+pub(crate) use impl_canonical_point_byte_conversion;
+
 #[cfg(feature = "alloc")]
 impl<E> AsBytes for ShortWeierstrassProjectivePoint<E>
 where
@@ -604,8 +705,12 @@ mod tests {
     };
     #[cfg(feature = "alloc")]
     use crate::{
-        elliptic_curve::short_weierstrass::curves::bls12_381::field_extension::BLS12381PrimeField,
-        field::element::FieldElement,
+        elliptic_curve::short_weierstrass::curves::{
+            bls12_381::field_extension::{BLS12381FieldModulus, BLS12381PrimeField},
+            bn_254::twist::BN254TwistCurve,
+        },
+        field::{element::FieldElement, fields::montgomery_backed_prime_fields::IsModulus},
+        unsigned_integer::element::UnsignedInteger,
     };
 
     #[cfg(feature = "alloc")]
@@ -871,6 +976,66 @@ mod tests {
         );
     }
 
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn canonical_bytes_round_trip() {
+        let p = point();
+        let bytes_be = p.to_bytes_be_array();
+        assert_eq!(
+            bytes_be.to_vec(),
+            p.serialize(PointFormat::Uncompressed, Endianness::BigEndian)
+        );
+        assert_eq!(
+            ShortWeierstrassProjectivePoint::<BLS12381Curve>::from_bytes_be_array(&bytes_be),
+            Ok(p.clone())
+        );
+        assert_eq!(
+            ShortWeierstrassProjectivePoint::<BLS12381Curve>::from_bytes_le_array(
+                &p.to_bytes_le_array()
+            ),
+            Ok(p)
+        );
+
+        let g2 = BN254TwistCurve::generator().operate_with_self(5_u64);
+        assert_eq!(
+            ShortWeierstrassProjectivePoint::<BN254TwistCurve>::from_bytes_be_array(
+                &g2.to_bytes_be_array()
+            ),
+            Ok(g2)
+        );
+    }
+
+    #[test]
+    fn canonical_bytes_of_the_neutral_element_are_zeros() {
+        let neutral = ShortWeierstrassProjectivePoint::<BLS12381Curve>::neutral_element();
+        assert_eq!(neutral.to_bytes_be_array(), [0; 96]);
+        assert_eq!(
+            ShortWeierstrassProjectivePoint::<BLS12381Curve>::from_bytes_le_array(&[0; 96]),
+            Ok(neutral)
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn canonical_bytes_of_invalid_points_are_rejected() {
+        let mut bytes = point().to_bytes_be_array();
+        bytes[95] ^= 1;
+        assert_eq!(
+            ShortWeierstrassProjectivePoint::<BLS12381Curve>::from_bytes_be_array(&bytes),
+            Err(ByteConversionError::InvalidValue)
+        );
+
+        // The same point, with the modulus added to its `x` coordinate.
+        let mut bytes = point().to_bytes_be_array();
+        let x = UnsignedInteger::<6>::from_bytes_be_array(bytes[..48].try_into().unwrap()).unwrap();
+        let (x, _) = UnsignedInteger::add(&x, &BLS12381FieldModulus::MODULUS);
+        bytes[..48].copy_from_slice(&x.to_bytes_be_array());
+        assert_eq!(
+            ShortWeierstrassProjectivePoint::<BLS12381Curve>::from_bytes_be_array(&bytes),
+            Err(ByteConversionError::InvalidValue)
+        );
+    }
+
     #[test]
     fn test_multiplication_by_order_jacobian() {
         let x = FEE::new_base("36bb494facde72d0da5c770c4b16d9b2d45cfdc27604a25a1a80b020798e5b0dbd4c6d939a8f8820f042a29ce552ee5");
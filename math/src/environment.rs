@@ -0,0 +1,254 @@
+//! Sources of randomness and time.
+//!
+//! Provers only access randomness and clocks through [`IsRandomSource`] and [`IsClock`], so
+//! they build on targets without an operating system, like `wasm32-unknown-unknown`, and can be
+//! made deterministic inside fuzzers or enclaves by passing them a [`SeededRandomSource`].
+//!
+//! With the `getrandom` feature, [`OsRandomSource`] draws bytes from the operating system. The
+//! `js` feature makes it, and [`DefaultClock`], use the JavaScript APIs on
+//! `wasm32-unknown-unknown`.
+
+use core::{cell::Cell, time::Duration};
+
+use crate::field::{
+    element::FieldElement,
+    traits::{IsField, IsPrimeField},
+};
+
+/// A source of random bytes.
+pub trait IsRandomSource {
+    /// Fills `dest` with random bytes.
+    fn fill_bytes(&self, dest: &mut [u8]);
+
+    fn next_u64(&self) -> u64 {
+        let mut bytes = [0; 8];
+        self.fill_bytes(&mut bytes);
+        u64::from_le_bytes(bytes)
+    }
+}
+
+/// Random bytes from the operating system, or from `crypto.getRandomValues` on
+/// `wasm32-unknown-unknown` with the `js` feature.
+#[cfg(feature = "getrandom")]
+#[derive(Clone, Copy, Debug, Default)]
+pub struct OsRandomSource;
+
+#[cfg(feature = "getrandom")]
+impl IsRandomSource for OsRandomSource {
+    /// # Panics
+    ///
+    /// Panics if the operating system fails to provide random bytes.
+    fn fill_bytes(&self, dest: &mut [u8]) {
+        getrandom::getrandom(dest).expect("the operating system failed to provide random bytes");
+    }
+}
+
+/// Deterministic bytes generated with SplitMix64 from a seed. It's not cryptographically
+/// secure, so it must only be used to make tests and fuzzers reproducible.
+#[derive(Clone, Debug)]
+pub struct SeededRandomSource {
+    state: Cell<u64>,
+}
+
+impl SeededRandomSource {
+    pub fn new(seed: u64) -> Self {
+        Self {
+            state: Cell::new(seed),
+        }
+    }
+
+    fn next_word(&self) -> u64 {
+        let state = self.state.get().wrapping_add(0x9e3779b97f4a7c15);
+        self.state.set(state);
+        let mut z = state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+        z ^ (z >> 31)
+    }
+}
+
+impl IsRandomSource for SeededRandomSource {
+    fn fill_bytes(&self, dest: &mut [u8]) {
+        for chunk in dest.chunks_mut(8) {
+            let word = self.next_word().to_le_bytes();
+            chunk.copy_from_slice(&word[..chunk.len()]);
+        }
+    }
+}
+
+/// Returns a random element of a prime field, reducing a random integer at least 64 bits
+/// longer than the modulus so that every element has close to the same probability.
+pub fn random_field_element<F: IsPrimeField>(source: &impl IsRandomSource) -> FieldElement<F> {
+    random_field_element_of_bit_size(source, F::field_bit_size())
+}
+
+/// Same as [`random_field_element`] for any field whose elements are represented with at most
+/// `bit_size` bits, such as extension fields.
+pub fn random_field_element_of_bit_size<F: IsField>(
+    source: &impl IsRandomSource,
+    bit_size: usize,
+) -> FieldElement<F> {
+    let num_limbs = bit_size / 64 + 2;
+    let two_to_64 = FieldElement::<F>::from(u64::MAX) + FieldElement::<F>::one();
+    (0..num_limbs).fold(FieldElement::zero(), |acc, _| {
+        acc * &two_to_64 + FieldElement::from(source.next_u64())
+    })
+}
+
+/// Implements [`IsRandomFieldElementGenerator`](crate::traits::IsRandomFieldElementGenerator)
+/// for prime fields with a random source, to provide the blinding factors of the provers.
+#[derive(Clone, Debug, Default)]
+pub struct RandomFieldElementGenerator<R>(pub R);
+
+impl<F: IsPrimeField, R: IsRandomSource> crate::traits::IsRandomFieldElementGenerator<F>
+    for RandomFieldElementGenerator<R>
+{
+    fn generate(&self) -> FieldElement<F> {
+        random_field_element(&self.0)
+    }
+}
+
+/// A monotonic clock.
+pub trait IsClock {
+    /// Returns the time elapsed since an arbitrary origin, or `None` if there's no clock.
+    fn now(&self) -> Option<Duration>;
+}
+
+/// The absence of a clock, for environments without one.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct NoClock;
+
+impl IsClock for NoClock {
+    fn now(&self) -> Option<Duration> {
+        None
+    }
+}
+
+/// The monotonic clock of the standard library.
+#[cfg(all(
+    feature = "std",
+    not(all(target_arch = "wasm32", target_os = "unknown"))
+))]
+#[derive(Clone, Copy, Debug)]
+pub struct StdClock {
+    origin: std::time::Instant,
+}
+
+#[cfg(all(
+    feature = "std",
+    not(all(target_arch = "wasm32", target_os = "unknown"))
+))]
+impl Default for StdClock {
+    fn default() -> Self {
+        Self {
+            origin: std::time::Instant::now(),
+        }
+    }
+}
+
+#[cfg(all(
+    feature = "std",
+    not(all(target_arch = "wasm32", target_os = "unknown"))
+))]
+impl IsClock for StdClock {
+    fn now(&self) -> Option<Duration> {
+        Some(self.origin.elapsed())
+    }
+}
+
+/// The clock of JavaScript, `Date.now()`, with millisecond resolution.
+#[cfg(all(feature = "js", target_arch = "wasm32", target_os = "unknown"))]
+#[derive(Clone, Copy, Debug, Default)]
+pub struct JsClock;
+
+#[cfg(all(feature = "js", target_arch = "wasm32", target_os = "unknown"))]
+impl IsClock for JsClock {
+    fn now(&self) -> Option<Duration> {
+        Some(Duration::from_secs_f64(js_sys::Date::now() / 1000.0))
+    }
+}
+
+/// The clock available in the target: the one of the standard library, the one of JavaScript on
+/// `wasm32-unknown-unknown` with the `js` feature, or none.
+#[cfg(all(
+    feature = "std",
+    not(all(target_arch = "wasm32", target_os = "unknown"))
+))]
+pub type DefaultClock = StdClock;
+#[cfg(all(feature = "js", target_arch = "wasm32", target_os = "unknown"))]
+pub type DefaultClock = JsClock;
+#[cfg(not(any(
+    all(
+        feature = "std",
+        not(all(target_arch = "wasm32", target_os = "unknown"))
+    ),
+    all(feature = "js", target_arch = "wasm32", target_os = "unknown")
+)))]
+pub type DefaultClock = NoClock;
+
+/// Measures the time elapsed since it was started.
+#[derive(Clone, Debug)]
+pub struct Stopwatch<C: IsClock = DefaultClock> {
+    clock: C,
+    start: Option<Duration>,
+}
+
+impl Stopwatch {
+    /// Starts a stopwatch with the [`DefaultClock`].
+    pub fn start() -> Self {
+        Self::start_with(DefaultClock::default())
+    }
+}
+
+impl<C: IsClock> Stopwatch<C> {
+    pub fn start_with(clock: C) -> Self {
+        let start = clock.now();
+        Self { clock, start }
+    }
+
+    /// Returns the time elapsed since the stopwatch was started, which is zero if there's no
+    /// clock.
+    pub fn elapsed(&self) -> Duration {
+        match (self.start, self.clock.now()) {
+            (Some(start), Some(now)) => now.saturating_sub(start),
+            _ => Duration::ZERO,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::field::fields::fft_friendly::stark_252_prime_field::Stark252PrimeField;
+
+    #[test]
+    fn seeded_random_source_is_deterministic() {
+        let (mut a, mut b) = ([0; 21], [0; 21]);
+        SeededRandomSource::new(7).fill_bytes(&mut a);
+        SeededRandomSource::new(7).fill_bytes(&mut b);
+        assert_eq!(a, b);
+
+        let mut c = [0; 21];
+        SeededRandomSource::new(8).fill_bytes(&mut c);
+        assert_ne!(a, c);
+    }
+
+    #[test]
+    fn seeded_random_source_matches_splitmix64() {
+        // First output of the reference implementation of SplitMix64 seeded with 0.
+        assert_eq!(SeededRandomSource::new(0).next_u64(), 0xe220a8397b1dcdaf);
+    }
+
+    #[test]
+    fn random_field_elements_differ() {
+        let source = SeededRandomSource::new(1);
+        let a: FieldElement<Stark252PrimeField> = random_field_element(&source);
+        let b: FieldElement<Stark252PrimeField> = random_field_element(&source);
+        assert_ne!(a, b);
+    }
+
+    #[test]
+    fn stopwatch_without_clock_measures_zero() {
+        assert_eq!(Stopwatch::start_with(NoClock).elapsed(), Duration::ZERO);
+    }
+}
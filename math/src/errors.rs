@@ -28,6 +28,14 @@ pub enum PairingError {
     PointNotInSubgroup,
 }
 
+#[derive(Debug, PartialEq, Eq)]
+pub enum PrecompileError {
+    InvalidInputLength,
+    InvalidFieldElement,
+    PointNotOnCurve,
+    PointNotInSubgroup,
+}
+
 impl From<ByteConversionError> for DeserializationError {
     fn from(error: ByteConversionError) -> Self {
         match error {
@@ -8,6 +8,9 @@ use lambdaworks_gpu::metal::abstractions::errors::MetalError;
 #[cfg(feature = "cuda")]
 use lambdaworks_gpu::cuda::abstractions::errors::CudaError;
 
+#[cfg(feature = "wgpu")]
+use lambdaworks_gpu::wgpu::abstractions::errors::WgpuError;
+
 #[derive(Debug)]
 pub enum FFTError {
     RootOfUnityError(u64),
@@ -17,6 +20,8 @@ pub enum FFTError {
     MetalError(MetalError),
     #[cfg(feature = "cuda")]
     CudaError(CudaError),
+    #[cfg(feature = "wgpu")]
+    WgpuError(WgpuError),
 }
 
 impl Display for FFTError {
@@ -37,6 +42,10 @@ impl Display for FFTError {
             FFTError::CudaError(_) => {
                 write!(f, "A CUDA related error has ocurred")
             }
+            #[cfg(feature = "wgpu")]
+            FFTError::WgpuError(_) => {
+                write!(f, "A WebGPU related error has ocurred")
+            }
         }
     }
 }
@@ -48,7 +57,9 @@ impl std::error::Error for FFTError {
             #[cfg(feature = "metal")]
             FFTError::MetalError(e) => Some(e),
             #[cfg(feature = "cuda")]
-            FFTError::CudaError(_) => Some(e),
+            FFTError::CudaError(e) => Some(e),
+            #[cfg(feature = "wgpu")]
+            FFTError::WgpuError(e) => Some(e),
             _ => None,
         }
     }
@@ -68,6 +79,13 @@ impl From<CudaError> for FFTError {
     }
 }
 
+#[cfg(feature = "wgpu")]
+impl From<WgpuError> for FFTError {
+    fn from(error: WgpuError) -> Self {
+        Self::WgpuError(error)
+    }
+}
+
 impl From<FieldError> for FFTError {
     fn from(error: FieldError) -> Self {
         match error {
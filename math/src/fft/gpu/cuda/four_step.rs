@@ -0,0 +1,424 @@
+use crate::{
+    fft::gpu::cuda::{ops::gen_twiddles, state::CudaState},
+    field::{
+        element::FieldElement,
+        traits::{IsFFTField, IsField, IsSubFieldOf, RootsConfig},
+    },
+    gpu::cuda::{
+        field::element::CUDAFieldElement,
+        memory::{PooledPinned, PooledSlice},
+    },
+};
+use cudarc::driver::{safe::CudaStream, LaunchAsync, LaunchConfig};
+use lambdaworks_gpu::cuda::abstractions::errors::CudaError;
+
+const WARP_SIZE: usize = 32; // the implementation will spawn threadblocks of this size.
+
+/// Batches alternate between this many streams, so that the transfers of a batch overlap with
+/// the kernels of the other one.
+const NUM_STREAMS: usize = 2;
+
+/// Executes an ordered FFT over a slice of elements of `E`, with the roots of unity of the
+/// two-adic subfield `F`, in CUDA. Input and output are in natural order. With `inverse`, the
+/// inverse roots are used and the result is not scaled by `1 / n`.
+///
+/// If the data and its twiddle factors fit in the device memory, the FFT is done in a single
+/// pass. Otherwise, for domains of `2^24` elements and beyond, it is split with the four-step
+/// algorithm into `n = n1 * n2` FFTs of size `n1` and `n2`, which are sent to the device in
+/// batches.
+///
+/// The kernels work on the representation of `F`, so `E` must be `F` itself.
+pub fn four_step_fft<F, E>(
+    input: &[FieldElement<E>],
+    inverse: bool,
+    state: &CudaState,
+) -> Result<Vec<FieldElement<E>>, CudaError>
+where
+    F: IsFFTField + IsSubFieldOf<E>,
+    E: IsField,
+{
+    // Blocks cached by the pool of the state are available to it too
+    let free_memory = state.free_memory()? + state.pool().cached_bytes();
+    let budget = free_memory / 4 * 3 / core::mem::size_of::<CUDAFieldElement<E>>();
+    four_step_fft_with_budget::<F, E>(input, inverse, state, budget)
+}
+
+/// Same as [`four_step_fft`], using at most `budget` elements of device memory.
+fn four_step_fft_with_budget<F, E>(
+    input: &[FieldElement<E>],
+    inverse: bool,
+    state: &CudaState,
+    budget: usize,
+) -> Result<Vec<FieldElement<E>>, CudaError>
+where
+    F: IsFFTField + IsSubFieldOf<E>,
+    E: IsField,
+{
+    if core::any::type_name::<E>() != core::any::type_name::<F>() {
+        return Err(CudaError::FunctionError(format!(
+            "FFT over extension {} of {}",
+            core::any::type_name::<E>(),
+            core::any::type_name::<F>()
+        )));
+    }
+    let len = input.len();
+    if !len.is_power_of_two() || len.trailing_zeros() >= u32::BITS {
+        return Err(CudaError::InvalidOrder(len));
+    }
+    if len == 1 {
+        return Ok(input.to_vec());
+    }
+
+    let order = len.trailing_zeros();
+    let mut output = vec![FieldElement::zero(); len];
+
+    if len + len / 2 <= budget {
+        let pass = Pass::<F, E>::new(state, order, inverse, None)?;
+        pass.run(input, &mut output, 1, 1, |_, k| k, |_, k| k)?;
+        return Ok(output);
+    }
+
+    // The input is seen as a matrix of `n2` rows and `n1` columns, stored by rows.
+    // 1. Each column `x[c + n1 * k]` is transformed, and its element `k` is multiplied by
+    //    `ω^(c * k)`. The result is stored transposed, as `y[c * n2 + k]`.
+    // 2. Each column `y[k * n2 + r]` of the result is transformed, and its element `k` is
+    //    stored at `X[r + n2 * k]`.
+    let log_n1 = order / 2;
+    let log_n2 = order - log_n1;
+    let (n1, n2) = (1 << log_n1, 1 << log_n2);
+
+    let mut columns = vec![FieldElement::zero(); len];
+    let pass = Pass::<F, E>::new(state, log_n2, inverse, Some(order))?;
+    pass.run(
+        input,
+        &mut columns,
+        n1,
+        batch_size(budget, n2)?,
+        |s, k| s + n1 * k,
+        |s, k| s * n2 + k,
+    )?;
+    let pass = Pass::<F, E>::new(state, log_n1, inverse, None)?;
+    pass.run(
+        &columns,
+        &mut output,
+        n2,
+        batch_size(budget, n1)?,
+        |s, k| k * n2 + s,
+        |s, k| s + n2 * k,
+    )?;
+
+    Ok(output)
+}
+
+/// Number of sequences of `len` elements per batch, so that the batches of all the streams and
+/// the twiddle factors fit in `budget` elements.
+fn batch_size(budget: usize, len: usize) -> Result<usize, CudaError> {
+    let batch_size = budget.saturating_sub(len / 2) / (NUM_STREAMS * len);
+    if batch_size == 0 {
+        return Err(CudaError::AllocateMemory(format!(
+            "not enough device memory for FFTs of size {len}"
+        )));
+    }
+    Ok(batch_size)
+}
+
+fn launch_config(threads: usize) -> LaunchConfig {
+    let block_size = WARP_SIZE;
+    let block_count = threads.div_ceil(block_size);
+
+    LaunchConfig {
+        grid_dim: (block_count as u32, 1, 1), // in blocks
+        block_dim: (block_size as u32, 1, 1),
+        shared_mem_bytes: 0,
+    }
+}
+
+/// FFTs of many sequences of `2^log_len` elements, with the twiddle factors kept in the device.
+struct Pass<'a, F: IsFFTField, E: IsField> {
+    state: &'a CudaState,
+    log_len: u32,
+    twiddles: PooledSlice<CUDAFieldElement<E>>,
+    /// Root of unity of order `2^log_domain` for the twiddle factors of a four-step FFT
+    four_step_root: Option<PooledSlice<CUDAFieldElement<E>>>,
+    phantom: core::marker::PhantomData<F>,
+}
+
+impl<'a, F, E> Pass<'a, F, E>
+where
+    F: IsFFTField + IsSubFieldOf<E>,
+    E: IsField,
+{
+    fn new(
+        state: &'a CudaState,
+        log_len: u32,
+        inverse: bool,
+        log_domain: Option<u32>,
+    ) -> Result<Self, CudaError> {
+        let config = if inverse {
+            RootsConfig::NaturalInversed
+        } else {
+            RootsConfig::Natural
+        };
+        let to_extension = |elements: Vec<FieldElement<F>>| {
+            elements
+                .into_iter()
+                .map(|element| element.to_extension::<E>())
+                .collect::<Vec<_>>()
+        };
+
+        let twiddles = to_extension(gen_twiddles::<F>(log_len.into(), config, state)?);
+        let twiddles = state.upload(&twiddles)?;
+        let four_step_root = log_domain
+            .map(|log_domain| {
+                let root = F::get_primitive_root_of_unity(log_domain.into())
+                    .and_then(|root| if inverse { root.inv() } else { Ok(root) })
+                    .map_err(|_| {
+                        CudaError::FunctionError(format!(
+                            "Couldn't get primitive root of unity of order {log_domain}"
+                        ))
+                    })?;
+                state.upload(&to_extension(vec![root]))
+            })
+            .transpose()?;
+
+        Ok(Self {
+            state,
+            log_len,
+            twiddles,
+            four_step_root,
+            phantom: core::marker::PhantomData,
+        })
+    }
+
+    /// Transforms the sequences `input[input_index(s, 0..len)]`, for `s` in
+    /// `0..num_sequences`, into `output[output_index(s, 0..len)]`, `batch_size` sequences at a
+    /// time.
+    fn run(
+        &self,
+        input: &[FieldElement<E>],
+        output: &mut [FieldElement<E>],
+        num_sequences: usize,
+        batch_size: usize,
+        input_index: impl Fn(usize, usize) -> usize,
+        output_index: impl Fn(usize, usize) -> usize,
+    ) -> Result<(), CudaError> {
+        let len = 1 << self.log_len;
+        let batch_size = batch_size.min(num_sequences);
+        let num_batches = num_sequences.div_ceil(batch_size);
+
+        let mut streams = (0..NUM_STREAMS.min(num_batches))
+            .map(|_| Stream::new(self.state, batch_size * len))
+            .collect::<Result<Vec<_>, _>>()?;
+        let num_streams = streams.len();
+        let input_index = &input_index;
+        for (i, first) in (0..num_sequences).step_by(batch_size).enumerate() {
+            let sequences = first..num_sequences.min(first + batch_size);
+            let stream = &mut streams[i % num_streams];
+            stream.finish(self.state, len, output, &output_index)?;
+
+            let count = stream.host.write(sequences.clone().flat_map(move |s| {
+                (0..len).map(move |k| CUDAFieldElement::from(&input[input_index(s, k)]))
+            }));
+            unsafe {
+                stream
+                    .host
+                    .copy_to_device_async(count, &mut stream.data, &stream.stream)?
+            };
+            self.launch(stream, count, first)?;
+            unsafe {
+                stream
+                    .host
+                    .copy_from_device_async(count, &stream.data, &stream.stream)?
+            };
+            stream.pending = Some(sequences);
+        }
+        for stream in streams.iter_mut() {
+            stream.finish(self.state, len, output, &output_index)?;
+        }
+
+        Ok(())
+    }
+
+    /// Queues the FFTs of the `total / len` sequences in the buffer of `stream`, the first of
+    /// them being `first_sequence`.
+    fn launch(
+        &self,
+        stream: &mut Stream<E>,
+        total: usize,
+        first_sequence: usize,
+    ) -> Result<(), CudaError> {
+        let function = |name| self.state.get_function::<F>(name);
+        let log_len = self.log_len;
+        let data = &mut *stream.data;
+
+        // Launching kernels must be done in an unsafe block.
+        // Calling a kernel is similar to calling a foreign-language function,
+        // as the kernel itself could be written in C or unsafe Rust.
+        unsafe {
+            function("batch_bitrev_permutation")?.launch_on_stream(
+                &stream.stream,
+                launch_config(total),
+                (&mut *data, log_len, total as u32),
+            )
+        }
+        .map_err(|err| CudaError::Launch(err.to_string()))?;
+        for stage in (0..log_len.saturating_sub(1)).step_by(2) {
+            unsafe {
+                function("batch_radix4_dit")?.launch_on_stream(
+                    &stream.stream,
+                    launch_config(total / 4),
+                    (
+                        &mut *data,
+                        &*self.twiddles,
+                        log_len,
+                        stage,
+                        (total / 4) as u32,
+                    ),
+                )
+            }
+            .map_err(|err| CudaError::Launch(err.to_string()))?;
+        }
+        if log_len % 2 == 1 {
+            unsafe {
+                function("batch_radix2_dit")?.launch_on_stream(
+                    &stream.stream,
+                    launch_config(total / 2),
+                    (
+                        &mut *data,
+                        &*self.twiddles,
+                        log_len,
+                        log_len - 1,
+                        (total / 2) as u32,
+                    ),
+                )
+            }
+            .map_err(|err| CudaError::Launch(err.to_string()))?;
+        }
+        if let Some(root) = self.four_step_root.as_deref() {
+            unsafe {
+                function("four_step_twiddles")?.launch_on_stream(
+                    &stream.stream,
+                    launch_config(total),
+                    (
+                        &mut *data,
+                        root,
+                        log_len,
+                        first_sequence as u32,
+                        total as u32,
+                    ),
+                )
+            }
+            .map_err(|err| CudaError::Launch(err.to_string()))?;
+        }
+
+        Ok(())
+    }
+}
+
+/// A stream with its staging buffer in the host and its data buffer in the device, both taken
+/// from the pool of the state.
+struct Stream<E: IsField> {
+    stream: CudaStream,
+    host: PooledPinned<CUDAFieldElement<E>>,
+    data: PooledSlice<CUDAFieldElement<E>>,
+    /// Sequences being copied back to `host`
+    pending: Option<core::ops::Range<usize>>,
+}
+
+impl<E: IsField> Stream<E> {
+    fn new(state: &CudaState, len: usize) -> Result<Self, CudaError> {
+        let data = state.pool().alloc(len)?;
+        // The stream starts after the allocation above
+        let stream = state.fork_stream()?;
+
+        Ok(Self {
+            stream,
+            host: state.pool().alloc_pinned(len)?,
+            data,
+            pending: None,
+        })
+    }
+
+    /// Waits for the pending sequences, of `len` elements each, and moves them to `output`.
+    fn finish(
+        &mut self,
+        state: &CudaState,
+        len: usize,
+        output: &mut [FieldElement<E>],
+        output_index: impl Fn(usize, usize) -> usize,
+    ) -> Result<(), CudaError> {
+        let Some(sequences) = self.pending.take() else {
+            return Ok(());
+        };
+        state.synchronize_stream(&self.stream)?;
+
+        let results = unsafe { self.host.as_slice(sequences.len() * len) };
+        for (s, sequence) in sequences.zip(results.chunks_exact(len)) {
+            for (k, element) in sequence.iter().enumerate() {
+                output[output_index(s, k)] = FieldElement::from(element.clone());
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fft::polynomial::evaluate_fft_cpu;
+    use crate::field::fields::fft_friendly::stark_252_prime_field::Stark252PrimeField;
+
+    type F = Stark252PrimeField;
+    type FE = FieldElement<F>;
+
+    fn input(order: u32) -> Vec<FE> {
+        (0..1_u64 << order)
+            .map(|i| FE::from(i * i + 5).pow(13_u64))
+            .collect()
+    }
+
+    #[test]
+    fn single_pass_fft_matches_cpu_fft() {
+        let state = CudaState::new().unwrap();
+        for order in 1..12 {
+            let input = input(order);
+            let result = four_step_fft::<F, F>(&input, false, &state).unwrap();
+            assert_eq!(result, evaluate_fft_cpu::<F, F>(&input).unwrap());
+        }
+    }
+
+    #[test]
+    fn four_step_fft_matches_cpu_fft() {
+        let state = CudaState::new().unwrap();
+        for order in [4, 5, 10, 11] {
+            let input = input(order);
+            // Too small for a single pass, but with room for batches of a few sequences on
+            // both streams
+            let budget = 5 << order.div_ceil(2);
+            let result = four_step_fft_with_budget::<F, F>(&input, false, &state, budget).unwrap();
+            assert_eq!(result, evaluate_fft_cpu::<F, F>(&input).unwrap());
+        }
+    }
+
+    #[test]
+    fn inverse_four_step_fft_interpolates() {
+        let state = CudaState::new().unwrap();
+        let input = input(9);
+        let evaluations = four_step_fft_with_budget::<F, F>(&input, false, &state, 100).unwrap();
+        let scale = FE::from(input.len() as u64).inv().unwrap();
+        let result: Vec<_> = four_step_fft_with_budget::<F, F>(&evaluations, true, &state, 100)
+            .unwrap()
+            .iter()
+            .map(|coefficient| coefficient * scale)
+            .collect();
+        assert_eq!(result, input);
+    }
+
+    #[test]
+    fn four_step_fft_of_non_power_of_two_length_fails() {
+        let state = CudaState::new().unwrap();
+        let result = four_step_fft::<F, F>(&input(3)[..7], false, &state);
+        assert!(matches!(result, Err(CudaError::InvalidOrder(7))));
+    }
+}
@@ -1,3 +1,4 @@
+pub mod four_step;
 pub mod ops;
 pub mod polynomial;
 pub mod state;
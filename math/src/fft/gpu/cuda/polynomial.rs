@@ -1,53 +1,116 @@
 use crate::{
     fft::{
         errors::FFTError,
-        gpu::cuda::{ops::gen_twiddles, state::CudaState},
+        gpu::cuda::{four_step::four_step_fft, state::CudaState},
     },
     field::{
         element::FieldElement,
-        traits::{IsFFTField, RootsConfig},
+        traits::{IsFFTField, IsField, IsSubFieldOf},
     },
+    gpu::cuda::devices::CudaDevices,
     polynomial::Polynomial,
 };
 
-use super::ops::fft;
 use lambdaworks_gpu::cuda::abstractions::errors::CudaError;
 
-pub fn evaluate_fft_cuda<F>(coeffs: &[FieldElement<F>]) -> Result<Vec<FieldElement<F>>, CudaError>
+/// Returns whether there are CUDA kernels for FFTs over `E` with the roots of unity of `F`.
+/// Only the Stark252 field is supported, and not its extensions.
+pub fn is_cuda_field<F: IsFFTField, E: IsField>() -> bool {
+    F::field_name() == "stark256" && core::any::type_name::<E>() == core::any::type_name::<F>()
+}
+
+pub fn evaluate_fft_cuda<F, E>(
+    coeffs: &[FieldElement<E>],
+) -> Result<Vec<FieldElement<E>>, CudaError>
 where
-    F: IsFFTField,
-    F::BaseType: Unpin,
+    F: IsFFTField + IsSubFieldOf<E>,
+    E: IsField,
 {
     let state = CudaState::new()?;
-    let order = log2(coeffs.len())?;
-    let twiddles = gen_twiddles::<F>(order, RootsConfig::BitReverse, &state)?;
+    log2(coeffs.len())?;
 
-    fft(coeffs, &twiddles, &state)
+    four_step_fft::<F, E>(coeffs, false, &state)
 }
 
 /// Returns a new polynomial that interpolates `fft_evals`, which are evaluations using twiddle
 /// factors. This is considered to be the inverse operation of [evaluate_fft_cuda()].
-pub fn interpolate_fft_cuda<F>(
-    fft_evals: &[FieldElement<F>],
-) -> Result<Polynomial<FieldElement<F>>, FFTError>
+pub fn interpolate_fft_cuda<F, E>(
+    fft_evals: &[FieldElement<E>],
+) -> Result<Polynomial<FieldElement<E>>, FFTError>
 where
-    F: IsFFTField,
-    F::BaseType: Unpin,
+    F: IsFFTField + IsSubFieldOf<E>,
+    E: IsField,
 {
     let state = CudaState::new()?;
+    log2(fft_evals.len())?;
 
-    // fft() can zero-pad the coeffs if there aren't 2^k of them (k being any integer).
-    // TODO: twiddle factors need to be handled with too much care, the FFT API shouldn't accept
-    // invalid twiddle factor collections. A better solution is needed.
-    let order = log2(fft_evals.len())?;
-    let twiddles = gen_twiddles::<F>(order, RootsConfig::BitReverseInversed, &state)?;
-
-    let coeffs = fft(fft_evals, &twiddles, &state)?;
+    let coeffs = four_step_fft::<F, E>(fft_evals, true, &state)?;
 
     let scale_factor = FieldElement::from(fft_evals.len() as u64).inv().unwrap();
     Ok(Polynomial::new(&coeffs).scale_coeffs(&scale_factor))
 }
 
+/// Evaluates each of the polynomials with coefficients `columns`, as in [evaluate_fft_cuda()],
+/// with the columns split in contiguous ranges across `devices`. This is how the columns of a
+/// trace are extended to the LDE domain in machines with several GPUs.
+pub fn evaluate_fft_columns_cuda<F, E>(
+    columns: &[Vec<FieldElement<E>>],
+    devices: &CudaDevices,
+) -> Result<Vec<Vec<FieldElement<E>>>, CudaError>
+where
+    F: IsFFTField + IsSubFieldOf<E>,
+    E: IsField,
+    E::BaseType: Send + Sync,
+{
+    fft_columns::<F, E>(columns, false, devices)
+}
+
+/// Interpolates each of `columns`, as in [interpolate_fft_cuda()], with the columns split in
+/// contiguous ranges across `devices`.
+pub fn interpolate_fft_columns_cuda<F, E>(
+    columns: &[Vec<FieldElement<E>>],
+    devices: &CudaDevices,
+) -> Result<Vec<Polynomial<FieldElement<E>>>, FFTError>
+where
+    F: IsFFTField + IsSubFieldOf<E>,
+    E: IsField,
+    E::BaseType: Send + Sync,
+{
+    let columns = fft_columns::<F, E>(columns, true, devices)?;
+
+    Ok(columns
+        .iter()
+        .map(|coeffs| {
+            let scale_factor = FieldElement::from(coeffs.len() as u64).inv().unwrap();
+            Polynomial::new(coeffs).scale_coeffs(&scale_factor)
+        })
+        .collect())
+}
+
+fn fft_columns<F, E>(
+    columns: &[Vec<FieldElement<E>>],
+    inverse: bool,
+    devices: &CudaDevices,
+) -> Result<Vec<Vec<FieldElement<E>>>, CudaError>
+where
+    F: IsFFTField + IsSubFieldOf<E>,
+    E: IsField,
+    E::BaseType: Send + Sync,
+{
+    let shards = devices.shard(columns.len(), |pool, range| {
+        let state = CudaState::with_pool(pool.clone())?;
+        columns[range]
+            .iter()
+            .map(|column| {
+                log2(column.len())?;
+                four_step_fft::<F, E>(column, inverse, &state)
+            })
+            .collect::<Result<Vec<_>, _>>()
+    })?;
+
+    Ok(shards.into_iter().flatten().collect())
+}
+
 // TODO: remove when fft works on non-multiple-of-two input length
 fn log2(n: usize) -> Result<u64, CudaError> {
     if !n.is_power_of_two() {
@@ -55,3 +118,38 @@ fn log2(n: usize) -> Result<u64, CudaError> {
     }
     Ok(n.trailing_zeros() as u64)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fft::polynomial::{evaluate_fft_cpu, interpolate_fft_cpu};
+    use crate::field::fields::fft_friendly::stark_252_prime_field::Stark252PrimeField;
+
+    type F = Stark252PrimeField;
+    type FE = FieldElement<F>;
+
+    fn columns(count: u64, order: u32) -> Vec<Vec<FE>> {
+        (0..count)
+            .map(|c| {
+                (0..1_u64 << order)
+                    .map(|i| FE::from(i * i + c + 5).pow(13_u64))
+                    .collect()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn fft_columns_across_devices_match_cpu_fft() {
+        let devices = CudaDevices::new(&[0, 0, 0]).unwrap();
+        let columns = columns(7, 10);
+
+        let evaluations = evaluate_fft_columns_cuda::<F, F>(&columns, &devices).unwrap();
+        let polys = interpolate_fft_columns_cuda::<F, F>(&columns, &devices).unwrap();
+
+        assert_eq!(evaluations.len(), columns.len());
+        for ((column, evaluation), poly) in columns.iter().zip(&evaluations).zip(&polys) {
+            assert_eq!(evaluation, &evaluate_fft_cpu::<F, F>(column).unwrap());
+            assert_eq!(poly, &interpolate_fft_cpu::<F, F>(column).unwrap());
+        }
+    }
+}
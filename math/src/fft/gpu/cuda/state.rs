@@ -4,11 +4,15 @@ use crate::{
         fields::fft_friendly::stark_252_prime_field::Stark252PrimeField,
         traits::{IsFFTField, IsField, RootsConfig},
     },
-    gpu::cuda::field::element::CUDAFieldElement,
+    gpu::cuda::{
+        field::element::CUDAFieldElement,
+        memory::{DeviceMemoryPool, PooledSlice},
+    },
 };
 use cudarc::{
     driver::{
-        safe::CudaSlice, safe::DeviceSlice, CudaDevice, CudaFunction, LaunchAsync, LaunchConfig,
+        safe::{CudaSlice, CudaStream, DeviceSlice},
+        sys, CudaDevice, CudaFunction, LaunchAsync, LaunchConfig,
     },
     nvrtc::safe::Ptx,
 };
@@ -22,17 +26,28 @@ const WARP_SIZE: usize = 32; // the implementation will spawn threadblocks of th
 /// implementing GPU parallel computations in CUDA.
 pub struct CudaState {
     device: Arc<CudaDevice>,
+    pool: DeviceMemoryPool,
 }
 
 impl CudaState {
-    /// Creates a new CUDA state with the first GPU.
+    /// Creates a new CUDA state with the first GPU, which takes its buffers from
+    /// [`DeviceMemoryPool::global`].
     pub fn new() -> Result<Self, CudaError> {
-        let device =
-            CudaDevice::new(0).map_err(|err| CudaError::DeviceNotFound(err.to_string()))?;
-        let state = Self { device };
+        Self::with_pool(DeviceMemoryPool::global()?)
+    }
 
-        // Load PTX libraries
-        state.load_library::<Stark252PrimeField>(STARK256_PTX)?;
+    /// Creates a new CUDA state with the device of `pool`, which takes its buffers from it.
+    pub fn with_pool(pool: DeviceMemoryPool) -> Result<Self, CudaError> {
+        let device = Arc::clone(pool.device());
+        let state = Self { device, pool };
+
+        // Load PTX libraries, once per device
+        if state
+            .get_function::<Stark252PrimeField>("four_step_twiddles")
+            .is_err()
+        {
+            state.load_library::<Stark252PrimeField>(STARK256_PTX)?;
+        }
 
         Ok(state)
     }
@@ -44,21 +59,58 @@ impl CudaState {
             "calc_twiddles",
             "calc_twiddles_bitrev",
             "bitrev_permutation",
+            "batch_bitrev_permutation",
+            "batch_radix2_dit",
+            "batch_radix4_dit",
+            "four_step_twiddles",
         ];
         self.device
             .load_ptx(Ptx::from_src(src), mod_name, &functions)
             .map_err(|err| CudaError::PtxError(err.to_string()))
     }
 
-    fn get_function<F: IsFFTField>(&self, func_name: &str) -> Result<CudaFunction, CudaError> {
+    pub(crate) fn get_function<F: IsFFTField>(
+        &self,
+        func_name: &str,
+    ) -> Result<CudaFunction, CudaError> {
         let mod_name = F::field_name();
         self.device
             .get_func(mod_name, func_name)
             .ok_or_else(|| CudaError::FunctionError(func_name.to_string()))
     }
 
+    pub fn pool(&self) -> &DeviceMemoryPool {
+        &self.pool
+    }
+
+    /// Returns the amount of free memory in the GPU, in bytes.
+    pub(crate) fn free_memory(&self) -> Result<usize, CudaError> {
+        self.device
+            .bind_to_thread()
+            .map_err(|err| CudaError::RetrieveMemory(err.to_string()))?;
+        let (mut free, mut total) = (0, 0);
+        unsafe { sys::cuMemGetInfo_v2(&mut free, &mut total) }
+            .result()
+            .map_err(|err| CudaError::RetrieveMemory(err.to_string()))?;
+        Ok(free)
+    }
+
+    /// Returns a new stream, which starts after the work already queued in the device.
+    pub(crate) fn fork_stream(&self) -> Result<CudaStream, CudaError> {
+        self.device
+            .fork_default_stream()
+            .map_err(|err| CudaError::AllocateMemory(err.to_string()))
+    }
+
+    /// Blocks until the work queued in `stream` is done.
+    pub(crate) fn synchronize_stream(&self, stream: &CudaStream) -> Result<(), CudaError> {
+        unsafe { sys::cuStreamSynchronize(stream.stream) }
+            .result()
+            .map_err(|err| CudaError::Launch(err.to_string()))
+    }
+
     /// Allocates a buffer in the GPU and copies `data` into it. Returns its handle.
-    fn alloc_buffer_with_data<F: IsField>(
+    pub(crate) fn alloc_buffer_with_data<F: IsField>(
         &self,
         data: &[FieldElement<F>],
     ) -> Result<CudaSlice<CUDAFieldElement<F>>, CudaError> {
@@ -67,6 +119,15 @@ impl CudaState {
             .map_err(|err| CudaError::AllocateMemory(err.to_string()))
     }
 
+    /// Takes a buffer from the pool of the state and copies `data` into it.
+    pub(crate) fn upload<F: IsField>(
+        &self,
+        data: &[FieldElement<F>],
+    ) -> Result<PooledSlice<CUDAFieldElement<F>>, CudaError> {
+        self.pool
+            .upload(&data.iter().map(CUDAFieldElement::from).collect::<Vec<_>>())
+    }
+
     /// Returns a wrapper object over the `radix2_dit_butterfly` function defined in `fft.cu`
     pub(crate) fn get_radix2_dit_butterfly<F: IsFFTField>(
         &self,
@@ -100,11 +161,14 @@ impl CudaState {
         })?;
 
         let (root, function_name) = match config {
-            RootsConfig::Natural => (root, "calc_twiddles"),
+            RootsConfig::Natural => (Ok(root), "calc_twiddles"),
             RootsConfig::NaturalInversed => (root.inv(), "calc_twiddles"),
-            RootsConfig::BitReverse => (root, "calc_twiddles_bitrev"),
+            RootsConfig::BitReverse => (Ok(root), "calc_twiddles_bitrev"),
             RootsConfig::BitReverseInversed => (root.inv(), "calc_twiddles_bitrev"),
         };
+        let root = root.map_err(|_| {
+            CudaError::FunctionError("Couldn't invert the root of unity".to_string())
+        })?;
 
         let function = self.get_function::<F>(function_name)?;
 
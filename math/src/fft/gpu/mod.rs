@@ -3,3 +3,6 @@ pub mod metal;
 
 #[cfg(feature = "cuda")]
 pub mod cuda;
+
+#[cfg(feature = "wgpu")]
+pub mod wgpu;
@@ -0,0 +1,208 @@
+use crate::{
+    field::{
+        element::FieldElement,
+        traits::{IsFFTField, IsField, IsSubFieldOf},
+    },
+    gpu::wgpu::FFT_STARK256_SHADER,
+};
+use lambdaworks_gpu::wgpu::abstractions::{errors::WgpuError, state::WgpuState};
+
+use wgpu::{Buffer, CommandEncoder, ShaderModule};
+
+/// Executes parallel ordered FFT over a slice of two-adic field elements, in WebGPU.
+/// Twiddle factors are required to be in bit-reverse order.
+///
+/// "Ordered" means that the input is required to be in natural order, and the output will be
+/// in this order too. Natural order means that input[i] corresponds to the i-th coefficient,
+/// as opposed to bit-reverse order in which input[bit_rev(i)] corresponds to the i-th
+/// coefficient.
+///
+/// Only the Stark252 field has kernels, for both the values and the domain.
+pub fn fft<F, E>(
+    input: &[FieldElement<E>],
+    twiddles: &[FieldElement<F>],
+    state: &WgpuState,
+) -> Result<Vec<FieldElement<E>>, WgpuError>
+where
+    F: IsFFTField + IsSubFieldOf<E>,
+    E: IsField,
+{
+    if !input.len().is_power_of_two() {
+        return Err(WgpuError::InputError(input.len()));
+    }
+    if input.len() == 1 {
+        return Ok(input.to_vec());
+    }
+
+    let module = state.load_shader(fft_shader::<F>()?)?;
+    let pipeline = state.setup_pipeline(&module, "radix2_dit_butterfly")?;
+
+    let input_buffer = state.alloc_buffer_data(input);
+    let twiddles_buffer = state.alloc_buffer_data(twiddles);
+    let result_buffer = state.alloc_buffer::<FieldElement<E>>(input.len());
+    // TODO: twiddle factors security (right now anything can be passed as twiddle factors)
+
+    let butterflies = input.len() / 2; // one thread per butterfly
+    let order = input.len().trailing_zeros();
+    let stage_params: Vec<Buffer> = (0..order)
+        .map(|stage| state.alloc_buffer_data(&[stage, butterflies as u32]))
+        .collect();
+    let bitrev_params = state.alloc_buffer_data(&[order, input.len() as u32]);
+
+    let mut encoder = state.setup_command();
+    for params in stage_params.iter() {
+        state.dispatch(
+            &mut encoder,
+            &pipeline,
+            &[(0, &input_buffer), (1, &twiddles_buffer), (2, params)],
+            butterflies,
+        );
+    }
+    encode_bitrev_permutation(
+        state,
+        &module,
+        &mut encoder,
+        (&input_buffer, &result_buffer, &bitrev_params),
+        input.len(),
+    )?;
+    state.submit(encoder);
+
+    let result = state.retrieve_contents::<E::BaseType>(&result_buffer)?;
+    Ok(result.into_iter().map(FieldElement::from_raw).collect())
+}
+
+/// Executes a parallel bit-reverse permutation with the elements of `input`, in WebGPU.
+pub fn bitrev_permutation<F: IsFFTField, T: Clone>(
+    input: &[T],
+    state: &WgpuState,
+) -> Result<Vec<T>, WgpuError> {
+    if !input.len().is_power_of_two() {
+        return Err(WgpuError::InputError(input.len()));
+    }
+    if input.len() == 1 {
+        return Ok(input.to_vec());
+    }
+
+    let module = state.load_shader(fft_shader::<F>()?)?;
+    let input_buffer = state.alloc_buffer_data(input);
+    let result_buffer = state.alloc_buffer::<T>(input.len());
+    let order = input.len().trailing_zeros();
+    let params = state.alloc_buffer_data(&[order, input.len() as u32]);
+
+    let mut encoder = state.setup_command();
+    encode_bitrev_permutation(
+        state,
+        &module,
+        &mut encoder,
+        (&input_buffer, &result_buffer, &params),
+        input.len(),
+    )?;
+    state.submit(encoder);
+
+    state.retrieve_contents::<T>(&result_buffer)
+}
+
+/// Records the permutation of the `len` elements of the input buffer into the result one. The
+/// params buffer holds the order of `len` and `len`.
+fn encode_bitrev_permutation(
+    state: &WgpuState,
+    module: &ShaderModule,
+    encoder: &mut CommandEncoder,
+    (input, result, params): (&Buffer, &Buffer, &Buffer),
+    len: usize,
+) -> Result<(), WgpuError> {
+    let pipeline = state.setup_pipeline(module, "bitrev_permutation")?;
+    state.dispatch(
+        encoder,
+        &pipeline,
+        &[(0, input), (2, params), (3, result)],
+        len,
+    );
+    Ok(())
+}
+
+fn fft_shader<F: IsFFTField>() -> Result<&'static str, WgpuError> {
+    match F::field_name() {
+        "stark256" => Ok(FFT_STARK256_SHADER),
+        _ => Err(WgpuError::FunctionError(format!(
+            "FFTs over {}",
+            core::any::type_name::<F>()
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::fft::cpu::roots_of_unity::get_twiddles;
+    use crate::field::{
+        fields::fft_friendly::{
+            stark_101_prime_field::Stark101PrimeField, stark_252_prime_field::Stark252PrimeField,
+        },
+        traits::RootsConfig,
+    };
+    use proptest::{collection, prelude::*};
+
+    use super::*;
+
+    type F = Stark252PrimeField;
+    type FE = FieldElement<F>;
+
+    prop_compose! {
+        fn powers_of_two(max_exp: u8)(exp in 1..max_exp) -> usize { 1 << exp }
+        // max_exp cannot be multiple of the bits that represent a usize, generally 64 or 32.
+        // also it can't exceed the test field's two-adicity.
+    }
+
+    prop_compose! {
+        fn field_element()(num in any::<u64>().prop_filter("Avoid null polynomial", |x| x != &0)) -> FE {
+            FE::from(num)
+        }
+    }
+
+    fn field_vec(max_exp: u8) -> impl Strategy<Value = Vec<FE>> {
+        powers_of_two(max_exp).prop_flat_map(|size| collection::vec(field_element(), size))
+    }
+
+    proptest! {
+        // Property-based test that ensures WebGPU parallel FFT gives same result as a sequential one.
+        #[test]
+        fn test_wgpu_fft_matches_sequential(input in field_vec(6)) {
+            let state = WgpuState::new().unwrap();
+            let order = input.len().trailing_zeros();
+            let twiddles = get_twiddles::<F>(order.into(), RootsConfig::BitReverse).unwrap();
+
+            let wgpu_result = super::fft(&input, &twiddles, &state).unwrap();
+            let sequential_result = crate::fft::cpu::ops::fft(&input, &twiddles).unwrap();
+
+            prop_assert_eq!(&wgpu_result, &sequential_result);
+        }
+    }
+
+    #[test]
+    fn test_wgpu_fft_matches_sequential_large_input() {
+        const ORDER: usize = 20;
+        let input = vec![FE::one(); 1 << ORDER];
+
+        let state = WgpuState::new().unwrap();
+        let order = input.len().trailing_zeros();
+        let twiddles = get_twiddles::<F>(order.into(), RootsConfig::BitReverse).unwrap();
+
+        let wgpu_result = super::fft(&input, &twiddles, &state).unwrap();
+        let sequential_result = crate::fft::cpu::ops::fft(&input, &twiddles).unwrap();
+
+        assert_eq!(&wgpu_result, &sequential_result);
+    }
+
+    #[test]
+    fn fft_over_a_field_without_kernels_should_fail() {
+        type G = Stark101PrimeField;
+        let state = WgpuState::new().unwrap();
+        let input = vec![FieldElement::<G>::one(); 4];
+        let twiddles = get_twiddles::<G>(2, RootsConfig::BitReverse).unwrap();
+
+        assert!(matches!(
+            super::fft(&input, &twiddles, &state),
+            Err(WgpuError::FunctionError(_))
+        ));
+    }
+}
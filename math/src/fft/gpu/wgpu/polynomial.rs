@@ -0,0 +1,50 @@
+use crate::{
+    fft::{cpu::roots_of_unity::get_twiddles, errors::FFTError},
+    field::{
+        element::FieldElement,
+        traits::{IsFFTField, IsField, IsSubFieldOf, RootsConfig},
+    },
+    polynomial::Polynomial,
+};
+use lambdaworks_gpu::wgpu::abstractions::state::WgpuState;
+
+use super::ops::*;
+
+/// Returns whether there are WebGPU kernels for FFTs over `E` with the roots of unity of `F`.
+/// Only the Stark252 field is supported, and not its extensions.
+pub fn is_wgpu_field<F: IsFFTField, E: IsField>() -> bool {
+    F::field_name() == "stark256" && core::any::type_name::<E>() == core::any::type_name::<F>()
+}
+
+pub fn evaluate_fft_wgpu<F, E>(coeffs: &[FieldElement<E>]) -> Result<Vec<FieldElement<E>>, FFTError>
+where
+    F: IsFFTField + IsSubFieldOf<E>,
+    E: IsField,
+{
+    let state = WgpuState::new()?;
+
+    let order = coeffs.len().trailing_zeros();
+    let twiddles = get_twiddles::<F>(order.into(), RootsConfig::BitReverse)?;
+
+    Ok(fft(coeffs, &twiddles, &state)?)
+}
+
+/// Returns a new polynomial that interpolates `fft_evals`, which are evaluations using twiddle
+/// factors. This is considered to be the inverse operation of [evaluate_fft_wgpu()].
+pub fn interpolate_fft_wgpu<F, E>(
+    fft_evals: &[FieldElement<E>],
+) -> Result<Polynomial<FieldElement<E>>, FFTError>
+where
+    F: IsFFTField + IsSubFieldOf<E>,
+    E: IsField,
+{
+    let state = WgpuState::new()?;
+
+    let order = fft_evals.len().trailing_zeros();
+    let twiddles = get_twiddles::<F>(order.into(), RootsConfig::BitReverseInversed)?;
+
+    let coeffs = fft(fft_evals, &twiddles, &state)?;
+
+    let scale_factor = FieldElement::from(fft_evals.len() as u64).inv().unwrap();
+    Ok(Polynomial::new(&coeffs).scale_coeffs(&scale_factor))
+}
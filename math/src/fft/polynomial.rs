@@ -10,10 +10,12 @@ use crate::{
 };
 use alloc::{vec, vec::Vec};
 
-#[cfg(feature = "cuda")]
-use crate::fft::gpu::cuda::polynomial::{evaluate_fft_cuda, interpolate_fft_cuda};
+#[cfg(all(feature = "cuda", not(feature = "metal")))]
+use crate::fft::gpu::cuda::polynomial::{evaluate_fft_cuda, interpolate_fft_cuda, is_cuda_field};
 #[cfg(feature = "metal")]
 use crate::fft::gpu::metal::polynomial::{evaluate_fft_metal, interpolate_fft_metal};
+#[cfg(all(feature = "wgpu", not(any(feature = "metal", feature = "cuda"))))]
+use crate::fft::gpu::wgpu::polynomial::{evaluate_fft_wgpu, interpolate_fft_wgpu, is_wgpu_field};
 
 use super::cpu::{ops, roots_of_unity};
 
@@ -22,6 +24,8 @@ impl<E: IsField> Polynomial<FieldElement<E>> {
     /// are P(w^i), with w being a primitive root of unity).
     /// `N = max(self.coeff_len(), domain_size).next_power_of_two() * blowup_factor`.
     /// If `domain_size` is `None`, it defaults to 0.
+    /// With several GPU features enabled, it runs on Metal, then CUDA, then wgpu, in that order
+    /// of preference.
     pub fn evaluate_fft<F: IsFFTField + IsSubFieldOf<E>>(
         poly: &Polynomial<FieldElement<E>>,
         blowup_factor: usize,
@@ -51,17 +55,26 @@ impl<E: IsField> Polynomial<FieldElement<E>> {
             }
         }
 
-        #[cfg(feature = "cuda")]
+        #[cfg(all(feature = "cuda", not(feature = "metal")))]
         {
             // TODO: support multiple fields with CUDA
-            if F::field_name() == "stark256" {
-                Ok(evaluate_fft_cuda(&coeffs)?)
+            if is_cuda_field::<F, E>() {
+                Ok(evaluate_fft_cuda::<F, E>(&coeffs)?)
             } else {
                 evaluate_fft_cpu::<F, E>(&coeffs)
             }
         }
 
-        #[cfg(all(not(feature = "metal"), not(feature = "cuda")))]
+        #[cfg(all(feature = "wgpu", not(any(feature = "metal", feature = "cuda"))))]
+        {
+            if is_wgpu_field::<F, E>() {
+                evaluate_fft_wgpu::<F, E>(&coeffs)
+            } else {
+                evaluate_fft_cpu::<F, E>(&coeffs)
+            }
+        }
+
+        #[cfg(all(not(feature = "metal"), not(feature = "cuda"), not(feature = "wgpu")))]
         {
             evaluate_fft_cpu::<F, E>(&coeffs)
         }
@@ -100,16 +113,25 @@ impl<E: IsField> Polynomial<FieldElement<E>> {
             }
         }
 
-        #[cfg(feature = "cuda")]
+        #[cfg(all(feature = "cuda", not(feature = "metal")))]
         {
-            if !F::field_name().is_empty() {
-                Ok(interpolate_fft_cuda(fft_evals)?)
+            if is_cuda_field::<F, E>() {
+                Ok(interpolate_fft_cuda::<F, E>(fft_evals)?)
+            } else {
+                interpolate_fft_cpu::<F, E>(fft_evals)
+            }
+        }
+
+        #[cfg(all(feature = "wgpu", not(any(feature = "metal", feature = "cuda"))))]
+        {
+            if is_wgpu_field::<F, E>() {
+                interpolate_fft_wgpu::<F, E>(fft_evals)
             } else {
                 interpolate_fft_cpu::<F, E>(fft_evals)
             }
         }
 
-        #[cfg(all(not(feature = "metal"), not(feature = "cuda")))]
+        #[cfg(all(not(feature = "metal"), not(feature = "cuda"), not(feature = "wgpu")))]
         {
             interpolate_fft_cpu::<F, E>(fft_evals)
         }
@@ -175,7 +197,7 @@ where
 
 #[cfg(test)]
 mod tests {
-    #[cfg(all(not(feature = "metal"), not(feature = "cuda")))]
+    #[cfg(all(not(feature = "metal"), not(feature = "cuda"), not(feature = "wgpu")))]
     use crate::field::traits::IsField;
 
     use alloc::format;
@@ -256,7 +278,7 @@ mod tests {
         (poly, new_poly)
     }
 
-    #[cfg(all(not(feature = "metal"), not(feature = "cuda")))]
+    #[cfg(all(not(feature = "metal"), not(feature = "cuda"), not(feature = "wgpu")))]
     mod u64_field_tests {
         use super::*;
         use crate::field::test_fields::u64_test_field::U64TestField;
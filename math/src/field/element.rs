@@ -31,6 +31,14 @@ use serde::ser::{Serialize, SerializeStruct, Serializer};
 ))]
 use serde::Deserialize;
 
+#[cfg(feature = "proptest")]
+use proptest::{
+    arbitrary::Arbitrary,
+    collection,
+    prelude::any,
+    strategy::{SBoxedStrategy, Strategy},
+};
+
 use super::fields::montgomery_backed_prime_fields::{IsModulus, MontgomeryBackendPrimeField};
 use super::traits::{IsPrimeField, IsSubFieldOf, LegendreSymbol};
 
@@ -74,6 +82,17 @@ impl<F: IsField> FieldElement<F> {
             .map(|x| FieldElement::from_raw(x))
             .collect()
     }
+
+    /// The element with coordinates `coordinates` over the subfield `S`, the inverse of
+    /// [`Self::to_subfield_vec`].
+    pub fn from_subfield_slice<S>(coordinates: &[FieldElement<S>]) -> Self
+    where
+        S: IsSubFieldOf<F>,
+    {
+        let values: alloc::vec::Vec<S::BaseType> =
+            coordinates.iter().map(|x| x.value().clone()).collect();
+        Self::from_raw(S::from_subfield_slice(&values))
+    }
 }
 
 /// From overloading for field elements
@@ -721,6 +740,27 @@ where
     }
 }
 
+/// Generates elements of a prime field by reducing random integers at least 64 bits longer than
+/// the modulus, so that every element is generated with close to the same probability.
+#[cfg(feature = "proptest")]
+impl<F: IsPrimeField + 'static> Arbitrary for FieldElement<F> {
+    type Parameters = ();
+
+    fn arbitrary_with(_args: Self::Parameters) -> Self::Strategy {
+        let num_limbs = F::field_bit_size() / 64 + 2;
+        collection::vec(any::<u64>(), num_limbs)
+            .prop_map(|limbs| {
+                let two_to_64 = Self::from(u64::MAX) + Self::one();
+                limbs.into_iter().fold(Self::zero(), |acc, limb| {
+                    acc * &two_to_64 + Self::from(limb)
+                })
+            })
+            .sboxed()
+    }
+
+    type Strategy = SBoxedStrategy<Self>;
+}
+
 #[cfg(test)]
 mod tests {
     use crate::field::element::FieldElement;
@@ -209,6 +209,16 @@ where
     ) -> alloc::vec::Vec<Self::BaseType> {
         b.into_iter().map(|x| x.to_raw()).collect()
     }
+
+    fn from_subfield_slice(
+        b: &[Self::BaseType],
+    ) -> <CubicExtensionField<F, Q> as IsField>::BaseType {
+        [
+            FieldElement::from_raw(b[0].clone()),
+            FieldElement::from_raw(b[1].clone()),
+            FieldElement::from_raw(b[2].clone()),
+        ]
+    }
 }
 
 #[cfg(test)]
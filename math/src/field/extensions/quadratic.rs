@@ -200,6 +200,15 @@ where
     ) -> alloc::vec::Vec<Self::BaseType> {
         b.into_iter().map(|x| x.to_raw()).collect()
     }
+
+    fn from_subfield_slice(
+        b: &[Self::BaseType],
+    ) -> <QuadraticExtensionField<F, Q> as IsField>::BaseType {
+        [
+            FieldElement::from_raw(b[0].clone()),
+            FieldElement::from_raw(b[1].clone()),
+        ]
+    }
 }
 
 impl<F: IsField, Q: Clone + Debug + HasQuadraticNonResidue<F>>
@@ -393,4 +402,13 @@ mod tests {
         let expected_result = FEE::new([FE::new(28), FE::new(45)]);
         assert_eq!(a / b, expected_result);
     }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn test_from_subfield_slice_is_the_inverse_of_to_subfield_vec() {
+        let a = FEE::new([FE::new(12), FE::new(5)]);
+        let coordinates = a.clone().to_subfield_vec::<U64PrimeField<ORDER_P>>();
+        assert_eq!(coordinates, vec![FE::new(12), FE::new(5)]);
+        assert_eq!(FEE::from_subfield_slice(&coordinates), a);
+    }
 }
@@ -2,14 +2,18 @@ use crate::field::{
     element::FieldElement, extensions::quadratic::*,
     fields::fft_friendly::babybear::Babybear31PrimeField,
 };
+#[cfg(feature = "alloc")]
+use crate::traits::AsBytes;
 
 /// Quadratic field extension of Babybear
 pub type QuadraticBabybearField =
     QuadraticExtensionField<Babybear31PrimeField, Babybear31PrimeField>;
 
+/// `-1` is a square in Babybear, whose modulus is `1` mod `4`, so the extension adds a square
+/// root of `11`, which is not.
 impl HasQuadraticNonResidue<Babybear31PrimeField> for Babybear31PrimeField {
     fn residue() -> FieldElement<Babybear31PrimeField> {
-        -FieldElement::one()
+        FieldElement::from(11)
     }
 }
 
@@ -17,9 +21,18 @@ impl HasQuadraticNonResidue<Babybear31PrimeField> for Babybear31PrimeField {
 pub type QuadraticBabybearFieldElement =
     QuadraticExtensionFieldElement<Babybear31PrimeField, Babybear31PrimeField>;
 
+/// The bytes of the coordinates, concatenated.
+#[cfg(feature = "alloc")]
+impl AsBytes for QuadraticBabybearFieldElement {
+    fn as_bytes(&self) -> alloc::vec::Vec<u8> {
+        self.value().iter().flat_map(AsBytes::as_bytes).collect()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::field::traits::LegendreSymbol;
 
     type FE = FieldElement<Babybear31PrimeField>;
     type Fee = QuadraticBabybearFieldElement;
@@ -71,6 +84,11 @@ mod tests {
         assert_eq!(a / b, expected_result);
     }
 
+    #[test]
+    fn test_residue_is_not_a_square() {
+        assert!(Babybear31PrimeField::residue().legendre_symbol() == LegendreSymbol::MinusOne);
+    }
+
     #[test]
     fn test_conjugate_quadratic() {
         let a = Fee::new([FE::from(12), FE::from(5)]);
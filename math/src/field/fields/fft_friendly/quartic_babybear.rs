@@ -0,0 +1,141 @@
+use crate::field::{
+    element::FieldElement,
+    extensions::quadratic::*,
+    fields::fft_friendly::{babybear::Babybear31PrimeField, quadratic_babybear::*},
+    traits::{IsField, IsSubFieldOf},
+};
+#[cfg(feature = "alloc")]
+use crate::traits::AsBytes;
+
+/// Quartic field extension of Babybear, built as a quadratic extension of its quadratic extension.
+/// It's large enough to sample the challenges of STARKs over Babybear from.
+pub type QuarticBabybearField =
+    QuadraticExtensionField<QuadraticBabybearField, QuarticBabybearNonResidue>;
+
+/// Field element type for the quartic extension of Babybear
+pub type QuarticBabybearFieldElement =
+    QuadraticExtensionFieldElement<QuadraticBabybearField, QuarticBabybearNonResidue>;
+
+/// The square root `v` of `11` that generates the quadratic extension is not a square in it, so
+/// the extension adds a square root `u` of `v`. Then `u⁴ = 11`, that is, the extension is
+/// `Babybear[X] / (X⁴ - 11)`.
+#[derive(Debug, Clone)]
+pub struct QuarticBabybearNonResidue;
+
+impl HasQuadraticNonResidue<QuadraticBabybearField> for QuarticBabybearNonResidue {
+    fn residue() -> QuadraticBabybearFieldElement {
+        FieldElement::new([FieldElement::zero(), FieldElement::one()])
+    }
+}
+
+type BaseType = <Babybear31PrimeField as IsField>::BaseType;
+type QuarticBaseType = <QuarticBabybearField as IsField>::BaseType;
+
+impl IsSubFieldOf<QuarticBabybearField> for Babybear31PrimeField {
+    fn mul(a: &BaseType, b: &QuarticBaseType) -> QuarticBaseType {
+        let a = <Self as IsSubFieldOf<QuadraticBabybearField>>::embed(*a);
+        <QuadraticBabybearField as IsSubFieldOf<QuarticBabybearField>>::mul(&a, b)
+    }
+
+    fn add(a: &BaseType, b: &QuarticBaseType) -> QuarticBaseType {
+        let a = <Self as IsSubFieldOf<QuadraticBabybearField>>::embed(*a);
+        <QuadraticBabybearField as IsSubFieldOf<QuarticBabybearField>>::add(&a, b)
+    }
+
+    fn div(a: &BaseType, b: &QuarticBaseType) -> QuarticBaseType {
+        let a = <Self as IsSubFieldOf<QuadraticBabybearField>>::embed(*a);
+        <QuadraticBabybearField as IsSubFieldOf<QuarticBabybearField>>::div(&a, b)
+    }
+
+    fn sub(a: &BaseType, b: &QuarticBaseType) -> QuarticBaseType {
+        let a = <Self as IsSubFieldOf<QuadraticBabybearField>>::embed(*a);
+        <QuadraticBabybearField as IsSubFieldOf<QuarticBabybearField>>::sub(&a, b)
+    }
+
+    fn embed(a: BaseType) -> QuarticBaseType {
+        let a = <Self as IsSubFieldOf<QuadraticBabybearField>>::embed(a);
+        <QuadraticBabybearField as IsSubFieldOf<QuarticBabybearField>>::embed(a)
+    }
+
+    #[cfg(feature = "alloc")]
+    fn to_subfield_vec(b: QuarticBaseType) -> alloc::vec::Vec<BaseType> {
+        b.into_iter()
+            .flat_map(|coordinate| coordinate.to_raw().map(FieldElement::to_raw))
+            .collect()
+    }
+
+    fn from_subfield_slice(b: &[BaseType]) -> QuarticBaseType {
+        [
+            FieldElement::from_raw(
+                <Self as IsSubFieldOf<QuadraticBabybearField>>::from_subfield_slice(&b[..2]),
+            ),
+            FieldElement::from_raw(
+                <Self as IsSubFieldOf<QuadraticBabybearField>>::from_subfield_slice(&b[2..4]),
+            ),
+        ]
+    }
+}
+
+/// The bytes of the coordinates, concatenated.
+#[cfg(feature = "alloc")]
+impl AsBytes for QuarticBabybearFieldElement {
+    fn as_bytes(&self) -> alloc::vec::Vec<u8> {
+        self.value().iter().flat_map(AsBytes::as_bytes).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    type FE = FieldElement<Babybear31PrimeField>;
+    type Fee = QuadraticBabybearFieldElement;
+    type Fqe = QuarticBabybearFieldElement;
+
+    fn element() -> Fqe {
+        Fqe::new([
+            Fee::new([FE::from(12), FE::from(5)]),
+            Fee::new([-FE::from(4), FE::from(2)]),
+        ])
+    }
+
+    #[test]
+    fn test_generator_to_the_fourth_is_eleven() {
+        let u = Fqe::new([Fee::zero(), Fee::one()]);
+        assert_eq!(u.pow(4_u64), Fqe::from(11));
+    }
+
+    #[test]
+    fn test_mul_by_inv_is_one() {
+        let a = element();
+        assert_eq!(&a * a.inv().unwrap(), Fqe::one());
+    }
+
+    #[test]
+    fn test_mul_babybear_by_quartic_is_mul_by_its_embedding() {
+        let a = FE::from(7);
+        assert_eq!(
+            &a * element(),
+            a.to_extension::<QuarticBabybearField>() * element()
+        );
+    }
+
+    #[test]
+    fn test_sub_babybear_and_quartic_is_sub_of_its_embedding() {
+        let a = FE::from(7);
+        assert_eq!(
+            &a - element(),
+            a.to_extension::<QuarticBabybearField>() - element()
+        );
+    }
+
+    #[test]
+    fn test_from_subfield_slice_is_the_inverse_of_to_subfield_vec() {
+        let coordinates = element().to_subfield_vec::<Babybear31PrimeField>();
+        assert_eq!(
+            coordinates,
+            vec![FE::from(12), FE::from(5), -FE::from(4), FE::from(2)]
+        );
+        assert_eq!(Fqe::from_subfield_slice(&coordinates), element());
+    }
+}
@@ -2,9 +2,11 @@ use super::field::Mersenne31Field;
 use crate::field::{
     element::FieldElement,
     errors::FieldError,
-    traits::{IsField, IsSubFieldOf},
+    traits::{IsFFTField, IsField, IsSubFieldOf},
 };
 #[cfg(feature = "alloc")]
+use crate::traits::AsBytes;
+#[cfg(feature = "alloc")]
 use alloc::vec::Vec;
 
 type FpE = FieldElement<Mersenne31Field>;
@@ -90,6 +92,19 @@ impl IsField for Degree2ExtensionField {
     }
 }
 
+/// The multiplicative group of the extension has order `p² - 1 = 2³² · (2³⁰ - 1)`, so unlike
+/// Mersenne31 itself, it has the roots of unity needed for FFTs.
+impl IsFFTField for Degree2ExtensionField {
+    const TWO_ADICITY: u64 = 32;
+
+    const TWO_ADIC_PRIMITVE_ROOT_OF_UNITY: Self::BaseType =
+        [FpE::const_from_raw(21189756), FpE::const_from_raw(42379512)];
+
+    fn field_name() -> &'static str {
+        "mersenne31_degree2"
+    }
+}
+
 impl IsSubFieldOf<Degree2ExtensionField> for Mersenne31Field {
     fn add(
         a: &Self::BaseType,
@@ -130,6 +145,10 @@ impl IsSubFieldOf<Degree2ExtensionField> for Mersenne31Field {
     ) -> alloc::vec::Vec<Self::BaseType> {
         b.into_iter().map(|x| x.to_raw()).collect()
     }
+
+    fn from_subfield_slice(b: &[Self::BaseType]) -> <Degree2ExtensionField as IsField>::BaseType {
+        [FpE::from_raw(b[0]), FpE::from_raw(b[1])]
+    }
 }
 
 type Fp2E = FieldElement<Degree2ExtensionField>;
@@ -254,6 +273,80 @@ impl IsSubFieldOf<Degree4ExtensionField> for Mersenne31Field {
         }
         result
     }
+
+    fn from_subfield_slice(b: &[Self::BaseType]) -> <Degree4ExtensionField as IsField>::BaseType {
+        [
+            Fp2E::from_raw(
+                <Self as IsSubFieldOf<Degree2ExtensionField>>::from_subfield_slice(&b[..2]),
+            ),
+            Fp2E::from_raw(
+                <Self as IsSubFieldOf<Degree2ExtensionField>>::from_subfield_slice(&b[2..4]),
+            ),
+        ]
+    }
+}
+
+impl IsSubFieldOf<Degree4ExtensionField> for Degree2ExtensionField {
+    fn add(
+        a: &Self::BaseType,
+        b: &<Degree4ExtensionField as IsField>::BaseType,
+    ) -> <Degree4ExtensionField as IsField>::BaseType {
+        [Fp2E::from_raw(*a) + &b[0], b[1].clone()]
+    }
+
+    fn sub(
+        a: &Self::BaseType,
+        b: &<Degree4ExtensionField as IsField>::BaseType,
+    ) -> <Degree4ExtensionField as IsField>::BaseType {
+        [Fp2E::from_raw(*a) - &b[0], -&b[1]]
+    }
+
+    fn mul(
+        a: &Self::BaseType,
+        b: &<Degree4ExtensionField as IsField>::BaseType,
+    ) -> <Degree4ExtensionField as IsField>::BaseType {
+        let a = Fp2E::from_raw(*a);
+        [&a * &b[0], &a * &b[1]]
+    }
+
+    fn div(
+        a: &Self::BaseType,
+        b: &<Degree4ExtensionField as IsField>::BaseType,
+    ) -> <Degree4ExtensionField as IsField>::BaseType {
+        let b_inv = Degree4ExtensionField::inv(b).unwrap();
+        <Self as IsSubFieldOf<Degree4ExtensionField>>::mul(a, &b_inv)
+    }
+
+    fn embed(a: Self::BaseType) -> <Degree4ExtensionField as IsField>::BaseType {
+        [Fp2E::from_raw(a), Fp2E::zero()]
+    }
+
+    #[cfg(feature = "alloc")]
+    fn to_subfield_vec(
+        b: <Degree4ExtensionField as IsField>::BaseType,
+    ) -> alloc::vec::Vec<Self::BaseType> {
+        b.into_iter().map(|x| x.to_raw()).collect()
+    }
+
+    fn from_subfield_slice(b: &[Self::BaseType]) -> <Degree4ExtensionField as IsField>::BaseType {
+        [Fp2E::from_raw(b[0]), Fp2E::from_raw(b[1])]
+    }
+}
+
+/// The bytes of the coordinates, concatenated.
+#[cfg(feature = "alloc")]
+impl AsBytes for FieldElement<Degree2ExtensionField> {
+    fn as_bytes(&self) -> alloc::vec::Vec<u8> {
+        self.value().iter().flat_map(AsBytes::as_bytes).collect()
+    }
+}
+
+/// The bytes of the coordinates, concatenated.
+#[cfg(feature = "alloc")]
+impl AsBytes for FieldElement<Degree4ExtensionField> {
+    fn as_bytes(&self) -> alloc::vec::Vec<u8> {
+        self.value().iter().flat_map(AsBytes::as_bytes).collect()
+    }
 }
 
 #[cfg(test)]
@@ -624,4 +717,33 @@ mod tests {
         ]);
         assert_eq!(a * &b, a_extension * b);
     }
+
+    #[test]
+    fn two_adic_primitive_root_of_unity_has_order_two_to_the_two_adicity() {
+        let root = Fp2E::new(Degree2ExtensionField::TWO_ADIC_PRIMITVE_ROOT_OF_UNITY);
+        let half_order = 1u64 << (Degree2ExtensionField::TWO_ADICITY - 1);
+        assert_eq!(root.pow(half_order), -Fp2E::one());
+        assert_eq!(root.pow(2 * half_order), Fp2E::one());
+    }
+
+    #[test]
+    fn mul_fp2_by_fp4() {
+        let a = Fp2E::new([FpE::from(5), FpE::from(7)]);
+        let a_extension = a.clone().to_extension::<Degree4ExtensionField>();
+        let b = Fp4E::new([
+            Fp2E::new([FpE::from(1), FpE::from(2)]),
+            Fp2E::new([FpE::from(3), FpE::from(4)]),
+        ]);
+        assert_eq!(a * &b, a_extension * b);
+    }
+
+    #[test]
+    fn fp4_from_fp2_subfield_slice_is_the_inverse_of_to_subfield_vec() {
+        let a = Fp4E::new([
+            Fp2E::new([FpE::from(1), FpE::from(2)]),
+            Fp2E::new([FpE::from(3), FpE::from(4)]),
+        ]);
+        let coordinates = a.clone().to_subfield_vec::<Degree2ExtensionField>();
+        assert_eq!(Fp4E::from_subfield_slice(&coordinates), a);
+    }
 }
@@ -1,10 +1,13 @@
+#[cfg(feature = "alloc")]
+use crate::traits::AsBytes;
 use crate::{
-    errors::CreationError,
+    errors::{ByteConversionError, CreationError},
     field::{
         element::FieldElement,
         errors::FieldError,
         traits::{IsField, IsPrimeField},
     },
+    traits::{ByteConversion, CanonicalByteConversion},
 };
 use core::fmt::{self, Display};
 
@@ -197,6 +200,69 @@ impl FieldElement<Mersenne31Field> {
     }
 }
 
+impl CanonicalByteConversion for FieldElement<Mersenne31Field> {
+    type Bytes = [u8; 4];
+
+    fn to_bytes_be_array(&self) -> Self::Bytes {
+        self.representative().to_be_bytes()
+    }
+
+    fn to_bytes_le_array(&self) -> Self::Bytes {
+        self.representative().to_le_bytes()
+    }
+
+    fn from_bytes_be_array(bytes: &Self::Bytes) -> Result<Self, ByteConversionError> {
+        match u32::from_be_bytes(*bytes) {
+            value if value < MERSENNE_31_PRIME_FIELD_ORDER => Ok(Self::from(value as u64)),
+            _ => Err(ByteConversionError::InvalidValue),
+        }
+    }
+
+    fn from_bytes_le_array(bytes: &Self::Bytes) -> Result<Self, ByteConversionError> {
+        match u32::from_le_bytes(*bytes) {
+            value if value < MERSENNE_31_PRIME_FIELD_ORDER => Ok(Self::from(value as u64)),
+            _ => Err(ByteConversionError::InvalidValue),
+        }
+    }
+}
+
+/// Elements are encoded as the 4 bytes of their representatives. Decoding reads the first 4 bytes
+/// and reduces them, so that elements can be sampled from longer random strings.
+impl ByteConversion for FieldElement<Mersenne31Field> {
+    #[cfg(feature = "alloc")]
+    fn to_bytes_be(&self) -> alloc::vec::Vec<u8> {
+        self.representative().to_be_bytes().to_vec()
+    }
+
+    #[cfg(feature = "alloc")]
+    fn to_bytes_le(&self) -> alloc::vec::Vec<u8> {
+        self.representative().to_le_bytes().to_vec()
+    }
+
+    fn from_bytes_be(bytes: &[u8]) -> Result<Self, ByteConversionError> {
+        let needed_bytes = bytes
+            .get(0..4)
+            .ok_or(ByteConversionError::FromBEBytesError)?;
+        let value = u32::from_be_bytes(needed_bytes.try_into().unwrap());
+        Ok(Self::from(value as u64))
+    }
+
+    fn from_bytes_le(bytes: &[u8]) -> Result<Self, ByteConversionError> {
+        let needed_bytes = bytes
+            .get(0..4)
+            .ok_or(ByteConversionError::FromLEBytesError)?;
+        let value = u32::from_le_bytes(needed_bytes.try_into().unwrap());
+        Ok(Self::from(value as u64))
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl AsBytes for FieldElement<Mersenne31Field> {
+    fn as_bytes(&self) -> alloc::vec::Vec<u8> {
+        self.representative().to_be_bytes().to_vec()
+    }
+}
+
 impl Display for FieldElement<Mersenne31Field> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "{:x}", self.representative())
@@ -462,4 +528,22 @@ mod tests {
         let x = 3476715743_u32;
         assert_eq!(FE::from(&x).inv().unwrap() * FE::from(&x), FE::one());
     }
+
+    #[test]
+    fn from_bytes_be_is_the_inverse_of_to_bytes_be() {
+        let x = FE::from(&3476715743_u32);
+        assert_eq!(
+            <FE as ByteConversion>::from_bytes_be(&x.to_bytes_be()).unwrap(),
+            x
+        );
+    }
+
+    #[test]
+    fn from_bytes_be_reduces_the_first_four_bytes() {
+        let bytes = [0xff; 32];
+        assert_eq!(
+            <FE as ByteConversion>::from_bytes_be(&bytes).unwrap(),
+            FE::from(u32::MAX as u64)
+        );
+    }
 }
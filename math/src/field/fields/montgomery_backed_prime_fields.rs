@@ -1,9 +1,10 @@
+use crate::errors::ByteConversionError;
 use crate::field::element::FieldElement;
 use crate::field::errors::FieldError;
 use crate::field::traits::IsPrimeField;
 #[cfg(feature = "alloc")]
 use crate::traits::AsBytes;
-use crate::traits::ByteConversion;
+use crate::traits::{ByteConversion, CanonicalByteConversion};
 use crate::{
     field::traits::IsField, unsigned_integer::element::UnsignedInteger,
     unsigned_integer::montgomery::MontgomeryAlgorithms,
@@ -294,8 +295,8 @@ where
 
     fn field_bit_size() -> usize {
         let mut evaluated_bit = NUM_LIMBS * 64 - 1;
-        let max_element = M::MODULUS - UnsignedInteger::<NUM_LIMBS>::from_u128(1);
-        let one = UnsignedInteger::from_u128(1);
+        let max_element = M::MODULUS - UnsignedInteger::<NUM_LIMBS>::from_u64(1);
+        let one = UnsignedInteger::from_u64(1);
 
         while ((max_element >> evaluated_bit) & one) != one {
             evaluated_bit -= 1;
@@ -363,6 +364,40 @@ where
     }
 }
 
+/// Elements are encoded as their representatives, which must be smaller than the modulus.
+impl<M, const NUM_LIMBS: usize> CanonicalByteConversion
+    for FieldElement<MontgomeryBackendPrimeField<M, NUM_LIMBS>>
+where
+    M: IsModulus<UnsignedInteger<NUM_LIMBS>> + Clone + Debug,
+    UnsignedInteger<NUM_LIMBS>: CanonicalByteConversion,
+{
+    type Bytes = <UnsignedInteger<NUM_LIMBS> as CanonicalByteConversion>::Bytes;
+
+    fn to_bytes_be_array(&self) -> Self::Bytes {
+        self.representative().to_bytes_be_array()
+    }
+
+    fn to_bytes_le_array(&self) -> Self::Bytes {
+        self.representative().to_bytes_le_array()
+    }
+
+    fn from_bytes_be_array(bytes: &Self::Bytes) -> Result<Self, ByteConversionError> {
+        let value = UnsignedInteger::from_bytes_be_array(bytes)?;
+        if value >= M::MODULUS {
+            return Err(ByteConversionError::InvalidValue);
+        }
+        Ok(Self::new(value))
+    }
+
+    fn from_bytes_le_array(bytes: &Self::Bytes) -> Result<Self, ByteConversionError> {
+        let value = UnsignedInteger::from_bytes_le_array(bytes)?;
+        if value >= M::MODULUS {
+            return Err(ByteConversionError::InvalidValue);
+        }
+        Ok(Self::new(value))
+    }
+}
+
 #[cfg(feature = "alloc")]
 impl<M, const NUM_LIMBS: usize> AsBytes for FieldElement<MontgomeryBackendPrimeField<M, NUM_LIMBS>>
 where
@@ -386,8 +421,10 @@ where
 
 #[cfg(test)]
 mod tests_u384_prime_fields {
+    use crate::errors::ByteConversionError;
     use crate::field::element::FieldElement;
     use crate::field::errors::FieldError;
+    use crate::field::fields::fft_friendly::babybear::Babybear31PrimeField;
     use crate::field::fields::fft_friendly::stark_252_prime_field::Stark252PrimeField;
     use crate::field::fields::montgomery_backed_prime_fields::{
         IsModulus, U256PrimeField, U384PrimeField,
@@ -396,6 +433,7 @@ mod tests_u384_prime_fields {
     use crate::field::traits::IsPrimeField;
     #[cfg(feature = "alloc")]
     use crate::traits::ByteConversion;
+    use crate::traits::CanonicalByteConversion;
     use crate::unsigned_integer::element::U384;
     use crate::unsigned_integer::element::{UnsignedInteger, U256};
 
@@ -418,6 +456,11 @@ mod tests_u384_prime_fields {
         assert_eq!(Stark252PrimeField::field_bit_size(), 252);
     }
 
+    #[test]
+    fn babybear_prime_field_uses_31_bits() {
+        assert_eq!(Babybear31PrimeField::field_bit_size(), 31);
+    }
+
     #[test]
     fn u256_mod_2_uses_1_bit() {
         #[derive(Clone, Debug)]
@@ -780,6 +823,45 @@ mod tests_u384_prime_fields {
             bytes
         );
     }
+
+    #[test]
+    fn canonical_bytes_round_trip() {
+        let x = U384FP2Element::new(UnsignedInteger::from_hex_unchecked(
+            "5f103b0bd4397d4df560eb559f38353f80eeb6",
+        ));
+        let bytes_be = x.to_bytes_be_array();
+        let bytes_le = x.to_bytes_le_array();
+        assert_eq!(bytes_be[47], 0xb6);
+        assert_eq!(bytes_le[0], 0xb6);
+        assert_eq!(
+            U384FP2Element::from_bytes_be_array(&bytes_be),
+            Ok(x.clone())
+        );
+        assert_eq!(U384FP2Element::from_bytes_le_array(&bytes_le), Ok(x));
+    }
+
+    #[test]
+    fn canonical_bytes_of_integers_not_smaller_than_the_modulus_are_rejected() {
+        let modulus = U384ModulusP2::MODULUS;
+        assert_eq!(
+            U384FP2Element::from_bytes_be_array(&modulus.to_bytes_be_array()),
+            Err(ByteConversionError::InvalidValue)
+        );
+        assert_eq!(
+            U384FP2Element::from_bytes_le_array(&[0xff; 48]),
+            Err(ByteConversionError::InvalidValue)
+        );
+        assert_eq!(
+            U384FP2Element::from_bytes_be_canonical(&[1; 47]),
+            Err(ByteConversionError::FromBEBytesError)
+        );
+        assert_eq!(
+            U384FP2Element::from_bytes_be_array(
+                &(modulus - UnsignedInteger::from_u64(1)).to_bytes_be_array()
+            ),
+            Ok(-U384FP2Element::one())
+        );
+    }
 }
 
 #[cfg(test)]
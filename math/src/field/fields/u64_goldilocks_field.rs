@@ -3,13 +3,14 @@ use core::fmt::{self, Display};
 #[cfg(feature = "lambdaworks-serde-binary")]
 use crate::traits::ByteConversion;
 use crate::{
-    errors::CreationError,
+    errors::{ByteConversionError, CreationError},
     field::{
         element::FieldElement,
         errors::FieldError,
         extensions::quadratic::{HasQuadraticNonResidue, QuadraticExtensionField},
         traits::{IsField, IsPrimeField},
     },
+    traits::CanonicalByteConversion,
 };
 
 /// Goldilocks Prime Field F_p where p = 2^64 - 2^32 + 1;
@@ -184,6 +185,32 @@ impl IsPrimeField for Goldilocks64Field {
     }
 }
 
+impl CanonicalByteConversion for FieldElement<Goldilocks64Field> {
+    type Bytes = [u8; 8];
+
+    fn to_bytes_be_array(&self) -> Self::Bytes {
+        self.representative().to_be_bytes()
+    }
+
+    fn to_bytes_le_array(&self) -> Self::Bytes {
+        self.representative().to_le_bytes()
+    }
+
+    fn from_bytes_be_array(bytes: &Self::Bytes) -> Result<Self, ByteConversionError> {
+        match u64::from_be_bytes(*bytes) {
+            value if value < Goldilocks64Field::ORDER => Ok(Self::from(value)),
+            _ => Err(ByteConversionError::InvalidValue),
+        }
+    }
+
+    fn from_bytes_le_array(bytes: &Self::Bytes) -> Result<Self, ByteConversionError> {
+        match u64::from_le_bytes(*bytes) {
+            value if value < Goldilocks64Field::ORDER => Ok(Self::from(value)),
+            _ => Err(ByteConversionError::InvalidValue),
+        }
+    }
+}
+
 #[inline(always)]
 fn reduce_128(x: u128) -> u64 {
     //possibly split apart into separate function to ensure inline
@@ -1,11 +1,11 @@
 use crate::cyclic_group::IsGroup;
-use crate::errors::ByteConversionError::{FromBEBytesError, FromLEBytesError};
+use crate::errors::ByteConversionError::{self, FromBEBytesError, FromLEBytesError, InvalidValue};
 use crate::errors::CreationError;
 use crate::errors::DeserializationError;
 use crate::field::element::FieldElement;
 use crate::field::errors::FieldError;
 use crate::field::traits::{IsFFTField, IsField, IsPrimeField};
-use crate::traits::{ByteConversion, Deserializable};
+use crate::traits::{ByteConversion, CanonicalByteConversion, Deserializable};
 
 /// Type representing prime fields over unsigned 64-bit integers.
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -143,6 +143,32 @@ impl<const MODULUS: u64> ByteConversion for U64FieldElement<MODULUS> {
     }
 }
 
+impl<const MODULUS: u64> CanonicalByteConversion for U64FieldElement<MODULUS> {
+    type Bytes = [u8; 8];
+
+    fn to_bytes_be_array(&self) -> Self::Bytes {
+        self.value().to_be_bytes()
+    }
+
+    fn to_bytes_le_array(&self) -> Self::Bytes {
+        self.value().to_le_bytes()
+    }
+
+    fn from_bytes_be_array(bytes: &Self::Bytes) -> Result<Self, ByteConversionError> {
+        match u64::from_be_bytes(*bytes) {
+            value if value < MODULUS => Ok(Self::from(value)),
+            _ => Err(InvalidValue),
+        }
+    }
+
+    fn from_bytes_le_array(bytes: &Self::Bytes) -> Result<Self, ByteConversionError> {
+        match u64::from_le_bytes(*bytes) {
+            value if value < MODULUS => Ok(Self::from(value)),
+            _ => Err(InvalidValue),
+        }
+    }
+}
+
 impl<const MODULUS: u64> Deserializable for FieldElement<U64PrimeField<MODULUS>> {
     fn deserialize(bytes: &[u8]) -> Result<Self, DeserializationError>
     where
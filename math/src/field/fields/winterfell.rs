@@ -277,4 +277,8 @@ impl IsSubFieldOf<QuadFelt> for Felt {
     fn to_subfield_vec(b: <QuadFelt as IsField>::BaseType) -> Vec<Self::BaseType> {
         b.to_base_elements().to_vec()
     }
+
+    fn from_subfield_slice(b: &[Self::BaseType]) -> <QuadFelt as IsField>::BaseType {
+        QuadFelt::new(b[0], b[1])
+    }
 }
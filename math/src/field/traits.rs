@@ -23,6 +23,9 @@ pub trait IsSubFieldOf<F: IsField>: IsField {
     fn embed(a: Self::BaseType) -> F::BaseType;
     #[cfg(feature = "alloc")]
     fn to_subfield_vec(b: F::BaseType) -> alloc::vec::Vec<Self::BaseType>;
+    /// The element of `F` with coordinates `b` over this field, as returned by
+    /// `to_subfield_vec`. Panics if `b` has fewer coordinates than the degree of `F`.
+    fn from_subfield_slice(b: &[Self::BaseType]) -> F::BaseType;
 }
 
 impl<F> IsSubFieldOf<F> for F
@@ -58,6 +61,11 @@ where
     fn to_subfield_vec(b: F::BaseType) -> alloc::vec::Vec<Self::BaseType> {
         alloc::vec![b]
     }
+
+    #[inline(always)]
+    fn from_subfield_slice(b: &[Self::BaseType]) -> F::BaseType {
+        b[0].clone()
+    }
 }
 
 /// Trait to define necessary parameters for FFT-friendly Fields.
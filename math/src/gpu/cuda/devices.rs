@@ -0,0 +1,126 @@
+use crate::gpu::cuda::memory::DeviceMemoryPool;
+use core::ops::Range;
+use lambdaworks_gpu::cuda::abstractions::errors::CudaError;
+
+/// The GPUs that work is split across, in machines with several of them. Each one takes its
+/// buffers from its own [`DeviceMemoryPool::of_device`].
+#[derive(Clone)]
+pub struct CudaDevices {
+    pools: Vec<DeviceMemoryPool>,
+}
+
+impl CudaDevices {
+    /// Returns the GPUs with the given `ordinals`. An ordinal may be repeated, to run several
+    /// shards on the same GPU.
+    pub fn new(ordinals: &[usize]) -> Result<Self, CudaError> {
+        if ordinals.is_empty() {
+            return Err(CudaError::DeviceNotFound("empty device list".to_string()));
+        }
+        let pools = ordinals
+            .iter()
+            .map(|&ordinal| DeviceMemoryPool::of_device(ordinal))
+            .collect::<Result<_, _>>()?;
+        Ok(Self { pools })
+    }
+
+    pub fn pools(&self) -> &[DeviceMemoryPool] {
+        &self.pools
+    }
+
+    /// Returns the number of GPUs in the list.
+    pub fn len(&self) -> usize {
+        self.pools.len()
+    }
+
+    /// Always `false`, as the list can't be empty.
+    pub fn is_empty(&self) -> bool {
+        self.pools.is_empty()
+    }
+
+    /// Splits `0..len` into contiguous ranges of about the same size, one per GPU, and runs
+    /// `work` on each of them in its own thread. Returns the results in the order of the
+    /// ranges, so that the host can put them together.
+    ///
+    /// If `len` is smaller than the number of GPUs, only the first `len` of them get work.
+    pub fn shard<T: Send>(
+        &self,
+        len: usize,
+        work: impl Fn(&DeviceMemoryPool, Range<usize>) -> Result<T, CudaError> + Sync,
+    ) -> Result<Vec<T>, CudaError> {
+        let ranges = shard_ranges(len, self.pools.len());
+        let work = &work;
+        std::thread::scope(|scope| {
+            let handles: Vec<_> = self
+                .pools
+                .iter()
+                .zip(ranges)
+                .map(|(pool, range)| scope.spawn(move || work(pool, range)))
+                .collect();
+            handles
+                .into_iter()
+                .map(|handle| {
+                    handle
+                        .join()
+                        .unwrap_or_else(|panic| std::panic::resume_unwind(panic))
+                })
+                .collect()
+        })
+    }
+}
+
+/// Splits `0..len` into at most `parts` non-empty contiguous ranges, whose lengths differ by one
+/// at most.
+fn shard_ranges(len: usize, parts: usize) -> Vec<Range<usize>> {
+    let parts = parts.min(len);
+    (0..parts)
+        .map(|i| i * len / parts..(i + 1) * len / parts)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shard_ranges_cover_the_input_in_order() {
+        for (len, parts) in [(10, 3), (3, 8), (16, 4), (0, 2), (1000, 7)] {
+            let ranges = shard_ranges(len, parts);
+            assert_eq!(ranges.len(), parts.min(len));
+            assert_eq!(ranges.first().map_or(0, |range| range.start), 0);
+            assert_eq!(ranges.last().map_or(0, |range| range.end), len);
+            for pair in ranges.windows(2) {
+                assert_eq!(pair[0].end, pair[1].start);
+                assert!(pair[0].len().abs_diff(pair[1].len()) <= 1);
+            }
+            assert!(ranges.iter().all(|range| !range.is_empty()));
+        }
+    }
+
+    #[test]
+    fn shards_run_on_every_device() {
+        let devices = CudaDevices::new(&[0, 0, 0]).unwrap();
+        let data: Vec<u64> = (0..100).collect();
+
+        let sums = devices
+            .shard(data.len(), |pool, range| {
+                let buffer = pool.upload(&data[range])?;
+                let values = pool
+                    .device()
+                    .dtoh_sync_copy(&*buffer)
+                    .map_err(|err| CudaError::RetrieveMemory(err.to_string()))?;
+                Ok(values.iter().sum::<u64>())
+            })
+            .unwrap();
+
+        assert_eq!(sums.len(), 3);
+        assert_eq!(sums.iter().sum::<u64>(), data.iter().sum::<u64>());
+    }
+
+    #[test]
+    fn empty_device_list_should_fail() {
+        assert!(matches!(
+            CudaDevices::new(&[]),
+            Err(CudaError::DeviceNotFound(_))
+        ));
+    }
+}
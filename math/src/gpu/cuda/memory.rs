@@ -0,0 +1,407 @@
+use cudarc::driver::{
+    safe::{CudaSlice, CudaStream, DeviceSlice},
+    sys, CudaDevice, DevicePtr, DeviceRepr,
+};
+use lambdaworks_gpu::cuda::abstractions::errors::CudaError;
+use std::{
+    collections::HashMap,
+    ops::{Deref, DerefMut},
+    sync::{Arc, Mutex, MutexGuard, OnceLock},
+};
+
+/// Device memory, and page-locked host memory, kept for reuse once the buffers taken from it are
+/// dropped. The FFTs of the columns of an LDE, the levels of its Merkle tree and the layers of
+/// FRI ask for buffers of the same few sizes, which are then allocated only once. Clones share
+/// the same memory.
+///
+/// A freed block is reused by requests of exactly its size in bytes, and its contents are
+/// unspecified. Cached blocks are freed with [`Self::release`], when an allocation fails, or
+/// when the last clone is dropped.
+///
+/// Buffers are returned to the pool as soon as they are dropped, so work queued on other
+/// streams than the default one must be synchronized before that.
+#[derive(Clone)]
+pub struct DeviceMemoryPool {
+    device: Arc<CudaDevice>,
+    blocks: Arc<Mutex<FreeBlocks>>,
+}
+
+impl DeviceMemoryPool {
+    /// Creates an empty pool for `device`.
+    pub fn new(device: Arc<CudaDevice>) -> Self {
+        let blocks = FreeBlocks {
+            device: Arc::clone(&device),
+            device_blocks: HashMap::new(),
+            host_blocks: HashMap::new(),
+        };
+        Self {
+            device,
+            blocks: Arc::new(Mutex::new(blocks)),
+        }
+    }
+
+    /// Returns the pool of the first GPU, shared by the whole process. The CUDA states created
+    /// with `new` take their buffers from it.
+    pub fn global() -> Result<Self, CudaError> {
+        Self::of_device(0)
+    }
+
+    /// Returns the pool of the GPU with the given `ordinal`, shared by the whole process.
+    pub fn of_device(ordinal: usize) -> Result<Self, CudaError> {
+        static POOLS: OnceLock<Mutex<HashMap<usize, DeviceMemoryPool>>> = OnceLock::new();
+        let mut pools = POOLS
+            .get_or_init(Default::default)
+            .lock()
+            .unwrap_or_else(|err| err.into_inner());
+        if let Some(pool) = pools.get(&ordinal) {
+            return Ok(pool.clone());
+        }
+        let device =
+            CudaDevice::new(ordinal).map_err(|err| CudaError::DeviceNotFound(err.to_string()))?;
+        Ok(pools.entry(ordinal).or_insert(Self::new(device)).clone())
+    }
+
+    pub fn device(&self) -> &Arc<CudaDevice> {
+        &self.device
+    }
+
+    /// Returns a device buffer of `len` elements.
+    pub fn alloc<T: DeviceRepr>(&self, len: usize) -> Result<PooledSlice<T>, CudaError> {
+        let bytes = len * core::mem::size_of::<T>();
+        let cached = self.lock().take_device(bytes);
+        let slice = match cached {
+            // SAFETY: the block was allocated in this device with `bytes` bytes, and nothing
+            // else owns it.
+            Some(ptr) => unsafe { self.device.upgrade_device_ptr(ptr, len) },
+            None => unsafe { self.device.alloc(len) }.or_else(|_| {
+                self.release();
+                unsafe { self.device.alloc(len) }
+                    .map_err(|err| CudaError::AllocateMemory(err.to_string()))
+            })?,
+        };
+
+        Ok(PooledSlice {
+            slice: Some(slice),
+            bytes,
+            blocks: Arc::clone(&self.blocks),
+        })
+    }
+
+    /// Returns a device buffer with a copy of `data`.
+    pub fn upload<T: DeviceRepr>(&self, data: &[T]) -> Result<PooledSlice<T>, CudaError> {
+        let mut buffer = self.alloc(data.len())?;
+        self.device
+            .htod_sync_copy_into(data, &mut *buffer)
+            .map_err(|err| CudaError::AllocateMemory(err.to_string()))?;
+        Ok(buffer)
+    }
+
+    /// Returns a page-locked host buffer of `len` elements.
+    pub(crate) fn alloc_pinned<T: DeviceRepr>(
+        &self,
+        len: usize,
+    ) -> Result<PooledPinned<T>, CudaError> {
+        let bytes = len * core::mem::size_of::<T>();
+        let cached = self.lock().take_host(bytes);
+        let buffer = match cached {
+            Some(ptr) => PinnedBuffer {
+                ptr: ptr as *mut T,
+                len,
+            },
+            None => PinnedBuffer::new(&self.device, len).or_else(|_| {
+                self.release();
+                PinnedBuffer::new(&self.device, len)
+            })?,
+        };
+
+        Ok(PooledPinned {
+            buffer: Some(buffer),
+            bytes,
+            blocks: Arc::clone(&self.blocks),
+        })
+    }
+
+    /// Returns the size in bytes of the device blocks kept for reuse. They are free for the
+    /// buffers of this pool, but not for other allocations.
+    pub fn cached_bytes(&self) -> usize {
+        let blocks = self.lock();
+        blocks
+            .device_blocks
+            .iter()
+            .map(|(bytes, ptrs)| bytes * ptrs.len())
+            .sum()
+    }
+
+    /// Frees the blocks kept for reuse. Buffers still in use are returned to the pool as usual.
+    pub fn release(&self) {
+        self.lock().free_all();
+    }
+
+    fn lock(&self) -> MutexGuard<'_, FreeBlocks> {
+        lock(&self.blocks)
+    }
+}
+
+/// Blocks of a [`DeviceMemoryPool`] that aren't in use, by size in bytes. Host pointers are
+/// kept as integers so that the pool can be shared between threads.
+struct FreeBlocks {
+    device: Arc<CudaDevice>,
+    device_blocks: HashMap<usize, Vec<sys::CUdeviceptr>>,
+    host_blocks: HashMap<usize, Vec<usize>>,
+}
+
+impl FreeBlocks {
+    fn take_device(&mut self, bytes: usize) -> Option<sys::CUdeviceptr> {
+        self.device_blocks.get_mut(&bytes).and_then(Vec::pop)
+    }
+
+    fn take_host(&mut self, bytes: usize) -> Option<usize> {
+        self.host_blocks.get_mut(&bytes).and_then(Vec::pop)
+    }
+
+    fn free_all(&mut self) {
+        for (bytes, ptrs) in self.device_blocks.drain() {
+            for ptr in ptrs {
+                // SAFETY: the block was allocated in this device with `bytes` bytes, and
+                // dropping the slice frees it.
+                drop(unsafe { self.device.upgrade_device_ptr::<u8>(ptr, bytes) });
+            }
+        }
+        for (_, ptrs) in self.host_blocks.drain() {
+            for ptr in ptrs {
+                unsafe { sys::cuMemFreeHost(ptr as *mut core::ffi::c_void) };
+            }
+        }
+    }
+}
+
+impl Drop for FreeBlocks {
+    fn drop(&mut self) {
+        self.free_all();
+    }
+}
+
+fn lock(blocks: &Mutex<FreeBlocks>) -> MutexGuard<'_, FreeBlocks> {
+    // The blocks are consistent even if a thread panicked while holding the lock
+    blocks.lock().unwrap_or_else(|err| err.into_inner())
+}
+
+/// A device buffer taken from a [`DeviceMemoryPool`], which is returned to it when dropped.
+pub struct PooledSlice<T> {
+    slice: Option<CudaSlice<T>>,
+    bytes: usize,
+    blocks: Arc<Mutex<FreeBlocks>>,
+}
+
+impl<T> Deref for PooledSlice<T> {
+    type Target = CudaSlice<T>;
+
+    fn deref(&self) -> &CudaSlice<T> {
+        self.slice
+            .as_ref()
+            .expect("the slice is only taken on drop")
+    }
+}
+
+impl<T> DerefMut for PooledSlice<T> {
+    fn deref_mut(&mut self) -> &mut CudaSlice<T> {
+        self.slice
+            .as_mut()
+            .expect("the slice is only taken on drop")
+    }
+}
+
+impl<T> Drop for PooledSlice<T> {
+    fn drop(&mut self) {
+        if let Some(slice) = self.slice.take() {
+            if self.bytes == 0 {
+                return;
+            }
+            let ptr = slice.leak();
+            lock(&self.blocks)
+                .device_blocks
+                .entry(self.bytes)
+                .or_default()
+                .push(ptr);
+        }
+    }
+}
+
+/// A page-locked host buffer taken from a [`DeviceMemoryPool`], which is returned to it when
+/// dropped.
+pub(crate) struct PooledPinned<T: DeviceRepr> {
+    buffer: Option<PinnedBuffer<T>>,
+    bytes: usize,
+    blocks: Arc<Mutex<FreeBlocks>>,
+}
+
+impl<T: DeviceRepr> Deref for PooledPinned<T> {
+    type Target = PinnedBuffer<T>;
+
+    fn deref(&self) -> &PinnedBuffer<T> {
+        self.buffer
+            .as_ref()
+            .expect("the buffer is only taken on drop")
+    }
+}
+
+impl<T: DeviceRepr> DerefMut for PooledPinned<T> {
+    fn deref_mut(&mut self) -> &mut PinnedBuffer<T> {
+        self.buffer
+            .as_mut()
+            .expect("the buffer is only taken on drop")
+    }
+}
+
+impl<T: DeviceRepr> Drop for PooledPinned<T> {
+    fn drop(&mut self) {
+        if let Some(buffer) = self.buffer.take() {
+            let ptr = buffer.ptr as usize;
+            // The block now belongs to the pool
+            core::mem::forget(buffer);
+            lock(&self.blocks)
+                .host_blocks
+                .entry(self.bytes)
+                .or_default()
+                .push(ptr);
+        }
+    }
+}
+
+/// Page-locked host memory. Copies between it and the device are asynchronous, so they overlap
+/// with the kernels running in other streams.
+pub(crate) struct PinnedBuffer<T: DeviceRepr> {
+    ptr: *mut T,
+    len: usize,
+}
+
+impl<T: DeviceRepr> PinnedBuffer<T> {
+    pub(crate) fn new(device: &Arc<CudaDevice>, len: usize) -> Result<Self, CudaError> {
+        device
+            .bind_to_thread()
+            .map_err(|err| CudaError::AllocateMemory(err.to_string()))?;
+        let mut ptr = core::ptr::null_mut();
+        unsafe { sys::cuMemAllocHost_v2(&mut ptr, len * core::mem::size_of::<T>()) }
+            .result()
+            .map_err(|err| CudaError::AllocateMemory(err.to_string()))?;
+        Ok(Self {
+            ptr: ptr as *mut T,
+            len,
+        })
+    }
+
+    /// Writes `values` at the start of the buffer. Returns how many were written.
+    pub(crate) fn write(&mut self, values: impl IntoIterator<Item = T>) -> usize {
+        let mut written = 0;
+        for value in values {
+            assert!(written < self.len, "pinned buffer overflow");
+            unsafe { self.ptr.add(written).write(value) };
+            written += 1;
+        }
+        written
+    }
+
+    /// Returns the first `len` elements.
+    ///
+    /// # Safety
+    /// They must have been written, by [`Self::write`] or by a copy from the device that has
+    /// already finished.
+    pub(crate) unsafe fn as_slice(&self, len: usize) -> &[T] {
+        assert!(len <= self.len);
+        core::slice::from_raw_parts(self.ptr, len)
+    }
+
+    /// Queues the copy of the first `len` elements into `dst` on `stream`.
+    ///
+    /// # Safety
+    /// The buffer must not be written until `stream` is synchronized.
+    pub(crate) unsafe fn copy_to_device_async(
+        &self,
+        len: usize,
+        dst: &mut CudaSlice<T>,
+        stream: &CudaStream,
+    ) -> Result<(), CudaError> {
+        assert!(len <= self.len && len <= dst.len());
+        sys::cuMemcpyHtoDAsync_v2(
+            *dst.device_ptr(),
+            self.ptr as *const core::ffi::c_void,
+            len * core::mem::size_of::<T>(),
+            stream.stream,
+        )
+        .result()
+        .map_err(|err| CudaError::AllocateMemory(err.to_string()))
+    }
+
+    /// Queues the copy of the first `len` elements of `src` into the buffer on `stream`.
+    ///
+    /// # Safety
+    /// The buffer must not be read or written until `stream` is synchronized.
+    pub(crate) unsafe fn copy_from_device_async(
+        &mut self,
+        len: usize,
+        src: &CudaSlice<T>,
+        stream: &CudaStream,
+    ) -> Result<(), CudaError> {
+        assert!(len <= self.len && len <= src.len());
+        sys::cuMemcpyDtoHAsync_v2(
+            self.ptr as *mut core::ffi::c_void,
+            *src.device_ptr(),
+            len * core::mem::size_of::<T>(),
+            stream.stream,
+        )
+        .result()
+        .map_err(|err| CudaError::RetrieveMemory(err.to_string()))
+    }
+}
+
+impl<T: DeviceRepr> Drop for PinnedBuffer<T> {
+    fn drop(&mut self) {
+        unsafe {
+            sys::cuMemFreeHost(self.ptr as *mut core::ffi::c_void);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dropped_buffers_are_reused_by_requests_of_the_same_size() {
+        let pool = DeviceMemoryPool::new(CudaDevice::new(0).unwrap());
+        let data: Vec<u64> = (0..1000).collect();
+
+        let buffer = pool.upload(&data).unwrap();
+        let ptr = *buffer.device_ptr();
+        assert_eq!(pool.device().dtoh_sync_copy(&*buffer).unwrap(), data);
+        drop(buffer);
+        assert_eq!(pool.cached_bytes(), 8000);
+
+        // Same size in bytes, with another type
+        let buffer = pool.alloc::<u32>(2000).unwrap();
+        assert_eq!(*buffer.device_ptr(), ptr);
+        assert_eq!(pool.cached_bytes(), 0);
+
+        let other = pool.alloc::<u64>(999).unwrap();
+        assert_ne!(*other.device_ptr(), ptr);
+        drop((buffer, other));
+        assert_eq!(pool.cached_bytes(), 8000 + 7992);
+    }
+
+    #[test]
+    fn released_blocks_are_freed() {
+        let pool = DeviceMemoryPool::new(CudaDevice::new(0).unwrap());
+        let buffer = pool.alloc::<u64>(1 << 20).unwrap();
+        let pinned = pool.alloc_pinned::<u64>(1 << 20).unwrap();
+        drop((buffer, pinned));
+        assert_eq!(pool.cached_bytes(), 8 << 20);
+
+        pool.release();
+        assert_eq!(pool.cached_bytes(), 0);
+        // A buffer in use is returned to the pool after a release too
+        let buffer = pool.alloc::<u64>(10).unwrap();
+        pool.release();
+        drop(buffer);
+        assert_eq!(pool.cached_bytes(), 80);
+    }
+}
@@ -1 +1,3 @@
+pub mod devices;
 pub mod field;
+pub mod memory;
@@ -0,0 +1,89 @@
+#ifndef short_weierstrass_cuh
+#define short_weierstrass_cuh
+
+/// Point of a short Weierstrass curve `y^2 = x^3 + b` in homogeneous projective coordinates
+/// `[x : y : z]`, laid out like lambdaworks' `ShortWeierstrassProjectivePoint`. The formulas
+/// are the ones of the CPU implementation with `a = 0`, which holds for BN254 and BLS12-381.
+template <class Fp>
+class ProjectivePoint
+{
+public:
+  Fp x;
+  Fp y;
+  Fp z;
+
+  __device__ static ProjectivePoint neutral_element()
+  {
+    ProjectivePoint r;
+    r.x = Fp::zero();
+    r.y = Fp::one();
+    r.z = Fp::zero();
+    return r;
+  }
+
+  __device__ bool is_neutral_element() const
+  {
+    return z.is_zero();
+  }
+
+  __device__ ProjectivePoint dbl() const
+  {
+    if (is_neutral_element())
+    {
+      return *this;
+    }
+    Fp px_square = x.square();
+    Fp w = px_square.dbl() + px_square;
+    Fp s = y * z;
+    Fp s_square = s.square();
+    Fp eight_s_cube = (s * s_square).dbl().dbl().dbl();
+    Fp four_b = (x * y * s).dbl().dbl();
+    Fp h = w.square() - four_b.dbl();
+    Fp eight_pys_square = (y.square() * s_square).dbl().dbl().dbl();
+
+    ProjectivePoint r;
+    r.x = (h * s).dbl();
+    r.y = w * (four_b - h) - eight_pys_square;
+    r.z = eight_s_cube;
+    return r;
+  }
+
+  __device__ ProjectivePoint operator+(const ProjectivePoint &other) const
+  {
+    if (other.is_neutral_element())
+    {
+      return *this;
+    }
+    if (is_neutral_element())
+    {
+      return other;
+    }
+    Fp u1 = other.y * z;
+    Fp u2 = y * other.z;
+    Fp v1 = other.x * z;
+    Fp v2 = x * other.z;
+    if (v1 == v2)
+    {
+      if (u1 != u2 || y.is_zero())
+      {
+        return neutral_element();
+      }
+      return dbl();
+    }
+    Fp u = u1 - u2;
+    Fp v = v1 - v2;
+    Fp w = z * other.z;
+    Fp v_square = v.square();
+    Fp v_cube = v * v_square;
+    Fp v_square_v2 = v_square * v2;
+    Fp a = u.square() * w - v_cube - v_square_v2.dbl();
+
+    ProjectivePoint r;
+    r.x = v * a;
+    r.y = u * (v_square_v2 - a) - v_cube * u2;
+    r.z = v_cube * w;
+    return r;
+  }
+};
+
+#endif
@@ -0,0 +1,119 @@
+#pragma once
+
+// In-place FFTs of several sequences of length `2^log_len` stored one after the other.
+// `twiddles` holds the first `2^(log_len - 1)` powers of the root of unity, in natural order.
+// The sequences are bit-reversed and then go through radix-4 stages, each one doing the work
+// of two radix-2 stages with a single pass over memory, and a last radix-2 stage if
+// `log_len` is odd.
+
+/// Reverses the `log_len` first bits of `i`
+inline __device__ unsigned reverse_bits(unsigned i, unsigned log_len)
+{
+    return log_len == 0 ? i : __brev(i) >> (32 - log_len);
+}
+
+template <class Fp>
+inline __device__ void _batch_bitrev_permutation(Fp *data,
+                                                 const unsigned log_len,
+                                                 const unsigned total)
+{
+    unsigned thread_pos = blockDim.x * blockIdx.x + threadIdx.x;
+    if (thread_pos >= total) return;
+
+    unsigned offset = thread_pos & ~((1u << log_len) - 1);
+    unsigned i = thread_pos - offset;
+    unsigned j = reverse_bits(i, log_len);
+    if (i < j)
+    {
+        Fp tmp = data[offset + i];
+        data[offset + i] = data[offset + j];
+        data[offset + j] = tmp;
+    }
+};
+
+template <class Fp>
+inline __device__ void _batch_radix2_dit(Fp *data,
+                                         const Fp *twiddles,
+                                         const unsigned log_len,
+                                         const unsigned stage,
+                                         const unsigned total_butterflies)
+{
+    unsigned thread_pos = blockDim.x * blockIdx.x + threadIdx.x;
+    if (thread_pos >= total_butterflies) return;
+
+    unsigned sequence = thread_pos >> (log_len - 1);
+    unsigned butterfly = thread_pos & ((1u << (log_len - 1)) - 1);
+    unsigned half_group_size = 1u << stage;
+    unsigned j = butterfly & (half_group_size - 1);
+    unsigned group = butterfly >> stage;
+    unsigned i = (sequence << log_len) + group * 2 * half_group_size + j;
+
+    Fp w = twiddles[j << (log_len - 1 - stage)];
+    Fp a = data[i];
+    Fp b = w * data[i + half_group_size];
+
+    data[i] = a + b;                   // --\/--
+    data[i + half_group_size] = a - b; // --/\--
+};
+
+/// Radix-2 stages `stage` and `stage + 1` at once, on groups of four elements.
+template <class Fp>
+inline __device__ void _batch_radix4_dit(Fp *data,
+                                         const Fp *twiddles,
+                                         const unsigned log_len,
+                                         const unsigned stage,
+                                         const unsigned total_quads)
+{
+    unsigned thread_pos = blockDim.x * blockIdx.x + threadIdx.x;
+    if (thread_pos >= total_quads) return;
+
+    unsigned sequence = thread_pos >> (log_len - 2);
+    unsigned quad = thread_pos & ((1u << (log_len - 2)) - 1);
+    unsigned h = 1u << stage;
+    unsigned j = quad & (h - 1);
+    unsigned group = quad >> stage;
+    unsigned i = (sequence << log_len) + group * 4 * h + j;
+
+    Fp a0 = data[i];
+    Fp a1 = data[i + h];
+    Fp a2 = data[i + 2 * h];
+    Fp a3 = data[i + 3 * h];
+
+    // Stage `stage`: butterflies (a0, a1) and (a2, a3)
+    Fp w1 = twiddles[j << (log_len - 1 - stage)];
+    Fp t1 = w1 * a1;
+    Fp t3 = w1 * a3;
+    Fp b0 = a0 + t1;
+    Fp b1 = a0 - t1;
+    Fp b2 = a2 + t3;
+    Fp b3 = a2 - t3;
+
+    // Stage `stage + 1`: butterflies (b0, b2) and (b1, b3)
+    Fp w2 = twiddles[j << (log_len - 2 - stage)];
+    Fp w3 = twiddles[(j + h) << (log_len - 2 - stage)];
+    Fp t2 = w2 * b2;
+    t3 = w3 * b3;
+
+    data[i] = b0 + t2;
+    data[i + h] = b1 + t3;
+    data[i + 2 * h] = b0 - t2;
+    data[i + 3 * h] = b1 - t3;
+};
+
+/// Multiplies the element `k` of the sequence `first_sequence + s` by `omega^((first_sequence + s) * k)`,
+/// the twiddle factors between the two passes of a four-step FFT.
+template <class Fp>
+inline __device__ void _four_step_twiddles(Fp *data,
+                                           const Fp *omega,
+                                           const unsigned log_len,
+                                           const unsigned first_sequence,
+                                           const unsigned total)
+{
+    unsigned thread_pos = blockDim.x * blockIdx.x + threadIdx.x;
+    if (thread_pos >= total) return;
+
+    unsigned sequence = first_sequence + (thread_pos >> log_len);
+    unsigned k = thread_pos & ((1u << log_len) - 1);
+    Fp w = *omega;
+    data[thread_pos] = data[thread_pos] * w.pow(sequence * k);
+};
@@ -0,0 +1,246 @@
+#ifndef fp_mont_cuh
+#define fp_mont_cuh
+
+/// Prime field in Montgomery form with `P::LIMBS` 64-bit limbs, `R = 2^(64 * LIMBS)`.
+///
+/// Limbs are stored most significant first, like lambdaworks' `UnsignedInteger`, so the
+/// values of `MontgomeryBackendPrimeField` elements are copied to the device as they are.
+/// `P` provides `LIMBS`, `MU = -N^{-1} mod 2^64` and the limbs of the modulus `N` and of
+/// `R mod N`, through `modulus(i)` and `one(i)`. The modulus must leave the top bit of the
+/// most significant limb free, so that the sum of two reduced values doesn't overflow.
+template <class P>
+class FpMont
+{
+public:
+  static constexpr unsigned LIMBS = P::LIMBS;
+  unsigned long long limbs[P::LIMBS];
+
+  __device__ static FpMont zero()
+  {
+    FpMont r;
+#pragma unroll
+    for (unsigned i = 0; i < LIMBS; i++)
+    {
+      r.limbs[i] = 0;
+    }
+    return r;
+  }
+
+  __device__ static FpMont one()
+  {
+    FpMont r;
+#pragma unroll
+    for (unsigned i = 0; i < LIMBS; i++)
+    {
+      r.limbs[i] = P::one(i);
+    }
+    return r;
+  }
+
+  __device__ bool is_zero() const
+  {
+    unsigned long long acc = 0;
+#pragma unroll
+    for (unsigned i = 0; i < LIMBS; i++)
+    {
+      acc |= limbs[i];
+    }
+    return acc == 0;
+  }
+
+  __device__ bool operator==(const FpMont &rhs) const
+  {
+#pragma unroll
+    for (unsigned i = 0; i < LIMBS; i++)
+    {
+      if (limbs[i] != rhs.limbs[i])
+      {
+        return false;
+      }
+    }
+    return true;
+  }
+
+  __device__ bool operator!=(const FpMont &rhs) const
+  {
+    return !(*this == rhs);
+  }
+
+  // Computes `self + rhs mod N`
+  __device__ FpMont operator+(const FpMont &rhs) const
+  {
+    FpMont r;
+    unsigned long long carry = 0;
+#pragma unroll
+    for (int i = LIMBS - 1; i >= 0; i--)
+    {
+      unsigned long long sum = limbs[i] + carry;
+      carry = sum < carry;
+      r.limbs[i] = sum + rhs.limbs[i];
+      carry += r.limbs[i] < sum;
+    }
+    r.reduce_once();
+    return r;
+  }
+
+  // Computes `self - rhs mod N`
+  __device__ FpMont operator-(const FpMont &rhs) const
+  {
+    FpMont r;
+    unsigned long long borrow = sub_limbs(r.limbs, limbs, rhs.limbs);
+    if (borrow)
+    {
+      unsigned long long carry = 0;
+#pragma unroll
+      for (int i = LIMBS - 1; i >= 0; i--)
+      {
+        unsigned long long sum = r.limbs[i] + carry;
+        carry = sum < carry;
+        r.limbs[i] = sum + P::modulus(i);
+        carry += r.limbs[i] < sum;
+      }
+    }
+    return r;
+  }
+
+  // Computes `self * rhs * R^{-1} mod N` with the coarsely integrated operand scanning (CIOS)
+  // Montgomery multiplication.
+  //
+  // Reference:
+  // - Koç, Acar, Kaliski, "Analyzing and Comparing Montgomery Multiplication Algorithms"
+  __device__ FpMont operator*(const FpMont &rhs) const
+  {
+    // Little endian accumulator
+    unsigned long long t[LIMBS + 2];
+#pragma unroll
+    for (unsigned i = 0; i < LIMBS + 2; i++)
+    {
+      t[i] = 0;
+    }
+
+#pragma unroll
+    for (unsigned i = 0; i < LIMBS; i++)
+    {
+      unsigned long long b_i = rhs.limbs[LIMBS - 1 - i];
+      unsigned long long carry = 0;
+#pragma unroll
+      for (unsigned j = 0; j < LIMBS; j++)
+      {
+        t[j] = mac(t[j], limbs[LIMBS - 1 - j], b_i, carry);
+      }
+      unsigned long long sum = t[LIMBS] + carry;
+      t[LIMBS + 1] = sum < carry;
+      t[LIMBS] = sum;
+
+      unsigned long long m = t[0] * P::MU;
+      carry = 0;
+      mac(t[0], m, P::modulus(LIMBS - 1), carry);
+#pragma unroll
+      for (unsigned j = 1; j < LIMBS; j++)
+      {
+        t[j - 1] = mac(t[j], m, P::modulus(LIMBS - 1 - j), carry);
+      }
+      sum = t[LIMBS] + carry;
+      t[LIMBS - 1] = sum;
+      t[LIMBS] = t[LIMBS + 1] + (sum < carry);
+    }
+
+    FpMont r;
+#pragma unroll
+    for (unsigned i = 0; i < LIMBS; i++)
+    {
+      r.limbs[LIMBS - 1 - i] = t[i];
+    }
+    if (t[LIMBS] != 0)
+    {
+      r.sub_modulus();
+    }
+    else
+    {
+      r.reduce_once();
+    }
+    return r;
+  }
+
+  __device__ FpMont square() const
+  {
+    return *this * *this;
+  }
+
+  __device__ FpMont dbl() const
+  {
+    return *this + *this;
+  }
+
+  __device__ FpMont neg() const
+  {
+    return zero() - *this;
+  }
+
+private:
+  // Returns the low limb of `a + b * c + carry` and leaves the high limb in `carry`
+  __device__ static inline unsigned long long mac(unsigned long long a,
+                                                  unsigned long long b,
+                                                  unsigned long long c,
+                                                  unsigned long long &carry)
+  {
+    unsigned long long low = b * c;
+    unsigned long long high = __umul64hi(b, c);
+    low += a;
+    high += low < a;
+    low += carry;
+    high += low < carry;
+    carry = high;
+    return low;
+  }
+
+  // Computes `r = a - b` and returns the borrow
+  __device__ static inline unsigned long long sub_limbs(unsigned long long *r,
+                                                        const unsigned long long *a,
+                                                        const unsigned long long *b)
+  {
+    unsigned long long borrow = 0;
+#pragma unroll
+    for (int i = LIMBS - 1; i >= 0; i--)
+    {
+      unsigned long long diff = a[i] - b[i];
+      unsigned long long next_borrow = diff > a[i];
+      r[i] = diff - borrow;
+      borrow = next_borrow | (r[i] > diff);
+    }
+    return borrow;
+  }
+
+  __device__ void sub_modulus()
+  {
+    unsigned long long n[LIMBS];
+#pragma unroll
+    for (unsigned i = 0; i < LIMBS; i++)
+    {
+      n[i] = P::modulus(i);
+    }
+    sub_limbs(limbs, limbs, n);
+  }
+
+  // Subtracts the modulus if `self >= N`
+  __device__ void reduce_once()
+  {
+#pragma unroll
+    for (unsigned i = 0; i < LIMBS; i++)
+    {
+      unsigned long long n = P::modulus(i);
+      if (limbs[i] != n)
+      {
+        if (limbs[i] > n)
+        {
+          sub_modulus();
+        }
+        return;
+      }
+    }
+    // Equal to the modulus
+    sub_modulus();
+  }
+};
+
+#endif
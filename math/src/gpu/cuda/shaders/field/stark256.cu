@@ -2,6 +2,9 @@
 #include "../fft/fft.cuh"
 #include "../fft/twiddles.cuh"
 #include "../fft/bitrev_permutation.cuh"
+#include "../fft/batch_fft.cuh"
+#include "../hash/poseidon.cuh"
+#include "../hash/poseidon2.cuh"
 #include "../utils.h"
 
 namespace p256
@@ -48,4 +51,94 @@ extern "C"
     ) {
         _bitrev_permutation<p256::Fp>(input, result, len);
     };
+
+    __global__ void batch_bitrev_permutation(p256::Fp *data,
+                                             const unsigned log_len,
+                                             const unsigned total)
+    {
+        _batch_bitrev_permutation<p256::Fp>(data, log_len, total);
+    };
+
+    __global__ void batch_radix2_dit(p256::Fp *data,
+                                     const p256::Fp *twiddles,
+                                     const unsigned log_len,
+                                     const unsigned stage,
+                                     const unsigned total_butterflies)
+    {
+        _batch_radix2_dit<p256::Fp>(data, twiddles, log_len, stage, total_butterflies);
+    };
+
+    __global__ void batch_radix4_dit(p256::Fp *data,
+                                     const p256::Fp *twiddles,
+                                     const unsigned log_len,
+                                     const unsigned stage,
+                                     const unsigned total_quads)
+    {
+        _batch_radix4_dit<p256::Fp>(data, twiddles, log_len, stage, total_quads);
+    };
+
+    __global__ void four_step_twiddles(p256::Fp *data,
+                                       const p256::Fp *omega,
+                                       const unsigned log_len,
+                                       const unsigned first_sequence,
+                                       const unsigned total)
+    {
+        _four_step_twiddles<p256::Fp>(data, omega, log_len, first_sequence, total);
+    };
+
+    __global__ void poseidon_hash_single(const p256::Fp *input,
+                                         p256::Fp *output,
+                                         const p256::Fp *round_constants,
+                                         const p256::Fp *one,
+                                         const unsigned count)
+    {
+        _poseidon_hash_single<p256::Fp>(input, output, round_constants, one, count);
+    };
+
+    __global__ void poseidon_hash_rows(const p256::Fp *rows,
+                                       p256::Fp *output,
+                                       const p256::Fp *round_constants,
+                                       const p256::Fp *one,
+                                       const unsigned row_len,
+                                       const unsigned count)
+    {
+        _poseidon_hash_rows<p256::Fp>(rows, output, round_constants, one, row_len, count);
+    };
+
+    __global__ void poseidon_merkle_level(p256::Fp *nodes,
+                                          const p256::Fp *round_constants,
+                                          const p256::Fp *one,
+                                          const unsigned first_parent,
+                                          const unsigned count)
+    {
+        _poseidon_merkle_level<p256::Fp>(nodes, round_constants, one, first_parent, count);
+    };
+
+    __global__ void poseidon2_hash_single(const p256::Fp *input,
+                                          p256::Fp *output,
+                                          const p256::Fp *round_constants,
+                                          const p256::Fp *one,
+                                          const unsigned count)
+    {
+        _poseidon2_hash_single<p256::Fp>(input, output, round_constants, one, count);
+    };
+
+    __global__ void poseidon2_hash_rows(const p256::Fp *rows,
+                                        p256::Fp *output,
+                                        const p256::Fp *round_constants,
+                                        const p256::Fp *one,
+                                        const unsigned row_len,
+                                        const unsigned count)
+    {
+        _poseidon2_hash_rows<p256::Fp>(rows, output, round_constants, one, row_len, count);
+    };
+
+    __global__ void poseidon2_merkle_level(p256::Fp *nodes,
+                                           const p256::Fp *round_constants,
+                                           const p256::Fp *one,
+                                           const unsigned first_parent,
+                                           const unsigned count)
+    {
+        _poseidon2_merkle_level<p256::Fp>(nodes, round_constants, one, first_parent, count);
+    };
 }
@@ -0,0 +1,110 @@
+#pragma once
+
+// Blake2s with a 32 bytes digest and no key, as `Blake2s256` of the `blake2` crate.
+
+#define BLAKE2S_BLOCK 64
+
+__device__ __constant__ const unsigned BLAKE2S_IV[8] = {
+    0x6a09e667, 0xbb67ae85, 0x3c6ef372, 0xa54ff53a,
+    0x510e527f, 0x9b05688c, 0x1f83d9ab, 0x5be0cd19};
+
+__device__ __constant__ const unsigned char BLAKE2S_SIGMA[10][16] = {
+    {0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15},
+    {14, 10, 4, 8, 9, 15, 13, 6, 1, 12, 0, 2, 11, 7, 5, 3},
+    {11, 8, 12, 0, 5, 2, 15, 13, 10, 14, 3, 6, 7, 1, 9, 4},
+    {7, 9, 3, 1, 13, 12, 11, 14, 2, 6, 5, 10, 4, 0, 15, 8},
+    {9, 0, 5, 7, 2, 4, 10, 15, 14, 1, 11, 12, 6, 8, 3, 13},
+    {2, 12, 6, 10, 0, 11, 8, 3, 4, 13, 7, 5, 15, 14, 1, 9},
+    {12, 5, 1, 15, 14, 13, 4, 10, 0, 7, 6, 3, 9, 2, 8, 11},
+    {13, 11, 7, 14, 12, 1, 3, 9, 5, 0, 15, 4, 8, 6, 2, 10},
+    {6, 15, 14, 9, 11, 3, 0, 8, 12, 2, 13, 7, 1, 4, 10, 5},
+    {10, 2, 8, 4, 7, 6, 1, 5, 15, 11, 9, 14, 3, 12, 13, 0}};
+
+inline __device__ unsigned blake2s_rotr(unsigned x, unsigned n)
+{
+    return (x >> n) | (x << (32 - n));
+}
+
+inline __device__ void blake2s_g(unsigned *v, unsigned a, unsigned b, unsigned c, unsigned d,
+                                 unsigned x, unsigned y)
+{
+    v[a] = v[a] + v[b] + x;
+    v[d] = blake2s_rotr(v[d] ^ v[a], 16);
+    v[c] = v[c] + v[d];
+    v[b] = blake2s_rotr(v[b] ^ v[c], 12);
+    v[a] = v[a] + v[b] + y;
+    v[d] = blake2s_rotr(v[d] ^ v[a], 8);
+    v[c] = v[c] + v[d];
+    v[b] = blake2s_rotr(v[b] ^ v[c], 7);
+}
+
+/// Compresses the 64 bytes of `block` into `h`, with `counter` bytes hashed so far
+inline __device__ void blake2s_compress(unsigned *h, const unsigned char *block,
+                                        unsigned long long counter, bool last)
+{
+    unsigned m[16];
+    for (unsigned i = 0; i < 16; i++)
+    {
+        m[i] = (unsigned)block[4 * i] | ((unsigned)block[4 * i + 1] << 8) |
+               ((unsigned)block[4 * i + 2] << 16) | ((unsigned)block[4 * i + 3] << 24);
+    }
+    unsigned v[16];
+    for (unsigned i = 0; i < 8; i++)
+    {
+        v[i] = h[i];
+        v[i + 8] = BLAKE2S_IV[i];
+    }
+    v[12] ^= (unsigned)counter;
+    v[13] ^= (unsigned)(counter >> 32);
+    if (last)
+    {
+        v[14] = ~v[14];
+    }
+    for (unsigned round = 0; round < 10; round++)
+    {
+        const unsigned char *s = BLAKE2S_SIGMA[round];
+        blake2s_g(v, 0, 4, 8, 12, m[s[0]], m[s[1]]);
+        blake2s_g(v, 1, 5, 9, 13, m[s[2]], m[s[3]]);
+        blake2s_g(v, 2, 6, 10, 14, m[s[4]], m[s[5]]);
+        blake2s_g(v, 3, 7, 11, 15, m[s[6]], m[s[7]]);
+        blake2s_g(v, 0, 5, 10, 15, m[s[8]], m[s[9]]);
+        blake2s_g(v, 1, 6, 11, 12, m[s[10]], m[s[11]]);
+        blake2s_g(v, 2, 7, 8, 13, m[s[12]], m[s[13]]);
+        blake2s_g(v, 3, 4, 9, 14, m[s[14]], m[s[15]]);
+    }
+    for (unsigned i = 0; i < 8; i++)
+    {
+        h[i] ^= v[i] ^ v[i + 8];
+    }
+}
+
+/// Writes the 32 bytes of the Blake2s256 hash of the `len` bytes of `input` to `output`
+inline __device__ void blake2s256(const unsigned char *input, unsigned len, unsigned char *output)
+{
+    unsigned h[8];
+    for (unsigned i = 0; i < 8; i++)
+    {
+        h[i] = BLAKE2S_IV[i];
+    }
+    // Parameter block: digest length 32, no key, fanout and depth 1
+    h[0] ^= 0x01010020;
+
+    // The last block, which may be partial or empty, is compressed with the final flag
+    unsigned offset = 0;
+    while (len - offset > BLAKE2S_BLOCK)
+    {
+        offset += BLAKE2S_BLOCK;
+        blake2s_compress(h, input + offset - BLAKE2S_BLOCK, offset, false);
+    }
+    unsigned char block[BLAKE2S_BLOCK] = {0};
+    for (unsigned i = offset; i < len; i++)
+    {
+        block[i - offset] = input[i];
+    }
+    blake2s_compress(h, block, len, true);
+
+    for (unsigned i = 0; i < 32; i++)
+    {
+        output[i] = (unsigned char)(h[i / 4] >> (8 * (i % 4)));
+    }
+}
@@ -0,0 +1,69 @@
+#include "./keccak.cuh"
+#include "./blake2s.cuh"
+
+// Batched hashing of Merkle leaves and nodes with byte-oriented hashes. Digests are 32 bytes.
+
+#define DIGEST_LEN 32
+
+/// `output[i] = hash(rows[i])`, for `count` rows of `row_len` bytes stored one after the other.
+template <void (*hash)(const unsigned char *, unsigned, unsigned char *)>
+inline __device__ void _hash_rows(const unsigned char *rows,
+                                  unsigned char *output,
+                                  const unsigned row_len,
+                                  const unsigned count)
+{
+    unsigned thread_pos = blockDim.x * blockIdx.x + threadIdx.x;
+    if (thread_pos >= count) return;
+
+    hash(rows + (unsigned long)thread_pos * row_len,
+         row_len,
+         output + (unsigned long)thread_pos * DIGEST_LEN);
+};
+
+/// Computes the nodes `[first_parent, first_parent + count)` of a Merkle tree stored in
+/// breadth-first order, where the children of node `k` are `2k + 1` and `2k + 2`. A parent is
+/// the hash of its children one after the other, which are contiguous.
+template <void (*hash)(const unsigned char *, unsigned, unsigned char *)>
+inline __device__ void _merkle_level(unsigned char *nodes,
+                                     const unsigned first_parent,
+                                     const unsigned count)
+{
+    unsigned thread_pos = blockDim.x * blockIdx.x + threadIdx.x;
+    if (thread_pos >= count) return;
+
+    unsigned long parent = first_parent + thread_pos;
+    hash(nodes + (2 * parent + 1) * DIGEST_LEN, 2 * DIGEST_LEN, nodes + parent * DIGEST_LEN);
+};
+
+extern "C"
+{
+    __global__ void keccak256_hash_rows(const unsigned char *rows,
+                                        unsigned char *output,
+                                        const unsigned row_len,
+                                        const unsigned count)
+    {
+        _hash_rows<keccak256>(rows, output, row_len, count);
+    };
+
+    __global__ void keccak256_merkle_level(unsigned char *nodes,
+                                           const unsigned first_parent,
+                                           const unsigned count)
+    {
+        _merkle_level<keccak256>(nodes, first_parent, count);
+    };
+
+    __global__ void blake2s256_hash_rows(const unsigned char *rows,
+                                         unsigned char *output,
+                                         const unsigned row_len,
+                                         const unsigned count)
+    {
+        _hash_rows<blake2s256>(rows, output, row_len, count);
+    };
+
+    __global__ void blake2s256_merkle_level(unsigned char *nodes,
+                                            const unsigned first_parent,
+                                            const unsigned count)
+    {
+        _merkle_level<blake2s256>(nodes, first_parent, count);
+    };
+}
@@ -0,0 +1,111 @@
+#pragma once
+
+// Poseidon hash of Starknet (`PoseidonCairoStark252`), with a state of three elements.
+// `round_constants` are the optimized ones: three per full round and one per partial round,
+// which is only added to the last element of the state. `one` is the element 1 of the field,
+// in the representation used by the kernels.
+
+#define POSEIDON_FULL_ROUNDS 8
+#define POSEIDON_PARTIAL_ROUNDS 83
+
+/// Multiplies the state by the MDS matrix `[[3, 1, 1], [1, -1, 1], [1, 1, -2]]`
+template <class Fp>
+inline __device__ void poseidon_mix(Fp *state)
+{
+    Fp t = state[0] + state[1] + state[2];
+    state[0] = t + state[0] + state[0];
+    state[1] = t - state[1] - state[1];
+    state[2] = t - state[2] - state[2] - state[2];
+}
+
+template <class Fp>
+inline __device__ void poseidon_full_round(Fp *state, const Fp *round_constants)
+{
+    for (unsigned i = 0; i < 3; i++)
+    {
+        Fp x = state[i] + round_constants[i];
+        state[i] = x * x * x;
+    }
+    poseidon_mix(state);
+}
+
+template <class Fp>
+inline __device__ void hades_permutation(Fp *state, const Fp *round_constants)
+{
+    const Fp *constant = round_constants;
+    for (unsigned r = 0; r < POSEIDON_FULL_ROUNDS / 2; r++, constant += 3)
+    {
+        poseidon_full_round(state, constant);
+    }
+    for (unsigned r = 0; r < POSEIDON_PARTIAL_ROUNDS; r++, constant++)
+    {
+        Fp x = state[2] + *constant;
+        state[2] = x * x * x;
+        poseidon_mix(state);
+    }
+    for (unsigned r = 0; r < POSEIDON_FULL_ROUNDS / 2; r++, constant += 3)
+    {
+        poseidon_full_round(state, constant);
+    }
+}
+
+/// `output[i] = hash_single(input[i])`
+template <class Fp>
+inline __device__ void _poseidon_hash_single(const Fp *input,
+                                             Fp *output,
+                                             const Fp *round_constants,
+                                             const Fp *one,
+                                             const unsigned count)
+{
+    unsigned thread_pos = blockDim.x * blockIdx.x + threadIdx.x;
+    if (thread_pos >= count) return;
+
+    Fp state[3] = {input[thread_pos], Fp(u256(0)), *one};
+    hades_permutation(state, round_constants);
+    output[thread_pos] = state[0];
+};
+
+/// `output[i] = hash_many(rows[i])`, for `count` rows of `row_len` elements stored one after
+/// the other. Rows are padded with a 1 and then zeros up to a multiple of the rate, 2.
+template <class Fp>
+inline __device__ void _poseidon_hash_rows(const Fp *rows,
+                                           Fp *output,
+                                           const Fp *round_constants,
+                                           const Fp *one,
+                                           const unsigned row_len,
+                                           const unsigned count)
+{
+    unsigned thread_pos = blockDim.x * blockIdx.x + threadIdx.x;
+    if (thread_pos >= count) return;
+
+    const Fp *row = rows + (unsigned long)thread_pos * row_len;
+    Fp zero = Fp(u256(0));
+    Fp state[3] = {zero, zero, zero};
+    for (unsigned i = 0; i <= row_len; i += 2)
+    {
+        Fp a = i < row_len ? row[i] : *one;
+        Fp b = i + 1 < row_len ? row[i + 1] : (i + 1 == row_len ? *one : zero);
+        state[0] = state[0] + a;
+        state[1] = state[1] + b;
+        hades_permutation(state, round_constants);
+    }
+    output[thread_pos] = state[0];
+};
+
+/// Computes the nodes `[first_parent, first_parent + count)` of a Merkle tree stored in
+/// breadth-first order, where the children of node `k` are `2k + 1` and `2k + 2`.
+template <class Fp>
+inline __device__ void _poseidon_merkle_level(Fp *nodes,
+                                              const Fp *round_constants,
+                                              const Fp *one,
+                                              const unsigned first_parent,
+                                              const unsigned count)
+{
+    unsigned thread_pos = blockDim.x * blockIdx.x + threadIdx.x;
+    if (thread_pos >= count) return;
+
+    unsigned parent = first_parent + thread_pos;
+    Fp state[3] = {nodes[2 * parent + 1], nodes[2 * parent + 2], *one + *one};
+    hades_permutation(state, round_constants);
+    nodes[parent] = state[0];
+};
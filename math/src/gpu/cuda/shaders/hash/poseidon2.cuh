@@ -0,0 +1,122 @@
+#pragma once
+
+// Poseidon2 over the Stark252 field (`Poseidon2Stark252`), with a state of three elements.
+// `round_constants` has three constants per full round and one per partial round, which is
+// only added to the first element of the state. `one` is the element 1 of the field, in the
+// representation used by the kernels.
+
+#define POSEIDON2_FULL_ROUNDS 8
+#define POSEIDON2_PARTIAL_ROUNDS 83
+
+/// Multiplies the state by the external matrix `circ(2, 1, 1)`
+template <class Fp>
+inline __device__ void poseidon2_external_mix(Fp *state)
+{
+    Fp t = state[0] + state[1] + state[2];
+    state[0] = state[0] + t;
+    state[1] = state[1] + t;
+    state[2] = state[2] + t;
+}
+
+/// Multiplies the state by the internal matrix `J + diag(1, 1, 2)`
+template <class Fp>
+inline __device__ void poseidon2_internal_mix(Fp *state)
+{
+    Fp t = state[0] + state[1] + state[2];
+    state[0] = state[0] + t;
+    state[1] = state[1] + t;
+    state[2] = state[2] + state[2] + t;
+}
+
+template <class Fp>
+inline __device__ void poseidon2_full_round(Fp *state, const Fp *round_constants)
+{
+    for (unsigned i = 0; i < 3; i++)
+    {
+        Fp x = state[i] + round_constants[i];
+        state[i] = x * x * x;
+    }
+    poseidon2_external_mix(state);
+}
+
+template <class Fp>
+inline __device__ void poseidon2_permutation(Fp *state, const Fp *round_constants)
+{
+    const Fp *constant = round_constants;
+    poseidon2_external_mix(state);
+    for (unsigned r = 0; r < POSEIDON2_FULL_ROUNDS / 2; r++, constant += 3)
+    {
+        poseidon2_full_round(state, constant);
+    }
+    for (unsigned r = 0; r < POSEIDON2_PARTIAL_ROUNDS; r++, constant++)
+    {
+        Fp x = state[0] + *constant;
+        state[0] = x * x * x;
+        poseidon2_internal_mix(state);
+    }
+    for (unsigned r = 0; r < POSEIDON2_FULL_ROUNDS / 2; r++, constant += 3)
+    {
+        poseidon2_full_round(state, constant);
+    }
+}
+
+/// `output[i] = hash_single(input[i])`
+template <class Fp>
+inline __device__ void _poseidon2_hash_single(const Fp *input,
+                                              Fp *output,
+                                              const Fp *round_constants,
+                                              const Fp *one,
+                                              const unsigned count)
+{
+    unsigned thread_pos = blockDim.x * blockIdx.x + threadIdx.x;
+    if (thread_pos >= count) return;
+
+    Fp state[3] = {input[thread_pos], Fp(u256(0)), *one};
+    poseidon2_permutation(state, round_constants);
+    output[thread_pos] = state[0];
+};
+
+/// `output[i] = hash_many(rows[i])`, for `count` rows of `row_len` elements stored one after
+/// the other. Rows are padded with a 1 and then zeros up to a multiple of the rate, 2.
+template <class Fp>
+inline __device__ void _poseidon2_hash_rows(const Fp *rows,
+                                            Fp *output,
+                                            const Fp *round_constants,
+                                            const Fp *one,
+                                            const unsigned row_len,
+                                            const unsigned count)
+{
+    unsigned thread_pos = blockDim.x * blockIdx.x + threadIdx.x;
+    if (thread_pos >= count) return;
+
+    const Fp *row = rows + (unsigned long)thread_pos * row_len;
+    Fp zero = Fp(u256(0));
+    Fp state[3] = {zero, zero, zero};
+    for (unsigned i = 0; i <= row_len; i += 2)
+    {
+        Fp a = i < row_len ? row[i] : *one;
+        Fp b = i + 1 < row_len ? row[i + 1] : (i + 1 == row_len ? *one : zero);
+        state[0] = state[0] + a;
+        state[1] = state[1] + b;
+        poseidon2_permutation(state, round_constants);
+    }
+    output[thread_pos] = state[0];
+};
+
+/// Computes the nodes `[first_parent, first_parent + count)` of a Merkle tree stored in
+/// breadth-first order, where the children of node `k` are `2k + 1` and `2k + 2`.
+template <class Fp>
+inline __device__ void _poseidon2_merkle_level(Fp *nodes,
+                                               const Fp *round_constants,
+                                               const Fp *one,
+                                               const unsigned first_parent,
+                                               const unsigned count)
+{
+    unsigned thread_pos = blockDim.x * blockIdx.x + threadIdx.x;
+    if (thread_pos >= count) return;
+
+    unsigned parent = first_parent + thread_pos;
+    Fp state[3] = {nodes[2 * parent + 1], nodes[2 * parent + 2], *one + *one};
+    poseidon2_permutation(state, round_constants);
+    nodes[parent] = state[0];
+};
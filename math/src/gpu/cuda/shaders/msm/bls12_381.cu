@@ -0,0 +1,97 @@
+#include "../field/fp_mont.cuh"
+#include "../curve/short_weierstrass.cuh"
+#include "./msm.cuh"
+
+namespace bls12_381
+{
+    // BLS12-381 base field, most significant limb first
+    __constant__ unsigned long long MODULUS[6] = {
+        0x1a0111ea397fe69a,
+        0x4b1ba7b6434bacd7,
+        0x64774b84f38512bf,
+        0x6730d2a0f6b0f624,
+        0x1eabfffeb153ffff,
+        0xb9feffffffffaaab};
+    // R mod N
+    __constant__ unsigned long long ONE[6] = {
+        0x15f65ec3fa80e493,
+        0x5c071a97a256ec6d,
+        0x77ce585370525745,
+        0x5f48985753c758ba,
+        0xebf4000bc40c0002,
+        0x760900000002fffd};
+
+    struct FpParams
+    {
+        static constexpr unsigned LIMBS = 6;
+        static constexpr unsigned long long MU = 0x89f3fffcfffcfffd;
+
+        __device__ static unsigned long long modulus(unsigned i) { return MODULUS[i]; }
+        __device__ static unsigned long long one(unsigned i) { return ONE[i]; }
+    };
+
+    using Fp = FpMont<FpParams>;
+    using Point = ProjectivePoint<Fp>;
+} // namespace bls12_381
+
+extern "C"
+{
+    __global__ void msm_clear_counts(unsigned *counts, const unsigned len)
+    {
+        _msm_clear_counts(counts, len);
+    }
+
+    __global__ void msm_count_buckets(const unsigned long long *scalars,
+                                      const unsigned scalar_limbs,
+                                      const unsigned len,
+                                      const unsigned window_size,
+                                      const unsigned num_windows,
+                                      unsigned *counts)
+    {
+        _msm_count_buckets(scalars, scalar_limbs, len, window_size, num_windows, counts);
+    }
+
+    __global__ void msm_bucket_offsets(const unsigned *counts,
+                                       const unsigned window_size,
+                                       const unsigned num_windows,
+                                       const unsigned len,
+                                       unsigned *starts,
+                                       unsigned *cursor)
+    {
+        _msm_bucket_offsets(counts, window_size, num_windows, len, starts, cursor);
+    }
+
+    __global__ void msm_scatter(const unsigned long long *scalars,
+                                const unsigned scalar_limbs,
+                                const unsigned len,
+                                const unsigned window_size,
+                                const unsigned num_windows,
+                                unsigned *cursor,
+                                unsigned *indices)
+    {
+        _msm_scatter(scalars, scalar_limbs, len, window_size, num_windows, cursor, indices);
+    }
+
+    __global__ void msm_init_buckets(bls12_381::Point *buckets, const unsigned len)
+    {
+        _msm_init_buckets<bls12_381::Point>(buckets, len);
+    }
+
+    __global__ void msm_accumulate_buckets(const bls12_381::Point *points,
+                                           const unsigned *indices,
+                                           const unsigned *starts,
+                                           const unsigned *counts,
+                                           const unsigned total_buckets,
+                                           bls12_381::Point *buckets)
+    {
+        _msm_accumulate_buckets<bls12_381::Point>(points, indices, starts, counts, total_buckets, buckets);
+    }
+
+    __global__ void msm_reduce_windows(const bls12_381::Point *buckets,
+                                       const unsigned window_size,
+                                       const unsigned num_windows,
+                                       bls12_381::Point *window_sums)
+    {
+        _msm_reduce_windows<bls12_381::Point>(buckets, window_size, num_windows, window_sums);
+    }
+}
@@ -0,0 +1,93 @@
+#include "../field/fp_mont.cuh"
+#include "../curve/short_weierstrass.cuh"
+#include "./msm.cuh"
+
+namespace bn254
+{
+    // BN254 base field, most significant limb first
+    __constant__ unsigned long long MODULUS[4] = {
+        0x30644e72e131a029,
+        0xb85045b68181585d,
+        0x97816a916871ca8d,
+        0x3c208c16d87cfd47};
+    // R mod N
+    __constant__ unsigned long long ONE[4] = {
+        0x0e0a77c19a07df2f,
+        0x666ea36f7879462c,
+        0x0a78eb28f5c70b3d,
+        0xd35d438dc58f0d9d};
+
+    struct FpParams
+    {
+        static constexpr unsigned LIMBS = 4;
+        static constexpr unsigned long long MU = 0x87d20782e4866389;
+
+        __device__ static unsigned long long modulus(unsigned i) { return MODULUS[i]; }
+        __device__ static unsigned long long one(unsigned i) { return ONE[i]; }
+    };
+
+    using Fp = FpMont<FpParams>;
+    using Point = ProjectivePoint<Fp>;
+} // namespace bn254
+
+extern "C"
+{
+    __global__ void msm_clear_counts(unsigned *counts, const unsigned len)
+    {
+        _msm_clear_counts(counts, len);
+    }
+
+    __global__ void msm_count_buckets(const unsigned long long *scalars,
+                                      const unsigned scalar_limbs,
+                                      const unsigned len,
+                                      const unsigned window_size,
+                                      const unsigned num_windows,
+                                      unsigned *counts)
+    {
+        _msm_count_buckets(scalars, scalar_limbs, len, window_size, num_windows, counts);
+    }
+
+    __global__ void msm_bucket_offsets(const unsigned *counts,
+                                       const unsigned window_size,
+                                       const unsigned num_windows,
+                                       const unsigned len,
+                                       unsigned *starts,
+                                       unsigned *cursor)
+    {
+        _msm_bucket_offsets(counts, window_size, num_windows, len, starts, cursor);
+    }
+
+    __global__ void msm_scatter(const unsigned long long *scalars,
+                                const unsigned scalar_limbs,
+                                const unsigned len,
+                                const unsigned window_size,
+                                const unsigned num_windows,
+                                unsigned *cursor,
+                                unsigned *indices)
+    {
+        _msm_scatter(scalars, scalar_limbs, len, window_size, num_windows, cursor, indices);
+    }
+
+    __global__ void msm_init_buckets(bn254::Point *buckets, const unsigned len)
+    {
+        _msm_init_buckets<bn254::Point>(buckets, len);
+    }
+
+    __global__ void msm_accumulate_buckets(const bn254::Point *points,
+                                           const unsigned *indices,
+                                           const unsigned *starts,
+                                           const unsigned *counts,
+                                           const unsigned total_buckets,
+                                           bn254::Point *buckets)
+    {
+        _msm_accumulate_buckets<bn254::Point>(points, indices, starts, counts, total_buckets, buckets);
+    }
+
+    __global__ void msm_reduce_windows(const bn254::Point *buckets,
+                                       const unsigned window_size,
+                                       const unsigned num_windows,
+                                       bn254::Point *window_sums)
+    {
+        _msm_reduce_windows<bn254::Point>(buckets, window_size, num_windows, window_sums);
+    }
+}
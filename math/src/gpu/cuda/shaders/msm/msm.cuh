@@ -0,0 +1,174 @@
+#ifndef msm_cuh
+#define msm_cuh
+
+// Bucket method for multi-scalar multiplication. Scalars are split in windows of
+// `window_size` bits, and each window has one bucket per nonzero digit. The points are
+// sorted by bucket with a counting sort (`count_buckets`, `bucket_offsets`, `scatter`), so
+// that every bucket is accumulated by a single thread without atomics on points. The
+// buckets of each window are then reduced to `sum_d d * bucket_d` with a running sum.
+//
+// Bucket `d` of window `w` is stored at `w * num_buckets + d - 1`, with
+// `num_buckets = 2^window_size - 1`. The point indices of window `w` are stored in
+// `[w * len, (w + 1) * len)`, as every point falls in at most one bucket per window.
+
+/// Returns the digit of `window_size` bits starting at `bit` of a scalar with `scalar_limbs`
+/// 64-bit limbs, most significant first.
+__device__ unsigned scalar_window(const unsigned long long *scalar,
+                                  unsigned scalar_limbs,
+                                  unsigned bit,
+                                  unsigned window_size)
+{
+  unsigned limb = bit / 64;
+  unsigned offset = bit % 64;
+  if (limb >= scalar_limbs)
+  {
+    return 0;
+  }
+  unsigned long long word = scalar[scalar_limbs - 1 - limb] >> offset;
+  if (offset + window_size > 64 && limb + 1 < scalar_limbs)
+  {
+    word |= scalar[scalar_limbs - 2 - limb] << (64 - offset);
+  }
+  return (unsigned)(word & ((1ull << window_size) - 1));
+}
+
+__device__ void _msm_clear_counts(unsigned *counts, const unsigned len)
+{
+  unsigned i = blockIdx.x * blockDim.x + threadIdx.x;
+  if (i < len)
+  {
+    counts[i] = 0;
+  }
+}
+
+__device__ void _msm_count_buckets(const unsigned long long *scalars,
+                                   const unsigned scalar_limbs,
+                                   const unsigned len,
+                                   const unsigned window_size,
+                                   const unsigned num_windows,
+                                   unsigned *counts)
+{
+  unsigned i = blockIdx.x * blockDim.x + threadIdx.x;
+  if (i >= len)
+  {
+    return;
+  }
+  unsigned num_buckets = (1u << window_size) - 1;
+  const unsigned long long *scalar = scalars + i * scalar_limbs;
+  for (unsigned w = 0; w < num_windows; w++)
+  {
+    unsigned digit = scalar_window(scalar, scalar_limbs, w * window_size, window_size);
+    if (digit != 0)
+    {
+      atomicAdd(&counts[w * num_buckets + digit - 1], 1u);
+    }
+  }
+}
+
+/// Exclusive prefix sum of the bucket sizes, one thread per window.
+__device__ void _msm_bucket_offsets(const unsigned *counts,
+                                    const unsigned window_size,
+                                    const unsigned num_windows,
+                                    const unsigned len,
+                                    unsigned *starts,
+                                    unsigned *cursor)
+{
+  unsigned w = blockIdx.x * blockDim.x + threadIdx.x;
+  if (w >= num_windows)
+  {
+    return;
+  }
+  unsigned num_buckets = (1u << window_size) - 1;
+  unsigned offset = w * len;
+  for (unsigned b = w * num_buckets; b < (w + 1) * num_buckets; b++)
+  {
+    starts[b] = offset;
+    cursor[b] = offset;
+    offset += counts[b];
+  }
+}
+
+__device__ void _msm_scatter(const unsigned long long *scalars,
+                             const unsigned scalar_limbs,
+                             const unsigned len,
+                             const unsigned window_size,
+                             const unsigned num_windows,
+                             unsigned *cursor,
+                             unsigned *indices)
+{
+  unsigned i = blockIdx.x * blockDim.x + threadIdx.x;
+  if (i >= len)
+  {
+    return;
+  }
+  unsigned num_buckets = (1u << window_size) - 1;
+  const unsigned long long *scalar = scalars + i * scalar_limbs;
+  for (unsigned w = 0; w < num_windows; w++)
+  {
+    unsigned digit = scalar_window(scalar, scalar_limbs, w * window_size, window_size);
+    if (digit != 0)
+    {
+      unsigned position = atomicAdd(&cursor[w * num_buckets + digit - 1], 1u);
+      indices[position] = i;
+    }
+  }
+}
+
+template <class Point>
+__device__ void _msm_init_buckets(Point *buckets, const unsigned len)
+{
+  unsigned i = blockIdx.x * blockDim.x + threadIdx.x;
+  if (i < len)
+  {
+    buckets[i] = Point::neutral_element();
+  }
+}
+
+/// Adds the points of each bucket to it, one thread per bucket. Buckets keep their value
+/// between calls, so the points can be sent in several chunks.
+template <class Point>
+__device__ void _msm_accumulate_buckets(const Point *points,
+                                        const unsigned *indices,
+                                        const unsigned *starts,
+                                        const unsigned *counts,
+                                        const unsigned total_buckets,
+                                        Point *buckets)
+{
+  unsigned b = blockIdx.x * blockDim.x + threadIdx.x;
+  if (b >= total_buckets)
+  {
+    return;
+  }
+  Point acc = buckets[b];
+  unsigned end = starts[b] + counts[b];
+  for (unsigned k = starts[b]; k < end; k++)
+  {
+    acc = acc + points[indices[k]];
+  }
+  buckets[b] = acc;
+}
+
+/// Computes `sum_d d * bucket_d` for each window, one thread per window.
+template <class Point>
+__device__ void _msm_reduce_windows(const Point *buckets,
+                                    const unsigned window_size,
+                                    const unsigned num_windows,
+                                    Point *window_sums)
+{
+  unsigned w = blockIdx.x * blockDim.x + threadIdx.x;
+  if (w >= num_windows)
+  {
+    return;
+  }
+  unsigned num_buckets = (1u << window_size) - 1;
+  Point running = Point::neutral_element();
+  Point total = Point::neutral_element();
+  for (int d = num_buckets - 1; d >= 0; d--)
+  {
+    running = running + buckets[w * num_buckets + d];
+    total = total + running;
+  }
+  window_sums[w] = total;
+}
+
+#endif // msm_cuh
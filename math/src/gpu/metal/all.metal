@@ -5,3 +5,6 @@
 // than headers and a unique source.
 
 #include "shaders/field/stark256.h.metal"
+#include "shaders/msm/bn254.h.metal"
+#include "shaders/msm/bls12_381.h.metal"
+#include "shaders/hash/bytes.h.metal"
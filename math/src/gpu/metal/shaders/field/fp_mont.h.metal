@@ -0,0 +1,228 @@
+#ifndef fp_mont_h
+#define fp_mont_h
+
+#include <metal_stdlib>
+
+/// Prime field in Montgomery form with `LIMBS` 64-bit limbs, `R = 2^(64 * LIMBS)`.
+///
+/// Limbs are stored most significant first, like lambdaworks' `UnsignedInteger`, so the
+/// values of `MontgomeryBackendPrimeField` elements are copied to the device as they are.
+/// `P` provides `mu() = -N^{-1} mod 2^64` and the limbs of the modulus `N` and of `R mod N`,
+/// through `modulus(i)` and `one(i)`. The modulus must leave the top bit of the most
+/// significant limb free, so that the sum of two reduced values doesn't overflow.
+template <uint LIMBS, class P>
+class FpMont
+{
+public:
+    ulong limbs[LIMBS];
+
+    static FpMont zero()
+    {
+        FpMont r;
+        for (uint i = 0; i < LIMBS; i++)
+        {
+            r.limbs[i] = 0;
+        }
+        return r;
+    }
+
+    static FpMont one()
+    {
+        FpMont r;
+        for (uint i = 0; i < LIMBS; i++)
+        {
+            r.limbs[i] = P::one(i);
+        }
+        return r;
+    }
+
+    bool is_zero() const
+    {
+        ulong acc = 0;
+        for (uint i = 0; i < LIMBS; i++)
+        {
+            acc |= limbs[i];
+        }
+        return acc == 0;
+    }
+
+    bool operator==(const FpMont rhs) const
+    {
+        for (uint i = 0; i < LIMBS; i++)
+        {
+            if (limbs[i] != rhs.limbs[i])
+            {
+                return false;
+            }
+        }
+        return true;
+    }
+
+    bool operator!=(const FpMont rhs) const
+    {
+        return !(*this == rhs);
+    }
+
+    // Computes `self + rhs mod N`
+    FpMont operator+(const FpMont rhs) const
+    {
+        FpMont r;
+        ulong carry = 0;
+        for (int i = LIMBS - 1; i >= 0; i--)
+        {
+            ulong sum = limbs[i] + carry;
+            carry = sum < carry;
+            r.limbs[i] = sum + rhs.limbs[i];
+            carry += r.limbs[i] < sum;
+        }
+        r.reduce_once();
+        return r;
+    }
+
+    // Computes `self - rhs mod N`
+    FpMont operator-(const FpMont rhs) const
+    {
+        FpMont r;
+        ulong borrow = sub_limbs(r.limbs, limbs, rhs.limbs);
+        if (borrow)
+        {
+            ulong carry = 0;
+            for (int i = LIMBS - 1; i >= 0; i--)
+            {
+                ulong sum = r.limbs[i] + carry;
+                carry = sum < carry;
+                r.limbs[i] = sum + P::modulus(i);
+                carry += r.limbs[i] < sum;
+            }
+        }
+        return r;
+    }
+
+    // Computes `self * rhs * R^{-1} mod N` with the coarsely integrated operand scanning (CIOS)
+    // Montgomery multiplication.
+    //
+    // Reference:
+    // - Koç, Acar, Kaliski, "Analyzing and Comparing Montgomery Multiplication Algorithms"
+    FpMont operator*(const FpMont rhs) const
+    {
+        // Little endian accumulator
+        ulong t[LIMBS + 2];
+        for (uint i = 0; i < LIMBS + 2; i++)
+        {
+            t[i] = 0;
+        }
+
+        for (uint i = 0; i < LIMBS; i++)
+        {
+            ulong b_i = rhs.limbs[LIMBS - 1 - i];
+            ulong carry = 0;
+            for (uint j = 0; j < LIMBS; j++)
+            {
+                t[j] = mac(t[j], limbs[LIMBS - 1 - j], b_i, carry);
+            }
+            ulong sum = t[LIMBS] + carry;
+            t[LIMBS + 1] = sum < carry;
+            t[LIMBS] = sum;
+
+            ulong m = t[0] * P::mu();
+            carry = 0;
+            mac(t[0], m, P::modulus(LIMBS - 1), carry);
+            for (uint j = 1; j < LIMBS; j++)
+            {
+                t[j - 1] = mac(t[j], m, P::modulus(LIMBS - 1 - j), carry);
+            }
+            sum = t[LIMBS] + carry;
+            t[LIMBS - 1] = sum;
+            t[LIMBS] = t[LIMBS + 1] + (sum < carry);
+        }
+
+        FpMont r;
+        for (uint i = 0; i < LIMBS; i++)
+        {
+            r.limbs[LIMBS - 1 - i] = t[i];
+        }
+        if (t[LIMBS] != 0)
+        {
+            r.sub_modulus();
+        }
+        else
+        {
+            r.reduce_once();
+        }
+        return r;
+    }
+
+    FpMont square() const
+    {
+        return *this * *this;
+    }
+
+    FpMont dbl() const
+    {
+        return *this + *this;
+    }
+
+    FpMont neg() const
+    {
+        return zero() - *this;
+    }
+
+private:
+    // Returns the low limb of `a + b * c + carry` and leaves the high limb in `carry`
+    static inline ulong mac(ulong a, ulong b, ulong c, thread ulong &carry)
+    {
+        ulong low = b * c;
+        ulong high = metal::mulhi(b, c);
+        low += a;
+        high += low < a;
+        low += carry;
+        high += low < carry;
+        carry = high;
+        return low;
+    }
+
+    // Computes `r = a - b` and returns the borrow
+    static inline ulong sub_limbs(thread ulong *r, const thread ulong *a, const thread ulong *b)
+    {
+        ulong borrow = 0;
+        for (int i = LIMBS - 1; i >= 0; i--)
+        {
+            ulong diff = a[i] - b[i];
+            ulong next_borrow = diff > a[i];
+            r[i] = diff - borrow;
+            borrow = next_borrow | (r[i] > diff);
+        }
+        return borrow;
+    }
+
+    void sub_modulus()
+    {
+        ulong n[LIMBS];
+        for (uint i = 0; i < LIMBS; i++)
+        {
+            n[i] = P::modulus(i);
+        }
+        sub_limbs(limbs, limbs, n);
+    }
+
+    // Subtracts the modulus if `self >= N`
+    void reduce_once()
+    {
+        for (uint i = 0; i < LIMBS; i++)
+        {
+            ulong n = P::modulus(i);
+            if (limbs[i] != n)
+            {
+                if (limbs[i] > n)
+                {
+                    sub_modulus();
+                }
+                return;
+            }
+        }
+        // Equal to the modulus
+        sub_modulus();
+    }
+};
+
+#endif // fp_mont_h
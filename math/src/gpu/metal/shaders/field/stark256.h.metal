@@ -5,6 +5,8 @@
 #include "../fft/fft.h.metal"
 #include "../fft/twiddles.h.metal"
 #include "../fft/permutation.h.metal"
+#include "../hash/poseidon.h.metal"
+#include "../hash/poseidon2.h.metal"
 
 // Prime Field of U256 with modulus 0x800000000000011000000000000000000000000000000000000000000000001, used for Starks
 namespace {
@@ -61,3 +63,59 @@ template [[ host_name("bitrev_permutation_stark256") ]]
     uint, 
     uint
 );
+
+template [[ host_name("poseidon_hash_single_stark256") ]]
+[[kernel]] void poseidon_hash_single<Fp>(
+    device const Fp*,
+    device Fp*,
+    constant Fp*,
+    constant Fp&,
+    uint32_t
+);
+
+template [[ host_name("poseidon_hash_rows_stark256") ]]
+[[kernel]] void poseidon_hash_rows<Fp>(
+    device const Fp*,
+    device Fp*,
+    constant Fp*,
+    constant Fp&,
+    constant uint32_t&,
+    uint32_t
+);
+
+template [[ host_name("poseidon_merkle_level_stark256") ]]
+[[kernel]] void poseidon_merkle_level<Fp>(
+    device Fp*,
+    constant Fp*,
+    constant Fp&,
+    constant uint32_t&,
+    uint32_t
+);
+
+template [[ host_name("poseidon2_hash_single_stark256") ]]
+[[kernel]] void poseidon2_hash_single<Fp>(
+    device const Fp*,
+    device Fp*,
+    constant Fp*,
+    constant Fp&,
+    uint32_t
+);
+
+template [[ host_name("poseidon2_hash_rows_stark256") ]]
+[[kernel]] void poseidon2_hash_rows<Fp>(
+    device const Fp*,
+    device Fp*,
+    constant Fp*,
+    constant Fp&,
+    constant uint32_t&,
+    uint32_t
+);
+
+template [[ host_name("poseidon2_merkle_level_stark256") ]]
+[[kernel]] void poseidon2_merkle_level<Fp>(
+    device Fp*,
+    constant Fp*,
+    constant Fp&,
+    constant uint32_t&,
+    uint32_t
+);
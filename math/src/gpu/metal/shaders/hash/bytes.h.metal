@@ -0,0 +1,53 @@
+#pragma once
+
+#include <metal_stdlib>
+#include "keccak.h.metal"
+#include "blake2s.h.metal"
+
+// Batched hashing of Merkle leaves and nodes with byte-oriented hashes. Digests are 32 bytes.
+// Leaves are `rows` of `row_len` bytes stored one after the other, and `output[i]` is the hash
+// of row `i`. Merkle levels compute the parents `[first_parent, first_parent + threads)` of a
+// tree stored in breadth-first order, where the children of node `k` are `2k + 1` and `2k + 2`.
+// A parent is the hash of its children one after the other, which are contiguous.
+
+constant uint DIGEST_LEN = 32;
+
+[[kernel]] void keccak256_hash_rows(
+    device const uchar* rows        [[ buffer(0) ]],
+    device uchar* output            [[ buffer(1) ]],
+    constant uint32_t& row_len      [[ buffer(2) ]],
+    uint32_t thread_pos             [[ thread_position_in_grid ]]
+)
+{
+    keccak256(rows + (ulong)thread_pos * row_len, row_len, output + (ulong)thread_pos * DIGEST_LEN);
+}
+
+[[kernel]] void keccak256_merkle_level(
+    device uchar* nodes             [[ buffer(0) ]],
+    constant uint32_t& first_parent [[ buffer(1) ]],
+    uint32_t thread_pos             [[ thread_position_in_grid ]]
+)
+{
+    ulong parent = first_parent + thread_pos;
+    keccak256(nodes + (2 * parent + 1) * DIGEST_LEN, 2 * DIGEST_LEN, nodes + parent * DIGEST_LEN);
+}
+
+[[kernel]] void blake2s256_hash_rows(
+    device const uchar* rows        [[ buffer(0) ]],
+    device uchar* output            [[ buffer(1) ]],
+    constant uint32_t& row_len      [[ buffer(2) ]],
+    uint32_t thread_pos             [[ thread_position_in_grid ]]
+)
+{
+    blake2s256(rows + (ulong)thread_pos * row_len, row_len, output + (ulong)thread_pos * DIGEST_LEN);
+}
+
+[[kernel]] void blake2s256_merkle_level(
+    device uchar* nodes             [[ buffer(0) ]],
+    constant uint32_t& first_parent [[ buffer(1) ]],
+    uint32_t thread_pos             [[ thread_position_in_grid ]]
+)
+{
+    ulong parent = first_parent + thread_pos;
+    blake2s256(nodes + (2 * parent + 1) * DIGEST_LEN, 2 * DIGEST_LEN, nodes + parent * DIGEST_LEN);
+}
@@ -0,0 +1,90 @@
+#pragma once
+
+#include <metal_stdlib>
+
+// Keccak256 as used by Ethereum and Stone: Keccak-f[1600] with a rate of 136 bytes and the
+// original padding (0x01 ... 0x80), not the SHA3 one.
+
+constant uint KECCAK_ROUNDS = 24;
+constant uint KECCAK_RATE = 136;
+
+constant ulong KECCAK_ROUND_CONSTANTS[24] = {
+    0x0000000000000001, 0x0000000000008082, 0x800000000000808a, 0x8000000080008000,
+    0x000000000000808b, 0x0000000080000001, 0x8000000080008081, 0x8000000000008009,
+    0x000000000000008a, 0x0000000000000088, 0x0000000080008009, 0x000000008000000a,
+    0x000000008000808b, 0x800000000000008b, 0x8000000000008089, 0x8000000000008003,
+    0x8000000000008002, 0x8000000000000080, 0x000000000000800a, 0x800000008000000a,
+    0x8000000080008081, 0x8000000000008080, 0x0000000080000001, 0x8000000080008008
+};
+
+// Rotation of each lane in the order visited by the rho and pi steps
+constant uint KECCAK_ROTATIONS[24] = {
+    1, 3, 6, 10, 15, 21, 28, 36, 45, 55, 2, 14, 27, 41, 56, 8, 25, 43, 62, 18, 39, 61, 20, 44
+};
+
+constant uint KECCAK_PI_LANES[24] = {
+    10, 7, 11, 17, 18, 3, 5, 16, 8, 21, 24, 4, 15, 23, 19, 13, 12, 2, 20, 14, 22, 9, 6, 1
+};
+
+inline ulong keccak_rotl(ulong x, uint n)
+{
+    return (x << n) | (x >> (64 - n));
+}
+
+void keccak_f(thread ulong* state)
+{
+    ulong c[5];
+    for (uint round = 0; round < KECCAK_ROUNDS; round++)
+    {
+        // theta
+        for (uint x = 0; x < 5; x++) {
+            c[x] = state[x] ^ state[x + 5] ^ state[x + 10] ^ state[x + 15] ^ state[x + 20];
+        }
+        for (uint x = 0; x < 5; x++) {
+            ulong d = c[(x + 4) % 5] ^ keccak_rotl(c[(x + 1) % 5], 1);
+            for (uint y = 0; y < 25; y += 5) {
+                state[y + x] ^= d;
+            }
+        }
+        // rho and pi
+        ulong current = state[1];
+        for (uint i = 0; i < 24; i++) {
+            uint lane = KECCAK_PI_LANES[i];
+            ulong next = state[lane];
+            state[lane] = keccak_rotl(current, KECCAK_ROTATIONS[i]);
+            current = next;
+        }
+        // chi
+        for (uint y = 0; y < 25; y += 5) {
+            for (uint x = 0; x < 5; x++) {
+                c[x] = state[y + x];
+            }
+            for (uint x = 0; x < 5; x++) {
+                state[y + x] = c[x] ^ (~c[(x + 1) % 5] & c[(x + 2) % 5]);
+            }
+        }
+        // iota
+        state[0] ^= KECCAK_ROUND_CONSTANTS[round];
+    }
+}
+
+/// Writes the 32 bytes of the Keccak256 hash of the `len` bytes of `input` to `output`
+void keccak256(device const uchar* input, uint len, device uchar* output)
+{
+    ulong state[25] = {0};
+    uint absorbed = 0;
+    for (uint i = 0; i < len; i++) {
+        state[absorbed / 8] ^= (ulong)input[i] << (8 * (absorbed % 8));
+        if (++absorbed == KECCAK_RATE) {
+            keccak_f(state);
+            absorbed = 0;
+        }
+    }
+    state[absorbed / 8] ^= (ulong)0x01 << (8 * (absorbed % 8));
+    state[(KECCAK_RATE - 1) / 8] ^= (ulong)0x80 << (8 * ((KECCAK_RATE - 1) % 8));
+    keccak_f(state);
+
+    for (uint i = 0; i < 32; i++) {
+        output[i] = (uchar)(state[i / 8] >> (8 * (i % 8)));
+    }
+}
@@ -0,0 +1,100 @@
+#pragma once
+
+#include <metal_stdlib>
+
+// Poseidon hash of Starknet (`PoseidonCairoStark252`), with a state of three elements.
+// `round_constants` are the optimized ones: three per full round and one per partial round,
+// which is only added to the last element of the state. `one` is the element 1 of the field,
+// in the representation used by the kernels.
+
+constant uint POSEIDON_FULL_ROUNDS = 8;
+constant uint POSEIDON_PARTIAL_ROUNDS = 83;
+
+/// Multiplies the state by the MDS matrix `[[3, 1, 1], [1, -1, 1], [1, 1, -2]]`
+template<typename Fp>
+void poseidon_mix(thread Fp* state)
+{
+    Fp t = state[0] + state[1] + state[2];
+    state[0] = t + state[0] + state[0];
+    state[1] = t - state[1] - state[1];
+    state[2] = t - state[2] - state[2] - state[2];
+}
+
+template<typename Fp>
+void hades_permutation(thread Fp* state, constant Fp* round_constants)
+{
+    uint index = 0;
+    for (uint r = 0; r < POSEIDON_FULL_ROUNDS + POSEIDON_PARTIAL_ROUNDS; r++)
+    {
+        bool full_round = r < POSEIDON_FULL_ROUNDS / 2
+            || r >= POSEIDON_FULL_ROUNDS / 2 + POSEIDON_PARTIAL_ROUNDS;
+        for (uint i = full_round ? 0 : 2; i < 3; i++)
+        {
+            Fp c = round_constants[index++];
+            Fp x = state[i] + c;
+            state[i] = x * x * x;
+        }
+        poseidon_mix(state);
+    }
+}
+
+/// `output[i] = hash_single(input[i])`
+template<typename Fp>
+[[kernel]] void poseidon_hash_single(
+    device const Fp* input          [[ buffer(0) ]],
+    device Fp* output               [[ buffer(1) ]],
+    constant Fp* round_constants    [[ buffer(2) ]],
+    constant Fp& one                [[ buffer(3) ]],
+    uint32_t thread_pos             [[ thread_position_in_grid ]]
+)
+{
+    Fp state[3] = {input[thread_pos], Fp(0), one};
+    hades_permutation(state, round_constants);
+    output[thread_pos] = state[0];
+}
+
+/// `output[i] = hash_many(rows[i])`, for rows of `row_len` elements stored one after the
+/// other. Rows are padded with a 1 and then zeros up to a multiple of the rate, 2.
+template<typename Fp>
+[[kernel]] void poseidon_hash_rows(
+    device const Fp* rows           [[ buffer(0) ]],
+    device Fp* output               [[ buffer(1) ]],
+    constant Fp* round_constants    [[ buffer(2) ]],
+    constant Fp& one                [[ buffer(3) ]],
+    constant uint32_t& row_len      [[ buffer(4) ]],
+    uint32_t thread_pos             [[ thread_position_in_grid ]]
+)
+{
+    device const Fp* row = rows + (ulong)thread_pos * row_len;
+    Fp zero = Fp(0);
+    Fp pad = one;
+    Fp state[3] = {zero, zero, zero};
+    for (uint32_t i = 0; i <= row_len; i += 2)
+    {
+        Fp a = i < row_len ? row[i] : pad;
+        Fp b = i + 1 < row_len ? row[i + 1] : (i + 1 == row_len ? pad : zero);
+        state[0] = state[0] + a;
+        state[1] = state[1] + b;
+        hades_permutation(state, round_constants);
+    }
+    output[thread_pos] = state[0];
+}
+
+/// Computes the nodes `[first_parent, first_parent + threads)` of a Merkle tree stored in
+/// breadth-first order, where the children of node `k` are `2k + 1` and `2k + 2`.
+template<typename Fp>
+[[kernel]] void poseidon_merkle_level(
+    device Fp* nodes                [[ buffer(0) ]],
+    constant Fp* round_constants    [[ buffer(1) ]],
+    constant Fp& one                [[ buffer(2) ]],
+    constant uint32_t& first_parent [[ buffer(3) ]],
+    uint32_t thread_pos             [[ thread_position_in_grid ]]
+)
+{
+    uint32_t parent = first_parent + thread_pos;
+    Fp two = one;
+    two = two + two;
+    Fp state[3] = {nodes[2 * parent + 1], nodes[2 * parent + 2], two};
+    hades_permutation(state, round_constants);
+    nodes[parent] = state[0];
+}
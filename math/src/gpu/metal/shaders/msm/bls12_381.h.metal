@@ -0,0 +1,64 @@
+#ifndef msm_bls12_381_h
+#define msm_bls12_381_h
+
+#include "../field/fp_mont.h.metal"
+#include "../curve/short_weierstrass.h.metal"
+#include "msm.h.metal"
+
+namespace bls12_381
+{
+    // BLS12-381 base field, most significant limb first
+    constant ulong MODULUS[6] = {
+        0x1a0111ea397fe69a,
+        0x4b1ba7b6434bacd7,
+        0x64774b84f38512bf,
+        0x6730d2a0f6b0f624,
+        0x1eabfffeb153ffff,
+        0xb9feffffffffaaab
+    };
+    // R mod N
+    constant ulong ONE[6] = {
+        0x15f65ec3fa80e493,
+        0x5c071a97a256ec6d,
+        0x77ce585370525745,
+        0x5f48985753c758ba,
+        0xebf4000bc40c0002,
+        0x760900000002fffd
+    };
+
+    struct FpParams
+    {
+        static ulong mu() { return 0x89f3fffcfffcfffd; }
+        static ulong modulus(uint i) { return MODULUS[i]; }
+        static ulong one(uint i) { return ONE[i]; }
+    };
+
+    typedef FpMont<6, FpParams> Fp;
+    typedef ProjectivePoint<Fp> Point;
+} // namespace bls12_381
+
+template [[ host_name("msm_init_buckets_bls12_381") ]]
+[[kernel]] void msm_init_buckets<bls12_381::Point>(
+    device bls12_381::Point*,
+    uint
+);
+
+template [[ host_name("msm_accumulate_buckets_bls12_381") ]]
+[[kernel]] void msm_accumulate_buckets<bls12_381::Point>(
+    device const bls12_381::Point*,
+    device const uint*,
+    device const uint*,
+    device const uint*,
+    device bls12_381::Point*,
+    uint
+);
+
+template [[ host_name("msm_reduce_windows_bls12_381") ]]
+[[kernel]] void msm_reduce_windows<bls12_381::Point>(
+    device const bls12_381::Point*,
+    device bls12_381::Point*,
+    constant uint&,
+    uint
+);
+
+#endif // msm_bls12_381_h
@@ -0,0 +1,60 @@
+#ifndef msm_bn254_h
+#define msm_bn254_h
+
+#include "../field/fp_mont.h.metal"
+#include "../curve/short_weierstrass.h.metal"
+#include "msm.h.metal"
+
+namespace bn254
+{
+    // BN254 base field, most significant limb first
+    constant ulong MODULUS[4] = {
+        0x30644e72e131a029,
+        0xb85045b68181585d,
+        0x97816a916871ca8d,
+        0x3c208c16d87cfd47
+    };
+    // R mod N
+    constant ulong ONE[4] = {
+        0x0e0a77c19a07df2f,
+        0x666ea36f7879462c,
+        0x0a78eb28f5c70b3d,
+        0xd35d438dc58f0d9d
+    };
+
+    struct FpParams
+    {
+        static ulong mu() { return 0x87d20782e4866389; }
+        static ulong modulus(uint i) { return MODULUS[i]; }
+        static ulong one(uint i) { return ONE[i]; }
+    };
+
+    typedef FpMont<4, FpParams> Fp;
+    typedef ProjectivePoint<Fp> Point;
+} // namespace bn254
+
+template [[ host_name("msm_init_buckets_bn254") ]]
+[[kernel]] void msm_init_buckets<bn254::Point>(
+    device bn254::Point*,
+    uint
+);
+
+template [[ host_name("msm_accumulate_buckets_bn254") ]]
+[[kernel]] void msm_accumulate_buckets<bn254::Point>(
+    device const bn254::Point*,
+    device const uint*,
+    device const uint*,
+    device const uint*,
+    device bn254::Point*,
+    uint
+);
+
+template [[ host_name("msm_reduce_windows_bn254") ]]
+[[kernel]] void msm_reduce_windows<bn254::Point>(
+    device const bn254::Point*,
+    device bn254::Point*,
+    constant uint&,
+    uint
+);
+
+#endif // msm_bn254_h
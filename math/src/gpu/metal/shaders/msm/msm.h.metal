@@ -0,0 +1,159 @@
+#ifndef msm_h
+#define msm_h
+
+#include <metal_stdlib>
+
+// Bucket method for multi-scalar multiplication. Scalars are split in windows of
+// `window_size` bits, and each window has one bucket per nonzero digit. The points are
+// sorted by bucket with a counting sort (`count_buckets`, `bucket_offsets`, `scatter`), so
+// that every bucket is accumulated by a single thread without atomics on points. The
+// buckets of each window are then reduced to `sum_d d * bucket_d` with a running sum.
+//
+// Bucket `d` of window `w` is stored at `w * num_buckets + d - 1`, with
+// `num_buckets = 2^window_size - 1`. The point indices of window `w` are stored in
+// `[w * len, (w + 1) * len)`, as every point falls in at most one bucket per window.
+
+/// Returns the digit of `window_size` bits starting at `bit` of a scalar with `scalar_limbs`
+/// 64-bit limbs, most significant first.
+uint scalar_window(device const ulong *scalar, uint scalar_limbs, uint bit, uint window_size)
+{
+    uint limb = bit / 64;
+    uint offset = bit % 64;
+    if (limb >= scalar_limbs)
+    {
+        return 0;
+    }
+    ulong word = scalar[scalar_limbs - 1 - limb] >> offset;
+    if (offset + window_size > 64 && limb + 1 < scalar_limbs)
+    {
+        word |= scalar[scalar_limbs - 2 - limb] << (64 - offset);
+    }
+    return (uint)(word & ((1ul << window_size) - 1));
+}
+
+[[kernel]] void msm_clear_counts(
+    device metal::atomic_uint *counts [[ buffer(0) ]],
+    uint thread_pos                   [[ thread_position_in_grid ]]
+)
+{
+    metal::atomic_store_explicit(&counts[thread_pos], 0, metal::memory_order_relaxed);
+}
+
+[[kernel]] void msm_count_buckets(
+    device const ulong *scalars       [[ buffer(0) ]],
+    device metal::atomic_uint *counts [[ buffer(1) ]],
+    constant uint &scalar_limbs       [[ buffer(2) ]],
+    constant uint &window_size        [[ buffer(3) ]],
+    constant uint &num_windows        [[ buffer(4) ]],
+    uint thread_pos                   [[ thread_position_in_grid ]]
+)
+{
+    uint num_buckets = (1u << window_size) - 1;
+    device const ulong *scalar = scalars + thread_pos * scalar_limbs;
+    for (uint w = 0; w < num_windows; w++)
+    {
+        uint digit = scalar_window(scalar, scalar_limbs, w * window_size, window_size);
+        if (digit != 0)
+        {
+            metal::atomic_fetch_add_explicit(&counts[w * num_buckets + digit - 1], 1,
+                                             metal::memory_order_relaxed);
+        }
+    }
+}
+
+/// Exclusive prefix sum of the bucket sizes, one thread per window.
+[[kernel]] void msm_bucket_offsets(
+    device const uint *counts  [[ buffer(0) ]],
+    device uint *starts        [[ buffer(1) ]],
+    device uint *cursor        [[ buffer(2) ]],
+    constant uint &window_size [[ buffer(3) ]],
+    constant uint &len         [[ buffer(4) ]],
+    uint w                     [[ thread_position_in_grid ]]
+)
+{
+    uint num_buckets = (1u << window_size) - 1;
+    uint offset = w * len;
+    for (uint b = w * num_buckets; b < (w + 1) * num_buckets; b++)
+    {
+        starts[b] = offset;
+        cursor[b] = offset;
+        offset += counts[b];
+    }
+}
+
+[[kernel]] void msm_scatter(
+    device const ulong *scalars       [[ buffer(0) ]],
+    device metal::atomic_uint *cursor [[ buffer(1) ]],
+    device uint *indices              [[ buffer(2) ]],
+    constant uint &scalar_limbs       [[ buffer(3) ]],
+    constant uint &window_size        [[ buffer(4) ]],
+    constant uint &num_windows        [[ buffer(5) ]],
+    uint thread_pos                   [[ thread_position_in_grid ]]
+)
+{
+    uint num_buckets = (1u << window_size) - 1;
+    device const ulong *scalar = scalars + thread_pos * scalar_limbs;
+    for (uint w = 0; w < num_windows; w++)
+    {
+        uint digit = scalar_window(scalar, scalar_limbs, w * window_size, window_size);
+        if (digit != 0)
+        {
+            uint position = metal::atomic_fetch_add_explicit(
+                &cursor[w * num_buckets + digit - 1], 1, metal::memory_order_relaxed);
+            indices[position] = thread_pos;
+        }
+    }
+}
+
+template <typename Point>
+[[kernel]] void msm_init_buckets(
+    device Point *buckets [[ buffer(0) ]],
+    uint thread_pos       [[ thread_position_in_grid ]]
+)
+{
+    buckets[thread_pos] = Point::neutral_element();
+}
+
+/// Adds the points of each bucket to it, one thread per bucket.
+template <typename Point>
+[[kernel]] void msm_accumulate_buckets(
+    device const Point *points  [[ buffer(0) ]],
+    device const uint *indices  [[ buffer(1) ]],
+    device const uint *starts   [[ buffer(2) ]],
+    device const uint *counts   [[ buffer(3) ]],
+    device Point *buckets       [[ buffer(4) ]],
+    uint b                      [[ thread_position_in_grid ]]
+)
+{
+    Point acc = buckets[b];
+    uint end = starts[b] + counts[b];
+    for (uint k = starts[b]; k < end; k++)
+    {
+        Point point = points[indices[k]];
+        acc = acc + point;
+    }
+    buckets[b] = acc;
+}
+
+/// Computes `sum_d d * bucket_d` for each window, one thread per window.
+template <typename Point>
+[[kernel]] void msm_reduce_windows(
+    device const Point *buckets [[ buffer(0) ]],
+    device Point *window_sums   [[ buffer(1) ]],
+    constant uint &window_size  [[ buffer(2) ]],
+    uint w                      [[ thread_position_in_grid ]]
+)
+{
+    uint num_buckets = (1u << window_size) - 1;
+    Point running = Point::neutral_element();
+    Point total = Point::neutral_element();
+    for (int d = num_buckets - 1; d >= 0; d--)
+    {
+        Point bucket = buckets[w * num_buckets + d];
+        running = running + bucket;
+        total = total + running;
+    }
+    window_sums[w] = total;
+}
+
+#endif // msm_h
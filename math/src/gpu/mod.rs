@@ -1,8 +1,15 @@
-#[cfg(all(feature = "metal", feature = "cuda"))]
+#[cfg(any(
+    all(feature = "metal", feature = "cuda"),
+    all(feature = "metal", feature = "wgpu"),
+    all(feature = "cuda", feature = "wgpu")
+))]
 compile_error!(
-    "Can't enable both \"metal\" and \"cuda\" features at the same time.
+    "Can't enable more than one of the \"metal\", \"cuda\" and \"wgpu\" features at the same time.
 If you were using the `--all-features` flag please read this crate's Cargo.toml"
 );
 
 #[cfg(feature = "cuda")]
 pub mod cuda;
+
+#[cfg(feature = "wgpu")]
+pub mod wgpu;
@@ -0,0 +1,24 @@
+//! WGSL sources of the WebGPU kernels. WGSL has no includes, so each module is the
+//! concatenation of the files it needs.
+
+/// Radix-2 FFT kernels over the Stark252 field.
+pub const FFT_STARK256_SHADER: &str = concat!(
+    include_str!("shaders/util.wgsl"),
+    include_str!("shaders/field/stark256.wgsl"),
+    include_str!("shaders/fft/fft.wgsl"),
+);
+
+/// Poseidon hashing and Merkle tree kernels over the Stark252 field.
+pub const POSEIDON_STARK256_SHADER: &str = concat!(
+    include_str!("shaders/util.wgsl"),
+    include_str!("shaders/field/stark256.wgsl"),
+    include_str!("shaders/hash/poseidon.wgsl"),
+);
+
+/// Interpreter of the transition constraint programs of the STARK prover, over the Stark252
+/// field.
+pub const TRANSITION_PROGRAM_STARK256_SHADER: &str = concat!(
+    include_str!("shaders/util.wgsl"),
+    include_str!("shaders/field/stark256.wgsl"),
+    include_str!("shaders/stark/transition_program.wgsl"),
+);
@@ -0,0 +1,52 @@
+// Radix-2 FFT over elements of `Fe`, with twiddle factors in bit-reverse order. Input is in
+// natural order and the output of the butterflies is in bit-reverse order, which
+// `bitrev_permutation` puts back in natural order.
+
+@group(0) @binding(0) var<storage, read_write> input: array<Fe>;
+@group(0) @binding(1) var<storage, read> twiddles: array<Fe>;
+// `radix2_dit_butterfly`: the stage and the number of butterflies, `n / 2`.
+// `bitrev_permutation`: the order of the input, `log2(n)`, and `n`.
+@group(0) @binding(2) var<storage, read> params: array<u32>;
+@group(0) @binding(3) var<storage, read_write> result: array<Fe>;
+
+@compute @workgroup_size(WORKGROUP_SIZE)
+fn radix2_dit_butterfly(
+    @builtin(global_invocation_id) id: vec3<u32>,
+    @builtin(num_workgroups) groups: vec3<u32>,
+) {
+    let thread_pos = global_index(id, groups);
+    let stage = params[0];
+    let thread_count = params[1];
+    if (thread_pos >= thread_count) {
+        return;
+    }
+
+    let half_group_size = thread_count >> stage;
+    let group = thread_pos / half_group_size;
+    let pos_in_group = thread_pos & (half_group_size - 1u);
+    let i = thread_pos * 2u - pos_in_group;
+
+    let w = swap_layout(twiddles[group]);
+    let a = swap_layout(input[i]);
+    let b = swap_layout(input[i + half_group_size]);
+    let wb = fe_mul(w, b);
+
+    input[i] = swap_layout(fe_add(a, wb));
+    input[i + half_group_size] = swap_layout(fe_sub(a, wb));
+}
+
+@compute @workgroup_size(WORKGROUP_SIZE)
+fn bitrev_permutation(
+    @builtin(global_invocation_id) id: vec3<u32>,
+    @builtin(num_workgroups) groups: vec3<u32>,
+) {
+    let index = global_index(id, groups);
+    let order = params[0];
+    let size = params[1];
+    if (index >= size) {
+        return;
+    }
+
+    // `order` is at least 1, as there is nothing to permute with a single element
+    result[index] = input[reverseBits(index) >> (32u - order)];
+}
@@ -0,0 +1,160 @@
+// Arithmetic of the Stark252 prime field, `p = 2^251 + 17 * 2^192 + 1`, in Montgomery form with
+// `R = 2^256`, the same representation as `FieldElement<Stark252PrimeField>`.
+//
+// WGSL has no 64-bit integers, so elements are arrays of eight 32-bit words. In buffers they
+// keep the layout of `UnsignedInteger<4>`: four 64-bit limbs, the most significant first, each
+// one stored as its low and then its high word. The arithmetic works on the words in order of
+// significance, least significant first; `swap_layout` converts between both.
+
+alias Fe = array<u32, 8>;
+
+// p, least significant word first
+const MODULUS: Fe = Fe(1u, 0u, 0u, 0u, 0u, 0u, 0x11u, 0x8000000u);
+// -p^-1 mod 2^32
+const MODULUS_INV_NEG: u32 = 0xffffffffu;
+
+/// Converts an element between the layout of the buffers and the one of the arithmetic. The
+/// permutation is its own inverse.
+fn swap_layout(x: Fe) -> Fe {
+    var value = x;
+    var result: Fe;
+    for (var k = 0u; k < 8u; k++) {
+        result[k] = value[6u - (k & 6u) + (k & 1u)];
+    }
+    return result;
+}
+
+/// Returns the 64-bit product `a * b` as its low and high words.
+fn mul_wide(a: u32, b: u32) -> vec2<u32> {
+    let a_lo = a & 0xffffu;
+    let a_hi = a >> 16u;
+    let b_lo = b & 0xffffu;
+    let b_hi = b >> 16u;
+    let lo_lo = a_lo * b_lo;
+    let lo_hi = a_lo * b_hi;
+    let hi_lo = a_hi * b_lo;
+    let hi_hi = a_hi * b_hi;
+    let mid = (lo_lo >> 16u) + (lo_hi & 0xffffu) + (hi_lo & 0xffffu);
+    let lo = (lo_lo & 0xffffu) | (mid << 16u);
+    let hi = hi_hi + (lo_hi >> 16u) + (hi_lo >> 16u) + (mid >> 16u);
+    return vec2<u32>(lo, hi);
+}
+
+/// Returns `x + a * b + carry` as its low and high words. It always fits in 64 bits.
+fn mul_add(x: u32, a: u32, b: u32, carry: u32) -> vec2<u32> {
+    let product = mul_wide(a, b);
+    let lo = x + product.x;
+    let carry_lo = select(0u, 1u, lo < x);
+    let result = lo + carry;
+    let carry_result = select(0u, 1u, result < lo);
+    return vec2<u32>(result, product.y + carry_lo + carry_result);
+}
+
+fn fe_gte_modulus(x: Fe) -> bool {
+    var value = x;
+    var modulus = MODULUS;
+    for (var i = 0u; i < 8u; i++) {
+        let k = 7u - i;
+        if (value[k] != modulus[k]) {
+            return value[k] > modulus[k];
+        }
+    }
+    return true;
+}
+
+/// Returns `x - p`, wrapping around `2^256`.
+fn fe_sub_modulus(x: Fe) -> Fe {
+    var value = x;
+    var modulus = MODULUS;
+    var result: Fe;
+    var borrow = 0u;
+    for (var k = 0u; k < 8u; k++) {
+        let difference = value[k] - modulus[k];
+        let borrow_difference = select(0u, 1u, value[k] < modulus[k]);
+        result[k] = difference - borrow;
+        borrow = borrow_difference + select(0u, 1u, difference < borrow);
+    }
+    return result;
+}
+
+fn fe_add(a: Fe, b: Fe) -> Fe {
+    var x = a;
+    var y = b;
+    var sum: Fe;
+    var carry = 0u;
+    for (var k = 0u; k < 8u; k++) {
+        let partial = x[k] + y[k];
+        let carry_partial = select(0u, 1u, partial < x[k]);
+        sum[k] = partial + carry;
+        carry = carry_partial + select(0u, 1u, sum[k] < partial);
+    }
+    // Both are smaller than p < 2^252, so the sum doesn't overflow
+    if (fe_gte_modulus(sum)) {
+        return fe_sub_modulus(sum);
+    }
+    return sum;
+}
+
+fn fe_sub(a: Fe, b: Fe) -> Fe {
+    var x = a;
+    var y = b;
+    var difference: Fe;
+    var borrow = 0u;
+    for (var k = 0u; k < 8u; k++) {
+        let partial = x[k] - y[k];
+        let borrow_partial = select(0u, 1u, x[k] < y[k]);
+        difference[k] = partial - borrow;
+        borrow = borrow_partial + select(0u, 1u, partial < borrow);
+    }
+    if (borrow != 0u) {
+        // a - b + p, which wraps around 2^256 back to the range [0, p)
+        var modulus = MODULUS;
+        var carry = 0u;
+        for (var k = 0u; k < 8u; k++) {
+            let partial = difference[k] + modulus[k];
+            let carry_partial = select(0u, 1u, partial < difference[k]);
+            difference[k] = partial + carry;
+            carry = carry_partial + select(0u, 1u, difference[k] < partial);
+        }
+    }
+    return difference;
+}
+
+/// Montgomery multiplication, `a * b / R mod p`, with the CIOS method.
+fn fe_mul(a: Fe, b: Fe) -> Fe {
+    var x = a;
+    var y = b;
+    var modulus = MODULUS;
+    var t: array<u32, 10>;
+    for (var i = 0u; i < 8u; i++) {
+        var carry = 0u;
+        for (var j = 0u; j < 8u; j++) {
+            let sum = mul_add(t[j], x[j], y[i], carry);
+            t[j] = sum.x;
+            carry = sum.y;
+        }
+        let high = t[8] + carry;
+        t[9] = select(0u, 1u, high < carry);
+        t[8] = high;
+
+        let m = t[0] * MODULUS_INV_NEG;
+        carry = mul_add(t[0], m, modulus[0], 0u).y;
+        for (var j = 1u; j < 8u; j++) {
+            let sum = mul_add(t[j], m, modulus[j], carry);
+            t[j - 1u] = sum.x;
+            carry = sum.y;
+        }
+        let top = t[8] + carry;
+        t[7] = top;
+        t[8] = t[9] + select(0u, 1u, top < carry);
+    }
+
+    var result: Fe;
+    for (var k = 0u; k < 8u; k++) {
+        result[k] = t[k];
+    }
+    if (t[8] != 0u || fe_gte_modulus(result)) {
+        return fe_sub_modulus(result);
+    }
+    return result;
+}
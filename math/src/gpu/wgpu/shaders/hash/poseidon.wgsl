@@ -0,0 +1,117 @@
+// Poseidon hash of Starknet (`PoseidonCairoStark252`), with a state of three elements.
+// `round_constants` are the optimized ones: three per full round and one per partial round,
+// which is only added to the last element of the state. `one` is the element 1 of the field.
+
+const POSEIDON_FULL_ROUNDS: u32 = 8u;
+const POSEIDON_PARTIAL_ROUNDS: u32 = 83u;
+
+@group(0) @binding(0) var<storage, read_write> nodes: array<Fe>;
+@group(0) @binding(1) var<storage, read_write> output: array<Fe>;
+@group(0) @binding(2) var<storage, read> round_constants: array<Fe>;
+@group(0) @binding(3) var<storage, read> one: Fe;
+// The number of threads, followed by the row length for `poseidon_hash_rows` or the first
+// parent for `poseidon_merkle_level`.
+@group(0) @binding(4) var<storage, read> params: array<u32>;
+
+alias PoseidonState = array<Fe, 3>;
+
+fn cube(x: Fe) -> Fe {
+    return fe_mul(fe_mul(x, x), x);
+}
+
+/// Multiplies the state by the MDS matrix `[[3, 1, 1], [1, -1, 1], [1, 1, -2]]`
+fn poseidon_mix(state: ptr<function, PoseidonState>) {
+    let t = fe_add(fe_add((*state)[0], (*state)[1]), (*state)[2]);
+    (*state)[0] = fe_add(fe_add(t, (*state)[0]), (*state)[0]);
+    (*state)[1] = fe_sub(fe_sub(t, (*state)[1]), (*state)[1]);
+    (*state)[2] = fe_sub(fe_sub(fe_sub(t, (*state)[2]), (*state)[2]), (*state)[2]);
+}
+
+fn hades_permutation(state: ptr<function, PoseidonState>) {
+    var index = 0u;
+    for (var r = 0u; r < POSEIDON_FULL_ROUNDS + POSEIDON_PARTIAL_ROUNDS; r++) {
+        let full_round = r < POSEIDON_FULL_ROUNDS / 2u
+            || r >= POSEIDON_FULL_ROUNDS / 2u + POSEIDON_PARTIAL_ROUNDS;
+        for (var i = select(2u, 0u, full_round); i < 3u; i++) {
+            let round_constant = swap_layout(round_constants[index]);
+            (*state)[i] = cube(fe_add((*state)[i], round_constant));
+            index++;
+        }
+        poseidon_mix(state);
+    }
+}
+
+/// `output[i] = hash_single(nodes[i])`
+@compute @workgroup_size(WORKGROUP_SIZE)
+fn poseidon_hash_single(
+    @builtin(global_invocation_id) id: vec3<u32>,
+    @builtin(num_workgroups) groups: vec3<u32>,
+) {
+    let thread_pos = global_index(id, groups);
+    if (thread_pos >= params[0]) {
+        return;
+    }
+
+    var zero: Fe;
+    var state = PoseidonState(swap_layout(nodes[thread_pos]), zero, swap_layout(one));
+    hades_permutation(&state);
+    output[thread_pos] = swap_layout(state[0]);
+}
+
+/// `output[i] = hash_many(rows[i])`, for rows of `row_len` elements stored one after the other
+/// in `nodes`. Rows are padded with a 1 and then zeros up to a multiple of the rate, 2.
+@compute @workgroup_size(WORKGROUP_SIZE)
+fn poseidon_hash_rows(
+    @builtin(global_invocation_id) id: vec3<u32>,
+    @builtin(num_workgroups) groups: vec3<u32>,
+) {
+    let thread_pos = global_index(id, groups);
+    let row_len = params[1];
+    if (thread_pos >= params[0]) {
+        return;
+    }
+
+    let row = thread_pos * row_len;
+    var zero: Fe;
+    let pad = swap_layout(one);
+    var state = PoseidonState(zero, zero, zero);
+    for (var i = 0u; i <= row_len; i += 2u) {
+        var a = pad;
+        if (i < row_len) {
+            a = swap_layout(nodes[row + i]);
+        }
+        var b = zero;
+        if (i + 1u < row_len) {
+            b = swap_layout(nodes[row + i + 1u]);
+        } else if (i + 1u == row_len) {
+            b = pad;
+        }
+        state[0] = fe_add(state[0], a);
+        state[1] = fe_add(state[1], b);
+        hades_permutation(&state);
+    }
+    output[thread_pos] = swap_layout(state[0]);
+}
+
+/// Computes the nodes `[first_parent, first_parent + count)` of a Merkle tree stored in
+/// breadth-first order in `nodes`, where the children of node `k` are `2k + 1` and `2k + 2`.
+@compute @workgroup_size(WORKGROUP_SIZE)
+fn poseidon_merkle_level(
+    @builtin(global_invocation_id) id: vec3<u32>,
+    @builtin(num_workgroups) groups: vec3<u32>,
+) {
+    let thread_pos = global_index(id, groups);
+    if (thread_pos >= params[0]) {
+        return;
+    }
+
+    let parent = params[1] + thread_pos;
+    let one_value = swap_layout(one);
+    var state = PoseidonState(
+        swap_layout(nodes[2u * parent + 1u]),
+        swap_layout(nodes[2u * parent + 2u]),
+        fe_add(one_value, one_value),
+    );
+    hades_permutation(&state);
+    nodes[parent] = swap_layout(state[0]);
+}
@@ -0,0 +1,93 @@
+// Interpreter of the constraint programs of the STARK prover
+// (`stark_platinum_prover::constraints::program::ConstraintProgram`). Each thread runs the
+// program over one row of the LDE domain and accumulates the transition part of the
+// composition polynomial, `sum_k zerofier_k(x) * coefficient_k * constraint_k(x)`.
+//
+// Instructions are three words: the opcode and two operands. Reads of the trace have the
+// shift of the row, in LDE rows, and the column.
+
+const OP_MAIN: u32 = 0u;
+const OP_AUX: u32 = 1u;
+const OP_PERIODIC: u32 = 2u;
+// Constants and RAP challenges
+const OP_ELEMENT: u32 = 3u;
+const OP_ADD: u32 = 4u;
+const OP_SUB: u32 = 5u;
+const OP_MUL: u32 = 6u;
+const OP_STORE: u32 = 7u;
+
+const MAX_STACK_DEPTH: u32 = 16u;
+// Words of `params` before the zerofier of each constraint
+const PARAMS_HEADER: u32 = 5u;
+
+// LDE tables, row by row
+@group(0) @binding(0) var<storage, read> main_trace: array<Fe>;
+@group(0) @binding(1) var<storage, read> aux_trace: array<Fe>;
+// LDE evaluations of the periodic columns, column by column
+@group(0) @binding(2) var<storage, read> periodic: array<Fe>;
+// The constants of the program, the RAP challenges and the transition coefficients
+@group(0) @binding(3) var<storage, read> elements: array<Fe>;
+// The evaluations of a cycle of the zerofier of each constraint, one after the other
+@group(0) @binding(4) var<storage, read> zerofiers: array<Fe>;
+// The LDE length, the main and auxiliary widths, the index of the first coefficient in
+// `elements`, the number of instructions, and then the first index and the length of the
+// zerofier of each constraint.
+@group(0) @binding(5) var<storage, read> params: array<u32>;
+@group(0) @binding(6) var<storage, read> program: array<u32>;
+@group(0) @binding(7) var<storage, read_write> result: array<Fe>;
+
+@compute @workgroup_size(WORKGROUP_SIZE)
+fn evaluate_transitions(
+    @builtin(global_invocation_id) id: vec3<u32>,
+    @builtin(num_workgroups) groups: vec3<u32>,
+) {
+    let row = global_index(id, groups);
+    let lde_len = params[0];
+    if (row >= lde_len) {
+        return;
+    }
+    let main_width = params[1];
+    let aux_width = params[2];
+    let coefficients_begin = params[3];
+    let instruction_count = params[4];
+
+    var stack: array<Fe, MAX_STACK_DEPTH>;
+    var top = 0u;
+    var acc: Fe;
+    for (var pc = 0u; pc < instruction_count; pc++) {
+        let op = program[3u * pc];
+        let a = program[3u * pc + 1u];
+        let b = program[3u * pc + 2u];
+        if (op == OP_MAIN) {
+            stack[top] = swap_layout(main_trace[((row + a) % lde_len) * main_width + b]);
+            top++;
+        } else if (op == OP_AUX) {
+            stack[top] = swap_layout(aux_trace[((row + a) % lde_len) * aux_width + b]);
+            top++;
+        } else if (op == OP_PERIODIC) {
+            stack[top] = swap_layout(periodic[a * lde_len + row]);
+            top++;
+        } else if (op == OP_ELEMENT) {
+            stack[top] = swap_layout(elements[a]);
+            top++;
+        } else if (op == OP_ADD) {
+            top--;
+            stack[top - 1u] = fe_add(stack[top - 1u], stack[top]);
+        } else if (op == OP_SUB) {
+            top--;
+            stack[top - 1u] = fe_sub(stack[top - 1u], stack[top]);
+        } else if (op == OP_MUL) {
+            top--;
+            stack[top - 1u] = fe_mul(stack[top - 1u], stack[top]);
+        } else if (op == OP_STORE) {
+            top--;
+            let zerofier_begin = params[PARAMS_HEADER + 2u * a];
+            let zerofier_len = params[PARAMS_HEADER + 2u * a + 1u];
+            // Zerofier evaluations are cyclical, so only one cycle is stored
+            let zerofier = swap_layout(zerofiers[zerofier_begin + row % zerofier_len]);
+            let coefficient = swap_layout(elements[coefficients_begin + a]);
+            acc = fe_add(acc, fe_mul(fe_mul(zerofier, stack[top]), coefficient));
+        }
+    }
+    result[row] = swap_layout(acc);
+}
@@ -0,0 +1,8 @@
+// Kernels are dispatched in a grid of `(x, y)` workgroups of `WORKGROUP_SIZE` threads, since
+// each dimension is limited to 65535 workgroups. `global_index` flattens it.
+
+const WORKGROUP_SIZE: u32 = 64u;
+
+fn global_index(id: vec3<u32>, groups: vec3<u32>) -> u32 {
+    return id.x + id.y * groups.x * WORKGROUP_SIZE;
+}
@@ -3,8 +3,13 @@
 #[cfg(feature = "alloc")]
 extern crate alloc;
 
+#[cfg(feature = "arkworks")]
+pub mod arkworks;
+#[cfg(feature = "alloc")]
+pub mod circle;
 pub mod cyclic_group;
 pub mod elliptic_curve;
+pub mod environment;
 pub mod errors;
 pub mod field;
 pub mod helpers;
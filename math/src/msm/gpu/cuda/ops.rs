@@ -0,0 +1,467 @@
+use crate::{
+    cyclic_group::IsGroup,
+    elliptic_curve::short_weierstrass::point::ShortWeierstrassProjectivePoint,
+    field::{element::FieldElement, traits::IsField},
+    gpu::cuda::{
+        devices::CudaDevices,
+        memory::{PooledPinned, PooledSlice},
+    },
+    msm::gpu::cuda::state::{CudaMsmState, IsCudaMsmCurve},
+    unsigned_integer::element::UnsignedInteger,
+};
+use cudarc::driver::{safe::CudaStream, LaunchAsync, LaunchConfig};
+use lambdaworks_gpu::cuda::abstractions::errors::CudaError;
+
+const WARP_SIZE: usize = 32; // the implementation will spawn threadblocks of this size.
+
+/// Number of points sent to the device at once.
+const CHUNK_SIZE: usize = 1 << 20;
+
+/// Chunks alternate between this many streams, each one with its own buckets, so that the
+/// transfer of a chunk overlaps with the accumulation of the previous one.
+const NUM_STREAMS: usize = 2;
+
+/// Computes the multi-scalar multiplication `k_1 * g_1 + ... + k_n * g_n` in CUDA, with the
+/// bucket method.
+///
+/// The points are sorted by bucket and accumulated on the device, and so are the sums of the
+/// windows. Only the final combination of the windows, a few hundred doublings, is done on the
+/// host. The result is the same as the one of [`crate::msm::pippenger::msm`].
+pub fn msm<const NUM_LIMBS: usize, const SCALAR_LIMBS: usize, E>(
+    cs: &[UnsignedInteger<SCALAR_LIMBS>],
+    points: &[ShortWeierstrassProjectivePoint<E>],
+    state: &CudaMsmState,
+) -> Result<ShortWeierstrassProjectivePoint<E>, CudaError>
+where
+    E: IsCudaMsmCurve,
+    E::BaseField: IsField<BaseType = UnsignedInteger<NUM_LIMBS>>,
+{
+    msm_with_chunk_size(cs, points, state, CHUNK_SIZE)
+}
+
+/// Same as [`msm`], with the points split in contiguous ranges across `devices`. Each GPU
+/// computes the MSM of its range, and their results are added on the host.
+pub fn msm_multi_gpu<const NUM_LIMBS: usize, const SCALAR_LIMBS: usize, E>(
+    cs: &[UnsignedInteger<SCALAR_LIMBS>],
+    points: &[ShortWeierstrassProjectivePoint<E>],
+    devices: &CudaDevices,
+) -> Result<ShortWeierstrassProjectivePoint<E>, CudaError>
+where
+    E: IsCudaMsmCurve + Sync,
+    E::BaseField: IsField<BaseType = UnsignedInteger<NUM_LIMBS>>,
+{
+    if cs.len() != points.len() {
+        return Err(CudaError::LengthMismatch(cs.len(), points.len()));
+    }
+
+    let partial_sums = devices.shard(cs.len(), |pool, range| {
+        let state = CudaMsmState::with_pool(pool.clone())?;
+        msm(&cs[range.clone()], &points[range], &state)
+    })?;
+
+    Ok(partial_sums.iter().fold(
+        ShortWeierstrassProjectivePoint::neutral_element(),
+        |acc, sum| acc.operate_with(sum),
+    ))
+}
+
+fn msm_with_chunk_size<const NUM_LIMBS: usize, const SCALAR_LIMBS: usize, E>(
+    cs: &[UnsignedInteger<SCALAR_LIMBS>],
+    points: &[ShortWeierstrassProjectivePoint<E>],
+    state: &CudaMsmState,
+    chunk_size: usize,
+) -> Result<ShortWeierstrassProjectivePoint<E>, CudaError>
+where
+    E: IsCudaMsmCurve,
+    E::BaseField: IsField<BaseType = UnsignedInteger<NUM_LIMBS>>,
+{
+    if cs.len() != points.len() {
+        return Err(CudaError::LengthMismatch(cs.len(), points.len()));
+    }
+    if cs.is_empty() {
+        return Ok(ShortWeierstrassProjectivePoint::neutral_element());
+    }
+
+    let window_size = window_size(cs.len());
+    let num_windows = (64 * SCALAR_LIMBS).div_ceil(window_size);
+    let chunk_size = chunk_size.min(cs.len());
+    let num_chunks = cs.len().div_ceil(chunk_size);
+
+    let mut pipelines = (0..NUM_STREAMS.min(num_chunks))
+        .map(|_| {
+            Pipeline::<E, NUM_LIMBS, SCALAR_LIMBS>::new(state, chunk_size, window_size, num_windows)
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    let num_pipelines = pipelines.len();
+    let chunks = cs.chunks(chunk_size).zip(points.chunks(chunk_size));
+    for (i, (cs_chunk, points_chunk)) in chunks.enumerate() {
+        pipelines[i % num_pipelines].accumulate(state, cs_chunk, points_chunk)?;
+    }
+
+    let mut window_sums =
+        vec![ShortWeierstrassProjectivePoint::<E>::neutral_element(); num_windows];
+    for pipeline in pipelines {
+        for (sum, partial) in window_sums.iter_mut().zip(pipeline.reduce(state)?) {
+            *sum = sum.operate_with(&partial);
+        }
+    }
+
+    Ok(window_sums.iter().rev().fold(
+        ShortWeierstrassProjectivePoint::neutral_element(),
+        |acc, sum| {
+            (0..window_size)
+                .fold(acc, |acc, _| acc.double())
+                .operate_with(sum)
+        },
+    ))
+}
+
+/// Window size in bits. There are `2^window_size - 1` buckets per window.
+fn window_size(len: usize) -> usize {
+    (len.ilog2() as usize).saturating_sub(3).clamp(4, 13)
+}
+
+fn launch_config(threads: usize) -> LaunchConfig {
+    let block_size = WARP_SIZE;
+    let block_count = threads.div_ceil(block_size);
+
+    LaunchConfig {
+        grid_dim: (block_count as u32, 1, 1), // in blocks
+        block_dim: (block_size as u32, 1, 1),
+        shared_mem_bytes: 0,
+    }
+}
+
+/// Buffers and stream processing a share of the chunks, and the buckets where they are
+/// accumulated.
+struct Pipeline<E, const NUM_LIMBS: usize, const SCALAR_LIMBS: usize> {
+    stream: CudaStream,
+    window_size: usize,
+    num_windows: usize,
+    total_buckets: usize,
+    points_host: PooledPinned<u64>,
+    scalars_host: PooledPinned<u64>,
+    points: PooledSlice<u64>,
+    scalars: PooledSlice<u64>,
+    counts: PooledSlice<u32>,
+    starts: PooledSlice<u32>,
+    cursor: PooledSlice<u32>,
+    indices: PooledSlice<u32>,
+    buckets: PooledSlice<u64>,
+    window_sums: PooledSlice<u64>,
+    phantom: core::marker::PhantomData<E>,
+}
+
+impl<E, const NUM_LIMBS: usize, const SCALAR_LIMBS: usize> Pipeline<E, NUM_LIMBS, SCALAR_LIMBS>
+where
+    E: IsCudaMsmCurve,
+    E::BaseField: IsField<BaseType = UnsignedInteger<NUM_LIMBS>>,
+{
+    const POINT_LIMBS: usize = 3 * NUM_LIMBS;
+
+    fn new(
+        state: &CudaMsmState,
+        chunk_size: usize,
+        window_size: usize,
+        num_windows: usize,
+    ) -> Result<Self, CudaError> {
+        let total_buckets = ((1 << window_size) - 1) * num_windows;
+        let points = state.alloc(chunk_size * Self::POINT_LIMBS)?;
+        let scalars = state.alloc(chunk_size * SCALAR_LIMBS)?;
+        let counts = state.alloc(total_buckets)?;
+        let starts = state.alloc(total_buckets)?;
+        let cursor = state.alloc(total_buckets)?;
+        let indices = state.alloc(num_windows * chunk_size)?;
+        let buckets = state.alloc(total_buckets * Self::POINT_LIMBS)?;
+        let window_sums = state.alloc(num_windows * Self::POINT_LIMBS)?;
+        // The stream starts after the allocations above
+        let stream = state.fork_stream()?;
+
+        let mut pipeline = Self {
+            stream,
+            window_size,
+            num_windows,
+            total_buckets,
+            points_host: state.alloc_pinned(chunk_size * Self::POINT_LIMBS)?,
+            scalars_host: state.alloc_pinned(chunk_size * SCALAR_LIMBS)?,
+            points,
+            scalars,
+            counts,
+            starts,
+            cursor,
+            indices,
+            buckets,
+            window_sums,
+            phantom: core::marker::PhantomData,
+        };
+        let function = state.get_function::<E>("msm_init_buckets")?;
+        let config = launch_config(total_buckets);
+        // Launching kernels must be done in an unsafe block.
+        // Calling a kernel is similar to calling a foreign-language function,
+        // as the kernel itself could be written in C or unsafe Rust.
+        unsafe {
+            function.launch_on_stream(
+                &pipeline.stream,
+                config,
+                (&mut *pipeline.buckets, total_buckets as u32),
+            )
+        }
+        .map_err(|err| CudaError::Launch(err.to_string()))?;
+        Ok(pipeline)
+    }
+
+    /// Copies a chunk to the device and queues its accumulation into the buckets.
+    fn accumulate(
+        &mut self,
+        state: &CudaMsmState,
+        cs: &[UnsignedInteger<SCALAR_LIMBS>],
+        points: &[ShortWeierstrassProjectivePoint<E>],
+    ) -> Result<(), CudaError> {
+        // The previous chunk of this stream may still be reading the staging buffers
+        state.synchronize_stream(&self.stream)?;
+
+        let len = cs.len();
+        self.points_host.write(points.iter().flat_map(|point| {
+            point
+                .coordinates()
+                .iter()
+                .flat_map(|coordinate| coordinate.value().limbs)
+        }));
+        self.scalars_host
+            .write(cs.iter().flat_map(|scalar| scalar.limbs));
+        unsafe {
+            self.points_host.copy_to_device_async(
+                len * Self::POINT_LIMBS,
+                &mut self.points,
+                &self.stream,
+            )?;
+            self.scalars_host.copy_to_device_async(
+                len * SCALAR_LIMBS,
+                &mut self.scalars,
+                &self.stream,
+            )?;
+        }
+
+        let (window_size, num_windows) = (self.window_size as u32, self.num_windows as u32);
+        let total_buckets = self.total_buckets as u32;
+        let function = |name| state.get_function::<E>(name);
+        // Launching kernels must be done in an unsafe block.
+        // Calling a kernel is similar to calling a foreign-language function,
+        // as the kernel itself could be written in C or unsafe Rust.
+        unsafe {
+            function("msm_clear_counts")?.launch_on_stream(
+                &self.stream,
+                launch_config(self.total_buckets),
+                (&mut *self.counts, total_buckets),
+            )
+        }
+        .map_err(|err| CudaError::Launch(err.to_string()))?;
+        unsafe {
+            function("msm_count_buckets")?.launch_on_stream(
+                &self.stream,
+                launch_config(len),
+                (
+                    &*self.scalars,
+                    SCALAR_LIMBS as u32,
+                    len as u32,
+                    window_size,
+                    num_windows,
+                    &mut *self.counts,
+                ),
+            )
+        }
+        .map_err(|err| CudaError::Launch(err.to_string()))?;
+        unsafe {
+            function("msm_bucket_offsets")?.launch_on_stream(
+                &self.stream,
+                launch_config(self.num_windows),
+                (
+                    &*self.counts,
+                    window_size,
+                    num_windows,
+                    len as u32,
+                    &mut *self.starts,
+                    &mut *self.cursor,
+                ),
+            )
+        }
+        .map_err(|err| CudaError::Launch(err.to_string()))?;
+        unsafe {
+            function("msm_scatter")?.launch_on_stream(
+                &self.stream,
+                launch_config(len),
+                (
+                    &*self.scalars,
+                    SCALAR_LIMBS as u32,
+                    len as u32,
+                    window_size,
+                    num_windows,
+                    &mut *self.cursor,
+                    &mut *self.indices,
+                ),
+            )
+        }
+        .map_err(|err| CudaError::Launch(err.to_string()))?;
+        unsafe {
+            function("msm_accumulate_buckets")?.launch_on_stream(
+                &self.stream,
+                launch_config(self.total_buckets),
+                (
+                    &*self.points,
+                    &*self.indices,
+                    &*self.starts,
+                    &*self.counts,
+                    total_buckets,
+                    &mut *self.buckets,
+                ),
+            )
+        }
+        .map_err(|err| CudaError::Launch(err.to_string()))
+    }
+
+    /// Returns `Σ_d d * bucket_d` for each window.
+    fn reduce(
+        mut self,
+        state: &CudaMsmState,
+    ) -> Result<Vec<ShortWeierstrassProjectivePoint<E>>, CudaError> {
+        let function = state.get_function::<E>("msm_reduce_windows")?;
+        unsafe {
+            function.launch_on_stream(
+                &self.stream,
+                launch_config(self.num_windows),
+                (
+                    &*self.buckets,
+                    self.window_size as u32,
+                    self.num_windows as u32,
+                    &mut *self.window_sums,
+                ),
+            )
+        }
+        .map_err(|err| CudaError::Launch(err.to_string()))?;
+
+        let device = state.device();
+        device
+            .wait_for(&self.stream)
+            .map_err(|err| CudaError::RetrieveMemory(err.to_string()))?;
+        let window_sums = device
+            .dtoh_sync_copy(&*self.window_sums)
+            .map_err(|err| CudaError::RetrieveMemory(err.to_string()))?;
+
+        Ok(window_sums
+            .chunks_exact(Self::POINT_LIMBS)
+            .map(|limbs| {
+                let coordinate = |i: usize| {
+                    let mut value = [0; NUM_LIMBS];
+                    value.copy_from_slice(&limbs[i * NUM_LIMBS..(i + 1) * NUM_LIMBS]);
+                    FieldElement::from_raw(UnsignedInteger::from_limbs(value))
+                };
+                ShortWeierstrassProjectivePoint::new([coordinate(0), coordinate(1), coordinate(2)])
+            })
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        elliptic_curve::{
+            short_weierstrass::curves::{
+                bls12_381::{curve::BLS12381Curve, default_types::FrElement as BLS12381FrElement},
+                bn_254::{curve::BN254Curve, default_types::FrElement as BN254FrElement},
+            },
+            traits::IsEllipticCurve,
+        },
+        msm::pippenger,
+    };
+
+    fn bn254_input(
+        len: usize,
+    ) -> (
+        Vec<UnsignedInteger<4>>,
+        Vec<<BN254Curve as IsEllipticCurve>::PointRepresentation>,
+    ) {
+        let g = BN254Curve::generator();
+        (0..len as u64)
+            .map(|i| {
+                let scalar = BN254FrElement::from(i * i + 7).pow(97_u64).representative();
+                (scalar, g.operate_with_self(3 * i + 1))
+            })
+            .unzip()
+    }
+
+    #[test]
+    fn bn254_cuda_msm_matches_pippenger() {
+        let state = CudaMsmState::new().unwrap();
+        let (cs, points) = bn254_input(300);
+        let expected = pippenger::msm(&cs, &points).unwrap();
+
+        assert_eq!(msm(&cs, &points, &state).unwrap(), expected);
+        // Several chunks on both streams
+        assert_eq!(
+            msm_with_chunk_size(&cs, &points, &state, 64).unwrap(),
+            expected
+        );
+    }
+
+    #[test]
+    fn bls12_381_cuda_msm_matches_pippenger() {
+        let state = CudaMsmState::new().unwrap();
+        let g = BLS12381Curve::generator();
+        let (cs, points): (Vec<_>, Vec<_>) = (0..200_u64)
+            .map(|i| {
+                let scalar = BLS12381FrElement::from(i + 3).pow(131_u64).representative();
+                (scalar, g.operate_with_self(i + 1))
+            })
+            .unzip();
+        let expected = pippenger::msm(&cs, &points).unwrap();
+
+        assert_eq!(
+            msm_with_chunk_size(&cs, &points, &state, 48).unwrap(),
+            expected
+        );
+    }
+
+    #[test]
+    fn cuda_msm_of_neutral_points_and_zero_scalars() {
+        let state = CudaMsmState::new().unwrap();
+        let (mut cs, mut points) = bn254_input(10);
+        cs[3] = UnsignedInteger::from_u64(0);
+        points[5] = ShortWeierstrassProjectivePoint::neutral_element();
+        let expected = pippenger::msm(&cs, &points).unwrap();
+
+        assert_eq!(msm(&cs, &points, &state).unwrap(), expected);
+        assert_eq!(
+            msm::<4, 4, BN254Curve>(&[], &[], &state).unwrap(),
+            ShortWeierstrassProjectivePoint::neutral_element()
+        );
+    }
+
+    #[test]
+    fn cuda_msm_with_different_lengths_should_fail() {
+        let state = CudaMsmState::new().unwrap();
+        let (cs, points) = bn254_input(10);
+
+        assert!(matches!(
+            msm(&cs[..9], &points, &state),
+            Err(CudaError::LengthMismatch(9, 10))
+        ));
+    }
+
+    #[test]
+    fn cuda_msm_across_devices_matches_pippenger() {
+        let devices = CudaDevices::new(&[0, 0, 0]).unwrap();
+        let (cs, points) = bn254_input(301);
+        let expected = pippenger::msm(&cs, &points).unwrap();
+
+        assert_eq!(msm_multi_gpu(&cs, &points, &devices).unwrap(), expected);
+        assert_eq!(
+            msm_multi_gpu(&cs[..2], &points[..2], &devices).unwrap(),
+            pippenger::msm(&cs[..2], &points[..2]).unwrap()
+        );
+        assert!(matches!(
+            msm_multi_gpu(&cs[..9], &points[..10], &devices),
+            Err(CudaError::LengthMismatch(9, 10))
+        ));
+    }
+}
@@ -0,0 +1,121 @@
+use crate::{
+    elliptic_curve::short_weierstrass::{
+        curves::{bls12_381::curve::BLS12381Curve, bn_254::curve::BN254Curve},
+        traits::IsShortWeierstrass,
+    },
+    gpu::cuda::memory::{DeviceMemoryPool, PooledPinned, PooledSlice},
+};
+use cudarc::{
+    driver::{safe::CudaStream, sys, CudaDevice, CudaFunction, DeviceRepr},
+    nvrtc::safe::Ptx,
+};
+use lambdaworks_gpu::cuda::abstractions::errors::CudaError;
+use std::sync::Arc;
+
+const BN254_MSM_PTX: &str = include_str!("../../../gpu/cuda/shaders/msm/bn254.ptx");
+const BLS12_381_MSM_PTX: &str = include_str!("../../../gpu/cuda/shaders/msm/bls12_381.ptx");
+
+const MSM_FUNCTIONS: [&str; 7] = [
+    "msm_clear_counts",
+    "msm_count_buckets",
+    "msm_bucket_offsets",
+    "msm_scatter",
+    "msm_init_buckets",
+    "msm_accumulate_buckets",
+    "msm_reduce_windows",
+];
+
+/// Curves with MSM kernels, identified by the name of their PTX module. The kernels use the
+/// formulas for `a = 0`.
+pub trait IsCudaMsmCurve: IsShortWeierstrass {
+    const MODULE_NAME: &'static str;
+}
+
+impl IsCudaMsmCurve for BN254Curve {
+    const MODULE_NAME: &'static str = "bn254";
+}
+
+impl IsCudaMsmCurve for BLS12381Curve {
+    const MODULE_NAME: &'static str = "bls12_381";
+}
+
+/// Structure for abstracting basic calls to a CUDA device and saving the state. Used for
+/// computing multi-scalar multiplications in CUDA.
+pub struct CudaMsmState {
+    device: Arc<CudaDevice>,
+    pool: DeviceMemoryPool,
+}
+
+impl CudaMsmState {
+    /// Creates a new CUDA state with the first GPU, which takes its buffers from
+    /// [`DeviceMemoryPool::global`].
+    pub fn new() -> Result<Self, CudaError> {
+        Self::with_pool(DeviceMemoryPool::global()?)
+    }
+
+    /// Creates a new CUDA state with the device of `pool`, which takes its buffers from it.
+    pub fn with_pool(pool: DeviceMemoryPool) -> Result<Self, CudaError> {
+        let device = Arc::clone(pool.device());
+        let state = Self { device, pool };
+
+        // Load PTX libraries, once per device
+        state.load_library::<BN254Curve>(BN254_MSM_PTX)?;
+        state.load_library::<BLS12381Curve>(BLS12_381_MSM_PTX)?;
+
+        Ok(state)
+    }
+
+    fn load_library<E: IsCudaMsmCurve>(&self, src: &'static str) -> Result<(), CudaError> {
+        if self.get_function::<E>(MSM_FUNCTIONS[0]).is_ok() {
+            return Ok(());
+        }
+        self.device
+            .load_ptx(Ptx::from_src(src), E::MODULE_NAME, &MSM_FUNCTIONS)
+            .map_err(|err| CudaError::PtxError(err.to_string()))
+    }
+
+    pub(crate) fn get_function<E: IsCudaMsmCurve>(
+        &self,
+        func_name: &str,
+    ) -> Result<CudaFunction, CudaError> {
+        self.device
+            .get_func(E::MODULE_NAME, func_name)
+            .ok_or_else(|| CudaError::FunctionError(func_name.to_string()))
+    }
+
+    pub(crate) fn device(&self) -> &Arc<CudaDevice> {
+        &self.device
+    }
+
+    pub fn pool(&self) -> &DeviceMemoryPool {
+        &self.pool
+    }
+
+    /// Takes a buffer of `len` elements in the GPU from the pool of the state. Its contents are
+    /// unspecified.
+    pub(crate) fn alloc<T: DeviceRepr>(&self, len: usize) -> Result<PooledSlice<T>, CudaError> {
+        self.pool.alloc(len)
+    }
+
+    /// Takes a page-locked host buffer of `len` elements from the pool of the state.
+    pub(crate) fn alloc_pinned<T: DeviceRepr>(
+        &self,
+        len: usize,
+    ) -> Result<PooledPinned<T>, CudaError> {
+        self.pool.alloc_pinned(len)
+    }
+
+    /// Returns a new stream, which starts after the work already queued in the device.
+    pub(crate) fn fork_stream(&self) -> Result<CudaStream, CudaError> {
+        self.device
+            .fork_default_stream()
+            .map_err(|err| CudaError::AllocateMemory(err.to_string()))
+    }
+
+    /// Blocks until the work queued in `stream` is done.
+    pub(crate) fn synchronize_stream(&self, stream: &CudaStream) -> Result<(), CudaError> {
+        unsafe { sys::cuStreamSynchronize(stream.stream) }
+            .result()
+            .map_err(|err| CudaError::Launch(err.to_string()))
+    }
+}
@@ -0,0 +1,294 @@
+use crate::{
+    cyclic_group::IsGroup,
+    elliptic_curve::short_weierstrass::{
+        curves::{bls12_381::curve::BLS12381Curve, bn_254::curve::BN254Curve},
+        point::ShortWeierstrassProjectivePoint,
+        traits::IsShortWeierstrass,
+    },
+    field::{element::FieldElement, traits::IsField},
+    unsigned_integer::element::UnsignedInteger,
+};
+use lambdaworks_gpu::metal::abstractions::{errors::MetalError, state::MetalState};
+use metal::{Buffer, ComputeCommandEncoderRef, ComputePipelineState, MTLSize};
+
+use core::mem;
+
+/// Curves with MSM kernels, identified by the suffix of their kernel names. The kernels use
+/// the formulas for `a = 0`.
+pub trait IsMetalMsmCurve: IsShortWeierstrass {
+    const SHADER_NAME: &'static str;
+}
+
+impl IsMetalMsmCurve for BN254Curve {
+    const SHADER_NAME: &'static str = "bn254";
+}
+
+impl IsMetalMsmCurve for BLS12381Curve {
+    const SHADER_NAME: &'static str = "bls12_381";
+}
+
+/// Computes the multi-scalar multiplication `k_1 * g_1 + ... + k_n * g_n` in Metal, with the
+/// bucket method.
+///
+/// The points are sorted by bucket and accumulated on the device, and so are the sums of the
+/// windows. Only the final combination of the windows, a few hundred doublings, is done on the
+/// host. The result is the same as the one of [`crate::msm::pippenger::msm`].
+pub fn msm<const NUM_LIMBS: usize, const SCALAR_LIMBS: usize, E>(
+    cs: &[UnsignedInteger<SCALAR_LIMBS>],
+    points: &[ShortWeierstrassProjectivePoint<E>],
+    state: &MetalState,
+) -> Result<ShortWeierstrassProjectivePoint<E>, MetalError>
+where
+    E: IsMetalMsmCurve,
+    E::BaseField: IsField<BaseType = UnsignedInteger<NUM_LIMBS>>,
+{
+    if cs.len() != points.len() {
+        return Err(MetalError::LengthMismatch(cs.len(), points.len()));
+    }
+    if cs.is_empty() {
+        return Ok(ShortWeierstrassProjectivePoint::neutral_element());
+    }
+
+    let len = cs.len();
+    let window_size = window_size(len);
+    let num_windows = (64 * SCALAR_LIMBS).div_ceil(window_size);
+    let total_buckets = ((1 << window_size) - 1) * num_windows;
+    let point_limbs = 3 * NUM_LIMBS;
+
+    let points_limbs: Vec<u64> = points
+        .iter()
+        .flat_map(|point| {
+            point
+                .coordinates()
+                .iter()
+                .flat_map(|coordinate| coordinate.value().limbs)
+        })
+        .collect();
+    let scalars_limbs: Vec<u64> = cs.iter().flat_map(|scalar| scalar.limbs).collect();
+
+    let points_buffer = state.alloc_buffer_data(&points_limbs);
+    let scalars_buffer = state.alloc_buffer_data(&scalars_limbs);
+    let counts_buffer = state.alloc_buffer::<u32>(total_buckets);
+    let starts_buffer = state.alloc_buffer::<u32>(total_buckets);
+    let cursor_buffer = state.alloc_buffer::<u32>(total_buckets);
+    let indices_buffer = state.alloc_buffer::<u32>(num_windows * len);
+    let buckets_buffer = state.alloc_buffer::<u64>(total_buckets * point_limbs);
+    let window_sums_buffer = state.alloc_buffer::<u64>(num_windows * point_limbs);
+
+    let curve_pipeline = |name| state.setup_pipeline(&format!("{name}_{}", E::SHADER_NAME));
+    let init_buckets = curve_pipeline("msm_init_buckets")?;
+    let clear_counts = state.setup_pipeline("msm_clear_counts")?;
+    let count_buckets = state.setup_pipeline("msm_count_buckets")?;
+    let bucket_offsets = state.setup_pipeline("msm_bucket_offsets")?;
+    let scatter = state.setup_pipeline("msm_scatter")?;
+    let accumulate_buckets = curve_pipeline("msm_accumulate_buckets")?;
+    let reduce_windows = curve_pipeline("msm_reduce_windows")?;
+
+    let (scalar_limbs, window_size_u32) = (SCALAR_LIMBS as u32, window_size as u32);
+    let (num_windows_u32, len_u32) = (num_windows as u32, len as u32);
+
+    objc::rc::autoreleasepool(|| {
+        // Dispatches of the same encoder run one after the other, so each kernel sees the
+        // writes of the previous ones.
+        let command_buffer = state.queue.new_command_buffer();
+        let command_encoder = command_buffer.new_compute_command_encoder();
+
+        dispatch(
+            command_encoder,
+            &init_buckets,
+            &[&buckets_buffer],
+            &[],
+            total_buckets,
+        );
+        dispatch(
+            command_encoder,
+            &clear_counts,
+            &[&counts_buffer],
+            &[],
+            total_buckets,
+        );
+        dispatch(
+            command_encoder,
+            &count_buckets,
+            &[&scalars_buffer, &counts_buffer],
+            &[scalar_limbs, window_size_u32, num_windows_u32],
+            len,
+        );
+        dispatch(
+            command_encoder,
+            &bucket_offsets,
+            &[&counts_buffer, &starts_buffer, &cursor_buffer],
+            &[window_size_u32, len_u32],
+            num_windows,
+        );
+        dispatch(
+            command_encoder,
+            &scatter,
+            &[&scalars_buffer, &cursor_buffer, &indices_buffer],
+            &[scalar_limbs, window_size_u32, num_windows_u32],
+            len,
+        );
+        dispatch(
+            command_encoder,
+            &accumulate_buckets,
+            &[
+                &points_buffer,
+                &indices_buffer,
+                &starts_buffer,
+                &counts_buffer,
+                &buckets_buffer,
+            ],
+            &[],
+            total_buckets,
+        );
+        dispatch(
+            command_encoder,
+            &reduce_windows,
+            &[&buckets_buffer, &window_sums_buffer],
+            &[window_size_u32],
+            num_windows,
+        );
+        command_encoder.end_encoding();
+
+        command_buffer.commit();
+        command_buffer.wait_until_completed();
+    });
+
+    let window_sums: Vec<_> = MetalState::retrieve_contents::<u64>(&window_sums_buffer)
+        .chunks_exact(point_limbs)
+        .map(|limbs| {
+            let coordinate = |i: usize| {
+                let mut value = [0; NUM_LIMBS];
+                value.copy_from_slice(&limbs[i * NUM_LIMBS..(i + 1) * NUM_LIMBS]);
+                FieldElement::from_raw(UnsignedInteger::from_limbs(value))
+            };
+            ShortWeierstrassProjectivePoint::<E>::new([coordinate(0), coordinate(1), coordinate(2)])
+        })
+        .collect();
+
+    Ok(window_sums.iter().rev().fold(
+        ShortWeierstrassProjectivePoint::neutral_element(),
+        |acc, sum| {
+            (0..window_size)
+                .fold(acc, |acc, _| acc.double())
+                .operate_with(sum)
+        },
+    ))
+}
+
+/// Window size in bits. There are `2^window_size - 1` buckets per window.
+fn window_size(len: usize) -> usize {
+    (len.ilog2() as usize).saturating_sub(3).clamp(4, 13)
+}
+
+/// Queues `threads` threads of `pipeline`, with `buffers` bound from index 0 and the
+/// `constants` after them.
+fn dispatch(
+    command_encoder: &ComputeCommandEncoderRef,
+    pipeline: &ComputePipelineState,
+    buffers: &[&Buffer],
+    constants: &[u32],
+    threads: usize,
+) {
+    command_encoder.set_compute_pipeline_state(pipeline);
+    for (i, buffer) in buffers.iter().enumerate() {
+        command_encoder.set_buffer(i as u64, Some(buffer), 0);
+    }
+    for (i, constant) in constants.iter().enumerate() {
+        command_encoder.set_bytes(
+            (buffers.len() + i) as u64,
+            mem::size_of_val(constant) as u64,
+            void_ptr(constant),
+        );
+    }
+
+    let grid_size = MTLSize::new(threads as u64, 1, 1);
+    let threadgroup_size = MTLSize::new(pipeline.thread_execution_width(), 1, 1);
+
+    // WARN: Device should support non-uniform threadgroups (Metal3 and Apple4 or latter).
+    command_encoder.dispatch_threads(grid_size, threadgroup_size);
+}
+
+fn void_ptr<T>(v: &T) -> *const core::ffi::c_void {
+    v as *const T as *const core::ffi::c_void
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        elliptic_curve::{
+            short_weierstrass::curves::{
+                bls12_381::default_types::FrElement as BLS12381FrElement,
+                bn_254::default_types::FrElement as BN254FrElement,
+            },
+            traits::IsEllipticCurve,
+        },
+        msm::pippenger,
+    };
+
+    fn bn254_input(
+        len: usize,
+    ) -> (
+        Vec<UnsignedInteger<4>>,
+        Vec<<BN254Curve as IsEllipticCurve>::PointRepresentation>,
+    ) {
+        let g = BN254Curve::generator();
+        (0..len as u64)
+            .map(|i| {
+                let scalar = BN254FrElement::from(i * i + 7).pow(97_u64).representative();
+                (scalar, g.operate_with_self(3 * i + 1))
+            })
+            .unzip()
+    }
+
+    #[test]
+    fn bn254_metal_msm_matches_pippenger() {
+        let state = MetalState::new(None).unwrap();
+        let (cs, points) = bn254_input(300);
+        let expected = pippenger::msm(&cs, &points).unwrap();
+
+        assert_eq!(msm(&cs, &points, &state).unwrap(), expected);
+    }
+
+    #[test]
+    fn bls12_381_metal_msm_matches_pippenger() {
+        let state = MetalState::new(None).unwrap();
+        let g = BLS12381Curve::generator();
+        let (cs, points): (Vec<_>, Vec<_>) = (0..200_u64)
+            .map(|i| {
+                let scalar = BLS12381FrElement::from(i + 3).pow(131_u64).representative();
+                (scalar, g.operate_with_self(i + 1))
+            })
+            .unzip();
+        let expected = pippenger::msm(&cs, &points).unwrap();
+
+        assert_eq!(msm(&cs, &points, &state).unwrap(), expected);
+    }
+
+    #[test]
+    fn metal_msm_of_neutral_points_and_zero_scalars() {
+        let state = MetalState::new(None).unwrap();
+        let (mut cs, mut points) = bn254_input(10);
+        cs[3] = UnsignedInteger::from_u64(0);
+        points[5] = ShortWeierstrassProjectivePoint::neutral_element();
+        let expected = pippenger::msm(&cs, &points).unwrap();
+
+        assert_eq!(msm(&cs, &points, &state).unwrap(), expected);
+        assert_eq!(
+            msm::<4, 4, BN254Curve>(&[], &[], &state).unwrap(),
+            ShortWeierstrassProjectivePoint::neutral_element()
+        );
+    }
+
+    #[test]
+    fn metal_msm_with_different_lengths_should_fail() {
+        let state = MetalState::new(None).unwrap();
+        let (cs, points) = bn254_input(10);
+
+        assert!(matches!(
+            msm(&cs[..9], &points, &state),
+            Err(MetalError::LengthMismatch(9, 10))
+        ));
+    }
+}
@@ -1,3 +1,4 @@
+pub mod gpu;
 pub mod naive;
 #[cfg(feature = "alloc")]
 pub mod pippenger;
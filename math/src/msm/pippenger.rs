@@ -31,6 +31,28 @@ where
     Ok(msm_with(cs, points, window_size))
 }
 
+/// Parallel version of [`msm`]. Computes the same result, but the windows are
+/// processed concurrently with rayon.
+///
+/// Panics if `cs` and `points` have different lengths.
+#[cfg(feature = "parallel")]
+pub fn parallel_msm<const NUM_LIMBS: usize, G>(
+    cs: &[UnsignedInteger<NUM_LIMBS>],
+    points: &[G],
+) -> Result<G, MSMError>
+where
+    G: IsGroup + Send + Sync,
+{
+    if cs.len() != points.len() {
+        return Err(MSMError::LengthMismatch(cs.len(), points.len()));
+    }
+
+    // `parallel_msm_with` does not clamp the window size, so we avoid empty windows here.
+    let window_size = optimum_window_size(cs.len()).max(2);
+
+    Ok(parallel_msm_with(cs, points, window_size))
+}
+
 fn optimum_window_size(data_length: usize) -> usize {
     const SCALE_FACTORS: (usize, usize) = (4, 5);
 
@@ -231,5 +253,19 @@ mod tests {
 
             prop_assert_eq!(parallel, sequential);
         }
+
+        // Property-based test that ensures `pippenger::parallel_msm` gives same result as `pippenger::msm`.
+        #[test]
+        #[cfg(feature = "parallel")]
+        fn test_parallel_msm_matches_msm(cs in unsigned_integer_vec(), points in points_vec()) {
+            let min_len = cs.len().min(points.len());
+            let cs = cs[..min_len].to_vec();
+            let points = points[..min_len].to_vec();
+
+            let sequential = pippenger::msm(&cs, &points).unwrap();
+            let parallel = pippenger::parallel_msm(&cs, &points).unwrap();
+
+            prop_assert_eq!(parallel, sequential);
+        }
     }
 }
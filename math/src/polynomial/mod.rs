@@ -3,6 +3,13 @@ use crate::field::traits::{IsField, IsPrimeField, IsSubFieldOf};
 use alloc::string::{String, ToString};
 use alloc::{borrow::ToOwned, format, vec, vec::Vec};
 use core::{fmt::Display, ops};
+#[cfg(feature = "proptest")]
+use proptest::{
+    arbitrary::Arbitrary,
+    collection::{self, SizeRange},
+    prelude::any,
+    strategy::{BoxedStrategy, Strategy},
+};
 pub mod dense_multilinear_poly;
 mod error;
 pub mod sparse_multilinear_poly;
@@ -885,6 +892,24 @@ impl Display for InterpolateError {
 #[cfg(feature = "std")]
 impl std::error::Error for InterpolateError {}
 
+/// Generates polynomials with random coefficients. The parameter is the range of the number of
+/// coefficients before trailing zeros are removed.
+#[cfg(feature = "proptest")]
+impl<F: IsField + 'static> Arbitrary for Polynomial<FieldElement<F>>
+where
+    FieldElement<F>: Arbitrary,
+{
+    type Parameters = SizeRange;
+
+    fn arbitrary_with(num_coefficients: Self::Parameters) -> Self::Strategy {
+        collection::vec(any::<FieldElement<F>>(), num_coefficients)
+            .prop_map(|coefficients| Polynomial::new(&coefficients))
+            .boxed()
+    }
+
+    type Strategy = BoxedStrategy<Self>;
+}
+
 #[cfg(test)]
 mod tests {
     use crate::field::fields::u64_prime_field::U64PrimeField;
@@ -1192,6 +1217,20 @@ mod tests {
         }
     }
 
+    #[cfg(feature = "proptest")]
+    proptest! {
+        #[test]
+        fn long_division_satisfies_the_division_identity(
+            p in any::<Polynomial<FE>>(),
+            d in any_with::<Polynomial<FE>>((1..8).into()),
+        ) {
+            prop_assume!(d != Polynomial::zero());
+            let (q, r) = p.clone().long_division_with_remainder(&d);
+            prop_assert!(r == Polynomial::zero() || r.degree() < d.degree());
+            prop_assert_eq!(q * d + r, p);
+        }
+    }
+
     proptest! {
         #[test]
         fn ruffini_inplace_equals_ruffini(p in any::<Vec<u64>>(), b in any::<u64>()) {
@@ -27,6 +27,47 @@ pub trait ByteConversion {
         Self: Sized;
 }
 
+/// Canonical byte encodings of a fixed length, for the types whose elements all encode to the
+/// same number of bytes. Unlike [`ByteConversion`], whose decoding truncates or reduces its
+/// input, decoding only accepts the bytes that encoding some element outputs, so each element
+/// has exactly one encoding.
+pub trait CanonicalByteConversion: Sized {
+    /// Array holding an encoding.
+    type Bytes: AsRef<[u8]> + AsMut<[u8]> + Copy + for<'a> TryFrom<&'a [u8]>;
+
+    /// Returns the encoding of the element in big-endian order.
+    fn to_bytes_be_array(&self) -> Self::Bytes;
+
+    /// Returns the encoding of the element in little-endian order.
+    fn to_bytes_le_array(&self) -> Self::Bytes;
+
+    /// Returns the element encoded in big-endian order by `bytes`, or
+    /// [`ByteConversionError::InvalidValue`] if they are not the encoding of any element.
+    fn from_bytes_be_array(bytes: &Self::Bytes) -> Result<Self, ByteConversionError>;
+
+    /// Returns the element encoded in little-endian order by `bytes`, or
+    /// [`ByteConversionError::InvalidValue`] if they are not the encoding of any element.
+    fn from_bytes_le_array(bytes: &Self::Bytes) -> Result<Self, ByteConversionError>;
+
+    /// Like [`Self::from_bytes_be_array`], for a slice that must have the length of the
+    /// encodings.
+    fn from_bytes_be_canonical(bytes: &[u8]) -> Result<Self, ByteConversionError> {
+        let bytes = bytes
+            .try_into()
+            .map_err(|_| ByteConversionError::FromBEBytesError)?;
+        Self::from_bytes_be_array(&bytes)
+    }
+
+    /// Like [`Self::from_bytes_le_array`], for a slice that must have the length of the
+    /// encodings.
+    fn from_bytes_le_canonical(bytes: &[u8]) -> Result<Self, ByteConversionError> {
+        let bytes = bytes
+            .try_into()
+            .map_err(|_| ByteConversionError::FromLEBytesError)?;
+        Self::from_bytes_le_array(&bytes)
+    }
+}
+
 /// Serialize function without args
 /// Used for serialization when formatting options are not relevant
 #[cfg(feature = "alloc")]
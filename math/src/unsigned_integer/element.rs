@@ -16,7 +16,7 @@ use crate::errors::ByteConversionError;
 use crate::errors::CreationError;
 #[cfg(feature = "alloc")]
 use crate::traits::AsBytes;
-use crate::traits::ByteConversion;
+use crate::traits::{ByteConversion, CanonicalByteConversion};
 use crate::unsigned_integer::traits::IsUnsignedInteger;
 
 use core::fmt::{self, Debug, Display};
@@ -988,6 +988,52 @@ impl<const NUM_LIMBS: usize> ByteConversion for UnsignedInteger<NUM_LIMBS> {
     }
 }
 
+/// Implements [`CanonicalByteConversion`] for integers of `$num_limbs` limbs, which encode to
+/// `$num_bytes` bytes. Every array is the encoding of an integer.
+macro_rules! impl_canonical_byte_conversion {
+    ($(($num_limbs:literal, $num_bytes:literal)),*) => {
+        $(
+            impl CanonicalByteConversion for UnsignedInteger<$num_limbs> {
+                type Bytes = [u8; $num_bytes];
+
+                fn to_bytes_be_array(&self) -> Self::Bytes {
+                    let mut bytes = [0; $num_bytes];
+                    for (chunk, limb) in bytes.chunks_exact_mut(8).zip(self.limbs.iter()) {
+                        chunk.copy_from_slice(&limb.to_be_bytes());
+                    }
+                    bytes
+                }
+
+                fn to_bytes_le_array(&self) -> Self::Bytes {
+                    let mut bytes = [0; $num_bytes];
+                    for (chunk, limb) in bytes.chunks_exact_mut(8).zip(self.limbs.iter().rev()) {
+                        chunk.copy_from_slice(&limb.to_le_bytes());
+                    }
+                    bytes
+                }
+
+                fn from_bytes_be_array(bytes: &Self::Bytes) -> Result<Self, ByteConversionError> {
+                    let mut limbs = [0; $num_limbs];
+                    for (limb, chunk) in limbs.iter_mut().zip(bytes.chunks_exact(8)) {
+                        *limb = u64::from_be_bytes(chunk.try_into().unwrap());
+                    }
+                    Ok(Self { limbs })
+                }
+
+                fn from_bytes_le_array(bytes: &Self::Bytes) -> Result<Self, ByteConversionError> {
+                    let mut limbs = [0; $num_limbs];
+                    for (limb, chunk) in limbs.iter_mut().rev().zip(bytes.chunks_exact(8)) {
+                        *limb = u64::from_le_bytes(chunk.try_into().unwrap());
+                    }
+                    Ok(Self { limbs })
+                }
+            }
+        )*
+    };
+}
+
+impl_canonical_byte_conversion!((1, 8), (2, 16), (4, 32), (6, 48), (8, 64), (12, 96));
+
 impl<const NUM_LIMBS: usize> From<UnsignedInteger<NUM_LIMBS>> for u16 {
     fn from(value: UnsignedInteger<NUM_LIMBS>) -> Self {
         value.limbs[NUM_LIMBS - 1] as u16
@@ -2121,7 +2167,7 @@ mod tests_u384 {
 
 #[cfg(test)]
 mod tests_u256 {
-    use crate::unsigned_integer::element::ByteConversion;
+    use crate::unsigned_integer::element::{ByteConversion, CanonicalByteConversion};
     use crate::unsigned_integer::element::{UnsignedInteger, U256};
     #[cfg(feature = "proptest")]
     use proptest::prelude::*;
@@ -3112,4 +3158,14 @@ mod tests_u256 {
         let a = U256::from_hex_unchecked("390aa99bead76bc0093b1bc1a8101f5ce");
         assert_eq!(U256::to_hex(&a), "390AA99BEAD76BC0093B1BC1A8101F5CE")
     }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn canonical_bytes_match_byte_conversion() {
+        let a = U256::from_hex_unchecked("390aa99bead76bc0093b1bc1a8101f5ce");
+        assert_eq!(a.to_bytes_be_array().to_vec(), a.to_bytes_be());
+        assert_eq!(a.to_bytes_le_array().to_vec(), a.to_bytes_le());
+        assert_eq!(U256::from_bytes_be_array(&a.to_bytes_be_array()), Ok(a));
+        assert_eq!(U256::from_bytes_le_array(&a.to_bytes_le_array()), Ok(a));
+    }
 }
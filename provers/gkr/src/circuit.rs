@@ -0,0 +1,128 @@
+use lambdaworks_crypto::fiat_shamir::is_transcript::IsTranscript;
+use lambdaworks_math::field::{element::FieldElement, traits::IsField};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GateType {
+    Add,
+    Mul,
+}
+
+/// A fan-in two gate, reading the values at `inputs` from the layer below.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Gate {
+    pub ty: GateType,
+    pub inputs: [usize; 2],
+}
+
+impl Gate {
+    pub fn new(ty: GateType, inputs: [usize; 2]) -> Self {
+        Self { ty, inputs }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Layer {
+    pub gates: Vec<Gate>,
+}
+
+impl Layer {
+    pub fn new(gates: Vec<Gate>) -> Self {
+        Self { gates }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum CircuitError {
+    EmptyCircuit,
+    EmptyLayer(usize),
+    /// A gate of the given layer reads a value out of the layer below.
+    InputOutOfRange(usize),
+}
+
+/// A layered arithmetic circuit. The first layer is the output layer and gates of each layer
+/// read from the next one, the last layer reading from the input. Layers are padded with zeros
+/// to a power of two when taking multilinear extensions.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Circuit {
+    layers: Vec<Layer>,
+    num_inputs: usize,
+}
+
+impl Circuit {
+    pub fn new(layers: Vec<Layer>, num_inputs: usize) -> Result<Self, CircuitError> {
+        if layers.is_empty() || num_inputs == 0 {
+            return Err(CircuitError::EmptyCircuit);
+        }
+        for (i, layer) in layers.iter().enumerate() {
+            if layer.gates.is_empty() {
+                return Err(CircuitError::EmptyLayer(i));
+            }
+            let below = layers.get(i + 1).map_or(num_inputs, |l| l.gates.len());
+            if layer
+                .gates
+                .iter()
+                .any(|gate| gate.inputs.iter().any(|input| *input >= below))
+            {
+                return Err(CircuitError::InputOutOfRange(i));
+            }
+        }
+        Ok(Self { layers, num_inputs })
+    }
+
+    pub fn layers(&self) -> &[Layer] {
+        &self.layers
+    }
+
+    pub fn num_inputs(&self) -> usize {
+        self.num_inputs
+    }
+
+    /// Appends the circuit to `transcript`: the number of inputs and of layers, then the number
+    /// of gates of each layer and the type and inputs of each gate.
+    pub fn append_to_transcript<F: IsField>(&self, transcript: &mut impl IsTranscript<F>) {
+        transcript.append_bytes(&(self.num_inputs as u64).to_le_bytes());
+        transcript.append_bytes(&(self.layers.len() as u64).to_le_bytes());
+        for layer in &self.layers {
+            transcript.append_bytes(&(layer.gates.len() as u64).to_le_bytes());
+            for gate in &layer.gates {
+                let ty = match gate.ty {
+                    GateType::Add => 0u8,
+                    GateType::Mul => 1u8,
+                };
+                transcript.append_bytes(&[ty]);
+                for input in gate.inputs {
+                    transcript.append_bytes(&(input as u64).to_le_bytes());
+                }
+            }
+        }
+    }
+
+    /// Returns the values of every layer, from the output layer to the input, each padded with
+    /// zeros to a power of two.
+    pub fn evaluate<F: IsField>(&self, input: &[FieldElement<F>]) -> Vec<Vec<FieldElement<F>>> {
+        assert_eq!(input.len(), self.num_inputs);
+        let mut values = vec![pad(input.to_vec())];
+        for layer in self.layers.iter().rev() {
+            let below = values.last().unwrap();
+            let layer_values = layer
+                .gates
+                .iter()
+                .map(|gate| {
+                    let [a, b] = gate.inputs.map(|i| &below[i]);
+                    match gate.ty {
+                        GateType::Add => a + b,
+                        GateType::Mul => a * b,
+                    }
+                })
+                .collect();
+            values.push(pad(layer_values));
+        }
+        values.reverse();
+        values
+    }
+}
+
+fn pad<F: IsField>(mut values: Vec<FieldElement<F>>) -> Vec<FieldElement<F>> {
+    values.resize(values.len().next_power_of_two(), FieldElement::zero());
+    values
+}
@@ -0,0 +1,17 @@
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GkrError {
+    /// The sumcheck proof does not have one round polynomial per variable.
+    WrongNumberOfRounds,
+    /// A round polynomial is not given by `degree + 1` evaluations.
+    WrongRoundPolynomialDegree(usize),
+    /// `s(0) + s(1)` does not match the claim of the round.
+    RoundSumMismatch(usize),
+    /// The final sumcheck claim does not match the values sent for the layer.
+    LayerCheckFailed(usize),
+    /// The proof does not have one entry per layer of the circuit.
+    WrongNumberOfLayers,
+    /// The claimed outputs do not match the output layer of the circuit.
+    WrongNumberOfOutputs,
+    /// The claims reduced to the input layer do not match the input.
+    InputCheckFailed,
+}
@@ -0,0 +1,7 @@
+pub mod circuit;
+pub mod errors;
+pub mod logup;
+pub mod prover;
+pub mod sumcheck;
+pub mod utils;
+pub mod verifier;
@@ -0,0 +1,245 @@
+//! GKR for sums of fractions `Σ pᵢ / qᵢ`, the backend of LogUp-GKR lookup arguments.
+//!
+//! Fractions are added in pairs, `p/q + p'/q' = (p q' + p' q) / (q q')`, along a binary tree
+//! whose root is the sum. Each layer of the tree is reduced to the one below with a degree 3
+//! sumcheck, so the prover only works in time linear in the number of fractions and never
+//! inverts a denominator. The proof ends with a claim on the multilinear extensions of the
+//! numerators and denominators at a random point, which the caller checks against its own
+//! commitments to them, for instance with a multilinear polynomial commitment scheme.
+
+use lambdaworks_crypto::fiat_shamir::is_transcript::IsTranscript;
+use lambdaworks_math::field::{element::FieldElement, traits::IsField};
+
+use crate::{
+    errors::GkrError,
+    sumcheck::{self, SumcheckProof},
+    utils::{eq, eq_evals, evaluate_mle},
+};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FractionLayerProof<F: IsField> {
+    pub sumcheck: SumcheckProof<F>,
+    /// `p(s, 0), p(s, 1), q(s, 0), q(s, 1)` on the layer below, `s` being the sumcheck point.
+    pub evaluations: [FieldElement<F>; 4],
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FractionalSumProof<F: IsField> {
+    /// Numerator and denominator of the sum.
+    pub sum: [FieldElement<F>; 2],
+    /// Proofs for each layer, from the root down to the input fractions.
+    pub layers: Vec<FractionLayerProof<F>>,
+}
+
+/// The outcome of a verified [`FractionalSumProof`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FractionalSumClaim<F: IsField> {
+    pub numerator: FieldElement<F>,
+    pub denominator: FieldElement<F>,
+    /// The point at which the input multilinear extensions were reduced.
+    pub point: Vec<FieldElement<F>>,
+    /// Claimed value of the multilinear extension of the numerators at `point`.
+    pub numerators_evaluation: FieldElement<F>,
+    /// Claimed value of the multilinear extension of the denominators at `point`.
+    pub denominators_evaluation: FieldElement<F>,
+}
+
+/// Proves the sum of `numerators[i] / denominators[i]`. The number of fractions must be a power
+/// of two; pad with `0 / 1` otherwise.
+pub fn prove_fractional_sum<F, T>(
+    numerators: Vec<FieldElement<F>>,
+    denominators: Vec<FieldElement<F>>,
+    transcript: &mut T,
+) -> FractionalSumProof<F>
+where
+    F: IsField,
+    T: IsTranscript<F>,
+{
+    assert!(numerators.len().is_power_of_two() && numerators.len() == denominators.len());
+
+    // tree[0] holds the input fractions and the last entry the sum
+    let mut tree = vec![(numerators, denominators)];
+    while tree.last().unwrap().0.len() > 1 {
+        let (p, q) = tree.last().unwrap();
+        let (parent_p, parent_q) = (0..p.len() / 2)
+            .map(|i| {
+                let (p0, p1, q0, q1) = (&p[2 * i], &p[2 * i + 1], &q[2 * i], &q[2 * i + 1]);
+                (p0 * q1 + p1 * q0, q0 * q1)
+            })
+            .unzip();
+        tree.push((parent_p, parent_q));
+    }
+
+    let (root_p, root_q) = tree.pop().unwrap();
+    let sum = [root_p[0].clone(), root_q[0].clone()];
+    transcript.append_field_element(&sum[0]);
+    transcript.append_field_element(&sum[1]);
+
+    let mut point = Vec::new();
+    let mut layers = Vec::with_capacity(tree.len());
+    for (p, q) in tree.iter().rev() {
+        let lambda = transcript.sample_field_element();
+        let half = p.len() / 2;
+        let split = |values: &Vec<FieldElement<F>>, bit: usize| -> Vec<_> {
+            (0..half).map(|i| values[2 * i + bit].clone()).collect()
+        };
+        let tables = vec![
+            eq_evals(&point),
+            split(p, 0),
+            split(p, 1),
+            split(q, 0),
+            split(q, 1),
+        ];
+        let (sumcheck, mut s) = sumcheck::prove(
+            tables,
+            3,
+            |v| &v[0] * (&lambda * (&v[1] * &v[4] + &v[2] * &v[3]) + &v[3] * &v[4]),
+            transcript,
+        );
+
+        let evaluations = [
+            evaluate_mle(&split(p, 0), &s),
+            evaluate_mle(&split(p, 1), &s),
+            evaluate_mle(&split(q, 0), &s),
+            evaluate_mle(&split(q, 1), &s),
+        ];
+        for evaluation in &evaluations {
+            transcript.append_field_element(evaluation);
+        }
+        s.push(transcript.sample_field_element());
+        point = s;
+        layers.push(FractionLayerProof {
+            sumcheck,
+            evaluations,
+        });
+    }
+
+    FractionalSumProof { sum, layers }
+}
+
+/// Checks a [`FractionalSumProof`] over `2^num_vars` fractions. The returned claim on the input
+/// multilinear extensions must still be checked by the caller.
+pub fn verify_fractional_sum<F, T>(
+    num_vars: usize,
+    proof: &FractionalSumProof<F>,
+    transcript: &mut T,
+) -> Result<FractionalSumClaim<F>, GkrError>
+where
+    F: IsField,
+    T: IsTranscript<F>,
+{
+    if proof.layers.len() != num_vars {
+        return Err(GkrError::WrongNumberOfLayers);
+    }
+    let [numerator, denominator] = proof.sum.clone();
+    transcript.append_field_element(&numerator);
+    transcript.append_field_element(&denominator);
+
+    let mut point = Vec::new();
+    let (mut p_claim, mut q_claim) = (numerator.clone(), denominator.clone());
+    for (i, layer) in proof.layers.iter().enumerate() {
+        let lambda = transcript.sample_field_element();
+        let claim = &lambda * &p_claim + &q_claim;
+        let (final_claim, mut s) = sumcheck::verify(i, 3, claim, &layer.sumcheck, transcript)?;
+
+        let [p0, p1, q0, q1] = &layer.evaluations;
+        if final_claim != eq(&point, &s) * (&lambda * (p0 * q1 + p1 * q0) + q0 * q1) {
+            return Err(GkrError::LayerCheckFailed(i));
+        }
+        for evaluation in &layer.evaluations {
+            transcript.append_field_element(evaluation);
+        }
+        let t = transcript.sample_field_element();
+        p_claim = p0 + &t * (p1 - p0);
+        q_claim = q0 + &t * (q1 - q0);
+        s.push(t);
+        point = s;
+    }
+
+    Ok(FractionalSumClaim {
+        numerator,
+        denominator,
+        point,
+        numerators_evaluation: p_claim,
+        denominators_evaluation: q_claim,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use lambdaworks_crypto::fiat_shamir::default_transcript::DefaultTranscript;
+    use lambdaworks_math::elliptic_curve::short_weierstrass::curves::bls12_381::default_types::{
+        FrElement, FrField,
+    };
+
+    /// LogUp fractions for the lookup of `values` into `table` with `multiplicities`:
+    /// `Σ 1 / (α - vᵢ) - Σ mⱼ / (α - tⱼ)`, which is zero when every value is in the table.
+    fn lookup_fractions(
+        values: &[u64],
+        table: &[u64],
+        multiplicities: &[u64],
+    ) -> [Vec<FrElement>; 2] {
+        let alpha = FrElement::from(1000);
+        let numerators = values
+            .iter()
+            .map(|_| FrElement::one())
+            .chain(multiplicities.iter().map(|m| -FrElement::from(*m)))
+            .collect();
+        let denominators = values
+            .iter()
+            .chain(table)
+            .map(|v| &alpha - FrElement::from(*v))
+            .collect();
+        [numerators, denominators]
+    }
+
+    #[test]
+    fn lookup_sum_is_zero_and_input_claim_holds() {
+        let [numerators, denominators] =
+            lookup_fractions(&[1, 3, 3, 2], &[1, 2, 3, 4], &[1, 1, 2, 0]);
+        let proof = prove_fractional_sum(
+            numerators.clone(),
+            denominators.clone(),
+            &mut DefaultTranscript::<FrField>::default(),
+        );
+        let claim = verify_fractional_sum(3, &proof, &mut DefaultTranscript::default()).unwrap();
+
+        assert_eq!(claim.numerator, FrElement::zero());
+        assert_ne!(claim.denominator, FrElement::zero());
+        assert_eq!(
+            evaluate_mle(&numerators, &claim.point),
+            claim.numerators_evaluation
+        );
+        assert_eq!(
+            evaluate_mle(&denominators, &claim.point),
+            claim.denominators_evaluation
+        );
+    }
+
+    #[test]
+    fn value_missing_from_the_table_gives_a_nonzero_sum() {
+        let [numerators, denominators] =
+            lookup_fractions(&[1, 3, 5, 2], &[1, 2, 3, 4], &[1, 1, 2, 0]);
+        let proof = prove_fractional_sum(
+            numerators,
+            denominators,
+            &mut DefaultTranscript::<FrField>::default(),
+        );
+        let claim = verify_fractional_sum(3, &proof, &mut DefaultTranscript::default()).unwrap();
+        assert_ne!(claim.numerator, FrElement::zero());
+    }
+
+    #[test]
+    fn forged_sum_is_rejected() {
+        let [numerators, denominators] =
+            lookup_fractions(&[1, 3, 5, 2], &[1, 2, 3, 4], &[1, 1, 2, 0]);
+        let mut proof = prove_fractional_sum(
+            numerators,
+            denominators,
+            &mut DefaultTranscript::<FrField>::default(),
+        );
+        proof.sum[0] = FrElement::zero();
+        assert!(verify_fractional_sum(3, &proof, &mut DefaultTranscript::default()).is_err());
+    }
+}
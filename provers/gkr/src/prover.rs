@@ -0,0 +1,127 @@
+use lambdaworks_crypto::fiat_shamir::is_transcript::IsTranscript;
+use lambdaworks_math::field::{element::FieldElement, traits::IsField};
+
+use crate::{
+    circuit::{Circuit, Gate, GateType},
+    sumcheck::{self, SumcheckProof},
+    utils::{append_statement, combined_eq_evals, eq_evals, evaluate_mle, num_vars},
+};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LayerProof<F: IsField> {
+    /// Sumcheck reducing the claims on the layer to claims on the layer below.
+    pub sumcheck: SumcheckProof<F>,
+    /// Values of the layer below at the two halves `r_b`, `r_c` of the sumcheck point.
+    pub claims: [FieldElement<F>; 2],
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct GkrProof<F: IsField> {
+    /// Values of the output layer, padded with zeros to a power of two.
+    pub outputs: Vec<FieldElement<F>>,
+    pub layers: Vec<LayerProof<F>>,
+}
+
+/// Proves the evaluation of `circuit` on `input`. The circuit and the input are appended to
+/// `transcript` before the outputs.
+///
+/// Each layer `i` is reduced to the one below with a sumcheck over
+/// `Σ_{b,c} add(b, c) (W(b) + W(c)) + mul(b, c) W(b) W(c)`, where the wiring predicates are
+/// weighted by the claims on layer `i`. The prover binds the variables of `b` and then the ones
+/// of `c` in two phases, as in [Libra](https://eprint.iacr.org/2019/317), so that each layer
+/// costs time linear in its number of gates and the size of the layer below.
+pub fn prove<F, T>(circuit: &Circuit, input: &[FieldElement<F>], transcript: &mut T) -> GkrProof<F>
+where
+    F: IsField,
+    T: IsTranscript<F>,
+{
+    let values = circuit.evaluate(input);
+    let outputs = values[0].clone();
+    append_statement(circuit, input, transcript);
+    for output in &outputs {
+        transcript.append_field_element(output);
+    }
+    let r = (0..num_vars(outputs.len()))
+        .map(|_| transcript.sample_field_element())
+        .collect();
+    let mut points = vec![r];
+
+    let mut layers = Vec::with_capacity(circuit.layers().len());
+    for (layer, below) in circuit.layers().iter().zip(&values[1..]) {
+        let lambda = transcript.sample_field_element();
+        let weights = combined_eq_evals(&points, &lambda);
+        let (sumcheck, r_b, r_c) = prove_layer(&layer.gates, &weights, below, transcript);
+
+        let claims = [evaluate_mle(below, &r_b), evaluate_mle(below, &r_c)];
+        for claim in &claims {
+            transcript.append_field_element(claim);
+        }
+        points = vec![r_b, r_c];
+        layers.push(LayerProof { sumcheck, claims });
+    }
+
+    GkrProof { outputs, layers }
+}
+
+/// Runs the sumcheck of a layer with gates `gates`, weighted by `weights`, and returns its proof
+/// and the two halves `r_b`, `r_c` of its point.
+///
+/// In the first phase, the sum over `c` is folded into tables over `b`, and the summand is
+/// `W(b) (Σ_c add(b, c) + Σ_c mul(b, c) W(c)) + Σ_c add(b, c) W(c)`. In the second, `b` is fixed
+/// to `r_b` and the summand is `add(r_b, c) (W(r_b) + W(c)) + mul(r_b, c) W(r_b) W(c)`. Both
+/// phases only touch one entry of each table per gate.
+fn prove_layer<F, T>(
+    gates: &[Gate],
+    weights: &[FieldElement<F>],
+    below: &[FieldElement<F>],
+    transcript: &mut T,
+) -> (SumcheckProof<F>, Vec<FieldElement<F>>, Vec<FieldElement<F>>)
+where
+    F: IsField,
+    T: IsTranscript<F>,
+{
+    let size = below.len();
+    let mut wiring = vec![FieldElement::zero(); size];
+    let mut wired_values = vec![FieldElement::zero(); size];
+    for (gate, weight) in gates.iter().zip(weights) {
+        let [b, c] = gate.inputs;
+        match gate.ty {
+            GateType::Add => {
+                wiring[b] += weight.clone();
+                wired_values[b] += weight * &below[c];
+            }
+            GateType::Mul => wiring[b] += weight * &below[c],
+        }
+    }
+    let (first_phase, r_b) = sumcheck::prove(
+        vec![below.to_vec(), wiring, wired_values],
+        2,
+        |v| &v[0] * &v[1] + &v[2],
+        transcript,
+    );
+
+    let eq_b = eq_evals(&r_b);
+    let w_b = evaluate_mle(below, &r_b);
+    let mut add = vec![FieldElement::zero(); size];
+    let mut mul = vec![FieldElement::zero(); size];
+    for (gate, weight) in gates.iter().zip(weights) {
+        let [b, c] = gate.inputs;
+        let table = match gate.ty {
+            GateType::Add => &mut add,
+            GateType::Mul => &mut mul,
+        };
+        table[c] += weight * &eq_b[b];
+    }
+    let (second_phase, r_c) = sumcheck::prove(
+        vec![add, mul, below.to_vec()],
+        2,
+        |v| &v[0] * (&w_b + &v[2]) + &v[1] * &w_b * &v[2],
+        transcript,
+    );
+
+    let round_polynomials = [first_phase, second_phase]
+        .into_iter()
+        .flat_map(|phase| phase.round_polynomials)
+        .collect();
+    (SumcheckProof { round_polynomials }, r_b, r_c)
+}
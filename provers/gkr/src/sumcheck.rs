@@ -0,0 +1,176 @@
+//! Sumcheck for sums over the boolean hypercube of `combine(f₁(x), ..., fₖ(x))`, where the `fᵢ`
+//! are multilinear and `combine` is a polynomial of the given degree. Variables are bound from the
+//! most significant bit of the evaluation index down.
+
+use lambdaworks_crypto::fiat_shamir::is_transcript::IsTranscript;
+use lambdaworks_math::field::{element::FieldElement, traits::IsField};
+
+use crate::errors::GkrError;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SumcheckProof<F: IsField> {
+    /// For each round, the evaluations of the round polynomial at `0, 1, ..., degree`.
+    pub round_polynomials: Vec<Vec<FieldElement<F>>>,
+}
+
+/// Proves the sum of `combine` over the hypercube, with `tables` holding the evaluations of the
+/// multilinear polynomials. Returns the proof and the point the sum was reduced to.
+pub fn prove<F, T>(
+    mut tables: Vec<Vec<FieldElement<F>>>,
+    degree: usize,
+    combine: impl Fn(&[FieldElement<F>]) -> FieldElement<F>,
+    transcript: &mut T,
+) -> (SumcheckProof<F>, Vec<FieldElement<F>>)
+where
+    F: IsField,
+    T: IsTranscript<F>,
+{
+    let len = tables[0].len();
+    assert!(len.is_power_of_two() && tables.iter().all(|t| t.len() == len));
+    let num_vars = len.trailing_zeros() as usize;
+
+    let mut round_polynomials = Vec::with_capacity(num_vars);
+    let mut challenges = Vec::with_capacity(num_vars);
+    let mut values = vec![FieldElement::zero(); tables.len()];
+    for round in 0..num_vars {
+        let half = len >> (round + 1);
+        let mut evaluations = vec![FieldElement::<F>::zero(); degree + 1];
+        for j in 0..half {
+            for (t, evaluation) in evaluations.iter_mut().enumerate() {
+                let t = FieldElement::<F>::from(t as u64);
+                for (value, table) in values.iter_mut().zip(&tables) {
+                    *value = &table[j] + &t * (&table[j + half] - &table[j]);
+                }
+                *evaluation += combine(&values);
+            }
+        }
+        for evaluation in &evaluations {
+            transcript.append_field_element(evaluation);
+        }
+        let r = transcript.sample_field_element();
+        for table in tables.iter_mut() {
+            for j in 0..half {
+                table[j] = &table[j] + &r * (&table[j + half] - &table[j]);
+            }
+            table.truncate(half);
+        }
+        round_polynomials.push(evaluations);
+        challenges.push(r);
+    }
+    (SumcheckProof { round_polynomials }, challenges)
+}
+
+/// Checks a sumcheck proof for `claim`. Returns the claimed value of the summand at the returned
+/// point, which the caller must check on its own.
+pub fn verify<F, T>(
+    num_vars: usize,
+    degree: usize,
+    claim: FieldElement<F>,
+    proof: &SumcheckProof<F>,
+    transcript: &mut T,
+) -> Result<(FieldElement<F>, Vec<FieldElement<F>>), GkrError>
+where
+    F: IsField,
+    T: IsTranscript<F>,
+{
+    if proof.round_polynomials.len() != num_vars {
+        return Err(GkrError::WrongNumberOfRounds);
+    }
+    let mut claim = claim;
+    let mut challenges = Vec::with_capacity(num_vars);
+    for (round, evaluations) in proof.round_polynomials.iter().enumerate() {
+        if evaluations.len() != degree + 1 {
+            return Err(GkrError::WrongRoundPolynomialDegree(round));
+        }
+        if &evaluations[0] + &evaluations[1] != claim {
+            return Err(GkrError::RoundSumMismatch(round));
+        }
+        for evaluation in evaluations {
+            transcript.append_field_element(evaluation);
+        }
+        let r = transcript.sample_field_element();
+        claim = interpolate(evaluations, &r);
+        challenges.push(r);
+    }
+    Ok((claim, challenges))
+}
+
+/// Evaluates at `x` the polynomial taking the values `evaluations` at `0, 1, ..., d`.
+fn interpolate<F: IsField>(
+    evaluations: &[FieldElement<F>],
+    x: &FieldElement<F>,
+) -> FieldElement<F> {
+    let nodes: Vec<FieldElement<F>> = (0..evaluations.len() as u64)
+        .map(FieldElement::from)
+        .collect();
+    let mut result = FieldElement::zero();
+    for (i, evaluation) in evaluations.iter().enumerate() {
+        let mut numerator = FieldElement::<F>::one();
+        let mut denominator = FieldElement::<F>::one();
+        for (j, node) in nodes.iter().enumerate().filter(|(j, _)| *j != i) {
+            numerator *= x - node;
+            denominator *= &nodes[i] - &nodes[j];
+        }
+        result += evaluation * numerator * denominator.inv().unwrap();
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::evaluate_mle;
+    use lambdaworks_crypto::fiat_shamir::default_transcript::DefaultTranscript;
+    use lambdaworks_math::elliptic_curve::short_weierstrass::curves::bls12_381::default_types::{
+        FrElement, FrField,
+    };
+
+    fn tables() -> Vec<Vec<FrElement>> {
+        let table = |values: [u64; 8]| values.into_iter().map(FrElement::from).collect();
+        vec![
+            table([1, 2, 3, 4, 5, 6, 7, 8]),
+            table([3, 1, 4, 1, 5, 9, 2, 6]),
+        ]
+    }
+
+    #[test]
+    fn product_sum_is_proven_and_reduced_to_a_point() {
+        let tables = tables();
+        let claim = tables[0]
+            .iter()
+            .zip(&tables[1])
+            .fold(FrElement::zero(), |acc, (a, b)| acc + a * b);
+        let product = |v: &[FrElement]| &v[0] * &v[1];
+        let (proof, point) = prove(
+            tables.clone(),
+            2,
+            product,
+            &mut DefaultTranscript::<FrField>::default(),
+        );
+
+        let (final_claim, verifier_point) = verify(
+            3,
+            2,
+            claim.clone(),
+            &proof,
+            &mut DefaultTranscript::default(),
+        )
+        .unwrap();
+        assert_eq!(point, verifier_point);
+        assert_eq!(
+            final_claim,
+            evaluate_mle(&tables[0], &point) * evaluate_mle(&tables[1], &point)
+        );
+
+        assert_eq!(
+            verify(
+                3,
+                2,
+                claim + FrElement::one(),
+                &proof,
+                &mut DefaultTranscript::default()
+            ),
+            Err(GkrError::RoundSumMismatch(0))
+        );
+    }
+}
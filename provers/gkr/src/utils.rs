@@ -0,0 +1,82 @@
+use lambdaworks_crypto::fiat_shamir::is_transcript::IsTranscript;
+use lambdaworks_math::field::{element::FieldElement, traits::IsField};
+
+use crate::circuit::Circuit;
+
+/// Returns the evaluations of `eq(r, x)` over the boolean hypercube, with `r[0]` as the most
+/// significant bit of `x`, the same ordering as `DenseMultilinearPolynomial`.
+pub fn eq_evals<F: IsField>(r: &[FieldElement<F>]) -> Vec<FieldElement<F>> {
+    let mut evals = vec![FieldElement::one()];
+    for r_i in r {
+        evals = evals
+            .iter()
+            .flat_map(|e| {
+                let high = e * r_i;
+                [e - &high, high]
+            })
+            .collect();
+    }
+    evals
+}
+
+/// Returns `eq(r, s) = Π (rᵢ sᵢ + (1 - rᵢ)(1 - sᵢ))`.
+pub fn eq<F: IsField>(r: &[FieldElement<F>], s: &[FieldElement<F>]) -> FieldElement<F> {
+    r.iter()
+        .zip(s)
+        .fold(FieldElement::one(), |acc, (r_i, s_i)| {
+            let product = r_i * s_i;
+            acc * (&product + &product - r_i - s_i + FieldElement::one())
+        })
+}
+
+/// Evaluates the multilinear extension of `evals` at `r`, binding the most significant bit first.
+pub fn evaluate_mle<F: IsField>(
+    evals: &[FieldElement<F>],
+    r: &[FieldElement<F>],
+) -> FieldElement<F> {
+    assert_eq!(evals.len(), 1 << r.len());
+    eq_evals(r)
+        .iter()
+        .zip(evals)
+        .fold(FieldElement::zero(), |acc, (e, v)| acc + e * v)
+}
+
+/// Returns the number of variables of a multilinear extension over `len` values, padded with
+/// zeros to the next power of two.
+pub fn num_vars(len: usize) -> usize {
+    len.next_power_of_two().trailing_zeros() as usize
+}
+
+/// Appends the statement of a GKR proof, `circuit` and its `input`, to `transcript`. Both
+/// prover and verifier do it before the first challenge, so that the prover can't choose the
+/// input after seeing the challenges.
+pub(crate) fn append_statement<F, T>(
+    circuit: &Circuit,
+    input: &[FieldElement<F>],
+    transcript: &mut T,
+) where
+    F: IsField,
+    T: IsTranscript<F>,
+{
+    circuit.append_to_transcript(transcript);
+    for value in input {
+        transcript.append_field_element(value);
+    }
+}
+
+/// Returns `Σ λʲ eq(pointⱼ, x)` over the boolean hypercube, the weights with which claims on
+/// several points of a layer are combined into one.
+pub(crate) fn combined_eq_evals<F: IsField>(
+    points: &[Vec<FieldElement<F>>],
+    lambda: &FieldElement<F>,
+) -> Vec<FieldElement<F>> {
+    let mut combined = vec![FieldElement::zero(); 1 << points[0].len()];
+    let mut power = FieldElement::<F>::one();
+    for point in points {
+        for (c, e) in combined.iter_mut().zip(eq_evals(point)) {
+            *c += &power * e;
+        }
+        power *= lambda;
+    }
+    combined
+}
@@ -0,0 +1,206 @@
+use lambdaworks_crypto::fiat_shamir::is_transcript::IsTranscript;
+use lambdaworks_math::field::{element::FieldElement, traits::IsField};
+
+use crate::{
+    circuit::{Circuit, GateType},
+    errors::GkrError,
+    prover::GkrProof,
+    sumcheck,
+    utils::{append_statement, combined_eq_evals, eq_evals, evaluate_mle, num_vars},
+};
+
+/// Checks that `proof.outputs` are the outputs of `circuit` on `input`.
+///
+/// The verifier evaluates the wiring predicates from the circuit description, in time linear in
+/// the number of gates, and the input layer from `input`.
+pub fn verify<F, T>(
+    circuit: &Circuit,
+    input: &[FieldElement<F>],
+    proof: &GkrProof<F>,
+    transcript: &mut T,
+) -> Result<(), GkrError>
+where
+    F: IsField,
+    T: IsTranscript<F>,
+{
+    let layers = circuit.layers();
+    if proof.outputs.len() != layers[0].gates.len().next_power_of_two() {
+        return Err(GkrError::WrongNumberOfOutputs);
+    }
+    if proof.layers.len() != layers.len() || input.len() != circuit.num_inputs() {
+        return Err(GkrError::WrongNumberOfLayers);
+    }
+
+    append_statement(circuit, input, transcript);
+    for output in &proof.outputs {
+        transcript.append_field_element(output);
+    }
+    let r: Vec<_> = (0..num_vars(proof.outputs.len()))
+        .map(|_| transcript.sample_field_element())
+        .collect();
+    let mut claims = vec![evaluate_mle(&proof.outputs, &r)];
+    let mut points = vec![r];
+
+    for (i, (layer, layer_proof)) in layers.iter().zip(&proof.layers).enumerate() {
+        let lambda = transcript.sample_field_element();
+        let weights = combined_eq_evals(&points, &lambda);
+        let mut claim = FieldElement::zero();
+        let mut power = FieldElement::<F>::one();
+        for value in &claims {
+            claim += &power * value;
+            power *= &lambda;
+        }
+
+        let k = num_vars(layers.get(i + 1).map_or(input.len(), |l| l.gates.len()));
+        let (final_claim, point) =
+            sumcheck::verify(2 * k, 2, claim, &layer_proof.sumcheck, transcript)?;
+        let (r_b, r_c) = point.split_at(k);
+
+        // add(r_b, r_c) and mul(r_b, r_c), weighted by the claims on the layer
+        let (eq_b, eq_c) = (eq_evals(r_b), eq_evals(r_c));
+        let mut add = FieldElement::<F>::zero();
+        let mut mul = FieldElement::<F>::zero();
+        for (gate, weight) in layer.gates.iter().zip(&weights) {
+            let term = weight * &eq_b[gate.inputs[0]] * &eq_c[gate.inputs[1]];
+            match gate.ty {
+                GateType::Add => add += term,
+                GateType::Mul => mul += term,
+            }
+        }
+        let [w_b, w_c] = &layer_proof.claims;
+        if final_claim != add * (w_b + w_c) + mul * w_b * w_c {
+            return Err(GkrError::LayerCheckFailed(i));
+        }
+
+        for claim in &layer_proof.claims {
+            transcript.append_field_element(claim);
+        }
+        points = vec![r_b.to_vec(), r_c.to_vec()];
+        claims = layer_proof.claims.to_vec();
+    }
+
+    let mut input = input.to_vec();
+    input.resize(input.len().next_power_of_two(), FieldElement::zero());
+    if points
+        .iter()
+        .zip(&claims)
+        .any(|(point, claim)| evaluate_mle(&input, point) != *claim)
+    {
+        return Err(GkrError::InputCheckFailed);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        circuit::{Gate, Layer},
+        prover::prove,
+    };
+    use lambdaworks_crypto::fiat_shamir::default_transcript::DefaultTranscript;
+    use lambdaworks_math::elliptic_curve::short_weierstrass::curves::bls12_381::default_types::{
+        FrElement, FrField,
+    };
+
+    /// Computes `(a + b + c d) · c² (d + a)` and `2 c² + (d + a)²` on the input `[a, b, c, d]`.
+    fn test_circuit() -> Circuit {
+        Circuit::new(
+            vec![
+                Layer::new(vec![
+                    Gate::new(GateType::Mul, [0, 1]),
+                    Gate::new(GateType::Add, [2, 3]),
+                ]),
+                Layer::new(vec![
+                    Gate::new(GateType::Add, [0, 1]),
+                    Gate::new(GateType::Mul, [2, 3]),
+                    Gate::new(GateType::Add, [2, 2]),
+                    Gate::new(GateType::Mul, [3, 3]),
+                ]),
+                Layer::new(vec![
+                    Gate::new(GateType::Add, [0, 1]),
+                    Gate::new(GateType::Mul, [2, 3]),
+                    Gate::new(GateType::Mul, [2, 2]),
+                    Gate::new(GateType::Add, [3, 0]),
+                ]),
+            ],
+            4,
+        )
+        .unwrap()
+    }
+
+    fn input() -> Vec<FrElement> {
+        [3, 5, 7, 2].map(FrElement::from).to_vec()
+    }
+
+    #[test]
+    fn circuit_evaluation_is_proven() {
+        let circuit = test_circuit();
+        let input = input();
+        let proof = prove(
+            &circuit,
+            &input,
+            &mut DefaultTranscript::<FrField>::default(),
+        );
+        assert_eq!(
+            proof.outputs,
+            vec![FrElement::from(22 * 245), FrElement::from(123)]
+        );
+        assert_eq!(
+            verify(&circuit, &input, &proof, &mut DefaultTranscript::default()),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn wrong_output_is_rejected() {
+        let circuit = test_circuit();
+        let input = input();
+        let mut proof = prove(
+            &circuit,
+            &input,
+            &mut DefaultTranscript::<FrField>::default(),
+        );
+        proof.outputs[1] += FrElement::one();
+        assert!(verify(&circuit, &input, &proof, &mut DefaultTranscript::default()).is_err());
+    }
+
+    #[test]
+    fn proof_for_another_input_is_rejected() {
+        let circuit = test_circuit();
+        let proof = prove(
+            &circuit,
+            &input(),
+            &mut DefaultTranscript::<FrField>::default(),
+        );
+        // The input is in the transcript, so the verifier samples other challenges than the
+        // prover from the first round on.
+        let other_input = [3, 5, 7, 3].map(FrElement::from);
+        assert!(verify(
+            &circuit,
+            &other_input,
+            &proof,
+            &mut DefaultTranscript::default()
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn challenges_depend_on_the_circuit_and_the_input() {
+        let first_challenge = |circuit: &Circuit, input: &[FrElement]| {
+            let mut transcript = DefaultTranscript::<FrField>::default();
+            append_statement(circuit, input, &mut transcript);
+            transcript.sample_field_element()
+        };
+        let circuit = test_circuit();
+        let challenge = first_challenge(&circuit, &input());
+
+        let other_input = [3, 5, 7, 3].map(FrElement::from);
+        assert_ne!(challenge, first_challenge(&circuit, &other_input));
+
+        let mut layers = circuit.layers().to_vec();
+        layers[0].gates[1].ty = GateType::Mul;
+        let other_circuit = Circuit::new(layers, circuit.num_inputs()).unwrap();
+        assert_ne!(challenge, first_challenge(&other_circuit, &input()));
+    }
+}
@@ -1,6 +1,10 @@
-use crate::arkworks_cs_to_lambda_cs;
-use ark_bls12_381::Fr;
+use crate::{
+    arkworks_cs_to_lambda_cs, r1cs_from_arkworks_bytes, serialize_arkworks_matrices,
+    witness_from_arkworks_bytes,
+};
+use ark_bls12_381::{Fq, Fr};
 use ark_relations::{lc, r1cs::ConstraintSystem, r1cs::Variable};
+use ark_serialize::CanonicalSerialize;
 use lambdaworks_groth16::{setup, verify, Prover, QuadraticArithmeticProgram};
 use rand::Rng;
 
@@ -37,7 +41,8 @@ fn pinocchio_paper_example() {
 
     let lambda_cs = arkworks_cs_to_lambda_cs(&cs);
 
-    let qap = QuadraticArithmeticProgram::from_r1cs(lambda_cs.constraints);
+    let qap: QuadraticArithmeticProgram =
+        QuadraticArithmeticProgram::from_r1cs(lambda_cs.constraints);
 
     let (pk, vk) = setup(&qap);
 
@@ -92,7 +97,8 @@ fn vitalik_example() {
 
     let lambda_cs = arkworks_cs_to_lambda_cs(&cs);
 
-    let qap = QuadraticArithmeticProgram::from_r1cs(lambda_cs.constraints);
+    let qap: QuadraticArithmeticProgram =
+        QuadraticArithmeticProgram::from_r1cs(lambda_cs.constraints);
 
     let (pk, vk) = setup(&qap);
 
@@ -139,7 +145,8 @@ fn failing_vitalik() {
 
     let lambda_cs = arkworks_cs_to_lambda_cs(&cs);
 
-    let qap = QuadraticArithmeticProgram::from_r1cs(lambda_cs.constraints);
+    let qap: QuadraticArithmeticProgram =
+        QuadraticArithmeticProgram::from_r1cs(lambda_cs.constraints);
 
     let (pk, vk) = setup(&qap);
 
@@ -183,7 +190,8 @@ fn exponentiation_example() {
 
     let lambda_cs = arkworks_cs_to_lambda_cs(&cs);
 
-    let qap = QuadraticArithmeticProgram::from_r1cs(lambda_cs.constraints);
+    let qap: QuadraticArithmeticProgram =
+        QuadraticArithmeticProgram::from_r1cs(lambda_cs.constraints);
 
     let (pk, vk) = setup(&qap);
 
@@ -193,3 +201,80 @@ fn exponentiation_example() {
     let accept = verify(&vk, &proof, public_inputs);
     assert!(accept);
 }
+
+#[test]
+fn serialized_matrices_import() {
+    // x * x = sym_1; sym_1 * x = y, with y public
+    let cs = ConstraintSystem::<Fr>::new_ref();
+    let x = Fr::from(3);
+
+    let _x = cs.new_witness_variable(|| Ok(x)).unwrap();
+    let _sym_1 = cs.new_witness_variable(|| Ok(x * x)).unwrap();
+    let _y = cs.new_input_variable(|| Ok(x * x * x)).unwrap();
+    cs.enforce_constraint(lc!() + _x, lc!() + _x, lc!() + _sym_1)
+        .unwrap();
+    cs.enforce_constraint(lc!() + _sym_1, lc!() + _x, lc!() + _y)
+        .unwrap();
+
+    let matrices = serialize_arkworks_matrices(&cs).unwrap();
+    let mut assignment = vec![];
+    {
+        let cs = cs.borrow().unwrap();
+        [
+            cs.instance_assignment.clone(),
+            cs.witness_assignment.clone(),
+        ]
+        .concat()
+        .serialize_compressed(&mut assignment)
+        .unwrap();
+    }
+
+    let r1cs = r1cs_from_arkworks_bytes::<Fr>(&matrices).unwrap();
+    let witness = witness_from_arkworks_bytes::<Fr>(&assignment).unwrap();
+
+    let lambda_cs = arkworks_cs_to_lambda_cs(&cs);
+    assert_eq!(r1cs, lambda_cs.constraints);
+    assert_eq!(witness, lambda_cs.witness);
+
+    let qap: QuadraticArithmeticProgram = QuadraticArithmeticProgram::from_r1cs(r1cs);
+    let (pk, vk) = setup(&qap);
+    let proof = Prover::prove(&witness, &qap, &pk);
+    assert!(verify(&vk, &proof, &witness[..qap.num_of_public_inputs]));
+
+    assert!(r1cs_from_arkworks_bytes::<Fr>(&matrices[..matrices.len() - 1]).is_err());
+    // The matrices must be over the scalar field of BLS12-381
+    assert!(r1cs_from_arkworks_bytes::<Fq>(&matrices).is_err());
+    assert!(witness_from_arkworks_bytes::<Fq>(&assignment).is_err());
+}
+
+#[test]
+fn serialized_matrices_with_too_many_variables_are_rejected() {
+    for (num_pub_vars, num_witness_vars) in [(usize::MAX, 1), (1, 1 << 40)] {
+        let mut bytes = vec![];
+        num_pub_vars.serialize_compressed(&mut bytes).unwrap();
+        num_witness_vars.serialize_compressed(&mut bytes).unwrap();
+        for _ in 0..3 {
+            vec![vec![(Fr::from(1), 0usize)]]
+                .serialize_compressed(&mut bytes)
+                .unwrap();
+        }
+        assert!(r1cs_from_arkworks_bytes::<Fr>(&bytes).is_err());
+    }
+}
+
+#[test]
+fn serialized_matrices_with_too_many_entries_are_rejected() {
+    // Empty rows are cheap to serialize, but each one is expanded to a dense row of one
+    // element per variable.
+    let num_constraints = 1000;
+    let mut bytes = vec![];
+    1usize.serialize_compressed(&mut bytes).unwrap();
+    999usize.serialize_compressed(&mut bytes).unwrap();
+    for _ in 0..3 {
+        vec![Vec::<(Fr, usize)>::new(); num_constraints]
+            .serialize_compressed(&mut bytes)
+            .unwrap();
+    }
+    assert!(num_constraints * 1000 > bytes.len());
+    assert!(r1cs_from_arkworks_bytes::<Fr>(&bytes).is_err());
+}
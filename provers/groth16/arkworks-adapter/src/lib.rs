@@ -1,8 +1,9 @@
 #[cfg(test)]
 mod integration_tests;
 
-use ark_ff::PrimeField;
-use ark_relations::r1cs::{ConstraintSystemRef, Field};
+use ark_ff::{BigInteger, PrimeField};
+use ark_relations::r1cs::{ConstraintMatrices, ConstraintSystemRef, Field};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize, SerializationError};
 use lambdaworks_groth16::{common::*, r1cs::R1CS, ConstraintSystem};
 use lambdaworks_math::traits::ByteConversion;
 
@@ -20,6 +21,98 @@ pub fn arkworks_cs_to_lambda_cs<ArkF: PrimeField>(
     }
 }
 
+/// Constraint matrices of an arkworks circuit, in the format used by
+/// [`serialize_arkworks_matrices`] and [`r1cs_from_arkworks_bytes`]:
+///
+/// num_instance_variables | num_witness_variables | a | b | c
+///
+/// Every item is serialized with ark-serialize in compressed form, and every matrix is a
+/// `Vec<Vec<(F, usize)>>` with one sparse row per constraint. Instance variables include "1".
+/// This allows importing circuits produced by other arkworks-based frameworks without
+/// rewriting them.
+pub fn serialize_arkworks_matrices<F: PrimeField>(
+    cs: &ConstraintSystemRef<F>,
+) -> Result<Vec<u8>, SerializationError> {
+    cs.inline_all_lcs();
+    let matrices: ConstraintMatrices<F> =
+        cs.to_matrices().ok_or(SerializationError::InvalidData)?;
+
+    let mut bytes = Vec::new();
+    matrices
+        .num_instance_variables
+        .serialize_compressed(&mut bytes)?;
+    matrices
+        .num_witness_variables
+        .serialize_compressed(&mut bytes)?;
+    for m in [&matrices.a, &matrices.b, &matrices.c] {
+        m.serialize_compressed(&mut bytes)?;
+    }
+    Ok(bytes)
+}
+
+/// Reads constraint matrices serialized as described in [`serialize_arkworks_matrices`]
+/// and creates a Lambdaworks R1CS. `F` is the arkworks scalar field the matrices were
+/// serialized with, which must be the one of BLS12-381.
+///
+/// The rows are expanded to dense rows of one element per variable, so the number of
+/// constraints times the number of variables can't exceed the length of `bytes`.
+pub fn r1cs_from_arkworks_bytes<F: PrimeField>(
+    mut bytes: &[u8],
+) -> Result<R1CS, SerializationError> {
+    check_bls12_381_scalar_field::<F>()?;
+    let max_entries = bytes.len();
+    let num_pub_vars = usize::deserialize_compressed(&mut bytes)?;
+    let num_witness_vars = usize::deserialize_compressed(&mut bytes)?;
+    let total_variables = num_pub_vars
+        .checked_add(num_witness_vars)
+        .filter(|total| *total <= max_entries)
+        .ok_or(SerializationError::InvalidData)?;
+
+    let [a, b, c] = [(); 3].map(|_| Vec::<Vec<(F, usize)>>::deserialize_compressed(&mut bytes));
+    let (a, b, c) = (a?, b?, c?);
+    if a.is_empty()
+        || a.len()
+            .checked_mul(total_variables)
+            .filter(|entries| *entries <= max_entries)
+            .is_none()
+        || a.len() != b.len()
+        || a.len() != c.len()
+        || [&a, &b, &c]
+            .iter()
+            .flat_map(|m| m.iter().flatten())
+            .any(|(_, column)| *column >= total_variables)
+    {
+        return Err(SerializationError::InvalidData);
+    }
+
+    Ok(R1CS::from_matrices(
+        ark_to_lambda_matrix(&a, total_variables),
+        ark_to_lambda_matrix(&b, total_variables),
+        ark_to_lambda_matrix(&c, total_variables),
+        num_pub_vars,
+    ))
+}
+
+/// Reads a full assignment serialized with ark-serialize as a `Vec<F>`, with the instance
+/// assignment (starting with "1") followed by the witness assignment. `F` must be the
+/// scalar field of BLS12-381.
+pub fn witness_from_arkworks_bytes<F: PrimeField>(
+    mut bytes: &[u8],
+) -> Result<Vec<FrElement>, SerializationError> {
+    check_bls12_381_scalar_field::<F>()?;
+    let assignment = Vec::<F>::deserialize_compressed(&mut bytes)?;
+    Ok(assignment.iter().map(ark_fr_to_fr_element).collect())
+}
+
+/// The Groth16 backend works over BLS12-381, so elements of any other field can't be
+/// converted to [`FrElement`].
+fn check_bls12_381_scalar_field<F: PrimeField>() -> Result<(), SerializationError> {
+    if F::MODULUS.to_bytes_le() != ark_bls12_381::Fr::MODULUS.to_bytes_le() {
+        return Err(SerializationError::InvalidData);
+    }
+    Ok(())
+}
+
 #[inline]
 fn r1cs_from_arkworks_cs<F: PrimeField>(cs: &ConstraintSystemRef<F>) -> R1CS {
     cs.inline_all_lcs();
@@ -0,0 +1,45 @@
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use lambdaworks_groth16::{common::*, setup, Prover, QuadraticArithmeticProgram as QAP};
+
+/*
+Represents `x * x = y` repeated `num_of_constraints` times.
+The witness is [1, x, y], so setup and proving are dominated by the number of
+constraints (FFTs over the domain and the MSM of the quotient polynomial)
+rather than by the number of variables.
+*/
+fn repeated_square_qap(num_of_constraints: usize) -> QAP {
+    let zeroes = vec![FrElement::zero(); num_of_constraints];
+    let ones = vec![FrElement::one(); num_of_constraints];
+
+    // Columns are indexed by variable: 1, x, y
+    let l = [zeroes.clone(), ones.clone(), zeroes.clone()];
+    let r = [zeroes.clone(), ones.clone(), zeroes.clone()];
+    let o = [zeroes.clone(), zeroes, ones];
+
+    QAP::from_variable_matrices(1, &l, &r, &o)
+}
+
+fn groth16_prover_benchmarks(c: &mut Criterion) {
+    let mut group = c.benchmark_group("Groth16 prover");
+    group.sample_size(10);
+
+    for exp in [16, 18, 20] {
+        let num_of_constraints = 1 << exp;
+        let qap = repeated_square_qap(num_of_constraints);
+        let (pk, _vk) = setup(&qap);
+
+        // 1, x = 3, y = 9
+        let w = [1u64, 3, 9].map(FrElement::from).to_vec();
+
+        group.bench_with_input(
+            BenchmarkId::new("prove", format!("2^{exp} constraints")),
+            &w,
+            |bench, w| bench.iter(|| black_box(Prover::prove(w, &qap, &pk))),
+        );
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, groth16_prover_benchmarks);
+criterion_main!(benches);
@@ -0,0 +1,141 @@
+use lambdaworks_math::{
+    field::{element::FieldElement, traits::IsPrimeField},
+    traits::ByteConversion,
+    unsigned_integer::element::{UnsignedInteger, U256},
+};
+
+use crate::errors::CircomError;
+
+/// Container format shared by circom and snarkjs binary files (`.r1cs`, `.wtns`, `.zkey`):
+///
+/// magic (4 bytes) | version (u32) | number of sections (u32) | sections
+///
+/// where every section is: type (u32) | size (u64) | content.
+/// All integers are little endian.
+pub(crate) struct BinFile<'a> {
+    sections: Vec<(u32, &'a [u8])>,
+}
+
+impl<'a> BinFile<'a> {
+    pub fn parse(bytes: &'a [u8], magic: &[u8; 4], max_version: u32) -> Result<Self, CircomError> {
+        let mut reader = Reader::new(bytes);
+        if reader.read_bytes(4)? != magic {
+            return Err(CircomError::InvalidMagic);
+        }
+
+        let version = reader.read_u32()?;
+        if version == 0 || version > max_version {
+            return Err(CircomError::UnsupportedVersion(version));
+        }
+
+        let num_of_sections = reader.read_u32()?;
+        let sections = (0..num_of_sections)
+            .map(|_| {
+                let section_type = reader.read_u32()?;
+                let section_size = usize::try_from(reader.read_u64()?)
+                    .map_err(|_| CircomError::UnexpectedEndOfFile)?;
+                Ok((section_type, reader.read_bytes(section_size)?))
+            })
+            .collect::<Result<Vec<_>, CircomError>>()?;
+
+        Ok(Self { sections })
+    }
+
+    /// Returns a reader over the first section with the given type.
+    pub fn section(&self, section_type: u32) -> Result<Reader<'a>, CircomError> {
+        self.sections
+            .iter()
+            .find(|(t, _)| *t == section_type)
+            .map(|(_, content)| Reader::new(content))
+            .ok_or(CircomError::MissingSection(section_type))
+    }
+}
+
+pub(crate) struct Reader<'a> {
+    bytes: &'a [u8],
+    offset: usize,
+}
+
+impl<'a> Reader<'a> {
+    pub fn new(bytes: &'a [u8]) -> Self {
+        Self { bytes, offset: 0 }
+    }
+
+    pub fn read_bytes(&mut self, len: usize) -> Result<&'a [u8], CircomError> {
+        // `len` can come from an untrusted section header, so the end is checked for overflow
+        let end = self
+            .offset
+            .checked_add(len)
+            .filter(|end| *end <= self.bytes.len())
+            .ok_or(CircomError::UnexpectedEndOfFile)?;
+        let bytes = &self.bytes[self.offset..end];
+        self.offset = end;
+        Ok(bytes)
+    }
+
+    pub fn read_u32(&mut self) -> Result<u32, CircomError> {
+        let bytes = self.read_bytes(4)?;
+        Ok(u32::from_le_bytes(bytes.try_into().unwrap()))
+    }
+
+    pub fn read_u64(&mut self) -> Result<u64, CircomError> {
+        let bytes = self.read_bytes(8)?;
+        Ok(u64::from_le_bytes(bytes.try_into().unwrap()))
+    }
+
+    /// Reads `n8` bytes holding a little endian integer.
+    pub fn read_uint<const NUM_LIMBS: usize>(
+        &mut self,
+        n8: usize,
+    ) -> Result<UnsignedInteger<NUM_LIMBS>, CircomError> {
+        if n8 != NUM_LIMBS * 8 {
+            return Err(CircomError::PrimeMismatch);
+        }
+        Ok(UnsignedInteger::from_bytes_le(self.read_bytes(n8)?)?)
+    }
+
+    /// Reads a field element stored in its canonical (non-Montgomery) form.
+    pub fn read_fr_element<F>(&mut self, n8: usize) -> Result<FieldElement<F>, CircomError>
+    where
+        F: IsPrimeField<BaseType = U256, RepresentativeType = U256>,
+    {
+        let value = self.read_uint(n8)?;
+        if value > F::modulus_minus_one() {
+            return Err(CircomError::InvalidFieldElement);
+        }
+        Ok(FieldElement::new(value))
+    }
+
+    /// Reads a field element stored in Montgomery form, as snarkjs does for curve points.
+    /// The Montgomery parameter R = 2^(8 * n8) coincides with the one lambdaworks uses,
+    /// so the raw value can be taken as is.
+    pub fn read_montgomery_element<F, const NUM_LIMBS: usize>(
+        &mut self,
+        n8: usize,
+    ) -> Result<FieldElement<F>, CircomError>
+    where
+        F: IsPrimeField<
+            BaseType = UnsignedInteger<NUM_LIMBS>,
+            RepresentativeType = UnsignedInteger<NUM_LIMBS>,
+        >,
+    {
+        let value = self.read_uint(n8)?;
+        if value > F::modulus_minus_one() {
+            return Err(CircomError::InvalidFieldElement);
+        }
+        Ok(FieldElement::from_raw(value))
+    }
+}
+
+/// Checks that the prime declared in a file header is the modulus of `F`, the scalar field of
+/// the Groth16 backend.
+pub(crate) fn check_fr_prime<F>(reader: &mut Reader, n8: usize) -> Result<(), CircomError>
+where
+    F: IsPrimeField<RepresentativeType = U256>,
+{
+    let prime: U256 = reader.read_uint(n8)?;
+    if prime != F::modulus_minus_one() + UnsignedInteger::from_u64(1) {
+        return Err(CircomError::PrimeMismatch);
+    }
+    Ok(())
+}
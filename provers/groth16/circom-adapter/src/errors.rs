@@ -0,0 +1,58 @@
+use core::fmt::Display;
+
+use lambdaworks_math::errors::ByteConversionError;
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum CircomError {
+    /// The file does not start with the expected magic string (e.g. `r1cs`, `wtns`, `zkey`).
+    InvalidMagic,
+    UnsupportedVersion(u32),
+    UnsupportedProtocol(u32),
+    MissingSection(u32),
+    InvalidHeader,
+    UnexpectedEndOfFile,
+    /// The prime declared in the file is not the one of the curve it is loaded for, e.g. a
+    /// file for circom's default curve, BN254, loaded as BLS12-381.
+    PrimeMismatch,
+    InvalidFieldElement,
+    InvalidPoint,
+    InvalidWireId(usize),
+    InvalidWitnessLength(usize, usize),
+}
+
+impl Display for CircomError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            CircomError::InvalidMagic => write!(f, "Invalid magic string"),
+            CircomError::UnsupportedVersion(version) => {
+                write!(f, "Unsupported file version: {version}")
+            }
+            CircomError::UnsupportedProtocol(protocol) => {
+                write!(f, "Unsupported protocol: {protocol}")
+            }
+            CircomError::MissingSection(section) => write!(f, "Missing section: {section}"),
+            CircomError::InvalidHeader => write!(f, "Invalid header"),
+            CircomError::UnexpectedEndOfFile => write!(f, "Unexpected end of file"),
+            CircomError::PrimeMismatch => {
+                write!(
+                    f,
+                    "The prime of the file does not match the one of the curve"
+                )
+            }
+            CircomError::InvalidFieldElement => write!(f, "Invalid field element"),
+            CircomError::InvalidPoint => write!(f, "Invalid elliptic curve point"),
+            CircomError::InvalidWireId(wire) => write!(f, "Invalid wire id: {wire}"),
+            CircomError::InvalidWitnessLength(expected, got) => {
+                write!(f, "Expected a witness of length {expected}, got {got}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for CircomError {}
+
+impl From<ByteConversionError> for CircomError {
+    fn from(_: ByteConversionError) -> Self {
+        CircomError::InvalidFieldElement
+    }
+}
@@ -1,7 +1,11 @@
 use std::fs;
 
 use crate::*;
-use lambdaworks_groth16::*;
+use lambdaworks_groth16::{common::*, *};
+use lambdaworks_math::elliptic_curve::short_weierstrass::curves::bn_254::{
+    default_types::{FrElement as BN254FrElement, FrField as BN254FrField},
+    pairing::BN254AtePairing,
+};
 
 const TEST_DIR: &str = "test_files";
 
@@ -10,7 +14,7 @@ const TEST_DIR: &str = "test_files";
 fn poseidon_parse_prove_verify() {
     let test_dir = format!("{TEST_DIR}/poseidon");
 
-    let (qap, w) = circom_to_lambda(
+    let (qap, w) = circom_to_lambda::<Pairing>(
         &fs::read_to_string(format!("{test_dir}/test.r1cs.json")).expect("Error reading the file"),
         &fs::read_to_string(format!("{test_dir}/witness.json")).expect("Error reading the file"),
     );
@@ -43,7 +47,7 @@ fn poseidon_parse_prove_verify() {
 fn vitalik_w_and_qap() {
     let test_dir = format!("{TEST_DIR}/vitalik_example");
 
-    let (qap, w) = circom_to_lambda(
+    let (qap, w) = circom_to_lambda::<Pairing>(
         &fs::read_to_string(format!("{test_dir}/test.r1cs.json")).expect("Error reading the file"),
         &fs::read_to_string(format!("{test_dir}/witness.json")).expect("Error reading the file"),
     );
@@ -91,8 +95,12 @@ fn vitalik_w_and_qap() {
         ],
     ]
     .map(|matrix| matrix.map(|row| row.map(FrElement::from_hex_unchecked).to_vec()));
-    let expected_qap =
-        QAP::from_variable_matrices(expected_num_of_public_inputs, &temp_l, &temp_r, &temp_o);
+    let expected_qap = QAP::<Pairing>::from_variable_matrices(
+        expected_num_of_public_inputs,
+        &temp_l,
+        &temp_r,
+        &temp_o,
+    );
 
     let expected_l = expected_qap.l;
     let expected_r = expected_qap.r;
@@ -102,3 +110,472 @@ fn vitalik_w_and_qap() {
     assert_eq!(qap.r, expected_r);
     assert_eq!(qap.o, expected_o);
 }
+
+// The binary .r1cs and .wtns files hold the same circuits as their JSON exports.
+#[test]
+fn binary_and_json_files_match() {
+    for test_dir in ["vitalik_example", "poseidon"].map(|dir| format!("{TEST_DIR}/{dir}")) {
+        let (json_qap, json_w) = circom_to_lambda::<Pairing>(
+            &fs::read_to_string(format!("{test_dir}/test.r1cs.json"))
+                .expect("Error reading the file"),
+            &fs::read_to_string(format!("{test_dir}/witness.json"))
+                .expect("Error reading the file"),
+        );
+        let (binary_qap, binary_w) = circom_binary_to_lambda::<Pairing>(
+            &fs::read(format!("{test_dir}/test.r1cs")).expect("Error reading the file"),
+            &fs::read(format!("{test_dir}/witness.wtns")).expect("Error reading the file"),
+        )
+        .unwrap();
+
+        assert_eq!(json_w, binary_w);
+        assert_eq!(
+            json_qap.num_of_public_inputs,
+            binary_qap.num_of_public_inputs
+        );
+        assert_eq!(json_qap.l, binary_qap.l);
+        assert_eq!(json_qap.r, binary_qap.r);
+        assert_eq!(json_qap.o, binary_qap.o);
+    }
+}
+
+#[test]
+fn poseidon_binary_parse_prove_verify() {
+    let test_dir = format!("{TEST_DIR}/poseidon");
+
+    let (qap, w) = circom_binary_to_lambda::<Pairing>(
+        &fs::read(format!("{test_dir}/test.r1cs")).expect("Error reading the file"),
+        &fs::read(format!("{test_dir}/witness.wtns")).expect("Error reading the file"),
+    )
+    .unwrap();
+
+    let (pk, vk) = setup(&qap);
+
+    let accept = verify(
+        &vk,
+        &Prover::prove(&w, &qap, &pk),
+        &w[..qap.num_of_public_inputs],
+    );
+    assert!(accept);
+}
+
+#[test]
+fn binary_files_with_wrong_magic_are_rejected() {
+    let test_dir = format!("{TEST_DIR}/vitalik_example");
+    let r1cs = fs::read(format!("{test_dir}/test.r1cs")).expect("Error reading the file");
+    let wtns = fs::read(format!("{test_dir}/witness.wtns")).expect("Error reading the file");
+
+    assert_eq!(
+        CircomR1CS::<FrField>::from_bytes(&wtns).unwrap_err(),
+        CircomError::InvalidMagic
+    );
+    assert_eq!(
+        witness_from_bytes::<FrField>(&r1cs).unwrap_err(),
+        CircomError::InvalidMagic
+    );
+    assert_eq!(
+        CircomR1CS::<FrField>::from_bytes(&r1cs[..r1cs.len() - 1]).unwrap_err(),
+        CircomError::UnexpectedEndOfFile
+    );
+
+    // A section whose size overflows the offset of the reader
+    let mut huge_section = r1cs[..12].to_vec();
+    huge_section.extend_from_slice(&1u32.to_le_bytes());
+    huge_section.extend_from_slice(&u64::MAX.to_le_bytes());
+    assert_eq!(
+        CircomR1CS::<FrField>::from_bytes(&huge_section).unwrap_err(),
+        CircomError::UnexpectedEndOfFile
+    );
+}
+
+#[test]
+fn vitalik_zkey_prove_verify() {
+    let test_dir = format!("{TEST_DIR}/vitalik_example");
+    let circom_r1cs =
+        CircomR1CS::<FrField>::from_bytes(&fs::read(format!("{test_dir}/test.r1cs")).unwrap())
+            .unwrap();
+    let w = witness_from_bytes::<FrField>(&fs::read(format!("{test_dir}/witness.wtns")).unwrap())
+        .unwrap();
+
+    let toxic_waste = [7u64, 11, 13, 17, 19].map(FrElement::from);
+    let zkey = ZKey::<Pairing>::from_bytes(&test_utils::snarkjs_zkey::<Pairing>(
+        &circom_r1cs,
+        toxic_waste,
+    ))
+    .unwrap();
+    assert_eq!(zkey.num_of_public, 1);
+
+    let proof = zkey.prove(&w).unwrap();
+
+    // Public inputs are "1" and ~out
+    assert!(verify(
+        &zkey.verifying_key,
+        &proof,
+        &w[..=zkey.num_of_public]
+    ));
+    assert!(!verify(
+        &zkey.verifying_key,
+        &proof,
+        &[FrElement::one(), FrElement::from(36)]
+    ));
+}
+
+/// The circuit of the vitalik example, compiled for BN254 (circom's default `-p bn128`).
+fn vitalik_bn254_r1cs() -> CircomR1CS<BN254FrField> {
+    let minus_one = -BN254FrElement::one();
+    CircomR1CS {
+        num_of_vars: 4,
+        num_of_outputs: 1,
+        num_of_pub_inputs: 0,
+        num_of_prv_inputs: 1,
+        // -x * x = -sym_1
+        // -sym_1 * x = 5 - ~out + x
+        constraints: vec![
+            [
+                vec![(2, minus_one.clone())],
+                vec![(2, BN254FrElement::one())],
+                vec![(3, minus_one.clone())],
+            ],
+            [
+                vec![(3, minus_one.clone())],
+                vec![(2, BN254FrElement::one())],
+                vec![
+                    (0, BN254FrElement::from(5)),
+                    (1, minus_one),
+                    (2, BN254FrElement::one()),
+                ],
+            ],
+        ],
+    }
+}
+
+#[test]
+fn bn254_binary_files_prove_verify() {
+    let circom_r1cs = vitalik_bn254_r1cs();
+    let r1cs = test_utils::r1cs_bytes(&circom_r1cs);
+    let wtns = test_utils::wtns_bytes(&[1u64, 35, 3, 9].map(BN254FrElement::from));
+
+    let (qap, w) = circom_binary_to_lambda::<BN254AtePairing>(&r1cs, &wtns).unwrap();
+    assert_eq!(
+        CircomR1CS::from_bytes(&r1cs).unwrap().constraints,
+        circom_r1cs.constraints
+    );
+
+    let (pk, vk) = setup(&qap);
+    let accept = verify(
+        &vk,
+        &Prover::prove(&w, &qap, &pk),
+        &w[..qap.num_of_public_inputs],
+    );
+    assert!(accept);
+
+    assert!(matches!(
+        circom_binary_to_lambda::<Pairing>(&r1cs, &wtns),
+        Err(CircomError::PrimeMismatch)
+    ));
+    assert_eq!(
+        witness_from_bytes::<FrField>(&wtns).unwrap_err(),
+        CircomError::PrimeMismatch
+    );
+}
+
+#[test]
+fn bn254_zkey_prove_verify() {
+    let circom_r1cs = vitalik_bn254_r1cs();
+    let w = [1u64, 35, 3, 9].map(BN254FrElement::from);
+
+    let toxic_waste = [7u64, 11, 13, 17, 19].map(BN254FrElement::from);
+    let zkey_bytes = test_utils::snarkjs_zkey::<BN254AtePairing>(&circom_r1cs, toxic_waste);
+    let zkey = ZKey::<BN254AtePairing>::from_bytes(&zkey_bytes).unwrap();
+
+    let proof = zkey.prove(&w).unwrap();
+    assert!(verify(
+        &zkey.verifying_key,
+        &proof,
+        &w[..=zkey.num_of_public]
+    ));
+    assert!(!verify(
+        &zkey.verifying_key,
+        &proof,
+        &[BN254FrElement::one(), BN254FrElement::from(36)]
+    ));
+
+    assert!(matches!(
+        ZKey::<Pairing>::from_bytes(&zkey_bytes),
+        Err(CircomError::PrimeMismatch)
+    ));
+}
+
+/// Encoders of the binary formats of circom and snarkjs, used to build files for circuits and
+/// curves there are no fixtures for.
+mod test_utils {
+    use lambdaworks_groth16::common::*;
+    use lambdaworks_math::{
+        cyclic_group::IsGroup,
+        elliptic_curve::{
+            short_weierstrass::{
+                curves::{
+                    bls12_381::pairing::BLS12381AtePairing, bn_254::pairing::BN254AtePairing,
+                },
+                point::ShortWeierstrassProjectivePoint,
+                traits::IsShortWeierstrass,
+            },
+            traits::IsEllipticCurve,
+        },
+        field::{
+            element::FieldElement,
+            traits::{IsFFTField, IsPrimeField},
+        },
+        traits::ByteConversion,
+        unsigned_integer::element::{UnsignedInteger, U256},
+    };
+
+    use crate::{CircomR1CS, IsCircomCurve};
+
+    pub trait ZKeyCurve: IsCircomCurve {
+        fn g1_bytes(point: &G1PointOf<Self>) -> Vec<u8>;
+        fn g2_bytes(point: &G2PointOf<Self>) -> Vec<u8>;
+    }
+
+    impl ZKeyCurve for BLS12381AtePairing {
+        fn g1_bytes(point: &G1PointOf<Self>) -> Vec<u8> {
+            affine_bytes(point, 96, |x| x.value().to_bytes_le())
+        }
+
+        fn g2_bytes(point: &G2PointOf<Self>) -> Vec<u8> {
+            affine_bytes(point, 192, |x| {
+                x.value()
+                    .iter()
+                    .flat_map(|c| c.value().to_bytes_le())
+                    .collect()
+            })
+        }
+    }
+
+    impl ZKeyCurve for BN254AtePairing {
+        fn g1_bytes(point: &G1PointOf<Self>) -> Vec<u8> {
+            affine_bytes(point, 64, |x| x.value().to_bytes_le())
+        }
+
+        fn g2_bytes(point: &G2PointOf<Self>) -> Vec<u8> {
+            affine_bytes(point, 128, |x| {
+                x.value()
+                    .iter()
+                    .flat_map(|c| c.value().to_bytes_le())
+                    .collect()
+            })
+        }
+    }
+
+    /// Affine coordinates in Montgomery form, with the point at infinity written as zeroes.
+    fn affine_bytes<E: IsShortWeierstrass>(
+        point: &ShortWeierstrassProjectivePoint<E>,
+        size: usize,
+        coordinate_bytes: impl Fn(&FieldElement<E::BaseField>) -> Vec<u8>,
+    ) -> Vec<u8> {
+        if point.is_neutral_element() {
+            return vec![0; size];
+        }
+        let point = point.to_affine();
+        [point.x(), point.y()]
+            .into_iter()
+            .flat_map(coordinate_bytes)
+            .collect()
+    }
+
+    fn section(section_type: u32, content: Vec<u8>) -> Vec<u8> {
+        let mut bytes = section_type.to_le_bytes().to_vec();
+        bytes.extend((content.len() as u64).to_le_bytes());
+        bytes.extend(content);
+        bytes
+    }
+
+    fn file(magic: &[u8; 4], version: u32, sections: Vec<Vec<u8>>) -> Vec<u8> {
+        let mut bytes = magic.to_vec();
+        bytes.extend(version.to_le_bytes());
+        bytes.extend((sections.len() as u32).to_le_bytes());
+        bytes.extend(sections.concat());
+        bytes
+    }
+
+    /// n8 followed by the prime of `F`.
+    fn prime_header<F: IsPrimeField<RepresentativeType = U256>>() -> Vec<u8> {
+        let mut bytes = 32u32.to_le_bytes().to_vec();
+        bytes.extend((F::modulus_minus_one() + UnsignedInteger::from_u64(1)).to_bytes_le());
+        bytes
+    }
+
+    fn canonical_bytes<F: IsPrimeField<RepresentativeType = U256>>(
+        value: &FieldElement<F>,
+    ) -> Vec<u8> {
+        value.representative().to_bytes_le()
+    }
+
+    /// Mimics `circom --r1cs`, without the optional sections.
+    pub fn r1cs_bytes<F: IsPrimeField<RepresentativeType = U256>>(
+        circom_r1cs: &CircomR1CS<F>,
+    ) -> Vec<u8> {
+        let mut header = prime_header::<F>();
+        for value in [
+            circom_r1cs.num_of_vars,
+            circom_r1cs.num_of_outputs,
+            circom_r1cs.num_of_pub_inputs,
+            circom_r1cs.num_of_prv_inputs,
+        ] {
+            header.extend((value as u32).to_le_bytes());
+        }
+        header.extend((circom_r1cs.num_of_vars as u64).to_le_bytes());
+        header.extend((circom_r1cs.num_of_constraints() as u32).to_le_bytes());
+
+        let mut constraints = vec![];
+        for linear_combination in circom_r1cs.constraints.iter().flatten() {
+            constraints.extend((linear_combination.len() as u32).to_le_bytes());
+            for (wire, value) in linear_combination {
+                constraints.extend((*wire as u32).to_le_bytes());
+                constraints.extend(canonical_bytes(value));
+            }
+        }
+
+        file(
+            b"r1cs",
+            1,
+            vec![section(1, header), section(2, constraints)],
+        )
+    }
+
+    /// Mimics the witness generator of a circom circuit.
+    pub fn wtns_bytes<F: IsPrimeField<RepresentativeType = U256>>(
+        witness: &[FieldElement<F>],
+    ) -> Vec<u8> {
+        let mut header = prime_header::<F>();
+        header.extend((witness.len() as u32).to_le_bytes());
+        let values = witness.iter().flat_map(canonical_bytes).collect();
+
+        file(b"wtns", 2, vec![section(1, header), section(2, values)])
+    }
+
+    /// Mimics `snarkjs groth16 setup` with the given toxic waste (τ, α, β, γ, ƍ).
+    pub fn snarkjs_zkey<C: ZKeyCurve>(
+        circom_r1cs: &CircomR1CS<C::FrField>,
+        toxic_waste: [FrElementOf<C>; 5],
+    ) -> Vec<u8> {
+        let [tau, alpha, beta, gamma, delta] = toxic_waste;
+        let g1 = C::G1Curve::generator();
+        let g2 = C::G2Curve::generator();
+
+        let num_of_vars = circom_r1cs.num_of_vars;
+        let num_of_public = circom_r1cs.num_of_outputs + circom_r1cs.num_of_pub_inputs;
+        // snarkjs adds a constraint `s * 0 = 0` for every public signal s, including "1"
+        let num_of_constraints = circom_r1cs.num_of_constraints() + num_of_public + 1;
+        let domain_size = num_of_constraints.next_power_of_two();
+        let log_domain_size = domain_size.trailing_zeros() as u64;
+
+        // Lagrange basis of the domain evaluated at τ
+        let n = FrElementOf::<C>::from(domain_size as u64);
+        let omega = C::FrField::get_primitive_root_of_unity(log_domain_size).unwrap();
+        let t_tau = tau.pow(domain_size) - FrElementOf::<C>::one();
+        let lagrange: Vec<_> = (0..domain_size as u64)
+            .map(|j| {
+                let omega_j = omega.pow(j);
+                &t_tau * &omega_j / (&n * (&tau - &omega_j))
+            })
+            .collect();
+
+        // (matrix, constraint, signal, value)
+        let mut coefficients = vec![];
+        let mut abc_tau = [(); 3].map(|_| vec![FrElementOf::<C>::zero(); num_of_vars]);
+        for (constraint, lro) in circom_r1cs.constraints.iter().enumerate() {
+            for (matrix, linear_combination) in lro.iter().enumerate() {
+                for (signal, value) in linear_combination {
+                    abc_tau[matrix][*signal] += value * &lagrange[constraint];
+                    if matrix < 2 {
+                        coefficients.push((matrix as u32, constraint, *signal, value.clone()));
+                    }
+                }
+            }
+        }
+        let num_of_constraints = circom_r1cs.num_of_constraints();
+        for (signal, a_tau) in abc_tau[0].iter_mut().enumerate().take(num_of_public + 1) {
+            let constraint = num_of_constraints + signal;
+            *a_tau += lagrange[constraint].clone();
+            coefficients.push((0, constraint, signal, FieldElement::one()));
+        }
+        let [a_tau, b_tau, c_tau] = abc_tau;
+
+        let k_tau: Vec<_> = (0..num_of_vars)
+            .map(|i| &beta * &a_tau[i] + &alpha * &b_tau[i] + &c_tau[i])
+            .collect();
+        let gamma_inv = gamma.inv().unwrap();
+        let delta_inv = delta.inv().unwrap();
+
+        // Lagrange basis of the coset g * <ω>, with g a 2n-th primitive root of unity,
+        // scaled by ƍ^{-1} * t(τ) / t(g)
+        let g = C::FrField::get_primitive_root_of_unity(log_domain_size + 1).unwrap();
+        let g_n = g.pow(domain_size);
+        let h_scale = &t_tau * &delta_inv / (&g_n - FrElementOf::<C>::one());
+        let h: Vec<_> = (0..domain_size as u64)
+            .map(|j| {
+                let x_j = &g * omega.pow(j);
+                (tau.pow(domain_size) - &g_n) * &x_j / (&n * &g_n * (&tau - &x_j)) * &h_scale
+            })
+            .collect();
+
+        let mul_g1 = |scalar: &FrElementOf<C>| g1.operate_with_self(scalar.representative());
+        let mul_g2 = |scalar: &FrElementOf<C>| g2.operate_with_self(scalar.representative());
+
+        let base_field_prime = C::base_field_prime();
+        let mut groth16_header = (base_field_prime.len() as u32).to_le_bytes().to_vec();
+        groth16_header.extend(base_field_prime);
+        groth16_header.extend(prime_header::<C::FrField>());
+        for value in [num_of_vars, num_of_public, domain_size] {
+            groth16_header.extend((value as u32).to_le_bytes());
+        }
+        groth16_header.extend(C::g1_bytes(&mul_g1(&alpha)));
+        groth16_header.extend(C::g1_bytes(&mul_g1(&beta)));
+        groth16_header.extend(C::g2_bytes(&mul_g2(&beta)));
+        groth16_header.extend(C::g2_bytes(&mul_g2(&gamma)));
+        groth16_header.extend(C::g1_bytes(&mul_g1(&delta)));
+        groth16_header.extend(C::g2_bytes(&mul_g2(&delta)));
+
+        // Coefficients are stored as v * R^2, which is the Montgomery representation of v * R
+        let montgomery_r = FrElementOf::<C>::from_raw(UnsignedInteger::from_u64(1))
+            .inv()
+            .unwrap();
+        let mut coefficients_section = (coefficients.len() as u32).to_le_bytes().to_vec();
+        for (matrix, constraint, signal, value) in coefficients {
+            coefficients_section.extend(matrix.to_le_bytes());
+            coefficients_section.extend((constraint as u32).to_le_bytes());
+            coefficients_section.extend((signal as u32).to_le_bytes());
+            coefficients_section.extend((value * &montgomery_r).value().to_bytes_le());
+        }
+
+        let g1_section = |scalars: &[FrElementOf<C>], scale: &FrElementOf<C>| -> Vec<u8> {
+            scalars
+                .iter()
+                .flat_map(|scalar| C::g1_bytes(&mul_g1(&(scalar * scale))))
+                .collect()
+        };
+        let one = FieldElement::one();
+
+        file(
+            b"zkey",
+            1,
+            vec![
+                section(1, 1u32.to_le_bytes().to_vec()),
+                section(2, groth16_header),
+                section(3, g1_section(&k_tau[..=num_of_public], &gamma_inv)),
+                section(4, coefficients_section),
+                section(5, g1_section(&a_tau, &one)),
+                section(6, g1_section(&b_tau, &one)),
+                section(
+                    7,
+                    b_tau
+                        .iter()
+                        .flat_map(|scalar| C::g2_bytes(&mul_g2(scalar)))
+                        .collect(),
+                ),
+                section(8, g1_section(&k_tau[num_of_public + 1..], &delta_inv)),
+                section(9, g1_section(&h, &one)),
+            ],
+        )
+    }
+}
@@ -1,26 +1,66 @@
 #[cfg(test)]
 mod integration_tests;
 
-use lambdaworks_groth16::{common::FrElement, QuadraticArithmeticProgram as QAP};
-use lambdaworks_math::unsigned_integer::element::UnsignedInteger;
+mod binary;
+pub mod errors;
+pub mod r1cs;
+pub mod wtns;
+pub mod zkey;
+
+use lambdaworks_groth16::{
+    common::{FrElementOf, IsGroth16Curve},
+    QuadraticArithmeticProgram as QAP,
+};
+use lambdaworks_math::field::{element::FieldElement, traits::IsField};
 use serde_json::Value;
 
-pub fn circom_to_lambda(
+pub use errors::CircomError;
+pub use r1cs::CircomR1CS;
+pub use wtns::{witness_from_bytes, witness_from_json};
+pub use zkey::{IsCircomCurve, ZKey};
+
+/// Takes the JSON exports of the R1CS and the witness of a circuit compiled for the curve `C`,
+/// e.g. `BN254AtePairing` for circom's default `-p bn128`.
+pub fn circom_to_lambda<C: IsGroth16Curve>(
     r1cs_file_content: &str,
     witness_file_content: &str,
-) -> (QAP, Vec<FrElement>) {
+) -> (QAP<C>, Vec<FrElementOf<C>>) {
     let circom_r1cs: Value = serde_json::from_str(r1cs_file_content).expect("Error parsing JSON");
-    let [mut l, mut r, mut o] = build_lro_from_circom_r1cs(&circom_r1cs);
 
-    let mut witness: Vec<_> = serde_json::from_str::<Vec<String>>(witness_file_content)
-        .expect("Error parsing JSON")
-        .iter()
-        .map(|num_str| circom_str_to_lambda_field_element(num_str))
-        .collect();
-    adjust_lro_and_witness(&circom_r1cs, &mut l, &mut r, &mut o, &mut witness);
+    circom_r1cs_to_lambda(
+        &CircomR1CS::from_json(&circom_r1cs),
+        witness_from_json(witness_file_content),
+    )
+}
+
+/// Same as [`circom_to_lambda`], but takes the binary `.r1cs` and `.wtns` files produced by
+/// circom directly, without exporting them to JSON with snarkjs first. Files generated for a
+/// curve other than `C` are rejected with [`CircomError::PrimeMismatch`].
+pub fn circom_binary_to_lambda<C: IsGroth16Curve>(
+    r1cs_bytes: &[u8],
+    wtns_bytes: &[u8],
+) -> Result<(QAP<C>, Vec<FrElementOf<C>>), CircomError> {
+    let circom_r1cs = CircomR1CS::from_bytes(r1cs_bytes)?;
+    let witness = witness_from_bytes(wtns_bytes)?;
+    if witness.len() != circom_r1cs.num_of_vars {
+        return Err(CircomError::InvalidWitnessLength(
+            circom_r1cs.num_of_vars,
+            witness.len(),
+        ));
+    }
+
+    Ok(circom_r1cs_to_lambda(&circom_r1cs, witness))
+}
+
+fn circom_r1cs_to_lambda<C: IsGroth16Curve>(
+    circom_r1cs: &CircomR1CS<C::FrField>,
+    mut witness: Vec<FrElementOf<C>>,
+) -> (QAP<C>, Vec<FrElementOf<C>>) {
+    let [mut l, mut r, mut o] = build_lro_from_circom_r1cs(circom_r1cs);
+    adjust_lro_and_witness(circom_r1cs, &mut l, &mut r, &mut o, &mut witness);
 
     // Lambdaworks considers "1" a public input, so compensate for it
-    let num_of_pub_inputs = circom_r1cs["nPubInputs"].as_u64().unwrap() as usize + 1;
+    let num_of_pub_inputs = circom_r1cs.num_of_pub_inputs + 1;
 
     (
         QAP::from_variable_matrices(num_of_pub_inputs, &l, &r, &o),
@@ -28,38 +68,25 @@ pub fn circom_to_lambda(
     )
 }
 
-/// Takes as input circom.r1cs.json file and outputs LRO matrices
+/// Takes a circom R1CS and outputs LRO matrices
 #[inline]
-fn build_lro_from_circom_r1cs(circom_r1cs: &Value) -> [Vec<Vec<FrElement>>; 3] {
-    let num_of_vars = circom_r1cs["nVars"].as_u64().unwrap() as usize; // Includes "1"
-    let num_of_gates = circom_r1cs["nConstraints"].as_u64().unwrap() as usize;
-
-    let mut l = vec![vec![FrElement::zero(); num_of_gates]; num_of_vars];
-    let mut r = vec![vec![FrElement::zero(); num_of_gates]; num_of_vars];
-    let mut o = vec![vec![FrElement::zero(); num_of_gates]; num_of_vars];
-
-    for (constraint_idx, constraint) in circom_r1cs["constraints"]
-        .as_array()
-        .unwrap()
-        .iter()
-        .enumerate()
-    {
-        let constraint = constraint.as_array().unwrap();
-        for (var_idx, str_val) in constraint[0].as_object().unwrap() {
-            l[var_idx.parse::<usize>().unwrap()][constraint_idx] =
-                circom_str_to_lambda_field_element(str_val.as_str().unwrap());
-        }
-        for (var_idx, str_val) in constraint[1].as_object().unwrap() {
-            r[var_idx.parse::<usize>().unwrap()][constraint_idx] =
-                circom_str_to_lambda_field_element(str_val.as_str().unwrap());
-        }
-        for (var_idx, str_val) in constraint[2].as_object().unwrap() {
-            o[var_idx.parse::<usize>().unwrap()][constraint_idx] =
-                circom_str_to_lambda_field_element(str_val.as_str().unwrap());
+fn build_lro_from_circom_r1cs<F: IsField>(
+    circom_r1cs: &CircomR1CS<F>,
+) -> [Vec<Vec<FieldElement<F>>>; 3] {
+    let num_of_vars = circom_r1cs.num_of_vars;
+    let num_of_gates = circom_r1cs.num_of_constraints();
+
+    let mut lro = [(); 3].map(|_| vec![vec![FieldElement::zero(); num_of_gates]; num_of_vars]);
+
+    for (constraint_idx, constraint) in circom_r1cs.constraints.iter().enumerate() {
+        for (matrix, linear_combination) in lro.iter_mut().zip(constraint) {
+            for (var_idx, value) in linear_combination {
+                matrix[*var_idx][constraint_idx] = value.clone();
+            }
         }
     }
 
-    [l, r, o]
+    lro
 }
 
 /// Circom witness ordering: ["1", ..outputs, ...inputs, ...other_signals]
@@ -67,17 +94,15 @@ fn build_lro_from_circom_r1cs(circom_r1cs: &Value) -> [Vec<Vec<FrElement>>; 3] {
 /// Same applies to rows of LRO (each representing a variable)
 /// This function compensates this difference
 #[inline]
-fn adjust_lro_and_witness(
-    circom_r1cs: &Value,
-    l: &mut [Vec<FrElement>],
-    r: &mut [Vec<FrElement>],
-    o: &mut [Vec<FrElement>],
-    witness: &mut [FrElement],
+fn adjust_lro_and_witness<F: IsField>(
+    circom_r1cs: &CircomR1CS<F>,
+    l: &mut [Vec<FieldElement<F>>],
+    r: &mut [Vec<FieldElement<F>>],
+    o: &mut [Vec<FieldElement<F>>],
+    witness: &mut [FieldElement<F>],
 ) {
-    let num_of_private_inputs = circom_r1cs["nPrvInputs"].as_u64().unwrap() as usize;
-    let num_of_pub_inputs = circom_r1cs["nPubInputs"].as_u64().unwrap() as usize;
-    let num_of_inputs = num_of_pub_inputs + num_of_private_inputs;
-    let num_of_outputs = circom_r1cs["nOutputs"].as_u64().unwrap() as usize;
+    let num_of_inputs = circom_r1cs.num_of_pub_inputs + circom_r1cs.num_of_prv_inputs;
+    let num_of_outputs = circom_r1cs.num_of_outputs;
 
     let mut temp_l = Vec::with_capacity(num_of_inputs);
     let mut temp_r = Vec::with_capacity(num_of_inputs);
@@ -109,8 +134,3 @@ fn adjust_lro_and_witness(
         witness[num_of_outputs + 1 + i].clone_from(&temp_witness_i);
     }
 }
-
-#[inline]
-fn circom_str_to_lambda_field_element(value: &str) -> FrElement {
-    FrElement::from(&UnsignedInteger::<4>::from_dec_str(value).unwrap())
-}
@@ -0,0 +1,155 @@
+use lambdaworks_groth16::common::FrField;
+use lambdaworks_math::{
+    field::{
+        element::FieldElement,
+        traits::{IsField, IsPrimeField},
+    },
+    unsigned_integer::element::U256,
+};
+use serde_json::Value;
+
+use crate::{
+    binary::{check_fr_prime, BinFile},
+    errors::CircomError,
+};
+
+const R1CS_MAGIC: &[u8; 4] = b"r1cs";
+const R1CS_VERSION: u32 = 1;
+
+const HEADER_SECTION: u32 = 1;
+const CONSTRAINTS_SECTION: u32 = 2;
+
+/// Sparse linear combination: pairs of (variable index, coefficient).
+pub type LinearCombination<F = FrField> = Vec<(usize, FieldElement<F>)>;
+
+/// Constraint system as exported by circom. Variables follow circom's ordering:
+/// ["1", ...outputs, ...public inputs, ...private inputs, ...other signals]
+///
+/// `F` is the scalar field of the curve the circuit was compiled for, e.g. the one of BN254
+/// for circom's default `-p bn128`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CircomR1CS<F: IsField = FrField> {
+    pub num_of_vars: usize, // Includes "1"
+    pub num_of_outputs: usize,
+    pub num_of_pub_inputs: usize,
+    pub num_of_prv_inputs: usize,
+    /// L, R and O linear combinations of each constraint
+    pub constraints: Vec<[LinearCombination<F>; 3]>,
+}
+
+impl<F> CircomR1CS<F>
+where
+    F: IsPrimeField<BaseType = U256, RepresentativeType = U256>,
+{
+    /// Parses the output of `snarkjs r1cs export json`.
+    ///
+    /// # Panics
+    ///
+    /// If the JSON is malformed or the prime it declares is not the modulus of `F`.
+    pub fn from_json(circom_r1cs: &Value) -> Self {
+        let prime = U256::from_dec_str(circom_r1cs["prime"].as_str().unwrap()).unwrap();
+        assert_eq!(
+            prime,
+            F::modulus_minus_one() + U256::from_u64(1),
+            "{}",
+            CircomError::PrimeMismatch
+        );
+
+        let constraints = circom_r1cs["constraints"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|constraint| {
+                let constraint = constraint.as_array().unwrap();
+                [0, 1, 2].map(|i| {
+                    constraint[i]
+                        .as_object()
+                        .unwrap()
+                        .iter()
+                        .map(|(var_idx, str_val)| {
+                            (
+                                var_idx.parse::<usize>().unwrap(),
+                                circom_str_to_lambda_field_element(str_val.as_str().unwrap()),
+                            )
+                        })
+                        .collect()
+                })
+            })
+            .collect();
+
+        Self {
+            num_of_vars: circom_r1cs["nVars"].as_u64().unwrap() as usize,
+            num_of_outputs: circom_r1cs["nOutputs"].as_u64().unwrap() as usize,
+            num_of_pub_inputs: circom_r1cs["nPubInputs"].as_u64().unwrap() as usize,
+            num_of_prv_inputs: circom_r1cs["nPrvInputs"].as_u64().unwrap() as usize,
+            constraints,
+        }
+    }
+
+    /// Parses a binary `.r1cs` file, as produced by `circom --r1cs`.
+    ///
+    /// Header section: n8 (u32) | prime | nWires (u32) | nPubOut (u32) | nPubIn (u32) |
+    /// nPrvIn (u32) | nLabels (u64) | nConstraints (u32)
+    ///
+    /// Constraints section: for every constraint, the L, R and O linear combinations, each one
+    /// being nFactors (u32) followed by nFactors pairs of wire id (u32) | coefficient (n8 bytes).
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, CircomError> {
+        let file = BinFile::parse(bytes, R1CS_MAGIC, R1CS_VERSION)?;
+
+        let mut header = file.section(HEADER_SECTION)?;
+        let n8 = header.read_u32()? as usize;
+        check_fr_prime::<F>(&mut header, n8)?;
+        let num_of_vars = header.read_u32()? as usize;
+        let num_of_outputs = header.read_u32()? as usize;
+        let num_of_pub_inputs = header.read_u32()? as usize;
+        let num_of_prv_inputs = header.read_u32()? as usize;
+        let _num_of_labels = header.read_u64()?;
+        let num_of_constraints = header.read_u32()? as usize;
+
+        let mut section = file.section(CONSTRAINTS_SECTION)?;
+        let mut read_linear_combination = || -> Result<LinearCombination<F>, CircomError> {
+            let num_of_factors = section.read_u32()?;
+            (0..num_of_factors)
+                .map(|_| {
+                    let wire_id = section.read_u32()? as usize;
+                    if wire_id >= num_of_vars {
+                        return Err(CircomError::InvalidWireId(wire_id));
+                    }
+                    Ok((wire_id, section.read_fr_element(n8)?))
+                })
+                .collect()
+        };
+
+        let constraints = (0..num_of_constraints)
+            .map(|_| {
+                Ok([
+                    read_linear_combination()?,
+                    read_linear_combination()?,
+                    read_linear_combination()?,
+                ])
+            })
+            .collect::<Result<Vec<_>, CircomError>>()?;
+
+        Ok(Self {
+            num_of_vars,
+            num_of_outputs,
+            num_of_pub_inputs,
+            num_of_prv_inputs,
+            constraints,
+        })
+    }
+}
+
+impl<F: IsField> CircomR1CS<F> {
+    pub fn num_of_constraints(&self) -> usize {
+        self.constraints.len()
+    }
+}
+
+#[inline]
+pub(crate) fn circom_str_to_lambda_field_element<F>(value: &str) -> FieldElement<F>
+where
+    F: IsPrimeField<BaseType = U256>,
+{
+    FieldElement::from(&U256::from_dec_str(value).unwrap())
+}
@@ -0,0 +1,52 @@
+use lambdaworks_math::{
+    field::{element::FieldElement, traits::IsPrimeField},
+    unsigned_integer::element::U256,
+};
+
+use crate::{
+    binary::{check_fr_prime, BinFile},
+    errors::CircomError,
+};
+
+const WTNS_MAGIC: &[u8; 4] = b"wtns";
+const WTNS_VERSION: u32 = 2;
+
+const HEADER_SECTION: u32 = 1;
+const WITNESS_SECTION: u32 = 2;
+
+/// Parses the output of `snarkjs wtns export json` into elements of `F`. The JSON export does
+/// not declare its prime, so it is not checked.
+pub fn witness_from_json<F>(witness_file_content: &str) -> Vec<FieldElement<F>>
+where
+    F: IsPrimeField<BaseType = U256>,
+{
+    serde_json::from_str::<Vec<String>>(witness_file_content)
+        .expect("Error parsing JSON")
+        .iter()
+        .map(|num_str| crate::r1cs::circom_str_to_lambda_field_element(num_str))
+        .collect()
+}
+
+/// Parses a binary `.wtns` file, as produced by the witness generator of a circom circuit.
+/// The witness is returned in circom's ordering, as elements of `F`, which must be the field
+/// the witness was generated for.
+///
+/// Header section: n8 (u32) | prime | nWitness (u32)
+///
+/// Witness section: nWitness field elements of n8 bytes each.
+pub fn witness_from_bytes<F>(bytes: &[u8]) -> Result<Vec<FieldElement<F>>, CircomError>
+where
+    F: IsPrimeField<BaseType = U256, RepresentativeType = U256>,
+{
+    let file = BinFile::parse(bytes, WTNS_MAGIC, WTNS_VERSION)?;
+
+    let mut header = file.section(HEADER_SECTION)?;
+    let n8 = header.read_u32()? as usize;
+    check_fr_prime::<F>(&mut header, n8)?;
+    let num_of_values = header.read_u32()? as usize;
+
+    let mut section = file.section(WITNESS_SECTION)?;
+    (0..num_of_values)
+        .map(|_| section.read_fr_element(n8))
+        .collect()
+}
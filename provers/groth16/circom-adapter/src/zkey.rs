@@ -0,0 +1,355 @@
+use lambdaworks_groth16::{common::*, Proof, Prover, ProvingKey, VerifyingKey};
+use lambdaworks_math::{
+    cyclic_group::IsGroup,
+    elliptic_curve::{
+        short_weierstrass::curves::{
+            bls12_381::{
+                curve::BLS12381Curve,
+                field_extension::{BLS12381PrimeField, BLS12381_PRIME_FIELD_ORDER},
+                pairing::BLS12381AtePairing,
+                twist::BLS12381TwistCurve,
+            },
+            bn_254::{
+                curve::BN254Curve,
+                field_extension::{BN254PrimeField, BN254_PRIME_FIELD_ORDER},
+                pairing::BN254AtePairing,
+                twist::BN254TwistCurve,
+            },
+        },
+        traits::IsEllipticCurve,
+    },
+    field::{
+        element::FieldElement,
+        traits::{IsFFTField, IsField, IsPrimeField},
+    },
+    polynomial::Polynomial,
+    traits::ByteConversion,
+    unsigned_integer::element::UnsignedInteger,
+};
+
+use crate::{
+    binary::{check_fr_prime, BinFile, Reader},
+    errors::CircomError,
+};
+
+const ZKEY_MAGIC: &[u8; 4] = b"zkey";
+const ZKEY_VERSION: u32 = 1;
+
+const HEADER_SECTION: u32 = 1;
+const GROTH16_HEADER_SECTION: u32 = 2;
+const IC_SECTION: u32 = 3;
+const COEFFICIENTS_SECTION: u32 = 4;
+const A_SECTION: u32 = 5;
+const B1_SECTION: u32 = 6;
+const B2_SECTION: u32 = 7;
+const C_SECTION: u32 = 8;
+const H_SECTION: u32 = 9;
+
+const GROTH16_PROTOCOL_ID: u32 = 1;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Matrix {
+    A,
+    B,
+}
+
+/// Non-zero entry of the A or B matrices of the constraint system.
+/// The C matrix is not needed, since for a valid witness C·w = A·w ∘ B·w.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Coefficient<F: IsField = FrField> {
+    pub matrix: Matrix,
+    pub constraint: usize,
+    pub signal: usize,
+    pub value: FieldElement<F>,
+}
+
+/// A curve snarkjs generates Groth16 keys for, given by how the points of its zkeys are
+/// decoded.
+pub trait IsCircomCurve: IsGroth16Curve {
+    /// Modulus of the base field, in little endian. Its length is the size of a coordinate.
+    fn base_field_prime() -> Vec<u8>;
+
+    /// Decodes a point of G1 from its affine coordinates, in Montgomery form.
+    fn g1_from_bytes(bytes: &[u8]) -> Result<G1PointOf<Self>, CircomError>;
+
+    /// Decodes a point of G2 from its affine coordinates, whose (c0, c1) components are in
+    /// Montgomery form.
+    fn g2_from_bytes(bytes: &[u8]) -> Result<G2PointOf<Self>, CircomError>;
+}
+
+impl IsCircomCurve for BLS12381AtePairing {
+    fn base_field_prime() -> Vec<u8> {
+        BLS12381_PRIME_FIELD_ORDER.to_bytes_le()
+    }
+
+    fn g1_from_bytes(bytes: &[u8]) -> Result<G1PointOf<Self>, CircomError> {
+        let [x, y] = read_coordinates::<BLS12381PrimeField, 6, 2>(bytes)?;
+        point_from_affine::<BLS12381Curve>(x, y)
+    }
+
+    fn g2_from_bytes(bytes: &[u8]) -> Result<G2PointOf<Self>, CircomError> {
+        let [x0, x1, y0, y1] = read_coordinates::<BLS12381PrimeField, 6, 4>(bytes)?;
+        point_from_affine::<BLS12381TwistCurve>(
+            FieldElement::new([x0, x1]),
+            FieldElement::new([y0, y1]),
+        )
+    }
+}
+
+impl IsCircomCurve for BN254AtePairing {
+    fn base_field_prime() -> Vec<u8> {
+        BN254_PRIME_FIELD_ORDER.to_bytes_le()
+    }
+
+    fn g1_from_bytes(bytes: &[u8]) -> Result<G1PointOf<Self>, CircomError> {
+        let [x, y] = read_coordinates::<BN254PrimeField, 4, 2>(bytes)?;
+        point_from_affine::<BN254Curve>(x, y)
+    }
+
+    fn g2_from_bytes(bytes: &[u8]) -> Result<G2PointOf<Self>, CircomError> {
+        let [x0, x1, y0, y1] = read_coordinates::<BN254PrimeField, 4, 4>(bytes)?;
+        point_from_affine::<BN254TwistCurve>(
+            FieldElement::new([x0, x1]),
+            FieldElement::new([y0, y1]),
+        )
+    }
+}
+
+/// Groth16 proving key generated by snarkjs (`snarkjs groth16 setup`).
+///
+/// The keys are mapped to the lambdaworks ones, so proofs produced with [`ZKey::prove`] are
+/// checked with [`lambdaworks_groth16::verify`] and `verifying_key`. Both the witness and the
+/// public inputs follow circom's ordering: ["1", ...outputs, ...public inputs, ...].
+///
+/// Keys over BN254, circom's default curve, are read as `ZKey<BN254AtePairing>`, and keys of
+/// circuits compiled with `-p bls12381` as `ZKey<BLS12381AtePairing>`. Keys over another
+/// curve are rejected with [`CircomError::PrimeMismatch`].
+pub struct ZKey<C: IsCircomCurve = BLS12381AtePairing> {
+    pub num_of_vars: usize,
+    /// Number of public signals, not including "1"
+    pub num_of_public: usize,
+    pub domain_size: usize,
+    pub coefficients: Vec<Coefficient<C::FrField>>,
+    /// Note that `z_powers_of_tau_g1` holds the H section of the zkey, which is expressed in the
+    /// Lagrange basis of a coset of the domain rather than in the monomial basis.
+    pub proving_key: ProvingKey<C>,
+    pub verifying_key: VerifyingKey<C>,
+}
+
+impl<C: IsCircomCurve> ZKey<C> {
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, CircomError> {
+        let file = BinFile::parse(bytes, ZKEY_MAGIC, ZKEY_VERSION)?;
+
+        let protocol = file.section(HEADER_SECTION)?.read_u32()?;
+        if protocol != GROTH16_PROTOCOL_ID {
+            return Err(CircomError::UnsupportedProtocol(protocol));
+        }
+
+        let mut header = file.section(GROTH16_HEADER_SECTION)?;
+        let n8q = header.read_u32()? as usize;
+        if header.read_bytes(n8q)? != C::base_field_prime() {
+            return Err(CircomError::PrimeMismatch);
+        }
+        let n8r = header.read_u32()? as usize;
+        check_fr_prime::<C::FrField>(&mut header, n8r)?;
+        let num_of_vars = header.read_u32()? as usize;
+        let num_of_public = header.read_u32()? as usize;
+        let domain_size = header.read_u32()? as usize;
+        if !domain_size.is_power_of_two() || num_of_public >= num_of_vars {
+            return Err(CircomError::InvalidHeader);
+        }
+        let alpha_g1 = read_g1::<C>(&mut header, n8q)?;
+        let beta_g1 = read_g1::<C>(&mut header, n8q)?;
+        let beta_g2 = read_g2::<C>(&mut header, n8q)?;
+        let gamma_g2 = read_g2::<C>(&mut header, n8q)?;
+        let delta_g1 = read_g1::<C>(&mut header, n8q)?;
+        let delta_g2 = read_g2::<C>(&mut header, n8q)?;
+
+        let read_g1_section = |section_type, len| -> Result<Vec<G1PointOf<C>>, CircomError> {
+            let mut section = file.section(section_type)?;
+            (0..len).map(|_| read_g1::<C>(&mut section, n8q)).collect()
+        };
+
+        let verifier_k_tau_g1 = read_g1_section(IC_SECTION, num_of_public + 1)?;
+        let l_tau_g1 = read_g1_section(A_SECTION, num_of_vars)?;
+        let r_tau_g1 = read_g1_section(B1_SECTION, num_of_vars)?;
+        let prover_k_tau_g1 = read_g1_section(C_SECTION, num_of_vars - num_of_public - 1)?;
+        let z_powers_of_tau_g1 = read_g1_section(H_SECTION, domain_size)?;
+        let r_tau_g2 = {
+            let mut section = file.section(B2_SECTION)?;
+            (0..num_of_vars)
+                .map(|_| read_g2::<C>(&mut section, n8q))
+                .collect::<Result<Vec<_>, _>>()?
+        };
+
+        let coefficients = read_coefficients(
+            &mut file.section(COEFFICIENTS_SECTION)?,
+            n8r,
+            num_of_vars,
+            domain_size,
+        )?;
+
+        let alpha_g1_times_beta_g2 =
+            C::compute(&alpha_g1, &beta_g2).map_err(|_| CircomError::InvalidPoint)?;
+
+        Ok(Self {
+            num_of_vars,
+            num_of_public,
+            domain_size,
+            coefficients,
+            proving_key: ProvingKey {
+                alpha_g1,
+                beta_g1,
+                beta_g2,
+                delta_g1,
+                delta_g2: delta_g2.clone(),
+                l_tau_g1,
+                r_tau_g1,
+                r_tau_g2,
+                prover_k_tau_g1,
+                z_powers_of_tau_g1,
+            },
+            verifying_key: VerifyingKey {
+                alpha_g1_times_beta_g2,
+                delta_g2,
+                gamma_g2,
+                verifier_k_tau_g1,
+            },
+        })
+    }
+
+    /// Proves a witness in circom's ordering, e.g. the one returned by
+    /// [`crate::witness_from_bytes`]. The public inputs to verify it are `w[..=num_of_public]`.
+    pub fn prove(&self, w: &[FrElementOf<C>]) -> Result<Proof<C>, CircomError> {
+        if w.len() != self.num_of_vars {
+            return Err(CircomError::InvalidWitnessLength(self.num_of_vars, w.len()));
+        }
+
+        let (coefficients, domain_size) = (&self.coefficients, self.domain_size);
+        Ok(Prover::prove_with_quotient(
+            w,
+            self.num_of_public + 1,
+            &self.proving_key,
+            || quotient_evaluations(coefficients, domain_size, w),
+        ))
+    }
+}
+
+/// Evaluates A·w * B·w - C·w over the coset ω_{2n}·<ω_n>, where n is the domain size.
+/// The H section of the zkey is the Lagrange basis of this coset, scaled by
+/// ƍ^{-1} * t(τ) / t(ω_{2n}), so these evaluations are the scalars of the H term MSM.
+fn quotient_evaluations<F: IsFFTField>(
+    coefficients: &[Coefficient<F>],
+    domain_size: usize,
+    w: &[FieldElement<F>],
+) -> Vec<FieldElement<F>> {
+    let mut a = vec![FieldElement::zero(); domain_size];
+    let mut b = vec![FieldElement::zero(); domain_size];
+    for coefficient in coefficients {
+        let column = match coefficient.matrix {
+            Matrix::A => &mut a,
+            Matrix::B => &mut b,
+        };
+        column[coefficient.constraint] += &coefficient.value * &w[coefficient.signal];
+    }
+    let c = a.iter().zip(&b).map(|(a, b)| a * b).collect::<Vec<_>>();
+
+    let offset = F::get_primitive_root_of_unity(domain_size.trailing_zeros() as u64 + 1).unwrap();
+    let [a, b, c] = [a, b, c].map(|evaluations| {
+        let poly = Polynomial::interpolate_fft::<F>(&evaluations).unwrap();
+        Polynomial::evaluate_offset_fft(&poly, 1, Some(domain_size), &offset).unwrap()
+    });
+
+    a.iter()
+        .zip(&b)
+        .zip(&c)
+        .map(|((a, b), c)| a * b - c)
+        .collect()
+}
+
+fn read_coefficients<F>(
+    section: &mut Reader,
+    n8r: usize,
+    num_of_vars: usize,
+    domain_size: usize,
+) -> Result<Vec<Coefficient<F>>, CircomError>
+where
+    F: IsPrimeField<BaseType = UnsignedInteger<4>, RepresentativeType = UnsignedInteger<4>>,
+{
+    // snarkjs stores v * R^2 as a plain integer, R being the Montgomery parameter.
+    // Taking it as a Montgomery representation gives v * R, which is fixed by multiplying
+    // by R^{-1}, whose Montgomery representation is 1.
+    let r_inv = FieldElement::<F>::from_raw(UnsignedInteger::from_u64(1));
+
+    let num_of_coefficients = section.read_u32()?;
+    (0..num_of_coefficients)
+        .map(|_| {
+            let matrix = match section.read_u32()? {
+                0 => Matrix::A,
+                1 => Matrix::B,
+                _ => return Err(CircomError::InvalidHeader),
+            };
+            let constraint = section.read_u32()? as usize;
+            if constraint >= domain_size {
+                return Err(CircomError::InvalidWireId(constraint));
+            }
+            let signal = section.read_u32()? as usize;
+            if signal >= num_of_vars {
+                return Err(CircomError::InvalidWireId(signal));
+            }
+            let value = section.read_montgomery_element::<F, 4>(n8r)? * &r_inv;
+            Ok(Coefficient {
+                matrix,
+                constraint,
+                signal,
+                value,
+            })
+        })
+        .collect()
+}
+
+/// Points are stored in affine form, with coordinates in Montgomery form.
+/// The point at infinity is encoded as (0, 0).
+fn read_g1<C: IsCircomCurve>(reader: &mut Reader, n8q: usize) -> Result<G1PointOf<C>, CircomError> {
+    C::g1_from_bytes(reader.read_bytes(2 * n8q)?)
+}
+
+/// G2 coordinates are stored as (c0, c1) pairs.
+fn read_g2<C: IsCircomCurve>(reader: &mut Reader, n8q: usize) -> Result<G2PointOf<C>, CircomError> {
+    C::g2_from_bytes(reader.read_bytes(4 * n8q)?)
+}
+
+/// Splits `bytes` into `N` elements of the base field `F` in Montgomery form.
+fn read_coordinates<F, const NUM_LIMBS: usize, const N: usize>(
+    bytes: &[u8],
+) -> Result<[FieldElement<F>; N], CircomError>
+where
+    F: IsPrimeField<
+        BaseType = UnsignedInteger<NUM_LIMBS>,
+        RepresentativeType = UnsignedInteger<NUM_LIMBS>,
+    >,
+{
+    let n8q = NUM_LIMBS * 8;
+    if bytes.len() != n8q * N {
+        return Err(CircomError::PrimeMismatch);
+    }
+    let mut reader = Reader::new(bytes);
+    let coordinates = (0..N)
+        .map(|_| reader.read_montgomery_element::<F, NUM_LIMBS>(n8q))
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(coordinates.try_into().unwrap())
+}
+
+fn point_from_affine<E>(
+    x: FieldElement<E::BaseField>,
+    y: FieldElement<E::BaseField>,
+) -> Result<E::PointRepresentation, CircomError>
+where
+    E: IsEllipticCurve,
+    E::PointRepresentation: IsGroup,
+{
+    if x == FieldElement::zero() && y == FieldElement::zero() {
+        return Ok(E::PointRepresentation::neutral_element());
+    }
+    E::create_point_from_affine(x, y).map_err(|_| CircomError::InvalidPoint)
+}
@@ -1,15 +1,105 @@
 use lambdaworks_math::{
+    cyclic_group::IsGroup,
     elliptic_curve::{
-        short_weierstrass::curves::bls12_381::{
-            curve::BLS12381Curve, default_types::FrElement as FE, default_types::FrField as FrF,
-            pairing::BLS12381AtePairing, twist::BLS12381TwistCurve,
+        short_weierstrass::curves::{
+            bls12_381::{
+                curve::BLS12381Curve, default_types::FrElement as FE,
+                default_types::FrField as FrF,
+                field_extension::Degree2ExtensionField as BLS12381Degree2ExtensionField,
+                pairing::BLS12381AtePairing, twist::BLS12381TwistCurve,
+            },
+            bn_254::{
+                curve::BN254Curve, default_types::FrField as BN254FrField,
+                field_extension::Degree2ExtensionField as BN254Degree2ExtensionField,
+                pairing::BN254AtePairing, twist::BN254TwistCurve,
+            },
         },
         traits::{IsEllipticCurve, IsPairing},
     },
-    field::element::FieldElement,
+    environment::{random_field_element, IsRandomSource, OsRandomSource},
+    field::{
+        element::FieldElement,
+        traits::{IsFFTField, IsField, IsPrimeField},
+    },
+    traits::{AsBytes, Deserializable},
     unsigned_integer::element::U256,
 };
-use rand::{Rng, SeedableRng};
+
+/// A pairing-friendly curve Groth16 can be instantiated with, given by its pairing.
+///
+/// It is implemented for [`BLS12381AtePairing`], the default of every type of this crate, and
+/// for [`BN254AtePairing`], the default curve of circom and snarkjs.
+pub trait IsGroth16Curve:
+    IsPairing<
+        G1Point: IsGroup + AsBytes + Deserializable + Send + Sync,
+        G2Point: IsGroup + AsBytes + Deserializable + Send + Sync,
+    > + Sized
+{
+    type G1Curve: IsEllipticCurve<PointRepresentation = Self::G1Point>;
+    type G2Curve: IsEllipticCurve<PointRepresentation = Self::G2Point>;
+    type FrField: IsFFTField<BaseType = U256> + IsPrimeField<RepresentativeType = U256>;
+    /// The quadratic extension of the base field. Pairing outputs are written as their six
+    /// coefficients over it.
+    type Fp2: IsField;
+
+    /// A generator of the multiplicative group of the scalar field, which is the offset of the
+    /// coset the quotient polynomial is computed on.
+    const FR_GENERATOR: FrElementOf<Self>;
+
+    fn output_coefficients(output: &PairingOutputOf<Self>) -> [FieldElement<Self::Fp2>; 6];
+    fn output_from_coefficients(
+        coefficients: [FieldElement<Self::Fp2>; 6],
+    ) -> PairingOutputOf<Self>;
+}
+
+impl IsGroth16Curve for BLS12381AtePairing {
+    type G1Curve = BLS12381Curve;
+    type G2Curve = BLS12381TwistCurve;
+    type FrField = FrF;
+    type Fp2 = BLS12381Degree2ExtensionField;
+
+    const FR_GENERATOR: FrElement = ORDER_R_MINUS_1_ROOT_UNITY;
+
+    fn output_coefficients(output: &PairingOutput) -> [FieldElement<Self::Fp2>; 6] {
+        let [c0, c1] = output.value();
+        let ([a, b, c], [d, e, f]) = (c0.value(), c1.value());
+        [a, b, c, d, e, f].map(Clone::clone)
+    }
+
+    fn output_from_coefficients(coefficients: [FieldElement<Self::Fp2>; 6]) -> PairingOutput {
+        let [a, b, c, d, e, f] = coefficients;
+        PairingOutput::new([FieldElement::new([a, b, c]), FieldElement::new([d, e, f])])
+    }
+}
+
+impl IsGroth16Curve for BN254AtePairing {
+    type G1Curve = BN254Curve;
+    type G2Curve = BN254TwistCurve;
+    type FrField = BN254FrField;
+    type Fp2 = BN254Degree2ExtensionField;
+
+    const FR_GENERATOR: FrElementOf<Self> = FieldElement::from_hex_unchecked("5");
+
+    fn output_coefficients(output: &PairingOutputOf<Self>) -> [FieldElement<Self::Fp2>; 6] {
+        let [c0, c1] = output.value();
+        let ([a, b, c], [d, e, f]) = (c0.value(), c1.value());
+        [a, b, c, d, e, f].map(Clone::clone)
+    }
+
+    fn output_from_coefficients(
+        coefficients: [FieldElement<Self::Fp2>; 6],
+    ) -> PairingOutputOf<Self> {
+        let [a, b, c, d, e, f] = coefficients;
+        FieldElement::new([FieldElement::new([a, b, c]), FieldElement::new([d, e, f])])
+    }
+}
+
+pub type FrElementOf<C> = FieldElement<<C as IsGroth16Curve>::FrField>;
+pub type G1PointOf<C> = <C as IsPairing>::G1Point;
+pub type G2PointOf<C> = <C as IsPairing>::G2Point;
+pub type PairingOutputOf<C> = FieldElement<<C as IsPairing>::OutputField>;
+
+// Types of the default curve, BLS12-381
 
 pub type Curve = BLS12381Curve;
 pub type TwistedCurve = BLS12381TwistCurve;
@@ -25,14 +115,27 @@ pub type PairingOutput = FieldElement<<Pairing as IsPairing>::OutputField>;
 
 pub const ORDER_R_MINUS_1_ROOT_UNITY: FrElement = FrElement::from_hex_unchecked("7");
 
+/// Runs both closures, in parallel when the `parallel` feature is enabled.
+#[cfg(feature = "parallel")]
+pub(crate) use rayon::join;
+
+/// Runs both closures, in parallel when the `parallel` feature is enabled.
+#[cfg(not(feature = "parallel"))]
+pub(crate) fn join<A, B, RA, RB>(oper_a: A, oper_b: B) -> (RA, RB)
+where
+    A: FnOnce() -> RA + Send,
+    B: FnOnce() -> RB + Send,
+    RA: Send,
+    RB: Send,
+{
+    (oper_a(), oper_b())
+}
+
+/// Samples a random element of the scalar field from the randomness of the operating system.
 pub fn sample_fr_elem() -> FrElement {
-    let mut rng = rand_chacha::ChaCha20Rng::seed_from_u64(9001);
-    FrElement::new(U256 {
-        limbs: [
-            rng.gen::<u64>(),
-            rng.gen::<u64>(),
-            rng.gen::<u64>(),
-            rng.gen::<u64>(),
-        ],
-    })
+    sample_fr_elem_with(&OsRandomSource)
+}
+
+pub fn sample_fr_elem_with<F: IsPrimeField>(source: &impl IsRandomSource) -> FieldElement<F> {
+    random_field_element(source)
 }
@@ -0,0 +1,269 @@
+use core::ops::{Add, Sub};
+
+use lambdaworks_math::field::{element::FieldElement, traits::IsField};
+
+use crate::{
+    common::FrField,
+    r1cs::{Constraint, ConstraintSystem, R1CS},
+};
+
+/// Variable of a [`ConstraintSystemBuilder`].
+/// Public inputs and private witness values are numbered separately, since the Groth16
+/// backend expects the witness as ["1", ...public inputs, ...private witness values].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Variable {
+    One,
+    Public(usize),
+    Private(usize),
+}
+
+/// Sum of terms `coefficient * variable`.
+#[derive(Clone, Debug)]
+pub struct LinearCombination<F: IsField = FrField>(pub Vec<(FieldElement<F>, Variable)>);
+
+impl<F: IsField> LinearCombination<F> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn constant(value: FieldElement<F>) -> Self {
+        Self(vec![(value, Variable::One)])
+    }
+}
+
+impl<F: IsField> Default for LinearCombination<F> {
+    fn default() -> Self {
+        Self(Vec::new())
+    }
+}
+
+impl<F: IsField> PartialEq for LinearCombination<F> {
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0
+    }
+}
+
+impl<F: IsField> Eq for LinearCombination<F> {}
+
+impl<F: IsField> From<Variable> for LinearCombination<F> {
+    fn from(variable: Variable) -> Self {
+        Self(vec![(FieldElement::one(), variable)])
+    }
+}
+
+impl<F: IsField> Add<(FieldElement<F>, Variable)> for LinearCombination<F> {
+    type Output = LinearCombination<F>;
+
+    fn add(mut self, term: (FieldElement<F>, Variable)) -> Self::Output {
+        self.0.push(term);
+        self
+    }
+}
+
+impl<F: IsField> Add<Variable> for LinearCombination<F> {
+    type Output = LinearCombination<F>;
+
+    fn add(self, variable: Variable) -> Self::Output {
+        self + (FieldElement::one(), variable)
+    }
+}
+
+impl<F: IsField> Sub<Variable> for LinearCombination<F> {
+    type Output = LinearCombination<F>;
+
+    fn sub(self, variable: Variable) -> Self::Output {
+        self + (-FieldElement::<F>::one(), variable)
+    }
+}
+
+impl<F: IsField> Add<LinearCombination<F>> for LinearCombination<F> {
+    type Output = LinearCombination<F>;
+
+    fn add(mut self, other: LinearCombination<F>) -> Self::Output {
+        self.0.extend(other.0);
+        self
+    }
+}
+
+/// Builds a rank one constraint system `(A·z) * (B·z) = C·z` together with its assignment,
+/// so circuits can be written directly against the lambdaworks backends. The scalar field
+/// defaults to the one of BLS12-381.
+///
+/// ```
+/// use lambdaworks_groth16::{common::FrElement, frontend::*, QuadraticArithmeticProgram};
+///
+/// // x * x = y, with y public
+/// let mut builder = ConstraintSystemBuilder::new();
+/// let y = builder.new_public_input(FrElement::from(9));
+/// let x = builder.new_witness(FrElement::from(3));
+/// builder.enforce(x, x, y);
+/// assert!(builder.is_satisfied());
+///
+/// let cs = builder.build();
+/// let qap: QuadraticArithmeticProgram = QuadraticArithmeticProgram::from_r1cs(cs.constraints);
+/// ```
+#[derive(Clone, Debug)]
+pub struct ConstraintSystemBuilder<F: IsField = FrField> {
+    public_inputs: Vec<FieldElement<F>>,
+    witness: Vec<FieldElement<F>>,
+    constraints: Vec<[LinearCombination<F>; 3]>,
+}
+
+impl<F: IsField> Default for ConstraintSystemBuilder<F> {
+    fn default() -> Self {
+        Self {
+            public_inputs: Vec::new(),
+            witness: Vec::new(),
+            constraints: Vec::new(),
+        }
+    }
+}
+
+impl<F: IsField> ConstraintSystemBuilder<F> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn new_public_input(&mut self, value: FieldElement<F>) -> Variable {
+        self.public_inputs.push(value);
+        Variable::Public(self.public_inputs.len() - 1)
+    }
+
+    pub fn new_witness(&mut self, value: FieldElement<F>) -> Variable {
+        self.witness.push(value);
+        Variable::Private(self.witness.len() - 1)
+    }
+
+    /// Adds the constraint `a * b = c`.
+    pub fn enforce(
+        &mut self,
+        a: impl Into<LinearCombination<F>>,
+        b: impl Into<LinearCombination<F>>,
+        c: impl Into<LinearCombination<F>>,
+    ) {
+        self.constraints.push([a.into(), b.into(), c.into()]);
+    }
+
+    /// Allocates a private variable holding `a * b` and constrains it.
+    pub fn mul(
+        &mut self,
+        a: impl Into<LinearCombination<F>>,
+        b: impl Into<LinearCombination<F>>,
+    ) -> Variable {
+        let (a, b) = (a.into(), b.into());
+        let product = self.new_witness(self.evaluate(&a) * self.evaluate(&b));
+        self.enforce(a, b, product);
+        product
+    }
+
+    pub fn value(&self, variable: Variable) -> FieldElement<F> {
+        match variable {
+            Variable::One => FieldElement::one(),
+            Variable::Public(i) => self.public_inputs[i].clone(),
+            Variable::Private(i) => self.witness[i].clone(),
+        }
+    }
+
+    pub fn evaluate(&self, linear_combination: &LinearCombination<F>) -> FieldElement<F> {
+        linear_combination
+            .0
+            .iter()
+            .map(|(coefficient, variable)| coefficient * self.value(*variable))
+            .fold(FieldElement::zero(), |acc, term| acc + term)
+    }
+
+    pub fn num_of_constraints(&self) -> usize {
+        self.constraints.len()
+    }
+
+    /// Number of variables, including "1".
+    pub fn num_of_variables(&self) -> usize {
+        1 + self.public_inputs.len() + self.witness.len()
+    }
+
+    /// Checks whether the current assignment satisfies all the constraints.
+    pub fn is_satisfied(&self) -> bool {
+        self.constraints
+            .iter()
+            .all(|[a, b, c]| self.evaluate(a) * self.evaluate(b) == self.evaluate(c))
+    }
+
+    /// Returns the dense R1CS and the witness ordered as ["1", ...public inputs, ...private].
+    pub fn build(self) -> ConstraintSystem<F> {
+        let num_of_variables = self.num_of_variables();
+        let index = |variable: Variable| match variable {
+            Variable::One => 0,
+            Variable::Public(i) => 1 + i,
+            Variable::Private(i) => 1 + self.public_inputs.len() + i,
+        };
+        let to_dense = |linear_combination: &LinearCombination<F>| {
+            let mut row = vec![FieldElement::zero(); num_of_variables];
+            for (coefficient, variable) in &linear_combination.0 {
+                row[index(*variable)] += coefficient.clone();
+            }
+            row
+        };
+
+        let constraints = self
+            .constraints
+            .iter()
+            .map(|[a, b, c]| Constraint {
+                a: to_dense(a),
+                b: to_dense(b),
+                c: to_dense(c),
+            })
+            .collect();
+
+        let mut witness = vec![FieldElement::one()];
+        witness.extend(self.public_inputs.iter().cloned());
+        witness.extend(self.witness.iter().cloned());
+
+        ConstraintSystem {
+            constraints: R1CS {
+                constraints,
+                number_of_inputs: 1 + self.public_inputs.len(),
+            },
+            witness,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::FrElement;
+
+    #[test]
+    fn unsatisfied_constraints_are_detected() {
+        let mut builder = ConstraintSystemBuilder::new();
+        let y = builder.new_public_input(FrElement::from(10));
+        let x = builder.new_witness(FrElement::from(3));
+        builder.enforce(x, x, y);
+
+        assert!(!builder.is_satisfied());
+    }
+
+    #[test]
+    fn build_places_public_inputs_first() {
+        let mut builder = ConstraintSystemBuilder::new();
+        let x = builder.new_witness(FrElement::from(3));
+        let x_squared = builder.mul(x, x);
+        let out = builder.new_public_input(FrElement::from(14));
+        // (x^2 + x + 2) * 1 = out
+        builder.enforce(
+            LinearCombination::from(x_squared) + x + (FrElement::from(2), Variable::One),
+            Variable::One,
+            out,
+        );
+        assert!(builder.is_satisfied());
+
+        let cs = builder.build();
+
+        assert_eq!(cs.witness, [1, 14, 3, 9].map(FrElement::from).to_vec());
+        assert_eq!(cs.constraints.number_of_inputs, 2);
+        assert_eq!(
+            cs.constraints.constraints[1].a,
+            [2, 0, 1, 1].map(FrElement::from).to_vec()
+        );
+    }
+}
@@ -1,8 +1,10 @@
 pub mod common;
+pub mod frontend;
 pub mod qap;
 pub mod r1cs;
 
 mod prover;
+mod serialization;
 mod setup;
 mod verifier;
 
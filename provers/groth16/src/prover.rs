@@ -1,16 +1,20 @@
 use crate::{common::*, ProvingKey, QuadraticArithmeticProgram};
+use lambdaworks_math::elliptic_curve::short_weierstrass::curves::bls12_381::pairing::BLS12381AtePairing;
+use lambdaworks_math::environment::{IsRandomSource, OsRandomSource};
 use lambdaworks_math::errors::DeserializationError;
 use lambdaworks_math::traits::{AsBytes, Deserializable};
-use lambdaworks_math::{cyclic_group::IsGroup, msm::pippenger::msm};
+use lambdaworks_math::{
+    cyclic_group::IsGroup, msm::pippenger, unsigned_integer::element::UnsignedInteger,
+};
 use std::mem::size_of;
 
-pub struct Proof {
-    pub pi1: G1Point,
-    pub pi2: G2Point,
-    pub pi3: G1Point,
+pub struct Proof<C: IsGroth16Curve = BLS12381AtePairing> {
+    pub pi1: G1PointOf<C>,
+    pub pi2: G2PointOf<C>,
+    pub pi3: G1PointOf<C>,
 }
 
-impl Proof {
+impl<C: IsGroth16Curve> Proof<C> {
     pub fn serialize(&self) -> Vec<u8> {
         let mut bytes: Vec<u8> = Vec::new();
         [
@@ -30,9 +34,9 @@ impl Proof {
     where
         Self: Sized,
     {
-        let (offset, pi1) = Self::deserialize_commitment::<G1Point>(bytes, 0)?;
-        let (offset, pi2) = Self::deserialize_commitment::<G2Point>(bytes, offset)?;
-        let (_, pi3) = Self::deserialize_commitment::<G1Point>(bytes, offset)?;
+        let (offset, pi1) = Self::deserialize_commitment::<G1PointOf<C>>(bytes, 0)?;
+        let (offset, pi2) = Self::deserialize_commitment::<G2PointOf<C>>(bytes, offset)?;
+        let (_, pi3) = Self::deserialize_commitment::<G1PointOf<C>>(bytes, offset)?;
         Ok(Self { pi1, pi2, pi3 })
     }
 
@@ -65,51 +69,118 @@ impl Proof {
 
 pub struct Prover;
 impl Prover {
-    pub fn prove(w: &[FrElement], qap: &QuadraticArithmeticProgram, pk: &ProvingKey) -> Proof {
-        let h_coefficients = qap
-            .calculate_h_coefficients(w)
-            .iter()
-            .map(|elem| elem.representative())
-            .collect::<Vec<_>>();
+    pub fn prove<C: IsGroth16Curve>(
+        w: &[FrElementOf<C>],
+        qap: &QuadraticArithmeticProgram<C>,
+        pk: &ProvingKey<C>,
+    ) -> Proof<C> {
+        Self::prove_with_random_source(w, qap, pk, &OsRandomSource)
+    }
+
+    /// Builds a proof sampling its blinding factors from `source` instead of the randomness of
+    /// the operating system.
+    pub fn prove_with_random_source<C: IsGroth16Curve>(
+        w: &[FrElementOf<C>],
+        qap: &QuadraticArithmeticProgram<C>,
+        pk: &ProvingKey<C>,
+        source: &impl IsRandomSource,
+    ) -> Proof<C> {
+        Self::prove_with_quotient_and_random_source(w, qap.num_of_public_inputs, pk, source, || {
+            qap.calculate_h_coefficients(w)
+        })
+    }
 
-        let w = w
+    /// Builds a proof given a closure computing the scalars that `pk.z_powers_of_tau_g1` is
+    /// multiplied with to obtain [ƍ^{-1} * t(τ)*h(τ)]_1. These are the coefficients of h for
+    /// keys produced by [`crate::setup`], but other setups (e.g. circom's zkeys) encode this
+    /// term in a different basis.
+    pub fn prove_with_quotient<C, H>(
+        w: &[FrElementOf<C>],
+        num_of_public_inputs: usize,
+        pk: &ProvingKey<C>,
+        quotient: H,
+    ) -> Proof<C>
+    where
+        C: IsGroth16Curve,
+        H: FnOnce() -> Vec<FrElementOf<C>> + Send,
+    {
+        Self::prove_with_quotient_and_random_source(
+            w,
+            num_of_public_inputs,
+            pk,
+            &OsRandomSource,
+            quotient,
+        )
+    }
+
+    /// Like [`Prover::prove_with_quotient`], sampling the blinding factors from `source`.
+    pub fn prove_with_quotient_and_random_source<C, H>(
+        w: &[FrElementOf<C>],
+        num_of_public_inputs: usize,
+        pk: &ProvingKey<C>,
+        source: &impl IsRandomSource,
+        quotient: H,
+    ) -> Proof<C>
+    where
+        C: IsGroth16Curve,
+        H: FnOnce() -> Vec<FrElementOf<C>> + Send,
+    {
+        // Sample randomness for hiding
+        let r: FrElementOf<C> = sample_fr_elem_with(source);
+        let s: FrElementOf<C> = sample_fr_elem_with(source);
+
+        let w_repr = w
             .iter()
             .map(|elem| elem.representative())
             .collect::<Vec<_>>();
-
-        // Sample randomness for hiding
-        let r = sample_fr_elem();
-        let s = sample_fr_elem();
+        let num_of_private_inputs = w.len() - num_of_public_inputs;
+
+        // The A, B and C terms are independent MSMs, and the quotient polynomial only depends
+        // on the witness, so its FFTs run concurrently with them.
+        let ((t_tau_h_tau_assigned_g1, k_tau_assigned_prover_g1), ((pi1_msm, pi2_msm), pi2_g1_msm)) =
+            join(
+                || {
+                    join(
+                        // [ƍ^{-1} * t(τ)*h(τ)]_1
+                        || {
+                            let h_coefficients = quotient()
+                                .iter()
+                                .map(|elem| elem.representative())
+                                .collect::<Vec<_>>();
+                            msm(
+                                &h_coefficients,
+                                &pk.z_powers_of_tau_g1[..h_coefficients.len()],
+                            )
+                        },
+                        // [ƍ^{-1} * (β*l(τ) + α*r(τ) + o(τ))]_1
+                        || {
+                            msm(
+                                &w_repr[num_of_public_inputs..],
+                                &pk.prover_k_tau_g1[..num_of_private_inputs],
+                            )
+                        },
+                    )
+                },
+                || {
+                    join(
+                        || join(|| msm(&w_repr, &pk.l_tau_g1), || msm(&w_repr, &pk.r_tau_g2)),
+                        || msm(&w_repr, &pk.r_tau_g1),
+                    )
+                },
+            );
 
         // [π_1]_1
-        let pi1 = msm(&w, &pk.l_tau_g1)
-            .unwrap()
+        let pi1 = pi1_msm
             .operate_with(&pk.alpha_g1)
             .operate_with(&pk.delta_g1.operate_with_self(r.representative()));
 
         // [π_2]_2
-        let pi2 = msm(&w, &pk.r_tau_g2)
-            .unwrap()
+        let pi2 = pi2_msm
             .operate_with(&pk.beta_g2)
             .operate_with(&pk.delta_g2.operate_with_self(s.representative()));
 
-        // [ƍ^{-1} * t(τ)*h(τ)]_1
-        let t_tau_h_tau_assigned_g1 = msm(
-            &h_coefficients,
-            &pk.z_powers_of_tau_g1[..h_coefficients.len()],
-        )
-        .unwrap();
-
-        // [ƍ^{-1} * (β*l(τ) + α*r(τ) + o(τ))]_1
-        let k_tau_assigned_prover_g1 = msm(
-            &w[qap.num_of_public_inputs..],
-            &pk.prover_k_tau_g1[..qap.num_of_private_inputs()],
-        )
-        .unwrap();
-
         // [π_2]_1
-        let pi2_g1 = msm(&w, &pk.r_tau_g1)
-            .unwrap()
+        let pi2_g1 = pi2_g1_msm
             .operate_with(&pk.beta_g1)
             .operate_with(&pk.delta_g1.operate_with_self(s.representative()));
 
@@ -127,6 +198,20 @@ impl Prover {
     }
 }
 
+/// Multi-scalar multiplication over the witness. Uses the parallel pippenger
+/// implementation when the `parallel` feature is enabled.
+fn msm<const NUM_LIMBS: usize, G>(cs: &[UnsignedInteger<NUM_LIMBS>], points: &[G]) -> G
+where
+    G: IsGroup + Send + Sync,
+{
+    #[cfg(feature = "parallel")]
+    let result = pippenger::parallel_msm(cs, points);
+    #[cfg(not(feature = "parallel"))]
+    let result = pippenger::msm(cs, points);
+
+    result.unwrap()
+}
+
 #[cfg(test)]
 mod tests {
     use lambdaworks_math::elliptic_curve::traits::IsEllipticCurve;
@@ -135,12 +220,12 @@ mod tests {
 
     #[test]
     fn serde() {
-        let proof = Proof {
+        let proof: Proof = Proof {
             pi1: Curve::generator().operate_with_self(sample_fr_elem().representative()),
             pi2: TwistedCurve::generator().operate_with_self(sample_fr_elem().representative()),
             pi3: Curve::generator().operate_with_self(sample_fr_elem().representative()),
         };
-        let deserialized_proof = Proof::deserialize(&proof.serialize()).unwrap();
+        let deserialized_proof: Proof = Proof::deserialize(&proof.serialize()).unwrap();
 
         assert_eq!(proof.pi1, deserialized_proof.pi1);
         assert_eq!(proof.pi2, deserialized_proof.pi2);
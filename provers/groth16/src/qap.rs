@@ -1,29 +1,55 @@
-use lambdaworks_math::polynomial::Polynomial;
+use lambdaworks_math::{
+    elliptic_curve::short_weierstrass::curves::bls12_381::pairing::BLS12381AtePairing,
+    field::{element::FieldElement, traits::IsFFTField},
+    polynomial::Polynomial,
+};
 
 use crate::{common::*, r1cs::R1CS};
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
 
-#[derive(Debug)]
-pub struct QuadraticArithmeticProgram {
+pub struct QuadraticArithmeticProgram<C: IsGroth16Curve = BLS12381AtePairing> {
     pub num_of_public_inputs: usize,
     pub num_of_gates: usize,
-    pub l: Vec<Polynomial<FrElement>>,
-    pub r: Vec<Polynomial<FrElement>>,
-    pub o: Vec<Polynomial<FrElement>>,
+    pub l: Vec<Polynomial<FrElementOf<C>>>,
+    pub r: Vec<Polynomial<FrElementOf<C>>>,
+    pub o: Vec<Polynomial<FrElementOf<C>>>,
 }
 
-impl QuadraticArithmeticProgram {
-    pub fn calculate_h_coefficients(&self, w: &[FrElement]) -> Vec<FrElement> {
-        let offset = &ORDER_R_MINUS_1_ROOT_UNITY;
+impl<C: IsGroth16Curve> core::fmt::Debug for QuadraticArithmeticProgram<C> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("QuadraticArithmeticProgram")
+            .field("num_of_public_inputs", &self.num_of_public_inputs)
+            .field("num_of_gates", &self.num_of_gates)
+            .field("l", &self.l)
+            .field("r", &self.r)
+            .field("o", &self.o)
+            .finish()
+    }
+}
+
+impl<C: IsGroth16Curve> QuadraticArithmeticProgram<C> {
+    pub fn calculate_h_coefficients(&self, w: &[FrElementOf<C>]) -> Vec<FrElementOf<C>> {
+        let offset = &C::FR_GENERATOR;
         let degree = self.num_of_gates * 2;
 
         let [l, r, o] = self.scale_and_accumulate_variable_polynomials(w, degree, offset);
 
         // TODO: Change to a vector of offsetted evaluations of x^N-1
-        let t_poly =
-            Polynomial::new_monomial(FrElement::one(), self.num_of_gates) - FrElement::one();
+        let t_poly = Polynomial::new_monomial(FieldElement::one(), self.num_of_gates)
+            - FieldElement::<C::FrField>::one();
         let mut t = Polynomial::evaluate_offset_fft(&t_poly, 1, Some(degree), offset).unwrap();
-        FrElement::inplace_batch_inverse(&mut t).unwrap();
+        FieldElement::inplace_batch_inverse(&mut t).unwrap();
 
+        #[cfg(feature = "parallel")]
+        let h_evaluated = l
+            .par_iter()
+            .zip(&r)
+            .zip(&o)
+            .zip(&t)
+            .map(|(((l, r), o), t)| (l * r - o) * t)
+            .collect::<Vec<_>>();
+        #[cfg(not(feature = "parallel"))]
         let h_evaluated = l
             .iter()
             .zip(&r)
@@ -41,42 +67,42 @@ impl QuadraticArithmeticProgram {
     // Compute A.s by summing up polynomials A[0].s, A[1].s, ..., A[n].s
     // In other words, assign the witness coefficients / execution values
     // Similarly for B.s and C.s
+    // The three accumulations and their FFTs are independent, so they run concurrently.
     fn scale_and_accumulate_variable_polynomials(
         &self,
-        w: &[FrElement],
+        w: &[FrElementOf<C>],
         degree: usize,
-        offset: &FrElement,
-    ) -> [Vec<FrElement>; 3] {
-        [&self.l, &self.r, &self.o].map(|var_polynomials| {
+        offset: &FrElementOf<C>,
+    ) -> [Vec<FrElementOf<C>>; 3] {
+        let evaluate = |var_polynomials: &[Polynomial<FrElementOf<C>>]| {
             Polynomial::evaluate_offset_fft(
-                &(var_polynomials
-                    .iter()
-                    .zip(w)
-                    .map(|(poly, coeff)| {
-                        poly.mul_with_ref(&Polynomial::new_monomial(coeff.clone(), 0))
-                    })
-                    .reduce(|poly1, poly2| poly1 + poly2)
-                    .unwrap()),
+                &scale_and_accumulate(var_polynomials, w),
                 1,
                 Some(degree),
                 offset,
             )
             .unwrap()
-        })
+        };
+
+        let (l, (r, o)) = join(
+            || evaluate(&self.l),
+            || join(|| evaluate(&self.r), || evaluate(&self.o)),
+        );
+        [l, r, o]
     }
 
     pub fn num_of_private_inputs(&self) -> usize {
         self.l.len() - self.num_of_public_inputs
     }
 
-    pub fn from_r1cs(r1cs: R1CS) -> QuadraticArithmeticProgram {
+    pub fn from_r1cs(r1cs: R1CS<C::FrField>) -> Self {
         let num_gates = r1cs.number_of_constraints();
         let next_power_of_two = num_gates.next_power_of_two();
         let pad_zeroes = next_power_of_two - num_gates;
 
-        let mut l: Vec<Polynomial<FrElement>> = vec![];
-        let mut r: Vec<Polynomial<FrElement>> = vec![];
-        let mut o: Vec<Polynomial<FrElement>> = vec![];
+        let mut l = vec![];
+        let mut r = vec![];
+        let mut o = vec![];
         for i in 0..r1cs.witness_size() {
             let [l_poly, r_poly, o_poly] =
                 get_variable_lro_polynomials_from_r1cs(&r1cs, i, pad_zeroes);
@@ -85,7 +111,7 @@ impl QuadraticArithmeticProgram {
             o.push(o_poly);
         }
 
-        QuadraticArithmeticProgram {
+        Self {
             l,
             r,
             o,
@@ -96,10 +122,10 @@ impl QuadraticArithmeticProgram {
 
     pub fn from_variable_matrices(
         num_of_public_inputs: usize,
-        l: &[Vec<FrElement>],
-        r: &[Vec<FrElement>],
-        o: &[Vec<FrElement>],
-    ) -> QuadraticArithmeticProgram {
+        l: &[Vec<FrElementOf<C>>],
+        r: &[Vec<FrElementOf<C>>],
+        o: &[Vec<FrElementOf<C>>],
+    ) -> Self {
         let num_of_vars = l.len();
         assert!(num_of_vars > 0);
         assert_eq!(num_of_vars, r.len());
@@ -110,7 +136,7 @@ impl QuadraticArithmeticProgram {
         let next_power_of_two = num_of_gates.next_power_of_two();
         let pad_zeroes = next_power_of_two - num_of_gates;
 
-        QuadraticArithmeticProgram {
+        Self {
             num_of_public_inputs,
             num_of_gates: next_power_of_two,
             l: build_variable_polynomials(&apply_padding(l, pad_zeroes)),
@@ -121,15 +147,36 @@ impl QuadraticArithmeticProgram {
 }
 
 #[inline]
-fn get_variable_lro_polynomials_from_r1cs(
-    r1cs: &R1CS,
+fn scale_and_accumulate<F: IsFFTField>(
+    var_polynomials: &[Polynomial<FieldElement<F>>],
+    w: &[FieldElement<F>],
+) -> Polynomial<FieldElement<F>> {
+    #[cfg(feature = "parallel")]
+    let accumulated = var_polynomials
+        .par_iter()
+        .zip(w)
+        .map(|(poly, coeff)| poly.scale_coeffs(coeff))
+        .reduce_with(|poly1, poly2| poly1 + poly2);
+    #[cfg(not(feature = "parallel"))]
+    let accumulated = var_polynomials
+        .iter()
+        .zip(w)
+        .map(|(poly, coeff)| poly.scale_coeffs(coeff))
+        .reduce(|poly1, poly2| poly1 + poly2);
+
+    accumulated.unwrap()
+}
+
+#[inline]
+fn get_variable_lro_polynomials_from_r1cs<F: IsFFTField>(
+    r1cs: &R1CS<F>,
     var_idx: usize,
     pad_zeroes: usize,
-) -> [Polynomial<FrElement>; 3] {
+) -> [Polynomial<FieldElement<F>>; 3] {
     let cap = r1cs.number_of_constraints() + pad_zeroes;
-    let mut current_var_l = vec![FrElement::zero(); cap];
-    let mut current_var_r = vec![FrElement::zero(); cap];
-    let mut current_var_o = vec![FrElement::zero(); cap];
+    let mut current_var_l = vec![FieldElement::zero(); cap];
+    let mut current_var_r = vec![FieldElement::zero(); cap];
+    let mut current_var_o = vec![FieldElement::zero(); cap];
 
     for (i, c) in r1cs.constraints.iter().enumerate() {
         current_var_l[i] = c.a[var_idx].clone();
@@ -138,24 +185,29 @@ fn get_variable_lro_polynomials_from_r1cs(
     }
 
     [current_var_l, current_var_r, current_var_o]
-        .map(|e| Polynomial::interpolate_fft::<FrField>(&e).unwrap())
+        .map(|e| Polynomial::interpolate_fft::<F>(&e).unwrap())
 }
 
 #[inline]
-fn build_variable_polynomials(from_matrix: &[Vec<FrElement>]) -> Vec<Polynomial<FrElement>> {
+fn build_variable_polynomials<F: IsFFTField>(
+    from_matrix: &[Vec<FieldElement<F>>],
+) -> Vec<Polynomial<FieldElement<F>>> {
     from_matrix
         .iter()
-        .map(|row| Polynomial::interpolate_fft::<FrField>(row).unwrap())
+        .map(|row| Polynomial::interpolate_fft::<F>(row).unwrap())
         .collect()
 }
 
 #[inline]
-fn apply_padding(columns: &[Vec<FrElement>], pad_zeroes: usize) -> Vec<Vec<FrElement>> {
+fn apply_padding<F: IsFFTField>(
+    columns: &[Vec<FieldElement<F>>],
+    pad_zeroes: usize,
+) -> Vec<Vec<FieldElement<F>>> {
     columns
         .iter()
         .map(|column| {
             let mut new_column = column.clone();
-            new_column.extend(vec![FrElement::zero(); pad_zeroes]);
+            new_column.extend(vec![FieldElement::zero(); pad_zeroes]);
             new_column
         })
         .collect()
@@ -1,31 +1,31 @@
-use crate::common::FrElement;
+use crate::common::FrField;
 use lambdaworks_math::field::{element::FieldElement, traits::IsField};
 
 // To be improved with a front-end implementation
 // TODO: Use CS in Groth16 tests instead of a plain QAP
 #[derive(Clone, Debug, PartialEq, Eq)]
-pub struct ConstraintSystem<F: IsField> {
-    pub constraints: R1CS,
+pub struct ConstraintSystem<F: IsField = FrField> {
+    pub constraints: R1CS<F>,
     pub witness: Vec<FieldElement<F>>,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
-pub struct Constraint {
-    pub a: Vec<FrElement>,
-    pub b: Vec<FrElement>,
-    pub c: Vec<FrElement>,
+pub struct Constraint<F: IsField = FrField> {
+    pub a: Vec<FieldElement<F>>,
+    pub b: Vec<FieldElement<F>>,
+    pub c: Vec<FieldElement<F>>,
 }
 #[derive(Clone, Debug, PartialEq, Eq)]
-pub struct R1CS {
-    pub constraints: Vec<Constraint>,
+pub struct R1CS<F: IsField = FrField> {
+    pub constraints: Vec<Constraint<F>>,
     pub number_of_inputs: usize,
 }
 
-impl R1CS {
+impl<F: IsField> R1CS<F> {
     pub fn from_matrices(
-        a: Vec<Vec<FrElement>>,
-        b: Vec<Vec<FrElement>>,
-        c: Vec<Vec<FrElement>>,
+        a: Vec<Vec<FieldElement<F>>>,
+        b: Vec<Vec<FieldElement<F>>>,
+        c: Vec<Vec<FieldElement<F>>>,
         number_of_inputs: usize,
     ) -> Self {
         Self {
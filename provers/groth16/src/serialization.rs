@@ -0,0 +1,153 @@
+use std::io::{Read, Write};
+
+use lambdaworks_crypto::{
+    errors::SetupFileError,
+    setup_file::{
+        field_element_from_bytes_le, field_element_size, point_size, SetupFileReader,
+        SetupFileWriter, GROTH16_PROVING_KEY_KIND, GROTH16_VERIFYING_KEY_KIND,
+    },
+};
+use lambdaworks_math::{field::element::FieldElement, traits::ByteConversion};
+
+use crate::{common::*, ProvingKey, VerifyingKey};
+
+const G1_POINTS_SECTION: u32 = 1;
+const G2_POINTS_SECTION: u32 = 2;
+const L_TAU_G1_SECTION: u32 = 3;
+const R_TAU_G1_SECTION: u32 = 4;
+const R_TAU_G2_SECTION: u32 = 5;
+const PROVER_K_TAU_G1_SECTION: u32 = 6;
+const Z_POWERS_OF_TAU_G1_SECTION: u32 = 7;
+
+const PAIRING_OUTPUT_SECTION: u32 = 1;
+const VERIFIER_K_TAU_G1_SECTION: u32 = 3;
+
+impl<C: IsGroth16Curve> ProvingKey<C> {
+    /// Writes the key as a [`lambdaworks_crypto::setup_file`], one point at a time.
+    pub fn write_to<W: Write>(&self, writer: W) -> Result<(), SetupFileError> {
+        let mut file = SetupFileWriter::new(writer, GROTH16_PROVING_KEY_KIND, 7)?;
+        file.write_section(
+            G1_POINTS_SECTION,
+            [&self.alpha_g1, &self.beta_g1, &self.delta_g1],
+        )?;
+        file.write_section(G2_POINTS_SECTION, [&self.beta_g2, &self.delta_g2])?;
+        file.write_section(L_TAU_G1_SECTION, &self.l_tau_g1)?;
+        file.write_section(R_TAU_G1_SECTION, &self.r_tau_g1)?;
+        file.write_section(R_TAU_G2_SECTION, &self.r_tau_g2)?;
+        file.write_section(PROVER_K_TAU_G1_SECTION, &self.prover_k_tau_g1)?;
+        file.write_section(Z_POWERS_OF_TAU_G1_SECTION, &self.z_powers_of_tau_g1)?;
+        file.finish()?;
+        Ok(())
+    }
+
+    /// Reads a key written by [`ProvingKey::write_to`]. Pass a buffered reader, since
+    /// points are read one at a time.
+    pub fn read_from<R: Read>(reader: R) -> Result<Self, SetupFileError> {
+        let mut file = SetupFileReader::new(reader, GROTH16_PROVING_KEY_KIND)?;
+        let g1_size = point_size::<G1PointOf<C>>();
+        let g2_size = point_size::<G2PointOf<C>>();
+        let mut section = file.section(G1_POINTS_SECTION, g1_size)?;
+        let (alpha_g1, beta_g1, delta_g1) = (section.read()?, section.read()?, section.read()?);
+        let mut section = file.section(G2_POINTS_SECTION, g2_size)?;
+        let (beta_g2, delta_g2) = (section.read()?, section.read()?);
+
+        Ok(Self {
+            alpha_g1,
+            beta_g1,
+            beta_g2,
+            delta_g1,
+            delta_g2,
+            l_tau_g1: file.section(L_TAU_G1_SECTION, g1_size)?.read_all()?,
+            r_tau_g1: file.section(R_TAU_G1_SECTION, g1_size)?.read_all()?,
+            r_tau_g2: file.section(R_TAU_G2_SECTION, g2_size)?.read_all()?,
+            prover_k_tau_g1: file.section(PROVER_K_TAU_G1_SECTION, g1_size)?.read_all()?,
+            z_powers_of_tau_g1: file
+                .section(Z_POWERS_OF_TAU_G1_SECTION, g1_size)?
+                .read_all()?,
+        })
+    }
+}
+
+impl<C: IsGroth16Curve> VerifyingKey<C>
+where
+    FieldElement<C::Fp2>: ByteConversion,
+{
+    /// Writes the key as a [`lambdaworks_crypto::setup_file`].
+    pub fn write_to<W: Write>(&self, writer: W) -> Result<(), SetupFileError> {
+        let mut file = SetupFileWriter::new(writer, GROTH16_VERIFYING_KEY_KIND, 3)?;
+        // The pairing output is stored as its six Fp2 coefficients
+        let coefficients = C::output_coefficients(&self.alpha_g1_times_beta_g2);
+        file.write_section_with(
+            PAIRING_OUTPUT_SECTION,
+            &coefficients,
+            ByteConversion::to_bytes_le,
+        )?;
+        file.write_section(G2_POINTS_SECTION, [&self.delta_g2, &self.gamma_g2])?;
+        file.write_section(VERIFIER_K_TAU_G1_SECTION, &self.verifier_k_tau_g1)?;
+        file.finish()?;
+        Ok(())
+    }
+
+    /// Reads a key written by [`VerifyingKey::write_to`].
+    pub fn read_from<R: Read>(reader: R) -> Result<Self, SetupFileError> {
+        let mut file = SetupFileReader::new(reader, GROTH16_VERIFYING_KEY_KIND)?;
+        let mut section = file.section(PAIRING_OUTPUT_SECTION, field_element_size::<C::Fp2>())?;
+        let mut read_fp2 = || section.read_with(field_element_from_bytes_le);
+        let alpha_g1_times_beta_g2 = C::output_from_coefficients([
+            read_fp2()?,
+            read_fp2()?,
+            read_fp2()?,
+            read_fp2()?,
+            read_fp2()?,
+            read_fp2()?,
+        ]);
+
+        let mut section = file.section(G2_POINTS_SECTION, point_size::<G2PointOf<C>>())?;
+        let (delta_g2, gamma_g2) = (section.read()?, section.read()?);
+
+        Ok(Self {
+            alpha_g1_times_beta_g2,
+            delta_g2,
+            gamma_g2,
+            verifier_k_tau_g1: file
+                .section(VERIFIER_K_TAU_G1_SECTION, point_size::<G1PointOf<C>>())?
+                .read_all()?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        frontend::ConstraintSystemBuilder, setup, verify, Prover, QuadraticArithmeticProgram,
+    };
+
+    #[test]
+    fn keys_can_be_written_and_read() {
+        // x * x = y, with y public
+        let mut builder = ConstraintSystemBuilder::new();
+        let y = builder.new_public_input(FrElement::from(9));
+        let x = builder.new_witness(FrElement::from(3));
+        builder.enforce(x, x, y);
+        let cs = builder.build();
+        let qap: QuadraticArithmeticProgram = QuadraticArithmeticProgram::from_r1cs(cs.constraints);
+        let (pk, vk) = setup(&qap);
+
+        let mut pk_bytes = Vec::new();
+        pk.write_to(&mut pk_bytes).unwrap();
+        let mut vk_bytes = Vec::new();
+        vk.write_to(&mut vk_bytes).unwrap();
+        let pk = ProvingKey::read_from(pk_bytes.as_slice()).unwrap();
+        let vk = VerifyingKey::read_from(vk_bytes.as_slice()).unwrap();
+
+        let w = cs.witness;
+        let proof = Prover::prove(&w, &qap, &pk);
+        assert!(verify(&vk, &proof, &w[..qap.num_of_public_inputs]));
+
+        assert!(matches!(
+            ProvingKey::<Pairing>::read_from(vk_bytes.as_slice()),
+            Err(SetupFileError::UnexpectedKind(GROTH16_VERIFYING_KEY_KIND))
+        ));
+    }
+}
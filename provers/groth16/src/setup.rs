@@ -2,73 +2,86 @@ use crate::{common::*, QuadraticArithmeticProgram};
 use lambdaworks_math::{
     cyclic_group::IsGroup,
     elliptic_curve::{
-        short_weierstrass::{point::ShortWeierstrassProjectivePoint, traits::IsShortWeierstrass},
-        traits::{IsEllipticCurve, IsPairing},
+        short_weierstrass::curves::bls12_381::pairing::BLS12381AtePairing, traits::IsEllipticCurve,
     },
+    environment::{IsRandomSource, OsRandomSource},
+    field::{element::FieldElement, traits::IsPrimeField},
 };
 
-pub struct VerifyingKey {
+pub struct VerifyingKey<C: IsGroth16Curve = BLS12381AtePairing> {
     // e([alpha]_1, [beta]_2) computed during setup as it's a constant
-    pub alpha_g1_times_beta_g2: PairingOutput,
-    pub delta_g2: G2Point,
-    pub gamma_g2: G2Point,
+    pub alpha_g1_times_beta_g2: PairingOutputOf<C>,
+    pub delta_g2: G2PointOf<C>,
+    pub gamma_g2: G2PointOf<C>,
     // [K_0(τ)]_1, [K_1(τ)]_1, ..., [K_k(τ)]_1
     // where K_i(τ) = γ^{-1} * (β*l(τ) + α*r(τ) + o(τ))
     // and "k" is the number of public inputs
-    pub verifier_k_tau_g1: Vec<G1Point>,
+    pub verifier_k_tau_g1: Vec<G1PointOf<C>>,
 }
 
-pub struct ProvingKey {
-    pub alpha_g1: G1Point,
-    pub beta_g1: G1Point,
-    pub beta_g2: G2Point,
-    pub delta_g1: G1Point,
-    pub delta_g2: G2Point,
+pub struct ProvingKey<C: IsGroth16Curve = BLS12381AtePairing> {
+    pub alpha_g1: G1PointOf<C>,
+    pub beta_g1: G1PointOf<C>,
+    pub beta_g2: G2PointOf<C>,
+    pub delta_g1: G1PointOf<C>,
+    pub delta_g2: G2PointOf<C>,
     // [A_0(τ)]_1, [A_1(τ)]_1, ..., [A_n(τ)]_1
-    pub l_tau_g1: Vec<G1Point>,
+    pub l_tau_g1: Vec<G1PointOf<C>>,
     // [B_0(τ)]_1, [B_1(τ)]_1, ..., [B_n(τ)]_1
-    pub r_tau_g1: Vec<G1Point>,
+    pub r_tau_g1: Vec<G1PointOf<C>>,
     // [B_0(τ)]_2, [B_1(τ)]_2, ..., [B_n(τ)]_2
-    pub r_tau_g2: Vec<G2Point>,
+    pub r_tau_g2: Vec<G2PointOf<C>>,
     // [K_{k+1}(τ)]_1, [K_{k+2}(τ)]_1, ..., [K_n(τ)]_1
     // where K_i(τ) = ƍ^{-1} * (β*l(τ) + α*r(τ) + o(τ))
     // and "k" is the number of public inputs
-    pub prover_k_tau_g1: Vec<G1Point>,
+    pub prover_k_tau_g1: Vec<G1PointOf<C>>,
     // [delta^{-1} * t(τ) * tau^0]_1, [delta^{-1} * t(τ) * τ^1]_1, ..., [delta^{-1} * t(τ) * τ^m]_1
-    pub z_powers_of_tau_g1: Vec<G1Point>,
+    pub z_powers_of_tau_g1: Vec<G1PointOf<C>>,
 }
 
-struct ToxicWaste {
-    tau: FrElement,
-    alpha: FrElement,
-    beta: FrElement,
-    gamma: FrElement,
-    delta: FrElement,
+struct ToxicWaste<C: IsGroth16Curve> {
+    tau: FrElementOf<C>,
+    alpha: FrElementOf<C>,
+    beta: FrElementOf<C>,
+    gamma: FrElementOf<C>,
+    delta: FrElementOf<C>,
 }
 
-impl ToxicWaste {
-    pub fn new() -> Self {
+impl<C: IsGroth16Curve> ToxicWaste<C> {
+    pub fn new(source: &impl IsRandomSource) -> Self {
         Self {
-            tau: sample_fr_elem(),
-            alpha: sample_fr_elem(),
-            beta: sample_fr_elem(),
-            gamma: sample_fr_elem(),
-            delta: sample_fr_elem(),
+            tau: sample_fr_elem_with(source),
+            alpha: sample_fr_elem_with(source),
+            beta: sample_fr_elem_with(source),
+            gamma: sample_fr_elem_with(source),
+            delta: sample_fr_elem_with(source),
         }
     }
 }
 
-pub fn setup(qap: &QuadraticArithmeticProgram) -> (ProvingKey, VerifyingKey) {
-    let g1: G1Point = Curve::generator();
-    let g2: G2Point = TwistedCurve::generator();
+/// Generates the keys of `qap`, sampling the toxic waste from the randomness of the operating
+/// system.
+pub fn setup<C: IsGroth16Curve>(
+    qap: &QuadraticArithmeticProgram<C>,
+) -> (ProvingKey<C>, VerifyingKey<C>) {
+    setup_with_random_source(qap, &OsRandomSource)
+}
+
+/// Generates the keys of `qap`, sampling the toxic waste from `source`.
+pub fn setup_with_random_source<C: IsGroth16Curve>(
+    qap: &QuadraticArithmeticProgram<C>,
+    source: &impl IsRandomSource,
+) -> (ProvingKey<C>, VerifyingKey<C>) {
+    let g1 = C::G1Curve::generator();
+    let g2 = C::G2Curve::generator();
 
-    let tw = ToxicWaste::new();
+    let tw = ToxicWaste::<C>::new(source);
 
     let l_tau: Vec<_> = qap.l.iter().map(|p| p.evaluate(&tw.tau)).collect();
     let r_tau: Vec<_> = qap.r.iter().map(|p| p.evaluate(&tw.tau)).collect();
 
     let mut to_be_inversed = [tw.delta.clone(), tw.gamma.clone()];
-    FrElement::inplace_batch_inverse(&mut to_be_inversed).unwrap();
+    FieldElement::inplace_batch_inverse(&mut to_be_inversed).unwrap();
     let [delta_inv, gamma_inv] = to_be_inversed;
 
     let k_tau: Vec<_> = l_tau
@@ -88,7 +101,7 @@ pub fn setup(qap: &QuadraticArithmeticProgram) -> (ProvingKey, VerifyingKey) {
     let alpha_g1 = g1.operate_with_self(tw.alpha.representative());
     let beta_g2 = g2.operate_with_self(tw.beta.representative());
 
-    let alpha_g1_times_beta_g2 = Pairing::compute(&alpha_g1, &beta_g2).unwrap();
+    let alpha_g1_times_beta_g2 = C::compute(&alpha_g1, &beta_g2).unwrap();
 
     let delta_g2 = g2.operate_with_self(tw.delta.representative());
 
@@ -107,7 +120,7 @@ pub fn setup(qap: &QuadraticArithmeticProgram) -> (ProvingKey, VerifyingKey) {
                 &core::iter::successors(
                     // Start from delta^{-1} * t(τ)
                     // Note that t(τ) = (τ^N - 1) because our domain is roots of unity
-                    Some(&delta_inv * (&tw.tau.pow(qap.num_of_gates) - FrElement::one())),
+                    Some(&delta_inv * (&tw.tau.pow(qap.num_of_gates) - FieldElement::one())),
                     |prev| Some(prev * &tw.tau),
                 )
                 .take(qap.num_of_gates * 2)
@@ -124,10 +137,7 @@ pub fn setup(qap: &QuadraticArithmeticProgram) -> (ProvingKey, VerifyingKey) {
     )
 }
 
-fn batch_operate<E: IsShortWeierstrass>(
-    elems: &[FrElement],
-    point: &ShortWeierstrassProjectivePoint<E>,
-) -> Vec<ShortWeierstrassProjectivePoint<E>> {
+fn batch_operate<F: IsPrimeField, G: IsGroup>(elems: &[FieldElement<F>], point: &G) -> Vec<G> {
     elems
         .iter()
         .map(|elem| point.operate_with_self(elem.representative()))
@@ -25,6 +25,26 @@ fn vitalik() {
     }
 }
 
+#[test]
+fn proofs_are_reproducible_with_a_seeded_random_source() {
+    use lambdaworks_groth16::setup_with_random_source;
+    use lambdaworks_math::environment::SeededRandomSource;
+
+    let qap = test_circuits::vitalik_qap();
+    let (pk, vk) = setup_with_random_source(&qap, &SeededRandomSource::new(1));
+    let w = ["0x1", "0x3", "0x23", "0x9", "0x1b", "0x1e"]
+        .map(FrElement::from_hex_unchecked)
+        .to_vec();
+
+    let proof = Prover::prove_with_random_source(&w, &qap, &pk, &SeededRandomSource::new(2));
+    let same_proof = Prover::prove_with_random_source(&w, &qap, &pk, &SeededRandomSource::new(2));
+    assert_eq!(proof.serialize(), same_proof.serialize());
+    assert!(verify(&vk, &proof, &w[..qap.num_of_public_inputs]));
+
+    let other_proof = Prover::prove_with_random_source(&w, &qap, &pk, &SeededRandomSource::new(3));
+    assert_ne!(proof.serialize(), other_proof.serialize());
+}
+
 #[test]
 fn example() {
     let qap = test_qap_2();
@@ -41,3 +61,67 @@ fn example() {
     let accept = verify(&vk, &deserialized_proof, &w[..qap.num_of_public_inputs]);
     assert!(accept);
 }
+
+#[test]
+fn constraint_system_builder() {
+    use lambdaworks_groth16::{frontend::*, QuadraticArithmeticProgram};
+
+    // x^3 + x + 5 = out
+    let mut builder = ConstraintSystemBuilder::new();
+    let out = builder.new_public_input(FrElement::from(35));
+    let x = builder.new_witness(FrElement::from(3));
+    let x_squared = builder.mul(x, x);
+    let x_cubed = builder.mul(x_squared, x);
+    builder.enforce(
+        LinearCombination::from(x_cubed) + x + (FrElement::from(5), Variable::One),
+        Variable::One,
+        out,
+    );
+    assert!(builder.is_satisfied());
+
+    let cs = builder.build();
+    let qap: QuadraticArithmeticProgram = QuadraticArithmeticProgram::from_r1cs(cs.constraints);
+    let (pk, vk) = setup(&qap);
+
+    let proof = Prover::prove(&cs.witness, &qap, &pk);
+    assert!(verify(&vk, &proof, &cs.witness[..qap.num_of_public_inputs]));
+}
+
+#[test]
+fn bn254() {
+    use lambdaworks_groth16::{frontend::*, ProvingKey, QuadraticArithmeticProgram, VerifyingKey};
+    use lambdaworks_math::elliptic_curve::short_weierstrass::curves::bn_254::{
+        default_types::FrElement as BN254FrElement, pairing::BN254AtePairing,
+    };
+
+    // x^3 + x + 5 = out
+    let mut builder = ConstraintSystemBuilder::new();
+    let out = builder.new_public_input(BN254FrElement::from(35));
+    let x = builder.new_witness(BN254FrElement::from(3));
+    let x_squared = builder.mul(x, x);
+    let x_cubed = builder.mul(x_squared, x);
+    builder.enforce(
+        LinearCombination::from(x_cubed) + x + (BN254FrElement::from(5), Variable::One),
+        Variable::One,
+        out,
+    );
+
+    let cs = builder.build();
+    let qap = QuadraticArithmeticProgram::<BN254AtePairing>::from_r1cs(cs.constraints);
+    let (pk, vk) = setup(&qap);
+
+    let mut pk_bytes = Vec::new();
+    pk.write_to(&mut pk_bytes).unwrap();
+    let mut vk_bytes = Vec::new();
+    vk.write_to(&mut vk_bytes).unwrap();
+    let pk = ProvingKey::<BN254AtePairing>::read_from(pk_bytes.as_slice()).unwrap();
+    let vk = VerifyingKey::<BN254AtePairing>::read_from(vk_bytes.as_slice()).unwrap();
+
+    let w = cs.witness;
+    let serialized_proof = Prover::prove(&w, &qap, &pk).serialize();
+    let proof = Proof::deserialize(&serialized_proof).unwrap();
+    assert!(verify(&vk, &proof, &w[..qap.num_of_public_inputs]));
+
+    let wrong_output = [BN254FrElement::one(), BN254FrElement::from(36)];
+    assert!(!verify(&vk, &proof, &wrong_output));
+}
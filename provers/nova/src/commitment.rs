@@ -0,0 +1,43 @@
+use core::marker::PhantomData;
+
+use lambdaworks_math::{
+    cyclic_group::IsGroup,
+    field::{element::FieldElement, traits::IsPrimeField},
+    msm::pippenger::msm,
+    unsigned_integer::element::UnsignedInteger,
+};
+
+/// Generators for Pedersen commitments to vectors, `commit(v) = Σ vᵢ Gᵢ`. The commitment is
+/// additively homomorphic, which is what folding relies on.
+#[derive(Clone, Debug)]
+pub struct PedersenKey<F: IsPrimeField, G> {
+    generators: Vec<G>,
+    phantom: PhantomData<F>,
+}
+
+impl<const N: usize, F, G> PedersenKey<F, G>
+where
+    F: IsPrimeField<RepresentativeType = UnsignedInteger<N>>,
+    G: IsGroup,
+{
+    /// The generators must have no known discrete logarithm relation, for instance obtained with
+    /// `lambdaworks_crypto::commitments::ipa::generators_from_transcript`.
+    pub fn new(generators: Vec<G>) -> Self {
+        Self {
+            generators,
+            phantom: PhantomData,
+        }
+    }
+
+    pub fn commit(&self, v: &[FieldElement<F>]) -> G {
+        if v.is_empty() {
+            return G::neutral_element();
+        }
+        let scalars: Vec<_> = v.iter().map(|s| s.representative()).collect();
+        msm(&scalars, &self.generators[..v.len()]).expect("`points` is sliced by `cs`'s length")
+    }
+
+    pub fn max_len(&self) -> usize {
+        self.generators.len()
+    }
+}
@@ -0,0 +1,128 @@
+//! The [Nova](https://eprint.iacr.org/2021/370) folding scheme for relaxed R1CS.
+//!
+//! Two instances are folded with a random `r` into `u = u₁ + r u₂`, `x = x₁ + r x₂`,
+//! `W = W₁ + r W₂` and `E = E₁ + r T + r² E₂`, where the cross term
+//! `T = Az₁ ∘ Bz₂ + Az₂ ∘ Bz₁ - u₁ Cz₂ - u₂ Cz₁` absorbs the mixed products. The prover only
+//! sends a commitment to `T`, and the verifier folds the instances with group operations.
+
+use lambdaworks_crypto::fiat_shamir::is_transcript::IsTranscript;
+use lambdaworks_math::{
+    cyclic_group::IsGroup,
+    field::{element::FieldElement, traits::IsPrimeField},
+    traits::AsBytes,
+    unsigned_integer::element::UnsignedInteger,
+};
+use lambdaworks_spartan::r1cs::R1CS;
+
+use crate::{
+    commitment::PedersenKey,
+    relaxed::{matrix_products, RelaxedR1CSInstance, RelaxedR1CSWitness},
+};
+
+/// Folds the second instance into the first one. Returns the folded instance and witness, and
+/// the commitment to the cross term that the verifier needs.
+pub fn fold<const N: usize, F, G, T>(
+    key: &PedersenKey<F, G>,
+    r1cs: &R1CS<F>,
+    (instance_1, witness_1): (&RelaxedR1CSInstance<F, G>, &RelaxedR1CSWitness<F>),
+    (instance_2, witness_2): (&RelaxedR1CSInstance<F, G>, &RelaxedR1CSWitness<F>),
+    transcript: &mut T,
+) -> (RelaxedR1CSInstance<F, G>, RelaxedR1CSWitness<F>, G)
+where
+    F: IsPrimeField<RepresentativeType = UnsignedInteger<N>>,
+    G: IsGroup + AsBytes,
+    T: IsTranscript<F>,
+{
+    let [az_1, bz_1, cz_1] = matrix_products(r1cs, &instance_1.u, &instance_1.x, &witness_1.w);
+    let [az_2, bz_2, cz_2] = matrix_products(r1cs, &instance_2.u, &instance_2.x, &witness_2.w);
+    let cross_term: Vec<_> = (0..r1cs.num_constraints)
+        .map(|i| {
+            &az_1[i] * &bz_2[i] + &az_2[i] * &bz_1[i]
+                - &instance_1.u * &cz_2[i]
+                - &instance_2.u * &cz_1[i]
+        })
+        .collect();
+    let commitment_t = key.commit(&cross_term);
+
+    let r = challenge(instance_1, instance_2, &commitment_t, transcript);
+    let instance = fold_with_challenge(instance_1, instance_2, &commitment_t, &r);
+    let r_squared = &r * &r;
+    let witness = RelaxedR1CSWitness {
+        e: (0..r1cs.num_constraints)
+            .map(|i| &witness_1.e[i] + &r * &cross_term[i] + &r_squared * &witness_2.e[i])
+            .collect(),
+        w: fold_vectors(&witness_1.w, &witness_2.w, &r),
+    };
+    (instance, witness, commitment_t)
+}
+
+/// The verifier side of [`fold`]: folds the instances given the commitment to the cross term.
+pub fn fold_instances<const N: usize, F, G, T>(
+    instance_1: &RelaxedR1CSInstance<F, G>,
+    instance_2: &RelaxedR1CSInstance<F, G>,
+    commitment_t: &G,
+    transcript: &mut T,
+) -> RelaxedR1CSInstance<F, G>
+where
+    F: IsPrimeField<RepresentativeType = UnsignedInteger<N>>,
+    G: IsGroup + AsBytes,
+    T: IsTranscript<F>,
+{
+    let r = challenge(instance_1, instance_2, commitment_t, transcript);
+    fold_with_challenge(instance_1, instance_2, commitment_t, &r)
+}
+
+fn challenge<const N: usize, F, G, T>(
+    instance_1: &RelaxedR1CSInstance<F, G>,
+    instance_2: &RelaxedR1CSInstance<F, G>,
+    commitment_t: &G,
+    transcript: &mut T,
+) -> FieldElement<F>
+where
+    F: IsPrimeField<RepresentativeType = UnsignedInteger<N>>,
+    G: IsGroup + AsBytes,
+    T: IsTranscript<F>,
+{
+    instance_1.append_to_transcript(transcript);
+    instance_2.append_to_transcript(transcript);
+    transcript.append_bytes(&commitment_t.as_bytes());
+    transcript.sample_field_element()
+}
+
+fn fold_with_challenge<const N: usize, F, G>(
+    instance_1: &RelaxedR1CSInstance<F, G>,
+    instance_2: &RelaxedR1CSInstance<F, G>,
+    commitment_t: &G,
+    r: &FieldElement<F>,
+) -> RelaxedR1CSInstance<F, G>
+where
+    F: IsPrimeField<RepresentativeType = UnsignedInteger<N>>,
+    G: IsGroup,
+{
+    let r_squared = r * r;
+    RelaxedR1CSInstance {
+        commitment_e: instance_1
+            .commitment_e
+            .operate_with(&commitment_t.operate_with_self(r.representative()))
+            .operate_with(
+                &instance_2
+                    .commitment_e
+                    .operate_with_self(r_squared.representative()),
+            ),
+        u: &instance_1.u + r * &instance_2.u,
+        commitment_w: instance_1.commitment_w.operate_with(
+            &instance_2
+                .commitment_w
+                .operate_with_self(r.representative()),
+        ),
+        x: fold_vectors(&instance_1.x, &instance_2.x, r),
+    }
+}
+
+fn fold_vectors<F: IsPrimeField>(
+    v_1: &[FieldElement<F>],
+    v_2: &[FieldElement<F>],
+    r: &FieldElement<F>,
+) -> Vec<FieldElement<F>> {
+    v_1.iter().zip(v_2).map(|(a, b)| a + r * b).collect()
+}
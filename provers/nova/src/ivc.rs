@@ -0,0 +1,263 @@
+//! Incrementally verifiable computation of `z_{i+1} = F(z_i)` by folding every step into a
+//! running relaxed instance, so that only the running instance has to be checked at the end.
+//!
+//! There is no augmented circuit yet: the verifier folds the step instances itself instead of
+//! checking a proof of the folding inside the next step, so its work grows with the number of
+//! steps, but it checks a single relaxed R1CS instead of one R1CS per step.
+
+use lambdaworks_crypto::fiat_shamir::is_transcript::IsTranscript;
+use lambdaworks_math::{
+    cyclic_group::IsGroup,
+    field::{element::FieldElement, traits::IsPrimeField},
+    traits::AsBytes,
+    unsigned_integer::element::UnsignedInteger,
+};
+use lambdaworks_spartan::r1cs::R1CS;
+
+use crate::{
+    commitment::PedersenKey,
+    folding::{fold, fold_instances},
+    relaxed::{is_satisfied, RelaxedR1CSInstance, RelaxedR1CSWitness},
+};
+
+/// A step `z_{i+1} = F(z_i)` of an incremental computation, arithmetized as an R1CS whose
+/// public inputs are `(z_i, z_{i+1})`.
+pub trait StepCircuit<F: IsPrimeField> {
+    /// Number of elements of the state `z_i`.
+    fn arity(&self) -> usize;
+
+    fn r1cs(&self) -> &R1CS<F>;
+
+    /// Returns the next state and the witness of the step.
+    fn synthesize(&self, z: &[FieldElement<F>]) -> (Vec<FieldElement<F>>, Vec<FieldElement<F>>);
+}
+
+#[derive(Clone, Debug)]
+pub struct IvcProof<F: IsPrimeField, G> {
+    /// The instance of each step, with `u = 1` and `E = 0`.
+    pub step_instances: Vec<RelaxedR1CSInstance<F, G>>,
+    /// Commitments to the cross terms of folding each step after the first one.
+    pub cross_term_commitments: Vec<G>,
+    pub running_instance: RelaxedR1CSInstance<F, G>,
+    pub running_witness: RelaxedR1CSWitness<F>,
+}
+
+/// Runs `num_steps` steps of `circuit` from `z_0`. Returns the final state and the proof.
+pub fn prove<const N: usize, F, G, C, T>(
+    key: &PedersenKey<F, G>,
+    circuit: &C,
+    z_0: &[FieldElement<F>],
+    num_steps: usize,
+    transcript: &mut T,
+) -> (Vec<FieldElement<F>>, IvcProof<F, G>)
+where
+    F: IsPrimeField<RepresentativeType = UnsignedInteger<N>>,
+    G: IsGroup + AsBytes,
+    C: StepCircuit<F>,
+    T: IsTranscript<F>,
+{
+    assert!(num_steps > 0);
+    let r1cs = circuit.r1cs();
+    let mut z = z_0.to_vec();
+    let mut step_instances = Vec::with_capacity(num_steps);
+    let mut cross_term_commitments = Vec::with_capacity(num_steps - 1);
+    let mut running: Option<(RelaxedR1CSInstance<F, G>, RelaxedR1CSWitness<F>)> = None;
+
+    for _ in 0..num_steps {
+        let (z_next, w) = circuit.synthesize(&z);
+        let x: Vec<_> = z.iter().chain(&z_next).cloned().collect();
+        let (instance, witness) = RelaxedR1CSInstance::from_r1cs(key, r1cs, &x, &w);
+        running = Some(match running {
+            None => (instance.clone(), witness),
+            Some((running_instance, running_witness)) => {
+                let (folded_instance, folded_witness, commitment_t) = fold(
+                    key,
+                    r1cs,
+                    (&running_instance, &running_witness),
+                    (&instance, &witness),
+                    transcript,
+                );
+                cross_term_commitments.push(commitment_t);
+                (folded_instance, folded_witness)
+            }
+        });
+        step_instances.push(instance);
+        z = z_next;
+    }
+
+    let (running_instance, running_witness) = running.unwrap();
+    let proof = IvcProof {
+        step_instances,
+        cross_term_commitments,
+        running_instance,
+        running_witness,
+    };
+    (z, proof)
+}
+
+/// Checks that `z_n` is reached from `z_0` by the steps of `proof`.
+pub fn verify<const N: usize, F, G, C, T>(
+    key: &PedersenKey<F, G>,
+    circuit: &C,
+    z_0: &[FieldElement<F>],
+    z_n: &[FieldElement<F>],
+    proof: &IvcProof<F, G>,
+    transcript: &mut T,
+) -> bool
+where
+    F: IsPrimeField<RepresentativeType = UnsignedInteger<N>>,
+    G: IsGroup + AsBytes,
+    C: StepCircuit<F>,
+    T: IsTranscript<F>,
+{
+    let arity = circuit.arity();
+    let instances = &proof.step_instances;
+    if instances.is_empty() || proof.cross_term_commitments.len() != instances.len() - 1 {
+        return false;
+    }
+    // Every step is a plain R1CS instance, and its input state is the output of the previous one
+    let mut z = z_0;
+    for instance in instances {
+        if instance.u != FieldElement::one()
+            || instance.commitment_e != G::neutral_element()
+            || instance.x.len() != 2 * arity
+            || &instance.x[..arity] != z
+        {
+            return false;
+        }
+        z = &instance.x[arity..];
+    }
+    if z != z_n {
+        return false;
+    }
+
+    let mut running = instances[0].clone();
+    for (instance, commitment_t) in instances[1..].iter().zip(&proof.cross_term_commitments) {
+        running = fold_instances(&running, instance, commitment_t, transcript);
+    }
+    running == proof.running_instance
+        && is_satisfied(key, circuit.r1cs(), &running, &proof.running_witness)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use lambdaworks_crypto::{
+        commitments::ipa::generators_from_transcript,
+        fiat_shamir::default_transcript::DefaultTranscript,
+    };
+    use lambdaworks_math::{
+        elliptic_curve::short_weierstrass::{
+            curves::pallas::curve::PallasCurve, point::ShortWeierstrassProjectivePoint,
+        },
+        field::fields::vesta_field::Vesta255PrimeField,
+    };
+    use lambdaworks_spartan::r1cs::SparseMatrix;
+
+    type F = Vesta255PrimeField;
+    type FE = FieldElement<F>;
+    type Key = PedersenKey<F, ShortWeierstrassProjectivePoint<PallasCurve>>;
+
+    /// The step `x ↦ x³ + x + 5`, with the witness `(x², x³)`.
+    struct CubicStep {
+        r1cs: R1CS<F>,
+    }
+
+    impl CubicStep {
+        fn new() -> Self {
+            // Columns: u, x_in, x_out, x², x³
+            let one = FE::one;
+            let a = SparseMatrix::new(vec![
+                (0, 1, one()),
+                (1, 3, one()),
+                (2, 0, FE::from(5)),
+                (2, 1, one()),
+                (2, 4, one()),
+            ]);
+            let b = SparseMatrix::new(vec![(0, 1, one()), (1, 1, one()), (2, 0, one())]);
+            let c = SparseMatrix::new(vec![(0, 3, one()), (1, 4, one()), (2, 2, one())]);
+            Self {
+                r1cs: R1CS::new(a, b, c, 3, 2, 2).unwrap(),
+            }
+        }
+    }
+
+    impl StepCircuit<F> for CubicStep {
+        fn arity(&self) -> usize {
+            1
+        }
+
+        fn r1cs(&self) -> &R1CS<F> {
+            &self.r1cs
+        }
+
+        fn synthesize(&self, z: &[FE]) -> (Vec<FE>, Vec<FE>) {
+            let x = &z[0];
+            let x2 = x * x;
+            let x3 = &x2 * x;
+            (vec![&x3 + x + FE::from(5)], vec![x2, x3])
+        }
+    }
+
+    fn test_key() -> Key {
+        let mut transcript = DefaultTranscript::new(b"nova test generators");
+        PedersenKey::new(generators_from_transcript::<PallasCurve, _>(
+            &mut transcript,
+            3,
+        ))
+    }
+
+    #[test]
+    fn folded_steps_are_verified() {
+        let key = test_key();
+        let circuit = CubicStep::new();
+        let z_0 = [FE::from(2)];
+        let (z_n, proof) = prove(&key, &circuit, &z_0, 4, &mut DefaultTranscript::default());
+
+        let mut expected = FE::from(2);
+        for _ in 0..4 {
+            expected = &expected * &expected * &expected + &expected + FE::from(5);
+        }
+        assert_eq!(z_n, vec![expected]);
+        assert!(is_satisfied(
+            &key,
+            &circuit.r1cs,
+            &proof.running_instance,
+            &proof.running_witness
+        ));
+        assert!(verify(
+            &key,
+            &circuit,
+            &z_0,
+            &z_n,
+            &proof,
+            &mut DefaultTranscript::default()
+        ));
+        assert!(!verify(
+            &key,
+            &circuit,
+            &z_0,
+            &[FE::from(7)],
+            &proof,
+            &mut DefaultTranscript::default()
+        ));
+    }
+
+    #[test]
+    fn wrong_step_breaks_the_running_instance() {
+        let key = test_key();
+        let circuit = CubicStep::new();
+        let z_0 = [FE::from(2)];
+        let (_, mut proof) = prove(&key, &circuit, &z_0, 3, &mut DefaultTranscript::default());
+        proof.running_witness.w[0] += FE::one();
+        let z_n = proof.step_instances[2].x[1..].to_vec();
+        assert!(!verify(
+            &key,
+            &circuit,
+            &z_0,
+            &z_n,
+            &proof,
+            &mut DefaultTranscript::default()
+        ));
+    }
+}
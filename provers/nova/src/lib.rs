@@ -0,0 +1,4 @@
+pub mod commitment;
+pub mod folding;
+pub mod ivc;
+pub mod relaxed;
@@ -0,0 +1,112 @@
+//! Relaxed R1CS: `(A z) ∘ (B z) = u (C z) + E` with `z = (u, x, W)`. A plain R1CS instance is
+//! the relaxed instance with `u = 1` and `E = 0`, and two relaxed instances can be folded into
+//! one.
+
+use lambdaworks_crypto::fiat_shamir::is_transcript::IsTranscript;
+use lambdaworks_math::{
+    cyclic_group::IsGroup,
+    field::{element::FieldElement, traits::IsPrimeField},
+    traits::AsBytes,
+    unsigned_integer::element::UnsignedInteger,
+};
+use lambdaworks_spartan::r1cs::R1CS;
+
+use crate::commitment::PedersenKey;
+
+#[derive(Clone, Debug)]
+pub struct RelaxedR1CSInstance<F: IsPrimeField, G> {
+    pub commitment_e: G,
+    pub u: FieldElement<F>,
+    pub commitment_w: G,
+    pub x: Vec<FieldElement<F>>,
+}
+
+impl<F: IsPrimeField, G: PartialEq> PartialEq for RelaxedR1CSInstance<F, G> {
+    fn eq(&self, other: &Self) -> bool {
+        self.commitment_e == other.commitment_e
+            && self.u == other.u
+            && self.commitment_w == other.commitment_w
+            && self.x == other.x
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RelaxedR1CSWitness<F: IsPrimeField> {
+    pub e: Vec<FieldElement<F>>,
+    pub w: Vec<FieldElement<F>>,
+}
+
+impl<const N: usize, F, G> RelaxedR1CSInstance<F, G>
+where
+    F: IsPrimeField<RepresentativeType = UnsignedInteger<N>>,
+    G: IsGroup + AsBytes,
+{
+    /// Returns the relaxed instance and witness of a satisfying assignment of `r1cs`.
+    pub fn from_r1cs(
+        key: &PedersenKey<F, G>,
+        r1cs: &R1CS<F>,
+        x: &[FieldElement<F>],
+        w: &[FieldElement<F>],
+    ) -> (Self, RelaxedR1CSWitness<F>) {
+        let instance = Self {
+            commitment_e: G::neutral_element(),
+            u: FieldElement::one(),
+            commitment_w: key.commit(w),
+            x: x.to_vec(),
+        };
+        let witness = RelaxedR1CSWitness {
+            e: vec![FieldElement::zero(); r1cs.num_constraints],
+            w: w.to_vec(),
+        };
+        (instance, witness)
+    }
+
+    pub(crate) fn append_to_transcript<T: IsTranscript<F>>(&self, transcript: &mut T) {
+        transcript.append_bytes(&self.commitment_e.as_bytes());
+        transcript.append_field_element(&self.u);
+        transcript.append_bytes(&self.commitment_w.as_bytes());
+        for x in &self.x {
+            transcript.append_field_element(x);
+        }
+    }
+}
+
+/// Returns `(A z, B z, C z)` for `z = (u, x, w)`.
+pub(crate) fn matrix_products<F: IsPrimeField>(
+    r1cs: &R1CS<F>,
+    u: &FieldElement<F>,
+    x: &[FieldElement<F>],
+    w: &[FieldElement<F>],
+) -> [Vec<FieldElement<F>>; 3] {
+    let z = |column: usize| match column {
+        0 => u.clone(),
+        c if c <= x.len() => x[c - 1].clone(),
+        c => w[c - 1 - x.len()].clone(),
+    };
+    [&r1cs.a, &r1cs.b, &r1cs.c].map(|m| m.mul_vector(r1cs.num_constraints, z))
+}
+
+/// Checks that `witness` satisfies `instance`, including the commitments.
+pub fn is_satisfied<const N: usize, F, G>(
+    key: &PedersenKey<F, G>,
+    r1cs: &R1CS<F>,
+    instance: &RelaxedR1CSInstance<F, G>,
+    witness: &RelaxedR1CSWitness<F>,
+) -> bool
+where
+    F: IsPrimeField<RepresentativeType = UnsignedInteger<N>>,
+    G: IsGroup + AsBytes,
+{
+    if instance.x.len() != r1cs.num_public_inputs
+        || witness.w.len() != r1cs.num_witnesses
+        || witness.e.len() != r1cs.num_constraints
+    {
+        return false;
+    }
+    let [az, bz, cz] = matrix_products(r1cs, &instance.u, &instance.x, &witness.w);
+    let constraints_hold =
+        (0..r1cs.num_constraints).all(|i| &az[i] * &bz[i] == &instance.u * &cz[i] + &witness.e[i]);
+    constraints_hold
+        && key.commit(&witness.w) == instance.commitment_w
+        && key.commit(&witness.e) == instance.commitment_e
+}
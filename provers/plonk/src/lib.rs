@@ -1,5 +1,7 @@
 pub mod constraint_system;
 pub mod prover;
+mod serialization;
 pub mod setup;
 pub mod test_utils;
+pub mod ultra;
 pub mod verifier;
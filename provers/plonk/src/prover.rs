@@ -4,11 +4,17 @@ use lambdaworks_math::field::traits::IsFFTField;
 use lambdaworks_math::traits::{AsBytes, Deserializable, IsRandomFieldElementGenerator};
 use std::marker::PhantomData;
 use std::mem::size_of;
+use std::slice;
 
 use crate::setup::{
     new_strong_fiat_shamir_transcript, CommonPreprocessedInput, VerificationKey, Witness,
 };
+use lambdaworks_crypto::commitments::fflonk::{Fflonk, OpeningSet};
+use lambdaworks_crypto::commitments::kzg::KateZaveruchaGoldberg;
 use lambdaworks_crypto::commitments::traits::IsCommitmentScheme;
+use lambdaworks_math::elliptic_curve::traits::IsPairing;
+use lambdaworks_math::field::traits::IsPrimeField;
+use lambdaworks_math::unsigned_integer::element::UnsignedInteger;
 use lambdaworks_math::{
     field::element::FieldElement,
     polynomial::{self, Polynomial},
@@ -72,9 +78,11 @@ pub struct Proof<F: IsField, CS: IsCommitmentScheme<F>> {
     pub p_non_constant_zeta: FieldElement<F>,
     ///  Value of `t(ζ)`.
     pub t_zeta: FieldElement<F>,
-    /// Batch opening proof for all the evaluations at ζ
+    /// Batch opening proof for all the evaluations at ζ.
+    /// With fflonk openings, the commitment `W` of the shplonk opening.
     pub w_zeta_1: CS::Commitment,
     /// Single opening proof for `z(ζω)`.
+    /// With fflonk openings, the commitment `W'` of the shplonk opening.
     pub w_zeta_omega_1: CS::Commitment,
 }
 
@@ -277,6 +285,13 @@ struct Round5Result<F: IsField, Hiding> {
     t_zeta: FieldElement<F>,
 }
 
+type Rounds1To4Result<F, Hiding> = (
+    Round1Result<F, Hiding>,
+    Round2Result<F, Hiding>,
+    Round3Result<F, Hiding>,
+    Round4Result<F>,
+);
+
 impl<F, CS, R> Prover<F, CS, R>
 where
     F: IsField + IsFFTField,
@@ -558,15 +573,16 @@ where
         }
     }
 
-    fn round_5(
+    /// Returns the polynomials opened at `ζ` in round 5: the partial evaluation of `t`,
+    /// `p_non_constant`, `a`, `b`, `c`, `S_σ1` and `S_σ2`.
+    fn round_5_polynomials(
         &self,
         common_preprocessed_input: &CommonPreprocessedInput<F>,
         round_1: &Round1Result<F, CS::Commitment>,
         round_2: &Round2Result<F, CS::Commitment>,
         round_3: &Round3Result<F, CS::Commitment>,
         round_4: &Round4Result<F>,
-        upsilon: FieldElement<F>,
-    ) -> Round5Result<F, CS::Commitment> {
+    ) -> Vec<Polynomial<FieldElement<F>>> {
         let cpi = common_preprocessed_input;
         let (r1, r2, r3, r4) = (round_1, round_2, round_3, round_4);
         // Precompute variables
@@ -601,7 +617,7 @@ where
         let partial_t = &r3.p_t_lo + zeta_raised_n * &r3.p_t_mid + zeta_raised_2n * &r3.p_t_hi;
 
         // TODO: Refactor to remove clones.
-        let polynomials = vec![
+        vec![
             partial_t,
             p_non_constant,
             r1.p_a.clone(),
@@ -609,7 +625,21 @@ where
             r1.p_c.clone(),
             cpi.s1.clone(),
             cpi.s2.clone(),
-        ];
+        ]
+    }
+
+    fn round_5(
+        &self,
+        common_preprocessed_input: &CommonPreprocessedInput<F>,
+        round_1: &Round1Result<F, CS::Commitment>,
+        round_2: &Round2Result<F, CS::Commitment>,
+        round_3: &Round3Result<F, CS::Commitment>,
+        round_4: &Round4Result<F>,
+        upsilon: FieldElement<F>,
+    ) -> Round5Result<F, CS::Commitment> {
+        let cpi = common_preprocessed_input;
+        let polynomials = self.round_5_polynomials(cpi, round_1, round_2, round_3, round_4);
+        let (r2, r4) = (round_2, round_4);
         let ys: Vec<FieldElement<F>> = polynomials.iter().map(|p| p.evaluate(&r4.zeta)).collect();
         let w_zeta_1 = self
             .commitment_scheme
@@ -627,15 +657,14 @@ where
         }
     }
 
-    pub fn prove(
+    /// Runs rounds 1 to 4, adding their commitments and evaluations to `transcript`.
+    fn rounds_1_to_4<T: IsTranscript<F>>(
         &self,
         witness: &Witness<F>,
         public_input: &[FieldElement<F>],
         common_preprocessed_input: &CommonPreprocessedInput<F>,
-        vk: &VerificationKey<CS::Commitment>,
-    ) -> Proof<F, CS> {
-        let mut transcript = new_strong_fiat_shamir_transcript::<F, CS>(vk, public_input);
-
+        transcript: &mut T,
+    ) -> Rounds1To4Result<F, CS::Commitment> {
         // Round 1
         let round_1 = self.round_1(witness, common_preprocessed_input);
         transcript.append_bytes(&round_1.a_1.as_bytes());
@@ -674,6 +703,24 @@ where
         transcript.append_field_element(&round_4.s2_zeta);
         transcript.append_field_element(&round_4.z_zeta_omega);
 
+        (round_1, round_2, round_3, round_4)
+    }
+
+    pub fn prove(
+        &self,
+        witness: &Witness<F>,
+        public_input: &[FieldElement<F>],
+        common_preprocessed_input: &CommonPreprocessedInput<F>,
+        vk: &VerificationKey<CS::Commitment>,
+    ) -> Proof<F, CS> {
+        let mut transcript = new_strong_fiat_shamir_transcript::<F, CS>(vk, public_input);
+        let (round_1, round_2, round_3, round_4) = self.rounds_1_to_4(
+            witness,
+            public_input,
+            common_preprocessed_input,
+            &mut transcript,
+        );
+
         // Round 5
         let upsilon = transcript.sample_field_element();
         let round_5 = self.round_5(
@@ -685,6 +732,16 @@ where
             upsilon,
         );
 
+        Self::assemble_proof(round_1, round_2, round_3, round_4, round_5)
+    }
+
+    fn assemble_proof(
+        round_1: Round1Result<F, CS::Commitment>,
+        round_2: Round2Result<F, CS::Commitment>,
+        round_3: Round3Result<F, CS::Commitment>,
+        round_4: Round4Result<F>,
+        round_5: Round5Result<F, CS::Commitment>,
+    ) -> Proof<F, CS> {
         Proof {
             a_1: round_1.a_1,
             b_1: round_1.b_1,
@@ -707,6 +764,58 @@ where
     }
 }
 
+impl<const N: usize, F, P, R> Prover<F, KateZaveruchaGoldberg<F, P>, R>
+where
+    F: IsFFTField + IsPrimeField<RepresentativeType = UnsignedInteger<N>>,
+    P: IsPairing,
+    P::G1Point: AsBytes,
+    FieldElement<F>: ByteConversion,
+    R: IsRandomFieldElementGenerator<F>,
+{
+    /// Same as [`Prover::prove`], but round 5 opens the seven polynomials at `ζ` and `z` at
+    /// `ζω` with a single shplonk opening (see [`Fflonk`]) instead of two KZG openings.
+    /// The proof has the same shape and is checked with
+    /// [`Verifier::verify_with_fflonk_openings`](crate::verifier::Verifier::verify_with_fflonk_openings),
+    /// which needs two pairings instead of four.
+    pub fn prove_with_fflonk_openings(
+        &self,
+        witness: &Witness<F>,
+        public_input: &[FieldElement<F>],
+        common_preprocessed_input: &CommonPreprocessedInput<F>,
+        vk: &VerificationKey<P::G1Point>,
+    ) -> Proof<F, KateZaveruchaGoldberg<F, P>> {
+        let cpi = common_preprocessed_input;
+        let mut transcript =
+            new_strong_fiat_shamir_transcript::<F, KateZaveruchaGoldberg<F, P>>(vk, public_input);
+        let (round_1, round_2, round_3, round_4) =
+            self.rounds_1_to_4(witness, public_input, cpi, &mut transcript);
+
+        // Round 5
+        let polynomials = self.round_5_polynomials(cpi, &round_1, &round_2, &round_3, &round_4);
+        let mut sets: Vec<_> = polynomials
+            .iter()
+            .map(|polynomial| OpeningSet {
+                polynomials: slice::from_ref(polynomial),
+                x: round_4.zeta.clone(),
+            })
+            .collect();
+        sets.push(OpeningSet {
+            polynomials: slice::from_ref(&round_2.p_z),
+            x: &round_4.zeta * &cpi.omega,
+        });
+        let (evaluations, opening) =
+            Fflonk::from(&self.commitment_scheme).open(&sets, &mut transcript);
+        let round_5 = Round5Result {
+            w_zeta_1: opening.w,
+            w_zeta_omega_1: opening.w_prime,
+            p_non_constant_zeta: evaluations[1][0].clone(),
+            t_zeta: evaluations[0][0].clone(),
+        };
+
+        Self::assemble_proof(round_1, round_2, round_3, round_4, round_5)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use lambdaworks_math::{
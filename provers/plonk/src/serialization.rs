@@ -0,0 +1,363 @@
+use std::io::{Read, Write};
+
+use lambdaworks_crypto::{
+    commitments::traits::IsCommitmentScheme,
+    errors::SetupFileError,
+    setup_file::{
+        field_element_from_bytes_le, field_element_size, point_size, SetupFileReader,
+        SetupFileWriter, PLONK_COMMON_PREPROCESSED_INPUT_KIND, PLONK_PROOF_KIND,
+        PLONK_VERIFICATION_KEY_KIND,
+    },
+};
+use lambdaworks_math::{
+    cyclic_group::IsGroup,
+    errors::DeserializationError,
+    field::{
+        element::FieldElement,
+        traits::{IsFFTField, IsField},
+    },
+    polynomial::Polynomial,
+    traits::{AsBytes, ByteConversion, Deserializable},
+};
+
+use crate::{
+    prover::Proof,
+    setup::{CommonPreprocessedInput, VerificationKey},
+    test_utils::utils::generate_domain,
+};
+
+const COMMITMENTS_SECTION: u32 = 1;
+
+const K1_SECTION: u32 = 1;
+/// Coefficients of `q_L, q_R, q_O, q_M, q_C, S_σ1, S_σ2, S_σ3` take the following sections.
+const POLYNOMIALS_FIRST_SECTION: u32 = 2;
+/// Evaluations of `S_σ1, S_σ2, S_σ3` over the domain take the following sections.
+const PERMUTATION_LAGRANGE_FIRST_SECTION: u32 = 10;
+
+/// Evaluations at ζ, in the order of the fields of [`Proof`].
+const PROOF_EVALUATIONS_SECTION: u32 = 1;
+/// Commitments, in the order of the rounds that compute them.
+const PROOF_COMMITMENTS_SECTION: u32 = 2;
+
+impl<G1Point> VerificationKey<G1Point> {
+    /// Writes the key as a [`lambdaworks_crypto::setup_file`].
+    pub fn write_to<W: Write>(&self, writer: W) -> Result<(), SetupFileError>
+    where
+        G1Point: AsBytes,
+    {
+        let mut file = SetupFileWriter::new(writer, PLONK_VERIFICATION_KEY_KIND, 1)?;
+        file.write_section(
+            COMMITMENTS_SECTION,
+            [
+                &self.qm_1, &self.ql_1, &self.qr_1, &self.qo_1, &self.qc_1, &self.s1_1, &self.s2_1,
+                &self.s3_1,
+            ],
+        )?;
+        file.finish()?;
+        Ok(())
+    }
+
+    /// Reads a key written by [`VerificationKey::write_to`].
+    pub fn read_from<R: Read>(reader: R) -> Result<Self, SetupFileError>
+    where
+        G1Point: IsGroup + AsBytes + Deserializable,
+    {
+        let mut file = SetupFileReader::new(reader, PLONK_VERIFICATION_KEY_KIND)?;
+        let mut section = file.section(COMMITMENTS_SECTION, point_size::<G1Point>())?;
+        Ok(Self {
+            qm_1: section.read()?,
+            ql_1: section.read()?,
+            qr_1: section.read()?,
+            qo_1: section.read()?,
+            qc_1: section.read()?,
+            s1_1: section.read()?,
+            s2_1: section.read()?,
+            s3_1: section.read()?,
+        })
+    }
+}
+
+impl<F> CommonPreprocessedInput<F>
+where
+    F: IsFFTField,
+    FieldElement<F>: ByteConversion,
+{
+    /// Writes the input as a [`lambdaworks_crypto::setup_file`]. The domain is not written,
+    /// since it is determined by the number of rows.
+    pub fn write_to<W: Write>(&self, writer: W) -> Result<(), SetupFileError> {
+        let mut file = SetupFileWriter::new(writer, PLONK_COMMON_PREPROCESSED_INPUT_KIND, 12)?;
+        let encode = FieldElement::<F>::to_bytes_le;
+        file.write_section_with(K1_SECTION, [&self.k1], encode)?;
+        for (id, polynomial) in (POLYNOMIALS_FIRST_SECTION..).zip(self.polynomials()) {
+            file.write_section_with(id, &polynomial.coefficients, encode)?;
+        }
+        let lagrange = [&self.s1_lagrange, &self.s2_lagrange, &self.s3_lagrange];
+        for (id, evaluations) in (PERMUTATION_LAGRANGE_FIRST_SECTION..).zip(lagrange) {
+            file.write_section_with(id, evaluations, encode)?;
+        }
+        file.finish()?;
+        Ok(())
+    }
+
+    /// Reads an input written by [`CommonPreprocessedInput::write_to`]. Pass a buffered
+    /// reader, since coefficients are read one at a time.
+    pub fn read_from<R: Read>(reader: R) -> Result<Self, SetupFileError> {
+        let mut file = SetupFileReader::new(reader, PLONK_COMMON_PREPROCESSED_INPUT_KIND)?;
+        let size = field_element_size::<F>();
+        let k1 = file
+            .section(K1_SECTION, size)?
+            .read_with(field_element_from_bytes_le)?;
+        let mut read = |id: u32| {
+            file.section(id, size)?
+                .read_all_with(field_element_from_bytes_le)
+        };
+        let mut polynomials = Vec::new();
+        for id in POLYNOMIALS_FIRST_SECTION..PERMUTATION_LAGRANGE_FIRST_SECTION {
+            polynomials.push(Polynomial::new(&read(id)?));
+        }
+        let [ql, qr, qo, qm, qc, s1, s2, s3]: [_; 8] = polynomials.try_into().unwrap();
+        let s1_lagrange = read(PERMUTATION_LAGRANGE_FIRST_SECTION)?;
+        let s2_lagrange = read(PERMUTATION_LAGRANGE_FIRST_SECTION + 1)?;
+        let s3_lagrange = read(PERMUTATION_LAGRANGE_FIRST_SECTION + 2)?;
+
+        let n = s1_lagrange.len();
+        if !n.is_power_of_two() || s2_lagrange.len() != n || s3_lagrange.len() != n {
+            return Err(DeserializationError::InvalidValue.into());
+        }
+        let omega = F::get_primitive_root_of_unity(n.trailing_zeros() as u64)
+            .map_err(|_| SetupFileError::Deserialization(DeserializationError::InvalidValue))?;
+        let domain = generate_domain(&omega, n);
+
+        Ok(Self {
+            n,
+            domain,
+            omega,
+            k1,
+            ql,
+            qr,
+            qo,
+            qm,
+            qc,
+            s1,
+            s2,
+            s3,
+            s1_lagrange,
+            s2_lagrange,
+            s3_lagrange,
+        })
+    }
+
+    fn polynomials(&self) -> [&Polynomial<FieldElement<F>>; 8] {
+        [
+            &self.ql, &self.qr, &self.qo, &self.qm, &self.qc, &self.s1, &self.s2, &self.s3,
+        ]
+    }
+}
+
+impl<F, CS> Proof<F, CS>
+where
+    F: IsField,
+    CS: IsCommitmentScheme<F>,
+    FieldElement<F>: ByteConversion,
+{
+    /// Writes the proof as a [`lambdaworks_crypto::setup_file`]. Public inputs are written
+    /// apart, with [`lambdaworks_crypto::setup_file::write_public_inputs`].
+    pub fn write_to<W: Write>(&self, writer: W) -> Result<(), SetupFileError>
+    where
+        CS::Commitment: AsBytes,
+    {
+        let mut file = SetupFileWriter::new(writer, PLONK_PROOF_KIND, 2)?;
+        file.write_section_with(
+            PROOF_EVALUATIONS_SECTION,
+            [
+                &self.a_zeta,
+                &self.b_zeta,
+                &self.c_zeta,
+                &self.s1_zeta,
+                &self.s2_zeta,
+                &self.z_zeta_omega,
+                &self.p_non_constant_zeta,
+                &self.t_zeta,
+            ],
+            FieldElement::<F>::to_bytes_le,
+        )?;
+        file.write_section(
+            PROOF_COMMITMENTS_SECTION,
+            [
+                &self.a_1,
+                &self.b_1,
+                &self.c_1,
+                &self.z_1,
+                &self.t_lo_1,
+                &self.t_mid_1,
+                &self.t_hi_1,
+                &self.w_zeta_1,
+                &self.w_zeta_omega_1,
+            ],
+        )?;
+        file.finish()?;
+        Ok(())
+    }
+
+    /// Reads a proof written by [`Proof::write_to`].
+    pub fn read_from<R: Read>(reader: R) -> Result<Self, SetupFileError>
+    where
+        CS::Commitment: IsGroup + AsBytes + Deserializable,
+    {
+        let mut file = SetupFileReader::new(reader, PLONK_PROOF_KIND)?;
+        let mut section = file.section(PROOF_EVALUATIONS_SECTION, field_element_size::<F>())?;
+        let mut evaluation = || section.read_with(field_element_from_bytes_le);
+        let a_zeta = evaluation()?;
+        let b_zeta = evaluation()?;
+        let c_zeta = evaluation()?;
+        let s1_zeta = evaluation()?;
+        let s2_zeta = evaluation()?;
+        let z_zeta_omega = evaluation()?;
+        let p_non_constant_zeta = evaluation()?;
+        let t_zeta = evaluation()?;
+
+        let mut section =
+            file.section(PROOF_COMMITMENTS_SECTION, point_size::<CS::Commitment>())?;
+        Ok(Self {
+            a_1: section.read()?,
+            b_1: section.read()?,
+            c_1: section.read()?,
+            z_1: section.read()?,
+            t_lo_1: section.read()?,
+            t_mid_1: section.read()?,
+            t_hi_1: section.read()?,
+            w_zeta_1: section.read()?,
+            w_zeta_omega_1: section.read()?,
+            a_zeta,
+            b_zeta,
+            c_zeta,
+            s1_zeta,
+            s2_zeta,
+            z_zeta_omega,
+            p_non_constant_zeta,
+            t_zeta,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        prover::Prover,
+        setup::setup,
+        test_utils::circuit_1::{test_common_preprocessed_input_1, test_witness_1},
+        test_utils::utils::{test_srs, G1Point, TestRandomFieldGenerator, KZG},
+        verifier::Verifier,
+    };
+    use lambdaworks_crypto::setup_file::{read_public_inputs, write_public_inputs};
+    use lambdaworks_math::elliptic_curve::short_weierstrass::curves::bls12_381::default_types::{
+        FrElement, FrField,
+    };
+
+    #[test]
+    fn setup_artifacts_can_be_written_and_read() {
+        let common_preprocessed_input = test_common_preprocessed_input_1();
+        let kzg = KZG::new(test_srs(common_preprocessed_input.n));
+        let verifying_key = setup(&common_preprocessed_input, &kzg);
+
+        let mut bytes = Vec::new();
+        common_preprocessed_input.write_to(&mut bytes).unwrap();
+        let common_preprocessed_input =
+            CommonPreprocessedInput::read_from(bytes.as_slice()).unwrap();
+        let mut bytes = Vec::new();
+        verifying_key.write_to(&mut bytes).unwrap();
+        let verifying_key = VerificationKey::read_from(bytes.as_slice()).unwrap();
+
+        let x = FrElement::from(4);
+        let public_input = vec![x.clone(), FrElement::from(12)];
+        let witness = test_witness_1(x, FrElement::from(3));
+        let prover = Prover::new(kzg.clone(), TestRandomFieldGenerator {});
+        let proof = prover.prove(
+            &witness,
+            &public_input,
+            &common_preprocessed_input,
+            &verifying_key,
+        );
+
+        let verifier = Verifier::new(kzg);
+        assert!(verifier.verify(
+            &proof,
+            &public_input,
+            &common_preprocessed_input,
+            &verifying_key
+        ));
+    }
+
+    #[test]
+    fn proofs_and_public_inputs_can_be_written_and_read() {
+        let common_preprocessed_input = test_common_preprocessed_input_1();
+        let kzg = KZG::new(test_srs(common_preprocessed_input.n));
+        let verifying_key = setup(&common_preprocessed_input, &kzg);
+
+        let x = FrElement::from(4);
+        let public_input = vec![x.clone(), FrElement::from(12)];
+        let witness = test_witness_1(x, FrElement::from(3));
+        let prover = Prover::new(kzg.clone(), TestRandomFieldGenerator {});
+        let proof = prover.prove(
+            &witness,
+            &public_input,
+            &common_preprocessed_input,
+            &verifying_key,
+        );
+
+        let mut bytes = Vec::new();
+        proof.write_to(&mut bytes).unwrap();
+        let proof = Proof::read_from(bytes.as_slice()).unwrap();
+        assert!(matches!(
+            VerificationKey::<G1Point>::read_from(bytes.as_slice()),
+            Err(SetupFileError::UnexpectedKind(PLONK_PROOF_KIND))
+        ));
+        let mut bytes = Vec::new();
+        write_public_inputs(&mut bytes, &public_input).unwrap();
+        let public_input = read_public_inputs::<FrField, _>(bytes.as_slice()).unwrap();
+
+        let verifier = Verifier::new(kzg);
+        assert!(verifier.verify(
+            &proof,
+            &public_input,
+            &common_preprocessed_input,
+            &verifying_key
+        ));
+    }
+
+    #[test]
+    fn proofs_with_crafted_section_headers_are_rejected() {
+        let common_preprocessed_input = test_common_preprocessed_input_1();
+        let kzg = KZG::new(test_srs(common_preprocessed_input.n));
+        let verifying_key = setup(&common_preprocessed_input, &kzg);
+        let x = FrElement::from(4);
+        let public_input = vec![x.clone(), FrElement::from(12)];
+        let witness = test_witness_1(x, FrElement::from(3));
+        let prover = Prover::new(kzg, TestRandomFieldGenerator {});
+        let proof = prover.prove(
+            &witness,
+            &public_input,
+            &common_preprocessed_input,
+            &verifying_key,
+        );
+        let mut bytes = Vec::new();
+        proof.write_to(&mut bytes).unwrap();
+
+        // The evaluations section starts after the 16 bytes of the file header, with its id,
+        // element size and number of elements.
+        let mut too_many_elements = bytes.clone();
+        too_many_elements[24..32].copy_from_slice(&u64::MAX.to_le_bytes());
+        assert!(matches!(
+            Proof::<FrField, KZG>::read_from(too_many_elements.as_slice()),
+            Err(SetupFileError::SectionTooLarge)
+        ));
+
+        let mut huge_elements = bytes;
+        huge_elements[20..24].copy_from_slice(&u32::MAX.to_le_bytes());
+        assert!(matches!(
+            Proof::<FrField, KZG>::read_from(huge_elements.as_slice()),
+            Err(SetupFileError::InvalidElementSize)
+        ));
+    }
+}
@@ -0,0 +1,537 @@
+//! Opt-in "ultra" configuration of the PLONK prover.
+//!
+//! Gates have four wires `w_1, w_2, w_3, w_4` and may read the wires of the next row,
+//! which allows packing several operations of the standard configuration into a single
+//! row. On top of the arithmetic gate there are:
+//! - a lookup gate, enforcing that `(w_1, w_2, w_3)` is an entry of a fixed table. This
+//!   replaces the bit decompositions needed by hash functions with a single row per lookup.
+//!   The argument is LogUp based: `Σ 1 / (δ + f_i) = Σ m_j / (δ + t_j)`.
+//! - an elliptic curve addition gate for curves defined over the scalar field of the proof
+//!   system (e.g. Jubjub or Bandersnatch over BLS12-381).
+//!
+//! The gate identities, over a row `i` and the next one, are:
+//! ```text
+//! arithmetic: q_arith (q_1 w_1 + q_2 w_2 + q_3 w_3 + q_4 w_4 + q_m w_1 w_2 + q_c + q_4_next w_4(ωX)) + PI = 0
+//! lookup:     q_lookup (w_1 + η w_2 + η² w_3 + η³ q_table) ∈ table
+//! ecc:        q_ecc ((x_3 + x_2 + x_1)(x_2 - x_1)² - (y_2 - y_1)²) = 0
+//!             q_ecc ((y_3 + y_1)(x_2 - x_1) - (y_2 - y_1)(x_1 - x_3)) = 0
+//!             q_ecc ((x_2 - x_1) i - 1) = 0
+//! ```
+//! where `(x_1, y_1, x_2, y_2) = (w_1, w_2, w_3, w_4)` and
+//! `(x_3, y_3, i) = (w_1(ωX), w_2(ωX), w_3(ωX))`. The inverse `i` of `x_2 - x_1` forces
+//! `x_1 ≠ x_2`: otherwise the first two identities hold for any `(x_3, y_3)` when `y_1 = y_2`.
+pub mod prover;
+pub mod setup;
+pub mod verifier;
+
+use std::collections::HashMap;
+
+use lambdaworks_math::{
+    field::{element::FieldElement, traits::IsField},
+    traits::ByteConversion,
+};
+
+use crate::constraint_system::Variable;
+
+/// Smallest domain used by the ultra configuration. Below this size the quotient
+/// polynomial does not fit in [`setup::QUOTIENT_CHUNKS`] chunks of size `n`.
+pub const MIN_DOMAIN_SIZE: usize = 16;
+
+/// Selector columns of the ultra configuration. The same structure holds the selector
+/// values of a single gate, their polynomials, their commitments and their evaluations.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Selectors<T> {
+    pub q_1: T,
+    pub q_2: T,
+    pub q_3: T,
+    pub q_4: T,
+    pub q_m: T,
+    pub q_c: T,
+    pub q_4_next: T,
+    pub q_arith: T,
+    pub q_lookup: T,
+    pub q_table: T,
+    pub q_ecc: T,
+}
+
+impl<T> Selectors<T> {
+    pub fn as_vec(&self) -> Vec<&T> {
+        vec![
+            &self.q_1,
+            &self.q_2,
+            &self.q_3,
+            &self.q_4,
+            &self.q_m,
+            &self.q_c,
+            &self.q_4_next,
+            &self.q_arith,
+            &self.q_lookup,
+            &self.q_table,
+            &self.q_ecc,
+        ]
+    }
+
+    /// Inverse of [`Selectors::as_vec`].
+    pub fn from_vec(values: Vec<T>) -> Self {
+        assert_eq!(values.len(), 11);
+        let mut values = values.into_iter();
+        let mut next = || values.next().unwrap();
+        Selectors {
+            q_1: next(),
+            q_2: next(),
+            q_3: next(),
+            q_4: next(),
+            q_m: next(),
+            q_c: next(),
+            q_4_next: next(),
+            q_arith: next(),
+            q_lookup: next(),
+            q_table: next(),
+            q_ecc: next(),
+        }
+    }
+
+    pub fn map<U>(&self, mut f: impl FnMut(&T) -> U) -> Selectors<U> {
+        Selectors {
+            q_1: f(&self.q_1),
+            q_2: f(&self.q_2),
+            q_3: f(&self.q_3),
+            q_4: f(&self.q_4),
+            q_m: f(&self.q_m),
+            q_c: f(&self.q_c),
+            q_4_next: f(&self.q_4_next),
+            q_arith: f(&self.q_arith),
+            q_lookup: f(&self.q_lookup),
+            q_table: f(&self.q_table),
+            q_ecc: f(&self.q_ecc),
+        }
+    }
+}
+
+impl<F: IsField> Selectors<FieldElement<F>> {
+    /// Selectors of a row that enforces nothing.
+    pub fn zero() -> Self {
+        let zero = FieldElement::zero();
+        Self {
+            q_1: zero.clone(),
+            q_2: zero.clone(),
+            q_3: zero.clone(),
+            q_4: zero.clone(),
+            q_m: zero.clone(),
+            q_c: zero.clone(),
+            q_4_next: zero.clone(),
+            q_arith: zero.clone(),
+            q_lookup: zero.clone(),
+            q_table: zero.clone(),
+            q_ecc: zero,
+        }
+    }
+
+    /// Selectors of the arithmetic gate
+    /// `q_1 w_1 + q_2 w_2 + q_3 w_3 + q_4 w_4 + q_m w_1 w_2 + q_c = 0`.
+    pub fn arithmetic(
+        q_w: [FieldElement<F>; 4],
+        q_m: FieldElement<F>,
+        q_c: FieldElement<F>,
+    ) -> Self {
+        let [q_1, q_2, q_3, q_4] = q_w;
+        Self {
+            q_1,
+            q_2,
+            q_3,
+            q_4,
+            q_m,
+            q_c,
+            q_arith: FieldElement::one(),
+            ..Self::zero()
+        }
+    }
+}
+
+/// A row of the ultra circuit.
+#[derive(Clone)]
+pub struct UltraGate<F: IsField> {
+    pub wires: [Variable; 4],
+    pub selectors: Selectors<FieldElement<F>>,
+}
+
+/// A lookup table with entries of three field elements. Tables are identified by
+/// their position, starting at 1, since the table identifier 0 is reserved for padding.
+pub type TableId = usize;
+
+/// A collection of variables, gates and lookup tables for the ultra configuration.
+/// Unlike [`crate::constraint_system::ConstraintSystem`], values are assigned when the
+/// variables are created, since most of the gates are cheaper to evaluate than to solve.
+pub struct UltraConstraintSystem<F: IsField> {
+    values: Vec<FieldElement<F>>,
+    public_input_variables: Vec<Variable>,
+    gates: Vec<UltraGate<F>>,
+    tables: Vec<Vec<[FieldElement<F>; 3]>>,
+}
+
+impl<F: IsField> UltraConstraintSystem<F> {
+    /// Returns a new empty constraint system.
+    pub fn new() -> Self {
+        Self {
+            // The null variable, used as a placeholder in gates.
+            values: vec![FieldElement::zero()],
+            public_input_variables: Vec::new(),
+            gates: Vec::new(),
+            tables: Vec::new(),
+        }
+    }
+
+    /// Returns a null variable, holding zero, to be used as a placeholder in gates.
+    pub fn null_variable(&self) -> Variable {
+        0
+    }
+
+    /// Creates a new variable holding `value`.
+    pub fn new_variable(&mut self, value: FieldElement<F>) -> Variable {
+        self.values.push(value);
+        self.values.len() - 1
+    }
+
+    /// Creates a new public variable holding `value`.
+    pub fn new_public_input(&mut self, value: FieldElement<F>) -> Variable {
+        let variable = self.new_variable(value);
+        self.public_input_variables.push(variable);
+        variable
+    }
+
+    pub fn value(&self, variable: &Variable) -> &FieldElement<F> {
+        &self.values[*variable]
+    }
+
+    /// Returns the values of the public variables, in the order they were created.
+    pub fn public_input_values(&self) -> Vec<FieldElement<F>> {
+        self.public_input_variables
+            .iter()
+            .map(|v| self.values[*v].clone())
+            .collect()
+    }
+
+    /// Adds a gate to the system.
+    pub fn add_gate(&mut self, wires: [Variable; 4], selectors: Selectors<FieldElement<F>>) {
+        self.gates.push(UltraGate { wires, selectors });
+    }
+
+    /// Number of gates, not including the public input header and the padding.
+    pub fn num_gates(&self) -> usize {
+        self.gates.len()
+    }
+
+    /// Creates a new variable `w` constrained to be equal to `c1 * v1 + c2 * v2 + c3 * v3 + b`,
+    /// with a single gate.
+    pub fn linear_combination(
+        &mut self,
+        v: [&Variable; 3],
+        c: [FieldElement<F>; 3],
+        b: FieldElement<F>,
+    ) -> Variable {
+        let value = v
+            .iter()
+            .zip(&c)
+            .fold(b.clone(), |acc, (v, c)| acc + c * self.value(v));
+        let result = self.new_variable(value);
+        let [c1, c2, c3] = c;
+        self.add_gate(
+            [*v[0], *v[1], *v[2], result],
+            Selectors::arithmetic([c1, c2, c3, -FieldElement::one()], FieldElement::zero(), b),
+        );
+        result
+    }
+
+    /// Creates a new variable `w` constrained to be equal to `v1 + v2 + v3`.
+    pub fn add3(&mut self, v1: &Variable, v2: &Variable, v3: &Variable) -> Variable {
+        let one = FieldElement::<F>::one();
+        self.linear_combination(
+            [v1, v2, v3],
+            [one.clone(), one.clone(), one],
+            FieldElement::zero(),
+        )
+    }
+
+    /// Creates a new variable `w` constrained to be equal to `v1 * v2`.
+    pub fn mul(&mut self, v1: &Variable, v2: &Variable) -> Variable {
+        let result = self.new_variable(self.value(v1) * self.value(v2));
+        let zero = FieldElement::<F>::zero();
+        let null = self.null_variable();
+        self.add_gate(
+            [*v1, *v2, null, result],
+            Selectors::arithmetic(
+                [
+                    zero.clone(),
+                    zero.clone(),
+                    zero.clone(),
+                    -FieldElement::one(),
+                ],
+                FieldElement::one(),
+                zero,
+            ),
+        );
+        result
+    }
+
+    /// Creates a new variable `w` constrained to be equal to `Σ a_i * b_i`, with one gate
+    /// per term. The partial sums `acc_i = a_i * b_i + acc_{i+1}` are kept in the fourth wire
+    /// and every gate reads the next partial sum from the following row.
+    pub fn inner_product(&mut self, a: &[Variable], b: &[Variable]) -> Variable {
+        assert_eq!(a.len(), b.len());
+        let null = self.null_variable();
+        let zero = FieldElement::<F>::zero();
+        let one = FieldElement::<F>::one();
+
+        let last = self.new_variable(FieldElement::zero());
+        let mut partial_sums = vec![last];
+        for (a_i, b_i) in a.iter().zip(b).rev() {
+            let value =
+                self.value(a_i) * self.value(b_i) + self.value(partial_sums.last().unwrap());
+            partial_sums.push(self.new_variable(value));
+        }
+        partial_sums.reverse();
+
+        for ((a_i, b_i), acc) in a.iter().zip(b).zip(&partial_sums) {
+            // a_i b_i - acc_i + acc_{i+1} = 0
+            self.add_gate(
+                [*a_i, *b_i, null, *acc],
+                Selectors {
+                    q_4_next: one.clone(),
+                    ..Selectors::arithmetic(
+                        [zero.clone(), zero.clone(), zero.clone(), -one.clone()],
+                        one.clone(),
+                        zero.clone(),
+                    )
+                },
+            );
+        }
+        // The partial sum after the last term is zero.
+        self.add_gate(
+            [null, null, null, last],
+            Selectors::arithmetic(
+                [zero.clone(), zero.clone(), zero.clone(), one],
+                zero.clone(),
+                zero,
+            ),
+        );
+        partial_sums[0]
+    }
+
+    /// Constrains `v1` and `v2` to be equal.
+    pub fn assert_eq(&mut self, v1: &Variable, v2: &Variable) {
+        let zero = FieldElement::<F>::zero();
+        let null = self.null_variable();
+        self.add_gate(
+            [*v1, *v2, null, null],
+            Selectors::arithmetic(
+                [
+                    FieldElement::one(),
+                    -FieldElement::one(),
+                    zero.clone(),
+                    zero.clone(),
+                ],
+                zero.clone(),
+                zero,
+            ),
+        );
+    }
+
+    /// Adds a lookup table and returns its identifier.
+    pub fn add_lookup_table(&mut self, entries: Vec<[FieldElement<F>; 3]>) -> TableId {
+        self.tables.push(entries);
+        self.tables.len()
+    }
+
+    /// Constrains `(v1, v2, v3)` to be an entry of the table.
+    pub fn assert_lookup(&mut self, table: TableId, v: [&Variable; 3]) {
+        assert!(table > 0 && table <= self.tables.len(), "Unknown table");
+        let selectors = Selectors {
+            q_lookup: FieldElement::one(),
+            q_table: FieldElement::from(table as u64),
+            ..Selectors::zero()
+        };
+        self.add_gate([*v[0], *v[1], *v[2], self.null_variable()], selectors);
+    }
+
+    /// Creates a new variable `w` such that `(v1, v2, w)` is an entry of the table.
+    /// Returns `None` if there is no entry starting with `(v1, v2)`.
+    pub fn lookup(&mut self, table: TableId, v1: &Variable, v2: &Variable) -> Option<Variable> {
+        let (a, b) = (self.value(v1), self.value(v2));
+        let value = self
+            .tables
+            .get(table.checked_sub(1)?)?
+            .iter()
+            .find(|entry| &entry[0] == a && &entry[1] == b)?[2]
+            .clone();
+        let result = self.new_variable(value);
+        self.assert_lookup(table, [v1, v2, &result]);
+        Some(result)
+    }
+
+    /// Adds two points of a short Weierstrass curve defined over `F`, with a single gate
+    /// and the row holding the result and the inverse of `x2 - x1`. The points must have
+    /// different x coordinates, so doubling a point isn't supported.
+    /// Since the addition formula does not depend on the curve coefficients, the caller
+    /// is responsible for the inputs being points of the curve.
+    pub fn ecc_add(
+        &mut self,
+        (x1, y1): (&Variable, &Variable),
+        (x2, y2): (&Variable, &Variable),
+    ) -> (Variable, Variable) {
+        let (x1_value, y1_value) = (self.value(x1), self.value(y1));
+        let (x2_value, y2_value) = (self.value(x2), self.value(y2));
+        let dx_inv_value = (x2_value - x1_value)
+            .inv()
+            .expect("points must have different x coordinates");
+        let lambda = (y2_value - y1_value) * &dx_inv_value;
+        let x3_value = &lambda * &lambda - x1_value - x2_value;
+        let y3_value = lambda * (x1_value - &x3_value) - y1_value;
+        let x3 = self.new_variable(x3_value);
+        let y3 = self.new_variable(y3_value);
+        let dx_inv = self.new_variable(dx_inv_value);
+
+        self.add_gate(
+            [*x1, *y1, *x2, *y2],
+            Selectors {
+                q_ecc: FieldElement::one(),
+                ..Selectors::zero()
+            },
+        );
+        let null = self.null_variable();
+        self.add_gate([x3, y3, dx_inv, null], Selectors::zero());
+        (x3, y3)
+    }
+
+    /// The public input header, one row per public input, enforcing `-w_1 + PI = 0`.
+    fn public_input_header(&self) -> Vec<UltraGate<F>> {
+        let zero = FieldElement::<F>::zero();
+        self.public_input_variables
+            .iter()
+            .map(|v| UltraGate {
+                wires: [
+                    *v,
+                    self.null_variable(),
+                    self.null_variable(),
+                    self.null_variable(),
+                ],
+                selectors: Selectors::arithmetic(
+                    [
+                        -FieldElement::one(),
+                        zero.clone(),
+                        zero.clone(),
+                        zero.clone(),
+                    ],
+                    zero.clone(),
+                    zero.clone(),
+                ),
+            })
+            .collect()
+    }
+
+    /// Total number of rows of the tables.
+    fn table_size(&self) -> usize {
+        self.tables.iter().map(Vec::len).sum()
+    }
+
+    /// Size of the domain: the number of rows, padded to a power of two.
+    /// The tables must fit in the domain too.
+    pub fn domain_size(&self) -> usize {
+        let rows = self.public_input_variables.len() + self.gates.len();
+        rows.max(self.table_size())
+            .max(MIN_DOMAIN_SIZE)
+            .next_power_of_two()
+    }
+
+    /// Returns the rows of the circuit. Their layout is:
+    /// #######################
+    /// # public input header #
+    /// #######################
+    /// #    circuit gates    #
+    /// #######################
+    /// #       padding       #
+    /// #######################
+    pub fn rows(&self) -> Vec<UltraGate<F>> {
+        let n = self.domain_size();
+        let mut rows = self.public_input_header();
+        rows.extend_from_slice(&self.gates);
+        let padding = UltraGate {
+            wires: [self.null_variable(); 4],
+            selectors: Selectors::zero(),
+        };
+        rows.resize(n, padding);
+        rows
+    }
+
+    /// Returns the four table columns `(t_1, t_2, t_3, table id)`, padded with zeros.
+    pub fn table_columns(&self) -> [Vec<FieldElement<F>>; 4] {
+        let n = self.domain_size();
+        let mut columns: [Vec<FieldElement<F>>; 4] = Default::default();
+        for (index, table) in self.tables.iter().enumerate() {
+            for entry in table {
+                for (column, value) in columns.iter_mut().zip(entry) {
+                    column.push(value.clone());
+                }
+                columns[3].push(FieldElement::from(index as u64 + 1));
+            }
+        }
+        for column in columns.iter_mut() {
+            column.resize(n, FieldElement::zero());
+        }
+        columns
+    }
+}
+
+impl<F: IsField> Default for UltraConstraintSystem<F> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Wire values and lookup multiplicities of an ultra circuit.
+pub struct UltraWitness<F: IsField> {
+    pub w: [Vec<FieldElement<F>>; 4],
+    /// Number of lookups of every table row.
+    pub m: Vec<FieldElement<F>>,
+}
+
+impl<F: IsField> UltraWitness<F>
+where
+    FieldElement<F>: ByteConversion,
+{
+    pub fn new(system: &UltraConstraintSystem<F>) -> Self {
+        let rows = system.rows();
+        let table = system.table_columns();
+        let n = rows.len();
+
+        let w = [0, 1, 2, 3].map(|j| {
+            rows.iter()
+                .map(|row| system.value(&row.wires[j]).clone())
+                .collect::<Vec<_>>()
+        });
+
+        let key = |entry: [&FieldElement<F>; 4]| -> Vec<u8> {
+            entry.iter().flat_map(|value| value.to_bytes_be()).collect()
+        };
+        let mut table_index = HashMap::new();
+        for i in (0..n).rev() {
+            // Duplicated entries are counted on their first occurrence.
+            table_index.insert(
+                key([&table[0][i], &table[1][i], &table[2][i], &table[3][i]]),
+                i,
+            );
+        }
+
+        let mut m = vec![FieldElement::zero(); n];
+        for (i, row) in rows.iter().enumerate() {
+            if row.selectors.q_lookup != FieldElement::zero() {
+                let entry = [&w[0][i], &w[1][i], &w[2][i], &row.selectors.q_table];
+                // Lookups of missing entries are left for the verifier to reject.
+                if let Some(j) = table_index.get(&key(entry)) {
+                    m[*j] = &m[*j] + FieldElement::one();
+                }
+            }
+        }
+
+        Self { w, m }
+    }
+}
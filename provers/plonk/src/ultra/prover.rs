@@ -0,0 +1,535 @@
+use std::marker::PhantomData;
+
+use lambdaworks_crypto::commitments::traits::IsCommitmentScheme;
+use lambdaworks_crypto::fiat_shamir::is_transcript::IsTranscript;
+use lambdaworks_math::field::traits::IsFFTField;
+use lambdaworks_math::traits::{AsBytes, ByteConversion, IsRandomFieldElementGenerator};
+use lambdaworks_math::{
+    field::{element::FieldElement, traits::IsField},
+    polynomial::{self, Polynomial},
+};
+
+use super::setup::{
+    new_ultra_fiat_shamir_transcript, UltraCommonPreprocessedInput, UltraVerificationKey,
+    QUOTIENT_CHUNKS,
+};
+use super::{Selectors, UltraWitness};
+
+/// Ultra PLONK proof.
+/// The challenges are denoted
+///     Round 2: η (lookup compression), β, γ (permutation), δ (lookup),
+///     Round 3: α,
+///     Round 4: ζ,
+///     Round 5: υ.
+/// Here `z` is the permutation grand product and `φ` is the running sum of the
+/// LogUp lookup argument. The polynomial `t` is the sum of all the gate identities
+/// combined with powers of α, divided by `Z_H`.
+/// Unlike the standard prover there is no linearization: every polynomial is opened at ζ,
+/// which makes the proof larger but keeps the verifier independent of the gate set.
+pub struct UltraProof<F: IsField, CS: IsCommitmentScheme<F>> {
+    // Round 1.
+    /// Commitments to the wire polynomials `w_1(x), ..., w_4(x)`
+    pub w_1: [CS::Commitment; 4],
+    /// Commitment to the lookup multiplicities polynomial `m(x)`
+    pub m_1: CS::Commitment,
+
+    // Round 2.
+    /// Commitment to the copy constraints polynomial `z(x)`
+    pub z_1: CS::Commitment,
+    /// Commitment to the lookup running sum polynomial `φ(x)`
+    pub phi_1: CS::Commitment,
+
+    // Round 3.
+    /// Commitments to the chunks of the quotient polynomial `t(x)`, from the lowest
+    pub t_1: Vec<CS::Commitment>,
+
+    // Round 4.
+    pub evaluations: UltraEvaluations<F>,
+
+    // Round 5.
+    /// Batch opening proof for all the evaluations at ζ
+    pub w_zeta_1: CS::Commitment,
+    /// Batch opening proof for all the evaluations at ζω
+    pub w_zeta_omega_1: CS::Commitment,
+}
+
+/// Evaluations included in an [`UltraProof`].
+#[derive(Clone, Debug)]
+pub struct UltraEvaluations<F: IsField> {
+    /// Values at ζ.
+    pub w: [FieldElement<F>; 4],
+    pub m: FieldElement<F>,
+    pub z: FieldElement<F>,
+    pub phi: FieldElement<F>,
+    pub t: Vec<FieldElement<F>>,
+    pub selectors: Selectors<FieldElement<F>>,
+    pub sigma: [FieldElement<F>; 4],
+    pub table: [FieldElement<F>; 4],
+
+    /// Values at ζω.
+    pub w_omega: [FieldElement<F>; 4],
+    pub z_omega: FieldElement<F>,
+    pub phi_omega: FieldElement<F>,
+}
+
+impl<F: IsField> UltraEvaluations<F> {
+    pub fn at_zeta(&self) -> Vec<FieldElement<F>> {
+        let mut fixed = self.selectors.as_vec();
+        fixed.extend(&self.sigma);
+        fixed.extend(&self.table);
+        batch_at_zeta(&self.w, &self.m, &self.z, &self.phi, &self.t, fixed)
+    }
+
+    pub fn at_zeta_omega(&self) -> Vec<FieldElement<F>> {
+        batch_at_zeta_omega(&self.w_omega, &self.z_omega, &self.phi_omega)
+    }
+}
+
+/// Order of the polynomials, commitments and evaluations of the opening at ζ:
+/// wires, multiplicities, permutation, running sum, quotient chunks and fixed columns.
+pub(crate) fn batch_at_zeta<T: Clone>(
+    w: &[T; 4],
+    m: &T,
+    z: &T,
+    phi: &T,
+    t: &[T],
+    fixed: Vec<&T>,
+) -> Vec<T> {
+    let mut batch = w.to_vec();
+    batch.extend([m.clone(), z.clone(), phi.clone()]);
+    batch.extend_from_slice(t);
+    batch.extend(fixed.into_iter().cloned());
+    batch
+}
+
+/// Order of the polynomials, commitments and evaluations of the opening at ζω.
+pub(crate) fn batch_at_zeta_omega<T: Clone>(w: &[T; 4], z: &T, phi: &T) -> Vec<T> {
+    let mut batch = w.to_vec();
+    batch.extend([z.clone(), phi.clone()]);
+    batch
+}
+
+pub(crate) struct Challenges<F: IsField> {
+    pub eta: FieldElement<F>,
+    pub beta: FieldElement<F>,
+    pub gamma: FieldElement<F>,
+    pub delta: FieldElement<F>,
+    pub alpha: FieldElement<F>,
+}
+
+/// Values of every column at a point `x`, and of the shifted columns at `ωx`.
+pub(crate) struct PointValues<F: IsField> {
+    pub x: FieldElement<F>,
+    pub w: [FieldElement<F>; 4],
+    pub w_omega: [FieldElement<F>; 4],
+    pub m: FieldElement<F>,
+    pub z: FieldElement<F>,
+    pub z_omega: FieldElement<F>,
+    pub phi: FieldElement<F>,
+    pub phi_omega: FieldElement<F>,
+    pub selectors: Selectors<FieldElement<F>>,
+    pub sigma: [FieldElement<F>; 4],
+    pub table: [FieldElement<F>; 4],
+    /// First Lagrange polynomial
+    pub l1: FieldElement<F>,
+    /// Public input polynomial
+    pub pi: FieldElement<F>,
+}
+
+/// Evaluates the combination of all the gate identities at a point. The quotient
+/// polynomial is this numerator divided by `Z_H`. Shared by the prover, which evaluates it
+/// over a coset, and by the verifier, which evaluates it at ζ.
+pub(crate) fn constraints_numerator<F: IsField>(
+    v: &PointValues<F>,
+    ch: &Challenges<F>,
+    k: &FieldElement<F>,
+) -> FieldElement<F> {
+    let [w1, w2, w3, w4] = &v.w;
+    let s = &v.selectors;
+
+    let arithmetic = &s.q_arith
+        * (&s.q_1 * w1
+            + &s.q_2 * w2
+            + &s.q_3 * w3
+            + &s.q_4 * w4
+            + &s.q_m * w1 * w2
+            + &s.q_c
+            + &s.q_4_next * &v.w_omega[3])
+        + &v.pi;
+
+    // z(ωX) Πⱼ (wⱼ + β σⱼ + γ) - z(X) Πⱼ (wⱼ + β kʲ X + γ)
+    let mut id_product = v.z.clone();
+    let mut sigma_product = v.z_omega.clone();
+    let mut k_j = FieldElement::<F>::one();
+    for j in 0..4 {
+        id_product *= &v.w[j] + &ch.beta * &k_j * &v.x + &ch.gamma;
+        sigma_product *= &v.w[j] + &ch.beta * &v.sigma[j] + &ch.gamma;
+        k_j *= k;
+    }
+    let permutation = sigma_product - id_product;
+    let permutation_start = (&v.z - FieldElement::<F>::one()) * &v.l1;
+
+    // (φ(ωX) - φ(X)) (δ + f) (δ + t) - q_lookup (δ + t) + m (δ + f)
+    let eta = &ch.eta;
+    let f = w1 + eta * (w2 + eta * (w3 + eta * &s.q_table));
+    let t = &v.table[0] + eta * (&v.table[1] + eta * (&v.table[2] + eta * &v.table[3]));
+    let (f, t) = (&ch.delta + f, &ch.delta + t);
+    let lookup = (&v.phi_omega - &v.phi) * &f * &t - &s.q_lookup * &t + &v.m * &f;
+
+    let (x1, y1, x2, y2) = (w1, w2, w3, w4);
+    let (x3, y3, dx_inv) = (&v.w_omega[0], &v.w_omega[1], &v.w_omega[2]);
+    let dx = x2 - x1;
+    let dy = y2 - y1;
+    let ecc_x = &s.q_ecc * ((x3 + x2 + x1) * &dx * &dx - &dy * &dy);
+    let ecc_y = &s.q_ecc * ((y3 + y1) * &dx - dy * (x1 - x3));
+    let ecc_dx = &s.q_ecc * (dx * dx_inv - FieldElement::<F>::one());
+
+    [
+        arithmetic,
+        permutation,
+        permutation_start,
+        lookup,
+        ecc_x,
+        ecc_y,
+        ecc_dx,
+    ]
+    .into_iter()
+    .rev()
+    .reduce(|acc, term| term + &ch.alpha * acc)
+    .unwrap()
+}
+
+pub struct UltraProver<F: IsField, CS: IsCommitmentScheme<F>, R: IsRandomFieldElementGenerator<F>> {
+    commitment_scheme: CS,
+    random_generator: R,
+    phantom: PhantomData<F>,
+}
+
+struct Round1Result<F: IsField, Hiding> {
+    w_1: [Hiding; 4],
+    m_1: Hiding,
+    p_w: [Polynomial<FieldElement<F>>; 4],
+    p_m: Polynomial<FieldElement<F>>,
+}
+
+struct Round2Result<F: IsField, Hiding> {
+    z_1: Hiding,
+    phi_1: Hiding,
+    p_z: Polynomial<FieldElement<F>>,
+    p_phi: Polynomial<FieldElement<F>>,
+}
+
+struct Round3Result<F: IsField, Hiding> {
+    t_1: Vec<Hiding>,
+    p_t: Vec<Polynomial<FieldElement<F>>>,
+}
+
+impl<F, CS, R> UltraProver<F, CS, R>
+where
+    F: IsField + IsFFTField,
+    CS: IsCommitmentScheme<F>,
+    FieldElement<F>: ByteConversion,
+    CS::Commitment: AsBytes + Clone,
+    R: IsRandomFieldElementGenerator<F>,
+{
+    pub fn new(commitment_scheme: CS, random_generator: R) -> Self {
+        Self {
+            commitment_scheme,
+            random_generator,
+            phantom: PhantomData,
+        }
+    }
+
+    fn blind_polynomial(
+        &self,
+        target: &Polynomial<FieldElement<F>>,
+        blinder: &Polynomial<FieldElement<F>>,
+        n: u64,
+    ) -> Polynomial<FieldElement<F>> {
+        let bs: Vec<FieldElement<F>> = (0..n).map(|_| self.random_generator.generate()).collect();
+        let random_part = Polynomial::new(&bs);
+        target + blinder * random_part
+    }
+
+    fn round_1(
+        &self,
+        witness: &UltraWitness<F>,
+        cpi: &UltraCommonPreprocessedInput<F>,
+    ) -> Round1Result<F, CS::Commitment> {
+        let z_h = Polynomial::new_monomial(FieldElement::one(), cpi.n) - FieldElement::<F>::one();
+
+        // Wires are opened at ζ and ζω, so they get one more blinding coefficient.
+        let p_w = [0, 1, 2, 3].map(|j| {
+            let p = Polynomial::interpolate_fft::<F>(&witness.w[j])
+                .expect("xs and ys have equal length and xs are unique");
+            self.blind_polynomial(&p, &z_h, 3)
+        });
+        let p_m = Polynomial::interpolate_fft::<F>(&witness.m)
+            .expect("xs and ys have equal length and xs are unique");
+        let p_m = self.blind_polynomial(&p_m, &z_h, 2);
+
+        Round1Result {
+            w_1: [0, 1, 2, 3].map(|j| self.commitment_scheme.commit(&p_w[j])),
+            m_1: self.commitment_scheme.commit(&p_m),
+            p_w,
+            p_m,
+        }
+    }
+
+    fn round_2(
+        &self,
+        witness: &UltraWitness<F>,
+        cpi: &UltraCommonPreprocessedInput<F>,
+        ch: &Challenges<F>,
+    ) -> Round2Result<F, CS::Commitment> {
+        let k_powers = [0u64, 1, 2, 3].map(|j| cpi.k.pow(j));
+        let (eta, delta) = (&ch.eta, &ch.delta);
+
+        let mut z = vec![FieldElement::one()];
+        let mut phi = vec![FieldElement::zero()];
+        for i in 0..cpi.n - 1 {
+            let (mut num, mut den) = (FieldElement::<F>::one(), FieldElement::<F>::one());
+            for (j, k_j) in k_powers.iter().enumerate() {
+                let w = &witness.w[j][i];
+                num *= w + &ch.beta * k_j * &cpi.domain[i] + &ch.gamma;
+                den *= w + &ch.beta * &cpi.sigma_lagrange[j][i] + &ch.gamma;
+            }
+            let new_z = z.last().unwrap() * num / den;
+            z.push(new_z);
+
+            let [w1, w2, w3, _] = [0, 1, 2, 3].map(|j| &witness.w[j][i]);
+            let table = [0, 1, 2, 3].map(|j| &cpi.table_lagrange[j][i]);
+            let q_table = &cpi.selectors_lagrange.q_table[i];
+            let f = w1 + eta * (w2 + eta * (w3 + eta * q_table));
+            let t = table[0] + eta * (table[1] + eta * (table[2] + eta * table[3]));
+            let new_phi = phi.last().unwrap() + &cpi.selectors_lagrange.q_lookup[i] / (delta + f)
+                - &witness.m[i] / (delta + t);
+            phi.push(new_phi);
+        }
+
+        let z_h = Polynomial::new_monomial(FieldElement::one(), cpi.n) - FieldElement::<F>::one();
+        let p_z = Polynomial::interpolate_fft::<F>(&z)
+            .expect("xs and ys have equal length and xs are unique");
+        let p_z = self.blind_polynomial(&p_z, &z_h, 3);
+        let p_phi = Polynomial::interpolate_fft::<F>(&phi)
+            .expect("xs and ys have equal length and xs are unique");
+        let p_phi = self.blind_polynomial(&p_phi, &z_h, 3);
+
+        Round2Result {
+            z_1: self.commitment_scheme.commit(&p_z),
+            phi_1: self.commitment_scheme.commit(&p_phi),
+            p_z,
+            p_phi,
+        }
+    }
+
+    fn round_3(
+        &self,
+        cpi: &UltraCommonPreprocessedInput<F>,
+        public_input: &[FieldElement<F>],
+        round_1: &Round1Result<F, CS::Commitment>,
+        round_2: &Round2Result<F, CS::Commitment>,
+        ch: &Challenges<F>,
+    ) -> Round3Result<F, CS::Commitment> {
+        let n = cpi.n;
+        let shift = |p: &Polynomial<FieldElement<F>>| {
+            let coefficients: Vec<_> = p
+                .coefficients()
+                .iter()
+                .enumerate()
+                .map(|(i, x)| x * &cpi.domain[i % n])
+                .collect();
+            Polynomial::new(&coefficients)
+        };
+
+        let mut e1 = vec![FieldElement::<F>::zero(); n];
+        e1[0] = FieldElement::one();
+        let l1 = Polynomial::interpolate_fft::<F>(&e1)
+            .expect("xs and ys have equal length and xs are unique");
+        let mut p_pi_y = public_input.to_vec();
+        p_pi_y.resize(n, FieldElement::zero());
+        let p_pi = Polynomial::interpolate_fft::<F>(&p_pi_y)
+            .expect("xs and ys have equal length and xs are unique");
+        let p_x = Polynomial::new_monomial(FieldElement::<F>::one(), 1);
+        let zh = Polynomial::new_monomial(FieldElement::<F>::one(), n) - FieldElement::<F>::one();
+
+        // To leverage FFT we work with the evaluation form of every polynomial involved.
+        // The numerator has degree at most 5n + 10, so 8n points are enough.
+        let degree = 8 * n;
+        let offset = &cpi.k;
+        let eval = |p: &Polynomial<FieldElement<F>>| {
+            Polynomial::evaluate_offset_fft(p, 1, Some(degree), offset).unwrap()
+        };
+
+        let w_eval = [0, 1, 2, 3].map(|j| eval(&round_1.p_w[j]));
+        let w_omega_eval = [0, 1, 2, 3].map(|j| eval(&shift(&round_1.p_w[j])));
+        let m_eval = eval(&round_1.p_m);
+        let z_eval = eval(&round_2.p_z);
+        let z_omega_eval = eval(&shift(&round_2.p_z));
+        let phi_eval = eval(&round_2.p_phi);
+        let phi_omega_eval = eval(&shift(&round_2.p_phi));
+        let selectors_eval = cpi.selectors.map(eval);
+        let sigma_eval = [0, 1, 2, 3].map(|j| eval(&cpi.sigma[j]));
+        let table_eval = [0, 1, 2, 3].map(|j| eval(&cpi.table[j]));
+        let l1_eval = eval(&l1);
+        let p_pi_eval = eval(&p_pi);
+        let p_x_eval = eval(&p_x);
+
+        let p_eval: Vec<_> = (0..degree)
+            .map(|i| {
+                let values = PointValues {
+                    x: p_x_eval[i].clone(),
+                    w: [0, 1, 2, 3].map(|j| w_eval[j][i].clone()),
+                    w_omega: [0, 1, 2, 3].map(|j| w_omega_eval[j][i].clone()),
+                    m: m_eval[i].clone(),
+                    z: z_eval[i].clone(),
+                    z_omega: z_omega_eval[i].clone(),
+                    phi: phi_eval[i].clone(),
+                    phi_omega: phi_omega_eval[i].clone(),
+                    selectors: selectors_eval.map(|e| e[i].clone()),
+                    sigma: [0, 1, 2, 3].map(|j| sigma_eval[j][i].clone()),
+                    table: [0, 1, 2, 3].map(|j| table_eval[j][i].clone()),
+                    l1: l1_eval[i].clone(),
+                    pi: p_pi_eval[i].clone(),
+                };
+                constraints_numerator(&values, ch, &cpi.k)
+            })
+            .collect();
+
+        let mut zh_eval = eval(&zh);
+        FieldElement::inplace_batch_inverse(&mut zh_eval).unwrap();
+        let c: Vec<_> = p_eval
+            .iter()
+            .zip(zh_eval.iter())
+            .map(|(a, b)| a * b)
+            .collect();
+        let mut t = Polynomial::interpolate_offset_fft(&c, offset).unwrap();
+        polynomial::pad_with_zero_coefficients_to_length(&mut t, QUOTIENT_CHUNKS * n);
+
+        // t = Σ Xⁱⁿ tᵢ. Every chunk but the last gets bᵢXⁿ added, and the next one bᵢ subtracted.
+        let mut p_t: Vec<_> = t.coefficients.chunks(n).map(Polynomial::new).collect();
+        for i in 0..QUOTIENT_CHUNKS - 1 {
+            let b = self.random_generator.generate();
+            p_t[i] = &p_t[i] + &b * Polynomial::new_monomial(FieldElement::one(), n);
+            p_t[i + 1] = &p_t[i + 1] - b;
+        }
+
+        Round3Result {
+            t_1: p_t
+                .iter()
+                .map(|p| self.commitment_scheme.commit(p))
+                .collect(),
+            p_t,
+        }
+    }
+
+    fn round_4(
+        &self,
+        cpi: &UltraCommonPreprocessedInput<F>,
+        round_1: &Round1Result<F, CS::Commitment>,
+        round_2: &Round2Result<F, CS::Commitment>,
+        round_3: &Round3Result<F, CS::Commitment>,
+        zeta: &FieldElement<F>,
+    ) -> UltraEvaluations<F> {
+        let zeta_omega = zeta * &cpi.omega;
+        UltraEvaluations {
+            w: [0, 1, 2, 3].map(|j| round_1.p_w[j].evaluate(zeta)),
+            m: round_1.p_m.evaluate(zeta),
+            z: round_2.p_z.evaluate(zeta),
+            phi: round_2.p_phi.evaluate(zeta),
+            t: round_3.p_t.iter().map(|p| p.evaluate(zeta)).collect(),
+            selectors: cpi.selectors.map(|p| p.evaluate(zeta)),
+            sigma: [0, 1, 2, 3].map(|j| cpi.sigma[j].evaluate(zeta)),
+            table: [0, 1, 2, 3].map(|j| cpi.table[j].evaluate(zeta)),
+            w_omega: [0, 1, 2, 3].map(|j| round_1.p_w[j].evaluate(&zeta_omega)),
+            z_omega: round_2.p_z.evaluate(&zeta_omega),
+            phi_omega: round_2.p_phi.evaluate(&zeta_omega),
+        }
+    }
+
+    pub fn prove(
+        &self,
+        witness: &UltraWitness<F>,
+        public_input: &[FieldElement<F>],
+        common_preprocessed_input: &UltraCommonPreprocessedInput<F>,
+        vk: &UltraVerificationKey<CS::Commitment>,
+    ) -> UltraProof<F, CS> {
+        let cpi = common_preprocessed_input;
+        let mut transcript = new_ultra_fiat_shamir_transcript::<F, CS>(vk, public_input);
+
+        // Round 1
+        let round_1 = self.round_1(witness, cpi);
+        for commitment in round_1.w_1.iter().chain([&round_1.m_1]) {
+            transcript.append_bytes(&commitment.as_bytes());
+        }
+
+        // Round 2
+        let eta = transcript.sample_field_element();
+        let beta = transcript.sample_field_element();
+        let gamma = transcript.sample_field_element();
+        let delta = transcript.sample_field_element();
+        let mut challenges = Challenges {
+            eta,
+            beta,
+            gamma,
+            delta,
+            alpha: FieldElement::zero(),
+        };
+        let round_2 = self.round_2(witness, cpi, &challenges);
+        transcript.append_bytes(&round_2.z_1.as_bytes());
+        transcript.append_bytes(&round_2.phi_1.as_bytes());
+
+        // Round 3
+        challenges.alpha = transcript.sample_field_element();
+        let round_3 = self.round_3(cpi, public_input, &round_1, &round_2, &challenges);
+        for commitment in round_3.t_1.iter() {
+            transcript.append_bytes(&commitment.as_bytes());
+        }
+
+        // Round 4
+        let zeta = transcript.sample_field_element();
+        let evaluations = self.round_4(cpi, &round_1, &round_2, &round_3, &zeta);
+        for value in evaluations
+            .at_zeta()
+            .iter()
+            .chain(evaluations.at_zeta_omega().iter())
+        {
+            transcript.append_field_element(value);
+        }
+
+        // Round 5
+        let upsilon = transcript.sample_field_element();
+        let polynomials = batch_at_zeta(
+            &round_1.p_w,
+            &round_1.p_m,
+            &round_2.p_z,
+            &round_2.p_phi,
+            &round_3.p_t,
+            cpi.fixed_polynomials(),
+        );
+        let w_zeta_1 = self.commitment_scheme.open_batch(
+            &zeta,
+            &evaluations.at_zeta(),
+            &polynomials,
+            &upsilon,
+        );
+        let polynomials = batch_at_zeta_omega(&round_1.p_w, &round_2.p_z, &round_2.p_phi);
+        let w_zeta_omega_1 = self.commitment_scheme.open_batch(
+            &(&zeta * &cpi.omega),
+            &evaluations.at_zeta_omega(),
+            &polynomials,
+            &upsilon,
+        );
+
+        UltraProof {
+            w_1: round_1.w_1,
+            m_1: round_1.m_1,
+            z_1: round_2.z_1,
+            phi_1: round_2.phi_1,
+            t_1: round_3.t_1,
+            evaluations,
+            w_zeta_1,
+            w_zeta_omega_1,
+        }
+    }
+}
@@ -0,0 +1,149 @@
+use lambdaworks_crypto::commitments::traits::IsCommitmentScheme;
+use lambdaworks_crypto::fiat_shamir::{
+    default_transcript::DefaultTranscript, is_transcript::IsTranscript,
+};
+use lambdaworks_math::field::traits::IsFFTField;
+use lambdaworks_math::field::{element::FieldElement, traits::IsField};
+use lambdaworks_math::polynomial::Polynomial;
+use lambdaworks_math::traits::{AsBytes, ByteConversion};
+
+use super::{Selectors, UltraConstraintSystem};
+use crate::constraint_system::get_permutation;
+use crate::test_utils::utils::generate_domain;
+
+/// Number of chunks of size `n` the quotient polynomial is split into.
+/// The highest degree term is the permutation argument, `z(X)` times four wire factors.
+pub const QUOTIENT_CHUNKS: usize = 5;
+
+// TODO: implement getters
+#[derive(Clone)]
+pub struct UltraCommonPreprocessedInput<F: IsField> {
+    /// Number of rows
+    pub n: usize,
+    pub domain: Vec<FieldElement<F>>,
+    pub omega: FieldElement<F>,
+    /// The cosets `kʲ H`, for j = 0..4, tell the four wire columns apart in the permutation.
+    pub k: FieldElement<F>,
+
+    pub selectors: Selectors<Polynomial<FieldElement<F>>>,
+    pub sigma: [Polynomial<FieldElement<F>>; 4],
+    /// Table columns `t_1, t_2, t_3` and the table identifiers.
+    pub table: [Polynomial<FieldElement<F>>; 4],
+
+    pub selectors_lagrange: Selectors<Vec<FieldElement<F>>>,
+    pub sigma_lagrange: [Vec<FieldElement<F>>; 4],
+    pub table_lagrange: [Vec<FieldElement<F>>; 4],
+}
+
+impl<F: IsFFTField> UltraCommonPreprocessedInput<F> {
+    pub fn from_constraint_system(system: &UltraConstraintSystem<F>, k: &FieldElement<F>) -> Self {
+        let rows = system.rows();
+        let n = rows.len();
+        let omega = F::get_primitive_root_of_unity(n.trailing_zeros() as u64).unwrap();
+        let domain = generate_domain(&omega, n);
+
+        let selectors_lagrange: Selectors<Vec<FieldElement<F>>> = Selectors::from_vec(
+            (0..11)
+                .map(|i| {
+                    rows.iter()
+                        .map(|row| row.selectors.as_vec()[i].clone())
+                        .collect()
+                })
+                .collect(),
+        );
+
+        // Wires are laid out as | w_1 column | w_2 column | w_3 column | w_4 column |
+        let wires: Vec<_> = (0..4)
+            .flat_map(|j| rows.iter().map(move |row| row.wires[j]))
+            .collect();
+        let permutation = get_permutation(&wires);
+        let identity: Vec<_> = (0..4)
+            .flat_map(|j| {
+                let k_j = k.pow(j as u64);
+                domain.iter().map(move |x| x * &k_j)
+            })
+            .collect();
+        let sigma_lagrange: [Vec<FieldElement<F>>; 4] = [0, 1, 2, 3].map(|j| {
+            (0..n)
+                .map(|i| identity[permutation[j * n + i]].clone())
+                .collect()
+        });
+
+        let table_lagrange = system.table_columns();
+
+        // TODO: Remove unwraps
+        let interpolate =
+            |values: &Vec<FieldElement<F>>| Polynomial::interpolate_fft::<F>(values).unwrap();
+
+        Self {
+            n,
+            domain,
+            omega,
+            k: k.clone(),
+            selectors: selectors_lagrange.map(interpolate),
+            sigma: [0, 1, 2, 3].map(|j| interpolate(&sigma_lagrange[j])),
+            table: [0, 1, 2, 3].map(|j| interpolate(&table_lagrange[j])),
+            selectors_lagrange,
+            sigma_lagrange,
+            table_lagrange,
+        }
+    }
+
+    /// Selectors, permutation and table polynomials, in the order of
+    /// [`UltraVerificationKey::commitments`].
+    pub fn fixed_polynomials(&self) -> Vec<&Polynomial<FieldElement<F>>> {
+        let mut polynomials = self.selectors.as_vec();
+        polynomials.extend(&self.sigma);
+        polynomials.extend(&self.table);
+        polynomials
+    }
+}
+
+pub struct UltraVerificationKey<Commitment> {
+    pub selectors: Selectors<Commitment>,
+    pub sigma: [Commitment; 4],
+    pub table: [Commitment; 4],
+}
+
+impl<Commitment> UltraVerificationKey<Commitment> {
+    /// Commitments to the selectors, the permutation and the tables, in this order.
+    pub fn commitments(&self) -> Vec<&Commitment> {
+        let mut commitments = self.selectors.as_vec();
+        commitments.extend(&self.sigma);
+        commitments.extend(&self.table);
+        commitments
+    }
+}
+
+pub fn setup<F: IsField, CS: IsCommitmentScheme<F>>(
+    common_input: &UltraCommonPreprocessedInput<F>,
+    commitment_scheme: &CS,
+) -> UltraVerificationKey<CS::Commitment> {
+    UltraVerificationKey {
+        selectors: common_input.selectors.map(|p| commitment_scheme.commit(p)),
+        sigma: [0, 1, 2, 3].map(|j| commitment_scheme.commit(&common_input.sigma[j])),
+        table: [0, 1, 2, 3].map(|j| commitment_scheme.commit(&common_input.table[j])),
+    }
+}
+
+pub fn new_ultra_fiat_shamir_transcript<F, CS>(
+    vk: &UltraVerificationKey<CS::Commitment>,
+    public_input: &[FieldElement<F>],
+) -> DefaultTranscript<F>
+where
+    F: IsField,
+    FieldElement<F>: ByteConversion,
+    CS: IsCommitmentScheme<F>,
+    CS::Commitment: AsBytes,
+{
+    let mut transcript = DefaultTranscript::default();
+
+    for commitment in vk.commitments() {
+        transcript.append_bytes(&commitment.as_bytes());
+    }
+    for value in public_input.iter() {
+        transcript.append_field_element(value);
+    }
+
+    transcript
+}
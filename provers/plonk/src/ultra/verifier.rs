@@ -0,0 +1,319 @@
+use std::marker::PhantomData;
+
+use lambdaworks_crypto::commitments::traits::IsCommitmentScheme;
+use lambdaworks_crypto::fiat_shamir::is_transcript::IsTranscript;
+use lambdaworks_math::field::element::FieldElement;
+use lambdaworks_math::field::traits::{IsFFTField, IsField};
+use lambdaworks_math::traits::{AsBytes, ByteConversion};
+
+use super::prover::{
+    batch_at_zeta, batch_at_zeta_omega, constraints_numerator, Challenges, PointValues, UltraProof,
+};
+use super::setup::{
+    new_ultra_fiat_shamir_transcript, UltraCommonPreprocessedInput, UltraVerificationKey,
+    QUOTIENT_CHUNKS,
+};
+
+pub struct UltraVerifier<F: IsField, CS: IsCommitmentScheme<F>> {
+    commitment_scheme: CS,
+    phantom: PhantomData<F>,
+}
+
+impl<F, CS> UltraVerifier<F, CS>
+where
+    F: IsField + IsFFTField,
+    CS: IsCommitmentScheme<F>,
+    CS::Commitment: AsBytes + Clone,
+    FieldElement<F>: ByteConversion,
+{
+    pub fn new(commitment_scheme: CS) -> Self {
+        Self {
+            commitment_scheme,
+            phantom: PhantomData,
+        }
+    }
+
+    fn compute_challenges(
+        &self,
+        p: &UltraProof<F, CS>,
+        vk: &UltraVerificationKey<CS::Commitment>,
+        public_input: &[FieldElement<F>],
+    ) -> (Challenges<F>, FieldElement<F>, FieldElement<F>) {
+        let mut transcript = new_ultra_fiat_shamir_transcript::<F, CS>(vk, public_input);
+
+        for commitment in p.w_1.iter().chain([&p.m_1]) {
+            transcript.append_bytes(&commitment.as_bytes());
+        }
+        let eta = transcript.sample_field_element();
+        let beta = transcript.sample_field_element();
+        let gamma = transcript.sample_field_element();
+        let delta = transcript.sample_field_element();
+
+        transcript.append_bytes(&p.z_1.as_bytes());
+        transcript.append_bytes(&p.phi_1.as_bytes());
+        let alpha = transcript.sample_field_element();
+
+        for commitment in p.t_1.iter() {
+            transcript.append_bytes(&commitment.as_bytes());
+        }
+        let zeta = transcript.sample_field_element();
+
+        for value in p
+            .evaluations
+            .at_zeta()
+            .iter()
+            .chain(p.evaluations.at_zeta_omega().iter())
+        {
+            transcript.append_field_element(value);
+        }
+        let upsilon = transcript.sample_field_element();
+
+        let challenges = Challenges {
+            eta,
+            beta,
+            gamma,
+            delta,
+            alpha,
+        };
+        (challenges, zeta, upsilon)
+    }
+
+    pub fn verify(
+        &self,
+        p: &UltraProof<F, CS>,
+        public_input: &[FieldElement<F>],
+        input: &UltraCommonPreprocessedInput<F>,
+        vk: &UltraVerificationKey<CS::Commitment>,
+    ) -> bool {
+        if p.t_1.len() != QUOTIENT_CHUNKS || p.evaluations.t.len() != QUOTIENT_CHUNKS {
+            return false;
+        }
+
+        let (challenges, zeta, upsilon) = self.compute_challenges(p, vk, public_input);
+        let e = &p.evaluations;
+        let n = FieldElement::<F>::from(input.n as u64);
+        let zh_zeta = zeta.pow(input.n) - FieldElement::<F>::one();
+
+        // Lᵢ(ζ) = ωⁱ (ζⁿ - 1) / (n (ζ - ωⁱ))
+        let lagrange = |i: usize| &input.domain[i] * &zh_zeta / (&n * (&zeta - &input.domain[i]));
+        let l1_zeta = lagrange(0);
+        let p_pi_zeta = public_input
+            .iter()
+            .enumerate()
+            .fold(FieldElement::zero(), |acc, (i, value)| {
+                acc + value * lagrange(i)
+            });
+
+        let values = PointValues {
+            x: zeta.clone(),
+            w: e.w.clone(),
+            w_omega: e.w_omega.clone(),
+            m: e.m.clone(),
+            z: e.z.clone(),
+            z_omega: e.z_omega.clone(),
+            phi: e.phi.clone(),
+            phi_omega: e.phi_omega.clone(),
+            selectors: e.selectors.clone(),
+            sigma: e.sigma.clone(),
+            table: e.table.clone(),
+            l1: l1_zeta,
+            pi: p_pi_zeta,
+        };
+        let p_zeta = constraints_numerator(&values, &challenges, &input.k);
+
+        // t(ζ) = Σ ζⁱⁿ tᵢ(ζ)
+        let zeta_raised_n = zeta.pow(input.n);
+        let t_zeta =
+            e.t.iter()
+                .rev()
+                .fold(FieldElement::zero(), |acc, t_i| acc * &zeta_raised_n + t_i);
+        let constraints_check = p_zeta == zh_zeta * t_zeta;
+
+        let commitments = batch_at_zeta(&p.w_1, &p.m_1, &p.z_1, &p.phi_1, &p.t_1, vk.commitments());
+        let batch_openings_check = self.commitment_scheme.verify_batch(
+            &zeta,
+            &e.at_zeta(),
+            &commitments,
+            &p.w_zeta_1,
+            &upsilon,
+        );
+
+        let commitments = batch_at_zeta_omega(&p.w_1, &p.z_1, &p.phi_1);
+        let shifted_openings_check = self.commitment_scheme.verify_batch(
+            &(&zeta * &input.omega),
+            &e.at_zeta_omega(),
+            &commitments,
+            &p.w_zeta_omega_1,
+            &upsilon,
+        );
+
+        constraints_check && batch_openings_check && shifted_openings_check
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use lambdaworks_math::elliptic_curve::short_weierstrass::curves::bls12_381::default_types::{
+        FrElement, FrField,
+    };
+
+    use super::*;
+    use crate::test_utils::utils::{
+        test_srs, TestRandomFieldGenerator, KZG, ORDER_R_MINUS_1_ROOT_UNITY,
+    };
+    use crate::ultra::{
+        prover::UltraProver, setup::setup, Selectors, UltraConstraintSystem, UltraWitness,
+    };
+
+    fn prove_and_verify(system: &UltraConstraintSystem<FrField>) -> bool {
+        let cpi = UltraCommonPreprocessedInput::from_constraint_system(
+            system,
+            &ORDER_R_MINUS_1_ROOT_UNITY,
+        );
+        let srs = test_srs(cpi.n);
+        let kzg = KZG::new(srs);
+        let vk = setup(&cpi, &kzg);
+
+        let public_inputs = system.public_input_values();
+        let witness = UltraWitness::new(system);
+
+        let prover = UltraProver::new(kzg.clone(), TestRandomFieldGenerator {});
+        let proof = prover.prove(&witness, &public_inputs, &cpi, &vk);
+
+        let verifier = UltraVerifier::new(kzg);
+        verifier.verify(&proof, &public_inputs, &cpi, &vk)
+    }
+
+    /// XOR table of 2-bit values
+    fn xor_table() -> Vec<[FrElement; 3]> {
+        (0..4u64)
+            .flat_map(|a| (0..4u64).map(move |b| [a, b, a ^ b].map(FrElement::from)))
+            .collect()
+    }
+
+    #[test]
+    fn test_arithmetic_gates() {
+        // out = (x + y + z) x + (x y + y z + z x)
+        let system = &mut UltraConstraintSystem::<FrField>::new();
+        let x = system.new_variable(FrElement::from(2));
+        let y = system.new_variable(FrElement::from(3));
+        let z = system.new_variable(FrElement::from(4));
+        let out = system.new_public_input(FrElement::from(2 * 9 + 2 * 3 + 3 * 4 + 4 * 2));
+
+        let sum = system.add3(&x, &y, &z);
+        let product = system.mul(&sum, &x);
+        let inner_product = system.inner_product(&[x, y, z], &[y, z, x]);
+        let null = system.null_variable();
+        let result = system.add3(&product, &inner_product, &null);
+        system.assert_eq(&result, &out);
+
+        assert!(prove_and_verify(system));
+    }
+
+    #[test]
+    fn test_wrong_public_input_is_rejected() {
+        let system = &mut UltraConstraintSystem::<FrField>::new();
+        let x = system.new_variable(FrElement::from(2));
+        let out = system.new_public_input(FrElement::from(5));
+        let square = system.mul(&x, &x);
+        system.assert_eq(&square, &out);
+
+        assert!(!prove_and_verify(system));
+    }
+
+    #[test]
+    fn test_lookup_gates() {
+        let system = &mut UltraConstraintSystem::<FrField>::new();
+        let xor = system.add_lookup_table(xor_table());
+
+        let a = system.new_variable(FrElement::from(1));
+        let b = system.new_variable(FrElement::from(3));
+        let c = system.lookup(xor, &a, &b).unwrap();
+        // Lookups can be repeated
+        let d = system.lookup(xor, &c, &b).unwrap();
+        let out = system.new_public_input(FrElement::from(1));
+        system.assert_eq(&d, &out);
+
+        assert_eq!(system.value(&c), &FrElement::from(2));
+        assert!(prove_and_verify(system));
+    }
+
+    #[test]
+    fn test_missing_lookup_entry_is_rejected() {
+        let system = &mut UltraConstraintSystem::<FrField>::new();
+        let xor = system.add_lookup_table(xor_table());
+
+        let a = system.new_variable(FrElement::from(1));
+        let b = system.new_variable(FrElement::from(3));
+        let c = system.new_variable(FrElement::from(3));
+        system.assert_lookup(xor, [&a, &b, &c]);
+
+        assert!(!prove_and_verify(system));
+    }
+
+    #[test]
+    fn test_ecc_add_gate() {
+        // The gate only checks the chord addition formula, which does not depend on the
+        // curve coefficients.
+        let system = &mut UltraConstraintSystem::<FrField>::new();
+        let x1 = system.new_variable(FrElement::from(1));
+        let y1 = system.new_variable(FrElement::from(2));
+        let x2 = system.new_variable(FrElement::from(3));
+        let y2 = system.new_variable(FrElement::from(6));
+        let (x3, y3) = system.ecc_add((&x1, &y1), (&x2, &y2));
+
+        // λ = 2, x₃ = λ² - x₁ - x₂ = 0, y₃ = λ (x₁ - x₃) - y₁ = 0
+        assert_eq!(system.value(&x3), &FrElement::from(0));
+        assert_eq!(system.value(&y3), &FrElement::from(0));
+        assert!(prove_and_verify(system));
+    }
+
+    /// Adds the rows of an ecc gate with the given result and inverse of `x2 - x1`,
+    /// without computing them.
+    fn ecc_add_rows(
+        system: &mut UltraConstraintSystem<FrField>,
+        [x1, y1, x2, y2]: [u64; 4],
+        [x3, y3, dx_inv]: [FrElement; 3],
+    ) {
+        let [x1, y1, x2, y2] = [x1, y1, x2, y2].map(|v| system.new_variable(FrElement::from(v)));
+        let [x3, y3, dx_inv] = [x3, y3, dx_inv].map(|v| system.new_variable(v));
+        system.add_gate(
+            [x1, y1, x2, y2],
+            Selectors {
+                q_ecc: FrElement::one(),
+                ..Selectors::zero()
+            },
+        );
+        let null = system.null_variable();
+        system.add_gate([x3, y3, dx_inv, null], Selectors::zero());
+    }
+
+    #[test]
+    fn test_wrong_ecc_add_is_rejected() {
+        let system = &mut UltraConstraintSystem::<FrField>::new();
+        let dx_inv = FrElement::from(2).inv().unwrap();
+        ecc_add_rows(
+            system,
+            [1, 2, 3, 6],
+            [FrElement::from(1), FrElement::from(0), dx_inv],
+        );
+
+        assert!(!prove_and_verify(system));
+    }
+
+    #[test]
+    fn test_ecc_add_with_equal_x_coordinates_is_rejected() {
+        // With x₁ = x₂ and y₁ = y₂ the addition identities hold for any result, so only
+        // the inverse of x₂ - x₁ rejects it.
+        for dx_inv in [FrElement::zero(), FrElement::one()] {
+            let system = &mut UltraConstraintSystem::<FrField>::new();
+            ecc_add_rows(
+                system,
+                [1, 2, 1, 2],
+                [FrElement::from(5), FrElement::from(7), dx_inv],
+            );
+
+            assert!(!prove_and_verify(system));
+        }
+    }
+}
@@ -1,10 +1,16 @@
+use lambdaworks_crypto::commitments::fflonk::{Fflonk, FflonkProof, OpeningClaim};
+use lambdaworks_crypto::commitments::kzg::KateZaveruchaGoldberg;
 use lambdaworks_crypto::commitments::traits::IsCommitmentScheme;
+use lambdaworks_crypto::fiat_shamir::default_transcript::DefaultTranscript;
 use lambdaworks_crypto::fiat_shamir::is_transcript::IsTranscript;
 use lambdaworks_math::cyclic_group::IsGroup;
+use lambdaworks_math::elliptic_curve::traits::IsPairing;
 use lambdaworks_math::field::element::FieldElement;
 use lambdaworks_math::field::traits::{IsFFTField, IsField, IsPrimeField};
 use lambdaworks_math::traits::{AsBytes, ByteConversion};
+use lambdaworks_math::unsigned_integer::element::UnsignedInteger;
 use std::marker::PhantomData;
+use std::slice;
 
 use crate::prover::Proof;
 use crate::setup::{new_strong_fiat_shamir_transcript, CommonPreprocessedInput, VerificationKey};
@@ -22,15 +28,13 @@ impl<F: IsField + IsFFTField, CS: IsCommitmentScheme<F>> Verifier<F, CS> {
         }
     }
 
-    fn compute_challenges(
-        &self,
+    /// Replays rounds 1 to 4 on the transcript. Returns it along with `[β, γ, α, ζ]`.
+    fn transcript_until_round_5(
         p: &Proof<F, CS>,
         vk: &VerificationKey<CS::Commitment>,
         public_input: &[FieldElement<F>],
-    ) -> [FieldElement<F>; 5]
+    ) -> (DefaultTranscript<F>, [FieldElement<F>; 4])
     where
-        F: IsField,
-        CS: IsCommitmentScheme<F>,
         CS::Commitment: AsBytes,
         FieldElement<F>: ByteConversion,
     {
@@ -56,34 +60,46 @@ impl<F: IsField + IsFFTField, CS: IsCommitmentScheme<F>> Verifier<F, CS> {
         transcript.append_field_element(&p.s1_zeta);
         transcript.append_field_element(&p.s2_zeta);
         transcript.append_field_element(&p.z_zeta_omega);
-        let upsilon = transcript.sample_field_element();
 
-        [beta, gamma, alpha, zeta, upsilon]
+        (transcript, [beta, gamma, alpha, zeta])
     }
 
-    pub fn verify(
+    fn compute_challenges(
         &self,
         p: &Proof<F, CS>,
-        public_input: &[FieldElement<F>],
-        input: &CommonPreprocessedInput<F>,
         vk: &VerificationKey<CS::Commitment>,
-    ) -> bool
+        public_input: &[FieldElement<F>],
+    ) -> [FieldElement<F>; 5]
     where
-        F: IsPrimeField + IsFFTField,
+        F: IsField,
         CS: IsCommitmentScheme<F>,
-        CS::Commitment: AsBytes + IsGroup,
+        CS::Commitment: AsBytes,
         FieldElement<F>: ByteConversion,
     {
-        // TODO: First three steps are validations: belonging to main subgroup, belonging to prime field.
-        let [beta, gamma, alpha, zeta, upsilon] = self.compute_challenges(p, vk, public_input);
-        let zh_zeta = zeta.pow(input.n) - FieldElement::<F>::one();
+        let (mut transcript, [beta, gamma, alpha, zeta]) =
+            Self::transcript_until_round_5(p, vk, public_input);
+        let upsilon = transcript.sample_field_element();
 
-        let k1 = &input.k1;
-        let k2 = k1 * k1;
+        [beta, gamma, alpha, zeta, upsilon]
+    }
 
-        let l1_zeta = (zeta.pow(input.n as u64) - FieldElement::<F>::one())
-            / (&zeta - FieldElement::<F>::one())
-            / FieldElement::from(input.n as u64);
+    /// Value at `ζ` of the first polynomial of the Lagrange basis.
+    fn l1_zeta(input: &CommonPreprocessedInput<F>, zeta: &FieldElement<F>) -> FieldElement<F> {
+        (zeta.pow(input.n as u64) - FieldElement::<F>::one())
+            / (zeta - FieldElement::<F>::one())
+            / FieldElement::<F>::from(input.n as u64)
+    }
+
+    /// Checks `p(ζ) = Z_H(ζ) t(ζ)` from the evaluations in the proof.
+    fn constraints_check(
+        p: &Proof<F, CS>,
+        public_input: &[FieldElement<F>],
+        input: &CommonPreprocessedInput<F>,
+        [beta, gamma, alpha, zeta]: &[FieldElement<F>; 4],
+    ) -> bool {
+        let zh_zeta = zeta.pow(input.n) - FieldElement::<F>::one();
+
+        let l1_zeta = Self::l1_zeta(input, zeta);
 
         // Use the following equality to compute PI(ζ)
         // without interpolating:
@@ -97,23 +113,41 @@ impl<F: IsField + IsFFTField, CS: IsCommitmentScheme<F>> Verifier<F, CS> {
             for (i, value) in public_input.iter().enumerate().skip(1) {
                 li_zeta = &input.omega
                     * &li_zeta
-                    * ((&zeta - &input.domain[i - 1]) / (&zeta - &input.domain[i]));
+                    * ((zeta - &input.domain[i - 1]) / (zeta - &input.domain[i]));
                 p_pi_zeta = &p_pi_zeta + value * &li_zeta;
             }
             p_pi_zeta
         };
 
-        let mut p_constant_zeta = &alpha
+        let mut p_constant_zeta = alpha
             * &p.z_zeta_omega
-            * (&p.c_zeta + &gamma)
-            * (&p.a_zeta + &beta * &p.s1_zeta + &gamma)
-            * (&p.b_zeta + &beta * &p.s2_zeta + &gamma);
-        p_constant_zeta = p_constant_zeta - &l1_zeta * &alpha * &alpha;
+            * (&p.c_zeta + gamma)
+            * (&p.a_zeta + beta * &p.s1_zeta + gamma)
+            * (&p.b_zeta + beta * &p.s2_zeta + gamma);
+        p_constant_zeta = p_constant_zeta - &l1_zeta * alpha * alpha;
         p_constant_zeta += p_pi_zeta;
 
         let p_zeta = p_constant_zeta + &p.p_non_constant_zeta;
 
-        let constraints_check = p_zeta - (&zh_zeta * &p.t_zeta) == FieldElement::zero();
+        p_zeta - (&zh_zeta * &p.t_zeta) == FieldElement::zero()
+    }
+
+    /// Returns the commitments to the partial evaluation of `t` and to `p_non_constant`,
+    /// the two polynomials of round 5 that the verifier has to commit to by itself.
+    fn linearization_commitments(
+        p: &Proof<F, CS>,
+        input: &CommonPreprocessedInput<F>,
+        vk: &VerificationKey<CS::Commitment>,
+        [beta, gamma, alpha, zeta]: &[FieldElement<F>; 4],
+    ) -> [CS::Commitment; 2]
+    where
+        F: IsPrimeField,
+        CS::Commitment: IsGroup,
+    {
+        let k1 = &input.k1;
+        let k2 = k1 * k1;
+
+        let l1_zeta = Self::l1_zeta(input, zeta);
 
         // Compute commitment of partial evaluation of t (p = zh * t)
         let partial_t_1 = p
@@ -139,11 +173,11 @@ impl<F: IsField + IsFFTField, CS: IsCommitmentScheme<F>> Verifier<F, CS> {
 
         // Second and third terms correspond to copy constraints
         // + α*((l(ζ)+β*s1(ζ)+γ)*(r(ζ)+β*s2(ζ)+γ)*Z(μζ)*β*s3(X) - Z(X)*(l(ζ)+β*id1(ζ)+γ)*(r(ζ)+β*id2(ζ)+γ)*(o(ζ)+β*id3(ζ)+γ))
-        let z_coefficient = -(&p.a_zeta + &beta * &zeta + &gamma)
-            * (&p.b_zeta + &beta * k1 * &zeta + &gamma)
-            * (&p.c_zeta + &beta * k2 * &zeta + &gamma);
-        let s3_coefficient = (&p.a_zeta + &beta * &p.s1_zeta + &gamma)
-            * (&p.b_zeta + &beta * &p.s2_zeta + &gamma)
+        let z_coefficient = -(&p.a_zeta + beta * zeta + gamma)
+            * (&p.b_zeta + beta * k1 * zeta + gamma)
+            * (&p.c_zeta + beta * k2 * zeta + gamma);
+        let s3_coefficient = (&p.a_zeta + beta * &p.s1_zeta + gamma)
+            * (&p.b_zeta + beta * &p.s2_zeta + gamma)
             * beta
             * &p.z_zeta_omega;
         let second_term = p
@@ -154,13 +188,18 @@ impl<F: IsField + IsFFTField, CS: IsCommitmentScheme<F>> Verifier<F, CS> {
         // α²*L₁(ζ)*Z(X)
         let third_term = p
             .z_1
-            .operate_with_self((&alpha * &alpha * l1_zeta).representative());
+            .operate_with_self((alpha * alpha * l1_zeta).representative());
 
         let p_non_constant_1 = first_term
             .operate_with(&second_term)
             .operate_with(&third_term);
 
-        let ys = [
+        [partial_t_1, p_non_constant_1]
+    }
+
+    /// The evaluations at `ζ` opened in round 5, in the order of their commitments.
+    fn round_5_evaluations(p: &Proof<F, CS>) -> [FieldElement<F>; 7] {
+        [
             p.t_zeta.clone(),
             p.p_non_constant_zeta.clone(),
             p.a_zeta.clone(),
@@ -168,7 +207,31 @@ impl<F: IsField + IsFFTField, CS: IsCommitmentScheme<F>> Verifier<F, CS> {
             p.c_zeta.clone(),
             p.s1_zeta.clone(),
             p.s2_zeta.clone(),
-        ];
+        ]
+    }
+
+    pub fn verify(
+        &self,
+        p: &Proof<F, CS>,
+        public_input: &[FieldElement<F>],
+        input: &CommonPreprocessedInput<F>,
+        vk: &VerificationKey<CS::Commitment>,
+    ) -> bool
+    where
+        F: IsPrimeField + IsFFTField,
+        CS: IsCommitmentScheme<F>,
+        CS::Commitment: AsBytes + IsGroup,
+        FieldElement<F>: ByteConversion,
+    {
+        // TODO: First three steps are validations: belonging to main subgroup, belonging to prime field.
+        let [beta, gamma, alpha, zeta, upsilon] = self.compute_challenges(p, vk, public_input);
+        let challenges = [beta, gamma, alpha, zeta];
+        let constraints_check = Self::constraints_check(p, public_input, input, &challenges);
+        let [partial_t_1, p_non_constant_1] =
+            Self::linearization_commitments(p, input, vk, &challenges);
+        let [.., zeta] = challenges;
+
+        let ys = Self::round_5_evaluations(p);
         let commitments = [
             partial_t_1,
             p_non_constant_1,
@@ -193,6 +256,64 @@ impl<F: IsField + IsFFTField, CS: IsCommitmentScheme<F>> Verifier<F, CS> {
     }
 }
 
+impl<const N: usize, F, P> Verifier<F, KateZaveruchaGoldberg<F, P>>
+where
+    F: IsFFTField + IsPrimeField<RepresentativeType = UnsignedInteger<N>>,
+    P: IsPairing,
+    P::G1Point: AsBytes,
+    FieldElement<F>: ByteConversion,
+{
+    /// Verifies a proof made with
+    /// [`Prover::prove_with_fflonk_openings`](crate::prover::Prover::prove_with_fflonk_openings).
+    /// The openings of round 5 are checked with [`Fflonk::verify`], using two pairings.
+    pub fn verify_with_fflonk_openings(
+        &self,
+        p: &Proof<F, KateZaveruchaGoldberg<F, P>>,
+        public_input: &[FieldElement<F>],
+        input: &CommonPreprocessedInput<F>,
+        vk: &VerificationKey<P::G1Point>,
+    ) -> bool {
+        let (mut transcript, challenges) = Self::transcript_until_round_5(p, vk, public_input);
+        let constraints_check = Self::constraints_check(p, public_input, input, &challenges);
+        let [partial_t_1, p_non_constant_1] =
+            Self::linearization_commitments(p, input, vk, &challenges);
+        let [.., zeta] = challenges;
+
+        let ys = Self::round_5_evaluations(p);
+        let commitments = [
+            &partial_t_1,
+            &p_non_constant_1,
+            &p.a_1,
+            &p.b_1,
+            &p.c_1,
+            &vk.s1_1,
+            &vk.s2_1,
+        ];
+        let mut claims: Vec<_> = commitments
+            .into_iter()
+            .zip(&ys)
+            .map(|(commitment, y)| OpeningClaim {
+                commitment,
+                x: zeta.clone(),
+                evaluations: slice::from_ref(y),
+            })
+            .collect();
+        claims.push(OpeningClaim {
+            commitment: &p.z_1,
+            x: zeta * &input.omega,
+            evaluations: slice::from_ref(&p.z_zeta_omega),
+        });
+        let opening = FflonkProof {
+            w: p.w_zeta_1.clone(),
+            w_prime: p.w_zeta_omega_1.clone(),
+        };
+        let openings_check =
+            Fflonk::from(&self.commitment_scheme).verify(&claims, &opening, &mut transcript);
+
+        constraints_check && openings_check
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use lambdaworks_math::traits::Deserializable;
@@ -420,4 +541,94 @@ mod tests {
             &verifying_key
         ));
     }
+
+    #[test]
+    fn test_happy_path_with_fflonk_openings_for_circuit_1() {
+        // This is the circuit for x * e == y
+        let common_preprocessed_input = test_common_preprocessed_input_1();
+        let srs = test_srs(common_preprocessed_input.n);
+
+        // Public input
+        let x = FieldElement::from(4_u64);
+        let y = FieldElement::from(12_u64);
+
+        // Private variable
+        let e = FieldElement::from(3_u64);
+
+        let public_input = vec![x.clone(), y];
+        let witness = test_witness_1(x, e);
+
+        let kzg = KZG::new(srs);
+        let verifying_key = setup(&common_preprocessed_input, &kzg);
+        let random_generator = TestRandomFieldGenerator {};
+
+        let prover = Prover::new(kzg.clone(), random_generator);
+        let mut proof = prover.prove_with_fflonk_openings(
+            &witness,
+            &public_input,
+            &common_preprocessed_input,
+            &verifying_key,
+        );
+
+        let verifier = Verifier::new(kzg);
+        assert!(verifier.verify_with_fflonk_openings(
+            &proof,
+            &public_input,
+            &common_preprocessed_input,
+            &verifying_key
+        ));
+
+        // The shplonk opening is not a pair of KZG openings
+        assert!(!verifier.verify(
+            &proof,
+            &public_input,
+            &common_preprocessed_input,
+            &verifying_key
+        ));
+
+        proof.z_zeta_omega = &proof.z_zeta_omega + FieldElement::one();
+        assert!(!verifier.verify_with_fflonk_openings(
+            &proof,
+            &public_input,
+            &common_preprocessed_input,
+            &verifying_key
+        ));
+    }
+
+    #[test]
+    fn test_fflonk_openings_reject_kzg_openings_for_circuit_2() {
+        // This is the circuit for x * e + 5 == y
+        let common_preprocessed_input = test_common_preprocessed_input_2();
+        let srs = test_srs(common_preprocessed_input.n);
+
+        // Public input
+        let x = FieldElement::from(2_u64);
+        let y = FieldElement::from(11_u64);
+
+        // Private variable
+        let e = FieldElement::from(3_u64);
+
+        let public_input = vec![x.clone(), y];
+        let witness = test_witness_2(x, e);
+
+        let kzg = KZG::new(srs);
+        let verifying_key = setup(&common_preprocessed_input, &kzg);
+        let random_generator = TestRandomFieldGenerator {};
+
+        let prover = Prover::new(kzg.clone(), random_generator);
+        let proof = prover.prove(
+            &witness,
+            &public_input,
+            &common_preprocessed_input,
+            &verifying_key,
+        );
+
+        let verifier = Verifier::new(kzg);
+        assert!(!verifier.verify_with_fflonk_openings(
+            &proof,
+            &public_input,
+            &common_preprocessed_input,
+            &verifying_key
+        ));
+    }
 }
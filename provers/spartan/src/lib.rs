@@ -0,0 +1,3 @@
+pub mod prover;
+pub mod r1cs;
+pub mod verifier;
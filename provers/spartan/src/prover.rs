@@ -0,0 +1,114 @@
+use lambdaworks_crypto::{
+    commitments::traits::IsMultilinearCommitmentScheme,
+    fiat_shamir::{default_transcript::DefaultTranscript, is_transcript::IsTranscript},
+};
+use lambdaworks_gkr::{
+    sumcheck::{self, SumcheckProof},
+    utils::{eq_evals, evaluate_mle},
+};
+use lambdaworks_math::{
+    field::{element::FieldElement, traits::IsField},
+    traits::{AsBytes, ByteConversion},
+};
+
+use crate::r1cs::R1CS;
+
+pub struct SpartanProof<F: IsField, P: IsMultilinearCommitmentScheme<F>> {
+    pub witness_commitment: P::Commitment,
+    /// Sumcheck of `Σₓ eq(τ, x) (Az(x) Bz(x) - Cz(x)) = 0`.
+    pub outer_sumcheck: SumcheckProof<F>,
+    /// `Az(r_x), Bz(r_x), Cz(r_x)` at the point `r_x` of the outer sumcheck.
+    pub matrix_evaluations: [FieldElement<F>; 3],
+    /// Sumcheck of `Σ_y (A + ρ B + ρ² C)(r_x, y) z(y)`.
+    pub inner_sumcheck: SumcheckProof<F>,
+    /// `w(r_y[1..])` at the point `r_y` of the inner sumcheck.
+    pub witness_evaluation: FieldElement<F>,
+    pub witness_opening: P::Proof,
+}
+
+pub struct Prover<F: IsField, P: IsMultilinearCommitmentScheme<F>> {
+    pcs: P,
+    phantom: core::marker::PhantomData<F>,
+}
+
+impl<F, P> Prover<F, P>
+where
+    F: IsField,
+    P: IsMultilinearCommitmentScheme<F>,
+    P::Commitment: AsBytes,
+    FieldElement<F>: ByteConversion,
+{
+    pub fn new(pcs: P) -> Self {
+        Self {
+            pcs,
+            phantom: core::marker::PhantomData,
+        }
+    }
+
+    pub fn prove(
+        &self,
+        r1cs: &R1CS<F>,
+        public_inputs: &[FieldElement<F>],
+        witness: &[FieldElement<F>],
+    ) -> SpartanProof<F, P> {
+        let half = r1cs.half_size();
+        let mut z = vec![FieldElement::zero(); 2 * half];
+        z[0] = FieldElement::one();
+        z[1..=public_inputs.len()].clone_from_slice(public_inputs);
+        z[half..half + witness.len()].clone_from_slice(witness);
+        let witness_evals = z[half..].to_vec();
+
+        let mut transcript = DefaultTranscript::<F>::default();
+        let witness_commitment = self.pcs.commit(&witness_evals);
+        transcript.append_bytes(&witness_commitment.as_bytes());
+        for input in public_inputs {
+            transcript.append_field_element(input);
+        }
+
+        // Outer sumcheck, reducing the constraints to a random row r_x
+        let tau: Vec<_> = (0..r1cs.num_row_vars())
+            .map(|_| transcript.sample_field_element())
+            .collect();
+        let num_rows = 1 << tau.len();
+        let z_at = |column| z[r1cs.padded_column(column)].clone();
+        let az = r1cs.a.mul_vector(num_rows, z_at);
+        let bz = r1cs.b.mul_vector(num_rows, z_at);
+        let cz = r1cs.c.mul_vector(num_rows, z_at);
+        let (outer_sumcheck, r_x) = sumcheck::prove(
+            vec![eq_evals(&tau), az.clone(), bz.clone(), cz.clone()],
+            3,
+            |v| &v[0] * (&v[1] * &v[2] - &v[3]),
+            &mut transcript,
+        );
+        let matrix_evaluations = [&az, &bz, &cz].map(|m| evaluate_mle(m, &r_x));
+        for evaluation in &matrix_evaluations {
+            transcript.append_field_element(evaluation);
+        }
+
+        // Inner sumcheck, reducing the three evaluations to a random column r_y
+        let rho = transcript.sample_field_element();
+        let eq_rx = eq_evals(&r_x);
+        let mut row = vec![FieldElement::zero(); 2 * half];
+        let mut coefficient = FieldElement::<F>::one();
+        for matrix in [&r1cs.a, &r1cs.b, &r1cs.c] {
+            for (i, j, value) in &matrix.entries {
+                row[r1cs.padded_column(*j)] += &coefficient * &eq_rx[*i] * value;
+            }
+            coefficient *= &rho;
+        }
+        let (inner_sumcheck, r_y) =
+            sumcheck::prove(vec![row, z], 2, |v| &v[0] * &v[1], &mut transcript);
+
+        let witness_evaluation = evaluate_mle(&witness_evals, &r_y[1..]);
+        let witness_opening = self.pcs.open(&witness_evals, &r_y[1..], &mut transcript);
+
+        SpartanProof {
+            witness_commitment,
+            outer_sumcheck,
+            matrix_evaluations,
+            inner_sumcheck,
+            witness_evaluation,
+            witness_opening,
+        }
+    }
+}
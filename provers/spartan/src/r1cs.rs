@@ -0,0 +1,162 @@
+use lambdaworks_math::field::{element::FieldElement, traits::IsField};
+
+/// A matrix given by its nonzero entries `(row, column, value)`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SparseMatrix<F: IsField> {
+    pub entries: Vec<(usize, usize, FieldElement<F>)>,
+}
+
+impl<F: IsField> SparseMatrix<F> {
+    pub fn new(entries: Vec<(usize, usize, FieldElement<F>)>) -> Self {
+        Self { entries }
+    }
+
+    /// Returns `M z` for a matrix with `num_rows` rows, reading `z[column]` through `z`.
+    pub fn mul_vector(
+        &self,
+        num_rows: usize,
+        z: impl Fn(usize) -> FieldElement<F>,
+    ) -> Vec<FieldElement<F>> {
+        let mut result = vec![FieldElement::zero(); num_rows];
+        for (row, column, value) in &self.entries {
+            result[*row] += value * z(*column);
+        }
+        result
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum R1CSError {
+    /// An entry of a matrix is out of the `num_constraints × num_variables` bounds.
+    EntryOutOfRange,
+}
+
+/// A rank-1 constraint system `(A z) ∘ (B z) = C z`, where `z = (1, x, w)` with `x` the public
+/// inputs and `w` the witness. Column `0` of the matrices is the constant, columns
+/// `1..=num_public_inputs` the public inputs and the following ones the witness.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct R1CS<F: IsField> {
+    pub a: SparseMatrix<F>,
+    pub b: SparseMatrix<F>,
+    pub c: SparseMatrix<F>,
+    pub num_constraints: usize,
+    pub num_public_inputs: usize,
+    pub num_witnesses: usize,
+}
+
+impl<F: IsField> R1CS<F> {
+    pub fn new(
+        a: SparseMatrix<F>,
+        b: SparseMatrix<F>,
+        c: SparseMatrix<F>,
+        num_constraints: usize,
+        num_public_inputs: usize,
+        num_witnesses: usize,
+    ) -> Result<Self, R1CSError> {
+        let num_variables = 1 + num_public_inputs + num_witnesses;
+        let in_range = |m: &SparseMatrix<F>| {
+            m.entries
+                .iter()
+                .all(|(row, column, _)| *row < num_constraints && *column < num_variables)
+        };
+        if !(in_range(&a) && in_range(&b) && in_range(&c)) {
+            return Err(R1CSError::EntryOutOfRange);
+        }
+        Ok(Self {
+            a,
+            b,
+            c,
+            num_constraints,
+            num_public_inputs,
+            num_witnesses,
+        })
+    }
+
+    pub fn is_satisfied(
+        &self,
+        public_inputs: &[FieldElement<F>],
+        witness: &[FieldElement<F>],
+    ) -> bool {
+        let z = |column: usize| match column {
+            0 => FieldElement::one(),
+            c if c <= self.num_public_inputs => public_inputs[c - 1].clone(),
+            c => witness[c - 1 - self.num_public_inputs].clone(),
+        };
+        let az = self.a.mul_vector(self.num_constraints, z);
+        let bz = self.b.mul_vector(self.num_constraints, z);
+        let cz = self.c.mul_vector(self.num_constraints, z);
+        public_inputs.len() == self.num_public_inputs
+            && witness.len() == self.num_witnesses
+            && (0..self.num_constraints).all(|i| &az[i] * &bz[i] == cz[i])
+    }
+
+    /// Number of variables of the multilinear extension of the constraint rows.
+    pub(crate) fn num_row_vars(&self) -> usize {
+        self.num_constraints.next_power_of_two().trailing_zeros() as usize
+    }
+
+    /// Size of each half of the padded `z`: the first half holds `(1, x)` and the second one
+    /// `w`, so that only the witness needs to be committed.
+    pub(crate) fn half_size(&self) -> usize {
+        (1 + self.num_public_inputs)
+            .next_power_of_two()
+            .max(self.num_witnesses.next_power_of_two())
+    }
+
+    /// Position of the variable in `column` inside the padded `z`.
+    pub(crate) fn padded_column(&self, column: usize) -> usize {
+        if column <= self.num_public_inputs {
+            column
+        } else {
+            self.half_size() + column - 1 - self.num_public_inputs
+        }
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use lambdaworks_math::field::fields::vesta_field::Vesta255PrimeField;
+
+    pub type FE = FieldElement<Vesta255PrimeField>;
+
+    /// Checks `x³ + x + 5 = y` with the public input `y` and the witness `(x, x², x³)`.
+    pub fn cubic_r1cs() -> R1CS<Vesta255PrimeField> {
+        let one = FE::one;
+        let a = SparseMatrix::new(vec![
+            (0, 2, one()),
+            (1, 3, one()),
+            (2, 0, FE::from(5)),
+            (2, 2, one()),
+            (2, 4, one()),
+        ]);
+        let b = SparseMatrix::new(vec![(0, 2, one()), (1, 2, one()), (2, 0, one())]);
+        let c = SparseMatrix::new(vec![(0, 3, one()), (1, 4, one()), (2, 1, one())]);
+        R1CS::new(a, b, c, 3, 1, 3).unwrap()
+    }
+
+    pub fn cubic_assignment(x: u64) -> (Vec<FE>, Vec<FE>) {
+        let x = FE::from(x);
+        let x2 = &x * &x;
+        let x3 = &x2 * &x;
+        (vec![&x3 + &x + FE::from(5)], vec![x, x2, x3])
+    }
+
+    #[test]
+    fn cubic_r1cs_is_satisfied_by_its_assignment() {
+        let r1cs = cubic_r1cs();
+        let (public_inputs, witness) = cubic_assignment(3);
+        assert_eq!(public_inputs[0], FE::from(35));
+        assert!(r1cs.is_satisfied(&public_inputs, &witness));
+        assert!(!r1cs.is_satisfied(&[FE::from(36)], &witness));
+    }
+
+    #[test]
+    fn entries_out_of_range_are_rejected() {
+        let m = || SparseMatrix::new(vec![(0, 5, FE::one())]);
+        assert!(matches!(
+            R1CS::new(m(), m(), m(), 1, 1, 3),
+            Err(R1CSError::EntryOutOfRange)
+        ));
+    }
+}
@@ -0,0 +1,161 @@
+use lambdaworks_crypto::{
+    commitments::traits::IsMultilinearCommitmentScheme,
+    fiat_shamir::{default_transcript::DefaultTranscript, is_transcript::IsTranscript},
+};
+use lambdaworks_gkr::{
+    sumcheck,
+    utils::{eq, eq_evals, evaluate_mle},
+};
+use lambdaworks_math::{
+    field::{element::FieldElement, traits::IsField},
+    traits::{AsBytes, ByteConversion},
+};
+
+use crate::{prover::SpartanProof, r1cs::R1CS};
+
+pub struct Verifier<F: IsField, P: IsMultilinearCommitmentScheme<F>> {
+    pcs: P,
+    phantom: core::marker::PhantomData<F>,
+}
+
+impl<F, P> Verifier<F, P>
+where
+    F: IsField,
+    P: IsMultilinearCommitmentScheme<F>,
+    P::Commitment: AsBytes,
+    FieldElement<F>: ByteConversion,
+{
+    pub fn new(pcs: P) -> Self {
+        Self {
+            pcs,
+            phantom: core::marker::PhantomData,
+        }
+    }
+
+    /// Checks the proof. The matrices are evaluated directly from their entries, in time linear
+    /// in their number of nonzero entries.
+    pub fn verify(
+        &self,
+        r1cs: &R1CS<F>,
+        public_inputs: &[FieldElement<F>],
+        proof: &SpartanProof<F, P>,
+    ) -> bool {
+        if public_inputs.len() != r1cs.num_public_inputs {
+            return false;
+        }
+        let half = r1cs.half_size();
+        let mut transcript = DefaultTranscript::<F>::default();
+        transcript.append_bytes(&proof.witness_commitment.as_bytes());
+        for input in public_inputs {
+            transcript.append_field_element(input);
+        }
+
+        let tau: Vec<_> = (0..r1cs.num_row_vars())
+            .map(|_| transcript.sample_field_element())
+            .collect();
+        let Ok((outer_claim, r_x)) = sumcheck::verify(
+            tau.len(),
+            3,
+            FieldElement::zero(),
+            &proof.outer_sumcheck,
+            &mut transcript,
+        ) else {
+            return false;
+        };
+        let [v_a, v_b, v_c] = &proof.matrix_evaluations;
+        if outer_claim != eq(&tau, &r_x) * (v_a * v_b - v_c) {
+            return false;
+        }
+        for evaluation in &proof.matrix_evaluations {
+            transcript.append_field_element(evaluation);
+        }
+
+        let rho = transcript.sample_field_element();
+        let claim = v_a + &rho * v_b + &rho * &rho * v_c;
+        let num_column_vars = (2 * half).trailing_zeros() as usize;
+        let Ok((inner_claim, r_y)) = sumcheck::verify(
+            num_column_vars,
+            2,
+            claim,
+            &proof.inner_sumcheck,
+            &mut transcript,
+        ) else {
+            return false;
+        };
+
+        // (A + ρ B + ρ² C)(r_x, r_y)
+        let (eq_rx, eq_ry) = (eq_evals(&r_x), eq_evals(&r_y));
+        let mut matrix_evaluation = FieldElement::<F>::zero();
+        let mut coefficient = FieldElement::<F>::one();
+        for matrix in [&r1cs.a, &r1cs.b, &r1cs.c] {
+            for (i, j, value) in &matrix.entries {
+                matrix_evaluation +=
+                    &coefficient * value * &eq_rx[*i] * &eq_ry[r1cs.padded_column(*j)];
+            }
+            coefficient *= &rho;
+        }
+
+        // z(r_y) = (1 - r_y[0]) (1, x)(r_y[1..]) + r_y[0] w(r_y[1..])
+        let mut io = vec![FieldElement::zero(); half];
+        io[0] = FieldElement::one();
+        io[1..=public_inputs.len()].clone_from_slice(public_inputs);
+        let io_evaluation = evaluate_mle(&io, &r_y[1..]);
+        let z_evaluation = &io_evaluation + &r_y[0] * (&proof.witness_evaluation - &io_evaluation);
+        if inner_claim != matrix_evaluation * z_evaluation {
+            return false;
+        }
+
+        self.pcs.verify(
+            &proof.witness_commitment,
+            &r_y[1..],
+            &proof.witness_evaluation,
+            &proof.witness_opening,
+            &mut transcript,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        prover::Prover,
+        r1cs::tests::{cubic_assignment, cubic_r1cs, FE},
+    };
+    use lambdaworks_crypto::commitments::ipa::{generators_from_transcript, InnerProductArgument};
+    use lambdaworks_math::{
+        elliptic_curve::short_weierstrass::{
+            curves::pallas::curve::PallasCurve, point::ShortWeierstrassProjectivePoint,
+        },
+        field::fields::vesta_field::Vesta255PrimeField,
+    };
+
+    type Ipa =
+        InnerProductArgument<Vesta255PrimeField, ShortWeierstrassProjectivePoint<PallasCurve>>;
+
+    fn test_ipa() -> Ipa {
+        let mut transcript = DefaultTranscript::new(b"spartan test generators");
+        let mut generators = generators_from_transcript::<PallasCurve, _>(&mut transcript, 5);
+        let u = generators.pop().unwrap();
+        InnerProductArgument::new(generators, u)
+    }
+
+    #[test]
+    fn satisfied_r1cs_is_proven() {
+        let r1cs = cubic_r1cs();
+        let (public_inputs, witness) = cubic_assignment(3);
+        let proof = Prover::new(test_ipa()).prove(&r1cs, &public_inputs, &witness);
+        let verifier = Verifier::new(test_ipa());
+        assert!(verifier.verify(&r1cs, &public_inputs, &proof));
+        assert!(!verifier.verify(&r1cs, &[FE::from(36)], &proof));
+    }
+
+    #[test]
+    fn unsatisfied_r1cs_is_rejected() {
+        let r1cs = cubic_r1cs();
+        let (public_inputs, mut witness) = cubic_assignment(3);
+        witness[2] += FE::one();
+        let proof = Prover::new(test_ipa()).prove(&r1cs, &public_inputs, &witness);
+        assert!(!Verifier::new(test_ipa()).verify(&r1cs, &public_inputs, &proof));
+    }
+}
@@ -0,0 +1,107 @@
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use lambdaworks_math::field::{
+    element::FieldElement, fields::fft_friendly::stark_252_prime_field::Stark252PrimeField,
+};
+use stark_platinum_prover::{
+    constraints::evaluator::ConstraintEvaluator,
+    domain::Domain,
+    examples::{
+        bit_flags::{self, BitFlagsAIR},
+        fibonacci_2_columns::{self, Fibonacci2ColsAIR},
+        simple_fibonacci::FibonacciPublicInputs,
+    },
+    proof::options::ProofOptions,
+    prover::evaluate_polynomial_on_lde_domain,
+    trace::{LDELayout, LDETraceTable, TraceTable},
+    traits::AIR,
+};
+
+type F = Stark252PrimeField;
+type FE = FieldElement<F>;
+
+const LAYOUTS: [LDELayout; 2] = [LDELayout::Natural, LDELayout::Coset];
+
+/// The LDE of the main trace `trace` of `air`, stored in `layout`.
+fn lde_trace<A: AIR<Field = F, FieldExtension = F>>(
+    air: &A,
+    trace: &TraceTable<F>,
+    layout: LDELayout,
+) -> LDETraceTable<F, F> {
+    let columns = trace
+        .compute_trace_polys::<F>()
+        .iter()
+        .map(|poly| {
+            evaluate_polynomial_on_lde_domain(
+                poly,
+                air.blowup_factor() as usize,
+                air.trace_length(),
+                &air.coset_offset(),
+            )
+            .unwrap()
+        })
+        .collect();
+    LDETraceTable::from_columns_with_layout(
+        columns,
+        Vec::new(),
+        A::STEP_SIZE,
+        air.blowup_factor() as usize,
+        layout,
+    )
+}
+
+/// Benchmarks the evaluation of the composition polynomial of `air` on the LDE of `trace`,
+/// with the LDE stored in each layout.
+fn bench_composition<A: AIR<Field = F, FieldExtension = F> + Send + Sync>(
+    c: &mut Criterion,
+    name: &str,
+    air: &A,
+    trace: &TraceTable<F>,
+) {
+    let mut group = c.benchmark_group(name);
+    group.sample_size(10);
+
+    let domain = Domain::new(air);
+    let evaluator = ConstraintEvaluator::new(air, &[]);
+    let transition_coefficients = vec![FE::from(3); air.num_transition_constraints()];
+    let boundary_coefficients = vec![FE::from(5); air.boundary_constraints(&[]).constraints.len()];
+
+    for layout in LAYOUTS {
+        let lde_trace = lde_trace(air, trace, layout);
+        group.bench_with_input(
+            BenchmarkId::new(format!("{layout:?}"), trace.n_rows()),
+            &lde_trace,
+            |bench, lde_trace| {
+                bench.iter(|| {
+                    evaluator.evaluate(
+                        air,
+                        lde_trace,
+                        &domain,
+                        &transition_coefficients,
+                        &boundary_coefficients,
+                        &[],
+                    )
+                });
+            },
+        );
+    }
+    group.finish();
+}
+
+fn composition_benchmarks(c: &mut Criterion) {
+    let options = ProofOptions::default_test_options();
+
+    let trace = bit_flags::bit_prefix_flag_trace(1 << 12);
+    let air = BitFlagsAIR::new(trace.n_rows(), &(), &options);
+    bench_composition(c, "Composition of 16-row steps", &air, &trace);
+
+    let trace = fibonacci_2_columns::compute_trace([FE::one(), FE::one()], 1 << 16);
+    let pub_inputs = FibonacciPublicInputs {
+        a0: FE::one(),
+        a1: FE::one(),
+    };
+    let air = Fibonacci2ColsAIR::new(trace.n_rows(), &pub_inputs, &options);
+    bench_composition(c, "Composition of 1-row steps", &air, &trace);
+}
+
+criterion_group!(benches, composition_benchmarks);
+criterion_main!(benches);
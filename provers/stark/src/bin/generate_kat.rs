@@ -0,0 +1,34 @@
+//! Writes the known-answer tests of the prover as JSON to the file given as the only argument,
+//! or to the standard output if there's none.
+
+use std::{
+    fs::File,
+    io::{self, BufWriter, Write},
+};
+
+use stark_platinum_prover::kat::known_answer_tests;
+
+fn main() {
+    let output: Box<dyn Write> = match std::env::args().nth(1) {
+        Some(path) => match File::create(&path) {
+            Ok(file) => Box::new(BufWriter::new(file)),
+            Err(e) => {
+                eprintln!("Error while creating {path}: {e}");
+                std::process::exit(1);
+            }
+        },
+        None => Box::new(io::stdout().lock()),
+    };
+
+    let tests = match known_answer_tests() {
+        Ok(tests) => tests,
+        Err(e) => {
+            eprintln!("Error while proving the known-answer tests: {e:?}");
+            std::process::exit(1);
+        }
+    };
+    if let Err(e) = serde_json::to_writer_pretty(output, &tests) {
+        eprintln!("Error while writing the known-answer tests: {e}");
+        std::process::exit(1);
+    }
+}
@@ -1,8 +1,30 @@
-use lambdaworks_crypto::merkle_tree::{
-    backends::types::{BatchKeccak256Backend, Keccak256Backend},
-    merkle::MerkleTree,
+use std::marker::PhantomData;
+
+use lambdaworks_crypto::{
+    hash::{
+        poseidon::{gpu::IsGpuPoseidon, starknet::PoseidonCairoStark252},
+        poseidon2::Poseidon2Stark252,
+    },
+    merkle_tree::{
+        backends::{
+            field_element_vector::FieldElementVectorBackend,
+            types::{BatchKeccak256Backend, Keccak256Backend},
+        },
+        merkle::MerkleTree,
+        proof::Proof,
+        traits::IsMerkleTreeBackend,
+    },
+};
+use lambdaworks_math::{
+    field::{element::FieldElement, traits::IsField},
+    traits::AsBytes,
 };
 
+#[cfg(feature = "wasm")]
+use wasm_bindgen::prelude::wasm_bindgen;
+
+use crate::Felt252;
+
 // Merkle Trees configuration
 
 // Security of both hashes should match
@@ -16,5 +38,262 @@ pub type FriMerkleTree<F> = MerkleTree<FriMerkleTreeBackend<F>>;
 pub const COMMITMENT_SIZE: usize = 32;
 pub type Commitment = [u8; COMMITMENT_SIZE];
 
-pub type BatchedMerkleTreeBackend<F> = BatchKeccak256Backend<F>;
-pub type BatchedMerkleTree<F> = MerkleTree<BatchedMerkleTreeBackend<F>>;
+/// The hash of the Merkle trees that commit to the trace, the composition polynomial and the
+/// layers of FRI, chosen with the `merkle_hash` of the
+/// [`ProofOptions`](crate::proof::options::ProofOptions) and recorded in the proof.
+///
+/// - `Keccak256`: the hash of Stone, cheap to verify on Ethereum.
+/// - `Blake3`: the fastest one to prove with.
+/// - `Poseidon`: Poseidon over the Stark252 field, as in Starknet, cheap to verify inside a
+///   STARK over that field. See [`BatchPoseidonBackend`].
+/// - `Poseidon2`: [`Poseidon2Stark252`], in the same trees as `Poseidon`. Cheaper to prove with
+///   than it.
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum MerkleHash {
+    #[default]
+    Keccak256,
+    Blake3,
+    Poseidon,
+    Poseidon2,
+}
+
+impl MerkleHash {
+    /// Verifies that `proof` opens the leaf `value` at `index` of a tree of this hash with root
+    /// `root`.
+    pub fn verify<F>(
+        self,
+        proof: &Proof<Commitment>,
+        root: &Commitment,
+        index: usize,
+        value: &Vec<FieldElement<F>>,
+    ) -> bool
+    where
+        F: IsField,
+        FieldElement<F>: AsBytes + Sync + Send,
+    {
+        match self {
+            MerkleHash::Keccak256 => proof.verify::<BatchKeccak256Backend<F>>(root, index, value),
+            MerkleHash::Blake3 => proof.verify::<BatchBlake3Backend<F>>(root, index, value),
+            MerkleHash::Poseidon => proof.verify::<BatchPoseidonBackend<F>>(root, index, value),
+            MerkleHash::Poseidon2 => proof.verify::<BatchPoseidon2Backend<F>>(root, index, value),
+        }
+    }
+}
+
+/// The backend of the Keccak256 trees built by the prover. With the `cuda` or `metal` features,
+/// their leaves are hashed and their inner nodes built in the GPU, falling back to the CPU if the
+/// device can't be used. The trees are the same as with [`BatchKeccak256Backend`], which
+/// verifies their proofs.
+#[cfg(feature = "cuda")]
+pub type ProverBatchKeccak256Backend<F> =
+    lambdaworks_crypto::merkle_tree::backends::gpu::cuda::CudaBatchKeccak256Backend<F>;
+#[cfg(all(feature = "metal", not(feature = "cuda")))]
+pub type ProverBatchKeccak256Backend<F> =
+    lambdaworks_crypto::merkle_tree::backends::gpu::metal::MetalBatchKeccak256Backend<F>;
+#[cfg(not(any(feature = "cuda", feature = "metal")))]
+pub type ProverBatchKeccak256Backend<F> = BatchKeccak256Backend<F>;
+
+pub type BatchBlake3Backend<F> = FieldElementVectorBackend<F, blake3::Hasher, 32>;
+
+/// The Poseidon trees of the Stark252 field built in the GPU with the `cuda` or `metal`
+/// features, which [`BatchPoseidonBackend`] builds its trees with.
+#[cfg(feature = "cuda")]
+type GpuBatchPoseidonTree<P> =
+    lambdaworks_crypto::merkle_tree::backends::gpu::cuda::CudaBatchPoseidonTree<P>;
+#[cfg(all(feature = "metal", not(feature = "cuda")))]
+type GpuBatchPoseidonTree<P> =
+    lambdaworks_crypto::merkle_tree::backends::gpu::metal::MetalBatchPoseidonTree<P>;
+
+/// A backend hashing vectors of field elements with Poseidon over the Stark252 field, or another
+/// hash `P` of that field such as [`Poseidon2Stark252`], with nodes encoded as the 32 big-endian
+/// bytes of its elements, so that trees over any field have the same commitments as the other
+/// hashes.
+///
+/// The bytes of each element of a leaf are split in chunks of 31 bytes, which are hashed as
+/// elements, so elements of fields of up to 248 bits take one. Parents are the hash of the two
+/// elements of their children.
+///
+/// With the `cuda` or `metal` features, the leaves are hashed and the inner nodes built in the
+/// GPU, falling back to the CPU if the device can't be used.
+#[derive(Clone)]
+pub struct BatchPoseidonBackend<F, P = PoseidonCairoStark252> {
+    _field: PhantomData<F>,
+    _hash: PhantomData<P>,
+}
+
+pub type BatchPoseidon2Backend<F> = BatchPoseidonBackend<F, Poseidon2Stark252>;
+
+impl<F, P> Default for BatchPoseidonBackend<F, P> {
+    fn default() -> Self {
+        Self {
+            _field: PhantomData,
+            _hash: PhantomData,
+        }
+    }
+}
+
+impl<F, P> BatchPoseidonBackend<F, P>
+where
+    F: IsField,
+    FieldElement<F>: AsBytes,
+{
+    /// The element of the Stark252 field of `bytes`, read in big-endian.
+    fn element_from_bytes(bytes: &[u8]) -> Felt252 {
+        let byte = Felt252::from(256);
+        bytes.iter().fold(Felt252::zero(), |acc, b| {
+            acc * byte + Felt252::from(*b as u64)
+        })
+    }
+
+    fn node(element: &Felt252) -> Commitment {
+        let mut node = [0; COMMITMENT_SIZE];
+        node.copy_from_slice(&element.to_bytes_be());
+        node
+    }
+
+    /// The elements of the Stark252 field that `leaf` is hashed as.
+    fn leaf_elements(leaf: &[FieldElement<F>]) -> Vec<Felt252> {
+        leaf.iter()
+            .flat_map(|element| {
+                element
+                    .as_bytes()
+                    .chunks(31)
+                    .map(Self::element_from_bytes)
+                    .collect::<Vec<_>>()
+            })
+            .collect()
+    }
+}
+
+impl<F, P> IsMerkleTreeBackend for BatchPoseidonBackend<F, P>
+where
+    F: IsField,
+    FieldElement<F>: AsBytes + Sync + Send,
+    P: IsGpuPoseidon,
+{
+    type Node = Commitment;
+    type Data = Vec<FieldElement<F>>;
+
+    fn hash_data(input: &Vec<FieldElement<F>>) -> Commitment {
+        Self::node(&P::hash_row(&Self::leaf_elements(input)))
+    }
+
+    #[cfg(any(feature = "cuda", feature = "metal"))]
+    fn hash_leaves(unhashed_leaves: &[Vec<FieldElement<F>>]) -> Vec<Commitment> {
+        let rows: Vec<Vec<Felt252>> = unhashed_leaves
+            .iter()
+            .map(|leaf| Self::leaf_elements(leaf))
+            .collect();
+        GpuBatchPoseidonTree::<P>::hash_leaves(&rows)
+            .iter()
+            .map(Self::node)
+            .collect()
+    }
+
+    fn hash_new_parent(left: &Commitment, right: &Commitment) -> Commitment {
+        Self::node(&P::hash_pair(
+            &Self::element_from_bytes(left),
+            &Self::element_from_bytes(right),
+        ))
+    }
+
+    #[cfg(any(feature = "cuda", feature = "metal"))]
+    fn build_inner_nodes(nodes: &mut [Commitment], leaves_len: usize) {
+        let mut elements: Vec<Felt252> = nodes
+            .iter()
+            .map(|node| Self::element_from_bytes(node))
+            .collect();
+        GpuBatchPoseidonTree::<P>::build_inner_nodes(&mut elements, leaves_len);
+        for (node, element) in nodes.iter_mut().zip(&elements[..leaves_len - 1]) {
+            *node = Self::node(element);
+        }
+    }
+}
+
+/// A Merkle tree whose leaves are vectors of field elements, hashed with a [`MerkleHash`].
+#[derive(Clone)]
+pub enum BatchedMerkleTree<F>
+where
+    F: IsField,
+    FieldElement<F>: AsBytes + Sync + Send,
+{
+    Keccak256(MerkleTree<ProverBatchKeccak256Backend<F>>),
+    Blake3(MerkleTree<BatchBlake3Backend<F>>),
+    Poseidon(MerkleTree<BatchPoseidonBackend<F>>),
+    Poseidon2(MerkleTree<BatchPoseidon2Backend<F>>),
+}
+
+impl<F> BatchedMerkleTree<F>
+where
+    F: IsField,
+    FieldElement<F>: AsBytes + Sync + Send,
+{
+    pub fn build(hash: MerkleHash, leaves: &[Vec<FieldElement<F>>]) -> Option<Self> {
+        Some(match hash {
+            MerkleHash::Keccak256 => Self::Keccak256(MerkleTree::build(leaves)?),
+            MerkleHash::Blake3 => Self::Blake3(MerkleTree::build(leaves)?),
+            MerkleHash::Poseidon => Self::Poseidon(MerkleTree::build(leaves)?),
+            MerkleHash::Poseidon2 => Self::Poseidon2(MerkleTree::build(leaves)?),
+        })
+    }
+
+    /// The hashes of the leaves `leaves` in a tree with hash `hash`.
+    pub fn hash_leaves(hash: MerkleHash, leaves: &[Vec<FieldElement<F>>]) -> Vec<Commitment> {
+        match hash {
+            MerkleHash::Keccak256 => ProverBatchKeccak256Backend::<F>::hash_leaves(leaves),
+            MerkleHash::Blake3 => BatchBlake3Backend::<F>::hash_leaves(leaves),
+            MerkleHash::Poseidon => BatchPoseidonBackend::<F>::hash_leaves(leaves),
+            MerkleHash::Poseidon2 => BatchPoseidon2Backend::<F>::hash_leaves(leaves),
+        }
+    }
+
+    /// Builds the tree with hash `hash` whose leaves have the hashes `hashed_leaves`, computed
+    /// with [`BatchedMerkleTree::hash_leaves`].
+    pub fn build_from_hashed_leaves(
+        hash: MerkleHash,
+        hashed_leaves: Vec<Commitment>,
+    ) -> Option<Self> {
+        Some(match hash {
+            MerkleHash::Keccak256 => {
+                Self::Keccak256(MerkleTree::build_from_hashed_leaves(hashed_leaves)?)
+            }
+            MerkleHash::Blake3 => {
+                Self::Blake3(MerkleTree::build_from_hashed_leaves(hashed_leaves)?)
+            }
+            MerkleHash::Poseidon => {
+                Self::Poseidon(MerkleTree::build_from_hashed_leaves(hashed_leaves)?)
+            }
+            MerkleHash::Poseidon2 => {
+                Self::Poseidon2(MerkleTree::build_from_hashed_leaves(hashed_leaves)?)
+            }
+        })
+    }
+
+    pub fn merkle_hash(&self) -> MerkleHash {
+        match self {
+            Self::Keccak256(_) => MerkleHash::Keccak256,
+            Self::Blake3(_) => MerkleHash::Blake3,
+            Self::Poseidon(_) => MerkleHash::Poseidon,
+            Self::Poseidon2(_) => MerkleHash::Poseidon2,
+        }
+    }
+
+    pub fn root(&self) -> Commitment {
+        match self {
+            Self::Keccak256(tree) => tree.root,
+            Self::Blake3(tree) => tree.root,
+            Self::Poseidon(tree) => tree.root,
+            Self::Poseidon2(tree) => tree.root,
+        }
+    }
+
+    pub fn get_proof_by_pos(&self, pos: usize) -> Option<Proof<Commitment>> {
+        match self {
+            Self::Keccak256(tree) => tree.get_proof_by_pos(pos),
+            Self::Blake3(tree) => tree.get_proof_by_pos(pos),
+            Self::Poseidon(tree) => tree.get_proof_by_pos(pos),
+            Self::Poseidon2(tree) => tree.get_proof_by_pos(pos),
+        }
+    }
+}
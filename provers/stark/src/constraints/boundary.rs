@@ -36,6 +36,28 @@ impl<F: IsField> BoundaryConstraint<F> {
         }
     }
 
+    /// Constraint on the row `rows_from_end` rows before the last one of a trace with
+    /// `trace_length` rows, so that `0` is the last row.
+    pub fn new_main_from_end(
+        col: usize,
+        rows_from_end: usize,
+        trace_length: usize,
+        value: FieldElement<F>,
+    ) -> Self {
+        Self::new_main(col, row_from_end(rows_from_end, trace_length), value)
+    }
+
+    /// Constraint on the row `rows_from_end` rows before the last one of an auxiliary trace
+    /// with `trace_length` rows, so that `0` is the last row.
+    pub fn new_aux_from_end(
+        col: usize,
+        rows_from_end: usize,
+        trace_length: usize,
+        value: FieldElement<F>,
+    ) -> Self {
+        Self::new_aux(col, row_from_end(rows_from_end, trace_length), value)
+    }
+
     /// Used for creating boundary constraints for a trace with only one column
     pub fn new_simple_main(step: usize, value: FieldElement<F>) -> Self {
         Self {
@@ -57,6 +79,23 @@ impl<F: IsField> BoundaryConstraint<F> {
     }
 }
 
+/// The index of the row `rows_from_end` rows before the last one of a trace with `trace_length`
+/// rows.
+fn row_from_end(rows_from_end: usize, trace_length: usize) -> usize {
+    assert!(
+        rows_from_end < trace_length,
+        "row {rows_from_end} from the end is out of a trace of {trace_length} rows"
+    );
+    trace_length - 1 - rows_from_end
+}
+
+impl<F: IsField> From<(usize, usize, FieldElement<F>)> for BoundaryConstraint<F> {
+    /// The constraint of a `(col, step, value)` triple on the main trace.
+    fn from((col, step, value): (usize, usize, FieldElement<F>)) -> Self {
+        Self::new_main(col, step, value)
+    }
+}
+
 /// Data structure that stores all the boundary constraints that must
 /// hold for the execution trace
 #[derive(Default, Debug)]
@@ -77,6 +116,10 @@ impl<F: IsField> BoundaryConstraints<F> {
         Self { constraints }
     }
 
+    pub fn push(&mut self, constraint: BoundaryConstraint<F>) {
+        self.constraints.push(constraint);
+    }
+
     /// Returns all the steps where boundary conditions exist for the given column
     pub fn steps(&self, col: usize) -> Vec<usize> {
         self.constraints
@@ -159,6 +202,20 @@ impl<F: IsField> BoundaryConstraints<F> {
     }
 }
 
+/// Collects constraints, or `(col, step, value)` triples on the main trace, so that batches of
+/// them can be generated with iterators.
+impl<F: IsField, C: Into<BoundaryConstraint<F>>> FromIterator<C> for BoundaryConstraints<F> {
+    fn from_iter<I: IntoIterator<Item = C>>(iter: I) -> Self {
+        Self::from_constraints(iter.into_iter().map(Into::into).collect())
+    }
+}
+
+impl<F: IsField, C: Into<BoundaryConstraint<F>>> Extend<C> for BoundaryConstraints<F> {
+    fn extend<I: IntoIterator<Item = C>>(&mut self, iter: I) {
+        self.constraints.extend(iter.into_iter().map(Into::into));
+    }
+}
+
 #[cfg(test)]
 mod test {
     use lambdaworks_math::field::{
@@ -197,4 +254,48 @@ mod test {
 
         assert_eq!(expected_zerofier, zerofier);
     }
+
+    #[test]
+    fn constraints_from_the_end_are_on_the_last_rows() {
+        let one = FieldElement::<PrimeField>::one();
+
+        let last = BoundaryConstraint::new_main_from_end(1, 0, 16, one);
+        let second_to_last = BoundaryConstraint::new_aux_from_end(0, 1, 16, one);
+
+        assert_eq!((last.col, last.step, last.is_aux), (1, 15, false));
+        assert_eq!(
+            (
+                second_to_last.col,
+                second_to_last.step,
+                second_to_last.is_aux
+            ),
+            (0, 14, true)
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn constraints_from_the_end_must_be_in_the_trace() {
+        BoundaryConstraint::new_main_from_end(0, 16, 16, FieldElement::<PrimeField>::one());
+    }
+
+    #[test]
+    fn batches_of_triples_can_be_collected_and_extended() {
+        let values: Vec<FieldElement<PrimeField>> = (1..4u64).map(FieldElement::from).collect();
+
+        let mut constraints: BoundaryConstraints<PrimeField> = values
+            .iter()
+            .enumerate()
+            .map(|(col, value)| (col, 0, *value))
+            .collect();
+        constraints.extend(
+            values
+                .iter()
+                .enumerate()
+                .map(|(col, value)| BoundaryConstraint::new_main_from_end(col, 0, 8, *value)),
+        );
+
+        assert_eq!(constraints.steps(2), vec![0, 7]);
+        assert_eq!(constraints.values(&[1]), vec![vec![values[1]; 2]]);
+    }
 }
@@ -1,4 +1,6 @@
 use super::boundary::BoundaryConstraints;
+#[cfg(feature = "wgpu")]
+use super::gpu::wgpu::evaluate_transitions_wgpu;
 #[cfg(all(debug_assertions, not(feature = "parallel")))]
 use crate::debug::check_boundary_polys_divisibility;
 use crate::domain::Domain;
@@ -6,16 +8,18 @@ use crate::trace::LDETraceTable;
 use crate::traits::AIR;
 use crate::{frame::Frame, prover::evaluate_polynomial_on_lde_domain};
 use itertools::Itertools;
+#[cfg(feature = "instruments")]
+use lambdaworks_math::environment::Stopwatch;
 #[cfg(all(debug_assertions, not(feature = "parallel")))]
 use lambdaworks_math::polynomial::Polynomial;
 use lambdaworks_math::{fft::errors::FFTError, field::element::FieldElement, traits::AsBytes};
+#[cfg(feature = "wgpu")]
+use log::info;
 #[cfg(feature = "parallel")]
 use rayon::{
     iter::IndexedParallelIterator,
     prelude::{IntoParallelIterator, ParallelIterator},
 };
-#[cfg(feature = "instruments")]
-use std::time::Instant;
 
 pub struct ConstraintEvaluator<A: AIR> {
     boundary_constraints: BoundaryConstraints<A::FieldExtension>,
@@ -29,7 +33,7 @@ impl<A: AIR> ConstraintEvaluator<A> {
         }
     }
 
-    pub(crate) fn evaluate(
+    pub fn evaluate(
         &self,
         air: &A,
         lde_trace: &LDETraceTable<A::Field, A::FieldExtension>,
@@ -65,7 +69,7 @@ impl<A: AIR> ConstraintEvaluator<A> {
         let boundary_polys: Vec<Polynomial<FieldElement<A::Field>>> = Vec::new();
 
         #[cfg(feature = "instruments")]
-        let timer = Instant::now();
+        let timer = Stopwatch::start();
 
         let lde_periodic_columns = air
             .get_periodic_column_polynomials()
@@ -88,7 +92,7 @@ impl<A: AIR> ConstraintEvaluator<A> {
         );
 
         #[cfg(feature = "instruments")]
-        let timer = Instant::now();
+        let timer = Stopwatch::start();
 
         let boundary_polys_evaluations = boundary_constraints
             .constraints
@@ -115,7 +119,7 @@ impl<A: AIR> ConstraintEvaluator<A> {
         #[cfg(feature = "instruments")]
         println!("     Created boundary polynomials: {:#?}", timer.elapsed());
         #[cfg(feature = "instruments")]
-        let timer = Instant::now();
+        let timer = Stopwatch::start();
 
         #[cfg(feature = "parallel")]
         let boundary_eval_iter = (0..domain.lde_roots_of_unity_coset.len()).into_par_iter();
@@ -151,7 +155,7 @@ impl<A: AIR> ConstraintEvaluator<A> {
         let mut transition_evaluations = Vec::new();
 
         #[cfg(feature = "instruments")]
-        let timer = Instant::now();
+        let timer = Stopwatch::start();
         let zerofiers_evals = air.transition_zerofier_evaluations(domain);
         #[cfg(feature = "instruments")]
         println!(
@@ -159,12 +163,37 @@ impl<A: AIR> ConstraintEvaluator<A> {
             timer.elapsed()
         );
 
+        // AIRs with a constraint program have their transitions evaluated in the GPU, over the
+        // whole LDE domain at once.
+        #[cfg(feature = "wgpu")]
+        if let Some(program) = air.transition_program() {
+            debug_assert_eq!(program.check(air.num_transition_constraints()), Ok(()));
+            match evaluate_transitions_wgpu(
+                &program,
+                lde_trace,
+                &lde_periodic_columns,
+                rap_challenges,
+                &zerofiers_evals,
+                transition_coefficients,
+                &air.context().transition_offsets,
+            ) {
+                Ok(evaluations_t) => {
+                    return evaluations_t
+                        .into_iter()
+                        .zip(boundary_evaluation)
+                        .map(|(transition, boundary)| transition + boundary)
+                        .collect();
+                }
+                Err(err) => info!("Evaluating transitions in the CPU: {err}"),
+            }
+        }
+
         // Iterate over all LDE domain and compute the part of the composition polynomial
         // related to the transition constraints and add it to the already computed part of the
         // boundary constraints.
 
         #[cfg(feature = "instruments")]
-        let timer = Instant::now();
+        let timer = Stopwatch::start();
         let evaluations_t_iter = 0..domain.lde_roots_of_unity_coset.len();
 
         #[cfg(feature = "parallel")]
@@ -0,0 +1,2 @@
+#[cfg(feature = "wgpu")]
+pub mod wgpu;
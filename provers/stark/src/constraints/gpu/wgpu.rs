@@ -0,0 +1,179 @@
+use crate::{
+    constraints::program::{ConstraintProgram, Instruction},
+    trace::{LDELayout, LDETraceTable},
+};
+use lambdaworks_gpu::wgpu::abstractions::{errors::WgpuError, state::WgpuState};
+use lambdaworks_math::{
+    fft::gpu::wgpu::polynomial::is_wgpu_field,
+    field::{
+        element::FieldElement,
+        traits::{IsFFTField, IsField, IsSubFieldOf},
+    },
+    gpu::wgpu::TRANSITION_PROGRAM_STARK256_SHADER,
+};
+
+// Opcodes of `transition_program.wgsl`
+const OP_MAIN: u32 = 0;
+const OP_AUX: u32 = 1;
+const OP_PERIODIC: u32 = 2;
+const OP_ELEMENT: u32 = 3;
+const OP_ADD: u32 = 4;
+const OP_SUB: u32 = 5;
+const OP_MUL: u32 = 6;
+const OP_STORE: u32 = 7;
+
+/// Returns the transition part of the composition polynomial over the LDE domain,
+/// `sum_k zerofier_k(x) * coefficient_k * constraint_k(x)`, with the constraints computed by
+/// interpreting `program` in WebGPU, one thread per row of `lde_trace`.
+///
+/// `lde_periodic_columns` and `zerofiers_evals` are the ones of the constraint evaluator, and
+/// `transition_offsets` the ones of the AIR's context. Only the Stark252 field is supported, and
+/// not its extensions.
+pub fn evaluate_transitions_wgpu<F, E>(
+    program: &ConstraintProgram<E>,
+    lde_trace: &LDETraceTable<F, E>,
+    lde_periodic_columns: &[Vec<FieldElement<F>>],
+    rap_challenges: &[FieldElement<E>],
+    zerofiers_evals: &[Vec<FieldElement<F>>],
+    transition_coefficients: &[FieldElement<E>],
+    transition_offsets: &[usize],
+) -> Result<Vec<FieldElement<E>>, WgpuError>
+where
+    F: IsFFTField + IsSubFieldOf<E>,
+    E: IsField,
+{
+    if !is_wgpu_field::<F, E>() {
+        return Err(WgpuError::FunctionError(format!(
+            "constraint programs over {}",
+            core::any::type_name::<E>()
+        )));
+    }
+    if lde_trace.layout != LDELayout::Natural {
+        return Err(WgpuError::FunctionError(format!(
+            "LDE traces in the {:?} layout",
+            lde_trace.layout
+        )));
+    }
+    if let Err(err) = program.check(zerofiers_evals.len()) {
+        return Err(WgpuError::FunctionError(format!(
+            "constraint programs with {err:?}"
+        )));
+    }
+
+    let lde_len = lde_trace.num_rows();
+    let main_width = lde_trace.main_table.width;
+    let aux_width = lde_trace.aux_table.width;
+
+    let mut program_words = encode_program(program, lde_trace, transition_offsets);
+    let mut aux_trace = lde_trace.aux_table.data.clone();
+    let mut periodic: Vec<_> = lde_periodic_columns.iter().flatten().cloned().collect();
+    let mut zerofiers: Vec<_> = zerofiers_evals.iter().flatten().cloned().collect();
+    let mut elements: Vec<_> = program
+        .constants()
+        .iter()
+        .chain(rap_challenges)
+        .chain(transition_coefficients)
+        .cloned()
+        .collect();
+    let coefficients_begin = program.constants().len() + rap_challenges.len();
+
+    let mut params = vec![
+        lde_len as u32,
+        main_width as u32,
+        aux_width as u32,
+        coefficients_begin as u32,
+        program.instructions().len() as u32,
+    ];
+    let mut zerofier_begin = 0;
+    for evals in zerofiers_evals {
+        params.extend([zerofier_begin as u32, evals.len() as u32]);
+        zerofier_begin += evals.len();
+    }
+
+    // Bound buffers can't be empty
+    if program_words.is_empty() {
+        program_words.extend([0; 3]);
+    }
+    if aux_trace.is_empty() {
+        aux_trace.push(FieldElement::zero());
+    }
+    if periodic.is_empty() {
+        periodic.push(FieldElement::zero());
+    }
+    if zerofiers.is_empty() {
+        zerofiers.push(FieldElement::zero());
+    }
+    if elements.is_empty() {
+        elements.push(FieldElement::zero());
+    }
+
+    let state = WgpuState::new()?;
+    let module = state.load_shader(TRANSITION_PROGRAM_STARK256_SHADER)?;
+    let pipeline = state.setup_pipeline(&module, "evaluate_transitions")?;
+
+    let main_buffer = state.alloc_buffer_data(&lde_trace.main_table.data);
+    let aux_buffer = state.alloc_buffer_data(&aux_trace);
+    let periodic_buffer = state.alloc_buffer_data(&periodic);
+    let elements_buffer = state.alloc_buffer_data(&elements);
+    let zerofiers_buffer = state.alloc_buffer_data(&zerofiers);
+    let params_buffer = state.alloc_buffer_data(&params);
+    let program_buffer = state.alloc_buffer_data(&program_words);
+    let result_buffer = state.alloc_buffer::<FieldElement<E>>(lde_len);
+
+    let mut encoder = state.setup_command();
+    state.dispatch(
+        &mut encoder,
+        &pipeline,
+        &[
+            (0, &main_buffer),
+            (1, &aux_buffer),
+            (2, &periodic_buffer),
+            (3, &elements_buffer),
+            (4, &zerofiers_buffer),
+            (5, &params_buffer),
+            (6, &program_buffer),
+            (7, &result_buffer),
+        ],
+        lde_len,
+    );
+    state.submit(encoder);
+
+    let result = state.retrieve_contents::<E::BaseType>(&result_buffer)?;
+    Ok(result.into_iter().map(FieldElement::from_raw).collect())
+}
+
+/// Encodes each instruction as its opcode and two operands. Reads of the frame become reads
+/// of the LDE row shifted by the rows between the frame's start and the element, and challenges
+/// are read after the constants.
+fn encode_program<F, E>(
+    program: &ConstraintProgram<E>,
+    lde_trace: &LDETraceTable<F, E>,
+    transition_offsets: &[usize],
+) -> Vec<u32>
+where
+    F: IsSubFieldOf<E>,
+    E: IsField,
+{
+    let lde_len = lde_trace.num_rows();
+    let shift = |step: usize, row: usize| {
+        ((transition_offsets[step] * lde_trace.lde_step_size + row * lde_trace.blowup_factor)
+            % lde_len) as u32
+    };
+    let challenges_begin = program.constants().len();
+
+    program
+        .instructions()
+        .iter()
+        .flat_map(|instruction| match *instruction {
+            Instruction::Main { step, row, col } => [OP_MAIN, shift(step, row), col as u32],
+            Instruction::Aux { step, row, col } => [OP_AUX, shift(step, row), col as u32],
+            Instruction::Periodic(index) => [OP_PERIODIC, index as u32, 0],
+            Instruction::Challenge(index) => [OP_ELEMENT, (challenges_begin + index) as u32, 0],
+            Instruction::Constant(index) => [OP_ELEMENT, index as u32, 0],
+            Instruction::Add => [OP_ADD, 0, 0],
+            Instruction::Sub => [OP_SUB, 0, 0],
+            Instruction::Mul => [OP_MUL, 0, 0],
+            Instruction::Store(constraint_idx) => [OP_STORE, constraint_idx as u32, 0],
+        })
+        .collect()
+}
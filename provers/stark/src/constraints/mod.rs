@@ -1,3 +1,5 @@
 pub mod boundary;
 pub mod evaluator;
+pub mod gpu;
+pub mod program;
 pub mod transition;
@@ -0,0 +1,318 @@
+use crate::frame::Frame;
+use lambdaworks_math::field::{
+    element::FieldElement,
+    traits::{IsField, IsSubFieldOf},
+};
+
+/// Maximum number of elements in the stack of a [`ConstraintProgram`]. GPU kernels keep the
+/// stack in registers, so it has a fixed size.
+pub const MAX_STACK_DEPTH: usize = 16;
+
+/// An instruction of a [`ConstraintProgram`]. Instructions that read a value push it to the
+/// stack, and the arithmetic ones replace the two elements at the top of the stack with their
+/// result.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Instruction {
+    /// Pushes the element of column `col` of the main trace, in row `row` of step `step` of
+    /// the frame.
+    Main { step: usize, row: usize, col: usize },
+    /// Pushes the element of column `col` of the auxiliary trace, in row `row` of step `step`
+    /// of the frame.
+    Aux { step: usize, row: usize, col: usize },
+    /// Pushes the value of a periodic column.
+    Periodic(usize),
+    /// Pushes a RAP challenge.
+    Challenge(usize),
+    /// Pushes a constant of the program.
+    Constant(usize),
+    /// Pops `b` and `a`, and pushes `a + b`.
+    Add,
+    /// Pops `b` and `a`, and pushes `a - b`.
+    Sub,
+    /// Pops `b` and `a`, and pushes `a * b`.
+    Mul,
+    /// Pops the evaluation of the transition constraint with the given index.
+    Store(usize),
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum ProgramError {
+    /// An instruction pops more elements than the stack has.
+    StackUnderflow(usize),
+    /// An instruction pushes more than [`MAX_STACK_DEPTH`] elements.
+    StackOverflow(usize),
+    /// The stack isn't empty after the last instruction. Holds the number of elements left.
+    UnusedValues(usize),
+    /// An instruction stores a constraint index out of range.
+    ConstraintOutOfRange(usize),
+    /// An instruction reads a constant that the program doesn't have.
+    ConstantOutOfRange(usize),
+}
+
+/// The transition constraints of an AIR written as a sequence of stack-machine
+/// [`Instruction`]s, so that they can be interpreted by a GPU kernel over the whole LDE
+/// domain. It must compute the same evaluations as
+/// [`AIR::compute_transition_prover`](crate::traits::AIR::compute_transition_prover), with
+/// the constraints it doesn't store being zero.
+///
+/// Programs are built by pushing instructions in postfix order. For example,
+/// `a2 - a1 - a0` over the first column of three consecutive steps is:
+///
+/// ```
+/// # use lambdaworks_math::field::fields::fft_friendly::stark_252_prime_field::Stark252PrimeField;
+/// # use stark_platinum_prover::constraints::program::ConstraintProgram;
+/// let mut program = ConstraintProgram::<Stark252PrimeField>::new();
+/// program.main(2, 0, 0).main(1, 0, 0).sub().main(0, 0, 0).sub().store(0);
+/// assert!(program.check(1).is_ok());
+/// ```
+#[derive(Clone, Debug)]
+pub struct ConstraintProgram<E: IsField> {
+    instructions: Vec<Instruction>,
+    constants: Vec<FieldElement<E>>,
+}
+
+impl<E: IsField> Default for ConstraintProgram<E> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<E: IsField> ConstraintProgram<E> {
+    pub fn new() -> Self {
+        Self {
+            instructions: Vec::new(),
+            constants: Vec::new(),
+        }
+    }
+
+    pub fn instructions(&self) -> &[Instruction] {
+        &self.instructions
+    }
+
+    pub fn constants(&self) -> &[FieldElement<E>] {
+        &self.constants
+    }
+
+    pub fn push(&mut self, instruction: Instruction) -> &mut Self {
+        self.instructions.push(instruction);
+        self
+    }
+
+    pub fn main(&mut self, step: usize, row: usize, col: usize) -> &mut Self {
+        self.push(Instruction::Main { step, row, col })
+    }
+
+    pub fn aux(&mut self, step: usize, row: usize, col: usize) -> &mut Self {
+        self.push(Instruction::Aux { step, row, col })
+    }
+
+    pub fn periodic(&mut self, index: usize) -> &mut Self {
+        self.push(Instruction::Periodic(index))
+    }
+
+    pub fn challenge(&mut self, index: usize) -> &mut Self {
+        self.push(Instruction::Challenge(index))
+    }
+
+    /// Pushes `value`, which is added to the constants of the program.
+    pub fn constant(&mut self, value: FieldElement<E>) -> &mut Self {
+        self.constants.push(value);
+        self.push(Instruction::Constant(self.constants.len() - 1))
+    }
+
+    pub fn add(&mut self) -> &mut Self {
+        self.push(Instruction::Add)
+    }
+
+    pub fn sub(&mut self) -> &mut Self {
+        self.push(Instruction::Sub)
+    }
+
+    pub fn mul(&mut self) -> &mut Self {
+        self.push(Instruction::Mul)
+    }
+
+    pub fn store(&mut self, constraint_idx: usize) -> &mut Self {
+        self.push(Instruction::Store(constraint_idx))
+    }
+
+    /// Checks that the program can run for an AIR with `num_constraints` transition
+    /// constraints: the stack never underflows nor grows past [`MAX_STACK_DEPTH`], it's empty
+    /// at the end, and constants and constraints are in range. Errors hold the index of the
+    /// first wrong instruction.
+    pub fn check(&self, num_constraints: usize) -> Result<(), ProgramError> {
+        let mut depth = 0;
+        for (pc, instruction) in self.instructions.iter().enumerate() {
+            match instruction {
+                Instruction::Main { .. }
+                | Instruction::Aux { .. }
+                | Instruction::Periodic(_)
+                | Instruction::Challenge(_) => depth += 1,
+                Instruction::Constant(index) => {
+                    if *index >= self.constants.len() {
+                        return Err(ProgramError::ConstantOutOfRange(pc));
+                    }
+                    depth += 1;
+                }
+                Instruction::Add | Instruction::Sub | Instruction::Mul => {
+                    if depth < 2 {
+                        return Err(ProgramError::StackUnderflow(pc));
+                    }
+                    depth -= 1;
+                }
+                Instruction::Store(constraint_idx) => {
+                    if depth < 1 {
+                        return Err(ProgramError::StackUnderflow(pc));
+                    }
+                    if *constraint_idx >= num_constraints {
+                        return Err(ProgramError::ConstraintOutOfRange(pc));
+                    }
+                    depth -= 1;
+                }
+            }
+            if depth > MAX_STACK_DEPTH {
+                return Err(ProgramError::StackOverflow(pc));
+            }
+        }
+        if depth != 0 {
+            return Err(ProgramError::UnusedValues(depth));
+        }
+        Ok(())
+    }
+
+    /// Interprets the program over a `frame`, writing the evaluation of each stored constraint
+    /// in `transition_evaluations`. This is the reference for the GPU kernels. The program
+    /// must pass [`Self::check`].
+    pub fn evaluate<F: IsSubFieldOf<E>>(
+        &self,
+        frame: &Frame<F, E>,
+        transition_evaluations: &mut [FieldElement<E>],
+        periodic_values: &[FieldElement<F>],
+        rap_challenges: &[FieldElement<E>],
+    ) {
+        let mut stack: Vec<FieldElement<E>> = Vec::with_capacity(MAX_STACK_DEPTH);
+        for instruction in &self.instructions {
+            match *instruction {
+                Instruction::Main { step, row, col } => stack.push(
+                    frame
+                        .get_evaluation_step(step)
+                        .get_main_evaluation_element(row, col)
+                        .clone()
+                        .to_extension(),
+                ),
+                Instruction::Aux { step, row, col } => stack.push(
+                    frame
+                        .get_evaluation_step(step)
+                        .get_aux_evaluation_element(row, col)
+                        .clone(),
+                ),
+                Instruction::Periodic(index) => {
+                    stack.push(periodic_values[index].clone().to_extension())
+                }
+                Instruction::Challenge(index) => stack.push(rap_challenges[index].clone()),
+                Instruction::Constant(index) => stack.push(self.constants[index].clone()),
+                Instruction::Add | Instruction::Sub | Instruction::Mul => {
+                    let b = stack.pop().expect("program should be checked");
+                    let a = stack.pop().expect("program should be checked");
+                    stack.push(match instruction {
+                        Instruction::Add => a + b,
+                        Instruction::Sub => a - b,
+                        _ => a * b,
+                    });
+                }
+                Instruction::Store(constraint_idx) => {
+                    transition_evaluations[constraint_idx] =
+                        stack.pop().expect("program should be checked");
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::table::{Table, TableView};
+    use lambdaworks_math::field::fields::fft_friendly::stark_252_prime_field::Stark252PrimeField;
+
+    type F = Stark252PrimeField;
+    type FE = FieldElement<F>;
+
+    #[test]
+    fn program_evaluates_a_fibonacci_rap_frame() {
+        // z_{i+1} * (b_i + gamma) - z_i * (a_i + gamma), and a_{i+1} - a_i - b_i * 3
+        let mut program = ConstraintProgram::<F>::new();
+        program
+            .aux(1, 0, 0)
+            .main(0, 0, 1)
+            .challenge(0)
+            .add()
+            .mul()
+            .aux(0, 0, 0)
+            .main(0, 0, 0)
+            .challenge(0)
+            .add()
+            .mul()
+            .sub()
+            .store(1);
+        program
+            .main(1, 0, 0)
+            .main(0, 0, 0)
+            .sub()
+            .main(0, 0, 1)
+            .constant(FE::from(3))
+            .mul()
+            .sub()
+            .store(0);
+        assert_eq!(program.check(2), Ok(()));
+
+        // Rows are (a, b, z) = (1, 2, 3) and (4, 5, 6)
+        let table = Table::new((1..=6).map(FE::from).collect(), 3);
+        let frame = Frame::new(
+            (0..2)
+                .map(|row| {
+                    let row = table.get_row(row);
+                    TableView::new(vec![&row[..2]], vec![&row[2..]])
+                })
+                .collect(),
+        );
+        let gamma = FE::from(10);
+        let mut evaluations = vec![FE::zero(); 2];
+        program.evaluate(&frame, &mut evaluations, &[], &[gamma]);
+
+        assert_eq!(
+            evaluations,
+            vec![
+                FE::from(4) - FE::from(1) - FE::from(2) * FE::from(3),
+                FE::from(6) * (FE::from(2) + gamma) - FE::from(3) * (FE::from(1) + gamma),
+            ]
+        );
+    }
+
+    #[test]
+    fn wrong_programs_should_fail_the_check() {
+        let mut underflow = ConstraintProgram::<F>::new();
+        underflow.main(0, 0, 0).add().store(0);
+        assert_eq!(underflow.check(1), Err(ProgramError::StackUnderflow(1)));
+
+        let mut unused = ConstraintProgram::<F>::new();
+        unused.main(0, 0, 0).main(1, 0, 0);
+        assert_eq!(unused.check(1), Err(ProgramError::UnusedValues(2)));
+
+        let mut out_of_range = ConstraintProgram::<F>::new();
+        out_of_range.main(0, 0, 0).store(1);
+        assert_eq!(
+            out_of_range.check(1),
+            Err(ProgramError::ConstraintOutOfRange(1))
+        );
+
+        let mut overflow = ConstraintProgram::<F>::new();
+        for _ in 0..=MAX_STACK_DEPTH {
+            overflow.main(0, 0, 0);
+        }
+        assert_eq!(
+            overflow.check(1),
+            Err(ProgramError::StackOverflow(MAX_STACK_DEPTH))
+        );
+    }
+}
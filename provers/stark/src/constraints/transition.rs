@@ -74,15 +74,41 @@ where
         None
     }
 
-    /// The number of exemptions at the end of the trace.
+    /// The number of steps at the end of the trace where the constraint doesn't apply, in
+    /// steps of `AIR::STEP_SIZE` rows.
     ///
     /// This method's output defines what trace elements should not be considered for
     /// the constraint evaluation at the end of the trace. For example, for a fibonacci
     /// computation that has to use the result 2 following steps, this method is defined
-    /// to return the value 2.
+    /// to return the value 2. The constraint is exempted from the rows where it applies, as
+    /// given by `period()`, `offset()` and the periodic exemptions, among the last
+    /// `end_exemptions() * step_size` rows, so constraints that only apply once per step are
+    /// exempted from one row for each step.
+    ///
+    /// For constraints with `period() > 1`, rows are counted, not applications of the
+    /// constraint. A constraint of period 4 and offset 1 with `end_exemptions() == 2` is
+    /// exempted from no row of a trace of 64 rows with one-row steps, since it doesn't apply to
+    /// the rows 62 and 63. To exempt it from its last two applications, at the rows 57 and 61,
+    /// it returns 8.
     fn end_exemptions(&self) -> usize;
 
-    /// Method for calculating the end exemptions polynomial.
+    /// The rows where the constraint would apply but is exempted from by `end_exemptions()`,
+    /// in a trace of `trace_length` rows with steps of `step_size` rows.
+    fn end_exemption_rows(&self, trace_length: usize, step_size: usize) -> Vec<usize> {
+        let first_exempted_row = trace_length.saturating_sub(self.end_exemptions() * step_size);
+        let periodic_exemptions = self
+            .exemptions_period()
+            .zip(self.periodic_exemptions_offset());
+        (first_exempted_row..trace_length)
+            .filter(|row| row % self.period() == self.offset())
+            .filter(|row| {
+                periodic_exemptions.map_or(true, |(period, offset)| row % period != offset)
+            })
+            .collect()
+    }
+
+    /// Method for calculating the end exemptions polynomial, which vanishes on the
+    /// `end_exemption_rows`.
     ///
     /// This polynomial is used to compute zerofiers of the constraint, and the default
     /// implementation should normally not be changed.
@@ -90,23 +116,24 @@ where
         &self,
         trace_primitive_root: &FieldElement<F>,
         trace_length: usize,
+        step_size: usize,
     ) -> Polynomial<FieldElement<F>> {
         let one_poly = Polynomial::new_monomial(FieldElement::<F>::one(), 0);
-        if self.end_exemptions() == 0 {
-            return one_poly;
-        }
-        let period = self.period();
-        // FIXME: CHECK IF WE NEED TO CHANGE THE NEW MONOMIAL'S ARGUMENTS TO trace_root^(offset * trace_length / period) INSTEAD OF ONE!!!!
-        (1..=self.end_exemptions())
-            .map(|exemption| trace_primitive_root.pow(trace_length - exemption * period))
-            .fold(one_poly, |acc, offset| {
-                acc * (Polynomial::new_monomial(FieldElement::<F>::one(), 1) - offset)
+        self.end_exemption_rows(trace_length, step_size)
+            .into_iter()
+            .map(|row| trace_primitive_root.pow(row))
+            .fold(one_poly, |acc, root| {
+                acc * (Polynomial::new_monomial(FieldElement::<F>::one(), 1) - root)
             })
     }
 
     /// Compute evaluations of the constraints zerofier over a LDE domain.
     #[allow(unstable_name_collisions)]
-    fn zerofier_evaluations_on_extended_domain(&self, domain: &Domain<F>) -> Vec<FieldElement<F>> {
+    fn zerofier_evaluations_on_extended_domain(
+        &self,
+        domain: &Domain<F>,
+        step_size: usize,
+    ) -> Vec<FieldElement<F>> {
         let blowup_factor = domain.blowup_factor;
         let trace_length = domain.trace_roots_of_unity.len();
         let trace_primitive_root = &domain.trace_primitive_root;
@@ -114,7 +141,8 @@ where
         let lde_root_order = u64::from((blowup_factor * trace_length).trailing_zeros());
         let lde_root = F::get_primitive_root_of_unity(lde_root_order).unwrap();
 
-        let end_exemptions_poly = self.end_exemptions_poly(trace_primitive_root, trace_length);
+        let end_exemptions_poly =
+            self.end_exemptions_poly(trace_primitive_root, trace_length, step_size);
 
         // If there is an exemptions period defined for this constraint, the evaluations are calculated directly
         // by computing P_exemptions(x) / Zerofier(x)
@@ -213,8 +241,10 @@ where
         z: &FieldElement<E>,
         trace_primitive_root: &FieldElement<F>,
         trace_length: usize,
+        step_size: usize,
     ) -> FieldElement<E> {
-        let end_exemptions_poly = self.end_exemptions_poly(trace_primitive_root, trace_length);
+        let end_exemptions_poly =
+            self.end_exemptions_poly(trace_primitive_root, trace_length, step_size);
 
         if let Some(exemptions_period) = self.exemptions_period() {
             debug_assert!(exemptions_period.is_multiple_of(&self.period()));
@@ -240,3 +270,119 @@ where
             * end_exemptions_poly.evaluate(z)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use lambdaworks_math::field::fields::fft_friendly::stark_252_prime_field::Stark252PrimeField;
+
+    use super::*;
+
+    type F = Stark252PrimeField;
+
+    struct ExemptedConstraint {
+        period: usize,
+        offset: usize,
+        periodic_exemptions: Option<(usize, usize)>,
+        end_exemptions: usize,
+    }
+
+    impl TransitionConstraint<F, F> for ExemptedConstraint {
+        fn degree(&self) -> usize {
+            1
+        }
+
+        fn constraint_idx(&self) -> usize {
+            0
+        }
+
+        fn evaluate(
+            &self,
+            _frame: &Frame<F, F>,
+            _transition_evaluations: &mut [FieldElement<F>],
+            _periodic_values: &[FieldElement<F>],
+            _rap_challenges: &[FieldElement<F>],
+        ) {
+        }
+
+        fn period(&self) -> usize {
+            self.period
+        }
+
+        fn offset(&self) -> usize {
+            self.offset
+        }
+
+        fn exemptions_period(&self) -> Option<usize> {
+            self.periodic_exemptions.map(|(period, _)| period)
+        }
+
+        fn periodic_exemptions_offset(&self) -> Option<usize> {
+            self.periodic_exemptions.map(|(_, offset)| offset)
+        }
+
+        fn end_exemptions(&self) -> usize {
+            self.end_exemptions
+        }
+    }
+
+    #[test]
+    fn end_exemptions_are_counted_in_steps() {
+        let every_row = ExemptedConstraint {
+            period: 1,
+            offset: 0,
+            periodic_exemptions: None,
+            end_exemptions: 2,
+        };
+        assert_eq!(every_row.end_exemption_rows(64, 1), vec![62, 63]);
+        assert_eq!(
+            every_row.end_exemption_rows(64, 16),
+            (32..64).collect::<Vec<_>>()
+        );
+
+        let once_per_step = ExemptedConstraint {
+            period: 16,
+            offset: 15,
+            periodic_exemptions: None,
+            end_exemptions: 2,
+        };
+        assert_eq!(once_per_step.end_exemption_rows(64, 16), vec![47, 63]);
+    }
+
+    #[test]
+    fn end_exemptions_of_periodic_constraints_count_rows() {
+        let last_rows = ExemptedConstraint {
+            period: 4,
+            offset: 1,
+            periodic_exemptions: None,
+            end_exemptions: 2,
+        };
+        assert!(last_rows.end_exemption_rows(64, 1).is_empty());
+
+        let last_applications = ExemptedConstraint {
+            end_exemptions: 8,
+            ..last_rows
+        };
+        assert_eq!(last_applications.end_exemption_rows(64, 1), vec![57, 61]);
+
+        let root = F::get_primitive_root_of_unity(6).unwrap();
+        let poly = last_applications.end_exemptions_poly(&root, 64, 1);
+        assert_eq!(poly.degree(), 2);
+        for row in [57, 61] {
+            assert_eq!(poly.evaluate(&root.pow(row as u64)), FieldElement::zero());
+        }
+        for row in [53, 56, 62, 63] {
+            assert_ne!(poly.evaluate(&root.pow(row as u64)), FieldElement::zero());
+        }
+    }
+
+    #[test]
+    fn end_exemptions_skip_periodic_exemptions() {
+        let constraint = ExemptedConstraint {
+            period: 2,
+            offset: 0,
+            periodic_exemptions: Some((8, 6)),
+            end_exemptions: 1,
+        };
+        assert_eq!(constraint.end_exemption_rows(64, 8), vec![56, 58, 60]);
+    }
+}
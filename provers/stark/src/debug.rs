@@ -25,7 +25,7 @@ pub fn validate_trace<A: AIR>(
         .iter()
         .map(|poly| {
             Polynomial::<FieldElement<A::Field>>::evaluate_fft::<A::Field>(
-                poly,
+                &reduce_on_trace_domain(poly, domain.interpolation_domain_size),
                 1,
                 Some(domain.interpolation_domain_size),
             )
@@ -36,8 +36,12 @@ pub fn validate_trace<A: AIR>(
     let aux_trace_columns: Vec<_> = aux_trace_polys
         .iter()
         .map(|poly| {
-            Polynomial::evaluate_fft::<A::Field>(poly, 1, Some(domain.interpolation_domain_size))
-                .unwrap()
+            Polynomial::evaluate_fft::<A::Field>(
+                &reduce_on_trace_domain(poly, domain.interpolation_domain_size),
+                1,
+                Some(domain.interpolation_domain_size),
+            )
+            .unwrap()
         })
         .collect();
 
@@ -82,7 +86,7 @@ pub fn validate_trace<A: AIR>(
     let n_transition_constraints = air.context().num_transition_constraints();
     let transition_exemptions = &air.context().transition_exemptions;
 
-    let exemption_steps: Vec<usize> = vec![lde_trace.num_rows(); n_transition_constraints]
+    let exemption_steps: Vec<usize> = vec![lde_trace.num_steps(); n_transition_constraints]
         .iter()
         .zip(transition_exemptions)
         .map(|(trace_steps, exemptions)| trace_steps - exemptions)
@@ -141,3 +145,17 @@ where
     let rows: Vec<Vec<FieldElement<F>>> = data.chunks(width).map(|c| c.to_vec()).collect();
     rows.iter().all(|r| r.len() == rows[0].len())
 }
+
+/// Returns the remainder of `poly` divided by `X^n - 1`, which takes the same values on the
+/// trace domain of size `n`. Polynomials of zero-knowledge proofs are blinded with multiples of
+/// `X^n - 1`, so they have more coefficients than the domain has points.
+fn reduce_on_trace_domain<F: IsField>(
+    poly: &Polynomial<FieldElement<F>>,
+    n: usize,
+) -> Polynomial<FieldElement<F>> {
+    let mut coefficients = vec![FieldElement::zero(); n.min(poly.coeff_len())];
+    for (i, coefficient) in poly.coefficients().iter().enumerate() {
+        coefficients[i % n] = &coefficients[i % n] + coefficient;
+    }
+    Polynomial::new(&coefficients)
+}
@@ -0,0 +1,207 @@
+//! Reports of the intermediate values of the prover, to debug failed or slow proving jobs after
+//! the fact and to compare runs across versions.
+//!
+//! [`IsStarkProver::prove_with_diagnostics`](crate::prover::IsStarkProver::prove_with_diagnostics)
+//! returns a [`ProverDiagnostics`] next to the proof, which can be written as JSON with
+//! [`ProverDiagnostics::write_json`].
+
+use std::io::Write;
+
+use lambdaworks_math::environment::Stopwatch;
+use serde::Serialize;
+
+use crate::{config::Commitment, proof::options::ProofOptions};
+
+/// Dimensions of the trace after padding, and of its low degree extension.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct TraceDimensions {
+    pub num_rows: usize,
+    pub num_main_columns: usize,
+    pub num_aux_columns: usize,
+    pub step_size: usize,
+    pub lde_num_rows: usize,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct ConstraintCounts {
+    pub num_transition_constraints: usize,
+    pub num_boundary_constraints: usize,
+    pub num_composition_poly_parts: usize,
+}
+
+/// Time spent by the prover in a phase of the protocol.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct PhaseTiming {
+    pub phase: &'static str,
+    pub duration_ns: u64,
+}
+
+/// Commitments of the proof, in hexadecimal.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct CommitmentRoots {
+    /// The commitment of the preprocessed columns, if the AIR has any.
+    pub preprocessed_trace: Option<String>,
+    pub main_trace: String,
+    /// One commitment for each interaction round with auxiliary columns.
+    pub aux_trace: Vec<String>,
+    pub composition_poly: String,
+    pub fri_layers: Vec<String>,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct ProverDiagnostics {
+    /// Version of the prover that generated the report.
+    pub version: &'static str,
+    /// Public inputs of the proof, if they were attached with
+    /// [`ProverDiagnostics::with_public_inputs`].
+    pub public_inputs: Option<serde_json::Value>,
+    pub proof_options: ProofOptions,
+    pub trace: TraceDimensions,
+    pub constraints: ConstraintCounts,
+    pub phases: Vec<PhaseTiming>,
+    pub commitments: CommitmentRoots,
+    pub nonce: Option<u64>,
+}
+
+impl ProverDiagnostics {
+    /// Attaches the public inputs of the proof to the report. The prover can't do it on its own,
+    /// since the public inputs of an AIR don't need to be serializable.
+    pub fn with_public_inputs<P: Serialize>(
+        mut self,
+        public_inputs: &P,
+    ) -> Result<Self, serde_json::Error> {
+        self.public_inputs = Some(serde_json::to_value(public_inputs)?);
+        Ok(self)
+    }
+
+    /// Returns the total time spent by the prover.
+    pub fn total_duration_ns(&self) -> u64 {
+        self.phases.iter().map(|phase| phase.duration_ns).sum()
+    }
+
+    pub fn write_json(&self, writer: impl Write) -> Result<(), serde_json::Error> {
+        serde_json::to_writer_pretty(writer, self)
+    }
+}
+
+/// Measures the time spent in each phase of the prover if a report was requested, and does
+/// nothing otherwise.
+pub(crate) struct PhaseTimer {
+    phases: Option<Vec<PhaseTiming>>,
+    stopwatch: Option<Stopwatch>,
+}
+
+impl PhaseTimer {
+    pub(crate) fn new(enabled: bool) -> Self {
+        Self {
+            phases: enabled.then(Vec::new),
+            stopwatch: None,
+        }
+    }
+
+    pub(crate) fn start(&mut self) {
+        if self.phases.is_some() {
+            self.stopwatch = Some(Stopwatch::start());
+        }
+    }
+
+    pub(crate) fn stop(&mut self, phase: &'static str) {
+        if let (Some(phases), Some(stopwatch)) = (&mut self.phases, self.stopwatch.take()) {
+            phases.push(PhaseTiming {
+                phase,
+                duration_ns: stopwatch.elapsed().as_nanos() as u64,
+            });
+        }
+    }
+
+    pub(crate) fn into_phases(self) -> Vec<PhaseTiming> {
+        self.phases.unwrap_or_default()
+    }
+}
+
+pub(crate) fn commitment_to_hex(commitment: &Commitment) -> String {
+    commitment
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        examples::simple_fibonacci::{self, FibonacciAIR, FibonacciPublicInputs},
+        prover::{IsStarkProver, Prover},
+        transcript::StoneProverTranscript,
+        Felt252, PrimeField,
+    };
+
+    #[test]
+    fn diagnostics_describe_the_proof() {
+        let trace = simple_fibonacci::fibonacci_trace([Felt252::one(), Felt252::one()], 32);
+        let pub_inputs = FibonacciPublicInputs {
+            a0: Felt252::one(),
+            a1: Felt252::one(),
+        };
+        let proof_options = ProofOptions::default_test_options();
+
+        let (proof, diagnostics) = Prover::<FibonacciAIR<PrimeField>>::prove_with_diagnostics(
+            &trace,
+            &pub_inputs,
+            &proof_options,
+            StoneProverTranscript::new(&[]),
+        )
+        .unwrap();
+
+        assert_eq!(diagnostics.trace.num_rows, 32);
+        assert_eq!(diagnostics.trace.lde_num_rows, 32 * 4);
+        assert_eq!(diagnostics.trace.num_main_columns, 1);
+        assert_eq!(diagnostics.phases.len(), 5);
+        assert_eq!(
+            diagnostics.commitments.main_trace,
+            commitment_to_hex(&proof.lde_trace_main_merkle_root)
+        );
+        assert_eq!(
+            diagnostics.commitments.fri_layers.len(),
+            proof.fri_layers_merkle_roots.len()
+        );
+        assert!(diagnostics.public_inputs.is_none());
+
+        let mut json = Vec::new();
+        diagnostics.write_json(&mut json).unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&json).unwrap();
+        assert_eq!(json["trace"]["num_rows"], 32);
+        assert_eq!(json["proof_options"]["blowup_factor"], 4);
+    }
+
+    #[test]
+    fn proving_with_diagnostics_doesnt_change_the_proof() {
+        let trace = simple_fibonacci::fibonacci_trace([Felt252::one(), Felt252::one()], 16);
+        let pub_inputs = FibonacciPublicInputs {
+            a0: Felt252::one(),
+            a1: Felt252::one(),
+        };
+        let proof_options = ProofOptions::default_test_options();
+
+        let proof = Prover::<FibonacciAIR<PrimeField>>::prove(
+            &trace,
+            &pub_inputs,
+            &proof_options,
+            StoneProverTranscript::new(&[]),
+        )
+        .unwrap();
+        let (proof_with_diagnostics, _) =
+            Prover::<FibonacciAIR<PrimeField>>::prove_with_diagnostics(
+                &trace,
+                &pub_inputs,
+                &proof_options,
+                StoneProverTranscript::new(&[]),
+            )
+            .unwrap();
+
+        assert_eq!(
+            serde_json::to_string(&proof).unwrap(),
+            serde_json::to_string(&proof_with_diagnostics).unwrap()
+        );
+    }
+}
@@ -3,7 +3,7 @@ use lambdaworks_math::{
     field::{element::FieldElement, traits::IsFFTField},
 };
 
-use super::traits::AIR;
+use super::{proof::options::ProofOptions, traits::AIR};
 
 pub struct Domain<F: IsFFTField> {
     pub(crate) root_order: u32,
@@ -20,11 +20,17 @@ impl<F: IsFFTField> Domain<F> {
     where
         A: AIR<Field = F>,
     {
+        Self::from_trace_length(air.trace_length(), air.options())
+    }
+
+    /// Returns the domain of a trace with `trace_length` rows, extended with the blowup factor
+    /// and the coset offset of `proof_options`.
+    pub fn from_trace_length(trace_length: usize, proof_options: &ProofOptions) -> Self {
         // Initial definitions
-        let blowup_factor = air.options().blowup_factor as usize;
-        let coset_offset = FieldElement::from(air.options().coset_offset);
-        let interpolation_domain_size = air.trace_length();
-        let root_order = air.trace_length().trailing_zeros();
+        let blowup_factor = proof_options.blowup_factor as usize;
+        let coset_offset = FieldElement::from(proof_options.coset_offset);
+        let interpolation_domain_size = trace_length;
+        let root_order = trace_length.trailing_zeros();
         // * Generate Coset
         let trace_primitive_root = F::get_primitive_root_of_unity(root_order as u64).unwrap();
         let trace_roots_of_unity = get_powers_of_primitive_root_coset(
@@ -34,10 +40,10 @@ impl<F: IsFFTField> Domain<F> {
         )
         .unwrap();
 
-        let lde_root_order = (air.trace_length() * blowup_factor).trailing_zeros();
+        let lde_root_order = (trace_length * blowup_factor).trailing_zeros();
         let lde_roots_of_unity_coset = get_powers_of_primitive_root_coset(
             lde_root_order as u64,
-            air.trace_length() * blowup_factor,
+            trace_length * blowup_factor,
             &coset_offset,
         )
         .unwrap();
@@ -52,4 +58,26 @@ impl<F: IsFFTField> Domain<F> {
             interpolation_domain_size,
         }
     }
+
+    /// Returns the `index`-th coset of the trace domain in the LDE domain, made of the points
+    /// `index`, `index + blowup_factor`, `index + 2 * blowup_factor`... of the LDE domain, as the
+    /// domain of an LDE with blowup factor 1. Evaluating the constraints on it gives their
+    /// evaluations at those points.
+    pub(crate) fn coset(&self, index: usize) -> Self {
+        Self {
+            root_order: self.root_order,
+            lde_roots_of_unity_coset: self
+                .lde_roots_of_unity_coset
+                .iter()
+                .skip(index)
+                .step_by(self.blowup_factor)
+                .cloned()
+                .collect(),
+            trace_primitive_root: self.trace_primitive_root.clone(),
+            trace_roots_of_unity: self.trace_roots_of_unity.clone(),
+            coset_offset: self.lde_roots_of_unity_coset[index].clone(),
+            blowup_factor: 1,
+            interpolation_domain_size: self.interpolation_domain_size,
+        }
+    }
 }
@@ -3,6 +3,7 @@ use std::{marker::PhantomData, ops::Div};
 use crate::{
     constraints::{
         boundary::{BoundaryConstraint, BoundaryConstraints},
+        program::ConstraintProgram,
         transition::TransitionConstraint,
     },
     context::AirContext,
@@ -13,17 +14,20 @@ use crate::{
 };
 use lambdaworks_crypto::fiat_shamir::is_transcript::IsTranscript;
 use lambdaworks_math::{
-    field::{element::FieldElement, traits::IsFFTField},
+    field::{
+        element::FieldElement,
+        traits::{IsFFTField, IsField, IsSubFieldOf},
+    },
     helpers::resize_to_next_power_of_two,
     traits::ByteConversion,
 };
 
 #[derive(Clone)]
-struct FibConstraint<F: IsFFTField> {
-    phantom: PhantomData<F>,
+struct FibConstraint<F: IsFFTField, E: IsField> {
+    phantom: PhantomData<(F, E)>,
 }
 
-impl<F: IsFFTField> FibConstraint<F> {
+impl<F: IsFFTField, E: IsField> FibConstraint<F, E> {
     pub fn new() -> Self {
         Self {
             phantom: PhantomData,
@@ -31,9 +35,10 @@ impl<F: IsFFTField> FibConstraint<F> {
     }
 }
 
-impl<F> TransitionConstraint<F, F> for FibConstraint<F>
+impl<F, E> TransitionConstraint<F, E> for FibConstraint<F, E>
 where
-    F: IsFFTField + Send + Sync,
+    F: IsFFTField + IsSubFieldOf<E> + Send + Sync,
+    E: IsField + Send + Sync,
 {
     fn degree(&self) -> usize {
         1
@@ -51,10 +56,10 @@ where
 
     fn evaluate(
         &self,
-        frame: &Frame<F, F>,
-        transition_evaluations: &mut [FieldElement<F>],
+        frame: &Frame<F, E>,
+        transition_evaluations: &mut [FieldElement<E>],
         _periodic_values: &[FieldElement<F>],
-        _rap_challenges: &[FieldElement<F>],
+        _rap_challenges: &[FieldElement<E>],
     ) {
         let first_step = frame.get_evaluation_step(0);
         let second_step = frame.get_evaluation_step(1);
@@ -66,16 +71,16 @@ where
 
         let res = a2 - a1 - a0;
 
-        transition_evaluations[self.constraint_idx()] = res;
+        transition_evaluations[self.constraint_idx()] = res.to_extension();
     }
 }
 
 #[derive(Clone)]
-struct PermutationConstraint<F: IsFFTField> {
-    phantom: PhantomData<F>,
+struct PermutationConstraint<F: IsFFTField, E: IsField> {
+    phantom: PhantomData<(F, E)>,
 }
 
-impl<F: IsFFTField> PermutationConstraint<F> {
+impl<F: IsFFTField, E: IsField> PermutationConstraint<F, E> {
     pub fn new() -> Self {
         Self {
             phantom: PhantomData,
@@ -83,9 +88,10 @@ impl<F: IsFFTField> PermutationConstraint<F> {
     }
 }
 
-impl<F> TransitionConstraint<F, F> for PermutationConstraint<F>
+impl<F, E> TransitionConstraint<F, E> for PermutationConstraint<F, E>
 where
-    F: IsFFTField + Send + Sync,
+    F: IsFFTField + IsSubFieldOf<E> + Send + Sync,
+    E: IsField + Send + Sync,
 {
     fn degree(&self) -> usize {
         2
@@ -101,10 +107,10 @@ where
 
     fn evaluate(
         &self,
-        frame: &Frame<F, F>,
-        transition_evaluations: &mut [FieldElement<F>],
+        frame: &Frame<F, E>,
+        transition_evaluations: &mut [FieldElement<E>],
         _periodic_values: &[FieldElement<F>],
-        rap_challenges: &[FieldElement<F>],
+        rap_challenges: &[FieldElement<E>],
     ) {
         let first_step = frame.get_evaluation_step(0);
         let second_step = frame.get_evaluation_step(1);
@@ -123,14 +129,17 @@ where
     }
 }
 
-pub struct FibonacciRAP<F>
+/// A Fibonacci sequence and a permutation of it over `F`, with the permutation argument
+/// sampling its challenge and building its auxiliary column in the extension `E` of `F`.
+pub struct FibonacciRAP<F, E = F>
 where
-    F: IsFFTField,
+    F: IsFFTField + IsSubFieldOf<E> + Send + Sync,
+    E: IsField + Send + Sync,
 {
     context: AirContext,
     trace_length: usize,
     pub_inputs: FibonacciRAPPublicInputs<F>,
-    transition_constraints: Vec<Box<dyn TransitionConstraint<F, F>>>,
+    transition_constraints: Vec<Box<dyn TransitionConstraint<F, E>>>,
 }
 
 #[derive(Clone, Debug)]
@@ -143,13 +152,14 @@ where
     pub a1: FieldElement<F>,
 }
 
-impl<F> AIR for FibonacciRAP<F>
+impl<F, E> AIR for FibonacciRAP<F, E>
 where
-    F: IsFFTField + Send + Sync + 'static,
+    F: IsFFTField + IsSubFieldOf<E> + Send + Sync + 'static,
+    E: IsField + Send + Sync + 'static,
     FieldElement<F>: ByteConversion,
 {
     type Field = F;
-    type FieldExtension = F;
+    type FieldExtension = E;
     type PublicInputs = FibonacciRAPPublicInputs<Self::Field>;
 
     const STEP_SIZE: usize = 1;
@@ -187,8 +197,8 @@ where
     fn build_auxiliary_trace(
         &self,
         main_trace: &TraceTable<Self::Field>,
-        challenges: &[FieldElement<F>],
-    ) -> TraceTable<Self::Field> {
+        challenges: &[FieldElement<E>],
+    ) -> TraceTable<Self::FieldExtension> {
         let main_segment_cols = main_trace.columns();
         let not_perm = &main_segment_cols[0];
         let perm = &main_segment_cols[1];
@@ -199,7 +209,7 @@ where
         let mut aux_col = Vec::new();
         for i in 0..trace_len {
             if i == 0 {
-                aux_col.push(FieldElement::<Self::Field>::one());
+                aux_col.push(FieldElement::<Self::FieldExtension>::one());
             } else {
                 let z_i = &aux_col[i - 1];
                 let n_p_term = not_perm[i - 1].clone() + gamma;
@@ -213,7 +223,7 @@ where
 
     fn build_rap_challenges(
         &self,
-        transcript: &mut impl IsTranscript<Self::Field>,
+        transcript: &mut impl IsTranscript<Self::FieldExtension>,
     ) -> Vec<FieldElement<Self::FieldExtension>> {
         vec![transcript.sample_field_element()]
     }
@@ -245,6 +255,33 @@ where
         &self.transition_constraints
     }
 
+    fn transition_program(&self) -> Option<ConstraintProgram<Self::FieldExtension>> {
+        let mut program = ConstraintProgram::new();
+        // a2 - a1 - a0
+        program
+            .main(2, 0, 0)
+            .main(1, 0, 0)
+            .sub()
+            .main(0, 0, 0)
+            .sub()
+            .store(0);
+        // z_{i+1} * (b_i + gamma) - z_i * (a_i + gamma)
+        program
+            .aux(1, 0, 0)
+            .main(0, 0, 1)
+            .challenge(0)
+            .add()
+            .mul()
+            .aux(0, 0, 0)
+            .main(0, 0, 0)
+            .challenge(0)
+            .add()
+            .mul()
+            .sub()
+            .store(1);
+        Some(program)
+    }
+
     fn context(&self) -> &AirContext {
         &self.context
     }
@@ -266,8 +303,14 @@ where
         frame: &Frame<Self::FieldExtension, Self::FieldExtension>,
         periodic_values: &[FieldElement<Self::FieldExtension>],
         rap_challenges: &[FieldElement<Self::FieldExtension>],
-    ) -> Vec<FieldElement<Self::Field>> {
-        self.compute_transition_prover(frame, periodic_values, rap_challenges)
+    ) -> Vec<FieldElement<Self::FieldExtension>> {
+        // The constraints read the main trace in `F`, while the out of domain frame is in `E`,
+        // so the verifier evaluates the same constraints as a program.
+        let mut evaluations = vec![FieldElement::zero(); self.num_transition_constraints()];
+        self.transition_program()
+            .expect("the transition program is always defined")
+            .evaluate(frame, &mut evaluations, periodic_values, rap_challenges);
+        evaluations
     }
 }
 
@@ -300,7 +343,10 @@ pub fn fibonacci_rap_trace<F: IsFFTField>(
 #[cfg(test)]
 mod test {
     use super::*;
-    use lambdaworks_math::field::fields::u64_prime_field::FE17;
+    use crate::trace::LDETraceTable;
+    use lambdaworks_math::field::fields::{
+        fft_friendly::stark_252_prime_field::Stark252PrimeField, u64_prime_field::FE17,
+    };
 
     #[test]
     fn test_build_fibonacci_rap_trace() {
@@ -367,4 +413,37 @@ mod test {
 
         assert_eq!(aux_col.last().unwrap(), &FE17::one());
     }
+
+    #[test]
+    fn transition_program_matches_transition_constraints() {
+        type F = Stark252PrimeField;
+        let pub_inputs = FibonacciRAPPublicInputs {
+            steps: 16,
+            a0: FieldElement::one(),
+            a1: FieldElement::one(),
+        };
+        let main_trace = fibonacci_rap_trace::<F>([FieldElement::one(), FieldElement::one()], 16);
+        let air = FibonacciRAP::<F>::new(
+            main_trace.n_rows(),
+            &pub_inputs,
+            &ProofOptions::default_test_options(),
+        );
+        let rap_challenges = vec![FieldElement::from(10)];
+        let aux_trace = air.build_auxiliary_trace(&main_trace, &rap_challenges);
+        let lde_trace =
+            LDETraceTable::from_columns(main_trace.columns(), aux_trace.columns(), 1, 1);
+
+        let program = air.transition_program().unwrap();
+        assert_eq!(program.check(air.num_transition_constraints()), Ok(()));
+        for row in 0..lde_trace.num_rows() {
+            let frame = Frame::read_from_lde(&lde_trace, row, &air.context().transition_offsets);
+            let mut evaluations = vec![FieldElement::zero(); air.num_transition_constraints()];
+            program.evaluate(&frame, &mut evaluations, &[], &rap_challenges);
+
+            assert_eq!(
+                evaluations,
+                air.compute_transition_prover(&frame, &[], &rap_challenges)
+            );
+        }
+    }
 }
@@ -3,6 +3,9 @@ pub mod dummy_air;
 pub mod fibonacci_2_cols_shifted;
 pub mod fibonacci_2_columns;
 pub mod fibonacci_rap;
+pub mod multi_round_lookup;
+pub mod preprocessed_range_check;
 pub mod quadratic_air;
+pub mod range_check_lookup;
 pub mod simple_fibonacci;
 pub mod simple_periodic_cols;
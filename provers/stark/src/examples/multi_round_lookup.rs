@@ -0,0 +1,240 @@
+use std::marker::PhantomData;
+
+use crate::{
+    constraints::{
+        boundary::{BoundaryConstraint, BoundaryConstraints},
+        transition::TransitionConstraint,
+    },
+    context::AirContext,
+    frame::Frame,
+    lookup::{self, LogUpLookup},
+    proof::options::ProofOptions,
+    trace::TraceTable,
+    traits::AIR,
+};
+use lambdaworks_crypto::fiat_shamir::is_transcript::IsTranscript;
+use lambdaworks_math::field::{
+    element::FieldElement,
+    traits::{IsFFTField, IsPrimeField},
+};
+
+const TABLE_COLUMN: usize = 0;
+const FIRST_VALUES_COLUMN: usize = 1;
+const FIRST_MULTIPLICITIES_COLUMN: usize = 2;
+const SECOND_VALUES_COLUMN: usize = 3;
+const SECOND_MULTIPLICITIES_COLUMN: usize = 4;
+
+/// The table holds the numbers `0, 1, ..., n - 1`, where `n` is the length of the trace.
+#[derive(Clone)]
+struct TableConstraint<F: IsFFTField> {
+    phantom: PhantomData<F>,
+}
+
+impl<F: IsFFTField> TableConstraint<F> {
+    pub fn new() -> Self {
+        Self {
+            phantom: PhantomData,
+        }
+    }
+}
+
+impl<F> TransitionConstraint<F, F> for TableConstraint<F>
+where
+    F: IsFFTField + Send + Sync,
+{
+    fn degree(&self) -> usize {
+        1
+    }
+
+    fn constraint_idx(&self) -> usize {
+        0
+    }
+
+    fn end_exemptions(&self) -> usize {
+        1
+    }
+
+    fn evaluate(
+        &self,
+        frame: &Frame<F, F>,
+        transition_evaluations: &mut [FieldElement<F>],
+        _periodic_values: &[FieldElement<F>],
+        _rap_challenges: &[FieldElement<F>],
+    ) {
+        let first_step = frame.get_evaluation_step(0);
+        let second_step = frame.get_evaluation_step(1);
+
+        let t_i = first_step.get_main_evaluation_element(0, TABLE_COLUMN);
+        let t_i_plus_one = second_step.get_main_evaluation_element(0, TABLE_COLUMN);
+
+        let res = t_i_plus_one - t_i - FieldElement::<F>::one();
+
+        transition_evaluations[self.constraint_idx()] = res;
+    }
+}
+
+/// Range checks two columns with a [`LogUpLookup`] each, in two interaction rounds: the columns
+/// of the second lookup are committed after the ones of the first, with its own challenge.
+pub struct MultiRoundLookupAIR<F>
+where
+    F: IsFFTField,
+{
+    context: AirContext,
+    trace_length: usize,
+    lookups: [LogUpLookup; 2],
+    transition_constraints: Vec<Box<dyn TransitionConstraint<F, F>>>,
+}
+
+impl<F> AIR for MultiRoundLookupAIR<F>
+where
+    F: IsFFTField + Send + Sync + 'static,
+{
+    type Field = F;
+    type FieldExtension = F;
+    type PublicInputs = ();
+
+    const STEP_SIZE: usize = 1;
+
+    fn new(
+        trace_length: usize,
+        _pub_inputs: &Self::PublicInputs,
+        proof_options: &ProofOptions,
+    ) -> Self {
+        let first_lookup = LogUpLookup::new(
+            vec![FIRST_VALUES_COLUMN],
+            TABLE_COLUMN,
+            FIRST_MULTIPLICITIES_COLUMN,
+        );
+        let second_lookup = LogUpLookup::new(
+            vec![SECOND_VALUES_COLUMN],
+            TABLE_COLUMN,
+            SECOND_MULTIPLICITIES_COLUMN,
+        )
+        .with_first_aux_column(first_lookup.num_aux_columns())
+        .with_challenge_idx(1);
+
+        let mut transition_constraints: Vec<
+            Box<dyn TransitionConstraint<Self::Field, Self::FieldExtension>>,
+        > = vec![Box::new(TableConstraint::new())];
+        transition_constraints.extend(first_lookup.transition_constraints(1));
+        transition_constraints.extend(
+            second_lookup.transition_constraints(1 + first_lookup.num_transition_constraints()),
+        );
+
+        let mut transition_exemptions = vec![1];
+        transition_exemptions.extend(vec![0; transition_constraints.len() - 1]);
+
+        let context = AirContext {
+            proof_options: proof_options.clone(),
+            trace_columns: 5 + first_lookup.num_aux_columns() + second_lookup.num_aux_columns(),
+            transition_offsets: vec![0, 1],
+            transition_exemptions,
+            num_transition_constraints: transition_constraints.len(),
+        };
+
+        Self {
+            context,
+            trace_length,
+            lookups: [first_lookup, second_lookup],
+            transition_constraints,
+        }
+    }
+
+    fn build_rap_challenges_for_round(
+        &self,
+        _round: usize,
+        transcript: &mut impl IsTranscript<Self::FieldExtension>,
+    ) -> Vec<FieldElement<Self::FieldExtension>> {
+        vec![transcript.sample_field_element()]
+    }
+
+    fn build_auxiliary_trace_for_round(
+        &self,
+        round: usize,
+        main_trace: &TraceTable<Self::Field>,
+        _aux_trace: &TraceTable<Self::FieldExtension>,
+        rap_challenges: &[FieldElement<Self::FieldExtension>],
+    ) -> TraceTable<Self::FieldExtension> {
+        let aux_columns = self.lookups[round].build_auxiliary_columns(main_trace, rap_challenges);
+        TraceTable::from_columns(aux_columns, 0, 1)
+    }
+
+    fn trace_layout(&self) -> (usize, usize) {
+        (5, self.num_auxiliary_rap_columns_per_round().iter().sum())
+    }
+
+    fn num_auxiliary_rap_columns_per_round(&self) -> Vec<usize> {
+        self.lookups
+            .iter()
+            .map(|lookup| lookup.num_aux_columns())
+            .collect()
+    }
+
+    fn boundary_constraints(
+        &self,
+        _rap_challenges: &[FieldElement<Self::FieldExtension>],
+    ) -> BoundaryConstraints<Self::FieldExtension> {
+        let table_start = BoundaryConstraint::new_main(TABLE_COLUMN, 0, FieldElement::zero());
+
+        BoundaryConstraints::from_constraints(vec![
+            table_start,
+            self.lookups[0].boundary_constraint(),
+            self.lookups[1].boundary_constraint(),
+        ])
+    }
+
+    fn transition_constraints(
+        &self,
+    ) -> &Vec<Box<dyn TransitionConstraint<Self::Field, Self::FieldExtension>>> {
+        &self.transition_constraints
+    }
+
+    fn context(&self) -> &AirContext {
+        &self.context
+    }
+
+    fn composition_poly_degree_bound(&self) -> usize {
+        self.trace_length()
+    }
+
+    fn trace_length(&self) -> usize {
+        self.trace_length
+    }
+
+    fn pub_inputs(&self) -> &Self::PublicInputs {
+        &()
+    }
+
+    fn compute_transition_verifier(
+        &self,
+        frame: &Frame<Self::FieldExtension, Self::FieldExtension>,
+        periodic_values: &[FieldElement<Self::FieldExtension>],
+        rap_challenges: &[FieldElement<Self::FieldExtension>],
+    ) -> Vec<FieldElement<Self::Field>> {
+        self.compute_transition_prover(frame, periodic_values, rap_challenges)
+    }
+}
+
+/// Builds the trace that range checks `first_values` and `second_values`, which must have the
+/// same length, a power of two.
+pub fn multi_round_lookup_trace<F: IsFFTField + IsPrimeField>(
+    first_values: Vec<FieldElement<F>>,
+    second_values: Vec<FieldElement<F>>,
+) -> TraceTable<F> {
+    let table: Vec<_> = (0..first_values.len() as u64)
+        .map(FieldElement::from)
+        .collect();
+    let first_multiplicities = lookup::multiplicities(&[&first_values[..]], &table);
+    let second_multiplicities = lookup::multiplicities(&[&second_values[..]], &table);
+
+    TraceTable::from_columns_main(
+        vec![
+            table,
+            first_values,
+            first_multiplicities,
+            second_values,
+            second_multiplicities,
+        ],
+        1,
+    )
+}
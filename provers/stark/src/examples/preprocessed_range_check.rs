@@ -0,0 +1,147 @@
+use crate::{
+    constraints::{boundary::BoundaryConstraints, transition::TransitionConstraint},
+    context::AirContext,
+    frame::Frame,
+    lookup::{self, LogUpLookup},
+    proof::options::ProofOptions,
+    trace::TraceTable,
+    traits::AIR,
+};
+use lambdaworks_crypto::fiat_shamir::is_transcript::IsTranscript;
+use lambdaworks_math::field::{
+    element::FieldElement,
+    traits::{IsFFTField, IsPrimeField},
+};
+
+const TABLE_COLUMN: usize = 0;
+const VALUES_COLUMN: usize = 1;
+const MULTIPLICITIES_COLUMN: usize = 2;
+
+/// Like [`RangeCheckLookupAIR`](super::range_check_lookup::RangeCheckLookupAIR), with the table
+/// as a preprocessed column. Its commitment fixes the table, so it needs no constraints of its
+/// own.
+pub struct PreprocessedRangeCheckAIR<F>
+where
+    F: IsFFTField,
+{
+    context: AirContext,
+    trace_length: usize,
+    lookup: LogUpLookup,
+    transition_constraints: Vec<Box<dyn TransitionConstraint<F, F>>>,
+}
+
+impl<F> AIR for PreprocessedRangeCheckAIR<F>
+where
+    F: IsFFTField + Send + Sync + 'static,
+{
+    type Field = F;
+    type FieldExtension = F;
+    type PublicInputs = ();
+
+    const STEP_SIZE: usize = 1;
+
+    fn new(
+        trace_length: usize,
+        _pub_inputs: &Self::PublicInputs,
+        proof_options: &ProofOptions,
+    ) -> Self {
+        let lookup = LogUpLookup::new(vec![VALUES_COLUMN], TABLE_COLUMN, MULTIPLICITIES_COLUMN);
+        let transition_constraints = lookup.transition_constraints(0);
+
+        let context = AirContext {
+            proof_options: proof_options.clone(),
+            trace_columns: 3 + lookup.num_aux_columns(),
+            transition_offsets: vec![0, 1],
+            transition_exemptions: vec![0; transition_constraints.len()],
+            num_transition_constraints: transition_constraints.len(),
+        };
+
+        Self {
+            context,
+            trace_length,
+            lookup,
+            transition_constraints,
+        }
+    }
+
+    fn build_auxiliary_trace(
+        &self,
+        main_trace: &TraceTable<Self::Field>,
+        rap_challenges: &[FieldElement<Self::FieldExtension>],
+    ) -> TraceTable<Self::FieldExtension> {
+        let aux_columns = self
+            .lookup
+            .build_auxiliary_columns(main_trace, rap_challenges);
+        TraceTable::from_columns(aux_columns, 0, 1)
+    }
+
+    fn build_rap_challenges(
+        &self,
+        transcript: &mut impl IsTranscript<Self::FieldExtension>,
+    ) -> Vec<FieldElement<Self::FieldExtension>> {
+        vec![transcript.sample_field_element()]
+    }
+
+    fn trace_layout(&self) -> (usize, usize) {
+        (3, self.lookup.num_aux_columns())
+    }
+
+    fn num_preprocessed_columns(&self) -> usize {
+        1
+    }
+
+    fn boundary_constraints(
+        &self,
+        _rap_challenges: &[FieldElement<Self::FieldExtension>],
+    ) -> BoundaryConstraints<Self::FieldExtension> {
+        BoundaryConstraints::from_constraints(vec![self.lookup.boundary_constraint()])
+    }
+
+    fn transition_constraints(
+        &self,
+    ) -> &Vec<Box<dyn TransitionConstraint<Self::Field, Self::FieldExtension>>> {
+        &self.transition_constraints
+    }
+
+    fn context(&self) -> &AirContext {
+        &self.context
+    }
+
+    fn composition_poly_degree_bound(&self) -> usize {
+        self.trace_length()
+    }
+
+    fn trace_length(&self) -> usize {
+        self.trace_length
+    }
+
+    fn pub_inputs(&self) -> &Self::PublicInputs {
+        &()
+    }
+
+    fn compute_transition_verifier(
+        &self,
+        frame: &Frame<Self::FieldExtension, Self::FieldExtension>,
+        periodic_values: &[FieldElement<Self::FieldExtension>],
+        rap_challenges: &[FieldElement<Self::FieldExtension>],
+    ) -> Vec<FieldElement<Self::Field>> {
+        self.compute_transition_prover(frame, periodic_values, rap_challenges)
+    }
+}
+
+/// The preprocessed table with the numbers `0, 1, ..., trace_length - 1`.
+pub fn range_check_table<F: IsFFTField + IsPrimeField>(trace_length: usize) -> TraceTable<F> {
+    let table = (0..trace_length as u64).map(FieldElement::from).collect();
+    TraceTable::from_columns_main(vec![table], 1)
+}
+
+/// Builds the main trace that range checks `values`, without the preprocessed table. The length
+/// of `values` must be a power of two.
+pub fn preprocessed_range_check_trace<F: IsFFTField + IsPrimeField>(
+    values: Vec<FieldElement<F>>,
+) -> TraceTable<F> {
+    let table: Vec<_> = (0..values.len() as u64).map(FieldElement::from).collect();
+    let multiplicities = lookup::multiplicities(&[&values[..]], &table);
+
+    TraceTable::from_columns_main(vec![values, multiplicities], 1)
+}
@@ -0,0 +1,198 @@
+use std::marker::PhantomData;
+
+use crate::{
+    constraints::{
+        boundary::{BoundaryConstraint, BoundaryConstraints},
+        transition::TransitionConstraint,
+    },
+    context::AirContext,
+    frame::Frame,
+    lookup::{self, LogUpLookup},
+    proof::options::ProofOptions,
+    trace::TraceTable,
+    traits::AIR,
+};
+use lambdaworks_crypto::fiat_shamir::is_transcript::IsTranscript;
+use lambdaworks_math::field::{
+    element::FieldElement,
+    traits::{IsFFTField, IsPrimeField},
+};
+
+const VALUES_COLUMN: usize = 0;
+const TABLE_COLUMN: usize = 1;
+const MULTIPLICITIES_COLUMN: usize = 2;
+
+/// The table holds the numbers `0, 1, ..., n - 1`, where `n` is the length of the trace.
+#[derive(Clone)]
+struct TableConstraint<F: IsFFTField> {
+    phantom: PhantomData<F>,
+}
+
+impl<F: IsFFTField> TableConstraint<F> {
+    pub fn new() -> Self {
+        Self {
+            phantom: PhantomData,
+        }
+    }
+}
+
+impl<F> TransitionConstraint<F, F> for TableConstraint<F>
+where
+    F: IsFFTField + Send + Sync,
+{
+    fn degree(&self) -> usize {
+        1
+    }
+
+    fn constraint_idx(&self) -> usize {
+        0
+    }
+
+    fn end_exemptions(&self) -> usize {
+        1
+    }
+
+    fn evaluate(
+        &self,
+        frame: &Frame<F, F>,
+        transition_evaluations: &mut [FieldElement<F>],
+        _periodic_values: &[FieldElement<F>],
+        _rap_challenges: &[FieldElement<F>],
+    ) {
+        let first_step = frame.get_evaluation_step(0);
+        let second_step = frame.get_evaluation_step(1);
+
+        let t_i = first_step.get_main_evaluation_element(0, TABLE_COLUMN);
+        let t_i_plus_one = second_step.get_main_evaluation_element(0, TABLE_COLUMN);
+
+        let res = t_i_plus_one - t_i - FieldElement::<F>::one();
+
+        transition_evaluations[self.constraint_idx()] = res;
+    }
+}
+
+/// Proves that every value of the first column is smaller than the length of the trace, by
+/// looking them up in a table with a [`LogUpLookup`].
+pub struct RangeCheckLookupAIR<F>
+where
+    F: IsFFTField,
+{
+    context: AirContext,
+    trace_length: usize,
+    lookup: LogUpLookup,
+    transition_constraints: Vec<Box<dyn TransitionConstraint<F, F>>>,
+}
+
+impl<F> AIR for RangeCheckLookupAIR<F>
+where
+    F: IsFFTField + Send + Sync + 'static,
+{
+    type Field = F;
+    type FieldExtension = F;
+    type PublicInputs = ();
+
+    const STEP_SIZE: usize = 1;
+
+    fn new(
+        trace_length: usize,
+        _pub_inputs: &Self::PublicInputs,
+        proof_options: &ProofOptions,
+    ) -> Self {
+        let lookup = LogUpLookup::new(vec![VALUES_COLUMN], TABLE_COLUMN, MULTIPLICITIES_COLUMN);
+
+        let mut transition_constraints: Vec<
+            Box<dyn TransitionConstraint<Self::Field, Self::FieldExtension>>,
+        > = vec![Box::new(TableConstraint::new())];
+        transition_constraints.extend(lookup.transition_constraints(1));
+
+        let mut transition_exemptions = vec![1];
+        transition_exemptions.extend(vec![0; lookup.num_transition_constraints()]);
+
+        let context = AirContext {
+            proof_options: proof_options.clone(),
+            trace_columns: 3 + lookup.num_aux_columns(),
+            transition_offsets: vec![0, 1],
+            transition_exemptions,
+            num_transition_constraints: transition_constraints.len(),
+        };
+
+        Self {
+            context,
+            trace_length,
+            lookup,
+            transition_constraints,
+        }
+    }
+
+    fn build_auxiliary_trace(
+        &self,
+        main_trace: &TraceTable<Self::Field>,
+        rap_challenges: &[FieldElement<Self::FieldExtension>],
+    ) -> TraceTable<Self::FieldExtension> {
+        let aux_columns = self
+            .lookup
+            .build_auxiliary_columns(main_trace, rap_challenges);
+        TraceTable::from_columns(aux_columns, 0, 1)
+    }
+
+    fn build_rap_challenges(
+        &self,
+        transcript: &mut impl IsTranscript<Self::FieldExtension>,
+    ) -> Vec<FieldElement<Self::FieldExtension>> {
+        vec![transcript.sample_field_element()]
+    }
+
+    fn trace_layout(&self) -> (usize, usize) {
+        (3, self.lookup.num_aux_columns())
+    }
+
+    fn boundary_constraints(
+        &self,
+        _rap_challenges: &[FieldElement<Self::FieldExtension>],
+    ) -> BoundaryConstraints<Self::FieldExtension> {
+        let table_start = BoundaryConstraint::new_main(TABLE_COLUMN, 0, FieldElement::zero());
+
+        BoundaryConstraints::from_constraints(vec![table_start, self.lookup.boundary_constraint()])
+    }
+
+    fn transition_constraints(
+        &self,
+    ) -> &Vec<Box<dyn TransitionConstraint<Self::Field, Self::FieldExtension>>> {
+        &self.transition_constraints
+    }
+
+    fn context(&self) -> &AirContext {
+        &self.context
+    }
+
+    fn composition_poly_degree_bound(&self) -> usize {
+        self.trace_length()
+    }
+
+    fn trace_length(&self) -> usize {
+        self.trace_length
+    }
+
+    fn pub_inputs(&self) -> &Self::PublicInputs {
+        &()
+    }
+
+    fn compute_transition_verifier(
+        &self,
+        frame: &Frame<Self::FieldExtension, Self::FieldExtension>,
+        periodic_values: &[FieldElement<Self::FieldExtension>],
+        rap_challenges: &[FieldElement<Self::FieldExtension>],
+    ) -> Vec<FieldElement<Self::Field>> {
+        self.compute_transition_prover(frame, periodic_values, rap_challenges)
+    }
+}
+
+/// Builds the trace that range checks `values`, whose length must be a power of two.
+pub fn range_check_lookup_trace<F: IsFFTField + IsPrimeField>(
+    values: Vec<FieldElement<F>>,
+) -> TraceTable<F> {
+    let table: Vec<_> = (0..values.len() as u64).map(FieldElement::from).collect();
+    let multiplicities = lookup::multiplicities(&[&values[..]], &table);
+
+    TraceTable::from_columns_main(vec![values, table, multiplicities], 1)
+}
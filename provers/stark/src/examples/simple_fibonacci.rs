@@ -1,6 +1,7 @@
 use crate::{
     constraints::{
         boundary::{BoundaryConstraint, BoundaryConstraints},
+        program::ConstraintProgram,
         transition::TransitionConstraint,
     },
     context::AirContext,
@@ -9,15 +10,18 @@ use crate::{
     trace::TraceTable,
     traits::AIR,
 };
-use lambdaworks_math::field::{element::FieldElement, traits::IsFFTField};
+use lambdaworks_math::field::{
+    element::FieldElement,
+    traits::{IsFFTField, IsField, IsSubFieldOf},
+};
 use std::marker::PhantomData;
 
 #[derive(Clone)]
-struct FibConstraint<F: IsFFTField> {
-    phantom: PhantomData<F>,
+struct FibConstraint<F: IsFFTField, E: IsField> {
+    phantom: PhantomData<(F, E)>,
 }
 
-impl<F: IsFFTField> FibConstraint<F> {
+impl<F: IsFFTField, E: IsField> FibConstraint<F, E> {
     pub fn new() -> Self {
         Self {
             phantom: PhantomData,
@@ -25,9 +29,10 @@ impl<F: IsFFTField> FibConstraint<F> {
     }
 }
 
-impl<F> TransitionConstraint<F, F> for FibConstraint<F>
+impl<F, E> TransitionConstraint<F, E> for FibConstraint<F, E>
 where
-    F: IsFFTField + Send + Sync,
+    F: IsFFTField + IsSubFieldOf<E> + Send + Sync,
+    E: IsField + Send + Sync,
 {
     fn degree(&self) -> usize {
         1
@@ -43,10 +48,10 @@ where
 
     fn evaluate(
         &self,
-        frame: &Frame<F, F>,
-        transition_evaluations: &mut [FieldElement<F>],
+        frame: &Frame<F, E>,
+        transition_evaluations: &mut [FieldElement<E>],
         _periodic_values: &[FieldElement<F>],
-        _rap_challenges: &[FieldElement<F>],
+        _rap_challenges: &[FieldElement<E>],
     ) {
         let first_step = frame.get_evaluation_step(0);
         let second_step = frame.get_evaluation_step(1);
@@ -58,18 +63,22 @@ where
 
         let res = a2 - a1 - a0;
 
-        transition_evaluations[self.constraint_idx()] = res;
+        transition_evaluations[self.constraint_idx()] = res.to_extension();
     }
 }
 
-pub struct FibonacciAIR<F>
+/// The AIR of a Fibonacci sequence over `F`, starting at the public inputs `a0` and `a1`. Its
+/// challenges are sampled from `E`, so small fields such as Babybear can take one of their
+/// extensions to be secure.
+pub struct FibonacciAIR<F, E = F>
 where
     F: IsFFTField,
+    E: IsField,
 {
     context: AirContext,
     trace_length: usize,
     pub_inputs: FibonacciPublicInputs<F>,
-    constraints: Vec<Box<dyn TransitionConstraint<F, F>>>,
+    constraints: Vec<Box<dyn TransitionConstraint<F, E>>>,
 }
 
 #[derive(Clone, Debug)]
@@ -81,12 +90,13 @@ where
     pub a1: FieldElement<F>,
 }
 
-impl<F> AIR for FibonacciAIR<F>
+impl<F, E> AIR for FibonacciAIR<F, E>
 where
-    F: IsFFTField + Send + Sync + 'static,
+    F: IsFFTField + IsSubFieldOf<E> + Send + Sync + 'static,
+    E: IsField + Send + Sync + 'static,
 {
     type Field = F;
-    type FieldExtension = F;
+    type FieldExtension = E;
     type PublicInputs = FibonacciPublicInputs<Self::Field>;
 
     const STEP_SIZE: usize = 1;
@@ -96,7 +106,7 @@ where
         pub_inputs: &Self::PublicInputs,
         proof_options: &ProofOptions,
     ) -> Self {
-        let constraints: Vec<Box<dyn TransitionConstraint<F, F>>> =
+        let constraints: Vec<Box<dyn TransitionConstraint<F, E>>> =
             vec![Box::new(FibConstraint::new())];
 
         let context = AirContext {
@@ -119,16 +129,29 @@ where
         self.trace_length()
     }
 
-    fn transition_constraints(&self) -> &Vec<Box<dyn TransitionConstraint<F, F>>> {
+    fn transition_constraints(&self) -> &Vec<Box<dyn TransitionConstraint<F, E>>> {
         &self.constraints
     }
 
+    fn transition_program(&self) -> Option<ConstraintProgram<E>> {
+        // a2 - a1 - a0
+        let mut program = ConstraintProgram::new();
+        program
+            .main(2, 0, 0)
+            .main(1, 0, 0)
+            .sub()
+            .main(0, 0, 0)
+            .sub()
+            .store(0);
+        Some(program)
+    }
+
     fn boundary_constraints(
         &self,
-        _rap_challenges: &[FieldElement<Self::Field>],
-    ) -> BoundaryConstraints<Self::Field> {
-        let a0 = BoundaryConstraint::new_simple_main(0, self.pub_inputs.a0.clone());
-        let a1 = BoundaryConstraint::new_simple_main(1, self.pub_inputs.a1.clone());
+        _rap_challenges: &[FieldElement<Self::FieldExtension>],
+    ) -> BoundaryConstraints<Self::FieldExtension> {
+        let a0 = BoundaryConstraint::new_simple_main(0, self.pub_inputs.a0.clone().to_extension());
+        let a1 = BoundaryConstraint::new_simple_main(1, self.pub_inputs.a1.clone().to_extension());
 
         BoundaryConstraints::from_constraints(vec![a0, a1])
     }
@@ -154,8 +177,14 @@ where
         frame: &Frame<Self::FieldExtension, Self::FieldExtension>,
         periodic_values: &[FieldElement<Self::FieldExtension>],
         rap_challenges: &[FieldElement<Self::FieldExtension>],
-    ) -> Vec<FieldElement<Self::Field>> {
-        self.compute_transition_prover(frame, periodic_values, rap_challenges)
+    ) -> Vec<FieldElement<Self::FieldExtension>> {
+        // The constraint reads the main trace in `F`, while the out of domain frame is in `E`,
+        // so the verifier evaluates it as a program.
+        let mut evaluations = vec![FieldElement::zero(); self.num_transition_constraints()];
+        self.transition_program()
+            .expect("the transition program is always defined")
+            .evaluate(frame, &mut evaluations, periodic_values, rap_challenges);
+        evaluations
     }
 }
 
@@ -0,0 +1,225 @@
+//! Low degree tests of standalone columns, on the domains their field has.
+//!
+//! [`TwoAdicBackend`] extends the columns on cosets of multiplicative subgroups, which fields
+//! with large two-adic subgroups have, and [`CircleBackend`] on the circle domains of
+//! Mersenne31, which has none. Code that is generic over [`IsLowDegreeTestBackend`] selects the
+//! backend with a type parameter.
+//!
+//! The AIR prover of [`crate::prover`] doesn't go through these backends: it always extends the
+//! trace on two-adic cosets, so an AIR over Mersenne31 is still proven over its quadratic
+//! extension. The backends only test columns handed to them by the caller.
+
+use lambdaworks_crypto::fiat_shamir::is_transcript::IsTranscript;
+use lambdaworks_math::{
+    field::{
+        element::FieldElement,
+        fields::mersenne31::field::Mersenne31Field,
+        traits::{IsFFTField, IsField, IsSubFieldOf},
+    },
+    polynomial::Polynomial,
+    traits::AsBytes,
+};
+
+use crate::{proof::options::ProofOptions, prover::ProvingError};
+
+use super::{
+    batch_prove, batch_verify,
+    circle::{self, CircleFriProof},
+    BatchedFriProof,
+};
+
+/// Commits to the columns of a trace over `F` and proves that they are the evaluations of
+/// polynomials of degree less than its length, with challenges sampled in `E`.
+pub trait IsLowDegreeTestBackend<F: IsField, E: IsField> {
+    type Proof;
+
+    /// Proves that the columns of equal length `columns` are of low degree. Zero-knowledge
+    /// options are rejected, since the backends don't blind the columns.
+    fn prove(
+        columns: &[Vec<FieldElement<F>>],
+        options: &ProofOptions,
+        transcript: &mut impl IsTranscript<E>,
+    ) -> Result<Self::Proof, ProvingError>;
+
+    /// Verifies a proof of [`IsLowDegreeTestBackend::prove`] for `number_of_columns` columns of
+    /// `trace_length` rows, with the same `options` and transcript.
+    fn verify(
+        proof: &Self::Proof,
+        trace_length: usize,
+        number_of_columns: usize,
+        options: &ProofOptions,
+        transcript: &mut impl IsTranscript<E>,
+    ) -> bool;
+}
+
+/// Extends the columns on a coset of a multiplicative subgroup of `F` and runs
+/// [`batch_prove`] on their interpolating polynomials.
+pub struct TwoAdicBackend;
+
+impl<F, E> IsLowDegreeTestBackend<F, E> for TwoAdicBackend
+where
+    F: IsFFTField + IsSubFieldOf<E>,
+    E: IsField,
+    FieldElement<F>: AsBytes + Sync + Send,
+    FieldElement<E>: AsBytes + Sync + Send,
+{
+    type Proof = BatchedFriProof<E>;
+
+    fn prove(
+        columns: &[Vec<FieldElement<F>>],
+        options: &ProofOptions,
+        transcript: &mut impl IsTranscript<E>,
+    ) -> Result<Self::Proof, ProvingError> {
+        if options.zk {
+            return Err(ProvingError::WrongParameter(
+                "Zero-knowledge isn't supported by the domain backends".to_string(),
+            ));
+        }
+        let trace_length = columns.first().map_or(0, Vec::len);
+        if columns.iter().any(|column| column.len() != trace_length) {
+            return Err(ProvingError::WrongParameter(
+                "Columns have different lengths".to_string(),
+            ));
+        }
+        let polys = columns
+            .iter()
+            .map(|column| {
+                Polynomial::<FieldElement<F>>::interpolate_fft::<F>(column)
+                    .map(Polynomial::to_extension)
+                    .map_err(|_| ProvingError::WrongParameter("Invalid trace length".to_string()))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        batch_prove::<F, E>(
+            &polys,
+            &vec![trace_length; columns.len()],
+            options,
+            transcript,
+        )
+    }
+
+    fn verify(
+        proof: &Self::Proof,
+        trace_length: usize,
+        number_of_columns: usize,
+        options: &ProofOptions,
+        transcript: &mut impl IsTranscript<E>,
+    ) -> bool {
+        !options.zk
+            && batch_verify::<F, E>(
+                proof,
+                &vec![trace_length; number_of_columns],
+                options,
+                transcript,
+            )
+    }
+}
+
+/// Extends the columns of a trace over Mersenne31 on a circle domain with
+/// [`circle::prove`].
+pub struct CircleBackend;
+
+impl<E> IsLowDegreeTestBackend<Mersenne31Field, E> for CircleBackend
+where
+    E: IsField,
+    Mersenne31Field: IsSubFieldOf<E>,
+    FieldElement<E>: AsBytes + Sync + Send,
+{
+    type Proof = CircleFriProof<E>;
+
+    fn prove(
+        columns: &[Vec<FieldElement<Mersenne31Field>>],
+        options: &ProofOptions,
+        transcript: &mut impl IsTranscript<E>,
+    ) -> Result<Self::Proof, ProvingError> {
+        circle::prove(columns, options, transcript)
+    }
+
+    fn verify(
+        proof: &Self::Proof,
+        trace_length: usize,
+        number_of_columns: usize,
+        options: &ProofOptions,
+        transcript: &mut impl IsTranscript<E>,
+    ) -> bool {
+        circle::verify(proof, trace_length, number_of_columns, options, transcript)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use lambdaworks_crypto::fiat_shamir::{
+        default_transcript::DefaultTranscript, extension_transcript::ExtensionTranscript,
+    };
+    use lambdaworks_math::field::fields::{
+        fft_friendly::{babybear::Babybear31PrimeField, quartic_babybear::QuarticBabybearField},
+        mersenne31::extensions::Degree4ExtensionField,
+    };
+
+    use super::*;
+
+    fn fibonacci_columns<F: IsField>(trace_length: usize) -> Vec<Vec<FieldElement<F>>> {
+        let mut a = vec![FieldElement::one()];
+        let mut b = vec![FieldElement::one()];
+        for i in 1..trace_length {
+            a.push(b[i - 1].clone());
+            b.push(&a[i - 1] + &b[i - 1]);
+        }
+        vec![a, b]
+    }
+
+    fn transcript<F, E>() -> ExtensionTranscript<F, E, DefaultTranscript<F>>
+    where
+        F: IsField + IsSubFieldOf<E>,
+        E: IsField,
+        FieldElement<F>: lambdaworks_math::traits::ByteConversion,
+    {
+        ExtensionTranscript::new(DefaultTranscript::new(&[]))
+    }
+
+    /// Proves and verifies the columns of a Fibonacci trace with the backend `B`.
+    fn prove_and_verify<B, F, E>(options: &ProofOptions) -> Result<bool, ProvingError>
+    where
+        B: IsLowDegreeTestBackend<F, E>,
+        F: IsField + IsSubFieldOf<E>,
+        E: IsField,
+        FieldElement<F>: lambdaworks_math::traits::ByteConversion,
+    {
+        let columns = fibonacci_columns::<F>(16);
+        let proof = B::prove(&columns, options, &mut transcript())?;
+        Ok(B::verify(&proof, 16, 2, options, &mut transcript()))
+    }
+
+    #[test]
+    fn each_backend_proves_a_trace_over_its_field() {
+        let options = ProofOptions::default_test_options();
+        assert!(
+            prove_and_verify::<TwoAdicBackend, Babybear31PrimeField, QuarticBabybearField>(
+                &options
+            )
+            .unwrap()
+        );
+        assert!(
+            prove_and_verify::<CircleBackend, Mersenne31Field, Degree4ExtensionField>(&options)
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn backends_reject_zero_knowledge_options() {
+        let options = ProofOptions {
+            zk: true,
+            ..ProofOptions::default_test_options()
+        };
+        assert!(
+            prove_and_verify::<TwoAdicBackend, Babybear31PrimeField, QuarticBabybearField>(
+                &options
+            )
+            .is_err()
+        );
+        assert!(
+            prove_and_verify::<CircleBackend, Mersenne31Field, Degree4ExtensionField>(&options)
+                .is_err()
+        );
+    }
+}
@@ -0,0 +1,590 @@
+//! FRI over the circle domains of Mersenne31, from [Circle STARKs](https://eprint.iacr.org/2024/278).
+//!
+//! Mersenne31 has no large multiplicative subgroups of power of two order, so its columns can't
+//! be extended with the FFTs of [`Domain`](crate::domain::Domain). Instead, a column of `n` rows
+//! is interpolated as a circle polynomial on the [`CircleDomain`] of `n` points and evaluated on
+//! the one of `n * blowup_factor` points, which doesn't meet it.
+//!
+//! The first fold of FRI takes `f(x, y) = f₀(x) + y·f₁(x)` to `f₀ + β·f₁`, a polynomial in `x`,
+//! and the following ones take `g(x) = g₀(2x² - 1) + x·g₁(2x² - 1)` to `g₀ + β·g₁`, until the
+//! polynomial is constant. The points opened together in each layer are a point and its
+//! conjugate in the first one, and two points with opposite `x` in the others.
+use lambdaworks_crypto::{fiat_shamir::is_transcript::IsTranscript, merkle_tree::proof::Proof};
+use lambdaworks_math::{
+    circle::{
+        cfft::{evaluate, interpolate},
+        domain::{half_coset_points, CircleDomain},
+        point::CirclePoint,
+    },
+    cyclic_group::IsGroup,
+    field::{
+        element::FieldElement,
+        fields::mersenne31::field::Mersenne31Field,
+        traits::{IsField, IsSubFieldOf},
+    },
+    traits::AsBytes,
+};
+
+use crate::{
+    config::{BatchedMerkleTree, Commitment},
+    grinding,
+    proof::options::ProofOptions,
+    prover::ProvingError,
+};
+
+use super::{fri_decommit::FriDecommitment, sample_query_indexes};
+
+type FpE = FieldElement<Mersenne31Field>;
+type Point = CirclePoint<Mersenne31Field>;
+
+/// The openings of a query of a [`CircleFriProof`].
+#[derive(Debug, Clone)]
+pub struct CircleFriQuery<E: IsField> {
+    /// Evaluations of the columns at the point of the query.
+    pub evaluations: Vec<FpE>,
+    /// Evaluations of the columns at the conjugate of the point.
+    pub evaluations_conjugate: Vec<FpE>,
+    /// Proof of both evaluations, which share a leaf of the commitment to the columns.
+    pub auth_path: Proof<Commitment>,
+    /// Openings of the inner layers of FRI.
+    pub decommitment: FriDecommitment<E>,
+}
+
+/// A proof that the columns of a trace over Mersenne31 are the evaluations of circle polynomials
+/// of degree less than its length, given by [`prove`].
+#[derive(Debug, Clone)]
+pub struct CircleFriProof<E: IsField> {
+    /// Commitment to the evaluations of the columns on the domain of the low degree extension.
+    pub columns_merkle_root: Commitment,
+    pub layers_merkle_roots: Vec<Commitment>,
+    /// The value of the last layer, which is constant.
+    pub last_layer_value: FieldElement<E>,
+    pub nonce: Option<u64>,
+    pub queries: Vec<CircleFriQuery<E>>,
+}
+
+/// A layer of circle FRI: the evaluations of a polynomial in `x` and their commitment.
+struct CircleFriLayer<E: IsField>
+where
+    FieldElement<E>: AsBytes + Sync + Send,
+{
+    evaluations: Vec<FieldElement<E>>,
+    merkle_tree: BatchedMerkleTree<E>,
+}
+
+/// Commits to the low degree extensions of `columns`, the evaluations of the columns of a trace
+/// over Mersenne31 on the [`CircleDomain`] of their length, and proves with FRI that they are
+/// the evaluations of circle polynomials of degree less than it. FRI runs on the random linear
+/// combination `∑ αⁱ·cᵢ` of the columns, with `α` sampled from the transcript in `E`.
+///
+/// The blowup factor, number of queries, grinding factor and hash of the Merkle trees are taken
+/// from `options`. The coset offset isn't used, since the domains of the low degree extensions
+/// don't meet the ones of the traces, and FRI folds the polynomial until it's constant. The
+/// columns aren't blinded, so zero-knowledge options are rejected. Check the proof with
+/// [`verify`].
+pub fn prove<E>(
+    columns: &[Vec<FpE>],
+    options: &ProofOptions,
+    transcript: &mut impl IsTranscript<E>,
+) -> Result<CircleFriProof<E>, ProvingError>
+where
+    E: IsField,
+    Mersenne31Field: IsSubFieldOf<E>,
+    FieldElement<E>: AsBytes + Sync + Send,
+{
+    if options.zk {
+        return Err(ProvingError::WrongParameter(
+            "Zero-knowledge isn't supported by circle FRI".to_string(),
+        ));
+    }
+    let trace_length = columns.first().map_or(0, Vec::len);
+    if columns.iter().any(|column| column.len() != trace_length) {
+        return Err(ProvingError::WrongParameter(
+            "Columns have different lengths".to_string(),
+        ));
+    }
+    let (trace_domain, lde_domain) = domains(trace_length, options).ok_or_else(|| {
+        ProvingError::WrongParameter("Invalid trace length or blowup factor".to_string())
+    })?;
+
+    let lde_columns: Vec<Vec<FpE>> = columns
+        .iter()
+        .map(|column| {
+            evaluate::<Mersenne31Field>(
+                &interpolate::<Mersenne31Field>(column, &trace_domain),
+                &lde_domain,
+            )
+        })
+        .collect();
+
+    Ok(prove_low_degree(
+        &lde_columns,
+        &lde_domain,
+        trace_domain.log_size(),
+        options,
+        transcript,
+    ))
+}
+
+/// Runs circle FRI on the evaluations `lde_columns` on `lde_domain`, for polynomials with
+/// `2^log_degree_bound` coefficients.
+fn prove_low_degree<E>(
+    lde_columns: &[Vec<FpE>],
+    lde_domain: &CircleDomain,
+    log_degree_bound: u32,
+    options: &ProofOptions,
+    transcript: &mut impl IsTranscript<E>,
+) -> CircleFriProof<E>
+where
+    E: IsField,
+    Mersenne31Field: IsSubFieldOf<E>,
+    FieldElement<E>: AsBytes + Sync + Send,
+{
+    let half_size = lde_domain.size() / 2;
+
+    // Commit to the evaluations of the columns, with the ones at a point and its conjugate in the
+    // same leaf.
+    let leaves: Vec<Vec<FpE>> = (0..half_size)
+        .map(|leaf| {
+            let mut row: Vec<_> = lde_columns.iter().map(|c| c[leaf]).collect();
+            row.extend(lde_columns.iter().map(|c| c[leaf + half_size]));
+            row
+        })
+        .collect();
+    let columns_merkle_tree =
+        BatchedMerkleTree::<Mersenne31Field>::build(options.merkle_hash, &leaves).unwrap();
+    transcript.append_bytes(&columns_merkle_tree.root());
+
+    let alpha: FieldElement<E> = transcript.sample_field_element();
+    let combined: Vec<FieldElement<E>> = (0..lde_domain.size())
+        .map(|i| combine(lde_columns.iter().map(|c| &c[i]), &alpha))
+        .collect();
+
+    // Fold on `y`, and then on `x` until the polynomial is constant.
+    let mut q = lde_domain.generator();
+    let beta = transcript.sample_field_element();
+    let mut evaluations = fold_circle(&combined, &q, &beta);
+    let mut layers: Vec<CircleFriLayer<E>> = Vec::new();
+    for _ in 1..log_degree_bound {
+        let merkle_tree = commit_line(&evaluations, options);
+        transcript.append_bytes(&merkle_tree.root());
+        let beta = transcript.sample_field_element();
+        let folded = fold_line(&evaluations, &q, &beta);
+        layers.push(CircleFriLayer {
+            evaluations,
+            merkle_tree,
+        });
+        evaluations = folded;
+        q = q.double();
+    }
+
+    let last_layer_value = evaluations[0].clone();
+    transcript.append_field_element(&last_layer_value);
+
+    let mut nonce = None;
+    if options.grinding_factor > 0 {
+        let nonce_value = grinding::generate_nonce(&transcript.state(), options.grinding_factor)
+            .expect("nonce not found");
+        transcript.append_bytes(&nonce_value.to_be_bytes());
+        nonce = Some(nonce_value);
+    }
+
+    let iotas = sample_query_indexes(options.fri_number_of_queries, lde_domain.size(), transcript);
+    let queries = iotas
+        .iter()
+        .map(|iota| CircleFriQuery {
+            evaluations: lde_columns.iter().map(|c| c[*iota]).collect(),
+            evaluations_conjugate: lde_columns.iter().map(|c| c[iota + half_size]).collect(),
+            auth_path: columns_merkle_tree.get_proof_by_pos(*iota).unwrap(),
+            decommitment: open_layers(&layers, *iota),
+        })
+        .collect();
+
+    CircleFriProof {
+        columns_merkle_root: columns_merkle_tree.root(),
+        layers_merkle_roots: layers
+            .iter()
+            .map(|layer| layer.merkle_tree.root())
+            .collect(),
+        last_layer_value,
+        nonce,
+        queries,
+    }
+}
+
+/// Verifies a [`CircleFriProof`] that the `number_of_columns` columns of a trace of
+/// `trace_length` rows committed in it are evaluations of circle polynomials of degree less than
+/// `trace_length`, with the same `options` and transcript as [`prove`].
+pub fn verify<E>(
+    proof: &CircleFriProof<E>,
+    trace_length: usize,
+    number_of_columns: usize,
+    options: &ProofOptions,
+    transcript: &mut impl IsTranscript<E>,
+) -> bool
+where
+    E: IsField,
+    Mersenne31Field: IsSubFieldOf<E>,
+    FieldElement<E>: AsBytes + Sync + Send,
+{
+    let Some((trace_domain, lde_domain)) = domains(trace_length, options) else {
+        return false;
+    };
+    if options.zk
+        || number_of_columns == 0
+        || proof.layers_merkle_roots.len() != trace_domain.log_size() as usize - 1
+    {
+        return false;
+    }
+
+    // Replay the transcript
+    transcript.append_bytes(&proof.columns_merkle_root);
+    let alpha: FieldElement<E> = transcript.sample_field_element();
+    let mut betas = vec![transcript.sample_field_element()];
+    for root in &proof.layers_merkle_roots {
+        transcript.append_bytes(root);
+        betas.push(transcript.sample_field_element());
+    }
+    transcript.append_field_element(&proof.last_layer_value);
+    if options.grinding_factor > 0 {
+        let nonce_is_valid = proof.nonce.map_or(false, |nonce_value| {
+            let is_valid =
+                grinding::is_valid_nonce(&transcript.state(), nonce_value, options.grinding_factor);
+            transcript.append_bytes(&nonce_value.to_be_bytes());
+            is_valid
+        });
+        if !nonce_is_valid {
+            return false;
+        }
+    }
+    let iotas = sample_query_indexes(options.fri_number_of_queries, lde_domain.size(), transcript);
+    if proof.queries.len() != iotas.len() {
+        return false;
+    }
+
+    proof.queries.iter().zip(&iotas).all(|(query, iota)| {
+        if query.evaluations.len() != number_of_columns
+            || query.evaluations_conjugate.len() != number_of_columns
+        {
+            return false;
+        }
+
+        let leaf: Vec<_> = query
+            .evaluations
+            .iter()
+            .chain(&query.evaluations_conjugate)
+            .cloned()
+            .collect();
+        if !options.merkle_hash.verify::<Mersenne31Field>(
+            &query.auth_path,
+            &proof.columns_merkle_root,
+            *iota,
+            &leaf,
+        ) {
+            return false;
+        }
+
+        let evaluation = combine(query.evaluations.iter(), &alpha);
+        let evaluation_conjugate = combine(query.evaluations_conjugate.iter(), &alpha);
+        let point = lde_domain.point(*iota);
+        let value = fold(&evaluation, &evaluation_conjugate, &point.y, &betas[0]);
+
+        verify_layers(
+            options,
+            &proof.layers_merkle_roots,
+            &query.decommitment,
+            &betas[1..],
+            &lde_domain,
+            *iota,
+            value,
+        )
+        .is_some_and(|value| value == proof.last_layer_value)
+    })
+}
+
+/// Checks the openings of the inner layers of a query at `iota`, where the first inner layer
+/// takes `value`, and returns the value of the query in the last layer, or `None` if an opening
+/// is wrong.
+fn verify_layers<E>(
+    options: &ProofOptions,
+    layers_merkle_roots: &[Commitment],
+    decommitment: &FriDecommitment<E>,
+    betas: &[FieldElement<E>],
+    lde_domain: &CircleDomain,
+    iota: usize,
+    mut value: FieldElement<E>,
+) -> Option<FieldElement<E>>
+where
+    E: IsField,
+    Mersenne31Field: IsSubFieldOf<E>,
+    FieldElement<E>: AsBytes + Sync + Send,
+{
+    if decommitment.layers_evaluations_sym.len() != layers_merkle_roots.len()
+        || decommitment.layers_auth_paths.len() != layers_merkle_roots.len()
+        || decommitment.layers_salts.len() != layers_merkle_roots.len()
+    {
+        return None;
+    }
+
+    let mut q = lde_domain.generator();
+    let mut layer_size = lde_domain.size() / 2;
+    let mut index = iota;
+    for (((root, auth_path), evaluation_sym), beta) in layers_merkle_roots
+        .iter()
+        .zip(&decommitment.layers_auth_paths)
+        .zip(&decommitment.layers_evaluations_sym)
+        .zip(betas)
+    {
+        // The value at `index` shares a leaf with the one at the point with opposite `x`.
+        let leaf_index = index.min(layer_size - 1 - index);
+        let leaf = if index == leaf_index {
+            vec![value, evaluation_sym.clone()]
+        } else {
+            vec![evaluation_sym.clone(), value]
+        };
+        if !options
+            .merkle_hash
+            .verify(auth_path, root, leaf_index, &leaf)
+        {
+            return None;
+        }
+
+        let point = q.operate_with_self(2 * leaf_index as u64 + 1);
+        value = fold(&leaf[0], &leaf[1], &point.x, beta);
+        q = q.double();
+        layer_size /= 2;
+        index = leaf_index;
+    }
+    Some(value)
+}
+
+/// The domains of a trace of `trace_length` rows and of its low degree extension, or `None` if
+/// the length or the blowup factor of `options` isn't a power of two larger than one, or the
+/// extension is too large.
+fn domains(trace_length: usize, options: &ProofOptions) -> Option<(CircleDomain, CircleDomain)> {
+    let blowup_factor = options.blowup_factor as usize;
+    if trace_length < 2
+        || !trace_length.is_power_of_two()
+        || blowup_factor < 2
+        || !blowup_factor.is_power_of_two()
+    {
+        return None;
+    }
+    let log_trace_length = trace_length.trailing_zeros();
+    let log_lde_size = log_trace_length + blowup_factor.trailing_zeros();
+    if log_lde_size > CircleDomain::MAX_LOG_SIZE {
+        return None;
+    }
+    Some((
+        CircleDomain::new(log_trace_length),
+        CircleDomain::new(log_lde_size),
+    ))
+}
+
+/// The random linear combination `∑ αⁱ·cᵢ` of `values`.
+fn combine<'a, E>(values: impl Iterator<Item = &'a FpE>, alpha: &FieldElement<E>) -> FieldElement<E>
+where
+    E: IsField,
+    Mersenne31Field: IsSubFieldOf<E>,
+{
+    let mut alpha_power = FieldElement::<E>::one();
+    let mut result = FieldElement::<E>::zero();
+    for value in values {
+        result += value * &alpha_power;
+        alpha_power = &alpha_power * alpha;
+    }
+    result
+}
+
+/// Folds the values `a` and `b` of a polynomial at two points whose coordinate `t` is opposite,
+/// `t` and `-t`, into `(a + b) / 2 + β·(a - b) / 2t`.
+fn fold<E>(
+    a: &FieldElement<E>,
+    b: &FieldElement<E>,
+    t: &FpE,
+    beta: &FieldElement<E>,
+) -> FieldElement<E>
+where
+    E: IsField,
+    Mersenne31Field: IsSubFieldOf<E>,
+{
+    let half_inverse = FpE::from(2).inv().unwrap();
+    half_inverse * (a + b) + t.double().inv().unwrap() * beta * (a - b)
+}
+
+/// Folds the evaluations on a circle domain with generator `q` on `y`.
+fn fold_circle<E>(
+    evaluations: &[FieldElement<E>],
+    q: &Point,
+    beta: &FieldElement<E>,
+) -> Vec<FieldElement<E>>
+where
+    E: IsField,
+    Mersenne31Field: IsSubFieldOf<E>,
+{
+    let half_size = evaluations.len() / 2;
+    half_coset_points(q, half_size)
+        .iter()
+        .enumerate()
+        .map(|(i, point)| fold(&evaluations[i], &evaluations[i + half_size], &point.y, beta))
+        .collect()
+}
+
+/// Folds the evaluations of a polynomial in `x` on the `x` coordinates of the first
+/// `evaluations.len()` odd multiples of `q`, where the one at `i` and the one at
+/// `evaluations.len() - 1 - i` are opposite.
+fn fold_line<E>(
+    evaluations: &[FieldElement<E>],
+    q: &Point,
+    beta: &FieldElement<E>,
+) -> Vec<FieldElement<E>>
+where
+    E: IsField,
+    Mersenne31Field: IsSubFieldOf<E>,
+{
+    let len = evaluations.len();
+    half_coset_points(q, len / 2)
+        .iter()
+        .enumerate()
+        .map(|(i, point)| fold(&evaluations[i], &evaluations[len - 1 - i], &point.x, beta))
+        .collect()
+}
+
+/// Commits to the evaluations of a polynomial in `x`, with the ones at opposite points in the
+/// same leaf.
+fn commit_line<E>(evaluations: &[FieldElement<E>], options: &ProofOptions) -> BatchedMerkleTree<E>
+where
+    E: IsField,
+    FieldElement<E>: AsBytes + Sync + Send,
+{
+    let len = evaluations.len();
+    let leaves: Vec<Vec<FieldElement<E>>> = (0..len / 2)
+        .map(|i| vec![evaluations[i].clone(), evaluations[len - 1 - i].clone()])
+        .collect();
+    BatchedMerkleTree::build(options.merkle_hash, &leaves).unwrap()
+}
+
+/// Opens the inner layers of FRI at the query `iota`.
+fn open_layers<E>(layers: &[CircleFriLayer<E>], iota: usize) -> FriDecommitment<E>
+where
+    E: IsField,
+    FieldElement<E>: AsBytes + Sync + Send,
+{
+    let mut decommitment = FriDecommitment {
+        layers_auth_paths: Vec::new(),
+        layers_evaluations_sym: Vec::new(),
+        layers_salts: Vec::new(),
+    };
+    let mut index = iota;
+    for layer in layers {
+        let len = layer.evaluations.len();
+        let leaf_index = index.min(len - 1 - index);
+        decommitment
+            .layers_evaluations_sym
+            .push(layer.evaluations[len - 1 - index].clone());
+        decommitment
+            .layers_auth_paths
+            .push(layer.merkle_tree.get_proof_by_pos(leaf_index).unwrap());
+        decommitment.layers_salts.push(Vec::new());
+        index = leaf_index;
+    }
+    decommitment
+}
+
+#[cfg(test)]
+mod tests {
+    use lambdaworks_crypto::fiat_shamir::{
+        default_transcript::DefaultTranscript, extension_transcript::ExtensionTranscript,
+    };
+    use lambdaworks_math::field::fields::mersenne31::extensions::Degree4ExtensionField;
+
+    use super::*;
+
+    type E = Degree4ExtensionField;
+    type Transcript = ExtensionTranscript<Mersenne31Field, E, DefaultTranscript<Mersenne31Field>>;
+
+    fn transcript() -> Transcript {
+        ExtensionTranscript::new(DefaultTranscript::new(&[]))
+    }
+
+    fn fibonacci_columns(trace_length: usize) -> Vec<Vec<FpE>> {
+        let mut a = vec![FpE::one()];
+        let mut b = vec![FpE::one()];
+        for i in 1..trace_length {
+            a.push(b[i - 1]);
+            b.push(a[i - 1] + b[i - 1]);
+        }
+        vec![a, b]
+    }
+
+    #[test]
+    fn circle_fri_proof_of_a_trace_is_valid() {
+        let options = ProofOptions::default_test_options();
+        for trace_length in [2, 4, 32] {
+            let columns = fibonacci_columns(trace_length);
+            let proof = prove::<E>(&columns, &options, &mut transcript()).unwrap();
+            assert!(verify(&proof, trace_length, 2, &options, &mut transcript()));
+        }
+    }
+
+    #[test]
+    fn circle_fri_proof_with_a_tampered_evaluation_is_invalid() {
+        let options = ProofOptions::default_test_options();
+        let mut proof = prove::<E>(&fibonacci_columns(16), &options, &mut transcript()).unwrap();
+        proof.queries[0].evaluations[1] += FpE::one();
+        assert!(!verify(&proof, 16, 2, &options, &mut transcript()));
+    }
+
+    #[test]
+    fn circle_fri_proof_with_another_transcript_is_invalid() {
+        let options = ProofOptions::default_test_options();
+        let proof = prove::<E>(&fibonacci_columns(16), &options, &mut transcript()).unwrap();
+        assert!(!verify(
+            &proof,
+            16,
+            2,
+            &options,
+            &mut Transcript::new(DefaultTranscript::new(&[1]))
+        ));
+    }
+
+    #[test]
+    fn circle_fri_rejects_evaluations_of_a_polynomial_of_larger_degree() {
+        let options = ProofOptions {
+            fri_number_of_queries: 30,
+            ..ProofOptions::default_test_options()
+        };
+        let lde_domain = CircleDomain::new(6);
+        let coefficients: Vec<FpE> = (0..32).map(FpE::from).collect();
+        let lde_column = evaluate(&coefficients, &lde_domain);
+
+        // The evaluations extend a trace of 32 rows, not one of 16.
+        let proof =
+            prove_low_degree::<E>(&[lde_column], &lde_domain, 4, &options, &mut transcript());
+        assert!(!verify(&proof, 16, 1, &options, &mut transcript()));
+    }
+
+    #[test]
+    fn circle_fri_rejects_traces_without_a_circle_domain() {
+        let options = ProofOptions::default_test_options();
+        assert!(prove::<E>(&fibonacci_columns(12), &options, &mut transcript()).is_err());
+        assert!(prove::<E>(&fibonacci_columns(1), &options, &mut transcript()).is_err());
+
+        let mut columns = fibonacci_columns(16);
+        columns[1].pop();
+        assert!(prove::<E>(&columns, &options, &mut transcript()).is_err());
+    }
+
+    #[test]
+    fn circle_fri_rejects_zero_knowledge_options() {
+        let options = ProofOptions::default_test_options();
+        let proof = prove::<E>(&fibonacci_columns(16), &options, &mut transcript()).unwrap();
+
+        let zk_options = ProofOptions {
+            zk: true,
+            ..options
+        };
+        assert!(prove::<E>(&fibonacci_columns(16), &zk_options, &mut transcript()).is_err());
+        assert!(!verify(&proof, 16, 2, &zk_options, &mut transcript()));
+    }
+}
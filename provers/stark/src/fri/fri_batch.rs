@@ -0,0 +1,413 @@
+use lambdaworks_crypto::{fiat_shamir::is_transcript::IsTranscript, merkle_tree::proof::Proof};
+use lambdaworks_math::{
+    fft::cpu::bit_reversing::{in_place_bit_reverse_permute, reverse_index},
+    field::{
+        element::FieldElement,
+        traits::{IsFFTField, IsField, IsSubFieldOf},
+    },
+    polynomial::Polynomial,
+    traits::AsBytes,
+};
+
+use crate::{
+    config::{BatchedMerkleTree, Commitment},
+    grinding,
+    proof::options::ProofOptions,
+    prover::ProvingError,
+};
+
+use super::{
+    commit_phase, fri_decommit::FriDecommitment, query_phase, sample_query_indexes, verify_query,
+};
+
+/// The openings of a query of a [`BatchedFriProof`].
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct BatchedFriQuery<E: IsField> {
+    /// Evaluations of the polynomials at the point 𝜐 of the query.
+    pub evaluations: Vec<FieldElement<E>>,
+    /// Evaluations of the polynomials at -𝜐.
+    pub evaluations_sym: Vec<FieldElement<E>>,
+    /// Proof of both evaluations, which share a leaf of the commitment to the polynomials.
+    pub auth_path: Proof<Commitment>,
+    /// Openings of the inner layers of FRI.
+    pub decommitment: FriDecommitment<E>,
+}
+
+/// A proof that several polynomials have degree less than their bounds, given by [`batch_prove`].
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct BatchedFriProof<E: IsField> {
+    /// Commitment to the evaluations of the polynomials on the domain of the first layer.
+    pub polys_merkle_root: Commitment,
+    pub layers_merkle_roots: Vec<Commitment>,
+    pub last_layer_coefficients: Vec<FieldElement<E>>,
+    pub nonce: Option<u64>,
+    pub queries: Vec<BatchedFriQuery<E>>,
+}
+
+/// Proves that each polynomial of `polys` has degree less than its bound in `degree_bounds`,
+/// with a single instance of FRI. The polynomials are committed together, and FRI runs on their
+/// random linear combination, with each polynomial `pᵢ` of bound `dᵢ` added as
+/// `(α²ⁱ + α²ⁱ⁺¹·x^(D - dᵢ))·pᵢ`, where `α` is sampled from the transcript and `D` is the largest
+/// bound rounded up to a power of two. The degree correction `x^(D - dᵢ)` lifts the bound of every polynomial to `D`, so that none can
+/// exceed its own bound without exceeding `D`.
+///
+/// The blowup factor, coset offset, number of queries, grinding factor and last layer of FRI are
+/// taken from `options`. Check the proof with [`batch_verify`].
+pub fn batch_prove<F, E>(
+    polys: &[Polynomial<FieldElement<E>>],
+    degree_bounds: &[usize],
+    options: &ProofOptions,
+    transcript: &mut impl IsTranscript<E>,
+) -> Result<BatchedFriProof<E>, ProvingError>
+where
+    F: IsFFTField + IsSubFieldOf<E>,
+    E: IsField,
+    FieldElement<F>: AsBytes + Sync + Send,
+    FieldElement<E>: AsBytes + Sync + Send,
+{
+    let max_degree_bound = check_degree_bounds(polys.len(), degree_bounds)
+        .ok_or_else(|| ProvingError::WrongParameter("Invalid degree bounds".to_string()))?;
+    if polys
+        .iter()
+        .zip(degree_bounds)
+        .any(|(poly, bound)| poly.coeff_len() > *bound)
+    {
+        return Err(ProvingError::WrongParameter(
+            "Polynomial exceeds its degree bound".to_string(),
+        ));
+    }
+
+    let number_layers = max_degree_bound.trailing_zeros() as usize;
+    let domain_size = max_degree_bound * options.blowup_factor as usize;
+    let coset_offset = FieldElement::<F>::from(options.coset_offset);
+
+    // Commit to the evaluations of the polynomials, with the ones at 𝜐 and -𝜐 in the same leaf.
+    let evaluations = polys
+        .iter()
+        .map(|poly| {
+            let mut evaluation =
+                Polynomial::evaluate_offset_fft(poly, 1, Some(domain_size), &coset_offset).unwrap();
+            in_place_bit_reverse_permute(&mut evaluation);
+            evaluation
+        })
+        .collect::<Vec<_>>();
+    let leaves: Vec<Vec<FieldElement<E>>> = (0..domain_size / 2)
+        .map(|leaf| {
+            let mut row: Vec<_> = evaluations.iter().map(|e| e[2 * leaf].clone()).collect();
+            row.extend(evaluations.iter().map(|e| e[2 * leaf + 1].clone()));
+            row
+        })
+        .collect();
+    let polys_merkle_tree = BatchedMerkleTree::<E>::build(options.merkle_hash, &leaves).unwrap();
+    transcript.append_bytes(&polys_merkle_tree.root());
+
+    // Combine the polynomials with degree correction.
+    let alpha: FieldElement<E> = transcript.sample_field_element();
+    let mut alpha_power = FieldElement::<E>::one();
+    let mut combined = Polynomial::zero();
+    for (poly, bound) in polys.iter().zip(degree_bounds) {
+        let mut shifted_coefficients = vec![FieldElement::zero(); max_degree_bound - bound];
+        shifted_coefficients.extend_from_slice(poly.coefficients());
+        let shifted = Polynomial::new(&shifted_coefficients);
+
+        combined = combined + poly * &alpha_power;
+        alpha_power = &alpha_power * &alpha;
+        combined = combined + shifted * &alpha_power;
+        alpha_power = &alpha_power * &alpha;
+    }
+
+    let (last_layer_coefficients, fri_layers) = commit_phase::<F, E>(
+        number_layers,
+        options.fri_last_layer_degree_bound,
+        combined,
+        transcript,
+        &coset_offset,
+        domain_size,
+        options.merkle_hash,
+        Vec::new,
+    );
+
+    let mut nonce = None;
+    if options.grinding_factor > 0 {
+        let nonce_value = grinding::generate_nonce(&transcript.state(), options.grinding_factor)
+            .expect("nonce not found");
+        transcript.append_bytes(&nonce_value.to_be_bytes());
+        nonce = Some(nonce_value);
+    }
+
+    let iotas = sample_query_indexes(options.fri_number_of_queries, domain_size, transcript);
+    let queries = query_phase(&fri_layers, &iotas)
+        .into_iter()
+        .zip(&iotas)
+        .map(|(decommitment, iota)| BatchedFriQuery {
+            evaluations: evaluations.iter().map(|e| e[2 * iota].clone()).collect(),
+            evaluations_sym: evaluations
+                .iter()
+                .map(|e| e[2 * iota + 1].clone())
+                .collect(),
+            auth_path: polys_merkle_tree.get_proof_by_pos(*iota).unwrap(),
+            decommitment,
+        })
+        .collect();
+
+    Ok(BatchedFriProof {
+        polys_merkle_root: polys_merkle_tree.root(),
+        layers_merkle_roots: fri_layers
+            .iter()
+            .map(|layer| layer.merkle_tree.root())
+            .collect(),
+        last_layer_coefficients,
+        nonce,
+        queries,
+    })
+}
+
+/// Verifies a [`BatchedFriProof`] that the polynomials committed in it have degree less than
+/// `degree_bounds`, with the same `options` and transcript as [`batch_prove`]. It checks the
+/// openings of the polynomials at each query, and FRI on their combination.
+pub fn batch_verify<F, E>(
+    proof: &BatchedFriProof<E>,
+    degree_bounds: &[usize],
+    options: &ProofOptions,
+    transcript: &mut impl IsTranscript<E>,
+) -> bool
+where
+    F: IsFFTField + IsSubFieldOf<E>,
+    E: IsField,
+    FieldElement<F>: AsBytes + Sync + Send,
+    FieldElement<E>: AsBytes + Sync + Send,
+{
+    let Some(max_degree_bound) = check_degree_bounds(degree_bounds.len(), degree_bounds) else {
+        return false;
+    };
+    let number_layers = max_degree_bound.trailing_zeros() as usize;
+    let domain_size = max_degree_bound * options.blowup_factor as usize;
+    let coset_offset = FieldElement::<F>::from(options.coset_offset);
+
+    let last_layer_degree_bound = options.fri_last_layer_degree_bound;
+    if proof.layers_merkle_roots.len()
+        != super::number_of_folds(number_layers, last_layer_degree_bound) - 1
+        || proof.last_layer_coefficients.len()
+            != super::last_layer_len(number_layers, last_layer_degree_bound)
+    {
+        return false;
+    }
+
+    // Replay the transcript
+    transcript.append_bytes(&proof.polys_merkle_root);
+    let alpha: FieldElement<E> = transcript.sample_field_element();
+    let mut zetas: Vec<FieldElement<E>> = proof
+        .layers_merkle_roots
+        .iter()
+        .map(|root| {
+            let zeta = transcript.sample_field_element();
+            transcript.append_bytes(root);
+            zeta
+        })
+        .collect();
+    zetas.push(transcript.sample_field_element());
+    for coefficient in proof.last_layer_coefficients.iter() {
+        transcript.append_field_element(coefficient);
+    }
+    if options.grinding_factor > 0 {
+        let nonce_is_valid = proof.nonce.map_or(false, |nonce_value| {
+            let is_valid =
+                grinding::is_valid_nonce(&transcript.state(), nonce_value, options.grinding_factor);
+            transcript.append_bytes(&nonce_value.to_be_bytes());
+            is_valid
+        });
+        if !nonce_is_valid {
+            return false;
+        }
+    }
+    let iotas = sample_query_indexes(options.fri_number_of_queries, domain_size, transcript);
+    if proof.queries.len() != iotas.len() {
+        return false;
+    }
+
+    let primitive_root =
+        F::get_primitive_root_of_unity(domain_size.trailing_zeros() as u64).unwrap();
+    let last_layer = Polynomial::new(&proof.last_layer_coefficients);
+
+    proof.queries.iter().zip(&iotas).all(|(query, iota)| {
+        if query.evaluations.len() != degree_bounds.len()
+            || query.evaluations_sym.len() != degree_bounds.len()
+        {
+            return false;
+        }
+
+        let leaf: Vec<_> = query
+            .evaluations
+            .iter()
+            .chain(&query.evaluations_sym)
+            .cloned()
+            .collect();
+        if !options
+            .merkle_hash
+            .verify(&query.auth_path, &proof.polys_merkle_root, *iota, &leaf)
+        {
+            return false;
+        }
+
+        // 𝜐 and -𝜐 are the points of the domain at `2 * iota` and `2 * iota + 1` in bit-reversed
+        // order.
+        let evaluation_point =
+            &coset_offset * primitive_root.pow(reverse_index(2 * iota, domain_size as u64));
+        let evaluation_point_sym = -&evaluation_point;
+        let p0_eval = combine(
+            &query.evaluations,
+            degree_bounds,
+            max_degree_bound,
+            &alpha,
+            &evaluation_point,
+        );
+        let p0_eval_sym = combine(
+            &query.evaluations_sym,
+            degree_bounds,
+            max_degree_bound,
+            &alpha,
+            &evaluation_point_sym,
+        );
+
+        verify_query(
+            options.merkle_hash,
+            &proof.layers_merkle_roots,
+            &last_layer,
+            &zetas,
+            *iota,
+            &query.decommitment,
+            &evaluation_point,
+            &evaluation_point.inv().unwrap(),
+            &p0_eval,
+            &p0_eval_sym,
+        )
+    })
+}
+
+/// Returns the largest bound of `degree_bounds` rounded up to a power of two, or `None` if there
+/// isn't one for each of the `number_of_polys` polynomials or one is zero.
+fn check_degree_bounds(number_of_polys: usize, degree_bounds: &[usize]) -> Option<usize> {
+    if number_of_polys == 0 || degree_bounds.len() != number_of_polys || degree_bounds.contains(&0)
+    {
+        return None;
+    }
+    degree_bounds
+        .iter()
+        .max()
+        .map(|bound| bound.next_power_of_two())
+}
+
+/// Evaluates the combination of [`batch_prove`] at `point`, given the evaluations of the
+/// polynomials there.
+fn combine<F, E>(
+    evaluations: &[FieldElement<E>],
+    degree_bounds: &[usize],
+    max_degree_bound: usize,
+    alpha: &FieldElement<E>,
+    point: &FieldElement<F>,
+) -> FieldElement<E>
+where
+    F: IsFFTField + IsSubFieldOf<E>,
+    E: IsField,
+{
+    let mut alpha_power = FieldElement::<E>::one();
+    let mut result = FieldElement::<E>::zero();
+    for (evaluation, bound) in evaluations.iter().zip(degree_bounds) {
+        let correction = point.pow(max_degree_bound - bound);
+        result += &alpha_power * evaluation;
+        alpha_power = &alpha_power * alpha;
+        result += correction * &alpha_power * evaluation;
+        alpha_power = &alpha_power * alpha;
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use lambdaworks_math::field::{
+        element::FieldElement, fields::fft_friendly::stark_252_prime_field::Stark252PrimeField,
+    };
+    use lambdaworks_math::polynomial::Polynomial;
+
+    use crate::{proof::options::ProofOptions, transcript::StoneProverTranscript};
+
+    use super::{batch_prove, batch_verify};
+
+    type FE = FieldElement<Stark252PrimeField>;
+
+    fn poly(degree: u64) -> Polynomial<FE> {
+        let coefficients: Vec<_> = (1..=degree + 1).map(FE::from).collect();
+        Polynomial::new(&coefficients)
+    }
+
+    #[test]
+    fn batched_fri_proof_of_polynomials_of_different_degrees_is_valid() {
+        let polys = [poly(15), poly(6), poly(0)];
+        let degree_bounds = [16, 7, 1];
+        let options = ProofOptions::default_test_options();
+
+        let proof = batch_prove::<Stark252PrimeField, _>(
+            &polys,
+            &degree_bounds,
+            &options,
+            &mut StoneProverTranscript::new(&[]),
+        )
+        .unwrap();
+
+        assert!(batch_verify::<Stark252PrimeField, _>(
+            &proof,
+            &degree_bounds,
+            &options,
+            &mut StoneProverTranscript::new(&[]),
+        ));
+    }
+
+    #[test]
+    fn batched_fri_rejects_a_polynomial_above_its_degree_bound() {
+        let options = ProofOptions::default_test_options();
+        let result = batch_prove::<Stark252PrimeField, _>(
+            &[poly(15), poly(7)],
+            &[16, 7],
+            &options,
+            &mut StoneProverTranscript::new(&[]),
+        );
+        assert!(result.is_err());
+
+        // The proof doesn't verify for bounds other than the ones it was made for.
+        let proof = batch_prove::<Stark252PrimeField, _>(
+            &[poly(15), poly(7)],
+            &[16, 8],
+            &options,
+            &mut StoneProverTranscript::new(&[]),
+        )
+        .unwrap();
+        assert!(!batch_verify::<Stark252PrimeField, _>(
+            &proof,
+            &[16, 7],
+            &options,
+            &mut StoneProverTranscript::new(&[]),
+        ));
+    }
+
+    #[test]
+    fn batched_fri_proof_with_a_tampered_evaluation_is_invalid() {
+        let degree_bounds = [16, 4];
+        let options = ProofOptions::default_test_options();
+
+        let mut proof = batch_prove::<Stark252PrimeField, _>(
+            &[poly(15), poly(3)],
+            &degree_bounds,
+            &options,
+            &mut StoneProverTranscript::new(&[]),
+        )
+        .unwrap();
+        proof.queries[0].evaluations[1] += FE::one();
+
+        assert!(!batch_verify::<Stark252PrimeField, _>(
+            &proof,
+            &degree_bounds,
+            &options,
+            &mut StoneProverTranscript::new(&[]),
+        ));
+    }
+}
@@ -1,37 +1,40 @@
-use lambdaworks_crypto::merkle_tree::{merkle::MerkleTree, traits::IsMerkleTreeBackend};
 use lambdaworks_math::{
     field::{element::FieldElement, traits::IsField},
     traits::AsBytes,
 };
 
+use crate::config::BatchedMerkleTree;
+
 #[derive(Clone)]
-pub struct FriLayer<F, B>
+pub struct FriLayer<F>
 where
     F: IsField,
-    FieldElement<F>: AsBytes,
-    B: IsMerkleTreeBackend,
+    FieldElement<F>: AsBytes + Sync + Send,
 {
     pub evaluation: Vec<FieldElement<F>>,
-    pub merkle_tree: MerkleTree<B>,
+    pub merkle_tree: BatchedMerkleTree<F>,
+    /// The salts of the leaves of `merkle_tree`, empty unless the proof is zero-knowledge.
+    pub salts: Vec<Vec<FieldElement<F>>>,
     pub coset_offset: FieldElement<F>,
     pub domain_size: usize,
 }
 
-impl<F, B> FriLayer<F, B>
+impl<F> FriLayer<F>
 where
     F: IsField,
-    FieldElement<F>: AsBytes,
-    B: IsMerkleTreeBackend,
+    FieldElement<F>: AsBytes + Sync + Send,
 {
     pub fn new(
         evaluation: &[FieldElement<F>],
-        merkle_tree: MerkleTree<B>,
+        merkle_tree: BatchedMerkleTree<F>,
+        salts: Vec<Vec<FieldElement<F>>>,
         coset_offset: FieldElement<F>,
         domain_size: usize,
     ) -> Self {
         Self {
             evaluation: evaluation.to_vec(),
             merkle_tree,
+            salts,
             coset_offset,
             domain_size,
         }
@@ -8,4 +8,6 @@ use crate::config::Commitment;
 pub struct FriDecommitment<F: IsField> {
     pub layers_auth_paths: Vec<Proof<Commitment>>,
     pub layers_evaluations_sym: Vec<FieldElement<F>>,
+    /// The salts of the opened leaves of each layer, empty unless the proof is zero-knowledge.
+    pub layers_salts: Vec<Vec<FieldElement<F>>>,
 }
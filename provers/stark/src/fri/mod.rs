@@ -1,8 +1,14 @@
+pub mod backend;
+pub mod circle;
+mod fri_batch;
 pub mod fri_commitment;
 pub mod fri_decommit;
 mod fri_functions;
 
+use std::collections::HashSet;
+
 use lambdaworks_crypto::fiat_shamir::is_transcript::IsTranscript;
+use lambdaworks_crypto::merkle_tree::proof::Proof;
 use lambdaworks_math::field::traits::{IsFFTField, IsField};
 use lambdaworks_math::traits::AsBytes;
 use lambdaworks_math::{
@@ -13,35 +19,57 @@ pub use lambdaworks_math::{
     polynomial::Polynomial,
 };
 
-use crate::config::{BatchedMerkleTree, BatchedMerkleTreeBackend};
+use crate::config::{BatchedMerkleTree, Commitment, MerkleHash};
 
+pub use self::fri_batch::{batch_prove, batch_verify, BatchedFriProof, BatchedFriQuery};
 use self::fri_commitment::FriLayer;
 use self::fri_decommit::FriDecommitment;
 use self::fri_functions::fold_polynomial;
 
+/// The number of times FRI folds a polynomial of degree less than `2^number_layers`, so that the
+/// last layer has degree less than `last_layer_degree_bound`, rounded down to a power of two.
+/// It folds at least once.
+pub fn number_of_folds(number_layers: usize, last_layer_degree_bound: usize) -> usize {
+    let log_last_layer_degree_bound = last_layer_degree_bound.max(1).ilog2() as usize;
+    number_layers
+        .saturating_sub(log_last_layer_degree_bound)
+        .max(1)
+}
+
+/// The number of coefficients of the last layer of FRI, see [`number_of_folds`].
+pub fn last_layer_len(number_layers: usize, last_layer_degree_bound: usize) -> usize {
+    1 << number_layers.saturating_sub(number_of_folds(number_layers, last_layer_degree_bound))
+}
+
+/// Commits to the layers of FRI for `p_0`, of degree less than `2^number_layers`. Returns the
+/// coefficients of the last layer, which is sent instead of being committed, and the committed
+/// layers, in Merkle trees with hash `merkle_hash`. Each leaf of the layers ends with a salt
+/// returned by `salt`, which can be empty.
+#[allow(clippy::too_many_arguments)]
 pub fn commit_phase<F: IsFFTField + IsSubFieldOf<E>, E: IsField>(
     number_layers: usize,
+    last_layer_degree_bound: usize,
     p_0: Polynomial<FieldElement<E>>,
     transcript: &mut impl IsTranscript<E>,
     coset_offset: &FieldElement<F>,
     domain_size: usize,
-) -> (
-    FieldElement<E>,
-    Vec<FriLayer<E, BatchedMerkleTreeBackend<E>>>,
-)
+    merkle_hash: MerkleHash,
+    mut salt: impl FnMut() -> Vec<FieldElement<E>>,
+) -> (Vec<FieldElement<E>>, Vec<FriLayer<E>>)
 where
     FieldElement<F>: AsBytes + Sync + Send,
     FieldElement<E>: AsBytes + Sync + Send,
 {
     let mut domain_size = domain_size;
+    let number_of_folds = number_of_folds(number_layers, last_layer_degree_bound);
 
-    let mut fri_layer_list = Vec::with_capacity(number_layers);
-    let mut current_layer: FriLayer<E, BatchedMerkleTreeBackend<E>>;
+    let mut fri_layer_list = Vec::with_capacity(number_of_folds);
+    let mut current_layer: FriLayer<E>;
     let mut current_poly = p_0;
 
     let mut coset_offset = coset_offset.clone();
 
-    for _ in 1..number_layers {
+    for _ in 1..number_of_folds {
         // <<<< Receive challenge 𝜁ₖ₋₁
         let zeta = transcript.sample_field_element();
         coset_offset = coset_offset.square();
@@ -49,12 +77,18 @@ where
 
         // Compute layer polynomial and domain
         current_poly = FieldElement::<F>::from(2) * fold_polynomial(&current_poly, &zeta);
-        current_layer = new_fri_layer(&current_poly, &coset_offset, domain_size);
-        let new_data = &current_layer.merkle_tree.root;
+        current_layer = new_fri_layer(
+            &current_poly,
+            &coset_offset,
+            domain_size,
+            merkle_hash,
+            &mut salt,
+        );
+        let new_data = current_layer.merkle_tree.root();
         fri_layer_list.push(current_layer.clone()); // TODO: remove this clone
 
         // >>>> Send commitment: [pₖ]
-        transcript.append_bytes(new_data);
+        transcript.append_bytes(&new_data);
     }
 
     // <<<< Receive challenge: 𝜁ₙ₋₁
@@ -62,61 +96,199 @@ where
 
     let last_poly = FieldElement::<F>::from(2) * fold_polynomial(&current_poly, &zeta);
 
-    let last_value = last_poly
-        .coefficients()
-        .first()
-        .unwrap_or(&FieldElement::zero())
-        .clone();
+    let mut last_layer_coefficients = last_poly.coefficients().to_vec();
+    last_layer_coefficients.resize(
+        last_layer_len(number_layers, last_layer_degree_bound),
+        FieldElement::zero(),
+    );
 
-    // >>>> Send value: pₙ
-    transcript.append_field_element(&last_value);
+    // >>>> Send coefficients: pₙ
+    for coefficient in last_layer_coefficients.iter() {
+        transcript.append_field_element(coefficient);
+    }
 
-    (last_value, fri_layer_list)
+    (last_layer_coefficients, fri_layer_list)
+}
+
+/// Samples `number_of_queries` indexes of queries to a first layer of FRI over a domain of
+/// `domain_size` elements, and removes the repeated ones, keeping the order in which they were
+/// sampled. Each index opens a pair of symmetric points, so there are `domain_size / 2` of them.
+pub fn sample_query_indexes<E: IsField>(
+    number_of_queries: usize,
+    domain_size: usize,
+    transcript: &mut impl IsTranscript<E>,
+) -> Vec<usize> {
+    let mut seen = HashSet::new();
+    (0..number_of_queries)
+        .map(|_| transcript.sample_u64(domain_size as u64 >> 1) as usize)
+        .filter(|iota| seen.insert(*iota))
+        .collect()
 }
 
 pub fn query_phase<F: IsField>(
-    fri_layers: &Vec<FriLayer<F, BatchedMerkleTreeBackend<F>>>,
+    fri_layers: &Vec<FriLayer<F>>,
     iotas: &[usize],
 ) -> Vec<FriDecommitment<F>>
 where
     FieldElement<F>: AsBytes + Sync + Send,
 {
-    if !fri_layers.is_empty() {
-        let query_list = iotas
-            .iter()
-            .map(|iota_s| {
-                let mut layers_evaluations_sym = Vec::new();
-                let mut layers_auth_paths_sym = Vec::new();
-
-                let mut index = *iota_s;
-                for layer in fri_layers {
-                    // symmetric element
-                    let evaluation_sym = layer.evaluation[index ^ 1].clone();
-                    let auth_path_sym = layer.merkle_tree.get_proof_by_pos(index >> 1).unwrap();
-                    layers_evaluations_sym.push(evaluation_sym);
-                    layers_auth_paths_sym.push(auth_path_sym);
-
-                    index >>= 1;
-                }
-
-                FriDecommitment {
-                    layers_auth_paths: layers_auth_paths_sym,
-                    layers_evaluations_sym,
-                }
-            })
-            .collect();
-
-        query_list
-    } else {
-        vec![]
+    iotas
+        .iter()
+        .map(|iota_s| {
+            let mut layers_evaluations_sym = Vec::new();
+            let mut layers_auth_paths_sym = Vec::new();
+            let mut layers_salts = Vec::new();
+
+            let mut index = *iota_s;
+            for layer in fri_layers {
+                // symmetric element
+                let evaluation_sym = layer.evaluation[index ^ 1].clone();
+                let auth_path_sym = layer.merkle_tree.get_proof_by_pos(index >> 1).unwrap();
+                layers_evaluations_sym.push(evaluation_sym);
+                layers_auth_paths_sym.push(auth_path_sym);
+                layers_salts.push(layer.salts.get(index >> 1).cloned().unwrap_or_default());
+
+                index >>= 1;
+            }
+
+            FriDecommitment {
+                layers_auth_paths: layers_auth_paths_sym,
+                layers_evaluations_sym,
+                layers_salts,
+            }
+        })
+        .collect()
+}
+
+/// Verifies a single FRI query.
+/// `merkle_hash`: the hash of the Merkle trees of the layers.
+/// `layers_merkle_roots`: the commitments to the inner layers of FRI.
+/// `last_layer`: the last layer pₙ of FRI, whose coefficients are given by the prover.
+/// `zetas`: the vector of all challenges sent by the verifier to the prover at the commit
+/// phase to fold polynomials.
+/// `iota`: the index challenge of this FRI query. This index uniquely determines two elements 𝜐 and -𝜐
+/// of the evaluation domain of FRI layer 0.
+/// `evaluation_point`: the value 𝜐.
+/// `evaluation_point_inv`: precomputed value of 𝜐⁻¹.
+/// `p0_eval`: the value p₀(𝜐), where p₀ is the first layer of FRI.
+/// `p0_eval_sym`: the value p₀(-𝜐).
+#[allow(clippy::too_many_arguments)]
+pub fn verify_query<F: IsSubFieldOf<E>, E: IsField>(
+    merkle_hash: MerkleHash,
+    layers_merkle_roots: &[Commitment],
+    last_layer: &Polynomial<FieldElement<E>>,
+    zetas: &[FieldElement<E>],
+    iota: usize,
+    decommitment: &FriDecommitment<E>,
+    evaluation_point: &FieldElement<F>,
+    evaluation_point_inv: &FieldElement<F>,
+    p0_eval: &FieldElement<E>,
+    p0_eval_sym: &FieldElement<E>,
+) -> bool
+where
+    FieldElement<E>: AsBytes + Sync + Send,
+{
+    if decommitment.layers_evaluations_sym.len() != layers_merkle_roots.len()
+        || decommitment.layers_auth_paths.len() != layers_merkle_roots.len()
+        || decommitment.layers_salts.len() != layers_merkle_roots.len()
+        || zetas.len() != layers_merkle_roots.len() + 1
+    {
+        return false;
     }
+
+    let evaluation_point_vec: Vec<FieldElement<F>> =
+        core::iter::successors(Some(evaluation_point_inv.square()), |evaluation_point| {
+            Some(evaluation_point.square())
+        })
+        .take(layers_merkle_roots.len())
+        .collect();
+
+    // Reconstruct p₁(𝜐²)
+    let mut v =
+        (p0_eval + p0_eval_sym) + evaluation_point_inv * &zetas[0] * (p0_eval - p0_eval_sym);
+    let mut index = iota;
+
+    // For each FRI layer, starting from the layer 1: use the proof to verify the validity of values pᵢ(−𝜐^(2ⁱ)) (given by the prover) and
+    // pᵢ(𝜐^(2ⁱ)) (computed on the previous iteration by the verifier). Then use them to obtain pᵢ₊₁(𝜐^(2ⁱ⁺¹)).
+    let openings_ok = layers_merkle_roots
+        .iter()
+        .enumerate()
+        .zip(&decommitment.layers_auth_paths)
+        .zip(&decommitment.layers_evaluations_sym)
+        .zip(&decommitment.layers_salts)
+        .zip(evaluation_point_vec)
+        .fold(
+            true,
+            |result,
+             (
+                ((((i, merkle_root), auth_path_sym), evaluation_sym), salt),
+                evaluation_point_inv,
+            )| {
+                // Verify opening Open(pᵢ(Dₖ), −𝜐^(2ⁱ)) and Open(pᵢ(Dₖ), 𝜐^(2ⁱ)).
+                // `v` is pᵢ(𝜐^(2ⁱ)).
+                // `evaluation_sym` is pᵢ(−𝜐^(2ⁱ)).
+                let openings_ok = verify_layer_openings(
+                    merkle_hash,
+                    merkle_root,
+                    auth_path_sym,
+                    &v,
+                    evaluation_sym,
+                    salt,
+                    index,
+                );
+
+                // Update `v` with next value pᵢ₊₁(𝜐^(2ⁱ⁺¹)).
+                v = (&v + evaluation_sym)
+                    + evaluation_point_inv * &zetas[i + 1] * (&v - evaluation_sym);
+
+                // Update index for next iteration. The index of the squares in the next layer
+                // is obtained by halving the current index. This is due to the bit-reverse
+                // ordering of the elements in the Merkle tree.
+                index >>= 1;
+
+                result & openings_ok
+            },
+        );
+
+    // Finally, check that the final value pₙ(𝜐^(2ⁿ)) is the evaluation of the last layer given
+    // by the prover.
+    let last_layer_point = evaluation_point.pow(1_u64 << (layers_merkle_roots.len() + 1));
+    openings_ok && v == last_layer.evaluate(&last_layer_point.to_extension())
+}
+
+/// Verifies the openings of a fold polynomial of an inner layer of FRI, in a leaf ending with
+/// `salt` of a Merkle tree with hash `merkle_hash`.
+fn verify_layer_openings<E: IsField>(
+    merkle_hash: MerkleHash,
+    merkle_root: &Commitment,
+    auth_path_sym: &Proof<Commitment>,
+    evaluation: &FieldElement<E>,
+    evaluation_sym: &FieldElement<E>,
+    salt: &[FieldElement<E>],
+    iota: usize,
+) -> bool
+where
+    FieldElement<E>: AsBytes + Sync + Send,
+{
+    let mut evaluations = if iota % 2 == 1 {
+        vec![evaluation_sym.clone(), evaluation.clone()]
+    } else {
+        vec![evaluation.clone(), evaluation_sym.clone()]
+    };
+    evaluations.extend_from_slice(salt);
+
+    merkle_hash.verify(auth_path_sym, merkle_root, iota >> 1, &evaluations)
 }
 
+/// Returns the layer of FRI of `poly`, in a Merkle tree with hash `merkle_hash`, with each leaf
+/// ending with a salt returned by `salt`.
 pub fn new_fri_layer<F: IsFFTField + IsSubFieldOf<E>, E: IsField>(
     poly: &Polynomial<FieldElement<E>>,
     coset_offset: &FieldElement<F>,
     domain_size: usize,
-) -> crate::fri::fri_commitment::FriLayer<E, BatchedMerkleTreeBackend<E>>
+    merkle_hash: MerkleHash,
+    salt: &mut impl FnMut() -> Vec<FieldElement<E>>,
+) -> crate::fri::fri_commitment::FriLayer<E>
 where
     FieldElement<F>: AsBytes + Sync + Send,
     FieldElement<E>: AsBytes + Sync + Send,
@@ -127,15 +299,23 @@ where
     in_place_bit_reverse_permute(&mut evaluation);
 
     let mut to_commit = Vec::new();
+    let mut salts = Vec::new();
     for chunk in evaluation.chunks(2) {
-        to_commit.push(vec![chunk[0].clone(), chunk[1].clone()]);
+        let leaf_salt = salt();
+        let mut leaf = vec![chunk[0].clone(), chunk[1].clone()];
+        leaf.extend_from_slice(&leaf_salt);
+        to_commit.push(leaf);
+        if !leaf_salt.is_empty() {
+            salts.push(leaf_salt);
+        }
     }
 
-    let merkle_tree = BatchedMerkleTree::build(&to_commit).unwrap();
+    let merkle_tree = BatchedMerkleTree::build(merkle_hash, &to_commit).unwrap();
 
     FriLayer::new(
         &evaluation,
         merkle_tree,
+        salts,
         coset_offset.clone().to_extension(),
         domain_size,
     )
@@ -36,7 +36,8 @@ pub fn is_valid_nonce(seed: &[u8; 32], nonce: u64, grinding_factor: u8) -> bool
 ///
 /// # Returns
 ///
-/// A `nonce` satisfying the required condition.
+/// The smallest `nonce` satisfying the required condition, so that proofs are reproducible with
+/// and without the `parallel` feature.
 pub fn generate_nonce(seed: &[u8; 32], grinding_factor: u8) -> Option<u64> {
     let inner_hash = get_inner_hash(seed, grinding_factor);
     let limit = 1 << (64 - grinding_factor);
@@ -47,9 +48,11 @@ pub fn generate_nonce(seed: &[u8; 32], grinding_factor: u8) -> Option<u64> {
     });
 
     #[cfg(feature = "parallel")]
-    return (0..u64::MAX).into_par_iter().find_any(|&candidate_nonce| {
-        is_valid_nonce_for_inner_hash(&inner_hash, candidate_nonce, limit)
-    });
+    return (0..u64::MAX)
+        .into_par_iter()
+        .find_first(|&candidate_nonce| {
+            is_valid_nonce_for_inner_hash(&inner_hash, candidate_nonce, limit)
+        });
 }
 
 /// Checks if the leftmost 8 bytes of `Hash(inner_hash || candidate_nonce)` are less than `limit`
@@ -81,7 +84,7 @@ fn get_inner_hash(seed: &[u8; 32], grinding_factor: u8) -> [u8; 32] {
 
 #[cfg(test)]
 mod test {
-    use crate::grinding::is_valid_nonce;
+    use crate::grinding::{generate_nonce, is_valid_nonce};
 
     #[test]
     fn test_invalid_nonce_grinding_factor_6() {
@@ -166,4 +169,16 @@ mod test {
         let grinding_factor = 33;
         assert!(is_valid_nonce(&seed, nonce, grinding_factor));
     }
+
+    #[test]
+    fn test_generate_nonce_returns_the_smallest_valid_nonce() {
+        let seed = [
+            37, 68, 26, 150, 139, 142, 66, 175, 33, 47, 199, 160, 9, 109, 79, 234, 135, 254, 39,
+            11, 225, 219, 206, 108, 224, 165, 25, 72, 189, 96, 218, 95,
+        ];
+        let grinding_factor = 10;
+        let nonce = generate_nonce(&seed, grinding_factor).unwrap();
+        assert!(is_valid_nonce(&seed, nonce, grinding_factor));
+        assert!((0..nonce).all(|candidate| !is_valid_nonce(&seed, candidate, grinding_factor)));
+    }
 }
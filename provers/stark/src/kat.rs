@@ -0,0 +1,366 @@
+//! Known-answer tests of the prover: every value the prover exchanges with the transcript while
+//! generating a proof, together with the commitments, out-of-domain evaluations and FRI layers
+//! of the proof. They are deterministic, so other implementations of the verifier can check
+//! that they reproduce this prover bit for bit.
+//!
+//! Byte strings and field elements are encoded in hexadecimal, with field elements encoded by
+//! their [`AsBytes`] implementation.
+
+use std::{cell::RefCell, rc::Rc};
+
+use lambdaworks_crypto::fiat_shamir::is_transcript::IsTranscript;
+use lambdaworks_math::{
+    field::{element::FieldElement, traits::IsField},
+    traits::AsBytes,
+};
+use serde::Serialize;
+
+use crate::{
+    config::{Commitment, MerkleHash},
+    examples::{
+        fibonacci_rap::{fibonacci_rap_trace, FibonacciRAP, FibonacciRAPPublicInputs},
+        simple_fibonacci::{self, FibonacciAIR, FibonacciPublicInputs},
+    },
+    proof::options::ProofOptions,
+    prover::{IsStarkProver, Prover, ProvingError},
+    trace::TraceTable,
+    traits::AIR,
+    transcript::StoneProverTranscript,
+    Felt252, PrimeField,
+};
+
+/// An operation on the transcript, with the state of the transcript after it.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[serde(tag = "operation", rename_all = "snake_case")]
+pub enum TranscriptEvent {
+    AppendBytes {
+        bytes: String,
+        state: String,
+    },
+    AppendFieldElement {
+        element: String,
+        state: String,
+    },
+    SampleFieldElement {
+        element: String,
+        state: String,
+    },
+    SampleU64 {
+        upper_bound: u64,
+        value: u64,
+        state: String,
+    },
+}
+
+/// A handle to the events recorded by a [`RecordingTranscript`], that can be read after the
+/// transcript is moved into the prover.
+#[derive(Clone, Debug, Default)]
+pub struct TranscriptLog(Rc<RefCell<Vec<TranscriptEvent>>>);
+
+impl TranscriptLog {
+    pub fn events(&self) -> Vec<TranscriptEvent> {
+        self.0.borrow().clone()
+    }
+
+    fn push(&self, event: TranscriptEvent) {
+        self.0.borrow_mut().push(event);
+    }
+}
+
+/// A transcript that records every operation on an inner transcript.
+pub struct RecordingTranscript<T> {
+    inner: T,
+    log: TranscriptLog,
+}
+
+impl<T> RecordingTranscript<T> {
+    pub fn new(inner: T) -> Self {
+        Self {
+            inner,
+            log: TranscriptLog::default(),
+        }
+    }
+
+    pub fn log(&self) -> TranscriptLog {
+        self.log.clone()
+    }
+}
+
+impl<F, T> IsTranscript<F> for RecordingTranscript<T>
+where
+    F: IsField,
+    T: IsTranscript<F>,
+    FieldElement<F>: AsBytes,
+{
+    fn append_field_element(&mut self, element: &FieldElement<F>) {
+        self.inner.append_field_element(element);
+        self.log.push(TranscriptEvent::AppendFieldElement {
+            element: to_hex(&element.as_bytes()),
+            state: to_hex(&self.inner.state()),
+        });
+    }
+
+    fn append_bytes(&mut self, new_bytes: &[u8]) {
+        self.inner.append_bytes(new_bytes);
+        self.log.push(TranscriptEvent::AppendBytes {
+            bytes: to_hex(new_bytes),
+            state: to_hex(&self.inner.state()),
+        });
+    }
+
+    fn state(&self) -> [u8; 32] {
+        self.inner.state()
+    }
+
+    fn sample_field_element(&mut self) -> FieldElement<F> {
+        let element = self.inner.sample_field_element();
+        self.log.push(TranscriptEvent::SampleFieldElement {
+            element: to_hex(&element.as_bytes()),
+            state: to_hex(&self.inner.state()),
+        });
+        element
+    }
+
+    fn sample_u64(&mut self, upper_bound: u64) -> u64 {
+        let value = self.inner.sample_u64(upper_bound);
+        self.log.push(TranscriptEvent::SampleU64 {
+            upper_bound,
+            value,
+            state: to_hex(&self.inner.state()),
+        });
+        value
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct KnownAnswerTestOptions {
+    pub blowup_factor: u8,
+    pub fri_number_of_queries: usize,
+    pub coset_offset: u64,
+    pub grinding_factor: u8,
+    pub fri_last_layer_degree_bound: usize,
+    pub zk: bool,
+    pub merkle_hash: MerkleHash,
+}
+
+impl From<&ProofOptions> for KnownAnswerTestOptions {
+    fn from(options: &ProofOptions) -> Self {
+        Self {
+            blowup_factor: options.blowup_factor,
+            fri_number_of_queries: options.fri_number_of_queries,
+            coset_offset: options.coset_offset,
+            grinding_factor: options.grinding_factor,
+            fri_last_layer_degree_bound: options.fri_last_layer_degree_bound,
+            zk: options.zk,
+            merkle_hash: options.merkle_hash,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct KnownAnswerTestCommitments {
+    pub main_trace: String,
+    /// One commitment for each interaction round with auxiliary columns.
+    pub aux_trace: Vec<String>,
+    pub composition_poly: String,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct KnownAnswerTestFri {
+    pub layers_merkle_roots: Vec<String>,
+    pub last_layer_coefficients: Vec<String>,
+    /// Evaluations of the FRI layers opened by each query, at the symmetric points.
+    pub layers_evaluations_sym: Vec<Vec<String>>,
+}
+
+/// The values of a proof of a fixed input, as described in the [module documentation](self).
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct KnownAnswerTest {
+    pub name: String,
+    pub options: KnownAnswerTestOptions,
+    pub trace_length: usize,
+    /// State of the transcript before the prover uses it.
+    pub initial_transcript_state: String,
+    pub transcript: Vec<TranscriptEvent>,
+    pub commitments: KnownAnswerTestCommitments,
+    pub trace_ood_evaluations: Vec<String>,
+    pub composition_poly_parts_ood_evaluation: Vec<String>,
+    pub fri: KnownAnswerTestFri,
+    pub nonce: Option<u64>,
+}
+
+impl KnownAnswerTest {
+    /// Proves `trace` with `transcript`, recording the values of the proof.
+    pub fn generate<A>(
+        name: &str,
+        trace: &TraceTable<A::Field>,
+        pub_inputs: &A::PublicInputs,
+        proof_options: &ProofOptions,
+        transcript: impl IsTranscript<A::FieldExtension>,
+    ) -> Result<Self, ProvingError>
+    where
+        A: AIR + Send + Sync,
+        FieldElement<A::Field>: AsBytes + Send + Sync,
+        FieldElement<A::FieldExtension>: AsBytes + Send + Sync,
+    {
+        let initial_transcript_state = to_hex(&transcript.state());
+        let transcript = RecordingTranscript::new(transcript);
+        let log = transcript.log();
+        let proof = Prover::<A>::prove(trace, pub_inputs, proof_options, transcript)?;
+
+        Ok(Self {
+            name: name.to_string(),
+            options: proof_options.into(),
+            trace_length: proof.trace_length,
+            initial_transcript_state,
+            transcript: log.events(),
+            commitments: KnownAnswerTestCommitments {
+                main_trace: commitment_to_hex(&proof.lde_trace_main_merkle_root),
+                aux_trace: proof
+                    .lde_trace_aux_merkle_roots
+                    .iter()
+                    .map(commitment_to_hex)
+                    .collect(),
+                composition_poly: commitment_to_hex(&proof.composition_poly_root),
+            },
+            trace_ood_evaluations: elements_to_hex(&proof.trace_ood_evaluations.data),
+            composition_poly_parts_ood_evaluation: elements_to_hex(
+                &proof.composition_poly_parts_ood_evaluation,
+            ),
+            fri: KnownAnswerTestFri {
+                layers_merkle_roots: proof
+                    .fri_layers_merkle_roots
+                    .iter()
+                    .map(commitment_to_hex)
+                    .collect(),
+                last_layer_coefficients: elements_to_hex(&proof.fri_last_layer_coefficients),
+                layers_evaluations_sym: proof
+                    .query_list
+                    .iter()
+                    .map(|query| elements_to_hex(&query.layers_evaluations_sym))
+                    .collect(),
+            },
+            nonce: proof.nonce,
+        })
+    }
+}
+
+/// The known-answer tests published by this crate: proofs of the Fibonacci examples over
+/// Stark252, with the Stone compatible transcript seeded with the name of each test.
+pub fn known_answer_tests() -> Result<Vec<KnownAnswerTest>, ProvingError> {
+    let proof_options = ProofOptions::default_test_options();
+
+    let fibonacci = {
+        let name = "fibonacci";
+        let pub_inputs = FibonacciPublicInputs {
+            a0: Felt252::one(),
+            a1: Felt252::one(),
+        };
+        let trace = simple_fibonacci::fibonacci_trace([Felt252::one(), Felt252::one()], 16);
+        KnownAnswerTest::generate::<FibonacciAIR<PrimeField>>(
+            name,
+            &trace,
+            &pub_inputs,
+            &proof_options,
+            StoneProverTranscript::new(name.as_bytes()),
+        )?
+    };
+
+    let fibonacci_rap = {
+        let name = "fibonacci-rap";
+        let steps = 16;
+        let pub_inputs = FibonacciRAPPublicInputs {
+            steps,
+            a0: Felt252::one(),
+            a1: Felt252::one(),
+        };
+        let trace = fibonacci_rap_trace([Felt252::one(), Felt252::one()], steps);
+        KnownAnswerTest::generate::<FibonacciRAP<PrimeField>>(
+            name,
+            &trace,
+            &pub_inputs,
+            &proof_options,
+            StoneProverTranscript::new(name.as_bytes()),
+        )?
+    };
+
+    Ok(vec![fibonacci, fibonacci_rap])
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+fn commitment_to_hex(commitment: &Commitment) -> String {
+    to_hex(commitment)
+}
+
+fn elements_to_hex<F: IsField>(elements: &[FieldElement<F>]) -> Vec<String>
+where
+    FieldElement<F>: AsBytes,
+{
+    elements
+        .iter()
+        .map(|element| to_hex(&element.as_bytes()))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn from_hex(hex: &str) -> Vec<u8> {
+        (0..hex.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn known_answer_tests_are_deterministic() {
+        assert_eq!(known_answer_tests().unwrap(), known_answer_tests().unwrap());
+    }
+
+    #[test]
+    fn recorded_transcript_can_be_replayed() {
+        for test in known_answer_tests().unwrap() {
+            let mut transcript = StoneProverTranscript::new(test.name.as_bytes());
+            assert_eq!(to_hex(&transcript.state()), test.initial_transcript_state);
+
+            for event in &test.transcript {
+                let state = match event {
+                    TranscriptEvent::AppendBytes { bytes, state } => {
+                        transcript.append_bytes(&from_hex(bytes));
+                        state
+                    }
+                    TranscriptEvent::AppendFieldElement { element, state } => {
+                        transcript.append_field_element(&Felt252::from_hex_unchecked(element));
+                        state
+                    }
+                    TranscriptEvent::SampleFieldElement { element, state } => {
+                        let sampled: Felt252 = transcript.sample_field_element();
+                        assert_eq!(&to_hex(&sampled.as_bytes()), element);
+                        state
+                    }
+                    TranscriptEvent::SampleU64 {
+                        upper_bound,
+                        value,
+                        state,
+                    } => {
+                        assert_eq!(transcript.sample_u64(*upper_bound), *value);
+                        state
+                    }
+                };
+                assert_eq!(&to_hex(&transcript.state()), state);
+            }
+        }
+    }
+
+    #[test]
+    fn known_answer_tests_record_the_aux_trace_commitment_of_raps() {
+        let tests = known_answer_tests().unwrap();
+        assert!(tests[0].commitments.aux_trace.is_empty());
+        assert_eq!(tests[1].commitments.aux_trace.len(), 1);
+        assert_eq!(tests[1].fri.layers_evaluations_sym.len(), 3);
+    }
+}
@@ -5,11 +5,16 @@ use lambdaworks_math::field::{
 pub mod constraints;
 pub mod context;
 pub mod debug;
+pub mod diagnostics;
 pub mod domain;
 pub mod examples;
 pub mod frame;
 pub mod fri;
 pub mod grinding;
+#[cfg(feature = "kat")]
+pub mod kat;
+pub mod lookup;
+pub mod preprocessing;
 pub mod proof;
 pub mod prover;
 pub mod table;
@@ -18,6 +23,9 @@ pub mod traits;
 pub mod transcript;
 pub mod utils;
 pub mod verifier;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+pub mod zk;
 
 #[cfg(test)]
 pub mod tests;
@@ -0,0 +1,341 @@
+//! The logarithmic derivative lookup argument, LogUp, from <https://eprint.iacr.org/2022/1530>.
+//!
+//! A [`LogUpLookup`] proves that every value of some columns of the main trace appears in a
+//! table column, with the number of times each row of the table is looked up given by a
+//! multiplicities column:
+//!
+//! `sum_j sum_i 1 / (a_j[i] - alpha) = sum_i m[i] / (t[i] - alpha)`
+//!
+//! for a challenge `alpha`. The auxiliary trace holds one column with the fractions of each
+//! looked up column, one with the fractions of the table, and a running sum that accumulates
+//! the difference of both sides row by row. The accumulator constraint also holds between the
+//! last row and the first one, which can only happen if the whole sum is zero.
+//!
+//! AIRs using it sample the challenge in [`AIR::build_rap_challenges`](crate::traits::AIR),
+//! and add the auxiliary columns, the transition constraints and the boundary constraint of the
+//! lookup to their own. The constraints read the first two steps of the frame, so the
+//! transition offsets of the AIR must start with `[0, 1]`.
+
+use std::collections::BTreeMap;
+
+use lambdaworks_math::field::{
+    element::FieldElement,
+    traits::{IsFFTField, IsField, IsPrimeField, IsSubFieldOf},
+};
+
+use crate::{
+    constraints::{boundary::BoundaryConstraint, transition::TransitionConstraint},
+    frame::Frame,
+    trace::TraceTable,
+};
+
+/// A lookup of some columns of the main trace into a table column.
+#[derive(Clone, Debug)]
+pub struct LogUpLookup {
+    looked_up_columns: Vec<usize>,
+    table_column: usize,
+    multiplicities_column: usize,
+    first_aux_column: usize,
+    challenge_idx: usize,
+}
+
+impl LogUpLookup {
+    /// Looks up the values of `looked_up_columns` in `table_column`, with the multiplicities of
+    /// the table in `multiplicities_column`. The auxiliary columns of the lookup start at the
+    /// first auxiliary column, and the challenge is the first RAP challenge.
+    pub fn new(
+        looked_up_columns: Vec<usize>,
+        table_column: usize,
+        multiplicities_column: usize,
+    ) -> Self {
+        Self {
+            looked_up_columns,
+            table_column,
+            multiplicities_column,
+            first_aux_column: 0,
+            challenge_idx: 0,
+        }
+    }
+
+    /// Places the auxiliary columns of the lookup after the ones of other arguments.
+    pub fn with_first_aux_column(mut self, first_aux_column: usize) -> Self {
+        self.first_aux_column = first_aux_column;
+        self
+    }
+
+    /// Uses another RAP challenge, to run independent lookups.
+    pub fn with_challenge_idx(mut self, challenge_idx: usize) -> Self {
+        self.challenge_idx = challenge_idx;
+        self
+    }
+
+    /// The number of auxiliary columns of the lookup: the fractions of each looked up column and
+    /// of the table, and the accumulator.
+    pub fn num_aux_columns(&self) -> usize {
+        self.looked_up_columns.len() + 2
+    }
+
+    /// The number of transition constraints of the lookup, one for each auxiliary column.
+    pub fn num_transition_constraints(&self) -> usize {
+        self.num_aux_columns()
+    }
+
+    fn table_fractions_column(&self) -> usize {
+        self.first_aux_column + self.looked_up_columns.len()
+    }
+
+    pub fn accumulator_column(&self) -> usize {
+        self.table_fractions_column() + 1
+    }
+
+    /// Builds the auxiliary columns of the lookup for `main_trace`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the challenge is equal to a value of the trace, which only happens with
+    /// negligible probability.
+    pub fn build_auxiliary_columns<F, E>(
+        &self,
+        main_trace: &TraceTable<F>,
+        rap_challenges: &[FieldElement<E>],
+    ) -> Vec<Vec<FieldElement<E>>>
+    where
+        F: IsSubFieldOf<E>,
+        E: IsField,
+    {
+        let challenge = &rap_challenges[self.challenge_idx];
+        let columns = main_trace.columns();
+        let fractions = |column: &[FieldElement<F>]| {
+            let mut fractions: Vec<FieldElement<E>> =
+                column.iter().map(|value| value - challenge).collect();
+            FieldElement::inplace_batch_inverse(&mut fractions)
+                .expect("the challenge is not a value of the trace");
+            fractions
+        };
+
+        let mut aux_columns: Vec<_> = self
+            .looked_up_columns
+            .iter()
+            .map(|&column| fractions(&columns[column]))
+            .collect();
+        let table_fractions = fractions(&columns[self.table_column]);
+
+        let multiplicities = &columns[self.multiplicities_column];
+        let mut accumulator = Vec::with_capacity(main_trace.n_rows());
+        let mut sum = FieldElement::<E>::zero();
+        for row in 0..main_trace.n_rows() {
+            accumulator.push(sum.clone());
+            for looked_up_fractions in &aux_columns {
+                sum = sum + &looked_up_fractions[row];
+            }
+            sum = sum - &multiplicities[row] * &table_fractions[row];
+        }
+
+        aux_columns.push(table_fractions);
+        aux_columns.push(accumulator);
+        aux_columns
+    }
+
+    /// The transition constraints of the lookup, with indexes starting at
+    /// `first_constraint_idx`.
+    pub fn transition_constraints<F, E>(
+        &self,
+        first_constraint_idx: usize,
+    ) -> Vec<Box<dyn TransitionConstraint<F, E>>>
+    where
+        F: IsFFTField + IsSubFieldOf<E> + Send + Sync + 'static,
+        E: IsField + Send + Sync + 'static,
+    {
+        let mut constraints: Vec<Box<dyn TransitionConstraint<F, E>>> = self
+            .looked_up_columns
+            .iter()
+            .chain([&self.table_column])
+            .enumerate()
+            .map(|(i, &column)| -> Box<dyn TransitionConstraint<F, E>> {
+                Box::new(FractionConstraint {
+                    column,
+                    aux_column: self.first_aux_column + i,
+                    challenge_idx: self.challenge_idx,
+                    constraint_idx: first_constraint_idx + i,
+                })
+            })
+            .collect();
+
+        constraints.push(Box::new(AccumulatorConstraint {
+            first_aux_column: self.first_aux_column,
+            num_looked_up_columns: self.looked_up_columns.len(),
+            multiplicities_column: self.multiplicities_column,
+            constraint_idx: first_constraint_idx + self.looked_up_columns.len() + 1,
+        }));
+
+        constraints
+    }
+
+    /// The boundary constraint of the lookup, which starts the accumulator at zero.
+    pub fn boundary_constraint<E: IsField>(&self) -> BoundaryConstraint<E> {
+        BoundaryConstraint::new_aux(self.accumulator_column(), 0, FieldElement::zero())
+    }
+}
+
+/// Counts how many times each row of `table` is looked up by the values of `looked_up_columns`.
+/// Values repeated in the table are counted in their first row.
+///
+/// # Panics
+///
+/// Panics if a looked up value is not in the table.
+pub fn multiplicities<F: IsPrimeField>(
+    looked_up_columns: &[&[FieldElement<F>]],
+    table: &[FieldElement<F>],
+) -> Vec<FieldElement<F>> {
+    let mut rows = BTreeMap::new();
+    for (row, value) in table.iter().enumerate().rev() {
+        rows.insert(value.representative(), row);
+    }
+
+    let mut counts = vec![0u64; table.len()];
+    for value in looked_up_columns.iter().flat_map(|column| column.iter()) {
+        let row = rows
+            .get(&value.representative())
+            .expect("looked up value is not in the table");
+        counts[*row] += 1;
+    }
+
+    counts.into_iter().map(FieldElement::from).collect()
+}
+
+/// `f * (a - alpha) = 1`, where `f` are the fractions of a column `a`.
+struct FractionConstraint {
+    column: usize,
+    aux_column: usize,
+    challenge_idx: usize,
+    constraint_idx: usize,
+}
+
+impl<F, E> TransitionConstraint<F, E> for FractionConstraint
+where
+    F: IsFFTField + IsSubFieldOf<E> + Send + Sync,
+    E: IsField + Send + Sync,
+{
+    fn degree(&self) -> usize {
+        2
+    }
+
+    fn constraint_idx(&self) -> usize {
+        self.constraint_idx
+    }
+
+    fn end_exemptions(&self) -> usize {
+        0
+    }
+
+    fn evaluate(
+        &self,
+        frame: &Frame<F, E>,
+        transition_evaluations: &mut [FieldElement<E>],
+        _periodic_values: &[FieldElement<F>],
+        rap_challenges: &[FieldElement<E>],
+    ) {
+        let step = frame.get_evaluation_step(0);
+
+        let value = step.get_main_evaluation_element(0, self.column);
+        let fraction = step.get_aux_evaluation_element(0, self.aux_column);
+        let challenge = &rap_challenges[self.challenge_idx];
+
+        let res = fraction * (value - challenge) - FieldElement::<E>::one();
+
+        transition_evaluations[self.constraint_idx] = res;
+    }
+}
+
+/// `s_{i+1} = s_i + sum_j f_j - m * f_t`, where `s` is the accumulator, `f_j` the fractions of
+/// the looked up columns, `f_t` the fractions of the table and `m` the multiplicities. It has
+/// no exemptions, so it also holds from the last row to the first one.
+struct AccumulatorConstraint {
+    first_aux_column: usize,
+    num_looked_up_columns: usize,
+    multiplicities_column: usize,
+    constraint_idx: usize,
+}
+
+impl<F, E> TransitionConstraint<F, E> for AccumulatorConstraint
+where
+    F: IsFFTField + IsSubFieldOf<E> + Send + Sync,
+    E: IsField + Send + Sync,
+{
+    fn degree(&self) -> usize {
+        2
+    }
+
+    fn constraint_idx(&self) -> usize {
+        self.constraint_idx
+    }
+
+    fn end_exemptions(&self) -> usize {
+        0
+    }
+
+    fn evaluate(
+        &self,
+        frame: &Frame<F, E>,
+        transition_evaluations: &mut [FieldElement<E>],
+        _periodic_values: &[FieldElement<F>],
+        _rap_challenges: &[FieldElement<E>],
+    ) {
+        let first_step = frame.get_evaluation_step(0);
+        let second_step = frame.get_evaluation_step(1);
+
+        let table_fractions_column = self.first_aux_column + self.num_looked_up_columns;
+        let accumulator_column = table_fractions_column + 1;
+
+        let accumulator = first_step.get_aux_evaluation_element(0, accumulator_column);
+        let next_accumulator = second_step.get_aux_evaluation_element(0, accumulator_column);
+        let multiplicity = first_step.get_main_evaluation_element(0, self.multiplicities_column);
+        let table_fraction = first_step.get_aux_evaluation_element(0, table_fractions_column);
+
+        let looked_up_fractions = (self.first_aux_column..table_fractions_column)
+            .map(|column| first_step.get_aux_evaluation_element(0, column))
+            .fold(FieldElement::<E>::zero(), |acc, fraction| acc + fraction);
+
+        let res =
+            next_accumulator - accumulator - looked_up_fractions + multiplicity * table_fraction;
+
+        transition_evaluations[self.constraint_idx] = res;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use lambdaworks_math::field::fields::u64_prime_field::{F17, FE17};
+
+    #[test]
+    fn multiplicities_count_the_looked_up_values() {
+        let table: Vec<_> = (0..4).map(FE17::from).collect();
+        let a = [FE17::from(1), FE17::from(3), FE17::from(1)];
+        let b = [FE17::from(0), FE17::from(1)];
+
+        assert_eq!(
+            multiplicities::<F17>(&[&a[..], &b[..]], &table),
+            vec![FE17::one(), FE17::from(3), FE17::zero(), FE17::one()]
+        );
+    }
+
+    #[test]
+    fn accumulator_wraps_around_to_zero_for_valid_lookups() {
+        let looked_up: Vec<_> = [2, 0, 2, 3].into_iter().map(FE17::from).collect();
+        let table: Vec<_> = (0..4).map(FE17::from).collect();
+        let multiplicities = multiplicities::<F17>(&[&looked_up[..]], &table);
+        let trace = TraceTable::from_columns_main(vec![looked_up, table, multiplicities], 1);
+
+        let lookup = LogUpLookup::new(vec![0], 1, 2);
+        let aux_columns = lookup.build_auxiliary_columns(&trace, &[FE17::from(10)]);
+        assert_eq!(aux_columns.len(), lookup.num_aux_columns());
+
+        let accumulator = &aux_columns[lookup.accumulator_column()];
+        let last_row = trace.n_rows() - 1;
+        let last_step = accumulator[last_row] + aux_columns[0][last_row]
+            - trace.columns()[2][last_row] * aux_columns[1][last_row];
+        assert_eq!(accumulator[0], FE17::zero());
+        assert_eq!(last_step, FE17::zero());
+    }
+}
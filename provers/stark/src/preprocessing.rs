@@ -0,0 +1,80 @@
+//! Preprocessed columns: columns of the main trace that don't depend on the witness, like
+//! selectors or lookup tables.
+//!
+//! [`IsStarkProver::preprocess`](crate::prover::IsStarkProver::preprocess) interpolates and
+//! commits them once, for a trace length and a LDE domain. Their commitment is a public
+//! parameter of the AIR: the prover reuses the [`PreprocessedTrace`] in every proof with
+//! [`IsStarkProver::prove_with_preprocessed_trace`](crate::prover::IsStarkProver::prove_with_preprocessed_trace),
+//! opening the columns at the queries without committing to them again, and the verifier only
+//! needs [`PreprocessedTrace::commitment`] in
+//! [`IsStarkVerifier::verify_with_preprocessed_commitment`](crate::verifier::IsStarkVerifier::verify_with_preprocessed_commitment).
+//!
+//! Constraints read the preprocessed columns as the first
+//! [`AIR::num_preprocessed_columns`](crate::traits::AIR::num_preprocessed_columns) columns of
+//! the main trace, but the main trace passed to the prover only holds the columns after them.
+
+use lambdaworks_math::{
+    field::{element::FieldElement, traits::IsField},
+    polynomial::Polynomial,
+    traits::AsBytes,
+};
+
+use crate::{
+    config::{BatchedMerkleTree, Commitment},
+    proof::options::ProofOptions,
+    trace::TraceTable,
+};
+
+/// The preprocessed columns of an AIR, with their commitment.
+pub struct PreprocessedTrace<F>
+where
+    F: IsField,
+    FieldElement<F>: AsBytes + Send + Sync,
+{
+    pub(crate) trace: TraceTable<F>,
+    pub(crate) trace_polys: Vec<Polynomial<FieldElement<F>>>,
+    /// Evaluations of `trace_polys` over the LDE domain.
+    pub(crate) lde_trace_evaluations: Vec<Vec<FieldElement<F>>>,
+    pub(crate) lde_trace_merkle_tree: BatchedMerkleTree<F>,
+    pub(crate) lde_trace_merkle_root: Commitment,
+    /// The blowup factor and coset offset of the LDE domain.
+    pub(crate) blowup_factor: u8,
+    pub(crate) coset_offset: u64,
+}
+
+impl<F> PreprocessedTrace<F>
+where
+    F: IsField,
+    FieldElement<F>: AsBytes + Send + Sync,
+{
+    /// The commitment to the preprocessed columns, which verifiers receive instead of the
+    /// columns.
+    pub fn commitment(&self) -> Commitment {
+        self.lde_trace_merkle_root
+    }
+
+    pub fn num_columns(&self) -> usize {
+        self.trace.n_cols()
+    }
+
+    pub fn trace_length(&self) -> usize {
+        self.trace.n_rows()
+    }
+
+    /// Returns whether the columns were preprocessed for traces of `trace_length` rows, and the
+    /// LDE domain and Merkle hash of `proof_options`.
+    pub fn is_compatible_with(&self, trace_length: usize, proof_options: &ProofOptions) -> bool {
+        self.trace_length() == trace_length
+            && self.blowup_factor == proof_options.blowup_factor
+            && self.coset_offset == proof_options.coset_offset
+            && self.lde_trace_merkle_tree.merkle_hash() == proof_options.merkle_hash
+    }
+
+    /// Returns the main trace read by the AIR: the preprocessed columns followed by the ones of
+    /// `main_trace`.
+    pub(crate) fn prepend_to(&self, main_trace: &TraceTable<F>) -> TraceTable<F> {
+        let mut columns = self.trace.columns();
+        columns.extend(main_trace.columns());
+        TraceTable::from_columns_main(columns, main_trace.step_size)
+    }
+}
@@ -1,3 +1,6 @@
 pub mod errors;
+#[cfg(feature = "proptest")]
+pub mod mutation;
 pub mod options;
+mod serialization;
 pub mod stark;
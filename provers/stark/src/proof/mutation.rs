@@ -0,0 +1,233 @@
+//! Adversarial mutations of STARK proofs, to check that the verifier rejects proofs that were
+//! tampered with after being generated.
+//!
+//! Every mutation keeps the shape of the proof and changes a single value that the verifier
+//! either checks or absorbs into its transcript, so a sound verifier must reject every mutated
+//! proof.
+
+use lambdaworks_math::field::{
+    element::FieldElement,
+    traits::{IsField, IsSubFieldOf},
+};
+use proptest::{
+    arbitrary::Arbitrary,
+    prelude::any,
+    prop_oneof,
+    strategy::{BoxedStrategy, Strategy},
+};
+
+use crate::config::{Commitment, COMMITMENT_SIZE};
+
+use super::stark::StarkProof;
+
+/// A change to a single value of a [`StarkProof`]. Indices are reduced modulo the length of
+/// the part of the proof they point to.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ProofMutation {
+    /// Flips a bit of the commitment to the main trace.
+    MainTraceRoot { bit: usize },
+    /// Flips a bit of the commitment to the composition polynomial.
+    CompositionPolyRoot { bit: usize },
+    /// Flips a bit of the commitment to a FRI layer.
+    FriLayerRoot { layer: usize, bit: usize },
+    /// Adds one to an out-of-domain evaluation of the trace.
+    TraceOodEvaluation { index: usize },
+    /// Adds one to an out-of-domain evaluation of a part of the composition polynomial.
+    CompositionPolyOodEvaluation { index: usize },
+    /// Adds one to the first coefficient of the last layer of FRI.
+    FriLastValue,
+    /// Adds one to the evaluation of a FRI layer opened by a query.
+    FriLayerEvaluation { query: usize, layer: usize },
+    /// Adds one to an evaluation of the main trace opened by a query.
+    MainTraceEvaluation { query: usize, index: usize },
+    /// Flips a bit of a node of the Merkle path that opens the main trace in a query.
+    MainTraceAuthPath {
+        query: usize,
+        node: usize,
+        bit: usize,
+    },
+    /// Adds one to an element of the salt of the main trace opened by a query, in
+    /// zero-knowledge proofs.
+    MainTraceSalt { query: usize, index: usize },
+}
+
+impl ProofMutation {
+    /// Applies the mutation to `proof`. Returns `false`, leaving the proof unchanged, if the
+    /// part of the proof the mutation points to is empty.
+    pub fn apply<F, E>(&self, proof: &mut StarkProof<F, E>) -> bool
+    where
+        F: IsSubFieldOf<E>,
+        E: IsField,
+    {
+        match *self {
+            ProofMutation::MainTraceRoot { bit } => {
+                flip_bit(&mut proof.lde_trace_main_merkle_root, bit);
+                true
+            }
+            ProofMutation::CompositionPolyRoot { bit } => {
+                flip_bit(&mut proof.composition_poly_root, bit);
+                true
+            }
+            ProofMutation::FriLayerRoot { layer, bit } => {
+                match nth_mut(&mut proof.fri_layers_merkle_roots, layer) {
+                    Some(root) => {
+                        flip_bit(root, bit);
+                        true
+                    }
+                    None => false,
+                }
+            }
+            ProofMutation::TraceOodEvaluation { index } => {
+                increment(nth_mut(&mut proof.trace_ood_evaluations.data, index))
+            }
+            ProofMutation::CompositionPolyOodEvaluation { index } => increment(nth_mut(
+                &mut proof.composition_poly_parts_ood_evaluation,
+                index,
+            )),
+            ProofMutation::FriLastValue => increment(proof.fri_last_layer_coefficients.first_mut()),
+            ProofMutation::FriLayerEvaluation { query, layer } => increment(
+                nth_mut(&mut proof.query_list, query)
+                    .and_then(|query| nth_mut(&mut query.layers_evaluations_sym, layer)),
+            ),
+            ProofMutation::MainTraceEvaluation { query, index } => increment(
+                nth_mut(&mut proof.deep_poly_openings, query)
+                    .and_then(|opening| nth_mut(&mut opening.main_trace_polys.evaluations, index)),
+            ),
+            ProofMutation::MainTraceAuthPath { query, node, bit } => {
+                match nth_mut(&mut proof.deep_poly_openings, query).and_then(|opening| {
+                    nth_mut(&mut opening.main_trace_polys.proof.merkle_path, node)
+                }) {
+                    Some(node) => {
+                        flip_bit(node, bit);
+                        true
+                    }
+                    None => false,
+                }
+            }
+            ProofMutation::MainTraceSalt { query, index } => increment(
+                nth_mut(&mut proof.deep_poly_openings, query)
+                    .and_then(|opening| nth_mut(&mut opening.main_trace_polys.salt, index)),
+            ),
+        }
+    }
+}
+
+impl Arbitrary for ProofMutation {
+    type Parameters = ();
+
+    fn arbitrary_with(_args: Self::Parameters) -> Self::Strategy {
+        prop_oneof![
+            any::<usize>().prop_map(|bit| ProofMutation::MainTraceRoot { bit }),
+            any::<usize>().prop_map(|bit| ProofMutation::CompositionPolyRoot { bit }),
+            any::<(usize, usize)>()
+                .prop_map(|(layer, bit)| ProofMutation::FriLayerRoot { layer, bit }),
+            any::<usize>().prop_map(|index| ProofMutation::TraceOodEvaluation { index }),
+            any::<usize>().prop_map(|index| ProofMutation::CompositionPolyOodEvaluation { index }),
+            proptest::strategy::Just(ProofMutation::FriLastValue),
+            any::<(usize, usize)>()
+                .prop_map(|(query, layer)| ProofMutation::FriLayerEvaluation { query, layer }),
+            any::<(usize, usize)>()
+                .prop_map(|(query, index)| ProofMutation::MainTraceEvaluation { query, index }),
+            any::<(usize, usize, usize)>().prop_map(|(query, node, bit)| {
+                ProofMutation::MainTraceAuthPath { query, node, bit }
+            }),
+            any::<(usize, usize)>()
+                .prop_map(|(query, index)| ProofMutation::MainTraceSalt { query, index }),
+        ]
+        .boxed()
+    }
+
+    type Strategy = BoxedStrategy<Self>;
+}
+
+/// Generates copies of `proof` with a single [`ProofMutation`] applied.
+pub fn mutated_proofs<F, E>(proof: StarkProof<F, E>) -> impl Strategy<Value = StarkProof<F, E>>
+where
+    F: IsSubFieldOf<E>,
+    E: IsField,
+{
+    any::<ProofMutation>().prop_filter_map(
+        "the mutation points to an empty part of the proof",
+        move |mutation| {
+            let mut mutated = proof.clone();
+            mutation.apply(&mut mutated).then_some(mutated)
+        },
+    )
+}
+
+fn nth_mut<T>(values: &mut [T], index: usize) -> Option<&mut T> {
+    if values.is_empty() {
+        return None;
+    }
+    let len = values.len();
+    values.get_mut(index % len)
+}
+
+fn flip_bit(commitment: &mut Commitment, bit: usize) {
+    let bit = bit % (COMMITMENT_SIZE * 8);
+    commitment[bit / 8] ^= 1 << (bit % 8);
+}
+
+fn increment<F: IsField>(value: Option<&mut FieldElement<F>>) -> bool {
+    match value {
+        Some(value) => {
+            *value = &*value + FieldElement::<F>::one();
+            true
+        }
+        None => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        examples::simple_fibonacci::{self, FibonacciAIR, FibonacciPublicInputs},
+        proof::options::ProofOptions,
+        prover::{IsStarkProver, Prover},
+        transcript::StoneProverTranscript,
+        verifier::{IsStarkVerifier, Verifier},
+        Felt252, PrimeField,
+    };
+    use proptest::{prop_assert, proptest, test_runner::Config};
+
+    fn fibonacci_inputs() -> FibonacciPublicInputs<PrimeField> {
+        FibonacciPublicInputs {
+            a0: Felt252::one(),
+            a1: Felt252::one(),
+        }
+    }
+
+    fn fibonacci_proof() -> StarkProof<PrimeField, PrimeField> {
+        let trace = simple_fibonacci::fibonacci_trace([Felt252::one(), Felt252::one()], 16);
+        Prover::<FibonacciAIR<PrimeField>>::prove(
+            &trace,
+            &fibonacci_inputs(),
+            &ProofOptions::default_test_options(),
+            StoneProverTranscript::new(&[]),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn mutations_of_empty_parts_are_not_applied() {
+        let mut proof = fibonacci_proof();
+        proof.fri_layers_merkle_roots.clear();
+
+        assert!(!ProofMutation::FriLayerRoot { layer: 0, bit: 0 }.apply(&mut proof));
+        assert!(ProofMutation::MainTraceRoot { bit: 257 }.apply(&mut proof));
+    }
+
+    proptest! {
+        #![proptest_config(Config::with_cases(32))]
+        #[test]
+        fn mutated_proofs_are_rejected(proof in mutated_proofs(fibonacci_proof())) {
+            prop_assert!(!Verifier::<FibonacciAIR<PrimeField>>::verify(
+                &proof,
+                &fibonacci_inputs(),
+                &ProofOptions::default_test_options(),
+                StoneProverTranscript::new(&[]),
+            ));
+        }
+    }
+}
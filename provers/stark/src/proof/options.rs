@@ -1,5 +1,10 @@
 use super::errors::InsecureOptionError;
-use lambdaworks_math::field::traits::IsPrimeField;
+use crate::config::MerkleHash;
+use crate::trace::LDELayout;
+use lambdaworks_math::field::{
+    element::FieldElement,
+    traits::{IsField, IsPrimeField, IsSubFieldOf},
+};
 
 #[cfg(feature = "wasm")]
 use wasm_bindgen::prelude::wasm_bindgen;
@@ -13,24 +18,43 @@ pub enum SecurityLevel {
     Provable128Bits,
 }
 
+/// Conjectured security levels of the production Cairo provers, such as Stone in Starknet.
+pub enum StarknetSecurityLevel {
+    Conjecturable80Bits,
+    Conjecturable96Bits,
+    Conjecturable128Bits,
+}
+
 /// The options for the proof
 ///
 /// - `blowup_factor`: the blowup factor for the trace
 /// - `fri_number_of_queries`: the number of queries for the FRI layer
 /// - `coset_offset`: the offset for the coset
 /// - `grinding_factor`: the number of leading zeros that we want for the Hash(hash || nonce)
+/// - `fri_last_layer_degree_bound`: FRI stops folding once the degree is less than this bound,
+///   rounded down to a power of two, and sends the coefficients of the last layer. With `1`, it
+///   folds down to a constant.
+/// - `zk`: whether the proof hides the trace. The trace polynomials are blinded, a random
+///   polynomial is added to the one FRI proves low degree, and the Merkle leaves are salted, so
+///   the openings reveal nothing about the witness. See [`crate::zk`].
+/// - `merkle_hash`: the hash of the Merkle trees of the commitments. See [`MerkleHash`].
+/// - `lde_layout`: the order in which the prover stores the rows of the LDE of the trace, or
+///   whether it recomputes them one coset at a time, which doesn't change the proof. See
+///   [`LDELayout`].
 #[cfg_attr(feature = "wasm", wasm_bindgen)]
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, serde::Serialize)]
 pub struct ProofOptions {
     pub blowup_factor: u8,
     pub fri_number_of_queries: usize,
     pub coset_offset: u64,
     pub grinding_factor: u8,
+    pub fri_last_layer_degree_bound: usize,
+    pub zk: bool,
+    pub merkle_hash: MerkleHash,
+    pub lde_layout: LDELayout,
 }
 
 impl ProofOptions {
-    // TODO: Make it work for extended fields
-    const EXTENSION_DEGREE: usize = 1;
     // Estimated maximum domain size. 2^40 = 1 TB
     const NUM_BITS_MAX_DOMAIN_SIZE: usize = 40;
 
@@ -42,49 +66,101 @@ impl ProofOptions {
                 fri_number_of_queries: 31,
                 coset_offset,
                 grinding_factor: 20,
+                fri_last_layer_degree_bound: 1,
+                zk: false,
+                merkle_hash: MerkleHash::Keccak256,
+                lde_layout: LDELayout::Natural,
             },
             SecurityLevel::Conjecturable100Bits => ProofOptions {
                 blowup_factor: 4,
                 fri_number_of_queries: 41,
                 coset_offset,
                 grinding_factor: 20,
+                fri_last_layer_degree_bound: 1,
+                zk: false,
+                merkle_hash: MerkleHash::Keccak256,
+                lde_layout: LDELayout::Natural,
             },
             SecurityLevel::Conjecturable128Bits => ProofOptions {
                 blowup_factor: 4,
                 fri_number_of_queries: 55,
                 coset_offset,
                 grinding_factor: 20,
+                fri_last_layer_degree_bound: 1,
+                zk: false,
+                merkle_hash: MerkleHash::Keccak256,
+                lde_layout: LDELayout::Natural,
             },
             SecurityLevel::Provable80Bits => ProofOptions {
                 blowup_factor: 4,
                 fri_number_of_queries: 80,
                 coset_offset,
                 grinding_factor: 20,
+                fri_last_layer_degree_bound: 1,
+                zk: false,
+                merkle_hash: MerkleHash::Keccak256,
+                lde_layout: LDELayout::Natural,
             },
             SecurityLevel::Provable100Bits => ProofOptions {
                 blowup_factor: 4,
                 fri_number_of_queries: 104,
                 coset_offset,
                 grinding_factor: 20,
+                fri_last_layer_degree_bound: 1,
+                zk: false,
+                merkle_hash: MerkleHash::Keccak256,
+                lde_layout: LDELayout::Natural,
             },
             SecurityLevel::Provable128Bits => ProofOptions {
                 blowup_factor: 4,
                 fri_number_of_queries: 140,
                 coset_offset,
                 grinding_factor: 20,
+                fri_last_layer_degree_bound: 1,
+                zk: false,
+                merkle_hash: MerkleHash::Keccak256,
+                lde_layout: LDELayout::Natural,
             },
         }
     }
 
-    /// Checks security of proof options given 128 bits of security
-    pub fn new_with_checked_security<F: IsPrimeField>(
+    /// Options with the blowup factor and the proof of work bits used by the production Cairo
+    /// provers, 16 and 24. The number of queries is the smallest one that reaches the security
+    /// level according to [`ProofOptions::new_with_checked_security`], one more than Stone uses
+    /// for 96 bits.
+    pub fn new_starknet(security_level: StarknetSecurityLevel, coset_offset: u64) -> Self {
+        let fri_number_of_queries = match security_level {
+            StarknetSecurityLevel::Conjecturable80Bits => 15,
+            StarknetSecurityLevel::Conjecturable96Bits => 19,
+            StarknetSecurityLevel::Conjecturable128Bits => 27,
+        };
+
+        ProofOptions {
+            blowup_factor: 16,
+            fri_number_of_queries,
+            coset_offset,
+            grinding_factor: 24,
+            fri_last_layer_degree_bound: 1,
+            zk: false,
+            merkle_hash: MerkleHash::Keccak256,
+            lde_layout: LDELayout::Natural,
+        }
+    }
+
+    /// Checks security of proof options given 128 bits of security, for proofs over `F` whose
+    /// challenges are sampled in its extension `E`.
+    pub fn new_with_checked_security<F, E>(
         blowup_factor: u8,
         fri_number_of_queries: usize,
         coset_offset: u64,
         grinding_factor: u8,
         security_target: u8,
-    ) -> Result<Self, InsecureOptionError> {
-        Self::check_field_security::<F>(security_target)?;
+    ) -> Result<Self, InsecureOptionError>
+    where
+        F: IsPrimeField + IsSubFieldOf<E>,
+        E: IsField,
+    {
+        Self::check_field_security::<F, E>(security_target)?;
 
         let num_bits_blowup_factor = blowup_factor.trailing_zeros() as usize;
 
@@ -99,20 +175,28 @@ impl ProofOptions {
             fri_number_of_queries,
             coset_offset,
             grinding_factor,
+            fri_last_layer_degree_bound: 1,
+            zk: false,
+            merkle_hash: MerkleHash::Keccak256,
+            lde_layout: LDELayout::Natural,
         })
     }
 
     /// Checks provable security of proof options given 128 bits of security
     /// This is an approximation. It's stricter than the formula in the paper.
     /// See https://eprint.iacr.org/2021/582.pdf
-    pub fn new_with_checked_provable_security<F: IsPrimeField>(
+    pub fn new_with_checked_provable_security<F, E>(
         blowup_factor: u8,
         fri_number_of_queries: usize,
         coset_offset: u64,
         grinding_factor: u8,
         security_target: u8,
-    ) -> Result<Self, InsecureOptionError> {
-        Self::check_field_security::<F>(security_target)?;
+    ) -> Result<Self, InsecureOptionError>
+    where
+        F: IsPrimeField + IsSubFieldOf<E>,
+        E: IsField,
+    {
+        Self::check_field_security::<F, E>(security_target)?;
 
         let num_bits_blowup_factor = blowup_factor.leading_zeros() as usize;
 
@@ -127,13 +211,21 @@ impl ProofOptions {
             fri_number_of_queries,
             coset_offset,
             grinding_factor,
+            fri_last_layer_degree_bound: 1,
+            zk: false,
+            merkle_hash: MerkleHash::Keccak256,
+            lde_layout: LDELayout::Natural,
         })
     }
 
-    fn check_field_security<F: IsPrimeField>(
-        security_target: u8,
-    ) -> Result<(), InsecureOptionError> {
-        if F::field_bit_size() * Self::EXTENSION_DEGREE
+    /// The challenges are sampled in `E`, whose size is the size of `F` to the degree of `E`.
+    fn check_field_security<F, E>(security_target: u8) -> Result<(), InsecureOptionError>
+    where
+        F: IsPrimeField + IsSubFieldOf<E>,
+        E: IsField,
+    {
+        let extension_degree = F::to_subfield_vec(FieldElement::<E>::one().value().clone()).len();
+        if F::field_bit_size() * extension_degree
             <= security_target as usize + Self::NUM_BITS_MAX_DOMAIN_SIZE
         {
             return Err(InsecureOptionError::FieldSize);
@@ -150,6 +242,10 @@ impl ProofOptions {
             fri_number_of_queries: 3,
             coset_offset: 3,
             grinding_factor: 1,
+            fri_last_layer_degree_bound: 1,
+            zk: false,
+            merkle_hash: MerkleHash::Keccak256,
+            lde_layout: LDELayout::Natural,
         }
     }
 }
@@ -157,13 +253,24 @@ impl ProofOptions {
 #[cfg(test)]
 mod tests {
     use lambdaworks_math::field::fields::{
-        fft_friendly::stark_252_prime_field::Stark252PrimeField, u64_prime_field::F17,
+        fft_friendly::{
+            babybear::Babybear31PrimeField, quartic_babybear::QuarticBabybearField,
+            stark_252_prime_field::Stark252PrimeField,
+        },
+        mersenne31::{extensions::Degree4ExtensionField, field::Mersenne31Field},
+        u64_goldilocks_field::{Goldilocks64ExtensionField, Goldilocks64Field},
+        u64_prime_field::F17,
     };
 
-    use crate::proof::{errors::InsecureOptionError, options::SecurityLevel};
+    use crate::proof::{
+        errors::InsecureOptionError,
+        options::{SecurityLevel, StarknetSecurityLevel},
+    };
 
     use super::ProofOptions;
 
+    type F = Stark252PrimeField;
+
     #[test]
     fn u64_prime_field_is_not_large_enough_to_be_secure() {
         let ProofOptions {
@@ -171,9 +278,10 @@ mod tests {
             fri_number_of_queries,
             coset_offset,
             grinding_factor,
+            ..
         } = ProofOptions::new_secure(SecurityLevel::Conjecturable128Bits, 1);
 
-        let u64_options = ProofOptions::new_with_checked_security::<F17>(
+        let u64_options = ProofOptions::new_with_checked_security::<F17, F17>(
             blowup_factor,
             fri_number_of_queries,
             coset_offset,
@@ -184,6 +292,71 @@ mod tests {
         assert!(matches!(u64_options, Err(InsecureOptionError::FieldSize)));
     }
 
+    #[test]
+    fn goldilocks_field_is_large_enough_to_be_secure_with_challenges_in_its_extension() {
+        let ProofOptions {
+            blowup_factor,
+            fri_number_of_queries,
+            coset_offset,
+            grinding_factor,
+            ..
+        } = ProofOptions::new_secure(SecurityLevel::Conjecturable80Bits, 1);
+
+        let base_options =
+            ProofOptions::new_with_checked_security::<Goldilocks64Field, Goldilocks64Field>(
+                blowup_factor,
+                fri_number_of_queries,
+                coset_offset,
+                grinding_factor,
+                80,
+            );
+        assert!(matches!(base_options, Err(InsecureOptionError::FieldSize)));
+
+        let extension_options = ProofOptions::new_with_checked_security::<
+            Goldilocks64Field,
+            Goldilocks64ExtensionField,
+        >(
+            blowup_factor,
+            fri_number_of_queries,
+            coset_offset,
+            grinding_factor,
+            80,
+        );
+        assert!(extension_options.is_ok());
+    }
+
+    #[test]
+    fn babybear_and_mersenne31_are_large_enough_to_be_secure_with_challenges_in_their_quartic_extensions(
+    ) {
+        let ProofOptions {
+            blowup_factor,
+            fri_number_of_queries,
+            coset_offset,
+            grinding_factor,
+            ..
+        } = ProofOptions::new_secure(SecurityLevel::Conjecturable80Bits, 1);
+
+        let babybear_options =
+            ProofOptions::new_with_checked_security::<Babybear31PrimeField, QuarticBabybearField>(
+                blowup_factor,
+                fri_number_of_queries,
+                coset_offset,
+                grinding_factor,
+                80,
+            );
+        assert!(babybear_options.is_ok());
+
+        let mersenne31_options =
+            ProofOptions::new_with_checked_security::<Mersenne31Field, Degree4ExtensionField>(
+                blowup_factor,
+                fri_number_of_queries,
+                coset_offset,
+                grinding_factor,
+                80,
+            );
+        assert!(mersenne31_options.is_ok());
+    }
+
     #[test]
     fn generated_stark_proof_options_for_128_bits_are_secure() {
         let ProofOptions {
@@ -191,9 +364,10 @@ mod tests {
             fri_number_of_queries,
             coset_offset,
             grinding_factor,
+            ..
         } = ProofOptions::new_secure(SecurityLevel::Conjecturable128Bits, 1);
 
-        let secure_options = ProofOptions::new_with_checked_security::<Stark252PrimeField>(
+        let secure_options = ProofOptions::new_with_checked_security::<F, F>(
             blowup_factor,
             fri_number_of_queries,
             coset_offset,
@@ -211,9 +385,10 @@ mod tests {
             fri_number_of_queries,
             coset_offset,
             grinding_factor,
+            ..
         } = ProofOptions::new_secure(SecurityLevel::Conjecturable128Bits, 1);
 
-        let insecure_options = ProofOptions::new_with_checked_security::<Stark252PrimeField>(
+        let insecure_options = ProofOptions::new_with_checked_security::<F, F>(
             blowup_factor,
             fri_number_of_queries - 1,
             coset_offset,
@@ -234,9 +409,10 @@ mod tests {
             fri_number_of_queries,
             coset_offset,
             grinding_factor,
+            ..
         } = ProofOptions::new_secure(SecurityLevel::Conjecturable100Bits, 1);
 
-        let secure_options = ProofOptions::new_with_checked_security::<Stark252PrimeField>(
+        let secure_options = ProofOptions::new_with_checked_security::<F, F>(
             blowup_factor,
             fri_number_of_queries,
             coset_offset,
@@ -254,9 +430,10 @@ mod tests {
             fri_number_of_queries,
             coset_offset,
             grinding_factor,
+            ..
         } = ProofOptions::new_secure(SecurityLevel::Conjecturable80Bits, 1);
 
-        let secure_options = ProofOptions::new_with_checked_security::<Stark252PrimeField>(
+        let secure_options = ProofOptions::new_with_checked_security::<F, F>(
             blowup_factor,
             fri_number_of_queries,
             coset_offset,
@@ -266,4 +443,42 @@ mod tests {
 
         assert!(secure_options.is_ok());
     }
+
+    #[test]
+    fn starknet_proof_options_are_secure_for_their_target_bits() {
+        for (security_level, security_target) in [
+            (StarknetSecurityLevel::Conjecturable80Bits, 80),
+            (StarknetSecurityLevel::Conjecturable96Bits, 96),
+            (StarknetSecurityLevel::Conjecturable128Bits, 128),
+        ] {
+            let ProofOptions {
+                blowup_factor,
+                fri_number_of_queries,
+                coset_offset,
+                grinding_factor,
+                ..
+            } = ProofOptions::new_starknet(security_level, 3);
+
+            let secure_options = ProofOptions::new_with_checked_security::<F, F>(
+                blowup_factor,
+                fri_number_of_queries,
+                coset_offset,
+                grinding_factor,
+                security_target,
+            );
+            assert!(secure_options.is_ok());
+
+            let insecure_options = ProofOptions::new_with_checked_security::<F, F>(
+                blowup_factor,
+                fri_number_of_queries - 1,
+                coset_offset,
+                grinding_factor,
+                security_target,
+            );
+            assert!(matches!(
+                insecure_options,
+                Err(InsecureOptionError::LowSecurityBits)
+            ));
+        }
+    }
 }
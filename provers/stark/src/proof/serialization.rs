@@ -0,0 +1,986 @@
+use std::io::{Read, Write};
+
+use lambdaworks_crypto::{
+    errors::SetupFileError,
+    merkle_tree::proof::Proof,
+    setup_file::{
+        field_element_from_bytes_le, field_element_size, u64_from_bytes, SetupFileReader,
+        SetupFileWriter, STARK_PROOF_KIND, U64_SIZE,
+    },
+};
+use lambdaworks_math::{
+    errors::DeserializationError,
+    field::{
+        element::FieldElement,
+        traits::{IsField, IsSubFieldOf},
+    },
+    traits::ByteConversion,
+};
+
+use crate::{
+    config::{Commitment, MerkleHash, COMMITMENT_SIZE},
+    fri::fri_decommit::FriDecommitment,
+    table::Table,
+};
+
+use super::stark::{DeepPolynomialOpening, PolynomialOpenings, StarkProof};
+
+const TRACE_LENGTH_SECTION: u32 = 1;
+/// Root of the main trace, followed by the ones of the auxiliary trace of each interaction round.
+const TRACE_ROOTS_SECTION: u32 = 2;
+const TRACE_OOD_WIDTH_SECTION: u32 = 3;
+const TRACE_OOD_EVALUATIONS_SECTION: u32 = 4;
+const COMPOSITION_POLY_ROOT_SECTION: u32 = 5;
+const COMPOSITION_POLY_OOD_EVALUATIONS_SECTION: u32 = 6;
+const FRI_LAYERS_ROOTS_SECTION: u32 = 7;
+/// Coefficients of the last layer of FRI.
+const FRI_LAST_VALUE_SECTION: u32 = 8;
+/// Number of authentication paths of each query.
+const FRI_QUERY_LAYERS_SECTION: u32 = 9;
+/// The paths of all the queries, as a list (two sections).
+const FRI_AUTH_PATHS_SECTION: u32 = 10;
+/// The symmetric evaluations of each query, as a list (two sections).
+const FRI_EVALUATIONS_SYM_SECTION: u32 = 12;
+/// Openings of the composition polynomial, main trace and auxiliary trace take four sections
+/// each: a list with the two paths of every opening and a list with its two evaluations.
+const COMPOSITION_POLY_OPENINGS_SECTION: u32 = 14;
+const MAIN_TRACE_OPENINGS_SECTION: u32 = 18;
+/// The auxiliary trace has one opening for each query and interaction round, by query.
+const AUX_TRACE_OPENINGS_SECTION: u32 = 22;
+/// Empty if the proof has no grinding nonce.
+const NONCE_SECTION: u32 = 26;
+/// Openings of the preprocessed columns, in four sections like the other openings. Proofs
+/// written before preprocessed columns existed end at the nonce, and are read without them.
+const PREPROCESSED_TRACE_OPENINGS_SECTION: u32 = 27;
+/// For every authentication path, in the order they are written, the earlier path of the same
+/// kind whose last nodes it shares, and how many, as a pair of `u64`. The paths are written
+/// without the shared nodes. Proofs written before sharing existed end before this section, with
+/// whole paths.
+const SHARED_PATH_NODES_SECTION: u32 = 31;
+/// The salts of the opened Merkle leaves, as a list (two sections): the ones of the layers of
+/// FRI of each query, then for each opening, the one of the composition polynomial and the two
+/// of each interaction round of the auxiliary trace. They are empty unless the proof is
+/// zero-knowledge. Proofs written before salts existed end before this section.
+const SALTS_SECTION: u32 = 32;
+/// The two salts of each opening of the main trace, as a list (two sections).
+const MAIN_TRACE_SALTS_SECTION: u32 = 34;
+/// The hash of the Merkle trees, as its index in [`MERKLE_HASHES`]. Proofs written before the
+/// hash could be chosen end before this section, and use Keccak256.
+const MERKLE_HASH_SECTION: u32 = 36;
+
+const NUM_SECTIONS: u32 = 36;
+
+const MERKLE_HASHES: [MerkleHash; 4] = [
+    MerkleHash::Keccak256,
+    MerkleHash::Blake3,
+    MerkleHash::Poseidon,
+    MerkleHash::Poseidon2,
+];
+
+impl<F, E> StarkProof<F, E>
+where
+    F: IsSubFieldOf<E>,
+    E: IsField,
+    FieldElement<F>: ByteConversion,
+    FieldElement<E>: ByteConversion,
+{
+    /// Writes the proof as a [`lambdaworks_crypto::setup_file`].
+    pub fn write_to<W: Write>(&self, writer: W) -> Result<(), SetupFileError> {
+        let mut file = SetupFileWriter::new(writer, STARK_PROOF_KIND, NUM_SECTIONS)?;
+        let encode_root = |root: &Commitment| root.to_vec();
+        let encode = FieldElement::<E>::to_bytes_le;
+        let [fri_shared, composition_shared, main_shared, aux_shared, preprocessed_shared] =
+            self.path_lists().map(|paths| share_path_nodes(&paths));
+
+        file.write_section(TRACE_LENGTH_SECTION, &[self.trace_length as u64])?;
+        let mut trace_roots = vec![&self.lde_trace_main_merkle_root];
+        trace_roots.extend(&self.lde_trace_aux_merkle_roots);
+        file.write_section_with(TRACE_ROOTS_SECTION, trace_roots, encode_root)?;
+        file.write_section(
+            TRACE_OOD_WIDTH_SECTION,
+            &[self.trace_ood_evaluations.width as u64],
+        )?;
+        file.write_section_with(
+            TRACE_OOD_EVALUATIONS_SECTION,
+            &self.trace_ood_evaluations.data,
+            encode,
+        )?;
+        file.write_section_with(
+            COMPOSITION_POLY_ROOT_SECTION,
+            [&self.composition_poly_root],
+            encode_root,
+        )?;
+        file.write_section_with(
+            COMPOSITION_POLY_OOD_EVALUATIONS_SECTION,
+            &self.composition_poly_parts_ood_evaluation,
+            encode,
+        )?;
+        file.write_section_with(
+            FRI_LAYERS_ROOTS_SECTION,
+            &self.fri_layers_merkle_roots,
+            encode_root,
+        )?;
+        file.write_section_with(
+            FRI_LAST_VALUE_SECTION,
+            &self.fri_last_layer_coefficients,
+            encode,
+        )?;
+
+        let query_layers: Vec<u64> = self
+            .query_list
+            .iter()
+            .map(|query| query.layers_auth_paths.len() as u64)
+            .collect();
+        file.write_section(FRI_QUERY_LAYERS_SECTION, &query_layers)?;
+        let auth_paths: Vec<&[Commitment]> = self
+            .query_list
+            .iter()
+            .flat_map(|query| &query.layers_auth_paths)
+            .zip(&fri_shared)
+            .map(|(path, shared)| shared.unshared_nodes(&path.merkle_path))
+            .collect();
+        write_lists(&mut file, FRI_AUTH_PATHS_SECTION, &auth_paths, encode_root)?;
+        let evaluations_sym: Vec<&[FieldElement<E>]> = self
+            .query_list
+            .iter()
+            .map(|query| query.layers_evaluations_sym.as_slice())
+            .collect();
+        write_lists(
+            &mut file,
+            FRI_EVALUATIONS_SYM_SECTION,
+            &evaluations_sym,
+            encode,
+        )?;
+
+        let openings = &self.deep_poly_openings;
+        let composition_poly: Vec<_> = openings.iter().map(|o| &o.composition_poly).collect();
+        write_openings(
+            &mut file,
+            COMPOSITION_POLY_OPENINGS_SECTION,
+            &composition_poly,
+            &composition_shared,
+        )?;
+        let main_trace: Vec<_> = openings.iter().map(|o| &o.main_trace_polys).collect();
+        write_openings(
+            &mut file,
+            MAIN_TRACE_OPENINGS_SECTION,
+            &main_trace,
+            &main_shared,
+        )?;
+        let aux_trace: Vec<_> = openings.iter().flat_map(|o| &o.aux_trace_polys).collect();
+        write_openings(
+            &mut file,
+            AUX_TRACE_OPENINGS_SECTION,
+            &aux_trace,
+            &aux_shared,
+        )?;
+
+        file.write_section(NONCE_SECTION, self.nonce.iter())?;
+        let preprocessed_trace: Vec<_> = openings
+            .iter()
+            .filter_map(|o| o.preprocessed_trace_polys.as_ref())
+            .collect();
+        write_openings(
+            &mut file,
+            PREPROCESSED_TRACE_OPENINGS_SECTION,
+            &preprocessed_trace,
+            &preprocessed_shared,
+        )?;
+        let shared: Vec<_> = [
+            fri_shared,
+            composition_shared,
+            main_shared,
+            aux_shared,
+            preprocessed_shared,
+        ]
+        .concat();
+        file.write_section_with(SHARED_PATH_NODES_SECTION, &shared, SharedPathNodes::encode)?;
+
+        let mut salts: Vec<&[FieldElement<E>]> = self
+            .query_list
+            .iter()
+            .flat_map(|query| query.layers_salts.iter().map(Vec::as_slice))
+            .collect();
+        for opening in openings {
+            salts.push(&opening.composition_poly.salt);
+            for opening in &opening.aux_trace_polys {
+                salts.extend([opening.salt.as_slice(), opening.salt_sym.as_slice()]);
+            }
+        }
+        write_lists(&mut file, SALTS_SECTION, &salts, encode)?;
+        let main_trace_salts: Vec<&[FieldElement<F>]> = main_trace
+            .iter()
+            .flat_map(|opening| [opening.salt.as_slice(), opening.salt_sym.as_slice()])
+            .collect();
+        write_lists(
+            &mut file,
+            MAIN_TRACE_SALTS_SECTION,
+            &main_trace_salts,
+            FieldElement::<F>::to_bytes_le,
+        )?;
+        let merkle_hash = MERKLE_HASHES
+            .iter()
+            .position(|hash| *hash == self.merkle_hash)
+            .unwrap();
+        file.write_section(MERKLE_HASH_SECTION, &[merkle_hash as u64])?;
+        file.finish()?;
+        Ok(())
+    }
+
+    /// Reads a proof written by [`StarkProof::write_to`]. Pass a buffered reader, since
+    /// elements are read one at a time.
+    pub fn read_from<R: Read>(reader: R) -> Result<Self, SetupFileError> {
+        let mut file = SetupFileReader::new(reader, STARK_PROOF_KIND)?;
+
+        let trace_length = read_single(&mut file, TRACE_LENGTH_SECTION, U64_SIZE, u64_from_bytes)?;
+        let trace_length = usize::try_from(trace_length).map_err(|_| invalid_value())?;
+        let mut lde_trace_aux_merkle_roots = file
+            .section(TRACE_ROOTS_SECTION, COMMITMENT_SIZE)?
+            .read_all_with(commitment_from_bytes)?;
+        if lde_trace_aux_merkle_roots.is_empty() {
+            return Err(invalid_value());
+        }
+        let lde_trace_main_merkle_root = lde_trace_aux_merkle_roots.remove(0);
+        let width = read_single(&mut file, TRACE_OOD_WIDTH_SECTION, U64_SIZE, u64_from_bytes)?;
+        let width = usize::try_from(width).map_err(|_| invalid_value())?;
+        let data = file
+            .section(TRACE_OOD_EVALUATIONS_SECTION, field_element_size::<E>())?
+            .read_all_with(field_element_from_bytes_le)?;
+        if (width == 0 && !data.is_empty()) || (width != 0 && data.len() % width != 0) {
+            return Err(invalid_value());
+        }
+        let trace_ood_evaluations = Table::new(data, width);
+        let composition_poly_root = read_single(
+            &mut file,
+            COMPOSITION_POLY_ROOT_SECTION,
+            COMMITMENT_SIZE,
+            commitment_from_bytes,
+        )?;
+        let composition_poly_parts_ood_evaluation = file
+            .section(
+                COMPOSITION_POLY_OOD_EVALUATIONS_SECTION,
+                field_element_size::<E>(),
+            )?
+            .read_all_with(field_element_from_bytes_le)?;
+        let fri_layers_merkle_roots = file
+            .section(FRI_LAYERS_ROOTS_SECTION, COMMITMENT_SIZE)?
+            .read_all_with(commitment_from_bytes)?;
+        let fri_last_layer_coefficients = file
+            .section(FRI_LAST_VALUE_SECTION, field_element_size::<E>())?
+            .read_all_with(field_element_from_bytes_le)?;
+
+        let query_layers = file
+            .section(FRI_QUERY_LAYERS_SECTION, U64_SIZE)?
+            .read_all_with(u64_from_bytes)?;
+        let auth_paths = read_lists(
+            &mut file,
+            FRI_AUTH_PATHS_SECTION,
+            COMMITMENT_SIZE,
+            commitment_from_bytes,
+        )?;
+        let evaluations_sym = read_lists(
+            &mut file,
+            FRI_EVALUATIONS_SYM_SECTION,
+            field_element_size::<E>(),
+            field_element_from_bytes_le,
+        )?;
+        let total_layers = query_layers
+            .iter()
+            .try_fold(0u64, |total, layers| total.checked_add(*layers));
+        if total_layers != Some(auth_paths.len() as u64)
+            || evaluations_sym.len() != query_layers.len()
+        {
+            return Err(invalid_value());
+        }
+        let mut auth_paths = auth_paths.into_iter();
+        let query_list = query_layers
+            .into_iter()
+            .zip(evaluations_sym)
+            .map(|(layers, layers_evaluations_sym)| FriDecommitment {
+                layers_auth_paths: auth_paths
+                    .by_ref()
+                    .take(layers as usize)
+                    .map(|merkle_path| Proof { merkle_path })
+                    .collect(),
+                layers_evaluations_sym,
+                layers_salts: vec![Vec::new(); layers as usize],
+            })
+            .collect();
+
+        let composition_poly = read_openings(&mut file, COMPOSITION_POLY_OPENINGS_SECTION)?;
+        let main_trace = read_openings(&mut file, MAIN_TRACE_OPENINGS_SECTION)?;
+        let aux_trace = read_openings(&mut file, AUX_TRACE_OPENINGS_SECTION)?;
+
+        let mut nonce = file
+            .section(NONCE_SECTION, U64_SIZE)?
+            .read_all_with(u64_from_bytes)?;
+        if nonce.len() > 1 {
+            return Err(invalid_value());
+        }
+
+        let preprocessed_trace = match read_openings(&mut file, PREPROCESSED_TRACE_OPENINGS_SECTION)
+        {
+            Err(SetupFileError::MissingSection) => Vec::new(),
+            preprocessed_trace => preprocessed_trace?,
+        };
+
+        let num_openings = composition_poly.len();
+        if main_trace.len() != num_openings
+            || aux_trace.len() != num_openings * lde_trace_aux_merkle_roots.len()
+            || !(preprocessed_trace.is_empty() || preprocessed_trace.len() == num_openings)
+        {
+            return Err(invalid_value());
+        }
+        let mut aux_trace = aux_trace.into_iter();
+        let num_aux_rounds = lde_trace_aux_merkle_roots.len();
+        let mut preprocessed_trace = preprocessed_trace.into_iter();
+        let deep_poly_openings = composition_poly
+            .into_iter()
+            .zip(main_trace)
+            .map(
+                |(composition_poly, main_trace_polys)| DeepPolynomialOpening {
+                    composition_poly,
+                    preprocessed_trace_polys: preprocessed_trace.next(),
+                    main_trace_polys,
+                    aux_trace_polys: aux_trace.by_ref().take(num_aux_rounds).collect(),
+                },
+            )
+            .collect();
+
+        let shared = match file.section(SHARED_PATH_NODES_SECTION, SharedPathNodes::SIZE) {
+            Err(SetupFileError::MissingSection) => None,
+            section => Some(section?.read_all_with(SharedPathNodes::decode)?),
+        };
+        let salts = match read_lists(
+            &mut file,
+            SALTS_SECTION,
+            field_element_size::<E>(),
+            field_element_from_bytes_le,
+        ) {
+            Err(SetupFileError::MissingSection) => None,
+            salts => Some((
+                salts?,
+                read_lists(
+                    &mut file,
+                    MAIN_TRACE_SALTS_SECTION,
+                    field_element_size::<F>(),
+                    field_element_from_bytes_le,
+                )?,
+            )),
+        };
+        let merkle_hash = match file.section(MERKLE_HASH_SECTION, U64_SIZE) {
+            Err(SetupFileError::MissingSection) => MerkleHash::Keccak256,
+            section => match section?.read_all_with(u64_from_bytes)?[..] {
+                [index] => *MERKLE_HASHES
+                    .get(index as usize)
+                    .ok_or_else(invalid_value)?,
+                _ => return Err(invalid_value()),
+            },
+        };
+
+        let mut proof = Self {
+            trace_length,
+            lde_trace_main_merkle_root,
+            lde_trace_aux_merkle_roots,
+            trace_ood_evaluations,
+            composition_poly_root,
+            composition_poly_parts_ood_evaluation,
+            fri_layers_merkle_roots,
+            fri_last_layer_coefficients,
+            query_list,
+            deep_poly_openings,
+            nonce: nonce.pop(),
+            merkle_hash,
+        };
+        if let Some(shared) = shared {
+            proof.restore_shared_path_nodes(&shared)?;
+        }
+        if let Some((salts, main_trace_salts)) = salts {
+            proof.restore_salts(salts, main_trace_salts)?;
+        }
+        Ok(proof)
+    }
+
+    /// Sets the salts of the openings to the ones read from [`SALTS_SECTION`] and
+    /// [`MAIN_TRACE_SALTS_SECTION`].
+    fn restore_salts(
+        &mut self,
+        salts: Vec<Vec<FieldElement<E>>>,
+        main_trace_salts: Vec<Vec<FieldElement<F>>>,
+    ) -> Result<(), SetupFileError> {
+        let num_layers: usize = self
+            .query_list
+            .iter()
+            .map(|query| query.layers_auth_paths.len())
+            .sum();
+        let num_opening_salts: usize = self
+            .deep_poly_openings
+            .iter()
+            .map(|opening| 1 + 2 * opening.aux_trace_polys.len())
+            .sum();
+        if salts.len() != num_layers + num_opening_salts
+            || main_trace_salts.len() != 2 * self.deep_poly_openings.len()
+        {
+            return Err(invalid_value());
+        }
+
+        let mut salts = salts.into_iter();
+        let mut main_trace_salts = main_trace_salts.into_iter();
+        for query in &mut self.query_list {
+            query.layers_salts = salts.by_ref().take(query.layers_auth_paths.len()).collect();
+        }
+        for opening in &mut self.deep_poly_openings {
+            let salt = salts.next().ok_or_else(invalid_value)?;
+            opening.composition_poly.salt_sym = salt.clone();
+            opening.composition_poly.salt = salt;
+            opening.main_trace_polys.salt = main_trace_salts.next().ok_or_else(invalid_value)?;
+            opening.main_trace_polys.salt_sym =
+                main_trace_salts.next().ok_or_else(invalid_value)?;
+            for opening in &mut opening.aux_trace_polys {
+                opening.salt = salts.next().ok_or_else(invalid_value)?;
+                opening.salt_sym = salts.next().ok_or_else(invalid_value)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// The authentication paths of the proof in the order they are written, in lists of paths
+    /// of the same kind: the ones of FRI, and the openings of the composition polynomial, the
+    /// main trace, the auxiliary trace and the preprocessed trace.
+    fn path_lists(&self) -> [Vec<&Vec<Commitment>>; 5] {
+        let mut lists: [Vec<&Vec<Commitment>>; 5] = Default::default();
+        for query in &self.query_list {
+            lists[0].extend(query.layers_auth_paths.iter().map(|path| &path.merkle_path));
+        }
+        for opening in &self.deep_poly_openings {
+            let [_, composition, main, aux, preprocessed] = &mut lists;
+            composition.extend([
+                &opening.composition_poly.proof.merkle_path,
+                &opening.composition_poly.proof_sym.merkle_path,
+            ]);
+            main.extend([
+                &opening.main_trace_polys.proof.merkle_path,
+                &opening.main_trace_polys.proof_sym.merkle_path,
+            ]);
+            for opening in &opening.aux_trace_polys {
+                aux.extend([&opening.proof.merkle_path, &opening.proof_sym.merkle_path]);
+            }
+            if let Some(opening) = &opening.preprocessed_trace_polys {
+                preprocessed.extend([&opening.proof.merkle_path, &opening.proof_sym.merkle_path]);
+            }
+        }
+        lists
+    }
+
+    /// Like [`Self::path_lists`], with mutable paths.
+    fn path_lists_mut(&mut self) -> [Vec<&mut Vec<Commitment>>; 5] {
+        let mut lists: [Vec<&mut Vec<Commitment>>; 5] = Default::default();
+        for query in &mut self.query_list {
+            lists[0].extend(
+                query
+                    .layers_auth_paths
+                    .iter_mut()
+                    .map(|path| &mut path.merkle_path),
+            );
+        }
+        for opening in &mut self.deep_poly_openings {
+            let [_, composition, main, aux, preprocessed] = &mut lists;
+            composition.extend([
+                &mut opening.composition_poly.proof.merkle_path,
+                &mut opening.composition_poly.proof_sym.merkle_path,
+            ]);
+            main.extend([
+                &mut opening.main_trace_polys.proof.merkle_path,
+                &mut opening.main_trace_polys.proof_sym.merkle_path,
+            ]);
+            for opening in &mut opening.aux_trace_polys {
+                aux.extend([
+                    &mut opening.proof.merkle_path,
+                    &mut opening.proof_sym.merkle_path,
+                ]);
+            }
+            if let Some(opening) = &mut opening.preprocessed_trace_polys {
+                preprocessed.extend([
+                    &mut opening.proof.merkle_path,
+                    &mut opening.proof_sym.merkle_path,
+                ]);
+            }
+        }
+        lists
+    }
+
+    /// Appends to the paths read without their shared nodes the ones of the earlier paths.
+    fn restore_shared_path_nodes(
+        &mut self,
+        shared: &[SharedPathNodes],
+    ) -> Result<(), SetupFileError> {
+        let mut shared = shared.iter();
+        for mut paths in self.path_lists_mut() {
+            for index in 0..paths.len() {
+                let SharedPathNodes { source, len } = *shared.next().ok_or_else(invalid_value)?;
+                if len == 0 {
+                    continue;
+                }
+                let (earlier, rest) = paths.split_at_mut(index);
+                let source = earlier.get(source as usize).ok_or_else(invalid_value)?;
+                let start = source
+                    .len()
+                    .checked_sub(len as usize)
+                    .ok_or_else(invalid_value)?;
+                rest[0].extend_from_slice(&source[start..]);
+            }
+        }
+        if shared.next().is_some() {
+            return Err(invalid_value());
+        }
+        Ok(())
+    }
+}
+
+/// The last `len` nodes of an authentication path, which are the last ones of the earlier path
+/// `source` of the same list.
+#[derive(Clone, Copy, Default)]
+struct SharedPathNodes {
+    source: u64,
+    len: u64,
+}
+
+impl SharedPathNodes {
+    const SIZE: usize = 2 * U64_SIZE;
+
+    /// The nodes of `path` that are not shared.
+    fn unshared_nodes<'a>(&self, path: &'a [Commitment]) -> &'a [Commitment] {
+        &path[..path.len() - self.len as usize]
+    }
+
+    fn encode(&self) -> Vec<u8> {
+        [self.source.to_le_bytes(), self.len.to_le_bytes()].concat()
+    }
+
+    fn decode(bytes: &[u8]) -> Result<Self, DeserializationError> {
+        if bytes.len() != Self::SIZE {
+            return Err(DeserializationError::InvalidAmountOfBytes);
+        }
+        Ok(Self {
+            source: u64_from_bytes(&bytes[..8])?,
+            len: u64_from_bytes(&bytes[8..])?,
+        })
+    }
+}
+
+/// Finds, for each path of `paths`, the earlier one with which it shares the most last nodes.
+/// Paths of leaves of the same tree share the nodes above the level where the leaves meet.
+fn share_path_nodes(paths: &[&Vec<Commitment>]) -> Vec<SharedPathNodes> {
+    paths
+        .iter()
+        .enumerate()
+        .map(|(index, path)| {
+            paths[..index]
+                .iter()
+                .enumerate()
+                .map(|(source, earlier)| SharedPathNodes {
+                    source: source as u64,
+                    len: path
+                        .iter()
+                        .rev()
+                        .zip(earlier.iter().rev())
+                        .take_while(|(node, earlier_node)| node == earlier_node)
+                        .count() as u64,
+                })
+                .max_by_key(|shared| shared.len)
+                .filter(|shared| shared.len > 0)
+                .unwrap_or_default()
+        })
+        .collect()
+}
+
+/// Writes `lists` as a section with their lengths, with id `id`, followed by a section with
+/// their elements, with id `id + 1`.
+fn write_lists<W: Write, T>(
+    file: &mut SetupFileWriter<W>,
+    id: u32,
+    lists: &[&[T]],
+    encode: impl Fn(&T) -> Vec<u8>,
+) -> Result<(), SetupFileError> {
+    let lengths: Vec<u64> = lists.iter().map(|list| list.len() as u64).collect();
+    file.write_section(id, &lengths)?;
+    let elements: Vec<&T> = lists.iter().flat_map(|list| list.iter()).collect();
+    file.write_section_with(id + 1, &elements, |element| encode(element))
+}
+
+/// Reads lists written by [`write_lists`].
+fn read_lists<R: Read, T>(
+    file: &mut SetupFileReader<R>,
+    id: u32,
+    element_size: usize,
+    decode: impl Fn(&[u8]) -> Result<T, DeserializationError>,
+) -> Result<Vec<Vec<T>>, SetupFileError> {
+    let lengths = file.section(id, U64_SIZE)?.read_all_with(u64_from_bytes)?;
+    let mut section = file.section(id + 1, element_size)?;
+    let lists = lengths
+        .into_iter()
+        .map(|len| (0..len).map(|_| section.read_with(&decode)).collect())
+        .collect::<Result<Vec<Vec<T>>, _>>()?;
+    if section.remaining() != 0 {
+        return Err(invalid_value());
+    }
+    Ok(lists)
+}
+
+/// Writes `openings`, with the paths without the nodes in `shared`.
+fn write_openings<W: Write, F>(
+    file: &mut SetupFileWriter<W>,
+    id: u32,
+    openings: &[&PolynomialOpenings<F>],
+    shared: &[SharedPathNodes],
+) -> Result<(), SetupFileError>
+where
+    F: IsField,
+    FieldElement<F>: ByteConversion,
+{
+    let paths: Vec<&[Commitment]> = openings
+        .iter()
+        .flat_map(|opening| [&opening.proof, &opening.proof_sym])
+        .zip(shared)
+        .map(|(proof, shared)| shared.unshared_nodes(&proof.merkle_path))
+        .collect();
+    write_lists(file, id, &paths, |node| node.to_vec())?;
+    let evaluations: Vec<&[FieldElement<F>]> = openings
+        .iter()
+        .flat_map(|opening| [&opening.evaluations, &opening.evaluations_sym])
+        .map(Vec::as_slice)
+        .collect();
+    write_lists(file, id + 2, &evaluations, FieldElement::<F>::to_bytes_le)
+}
+
+fn read_openings<R: Read, F>(
+    file: &mut SetupFileReader<R>,
+    id: u32,
+) -> Result<Vec<PolynomialOpenings<F>>, SetupFileError>
+where
+    F: IsField,
+    FieldElement<F>: ByteConversion,
+{
+    let paths = read_lists(file, id, COMMITMENT_SIZE, commitment_from_bytes)?;
+    let evaluations = read_lists(
+        file,
+        id + 2,
+        field_element_size::<F>(),
+        field_element_from_bytes_le,
+    )?;
+    if paths.len() != evaluations.len() || paths.len() % 2 != 0 {
+        return Err(invalid_value());
+    }
+    let mut paths = paths.into_iter();
+    let mut evaluations = evaluations.into_iter();
+    let mut openings = Vec::with_capacity(paths.len() / 2);
+    while let (Some(path), Some(path_sym)) = (paths.next(), paths.next()) {
+        openings.push(PolynomialOpenings {
+            proof: Proof { merkle_path: path },
+            proof_sym: Proof {
+                merkle_path: path_sym,
+            },
+            evaluations: evaluations.next().unwrap(),
+            evaluations_sym: evaluations.next().unwrap(),
+            salt: Vec::new(),
+            salt_sym: Vec::new(),
+        });
+    }
+    Ok(openings)
+}
+
+/// Reads a section that must hold a single element.
+fn read_single<R: Read, T>(
+    file: &mut SetupFileReader<R>,
+    id: u32,
+    element_size: usize,
+    decode: impl Fn(&[u8]) -> Result<T, DeserializationError>,
+) -> Result<T, SetupFileError> {
+    let mut section = file.section(id, element_size)?;
+    if section.remaining() != 1 {
+        return Err(invalid_value());
+    }
+    section.read_with(decode)
+}
+
+fn commitment_from_bytes(bytes: &[u8]) -> Result<Commitment, DeserializationError> {
+    bytes
+        .try_into()
+        .map_err(|_| DeserializationError::InvalidAmountOfBytes)
+}
+
+fn invalid_value() -> SetupFileError {
+    SetupFileError::Deserialization(DeserializationError::InvalidValue)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        examples::{
+            fibonacci_rap::{fibonacci_rap_trace, FibonacciRAP, FibonacciRAPPublicInputs},
+            preprocessed_range_check::{
+                preprocessed_range_check_trace, range_check_table, PreprocessedRangeCheckAIR,
+            },
+            simple_fibonacci::{self, FibonacciAIR, FibonacciPublicInputs},
+        },
+        proof::options::ProofOptions,
+        prover::{IsStarkProver, Prover},
+        transcript::StoneProverTranscript,
+        verifier::{IsStarkVerifier, Verifier},
+        Felt252, PrimeField,
+    };
+    use lambdaworks_crypto::setup_file::{read_public_inputs, SETUP_FILE_VERSION};
+
+    fn write(proof: &StarkProof<PrimeField, PrimeField>) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        proof.write_to(&mut bytes).unwrap();
+        bytes
+    }
+
+    /// Reads the proof written as `bytes`, checking that writing it again gives the same bytes.
+    fn read(bytes: &[u8]) -> StarkProof<PrimeField, PrimeField> {
+        let proof = StarkProof::read_from(bytes).unwrap();
+        assert_eq!(write(&proof), bytes);
+        proof
+    }
+
+    #[test]
+    fn fibonacci_proof_can_be_written_and_read() {
+        let trace = simple_fibonacci::fibonacci_trace([Felt252::one(), Felt252::one()], 8);
+        let proof_options = ProofOptions::default_test_options();
+        let pub_inputs = FibonacciPublicInputs {
+            a0: Felt252::one(),
+            a1: Felt252::one(),
+        };
+        let proof = Prover::<FibonacciAIR<PrimeField>>::prove(
+            &trace,
+            &pub_inputs,
+            &proof_options,
+            StoneProverTranscript::new(&[]),
+        )
+        .unwrap();
+
+        let proof = read(&write(&proof));
+        assert!(Verifier::<FibonacciAIR<PrimeField>>::verify(
+            &proof,
+            &pub_inputs,
+            &proof_options,
+            StoneProverTranscript::new(&[]),
+        ));
+    }
+
+    #[test]
+    fn proof_with_auxiliary_trace_can_be_written_and_read() {
+        let steps = 16;
+        let trace = fibonacci_rap_trace([Felt252::one(), Felt252::one()], steps);
+        let proof_options = ProofOptions::default_test_options();
+        let pub_inputs = FibonacciRAPPublicInputs {
+            steps,
+            a0: Felt252::one(),
+            a1: Felt252::one(),
+        };
+        let proof = Prover::<FibonacciRAP<PrimeField>>::prove(
+            &trace,
+            &pub_inputs,
+            &proof_options,
+            StoneProverTranscript::new(&[]),
+        )
+        .unwrap();
+        assert_eq!(proof.lde_trace_aux_merkle_roots.len(), 1);
+
+        let proof = read(&write(&proof));
+        assert!(Verifier::<FibonacciRAP<PrimeField>>::verify(
+            &proof,
+            &pub_inputs,
+            &proof_options,
+            StoneProverTranscript::new(&[]),
+        ));
+    }
+
+    #[test]
+    fn zero_knowledge_proof_can_be_written_and_read() {
+        let steps = 120;
+        let trace = fibonacci_rap_trace([Felt252::one(), Felt252::one()], steps);
+        let proof_options = ProofOptions {
+            zk: true,
+            ..ProofOptions::default_test_options()
+        };
+        let pub_inputs = FibonacciRAPPublicInputs {
+            steps,
+            a0: Felt252::one(),
+            a1: Felt252::one(),
+        };
+        let proof = Prover::<FibonacciRAP<PrimeField>>::prove(
+            &trace,
+            &pub_inputs,
+            &proof_options,
+            StoneProverTranscript::new(&[]),
+        )
+        .unwrap();
+
+        let proof = read(&write(&proof));
+        assert!(!proof.deep_poly_openings[0].aux_trace_polys[0]
+            .salt
+            .is_empty());
+        assert!(Verifier::<FibonacciRAP<PrimeField>>::verify(
+            &proof,
+            &pub_inputs,
+            &proof_options,
+            StoneProverTranscript::new(&[]),
+        ));
+    }
+
+    #[test]
+    fn proof_with_poseidon_merkle_trees_can_be_written_and_read() {
+        let trace = simple_fibonacci::fibonacci_trace([Felt252::one(), Felt252::one()], 8);
+        let proof_options = ProofOptions {
+            merkle_hash: MerkleHash::Poseidon,
+            ..ProofOptions::default_test_options()
+        };
+        let pub_inputs = FibonacciPublicInputs {
+            a0: Felt252::one(),
+            a1: Felt252::one(),
+        };
+        let proof = Prover::<FibonacciAIR<PrimeField>>::prove(
+            &trace,
+            &pub_inputs,
+            &proof_options,
+            StoneProverTranscript::new(&[]),
+        )
+        .unwrap();
+
+        let proof = read(&write(&proof));
+        assert_eq!(proof.merkle_hash, MerkleHash::Poseidon);
+        assert!(Verifier::<FibonacciAIR<PrimeField>>::verify(
+            &proof,
+            &pub_inputs,
+            &proof_options,
+            StoneProverTranscript::new(&[]),
+        ));
+    }
+
+    #[test]
+    fn proof_with_preprocessed_columns_can_be_written_and_read() {
+        let proof_options = ProofOptions::default_test_options();
+        let preprocessed = Prover::<PreprocessedRangeCheckAIR<PrimeField>>::preprocess(
+            &range_check_table(8),
+            &proof_options,
+        );
+        let trace = preprocessed_range_check_trace((0..8).map(Felt252::from).collect());
+        let proof = Prover::<PreprocessedRangeCheckAIR<PrimeField>>::prove_with_preprocessed_trace(
+            &trace,
+            &preprocessed,
+            &(),
+            &proof_options,
+            StoneProverTranscript::new(&[]),
+        )
+        .unwrap();
+
+        let proof = read(&write(&proof));
+        assert!(proof.deep_poly_openings[0]
+            .preprocessed_trace_polys
+            .is_some());
+        assert!(
+            Verifier::<PreprocessedRangeCheckAIR<PrimeField>>::verify_with_preprocessed_commitment(
+                &proof,
+                Some(&preprocessed.commitment()),
+                &(),
+                &proof_options,
+                StoneProverTranscript::new(&[]),
+            )
+        );
+    }
+
+    #[test]
+    fn paths_are_written_without_the_nodes_they_share() {
+        let trace = simple_fibonacci::fibonacci_trace([Felt252::one(), Felt252::one()], 32);
+        let proof_options = ProofOptions {
+            fri_number_of_queries: 30,
+            ..ProofOptions::default_test_options()
+        };
+        let pub_inputs = FibonacciPublicInputs {
+            a0: Felt252::one(),
+            a1: Felt252::one(),
+        };
+        let proof = Prover::<FibonacciAIR<PrimeField>>::prove(
+            &trace,
+            &pub_inputs,
+            &proof_options,
+            StoneProverTranscript::new(&[]),
+        )
+        .unwrap();
+
+        let shared_nodes: u64 = proof
+            .path_lists()
+            .iter()
+            .flat_map(|paths| share_path_nodes(paths))
+            .map(|shared| shared.len)
+            .sum();
+        assert!(shared_nodes > 0);
+
+        let read_proof = read(&write(&proof));
+        assert_eq!(read_proof.path_lists(), proof.path_lists());
+        assert!(Verifier::<FibonacciAIR<PrimeField>>::verify(
+            &read_proof,
+            &pub_inputs,
+            &proof_options,
+            StoneProverTranscript::new(&[]),
+        ));
+    }
+
+    /// A Fibonacci proof and its public inputs as written by version 1 of the format. These
+    /// files must not be regenerated: readers have to keep accepting them after the format
+    /// changes.
+    const FIBONACCI_PROOF_V1: &[u8] = include_bytes!("test_files/fibonacci_proof_v1.bin");
+    const FIBONACCI_PUBLIC_INPUTS_V1: &[u8] =
+        include_bytes!("test_files/fibonacci_public_inputs_v1.bin");
+
+    #[test]
+    fn version_1_proof_and_public_inputs_are_read() {
+        for bytes in [FIBONACCI_PROOF_V1, FIBONACCI_PUBLIC_INPUTS_V1] {
+            assert_eq!(bytes[4..8], 1u32.to_le_bytes());
+        }
+        let proof = StarkProof::<PrimeField, PrimeField>::read_from(FIBONACCI_PROOF_V1).unwrap();
+        let [a0, a1] = read_public_inputs::<PrimeField, _>(FIBONACCI_PUBLIC_INPUTS_V1)
+            .unwrap()
+            .try_into()
+            .unwrap();
+        assert_eq!((&a0, &a1), (&Felt252::one(), &Felt252::one()));
+
+        assert!(Verifier::<FibonacciAIR<PrimeField>>::verify(
+            &proof,
+            &FibonacciPublicInputs { a0, a1 },
+            &ProofOptions::default_test_options(),
+            StoneProverTranscript::new(&[]),
+        ));
+    }
+
+    #[test]
+    fn malformed_proofs_are_rejected() {
+        let trace = simple_fibonacci::fibonacci_trace([Felt252::one(), Felt252::one()], 8);
+        let proof = Prover::<FibonacciAIR<PrimeField>>::prove(
+            &trace,
+            &FibonacciPublicInputs {
+                a0: Felt252::one(),
+                a1: Felt252::one(),
+            },
+            &ProofOptions::default_test_options(),
+            StoneProverTranscript::new(&[]),
+        )
+        .unwrap();
+        let bytes = write(&proof);
+
+        let truncated = &bytes[..bytes.len() - 1];
+        assert!(matches!(
+            StarkProof::<PrimeField, PrimeField>::read_from(truncated),
+            Err(SetupFileError::Io(_))
+        ));
+        let mut newer_version = bytes;
+        newer_version[4..8].copy_from_slice(&(SETUP_FILE_VERSION + 1).to_le_bytes());
+        assert!(matches!(
+            StarkProof::<PrimeField, PrimeField>::read_from(newer_version.as_slice()),
+            Err(SetupFileError::UnsupportedVersion(_))
+        ));
+    }
+}
@@ -11,7 +11,7 @@ use lambdaworks_math::{
 };
 
 use crate::{
-    config::Commitment,
+    config::{Commitment, MerkleHash},
     domain::Domain,
     fri::fri_decommit::FriDecommitment,
     table::Table,
@@ -28,27 +28,33 @@ pub struct PolynomialOpenings<F: IsField> {
     pub proof_sym: Proof<Commitment>,
     pub evaluations: Vec<FieldElement<F>>,
     pub evaluations_sym: Vec<FieldElement<F>>,
+    /// The salts at the end of the opened leaves, empty unless the proof is zero-knowledge.
+    pub salt: Vec<FieldElement<F>>,
+    pub salt_sym: Vec<FieldElement<F>>,
 }
 
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct DeepPolynomialOpening<F: IsSubFieldOf<E>, E: IsField> {
     pub composition_poly: PolynomialOpenings<E>,
+    /// Openings of the preprocessed columns, against their commitment known by the verifier.
+    pub preprocessed_trace_polys: Option<PolynomialOpenings<F>>,
     pub main_trace_polys: PolynomialOpenings<F>,
-    pub aux_trace_polys: Option<PolynomialOpenings<E>>,
+    /// Openings of the auxiliary columns of each interaction round.
+    pub aux_trace_polys: Vec<PolynomialOpenings<E>>,
 }
 
 pub type DeepPolynomialOpenings<F, E> = Vec<DeepPolynomialOpening<F, E>>;
 
-#[derive(Debug, serde::Serialize, serde::Deserialize)]
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct StarkProof<F: IsSubFieldOf<E>, E: IsField> {
     // Length of the execution trace
     pub trace_length: usize,
     // Commitments of the trace columns
     // [tⱼ]
     pub lde_trace_main_merkle_root: Commitment,
-    // Commitments of auxiliary trace columns
+    // Commitments of auxiliary trace columns, one for each interaction round with columns
     // [tⱼ]
-    pub lde_trace_aux_merkle_root: Option<Commitment>,
+    pub lde_trace_aux_merkle_roots: Vec<Commitment>,
     // tⱼ(zgᵏ)
     pub trace_ood_evaluations: Table<E>,
     // Commitments to Hᵢ
@@ -57,8 +63,8 @@ pub struct StarkProof<F: IsSubFieldOf<E>, E: IsField> {
     pub composition_poly_parts_ood_evaluation: Vec<FieldElement<E>>,
     // [pₖ]
     pub fri_layers_merkle_roots: Vec<Commitment>,
-    // pₙ
-    pub fri_last_value: FieldElement<E>,
+    // Coefficients of pₙ, of degree less than the `fri_last_layer_degree_bound` of the options
+    pub fri_last_layer_coefficients: Vec<FieldElement<E>>,
     // Open(pₖ(Dₖ), −𝜐ₛ^(2ᵏ))
     pub query_list: Vec<FriDecommitment<E>>,
     // Open(H₁(D_LDE, 𝜐ᵢ), Open(H₂(D_LDE, 𝜐ᵢ), Open(tⱼ(D_LDE), 𝜐ᵢ)
@@ -66,6 +72,8 @@ pub struct StarkProof<F: IsSubFieldOf<E>, E: IsField> {
     pub deep_poly_openings: DeepPolynomialOpenings<F, E>,
     // nonce obtained from grinding
     pub nonce: Option<u64>,
+    // Hash of the Merkle trees of all the commitments
+    pub merkle_hash: MerkleHash,
 }
 
 /// Serializer compatible with Stone prover
@@ -97,16 +105,16 @@ impl StoneCompatibleSerializer {
         output
     }
 
-    /// Appends the root bytes of the Merkle tree for the main trace, and if there is a RAP round,
-    /// it also appends the root bytes of the Merkle tree for the extended columns.
+    /// Appends the root bytes of the Merkle tree for the main trace, followed by the root bytes of
+    /// the Merkle trees for the extended columns of each RAP round.
     fn append_trace_commitment(
         proof: &StarkProof<Stark252PrimeField, Stark252PrimeField>,
         output: &mut Vec<u8>,
     ) {
         output.extend_from_slice(&proof.lde_trace_main_merkle_root);
 
-        if let Some(lde_trace_aux_merkle_root) = proof.lde_trace_aux_merkle_root {
-            output.extend_from_slice(&lde_trace_aux_merkle_root);
+        for lde_trace_aux_merkle_root in proof.lde_trace_aux_merkle_roots.iter() {
+            output.extend_from_slice(lde_trace_aux_merkle_root);
         }
     }
 
@@ -156,7 +164,9 @@ impl StoneCompatibleSerializer {
                 .collect::<Vec<_>>(),
         );
 
-        output.extend_from_slice(&proof.fri_last_value.as_bytes());
+        for coefficient in proof.fri_last_layer_coefficients.iter() {
+            output.extend_from_slice(&coefficient.as_bytes());
+        }
     }
 
     /// Appends the proof of work nonce in case there is one. There could be none if the `grinding_factor`
@@ -216,7 +226,7 @@ impl StoneCompatibleSerializer {
             for elem in opening.main_trace_polys.evaluations.iter() {
                 output.extend_from_slice(&elem.as_bytes());
             }
-            if let Some(aux) = &opening.aux_trace_polys {
+            for aux in opening.aux_trace_polys.iter() {
                 for elem in aux.evaluations.iter() {
                     output.extend_from_slice(&elem.as_bytes());
                 }
@@ -225,7 +235,7 @@ impl StoneCompatibleSerializer {
             for elem in opening.main_trace_polys.evaluations_sym.iter() {
                 output.extend_from_slice(&elem.as_bytes());
             }
-            if let Some(aux) = &opening.aux_trace_polys {
+            for aux in opening.aux_trace_polys.iter() {
                 for elem in aux.evaluations_sym.iter() {
                     output.extend_from_slice(&elem.as_bytes());
                 }
@@ -254,22 +264,24 @@ impl StoneCompatibleSerializer {
             output.extend_from_slice(node);
         }
 
-        //    Aux trace
-        let mut all_openings_aux_trace_polys_are_some = true;
-        let mut fri_trace_paths: Vec<&Proof<Commitment>> = Vec::new();
-        for opening in proof.deep_poly_openings.iter() {
-            if let Some(aux_trace_polys) = &opening.aux_trace_polys {
-                fri_trace_paths.push(&aux_trace_polys.proof);
-                fri_trace_paths.push(&aux_trace_polys.proof_sym);
-            } else {
-                all_openings_aux_trace_polys_are_some = false;
+        //    Aux trace, one tree for each RAP round
+        for round in 0..proof.lde_trace_aux_merkle_roots.len() {
+            let mut all_openings_aux_trace_polys_are_some = true;
+            let mut fri_trace_paths: Vec<&Proof<Commitment>> = Vec::new();
+            for opening in proof.deep_poly_openings.iter() {
+                if let Some(aux_trace_polys) = opening.aux_trace_polys.get(round) {
+                    fri_trace_paths.push(&aux_trace_polys.proof);
+                    fri_trace_paths.push(&aux_trace_polys.proof_sym);
+                } else {
+                    all_openings_aux_trace_polys_are_some = false;
+                }
             }
-        }
-        if all_openings_aux_trace_polys_are_some {
-            let nodes =
-                Self::merge_authentication_paths(&fri_trace_paths, &fri_trace_query_indexes);
-            for node in nodes.iter() {
-                output.extend_from_slice(node);
+            if all_openings_aux_trace_polys_are_some {
+                let nodes =
+                    Self::merge_authentication_paths(&fri_trace_paths, &fri_trace_query_indexes);
+                for node in nodes.iter() {
+                    output.extend_from_slice(node);
+                }
             }
         }
 
@@ -457,6 +469,7 @@ impl StoneCompatibleSerializer {
         let challenges = Verifier::step_1_replay_rounds_and_recover_challenges(
             &air,
             proof,
+            None,
             &domain,
             &mut transcript,
         );
@@ -469,9 +482,11 @@ mod tests {
     use lambdaworks_math::{field::element::FieldElement, traits::AsBytes};
 
     use crate::{
+        config::MerkleHash,
         examples::fibonacci_2_cols_shifted::{self, Fibonacci2ColsShifted},
         proof::{options::ProofOptions, stark::StoneCompatibleSerializer},
         prover::{IsStarkProver, Prover},
+        trace::LDELayout,
         transcript::StoneProverTranscript,
     };
 
@@ -485,6 +500,10 @@ mod tests {
             blowup_factor: 4,
             coset_offset: 3,
             grinding_factor: 0,
+            fri_last_layer_degree_bound: 1,
+            zk: false,
+            merkle_hash: MerkleHash::Keccak256,
+            lde_layout: LDELayout::Natural,
             fri_number_of_queries: 1,
         };
 
@@ -563,6 +582,10 @@ mod tests {
             blowup_factor: 2,
             coset_offset: 3,
             grinding_factor: 0,
+            fri_last_layer_degree_bound: 1,
+            zk: false,
+            merkle_hash: MerkleHash::Keccak256,
+            lde_layout: LDELayout::Natural,
             fri_number_of_queries: 10,
         };
 
@@ -655,6 +678,10 @@ mod tests {
             blowup_factor: 64,
             coset_offset: 3,
             grinding_factor: 0,
+            fri_last_layer_degree_bound: 1,
+            zk: false,
+            merkle_hash: MerkleHash::Keccak256,
+            lde_layout: LDELayout::Natural,
             fri_number_of_queries: 1,
         };
 
@@ -919,6 +946,10 @@ mod tests {
             blowup_factor: 2,
             coset_offset: 3,
             grinding_factor: 0,
+            fri_last_layer_degree_bound: 1,
+            zk: false,
+            merkle_hash: MerkleHash::Keccak256,
+            lde_layout: LDELayout::Natural,
             fri_number_of_queries: 2,
         };
 
@@ -997,6 +1028,10 @@ mod tests {
             blowup_factor: 4,
             coset_offset: 3,
             grinding_factor: 0,
+            fri_last_layer_degree_bound: 1,
+            zk: false,
+            merkle_hash: MerkleHash::Keccak256,
+            lde_layout: LDELayout::Natural,
             fri_number_of_queries: 3,
         };
 
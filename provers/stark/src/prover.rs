@@ -1,6 +1,8 @@
-use std::marker::PhantomData;
 #[cfg(feature = "instruments")]
-use std::time::Instant;
+use lambdaworks_math::environment::Stopwatch;
+use lambdaworks_math::environment::{IsRandomSource, OsRandomSource};
+use std::collections::{BTreeMap, BTreeSet};
+use std::marker::PhantomData;
 
 use lambdaworks_crypto::fiat_shamir::is_transcript::IsTranscript;
 use lambdaworks_math::fft::cpu::bit_reversing::{in_place_bit_reverse_permute, reverse_index};
@@ -13,6 +15,7 @@ use lambdaworks_math::{
     polynomial::Polynomial,
 };
 use log::info;
+use tracing::{debug, info_span};
 
 #[cfg(feature = "parallel")]
 use rayon::prelude::{IndexedParallelIterator, IntoParallelRefIterator, ParallelIterator};
@@ -22,13 +25,19 @@ use crate::debug::validate_trace;
 use crate::fri;
 use crate::proof::stark::{DeepPolynomialOpenings, PolynomialOpenings};
 use crate::table::Table;
-use crate::trace::{columns2rows, LDETraceTable};
+use crate::trace::{columns2rows, LDELayout, LDETraceTable};
+use crate::zk;
 
-use super::config::{BatchedMerkleTree, Commitment};
+use super::config::{BatchedMerkleTree, Commitment, MerkleHash, COMMITMENT_SIZE};
 use super::constraints::evaluator::ConstraintEvaluator;
+use super::diagnostics::{
+    commitment_to_hex, CommitmentRoots, ConstraintCounts, PhaseTimer, ProverDiagnostics,
+    TraceDimensions,
+};
 use super::domain::Domain;
 use super::fri::fri_decommit::FriDecommitment;
 use super::grinding;
+use super::preprocessing::PreprocessedTrace;
 use super::proof::options::ProofOptions;
 use super::proof::stark::{DeepPolynomialOpening, StarkProof};
 use super::trace::TraceTable;
@@ -53,12 +62,16 @@ where
     F: IsField,
     FieldElement<F>: AsBytes + Send + Sync,
 {
-    /// The result of the interpolation of the columns of the trace table.
+    /// The result of the interpolation of the columns of the trace table. For the main trace,
+    /// the polynomials of the preprocessed columns come first, but they are not committed again.
     pub(crate) trace_polys: Vec<Polynomial<FieldElement<F>>>,
     /// The Merkle trees constructed to obtain the commitment of the entire trace table.
     pub(crate) lde_trace_merkle_tree: BatchedMerkleTree<F>,
     /// The root of the Merkle tree in `lde_trace_merkle_tree`.
     pub(crate) lde_trace_merkle_root: Commitment,
+    /// The salts of the leaves of `lde_trace_merkle_tree`, empty unless the proof is
+    /// zero-knowledge.
+    pub(crate) salts: Vec<Vec<FieldElement<F>>>,
 }
 
 /// A container for the results of the first round of the STARK Prove protocol.
@@ -68,13 +81,15 @@ where
     FieldElement<A::FieldExtension>: AsBytes + Sync + Send,
     FieldElement<A::Field>: AsBytes + Sync + Send,
 {
-    /// The table of evaluations over the LDE of the main and auxiliary trace tables.
-    pub(crate) lde_trace: LDETraceTable<A::Field, A::FieldExtension>,
+    /// The table of evaluations over the LDE of the main and auxiliary trace tables, `None` with
+    /// [`LDELayout::Streaming`].
+    pub(crate) lde_trace: Option<LDETraceTable<A::Field, A::FieldExtension>>,
     /// The intermediate results of the commitment to the main trace table.
     pub(crate) main: Round1CommitmentData<A::Field>,
-    /// The intermediate results of the commitment to the auxiliary trace table in case of RAP.
-    pub(crate) aux: Option<Round1CommitmentData<A::FieldExtension>>,
-    /// The challenges of the RAP round.
+    /// The intermediate results of the commitments to the auxiliary trace table in case of RAP,
+    /// one for each interaction round with columns.
+    pub(crate) aux: Vec<Round1CommitmentData<A::FieldExtension>>,
+    /// The challenges of all the interaction rounds of the RAP.
     pub(crate) rap_challenges: Vec<FieldElement<A::FieldExtension>>,
 }
 
@@ -96,11 +111,17 @@ where
             .map(|poly| poly.to_extension())
             .collect();
 
-        if let Some(aux) = &self.aux {
-            trace_polys.extend_from_slice(&aux.trace_polys.to_owned())
-        }
+        trace_polys.extend(self.aux_trace_polys());
         trace_polys
     }
+
+    /// Returns the polynomials interpolating the auxiliary trace, from all interaction rounds.
+    fn aux_trace_polys(&self) -> Vec<Polynomial<FieldElement<A::FieldExtension>>> {
+        self.aux
+            .iter()
+            .flat_map(|aux| aux.trace_polys.iter().cloned())
+            .collect()
+    }
 }
 
 /// A container for the results of the second round of the STARK Prove protocol.
@@ -117,6 +138,13 @@ where
     pub(crate) composition_poly_merkle_tree: BatchedMerkleTree<F>,
     /// The commitment to the composition polynomial parts.
     pub(crate) composition_poly_root: Commitment,
+    /// In zero-knowledge proofs, the random polynomial added to the DEEP composition polynomial.
+    /// It is committed with the parts, and its evaluations are the last ones in
+    /// `lde_composition_poly_evaluations`.
+    pub(crate) randomizer_poly: Option<Polynomial<FieldElement<F>>>,
+    /// The salts of the leaves of `composition_poly_merkle_tree`, empty unless the proof is
+    /// zero-knowledge.
+    pub(crate) composition_poly_salts: Vec<Vec<FieldElement<F>>>,
 }
 
 /// A container for the results of the third round of the STARK Prove protocol.
@@ -129,8 +157,9 @@ pub struct Round3<F: IsField> {
 
 /// A container for the results of the fourth round of the STARK Prove protocol.
 pub struct Round4<F: IsSubFieldOf<E>, E: IsField> {
-    /// The final value resulting from folding the Deep composition polynomial all the way down to a constant value.
-    fri_last_value: FieldElement<E>,
+    /// The coefficients of the last layer of FRI, resulting from folding the Deep composition polynomial
+    /// down to a polynomial of degree less than the `fri_last_layer_degree_bound` of the options.
+    fri_last_layer_coefficients: Vec<FieldElement<E>>,
     /// The commitments to the fold polynomials of the inner layers of FRI.
     fri_layers_merkle_roots: Vec<Commitment>,
     /// The values and proofs of validity of the evaluations of the trace polynomials and the composition polynomials
@@ -156,7 +185,11 @@ where
     F: IsFFTField + IsSubFieldOf<E>,
     E: IsField,
 {
-    let evaluations = Polynomial::evaluate_offset_fft(p, blowup_factor, Some(domain_size), offset)?;
+    let evaluations = Polynomial::evaluate_offset_fft(p, blowup_factor, Some(domain_size), offset);
+    #[cfg(any(feature = "cuda", feature = "metal"))]
+    let evaluations =
+        evaluations.or_else(|_| evaluate_offset_fft_cpu(p, blowup_factor, domain_size, offset));
+    let evaluations = evaluations?;
     let step = evaluations.len() / (domain_size * blowup_factor);
     match step {
         1 => Ok(evaluations),
@@ -164,13 +197,38 @@ where
     }
 }
 
+/// Same evaluations as [`Polynomial::evaluate_offset_fft`], computed on the CPU, for when the
+/// GPU can't be used.
+#[cfg(any(feature = "cuda", feature = "metal"))]
+fn evaluate_offset_fft_cpu<F, E>(
+    p: &Polynomial<FieldElement<E>>,
+    blowup_factor: usize,
+    domain_size: usize,
+    offset: &FieldElement<F>,
+) -> Result<Vec<FieldElement<E>>, FFTError>
+where
+    F: IsFFTField + IsSubFieldOf<E>,
+    E: IsField,
+{
+    let len = p.coeff_len().max(domain_size).next_power_of_two() * blowup_factor;
+    if p.coefficients().is_empty() {
+        return Ok(vec![FieldElement::zero(); len]);
+    }
+    let mut coefficients = p.scale(offset).coefficients().to_vec();
+    coefficients.resize(len, FieldElement::zero());
+    lambdaworks_math::fft::polynomial::evaluate_fft_cpu::<F, E>(&coefficients)
+}
+
 /// The functionality of a STARK prover providing methods to run the STARK Prove protocol
 /// https://lambdaclass.github.io/lambdaworks/starks/protocol.html
 /// The default implementation is complete and is compatible with Stone prover
 /// https://github.com/starkware-libs/stone-prover
 pub trait IsStarkProver<A: AIR> {
-    /// Returns the Merkle tree and the commitment to the vectors `vectors`.
-    fn batch_commit<E>(vectors: &[Vec<FieldElement<E>>]) -> (BatchedMerkleTree<E>, Commitment)
+    /// Returns the Merkle tree with hash `merkle_hash` and the commitment to the vectors `vectors`.
+    fn batch_commit<E>(
+        vectors: &[Vec<FieldElement<E>>],
+        merkle_hash: MerkleHash,
+    ) -> (BatchedMerkleTree<E>, Commitment)
     where
         FieldElement<A::Field>: AsBytes + Sync + Send,
         FieldElement<A::FieldExtension>: AsBytes + Sync + Send,
@@ -178,13 +236,13 @@ pub trait IsStarkProver<A: AIR> {
         E: IsSubFieldOf<A::FieldExtension>,
         A::Field: IsSubFieldOf<E>,
     {
-        let tree = BatchedMerkleTree::<E>::build(vectors).unwrap();
-        let commitment = tree.root;
+        let tree = BatchedMerkleTree::<E>::build(merkle_hash, vectors).unwrap();
+        let commitment = tree.root();
         (tree, commitment)
     }
 
     /// Given a `TraceTable`, this method interpolates its columns, computes the commitment to the
-    /// table and appends it to the transcript.
+    /// table with hash `merkle_hash` and appends it to the transcript.
     /// Output: a touple of length 4 with the following:
     /// • The polynomials interpolating the columns of `trace`.
     /// • The evaluations of the above polynomials over the domain `domain`.
@@ -194,6 +252,7 @@ pub trait IsStarkProver<A: AIR> {
     fn interpolate_and_commit<E>(
         trace: &TraceTable<E>,
         domain: &Domain<A::Field>,
+        merkle_hash: MerkleHash,
         transcript: &mut impl IsTranscript<A::FieldExtension>,
     ) -> (
         Vec<Polynomial<FieldElement<E>>>,
@@ -201,6 +260,40 @@ pub trait IsStarkProver<A: AIR> {
         BatchedMerkleTree<E>,
         Commitment,
     )
+    where
+        FieldElement<A::Field>: AsBytes + Send + Sync,
+        FieldElement<E>: AsBytes + Send + Sync,
+        FieldElement<A::FieldExtension>: AsBytes + Send + Sync,
+        E: IsSubFieldOf<A::FieldExtension>,
+        A::Field: IsSubFieldOf<E>,
+    {
+        let (trace_polys, lde_trace_evaluations, lde_trace_merkle_tree, lde_trace_merkle_root) =
+            Self::interpolate_and_build_merkle_tree(trace, domain, merkle_hash);
+
+        // >>>> Send commitment.
+        transcript.append_bytes(&lde_trace_merkle_root);
+
+        (
+            trace_polys,
+            lde_trace_evaluations,
+            lde_trace_merkle_tree,
+            lde_trace_merkle_root,
+        )
+    }
+
+    /// Like [`IsStarkProver::interpolate_and_commit`], without appending the commitment to a
+    /// transcript.
+    #[allow(clippy::type_complexity)]
+    fn interpolate_and_build_merkle_tree<E>(
+        trace: &TraceTable<E>,
+        domain: &Domain<A::Field>,
+        merkle_hash: MerkleHash,
+    ) -> (
+        Vec<Polynomial<FieldElement<E>>>,
+        Vec<Vec<FieldElement<E>>>,
+        BatchedMerkleTree<E>,
+        Commitment,
+    )
     where
         FieldElement<A::Field>: AsBytes + Send + Sync,
         FieldElement<E>: AsBytes + Send + Sync,
@@ -209,30 +302,214 @@ pub trait IsStarkProver<A: AIR> {
         A::Field: IsSubFieldOf<E>,
     {
         // Interpolate columns of `trace`.
-        let trace_polys = trace.compute_trace_polys::<A::Field>();
+        let trace_polys = info_span!(
+            "interpolation",
+            columns = trace.n_cols(),
+            rows = trace.n_rows()
+        )
+        .in_scope(|| trace.compute_trace_polys::<A::Field>());
+
+        let (lde_trace_evaluations, lde_trace_merkle_tree, lde_trace_merkle_root) =
+            Self::evaluate_and_build_merkle_tree(&trace_polys, domain, merkle_hash, &[]);
+
+        (
+            trace_polys,
+            lde_trace_evaluations,
+            lde_trace_merkle_tree,
+            lde_trace_merkle_root,
+        )
+    }
+
+    /// Evaluates the polynomials `trace_polys` over the LDE domain, and builds the Merkle tree
+    /// with hash `merkle_hash` and the evaluations at each point in a leaf, followed by its salt in `salts` if there is
+    /// one. Returns the evaluations, the tree and its root.
+    #[allow(clippy::type_complexity)]
+    fn evaluate_and_build_merkle_tree<E>(
+        trace_polys: &[Polynomial<FieldElement<E>>],
+        domain: &Domain<A::Field>,
+        merkle_hash: MerkleHash,
+        salts: &[Vec<FieldElement<E>>],
+    ) -> (Vec<Vec<FieldElement<E>>>, BatchedMerkleTree<E>, Commitment)
+    where
+        FieldElement<A::Field>: AsBytes + Send + Sync,
+        FieldElement<E>: AsBytes + Send + Sync,
+        FieldElement<A::FieldExtension>: AsBytes + Send + Sync,
+        E: IsSubFieldOf<A::FieldExtension>,
+        A::Field: IsSubFieldOf<E>,
+    {
+        let lde_size = domain.lde_roots_of_unity_coset.len();
 
         // Evaluate those polynomials t_j on the large domain D_LDE.
-        let lde_trace_evaluations = Self::compute_lde_trace_evaluations(&trace_polys, domain);
+        let lde_trace_evaluations = info_span!("lde", columns = trace_polys.len(), lde_size)
+            .in_scope(|| Self::compute_lde_trace_evaluations(trace_polys, domain));
+
+        // Compute commitment.
+        let (lde_trace_merkle_tree, lde_trace_merkle_root) = {
+            let _span = info_span!("commitment", leaves = lde_size).entered();
+            let mut lde_trace_permuted = lde_trace_evaluations.clone();
+            for col in lde_trace_permuted.iter_mut() {
+                in_place_bit_reverse_permute(col);
+            }
+            let mut lde_trace_permuted_rows = columns2rows(lde_trace_permuted);
+            for (row, salt) in lde_trace_permuted_rows.iter_mut().zip(salts) {
+                row.extend_from_slice(salt);
+            }
+            Self::batch_commit(&lde_trace_permuted_rows, merkle_hash)
+        };
+
+        (
+            lde_trace_evaluations,
+            lde_trace_merkle_tree,
+            lde_trace_merkle_root,
+        )
+    }
 
-        let mut lde_trace_permuted = lde_trace_evaluations.clone();
-        for col in lde_trace_permuted.iter_mut() {
-            in_place_bit_reverse_permute(col);
+    /// Like [`IsStarkProver::evaluate_and_build_merkle_tree`], evaluating the polynomials on one
+    /// coset of the trace domain at a time, see [`LDELayout::Streaming`]. In bit-reversed order,
+    /// the leaves of each coset are contiguous, so they are hashed one coset after the other and
+    /// the tree is the same. Returns the tree and its root.
+    fn build_merkle_tree_on_cosets<E>(
+        trace_polys: &[Polynomial<FieldElement<E>>],
+        domain: &Domain<A::Field>,
+        merkle_hash: MerkleHash,
+        salts: &[Vec<FieldElement<E>>],
+    ) -> (BatchedMerkleTree<E>, Commitment)
+    where
+        FieldElement<A::Field>: AsBytes + Send + Sync,
+        FieldElement<E>: AsBytes + Send + Sync,
+        FieldElement<A::FieldExtension>: AsBytes + Send + Sync,
+        E: IsSubFieldOf<A::FieldExtension>,
+        A::Field: IsSubFieldOf<E>,
+    {
+        let lde_size = domain.lde_roots_of_unity_coset.len();
+        let coset_size = domain.interpolation_domain_size;
+        let _span = info_span!("commitment", leaves = lde_size).entered();
+
+        let mut hashed_leaves = vec![[0; COMMITMENT_SIZE]; lde_size];
+        for coset in 0..domain.blowup_factor {
+            let evaluations =
+                info_span!("lde", columns = trace_polys.len(), coset).in_scope(|| {
+                    Self::compute_lde_trace_evaluations(trace_polys, &domain.coset(coset))
+                });
+            let mut rows = columns2rows(evaluations);
+            in_place_bit_reverse_permute(&mut rows);
+            let first_leaf = reverse_index(coset, domain.blowup_factor as u64) * coset_size;
+            for (row, salt) in rows.iter_mut().zip(salts.iter().skip(first_leaf)) {
+                row.extend_from_slice(salt);
+            }
+            hashed_leaves[first_leaf..first_leaf + coset_size]
+                .clone_from_slice(&BatchedMerkleTree::hash_leaves(merkle_hash, &rows));
         }
 
-        // Compute commitment.
-        let lde_trace_permuted_rows = columns2rows(lde_trace_permuted);
-        let (lde_trace_merkle_tree, lde_trace_merkle_root) =
-            Self::batch_commit(&lde_trace_permuted_rows);
+        let tree = BatchedMerkleTree::build_from_hashed_leaves(merkle_hash, hashed_leaves).unwrap();
+        let root = tree.root();
+        (tree, root)
+    }
+
+    /// Like [`IsStarkProver::interpolate_and_commit`], for columns that depend on the witness.
+    /// In zero-knowledge proofs, `hiding` is the source of the randomness that blinds their
+    /// polynomials and salts the leaves, see [`crate::zk`]. Returns the commitment data and the
+    /// evaluations of the polynomials over the LDE domain, which are empty with
+    /// [`LDELayout::Streaming`].
+    #[allow(clippy::type_complexity)]
+    fn commit_witness_trace<E>(
+        trace: &TraceTable<E>,
+        domain: &Domain<A::Field>,
+        merkle_hash: MerkleHash,
+        lde_layout: LDELayout,
+        hiding: Option<&impl IsRandomSource>,
+        transcript: &mut impl IsTranscript<A::FieldExtension>,
+    ) -> (Round1CommitmentData<E>, Vec<Vec<FieldElement<E>>>)
+    where
+        FieldElement<A::Field>: AsBytes + Send + Sync,
+        FieldElement<E>: AsBytes + Send + Sync,
+        FieldElement<A::FieldExtension>: AsBytes + Send + Sync,
+        E: IsSubFieldOf<A::FieldExtension>,
+        A::Field: IsSubFieldOf<E>,
+    {
+        let trace_polys: Vec<_> = info_span!(
+            "interpolation",
+            columns = trace.n_cols(),
+            rows = trace.n_rows()
+        )
+        .in_scope(|| {
+            let trace_polys = trace.compute_trace_polys::<A::Field>();
+            match hiding {
+                Some(source) => trace_polys
+                    .iter()
+                    .map(|poly| {
+                        zk::blind_trace_polynomial::<A::Field, E>(poly, trace.n_rows(), source)
+                    })
+                    .collect(),
+                None => trace_polys,
+            }
+        });
+        let salts: Vec<_> = hiding
+            .map(|source| {
+                (0..domain.lde_roots_of_unity_coset.len())
+                    .map(|_| zk::random_salt(source))
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        let (lde_trace_evaluations, lde_trace_merkle_tree, lde_trace_merkle_root) = match lde_layout
+        {
+            LDELayout::Streaming => {
+                let (tree, root) =
+                    Self::build_merkle_tree_on_cosets(&trace_polys, domain, merkle_hash, &salts);
+                (Vec::new(), tree, root)
+            }
+            LDELayout::Natural | LDELayout::Coset => {
+                Self::evaluate_and_build_merkle_tree(&trace_polys, domain, merkle_hash, &salts)
+            }
+        };
 
         // >>>> Send commitment.
         transcript.append_bytes(&lde_trace_merkle_root);
 
-        (
+        let commitment = Round1CommitmentData {
             trace_polys,
-            lde_trace_evaluations,
             lde_trace_merkle_tree,
             lde_trace_merkle_root,
+            salts,
+        };
+        (commitment, lde_trace_evaluations)
+    }
+
+    /// Interpolates and commits the preprocessed columns of the AIR, for traces with as many rows
+    /// as `preprocessed_trace` proven with `proof_options`. The result is reused by all those
+    /// proofs, see [`crate::preprocessing`].
+    fn preprocess(
+        preprocessed_trace: &TraceTable<A::Field>,
+        proof_options: &ProofOptions,
+    ) -> PreprocessedTrace<A::Field>
+    where
+        FieldElement<A::Field>: AsBytes + Send + Sync,
+        FieldElement<A::FieldExtension>: AsBytes + Send + Sync,
+    {
+        let _span = info_span!(
+            "preprocess",
+            trace_length = preprocessed_trace.n_rows(),
+            columns = preprocessed_trace.n_cols()
         )
+        .entered();
+        let domain = Domain::from_trace_length(preprocessed_trace.n_rows(), proof_options);
+        let (trace_polys, lde_trace_evaluations, lde_trace_merkle_tree, lde_trace_merkle_root) =
+            Self::interpolate_and_build_merkle_tree::<A::Field>(
+                preprocessed_trace,
+                &domain,
+                proof_options.merkle_hash,
+            );
+
+        PreprocessedTrace {
+            trace: preprocessed_trace.clone(),
+            trace_polys,
+            lde_trace_evaluations,
+            lde_trace_merkle_tree,
+            lde_trace_merkle_root,
+            blowup_factor: proof_options.blowup_factor,
+            coset_offset: proof_options.coset_offset,
+        }
     }
 
     /// Evaluate polynomials `trace_polys` over the domain `domain`.
@@ -265,49 +542,130 @@ pub trait IsStarkProver<A: AIR> {
             .unwrap()
     }
 
-    /// Returns the result of the first round of the STARK Prove protocol.
+    /// Returns the table of the evaluations of the trace polynomials of `round_1_result`, main and
+    /// auxiliary, on `coset`, one of the cosets of the trace domain given by [`Domain::coset`].
+    fn lde_trace_on_coset(
+        round_1_result: &Round1<A>,
+        coset: &Domain<A::Field>,
+    ) -> LDETraceTable<A::Field, A::FieldExtension>
+    where
+        FieldElement<A::Field>: AsBytes + Send + Sync,
+        FieldElement<A::FieldExtension>: AsBytes + Send + Sync,
+    {
+        let _span = info_span!("lde", rows = coset.lde_roots_of_unity_coset.len()).entered();
+        let main_evaluations = Self::compute_lde_trace_evaluations::<A::Field>(
+            &round_1_result.main.trace_polys,
+            coset,
+        );
+        let aux_evaluations = round_1_result
+            .aux
+            .iter()
+            .flat_map(|aux| Self::compute_lde_trace_evaluations(&aux.trace_polys, coset))
+            .collect();
+        LDETraceTable::from_columns(main_evaluations, aux_evaluations, A::STEP_SIZE, 1)
+    }
+
+    /// Returns the result of the first round of the STARK Prove protocol. In zero-knowledge
+    /// proofs, `hiding` is the source of randomness to hide the trace with.
     fn round_1_randomized_air_with_preprocessing(
         air: &A,
         main_trace: &TraceTable<A::Field>,
+        preprocessed_trace: Option<&PreprocessedTrace<A::Field>>,
         domain: &Domain<A::Field>,
+        hiding: Option<&impl IsRandomSource>,
         transcript: &mut impl IsTranscript<A::FieldExtension>,
     ) -> Result<Round1<A>, ProvingError>
     where
         FieldElement<A::Field>: AsBytes + Send + Sync,
         FieldElement<A::FieldExtension>: AsBytes + Send + Sync,
     {
-        let (trace_polys, evaluations, main_merkle_tree, main_merkle_root) =
-            Self::interpolate_and_commit::<A::Field>(main_trace, domain, transcript);
+        if let Some(preprocessed_trace) = preprocessed_trace {
+            // >>>> Send commitment of the preprocessed columns, known beforehand by the verifier.
+            transcript.append_bytes(&preprocessed_trace.commitment());
+        }
 
-        let main = Round1CommitmentData::<A::Field> {
-            trace_polys,
-            lde_trace_merkle_tree: main_merkle_tree,
-            lde_trace_merkle_root: main_merkle_root,
-        };
+        let lde_layout = air.options().lde_layout;
+        let (mut main, mut evaluations) = Self::commit_witness_trace::<A::Field>(
+            main_trace,
+            domain,
+            air.options().merkle_hash,
+            lde_layout,
+            hiding,
+            transcript,
+        );
 
-        let rap_challenges = air.build_rap_challenges(transcript);
-
-        let aux_trace = air.build_auxiliary_trace(main_trace, &rap_challenges);
-        let (aux, aux_evaluations) = if !aux_trace.is_empty() {
-            let (aux_trace_polys, aux_trace_polys_evaluations, aux_merkle_tree, aux_merkle_root) =
-                Self::interpolate_and_commit(&aux_trace, domain, transcript);
-            let aux_evaluations = aux_trace_polys_evaluations;
-            let aux = Some(Round1CommitmentData::<A::FieldExtension> {
-                trace_polys: aux_trace_polys,
-                lde_trace_merkle_tree: aux_merkle_tree,
-                lde_trace_merkle_root: aux_merkle_root,
-            });
-            (aux, aux_evaluations)
-        } else {
-            (None, Vec::new())
+        // The AIR reads the preprocessed columns as the first ones of the main trace.
+        let full_main_trace;
+        let main_trace = match preprocessed_trace {
+            Some(preprocessed_trace) => {
+                main.trace_polys
+                    .splice(0..0, preprocessed_trace.trace_polys.iter().cloned());
+                if lde_layout != LDELayout::Streaming {
+                    evaluations.splice(
+                        0..0,
+                        preprocessed_trace.lde_trace_evaluations.iter().cloned(),
+                    );
+                }
+                full_main_trace = preprocessed_trace.prepend_to(main_trace);
+                &full_main_trace
+            }
+            None => main_trace,
         };
 
-        let lde_trace = LDETraceTable::from_columns(
-            evaluations,
-            aux_evaluations,
-            A::STEP_SIZE,
-            domain.blowup_factor,
-        );
+        let mut rap_challenges = Vec::new();
+        let mut aux_columns = Vec::new();
+        let mut aux = Vec::new();
+        let mut aux_evaluations = Vec::new();
+        for (round, num_columns) in air
+            .num_auxiliary_rap_columns_per_round()
+            .into_iter()
+            .enumerate()
+        {
+            rap_challenges.extend(air.build_rap_challenges_for_round(round, transcript));
+
+            let previous_aux_trace = TraceTable::from_columns(aux_columns.clone(), 0, A::STEP_SIZE);
+            let aux_trace =
+                info_span!("trace_build", rows = main_trace.n_rows(), round).in_scope(|| {
+                    air.build_auxiliary_trace_for_round(
+                        round,
+                        main_trace,
+                        &previous_aux_trace,
+                        &rap_challenges,
+                    )
+                });
+            debug!(columns = aux_trace.n_cols(), round, "built auxiliary trace");
+            if aux_trace.n_cols() != num_columns {
+                return Err(ProvingError::WrongParameter(format!(
+                    "the interaction round {round} of the AIR has {num_columns} auxiliary columns, but {} were built",
+                    aux_trace.n_cols()
+                )));
+            }
+            if aux_trace.is_empty() {
+                continue;
+            }
+
+            let (aux_commitment, aux_trace_polys_evaluations) = Self::commit_witness_trace(
+                &aux_trace,
+                domain,
+                air.options().merkle_hash,
+                lde_layout,
+                hiding,
+                transcript,
+            );
+            aux_columns.extend(aux_trace.columns());
+            aux_evaluations.extend(aux_trace_polys_evaluations);
+            aux.push(aux_commitment);
+        }
+
+        let lde_trace = (lde_layout != LDELayout::Streaming).then(|| {
+            LDETraceTable::from_columns_with_layout(
+                evaluations,
+                aux_evaluations,
+                A::STEP_SIZE,
+                domain.blowup_factor,
+                lde_layout,
+            )
+        });
 
         Ok(Round1 {
             lde_trace,
@@ -318,9 +676,12 @@ pub trait IsStarkProver<A: AIR> {
     }
 
     /// Returns the Merkle tree and the commitment to the evaluations of the parts of the
-    /// composition polynomial.
+    /// composition polynomial, with hash `merkle_hash` and each leaf followed by its salt in
+    /// `salts` if there is one.
     fn commit_composition_polynomial(
         lde_composition_poly_parts_evaluations: &[Vec<FieldElement<A::FieldExtension>>],
+        salts: &[Vec<FieldElement<A::FieldExtension>>],
+        merkle_hash: MerkleHash,
     ) -> (BatchedMerkleTree<A::FieldExtension>, Commitment)
     where
         FieldElement<A::Field>: AsBytes + Sync + Send,
@@ -344,17 +705,25 @@ pub trait IsStarkProver<A: AIR> {
             chunk0.extend_from_slice(chunk1);
             lde_composition_poly_evaluations_merged.push(chunk0);
         }
+        for (leaf, salt) in lde_composition_poly_evaluations_merged
+            .iter_mut()
+            .zip(salts)
+        {
+            leaf.extend_from_slice(salt);
+        }
 
-        Self::batch_commit(&lde_composition_poly_evaluations_merged)
+        Self::batch_commit(&lde_composition_poly_evaluations_merged, merkle_hash)
     }
 
-    /// Returns the result of the second round of the STARK Prove protocol.
+    /// Returns the result of the second round of the STARK Prove protocol. In zero-knowledge
+    /// proofs, `hiding` is the source of randomness to hide the composition polynomial with.
     fn round_2_compute_composition_polynomial(
         air: &A,
         domain: &Domain<A::Field>,
         round_1_result: &Round1<A>,
         transition_coefficients: &[FieldElement<A::FieldExtension>],
         boundary_coefficients: &[FieldElement<A::FieldExtension>],
+        hiding: Option<&impl IsRandomSource>,
     ) -> Round2<A::FieldExtension>
     where
         A: Send + Sync,
@@ -362,45 +731,123 @@ pub trait IsStarkProver<A: AIR> {
         FieldElement<A::FieldExtension>: AsBytes + Send + Sync,
     {
         // Compute the evaluations of the composition polynomial on the LDE domain.
-        let evaluator = ConstraintEvaluator::new(air, &round_1_result.rap_challenges);
-        let constraint_evaluations = evaluator.evaluate(
-            air,
-            &round_1_result.lde_trace,
-            domain,
-            transition_coefficients,
-            boundary_coefficients,
-            &round_1_result.rap_challenges,
-        );
+        let constraint_evaluations = info_span!(
+            "constraint_evaluation",
+            transition_constraints = transition_coefficients.len(),
+            boundary_constraints = boundary_coefficients.len(),
+            lde_size = domain.lde_roots_of_unity_coset.len()
+        )
+        .in_scope(|| {
+            let evaluator = ConstraintEvaluator::new(air, &round_1_result.rap_challenges);
+            let evaluate = |lde_trace: &LDETraceTable<_, _>, domain: &Domain<A::Field>| {
+                evaluator.evaluate(
+                    air,
+                    lde_trace,
+                    domain,
+                    transition_coefficients,
+                    boundary_coefficients,
+                    &round_1_result.rap_challenges,
+                )
+            };
+            let Some(lde_trace) = &round_1_result.lde_trace else {
+                // The points of each coset are `blowup_factor` points apart in the LDE domain.
+                let mut evaluations =
+                    vec![FieldElement::zero(); domain.lde_roots_of_unity_coset.len()];
+                for index in 0..domain.blowup_factor {
+                    let coset = domain.coset(index);
+                    let lde_trace = Self::lde_trace_on_coset(round_1_result, &coset);
+                    for (evaluation, value) in evaluations
+                        .iter_mut()
+                        .skip(index)
+                        .step_by(domain.blowup_factor)
+                        .zip(evaluate(&lde_trace, &coset))
+                    {
+                        *evaluation = value;
+                    }
+                }
+                return evaluations;
+            };
+            evaluate(lde_trace, domain)
+        });
 
         // Get coefficients of the composition poly H
-        let composition_poly =
+        let number_of_parts = match hiding {
+            Some(_) => zk::number_of_composition_poly_parts(air),
+            None => air.composition_poly_degree_bound() / air.trace_length(),
+        };
+        let composition_poly_parts = info_span!(
+            "interpolation",
+            evaluations = constraint_evaluations.len(),
+            parts = number_of_parts
+        )
+        .in_scope(|| {
             Polynomial::interpolate_offset_fft(&constraint_evaluations, &domain.coset_offset)
-                .unwrap();
-
-        let number_of_parts = air.composition_poly_degree_bound() / air.trace_length();
-        let composition_poly_parts = composition_poly.break_in_parts(number_of_parts);
+                .unwrap()
+                .break_in_parts(number_of_parts)
+        });
 
-        let lde_composition_poly_parts_evaluations: Vec<_> = composition_poly_parts
-            .iter()
-            .map(|part| {
+        let mut lde_composition_poly_parts_evaluations: Vec<_> = info_span!(
+            "lde",
+            columns = number_of_parts,
+            lde_size = domain.lde_roots_of_unity_coset.len()
+        )
+        .in_scope(|| {
+            composition_poly_parts
+                .iter()
+                .map(|part| {
+                    evaluate_polynomial_on_lde_domain(
+                        part,
+                        domain.blowup_factor,
+                        domain.interpolation_domain_size,
+                        &domain.coset_offset,
+                    )
+                    .unwrap()
+                })
+                .collect()
+        });
+
+        // In zero-knowledge proofs, a random polynomial of the degree of the DEEP composition
+        // polynomial is committed with the parts.
+        let randomizer_poly =
+            hiding.map(|source| zk::random_polynomial(2 * air.trace_length(), source));
+        if let Some(randomizer_poly) = &randomizer_poly {
+            lde_composition_poly_parts_evaluations.push(
                 evaluate_polynomial_on_lde_domain(
-                    part,
+                    randomizer_poly,
                     domain.blowup_factor,
                     domain.interpolation_domain_size,
                     &domain.coset_offset,
                 )
-                .unwrap()
+                .unwrap(),
+            );
+        }
+        let composition_poly_salts: Vec<_> = hiding
+            .map(|source| {
+                (0..domain.lde_roots_of_unity_coset.len() / 2)
+                    .map(|_| zk::random_salt(source))
+                    .collect()
             })
-            .collect();
+            .unwrap_or_default();
 
-        let (composition_poly_merkle_tree, composition_poly_root) =
-            Self::commit_composition_polynomial(&lde_composition_poly_parts_evaluations);
+        let (composition_poly_merkle_tree, composition_poly_root) = info_span!(
+            "commitment",
+            leaves = domain.lde_roots_of_unity_coset.len() / 2
+        )
+        .in_scope(|| {
+            Self::commit_composition_polynomial(
+                &lde_composition_poly_parts_evaluations,
+                &composition_poly_salts,
+                air.options().merkle_hash,
+            )
+        });
 
         Round2 {
             lde_composition_poly_evaluations: lde_composition_poly_parts_evaluations,
             composition_poly_parts,
             composition_poly_merkle_tree,
             composition_poly_root,
+            randomizer_poly,
+            composition_poly_salts,
         }
     }
 
@@ -436,11 +883,7 @@ pub trait IsStarkProver<A: AIR> {
         let trace_ood_evaluations =
             crate::trace::get_trace_evaluations::<A::Field, A::FieldExtension>(
                 &round_1_result.main.trace_polys,
-                round_1_result
-                    .aux
-                    .as_ref()
-                    .map(|aux| &aux.trace_polys)
-                    .unwrap_or(&vec![]),
+                &round_1_result.aux_trace_polys(),
                 z,
                 &air.context().transition_offsets,
                 &domain.trace_primitive_root,
@@ -453,14 +896,18 @@ pub trait IsStarkProver<A: AIR> {
         }
     }
 
-    /// Returns the result of the fourth round of the STARK Prove protocol.
+    /// Returns the result of the fourth round of the STARK Prove protocol. In zero-knowledge
+    /// proofs, `hiding` is the source of randomness to salt the layers of FRI with.
+    #[allow(clippy::too_many_arguments)]
     fn round_4_compute_and_run_fri_on_the_deep_composition_polynomial(
         air: &A,
         domain: &Domain<A::Field>,
+        preprocessed_trace: Option<&PreprocessedTrace<A::Field>>,
         round_1_result: &Round1<A>,
         round_2_result: &Round2<A::FieldExtension>,
         round_3_result: &Round3<A::FieldExtension>,
         z: &FieldElement<A::FieldExtension>,
+        hiding: Option<&impl IsRandomSource>,
         transcript: &mut impl IsTranscript<A::FieldExtension>,
     ) -> Round4<A::Field, A::FieldExtension>
     where
@@ -488,32 +935,45 @@ pub trait IsStarkProver<A: AIR> {
         let gammas = deep_composition_coefficients;
 
         // Compute p₀ (deep composition polynomial)
-        let deep_composition_poly = Self::compute_deep_composition_poly(
-            air,
-            &round_1_result.all_trace_polys(),
-            round_2_result,
-            round_3_result,
-            z,
-            &domain.trace_primitive_root,
-            &gammas,
-            &trace_poly_coeffients,
-        );
+        let deep_composition_poly =
+            info_span!("deep_composition", terms = gammas.len() + n_terms_trace).in_scope(|| {
+                Self::compute_deep_composition_poly(
+                    air,
+                    &round_1_result.all_trace_polys(),
+                    round_2_result,
+                    round_3_result,
+                    z,
+                    &domain.trace_primitive_root,
+                    &gammas,
+                    &trace_poly_coeffients,
+                )
+            });
 
         let domain_size = domain.lde_roots_of_unity_coset.len();
+        // In zero-knowledge proofs, the trace polynomials have twice the degree.
+        let number_layers = domain.root_order as usize + usize::from(hiding.is_some());
 
         // FRI commit and query phases
-        let (fri_last_value, fri_layers) = fri::commit_phase::<A::Field, A::FieldExtension>(
-            domain.root_order as usize,
-            deep_composition_poly,
-            transcript,
-            &coset_offset,
-            domain_size,
-        );
+        let (fri_last_layer_coefficients, fri_layers) = info_span!("fri_commit", domain_size)
+            .in_scope(|| {
+                fri::commit_phase::<A::Field, A::FieldExtension>(
+                    number_layers,
+                    air.options().fri_last_layer_degree_bound,
+                    deep_composition_poly,
+                    transcript,
+                    &coset_offset,
+                    domain_size,
+                    air.options().merkle_hash,
+                    || hiding.map(zk::random_salt).unwrap_or_default(),
+                )
+            });
+        debug!(layers = fri_layers.len(), "committed FRI layers");
 
         // grinding: generate nonce and append it to the transcript
         let security_bits = air.context().proof_options.grinding_factor;
         let mut nonce = None;
         if security_bits > 0 {
+            let _span = info_span!("grinding", security_bits).entered();
             let nonce_value = grinding::generate_nonce(&transcript.state(), security_bits)
                 .expect("nonce not found");
             transcript.append_bytes(&nonce_value.to_be_bytes());
@@ -521,19 +981,25 @@ pub trait IsStarkProver<A: AIR> {
         }
 
         let number_of_queries = air.options().fri_number_of_queries;
+        let _span = info_span!("queries", number_of_queries).entered();
         let iotas = Self::sample_query_indexes(number_of_queries, domain, transcript);
         let query_list = fri::query_phase(&fri_layers, &iotas);
 
         let fri_layers_merkle_roots: Vec<_> = fri_layers
             .iter()
-            .map(|layer| layer.merkle_tree.root)
+            .map(|layer| layer.merkle_tree.root())
             .collect();
 
-        let deep_poly_openings =
-            Self::open_deep_composition_poly(domain, round_1_result, round_2_result, &iotas);
+        let deep_poly_openings = Self::open_deep_composition_poly(
+            domain,
+            preprocessed_trace,
+            round_1_result,
+            round_2_result,
+            &iotas,
+        );
 
         Round4 {
-            fri_last_value,
+            fri_last_layer_coefficients,
             fri_layers_merkle_roots,
             deep_poly_openings,
             query_list,
@@ -541,15 +1007,18 @@ pub trait IsStarkProver<A: AIR> {
         }
     }
 
+    /// Samples the indexes of the FRI queries, without repetitions. See
+    /// [`fri::sample_query_indexes`].
     fn sample_query_indexes(
         number_of_queries: usize,
         domain: &Domain<A::Field>,
         transcript: &mut impl IsTranscript<A::FieldExtension>,
     ) -> Vec<usize> {
-        let domain_size = domain.lde_roots_of_unity_coset.len() as u64;
-        (0..number_of_queries)
-            .map(|_| (transcript.sample_u64(domain_size >> 1)) as usize)
-            .collect::<Vec<usize>>()
+        fri::sample_query_indexes(
+            number_of_queries,
+            domain.lde_roots_of_unity_coset.len(),
+            transcript,
+        )
     }
 
     /// Returns the DEEP composition polynomial that the prover then commits to using
@@ -584,6 +1053,12 @@ pub trait IsStarkProver<A: AIR> {
         assert_eq!(h_terms.evaluate(&z_power), FieldElement::zero());
         h_terms.ruffini_division_inplace(&z_power);
 
+        // In zero-knowledge proofs, 𝛾 R, for the random polynomial R committed with the parts.
+        if let Some(randomizer_poly) = &round_2_result.randomizer_poly {
+            let gamma = &composition_poly_gammas[round_2_result.composition_poly_parts.len()];
+            h_terms = h_terms + gamma * randomizer_poly.clone();
+        }
+
         // Get trace evaluations needed for the trace terms of the deep composition polynomial
         let transition_offsets = &air.context().transition_offsets;
         let trace_frame_evaluations = &round_3_result.trace_ood_evaluations;
@@ -673,6 +1148,7 @@ pub trait IsStarkProver<A: AIR> {
     fn open_composition_poly(
         composition_poly_merkle_tree: &BatchedMerkleTree<A::FieldExtension>,
         lde_composition_poly_evaluations: &[Vec<FieldElement<A::FieldExtension>>],
+        salts: &[Vec<FieldElement<A::FieldExtension>>],
         index: usize,
     ) -> PolynomialOpenings<A::FieldExtension>
     where
@@ -693,6 +1169,8 @@ pub trait IsStarkProver<A: AIR> {
             })
             .collect();
 
+        let salt = salts.get(index).cloned().unwrap_or_default();
+
         PolynomialOpenings {
             proof: proof.clone(),
             proof_sym: proof,
@@ -706,16 +1184,19 @@ pub trait IsStarkProver<A: AIR> {
                 .skip(1)
                 .step_by(2)
                 .collect(),
+            salt: salt.clone(),
+            salt_sym: salt,
         }
     }
 
     /// Computes values and validity proofs of the evaluations of the trace polynomials
     /// at the domain value corresponding to the FRI query challenge `index` and its symmetric
-    /// element.
+    /// element. `lde_row` returns the row of the LDE at an index.
     fn open_trace_polys<E>(
         domain: &Domain<A::Field>,
         tree: &BatchedMerkleTree<E>,
-        lde_trace: &Table<E>,
+        lde_row: impl Fn(usize) -> Vec<FieldElement<E>>,
+        salts: &[Vec<FieldElement<E>>],
         challenge: usize,
     ) -> PolynomialOpenings<E>
     where
@@ -731,18 +1212,73 @@ pub trait IsStarkProver<A: AIR> {
         PolynomialOpenings {
             proof: tree.get_proof_by_pos(index).unwrap(),
             proof_sym: tree.get_proof_by_pos(index_sym).unwrap(),
-            evaluations: lde_trace
-                .get_row(reverse_index(index, domain_size as u64))
-                .to_vec(),
-            evaluations_sym: lde_trace
-                .get_row(reverse_index(index_sym, domain_size as u64))
-                .to_vec(),
+            evaluations: lde_row(reverse_index(index, domain_size as u64)),
+            evaluations_sym: lde_row(reverse_index(index_sym, domain_size as u64)),
+            salt: salts.get(index).cloned().unwrap_or_default(),
+            salt_sym: salts.get(index_sym).cloned().unwrap_or_default(),
+        }
+    }
+
+    /// Returns the rows of the LDE of the trace, main and auxiliary, opened by the queries
+    /// `indexes`, by their index in the LDE. With [`LDELayout::Streaming`], the trace polynomials
+    /// are evaluated again on the cosets of those rows.
+    #[allow(clippy::type_complexity)]
+    fn opened_lde_trace_rows(
+        domain: &Domain<A::Field>,
+        round_1_result: &Round1<A>,
+        indexes: &[usize],
+    ) -> BTreeMap<
+        usize,
+        (
+            Vec<FieldElement<A::Field>>,
+            Vec<FieldElement<A::FieldExtension>>,
+        ),
+    >
+    where
+        FieldElement<A::Field>: AsBytes + Send + Sync,
+        FieldElement<A::FieldExtension>: AsBytes + Send + Sync,
+    {
+        let domain_size = domain.lde_roots_of_unity_coset.len() as u64;
+        let rows: BTreeSet<usize> = indexes
+            .iter()
+            .flat_map(|index| {
+                [
+                    reverse_index(index * 2, domain_size),
+                    reverse_index(index * 2 + 1, domain_size),
+                ]
+            })
+            .collect();
+        let mut opened_rows = BTreeMap::new();
+        match &round_1_result.lde_trace {
+            Some(lde_trace) => {
+                for row in rows {
+                    let main_row = lde_trace.get_main_row(row).to_vec();
+                    let aux_row = lde_trace.get_aux_row(row).to_vec();
+                    opened_rows.insert(row, (main_row, aux_row));
+                }
+            }
+            None => {
+                // The row `i` of the LDE is the row `i / blowup_factor` of the coset
+                // `i % blowup_factor`.
+                let blowup_factor = domain.blowup_factor;
+                let cosets: BTreeSet<usize> = rows.iter().map(|row| row % blowup_factor).collect();
+                for index in cosets {
+                    let lde_trace = Self::lde_trace_on_coset(round_1_result, &domain.coset(index));
+                    for row in rows.iter().filter(|row| *row % blowup_factor == index) {
+                        let main_row = lde_trace.get_main_row(row / blowup_factor).to_vec();
+                        let aux_row = lde_trace.get_aux_row(row / blowup_factor).to_vec();
+                        opened_rows.insert(*row, (main_row, aux_row));
+                    }
+                }
+            }
         }
+        opened_rows
     }
 
     /// Open the deep composition polynomial on a list of indexes and their symmetric elements.
     fn open_deep_composition_poly(
         domain: &Domain<A::Field>,
+        preprocessed_trace: Option<&PreprocessedTrace<A::Field>>,
         round_1_result: &Round1<A>,
         round_2_result: &Round2<A::FieldExtension>,
         indexes_to_open: &[usize],
@@ -752,32 +1288,75 @@ pub trait IsStarkProver<A: AIR> {
         FieldElement<A::FieldExtension>: AsBytes + Send + Sync,
     {
         let mut openings = Vec::new();
+        let opened_rows = Self::opened_lde_trace_rows(domain, round_1_result, indexes_to_open);
 
         for index in indexes_to_open.iter() {
-            let main_trace_opening = Self::open_trace_polys::<A::Field>(
+            let mut main_trace_opening = Self::open_trace_polys::<A::Field>(
                 domain,
                 &round_1_result.main.lde_trace_merkle_tree,
-                &round_1_result.lde_trace.main_table,
+                |row| opened_rows[&row].0.clone(),
+                &round_1_result.main.salts,
                 *index,
             );
 
+            // The evaluations of the preprocessed columns are the first ones of the main table,
+            // but they are committed in their own tree.
+            let preprocessed_trace_polys = preprocessed_trace.map(|preprocessed_trace| {
+                let num_columns = preprocessed_trace.num_columns();
+                PolynomialOpenings {
+                    proof: preprocessed_trace
+                        .lde_trace_merkle_tree
+                        .get_proof_by_pos(index * 2)
+                        .unwrap(),
+                    proof_sym: preprocessed_trace
+                        .lde_trace_merkle_tree
+                        .get_proof_by_pos(index * 2 + 1)
+                        .unwrap(),
+                    evaluations: main_trace_opening
+                        .evaluations
+                        .drain(..num_columns)
+                        .collect(),
+                    evaluations_sym: main_trace_opening
+                        .evaluations_sym
+                        .drain(..num_columns)
+                        .collect(),
+                    salt: Vec::new(),
+                    salt_sym: Vec::new(),
+                }
+            });
+
             let composition_openings = Self::open_composition_poly(
                 &round_2_result.composition_poly_merkle_tree,
                 &round_2_result.lde_composition_poly_evaluations,
+                &round_2_result.composition_poly_salts,
                 *index,
             );
 
-            let aux_trace_polys = round_1_result.aux.as_ref().map(|aux| {
-                Self::open_trace_polys::<A::FieldExtension>(
-                    domain,
-                    &aux.lde_trace_merkle_tree,
-                    &round_1_result.lde_trace.aux_table,
-                    *index,
-                )
-            });
+            // The auxiliary table holds the columns of all the interaction rounds, but each round
+            // is opened against its own tree.
+            let mut first_column = 0;
+            let aux_trace_polys = round_1_result
+                .aux
+                .iter()
+                .map(|aux| {
+                    let mut opening = Self::open_trace_polys::<A::FieldExtension>(
+                        domain,
+                        &aux.lde_trace_merkle_tree,
+                        |row| opened_rows[&row].1.clone(),
+                        &aux.salts,
+                        *index,
+                    );
+                    let columns = first_column..first_column + aux.trace_polys.len();
+                    first_column = columns.end;
+                    opening.evaluations = opening.evaluations[columns.clone()].to_vec();
+                    opening.evaluations_sym = opening.evaluations_sym[columns].to_vec();
+                    opening
+                })
+                .collect();
 
             openings.push(DeepPolynomialOpening {
                 composition_poly: composition_openings,
+                preprocessed_trace_polys,
                 main_trace_polys: main_trace_opening,
                 aux_trace_polys,
             });
@@ -786,29 +1365,223 @@ pub trait IsStarkProver<A: AIR> {
         openings
     }
 
-    // FIXME remove unwrap() calls and return errors
     /// Generates a STARK proof for the trace `main_trace` with public inputs `pub_inputs`.
     /// Warning: the transcript must be safely initializated before passing it to this method.
     fn prove(
         main_trace: &TraceTable<A::Field>,
         pub_inputs: &A::PublicInputs,
         proof_options: &ProofOptions,
-        mut transcript: impl IsTranscript<A::FieldExtension>,
+        transcript: impl IsTranscript<A::FieldExtension>,
     ) -> Result<StarkProof<A::Field, A::FieldExtension>, ProvingError>
+    where
+        A: Send + Sync,
+        FieldElement<A::Field>: AsBytes + Send + Sync,
+        FieldElement<A::FieldExtension>: AsBytes + Send + Sync,
+    {
+        Self::prove_rounds(
+            main_trace,
+            None,
+            pub_inputs,
+            proof_options,
+            &OsRandomSource,
+            false,
+            transcript,
+        )
+        .map(|(proof, _)| proof)
+    }
+
+    /// Like [`IsStarkProver::prove`], sampling the randomness of zero-knowledge proofs from
+    /// `source` instead of the operating system, see [`crate::zk`].
+    fn prove_with_random_source(
+        main_trace: &TraceTable<A::Field>,
+        pub_inputs: &A::PublicInputs,
+        proof_options: &ProofOptions,
+        source: &impl IsRandomSource,
+        transcript: impl IsTranscript<A::FieldExtension>,
+    ) -> Result<StarkProof<A::Field, A::FieldExtension>, ProvingError>
+    where
+        A: Send + Sync,
+        FieldElement<A::Field>: AsBytes + Send + Sync,
+        FieldElement<A::FieldExtension>: AsBytes + Send + Sync,
+    {
+        Self::prove_rounds(
+            main_trace,
+            None,
+            pub_inputs,
+            proof_options,
+            source,
+            false,
+            transcript,
+        )
+        .map(|(proof, _)| proof)
+    }
+
+    /// Like [`IsStarkProver::prove`], for an AIR with preprocessed columns. `main_trace` holds
+    /// the columns of the main trace after the preprocessed ones.
+    fn prove_with_preprocessed_trace(
+        main_trace: &TraceTable<A::Field>,
+        preprocessed_trace: &PreprocessedTrace<A::Field>,
+        pub_inputs: &A::PublicInputs,
+        proof_options: &ProofOptions,
+        transcript: impl IsTranscript<A::FieldExtension>,
+    ) -> Result<StarkProof<A::Field, A::FieldExtension>, ProvingError>
+    where
+        A: Send + Sync,
+        FieldElement<A::Field>: AsBytes + Send + Sync,
+        FieldElement<A::FieldExtension>: AsBytes + Send + Sync,
+    {
+        Self::prove_rounds(
+            main_trace,
+            Some(preprocessed_trace),
+            pub_inputs,
+            proof_options,
+            &OsRandomSource,
+            false,
+            transcript,
+        )
+        .map(|(proof, _)| proof)
+    }
+
+    /// Like [`IsStarkProver::prove`], also returning a report of the dimensions of the trace,
+    /// the time spent in each round and the commitments of the proof.
+    #[allow(clippy::type_complexity)]
+    fn prove_with_diagnostics(
+        main_trace: &TraceTable<A::Field>,
+        pub_inputs: &A::PublicInputs,
+        proof_options: &ProofOptions,
+        transcript: impl IsTranscript<A::FieldExtension>,
+    ) -> Result<(StarkProof<A::Field, A::FieldExtension>, ProverDiagnostics), ProvingError>
+    where
+        A: Send + Sync,
+        FieldElement<A::Field>: AsBytes + Send + Sync,
+        FieldElement<A::FieldExtension>: AsBytes + Send + Sync,
+    {
+        Self::prove_with_diagnostics_and_preprocessed_trace(
+            main_trace,
+            None,
+            pub_inputs,
+            proof_options,
+            transcript,
+        )
+    }
+
+    /// Like [`IsStarkProver::prove_with_diagnostics`], with the preprocessed columns of the AIR
+    /// if it has any.
+    #[allow(clippy::type_complexity)]
+    fn prove_with_diagnostics_and_preprocessed_trace(
+        main_trace: &TraceTable<A::Field>,
+        preprocessed_trace: Option<&PreprocessedTrace<A::Field>>,
+        pub_inputs: &A::PublicInputs,
+        proof_options: &ProofOptions,
+        transcript: impl IsTranscript<A::FieldExtension>,
+    ) -> Result<(StarkProof<A::Field, A::FieldExtension>, ProverDiagnostics), ProvingError>
+    where
+        A: Send + Sync,
+        FieldElement<A::Field>: AsBytes + Send + Sync,
+        FieldElement<A::FieldExtension>: AsBytes + Send + Sync,
+    {
+        Self::prove_with_diagnostics_preprocessed_trace_and_random_source(
+            main_trace,
+            preprocessed_trace,
+            pub_inputs,
+            proof_options,
+            &OsRandomSource,
+            transcript,
+        )
+    }
+
+    /// Like [`IsStarkProver::prove_with_diagnostics_and_preprocessed_trace`], sampling the
+    /// randomness of zero-knowledge proofs from `source`.
+    #[allow(clippy::type_complexity)]
+    fn prove_with_diagnostics_preprocessed_trace_and_random_source(
+        main_trace: &TraceTable<A::Field>,
+        preprocessed_trace: Option<&PreprocessedTrace<A::Field>>,
+        pub_inputs: &A::PublicInputs,
+        proof_options: &ProofOptions,
+        source: &impl IsRandomSource,
+        transcript: impl IsTranscript<A::FieldExtension>,
+    ) -> Result<(StarkProof<A::Field, A::FieldExtension>, ProverDiagnostics), ProvingError>
+    where
+        A: Send + Sync,
+        FieldElement<A::Field>: AsBytes + Send + Sync,
+        FieldElement<A::FieldExtension>: AsBytes + Send + Sync,
+    {
+        let (proof, diagnostics) = Self::prove_rounds(
+            main_trace,
+            preprocessed_trace,
+            pub_inputs,
+            proof_options,
+            source,
+            true,
+            transcript,
+        )?;
+        Ok((proof, diagnostics.expect("the diagnostics were requested")))
+    }
+
+    // FIXME remove unwrap() calls and return errors
+    /// Runs the rounds of the protocol. The time spent in each round is only measured, and the
+    /// [`ProverDiagnostics`] report only built, if `with_diagnostics` is set.
+    #[allow(clippy::type_complexity)]
+    fn prove_rounds(
+        main_trace: &TraceTable<A::Field>,
+        preprocessed_trace: Option<&PreprocessedTrace<A::Field>>,
+        pub_inputs: &A::PublicInputs,
+        proof_options: &ProofOptions,
+        source: &impl IsRandomSource,
+        with_diagnostics: bool,
+        mut transcript: impl IsTranscript<A::FieldExtension>,
+    ) -> Result<
+        (
+            StarkProof<A::Field, A::FieldExtension>,
+            Option<ProverDiagnostics>,
+        ),
+        ProvingError,
+    >
     where
         A: Send + Sync,
         FieldElement<A::Field>: AsBytes + Send + Sync,
         FieldElement<A::FieldExtension>: AsBytes + Send + Sync,
     {
         info!("Started proof generation...");
+        let _span = info_span!(
+            "prove",
+            trace_length = main_trace.n_rows(),
+            trace_columns = main_trace.n_cols(),
+            blowup_factor = proof_options.blowup_factor,
+            fri_number_of_queries = proof_options.fri_number_of_queries
+        )
+        .entered();
+        let mut phase_timer = PhaseTimer::new(with_diagnostics);
         #[cfg(feature = "instruments")]
         println!("- Started round 0: Air Initialization");
         #[cfg(feature = "instruments")]
-        let timer0 = Instant::now();
+        let timer0 = Stopwatch::start();
+        phase_timer.start();
 
         let air = A::new(main_trace.n_rows(), pub_inputs, proof_options);
         let domain = Domain::new(&air);
 
+        let num_preprocessed_columns = preprocessed_trace.map_or(0, |p| p.num_columns());
+        if num_preprocessed_columns != air.num_preprocessed_columns() {
+            return Err(ProvingError::WrongParameter(format!(
+                "the AIR has {} preprocessed columns, but {num_preprocessed_columns} were given",
+                air.num_preprocessed_columns()
+            )));
+        }
+        if let Some(preprocessed_trace) = preprocessed_trace {
+            if !preprocessed_trace.is_compatible_with(main_trace.n_rows(), proof_options) {
+                return Err(ProvingError::WrongParameter(
+                    "the columns were preprocessed for another trace length, LDE domain or Merkle hash"
+                        .to_string(),
+                ));
+            }
+        }
+        if proof_options.zk {
+            zk::check_air(&air)?;
+        }
+        let hiding = proof_options.zk.then_some(source);
+
+        phase_timer.stop("air_initialization");
         #[cfg(feature = "instruments")]
         let elapsed0 = timer0.elapsed();
         #[cfg(feature = "instruments")]
@@ -821,28 +1594,30 @@ pub trait IsStarkProver<A: AIR> {
         #[cfg(feature = "instruments")]
         println!("- Started round 1: RAP");
         #[cfg(feature = "instruments")]
-        let timer1 = Instant::now();
-
-        let round_1_result = Self::round_1_randomized_air_with_preprocessing(
-            &air,
-            main_trace,
-            &domain,
-            &mut transcript,
-        )?;
+        let timer1 = Stopwatch::start();
+        phase_timer.start();
+
+        let round_1_result = info_span!("round_1").in_scope(|| {
+            Self::round_1_randomized_air_with_preprocessing(
+                &air,
+                main_trace,
+                preprocessed_trace,
+                &domain,
+                hiding,
+                &mut transcript,
+            )
+        })?;
 
         #[cfg(debug_assertions)]
         validate_trace(
             &air,
             &round_1_result.main.trace_polys,
-            round_1_result
-                .aux
-                .as_ref()
-                .map(|a| &a.trace_polys)
-                .unwrap_or(&vec![]),
+            &round_1_result.aux_trace_polys(),
             &domain,
             &round_1_result.rap_challenges,
         );
 
+        phase_timer.stop("round_1");
         #[cfg(feature = "instruments")]
         let elapsed1 = timer1.elapsed();
         #[cfg(feature = "instruments")]
@@ -855,7 +1630,10 @@ pub trait IsStarkProver<A: AIR> {
         #[cfg(feature = "instruments")]
         println!("- Started round 2: Compute composition polynomial");
         #[cfg(feature = "instruments")]
-        let timer2 = Instant::now();
+        let timer2 = Stopwatch::start();
+        phase_timer.start();
+
+        let round_2_span = info_span!("round_2").entered();
 
         // <<<< Receive challenge: 𝛽
         let beta = transcript.sample_field_element();
@@ -881,11 +1659,14 @@ pub trait IsStarkProver<A: AIR> {
             &round_1_result,
             &transition_coefficients,
             &boundary_coefficients,
+            hiding,
         );
 
         // >>>> Send commitments: [H₁], [H₂]
         transcript.append_bytes(&round_2_result.composition_poly_root);
+        drop(round_2_span);
 
+        phase_timer.stop("round_2");
         #[cfg(feature = "instruments")]
         let elapsed2 = timer2.elapsed();
         #[cfg(feature = "instruments")]
@@ -898,7 +1679,10 @@ pub trait IsStarkProver<A: AIR> {
         #[cfg(feature = "instruments")]
         println!("- Started round 3: Evaluate polynomial in out of domain elements");
         #[cfg(feature = "instruments")]
-        let timer3 = Instant::now();
+        let timer3 = Stopwatch::start();
+        phase_timer.start();
+
+        let round_3_span = info_span!("round_3").entered();
 
         // <<<< Receive challenge: z
         let z = transcript.sample_z_ood(
@@ -926,7 +1710,9 @@ pub trait IsStarkProver<A: AIR> {
         for element in round_3_result.composition_poly_parts_ood_evaluation.iter() {
             transcript.append_field_element(element);
         }
+        drop(round_3_span);
 
+        phase_timer.stop("round_3");
         #[cfg(feature = "instruments")]
         let elapsed3 = timer3.elapsed();
         #[cfg(feature = "instruments")]
@@ -939,21 +1725,27 @@ pub trait IsStarkProver<A: AIR> {
         #[cfg(feature = "instruments")]
         println!("- Started round 4: FRI");
         #[cfg(feature = "instruments")]
-        let timer4 = Instant::now();
+        let timer4 = Stopwatch::start();
+        phase_timer.start();
 
         // Part of this round is running FRI, which is an interactive
         // protocol on its own. Therefore we pass it the transcript
         // to simulate the interactions with the verifier.
-        let round_4_result = Self::round_4_compute_and_run_fri_on_the_deep_composition_polynomial(
-            &air,
-            &domain,
-            &round_1_result,
-            &round_2_result,
-            &round_3_result,
-            &z,
-            &mut transcript,
-        );
+        let round_4_result = info_span!("round_4").in_scope(|| {
+            Self::round_4_compute_and_run_fri_on_the_deep_composition_polynomial(
+                &air,
+                &domain,
+                preprocessed_trace,
+                &round_1_result,
+                &round_2_result,
+                &round_3_result,
+                &z,
+                hiding,
+                &mut transcript,
+            )
+        });
 
+        phase_timer.stop("round_4");
         #[cfg(feature = "instruments")]
         let elapsed4 = timer4.elapsed();
         #[cfg(feature = "instruments")]
@@ -974,11 +1766,15 @@ pub trait IsStarkProver<A: AIR> {
 
         info!("End proof generation");
 
-        Ok(StarkProof::<A::Field, A::FieldExtension> {
+        let proof = StarkProof::<A::Field, A::FieldExtension> {
             // [t]
             lde_trace_main_merkle_root: round_1_result.main.lde_trace_merkle_root,
             // [t]
-            lde_trace_aux_merkle_root: round_1_result.aux.map(|x| x.lde_trace_merkle_root),
+            lde_trace_aux_merkle_roots: round_1_result
+                .aux
+                .iter()
+                .map(|x| x.lde_trace_merkle_root)
+                .collect(),
             // tⱼ(zgᵏ)
             trace_ood_evaluations: round_3_result.trace_ood_evaluations,
             // [H₁] and [H₂]
@@ -989,7 +1785,7 @@ pub trait IsStarkProver<A: AIR> {
             // [pₖ]
             fri_layers_merkle_roots: round_4_result.fri_layers_merkle_roots,
             // pₙ
-            fri_last_value: round_4_result.fri_last_value,
+            fri_last_layer_coefficients: round_4_result.fri_last_layer_coefficients,
             // Open(p₀(D₀), 𝜐ₛ), Open(pₖ(Dₖ), −𝜐ₛ^(2ᵏ))
             query_list: round_4_result.query_list,
             // Open(H₁(D_LDE, 𝜐₀), Open(H₂(D_LDE, 𝜐₀), Open(tⱼ(D_LDE), 𝜐₀)
@@ -997,15 +1793,58 @@ pub trait IsStarkProver<A: AIR> {
             deep_poly_openings: round_4_result.deep_poly_openings,
             // nonce obtained from grinding
             nonce: round_4_result.nonce,
+            merkle_hash: air.options().merkle_hash,
 
             trace_length: air.trace_length(),
-        })
+        };
+
+        let diagnostics = with_diagnostics.then(|| {
+            let (num_main_columns, num_aux_columns) = air.trace_layout();
+            ProverDiagnostics {
+                version: env!("CARGO_PKG_VERSION"),
+                public_inputs: None,
+                proof_options: proof_options.clone(),
+                trace: TraceDimensions {
+                    num_rows: air.trace_length(),
+                    num_main_columns,
+                    num_aux_columns,
+                    step_size: A::STEP_SIZE,
+                    lde_num_rows: domain.lde_roots_of_unity_coset.len(),
+                },
+                constraints: ConstraintCounts {
+                    num_transition_constraints,
+                    num_boundary_constraints,
+                    num_composition_poly_parts: round_2_result.composition_poly_parts.len(),
+                },
+                phases: phase_timer.into_phases(),
+                commitments: CommitmentRoots {
+                    preprocessed_trace: preprocessed_trace
+                        .map(|p| commitment_to_hex(&p.commitment())),
+                    main_trace: commitment_to_hex(&proof.lde_trace_main_merkle_root),
+                    aux_trace: proof
+                        .lde_trace_aux_merkle_roots
+                        .iter()
+                        .map(commitment_to_hex)
+                        .collect(),
+                    composition_poly: commitment_to_hex(&proof.composition_poly_root),
+                    fri_layers: proof
+                        .fri_layers_merkle_roots
+                        .iter()
+                        .map(commitment_to_hex)
+                        .collect(),
+                },
+                nonce: proof.nonce,
+            }
+        });
+
+        Ok((proof, diagnostics))
     }
 }
 
 #[cfg(test)]
 mod tests {
     use std::num::ParseIntError;
+    use std::sync::{Arc, Mutex};
 
     fn decode_hex(s: &str) -> Result<Vec<u8>, ParseIntError> {
         (0..s.len())
@@ -1020,6 +1859,7 @@ mod tests {
             simple_fibonacci::{self, FibonacciPublicInputs},
         },
         proof::options::ProofOptions,
+        trace::LDELayout,
         transcript::StoneProverTranscript,
         verifier::{Challenges, IsStarkVerifier, Verifier},
         Felt252,
@@ -1051,9 +1891,13 @@ mod tests {
             fri_number_of_queries: 1,
             coset_offset,
             grinding_factor,
+            fri_last_layer_degree_bound: 1,
+            zk: false,
+            merkle_hash: MerkleHash::Keccak256,
+            lde_layout: LDELayout::Natural,
         };
 
-        let domain = Domain::new(&simple_fibonacci::FibonacciAIR::new(
+        let domain = Domain::new(&simple_fibonacci::FibonacciAIR::<Stark252PrimeField>::new(
             trace_length,
             &pub_inputs,
             &proof_options,
@@ -1127,6 +1971,70 @@ mod tests {
         }
     }
 
+    /// Records the names of the spans created while it's the default subscriber.
+    struct SpanNames(Arc<Mutex<Vec<&'static str>>>);
+
+    impl tracing::Subscriber for SpanNames {
+        fn enabled(&self, _metadata: &tracing::Metadata<'_>) -> bool {
+            true
+        }
+
+        fn new_span(&self, span: &tracing::span::Attributes<'_>) -> tracing::span::Id {
+            let mut names = self.0.lock().unwrap();
+            names.push(span.metadata().name());
+            tracing::span::Id::from_u64(names.len() as u64)
+        }
+
+        fn record(&self, _span: &tracing::span::Id, _values: &tracing::span::Record<'_>) {}
+
+        fn record_follows_from(&self, _span: &tracing::span::Id, _follows: &tracing::span::Id) {}
+
+        fn event(&self, _event: &tracing::Event<'_>) {}
+
+        fn enter(&self, _span: &tracing::span::Id) {}
+
+        fn exit(&self, _span: &tracing::span::Id) {}
+    }
+
+    #[test]
+    fn prover_emits_a_span_for_each_phase() {
+        let trace = simple_fibonacci::fibonacci_trace([Felt252::from(1), Felt252::from(1)], 8);
+        let pub_inputs = FibonacciPublicInputs {
+            a0: Felt252::one(),
+            a1: Felt252::one(),
+        };
+        let names = Arc::new(Mutex::new(Vec::new()));
+
+        tracing::subscriber::with_default(SpanNames(names.clone()), || {
+            Prover::<simple_fibonacci::FibonacciAIR<Stark252PrimeField>>::prove(
+                &trace,
+                &pub_inputs,
+                &ProofOptions::default_test_options(),
+                StoneProverTranscript::new(&[]),
+            )
+            .unwrap();
+        });
+
+        let names = names.lock().unwrap();
+        for phase in [
+            "prove",
+            "round_1",
+            "trace_build",
+            "interpolation",
+            "lde",
+            "commitment",
+            "round_2",
+            "constraint_evaluation",
+            "round_3",
+            "round_4",
+            "deep_composition",
+            "fri_commit",
+            "queries",
+        ] {
+            assert!(names.contains(&phase), "missing span {phase}");
+        }
+    }
+
     fn proof_parts_stone_compatibility_case_1() -> (
         StarkProof<Stark252PrimeField, Stark252PrimeField>,
         fibonacci_2_cols_shifted::PublicInputs<Stark252PrimeField>,
@@ -1174,6 +2082,7 @@ mod tests {
         Verifier::step_1_replay_rounds_and_recover_challenges(
             &air,
             &proof,
+            None,
             &domain,
             &mut StoneProverTranscript::new(&seed),
         )
@@ -1340,10 +2249,10 @@ mod tests {
         let proof = stone_compatibility_case_1_proof();
 
         assert_eq!(
-            proof.fri_last_value,
-            FieldElement::from_hex_unchecked(
+            proof.fri_last_layer_coefficients,
+            vec![FieldElement::from_hex_unchecked(
                 "43fedf9f9e3d1469309862065c7d7ca0e7e9ce451906e9c01553056f695aec9"
-            )
+            )]
         );
     }
 
@@ -1570,6 +2479,7 @@ mod tests {
         Verifier::step_1_replay_rounds_and_recover_challenges(
             &air,
             &proof,
+            None,
             &domain,
             &mut StoneProverTranscript::new(&seed),
         )
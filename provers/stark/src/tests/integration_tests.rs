@@ -1,20 +1,47 @@
-use lambdaworks_math::field::{
-    element::FieldElement, fields::fft_friendly::stark_252_prime_field::Stark252PrimeField,
+use lambdaworks_crypto::{
+    fiat_shamir::{
+        default_transcript::DefaultTranscript, extension_transcript::ExtensionTranscript,
+        poseidon_transcript::PoseidonTranscript,
+    },
+    hash::poseidon::starknet::PoseidonCairoStark252,
+};
+use lambdaworks_math::{
+    environment::SeededRandomSource,
+    field::{
+        element::FieldElement,
+        fields::{
+            fft_friendly::{
+                babybear::Babybear31PrimeField, quadratic_babybear::QuadraticBabybearField,
+                quartic_babybear::QuarticBabybearField, stark_252_prime_field::Stark252PrimeField,
+            },
+            mersenne31::{
+                extensions::{Degree2ExtensionField, Degree4ExtensionField},
+                field::Mersenne31Field,
+            },
+        },
+    },
 };
 
 use crate::{
+    config::MerkleHash,
     examples::{
         bit_flags::{self, BitFlagsAIR},
         dummy_air::{self, DummyAIR},
         fibonacci_2_cols_shifted::{self, Fibonacci2ColsShifted},
         fibonacci_2_columns::{self, Fibonacci2ColsAIR},
         fibonacci_rap::{fibonacci_rap_trace, FibonacciRAP, FibonacciRAPPublicInputs},
+        multi_round_lookup::{multi_round_lookup_trace, MultiRoundLookupAIR},
+        preprocessed_range_check::{
+            preprocessed_range_check_trace, range_check_table, PreprocessedRangeCheckAIR,
+        },
         quadratic_air::{self, QuadraticAIR, QuadraticPublicInputs},
+        range_check_lookup::{range_check_lookup_trace, RangeCheckLookupAIR},
         simple_fibonacci::{self, FibonacciAIR, FibonacciPublicInputs},
         simple_periodic_cols::{self, SimplePeriodicAIR, SimplePeriodicPublicInputs},
     },
     proof::options::ProofOptions,
-    prover::{IsStarkProver, Prover},
+    prover::{IsStarkProver, Prover, ProvingError},
+    trace::{LDELayout, TraceTable},
     transcript::StoneProverTranscript,
     verifier::{IsStarkVerifier, Verifier},
     Felt252,
@@ -46,6 +73,116 @@ fn test_prove_fib() {
     ));
 }
 
+#[test_log::test]
+fn test_prove_fib_with_poseidon_transcript() {
+    let trace = simple_fibonacci::fibonacci_trace([Felt252::from(1), Felt252::from(1)], 32);
+
+    let proof_options = ProofOptions::default_test_options();
+
+    let pub_inputs = FibonacciPublicInputs {
+        a0: Felt252::one(),
+        a1: Felt252::one(),
+    };
+
+    let proof = Prover::<FibonacciAIR<Stark252PrimeField>>::prove(
+        &trace,
+        &pub_inputs,
+        &proof_options,
+        PoseidonTranscript::<PoseidonCairoStark252>::new(&[]),
+    )
+    .unwrap();
+    assert!(Verifier::<FibonacciAIR<Stark252PrimeField>>::verify(
+        &proof,
+        &pub_inputs,
+        &proof_options,
+        PoseidonTranscript::<PoseidonCairoStark252>::new(&[]),
+    ));
+    assert!(!Verifier::<FibonacciAIR<Stark252PrimeField>>::verify(
+        &proof,
+        &pub_inputs,
+        &proof_options,
+        StoneProverTranscript::new(&[]),
+    ));
+}
+
+#[test_log::test]
+fn test_prove_fib_with_each_merkle_hash() {
+    let trace = simple_fibonacci::fibonacci_trace([Felt252::from(1), Felt252::from(1)], 32);
+
+    let pub_inputs = FibonacciPublicInputs {
+        a0: Felt252::one(),
+        a1: Felt252::one(),
+    };
+
+    let mut roots = Vec::new();
+    for merkle_hash in [
+        MerkleHash::Keccak256,
+        MerkleHash::Blake3,
+        MerkleHash::Poseidon,
+        MerkleHash::Poseidon2,
+    ] {
+        let proof_options = ProofOptions {
+            merkle_hash,
+            ..ProofOptions::default_test_options()
+        };
+        let proof = Prover::<FibonacciAIR<Stark252PrimeField>>::prove(
+            &trace,
+            &pub_inputs,
+            &proof_options,
+            StoneProverTranscript::new(&[]),
+        )
+        .unwrap();
+        assert_eq!(proof.merkle_hash, merkle_hash);
+        assert!(Verifier::<FibonacciAIR<Stark252PrimeField>>::verify(
+            &proof,
+            &pub_inputs,
+            &proof_options,
+            StoneProverTranscript::new(&[]),
+        ));
+        roots.push(proof.lde_trace_main_merkle_root);
+    }
+    for (i, root) in roots.iter().enumerate() {
+        assert!(!roots[i + 1..].contains(root));
+    }
+}
+
+#[test_log::test]
+fn test_verify_fib_with_another_merkle_hash_fails() {
+    let trace = simple_fibonacci::fibonacci_trace([Felt252::from(1), Felt252::from(1)], 32);
+
+    let proof_options = ProofOptions {
+        merkle_hash: MerkleHash::Poseidon,
+        ..ProofOptions::default_test_options()
+    };
+
+    let pub_inputs = FibonacciPublicInputs {
+        a0: Felt252::one(),
+        a1: Felt252::one(),
+    };
+
+    let mut proof = Prover::<FibonacciAIR<Stark252PrimeField>>::prove(
+        &trace,
+        &pub_inputs,
+        &proof_options,
+        StoneProverTranscript::new(&[]),
+    )
+    .unwrap();
+    assert!(!Verifier::<FibonacciAIR<Stark252PrimeField>>::verify(
+        &proof,
+        &pub_inputs,
+        &ProofOptions::default_test_options(),
+        StoneProverTranscript::new(&[]),
+    ));
+
+    proof.merkle_hash = MerkleHash::Keccak256;
+    assert!(!Verifier::<FibonacciAIR<Stark252PrimeField>>::verify(
+        &proof,
+        &pub_inputs,
+        &ProofOptions::default_test_options(),
+        StoneProverTranscript::new(&[]),
+    ));
+}
+
 #[test_log::test]
 fn test_prove_fib17() {
     type FE = FieldElement<Stark252PrimeField>;
@@ -56,6 +193,10 @@ fn test_prove_fib17() {
         fri_number_of_queries: 7,
         coset_offset: 3,
         grinding_factor: 1,
+        fri_last_layer_degree_bound: 1,
+        zk: false,
+        merkle_hash: MerkleHash::Keccak256,
+        lde_layout: LDELayout::Natural,
     };
 
     let pub_inputs = FibonacciPublicInputs {
@@ -78,6 +219,170 @@ fn test_prove_fib17() {
     ));
 }
 
+#[test_log::test]
+fn test_prove_fib_opens_repeated_queries_once() {
+    let trace = simple_fibonacci::fibonacci_trace([Felt252::from(1), Felt252::from(1)], 4);
+
+    // The LDE domain has 8 elements, so there are only 4 different queries.
+    let proof_options = ProofOptions {
+        blowup_factor: 2,
+        fri_number_of_queries: 7,
+        coset_offset: 3,
+        grinding_factor: 1,
+        fri_last_layer_degree_bound: 1,
+        zk: false,
+        merkle_hash: MerkleHash::Keccak256,
+        lde_layout: LDELayout::Natural,
+    };
+
+    let pub_inputs = FibonacciPublicInputs {
+        a0: Felt252::one(),
+        a1: Felt252::one(),
+    };
+
+    let mut proof = Prover::<FibonacciAIR<Stark252PrimeField>>::prove(
+        &trace,
+        &pub_inputs,
+        &proof_options,
+        StoneProverTranscript::new(&[]),
+    )
+    .unwrap();
+    assert!(proof.query_list.len() <= 4);
+    assert_eq!(proof.query_list.len(), proof.deep_poly_openings.len());
+    assert!(Verifier::<FibonacciAIR<Stark252PrimeField>>::verify(
+        &proof,
+        &pub_inputs,
+        &proof_options,
+        StoneProverTranscript::new(&[]),
+    ));
+
+    // Repeating an opening doesn't make up for a missing query
+    proof.query_list.push(proof.query_list[0].clone());
+    proof
+        .deep_poly_openings
+        .push(proof.deep_poly_openings[0].clone());
+    assert!(!Verifier::<FibonacciAIR<Stark252PrimeField>>::verify(
+        &proof,
+        &pub_inputs,
+        &proof_options,
+        StoneProverTranscript::new(&[]),
+    ));
+}
+
+#[test_log::test]
+fn test_prove_fib_with_fri_last_layer_of_degree_4() {
+    let trace = simple_fibonacci::fibonacci_trace([Felt252::from(1), Felt252::from(1)], 1024);
+
+    let default_options = ProofOptions::default_test_options();
+    let proof_options = ProofOptions {
+        fri_last_layer_degree_bound: 4,
+        zk: false,
+        ..default_options.clone()
+    };
+
+    let pub_inputs = FibonacciPublicInputs {
+        a0: Felt252::one(),
+        a1: Felt252::one(),
+    };
+
+    let default_proof = Prover::<FibonacciAIR<Stark252PrimeField>>::prove(
+        &trace,
+        &pub_inputs,
+        &default_options,
+        StoneProverTranscript::new(&[]),
+    )
+    .unwrap();
+    let proof = Prover::<FibonacciAIR<Stark252PrimeField>>::prove(
+        &trace,
+        &pub_inputs,
+        &proof_options,
+        StoneProverTranscript::new(&[]),
+    )
+    .unwrap();
+
+    assert_eq!(proof.fri_last_layer_coefficients.len(), 4);
+    assert_eq!(
+        proof.fri_layers_merkle_roots.len() + 2,
+        default_proof.fri_layers_merkle_roots.len()
+    );
+    assert!(Verifier::<FibonacciAIR<Stark252PrimeField>>::verify(
+        &proof,
+        &pub_inputs,
+        &proof_options,
+        StoneProverTranscript::new(&[]),
+    ));
+    assert!(!Verifier::<FibonacciAIR<Stark252PrimeField>>::verify(
+        &proof,
+        &pub_inputs,
+        &default_options,
+        StoneProverTranscript::new(&[]),
+    ));
+}
+
+#[test_log::test]
+fn test_prove_fib_with_fri_stopping_at_the_first_fold() {
+    let trace = simple_fibonacci::fibonacci_trace([Felt252::from(1), Felt252::from(1)], 16);
+
+    let proof_options = ProofOptions {
+        fri_last_layer_degree_bound: 1 << 20,
+        zk: false,
+        ..ProofOptions::default_test_options()
+    };
+
+    let pub_inputs = FibonacciPublicInputs {
+        a0: Felt252::one(),
+        a1: Felt252::one(),
+    };
+
+    let proof = Prover::<FibonacciAIR<Stark252PrimeField>>::prove(
+        &trace,
+        &pub_inputs,
+        &proof_options,
+        StoneProverTranscript::new(&[]),
+    )
+    .unwrap();
+
+    assert!(proof.fri_layers_merkle_roots.is_empty());
+    assert!(Verifier::<FibonacciAIR<Stark252PrimeField>>::verify(
+        &proof,
+        &pub_inputs,
+        &proof_options,
+        StoneProverTranscript::new(&[]),
+    ));
+}
+
+#[test_log::test]
+fn test_verify_fib_with_a_wrong_fri_last_layer_fails() {
+    let trace = simple_fibonacci::fibonacci_trace([Felt252::from(1), Felt252::from(1)], 16);
+
+    let proof_options = ProofOptions {
+        fri_last_layer_degree_bound: 4,
+        zk: false,
+        ..ProofOptions::default_test_options()
+    };
+
+    let pub_inputs = FibonacciPublicInputs {
+        a0: Felt252::one(),
+        a1: Felt252::one(),
+    };
+
+    let mut proof = Prover::<FibonacciAIR<Stark252PrimeField>>::prove(
+        &trace,
+        &pub_inputs,
+        &proof_options,
+        StoneProverTranscript::new(&[]),
+    )
+    .unwrap();
+    proof.fri_last_layer_coefficients[3] += Felt252::one();
+
+    assert!(!Verifier::<FibonacciAIR<Stark252PrimeField>>::verify(
+        &proof,
+        &pub_inputs,
+        &proof_options,
+        StoneProverTranscript::new(&[]),
+    ));
+}
+
 #[test_log::test]
 fn test_prove_simple_periodic_8() {
     let trace = simple_periodic_cols::simple_periodic_trace::<Stark252PrimeField>(8);
@@ -236,6 +541,362 @@ fn test_prove_rap_fib() {
     ));
 }
 
+#[test_log::test]
+fn test_prove_rap_fib_over_babybear_with_challenges_in_its_extension() {
+    type F = Babybear31PrimeField;
+    type E = QuadraticBabybearField;
+    let transcript = || ExtensionTranscript::<F, E, _>::new(DefaultTranscript::<F>::new(&[]));
+
+    let steps = 16;
+    let trace = fibonacci_rap_trace([FieldElement::<F>::one(), FieldElement::one()], steps);
+
+    let proof_options = ProofOptions::default_test_options();
+
+    let pub_inputs = FibonacciRAPPublicInputs {
+        steps,
+        a0: FieldElement::one(),
+        a1: FieldElement::one(),
+    };
+
+    let proof =
+        Prover::<FibonacciRAP<F, E>>::prove(&trace, &pub_inputs, &proof_options, transcript())
+            .unwrap();
+    assert!(Verifier::<FibonacciRAP<F, E>>::verify(
+        &proof,
+        &pub_inputs,
+        &proof_options,
+        transcript()
+    ));
+
+    // The challenges sampled by a different transcript don't match the proof.
+    assert!(!Verifier::<FibonacciRAP<F, E>>::verify(
+        &proof,
+        &pub_inputs,
+        &proof_options,
+        ExtensionTranscript::<F, E, _>::new(DefaultTranscript::<F>::new(&[1]))
+    ));
+}
+
+#[test_log::test]
+fn test_prove_fib_over_babybear_with_challenges_in_its_quartic_extension() {
+    type F = Babybear31PrimeField;
+    type E = QuarticBabybearField;
+    let transcript = || ExtensionTranscript::<F, E, _>::new(DefaultTranscript::<F>::new(&[]));
+
+    let trace =
+        simple_fibonacci::fibonacci_trace([FieldElement::<F>::one(), FieldElement::one()], 32);
+
+    let proof_options = ProofOptions::default_test_options();
+
+    let pub_inputs = FibonacciPublicInputs {
+        a0: FieldElement::one(),
+        a1: FieldElement::one(),
+    };
+
+    let proof =
+        Prover::<FibonacciAIR<F, E>>::prove(&trace, &pub_inputs, &proof_options, transcript())
+            .unwrap();
+    assert!(Verifier::<FibonacciAIR<F, E>>::verify(
+        &proof,
+        &pub_inputs,
+        &proof_options,
+        transcript()
+    ));
+
+    let wrong_pub_inputs = FibonacciPublicInputs {
+        a0: FieldElement::one(),
+        a1: FieldElement::from(2),
+    };
+    assert!(!Verifier::<FibonacciAIR<F, E>>::verify(
+        &proof,
+        &wrong_pub_inputs,
+        &proof_options,
+        transcript()
+    ));
+}
+
+/// Mersenne31 has no large power of two roots of unity, so the trace is over its quadratic
+/// extension, which has roots of unity of order up to `2³²`, while the challenges are sampled
+/// from its quartic extension.
+#[test_log::test]
+fn test_prove_fib_over_mersenne31_quadratic_extension_with_challenges_in_its_quartic_extension() {
+    type F = Degree2ExtensionField;
+    type E = Degree4ExtensionField;
+    let transcript = || {
+        ExtensionTranscript::<Mersenne31Field, E, _>::new(
+            DefaultTranscript::<Mersenne31Field>::new(&[]),
+        )
+    };
+
+    let trace =
+        simple_fibonacci::fibonacci_trace([FieldElement::<F>::one(), FieldElement::one()], 32);
+
+    let proof_options = ProofOptions::default_test_options();
+
+    let pub_inputs = FibonacciPublicInputs {
+        a0: FieldElement::one(),
+        a1: FieldElement::one(),
+    };
+
+    let proof =
+        Prover::<FibonacciAIR<F, E>>::prove(&trace, &pub_inputs, &proof_options, transcript())
+            .unwrap();
+    assert!(Verifier::<FibonacciAIR<F, E>>::verify(
+        &proof,
+        &pub_inputs,
+        &proof_options,
+        transcript()
+    ));
+
+    let wrong_pub_inputs = FibonacciPublicInputs {
+        a0: FieldElement::one(),
+        a1: FieldElement::from(2),
+    };
+    assert!(!Verifier::<FibonacciAIR<F, E>>::verify(
+        &proof,
+        &wrong_pub_inputs,
+        &proof_options,
+        transcript()
+    ));
+}
+
+#[test_log::test]
+fn test_prove_range_check_lookup() {
+    let values = [3, 0, 15, 3, 7, 7, 7, 1, 12, 0, 9, 3, 14, 2, 6, 5]
+        .into_iter()
+        .map(Felt252::from)
+        .collect();
+    let trace = range_check_lookup_trace(values);
+
+    let proof_options = ProofOptions::default_test_options();
+
+    let proof = Prover::<RangeCheckLookupAIR<Stark252PrimeField>>::prove(
+        &trace,
+        &(),
+        &proof_options,
+        StoneProverTranscript::new(&[]),
+    )
+    .unwrap();
+    assert!(Verifier::<RangeCheckLookupAIR<Stark252PrimeField>>::verify(
+        &proof,
+        &(),
+        &proof_options,
+        StoneProverTranscript::new(&[])
+    ));
+}
+
+#[test_log::test]
+fn test_prove_range_check_lookup_with_a_value_out_of_range_fails() {
+    let values = (0..16).map(Felt252::from).collect();
+    let mut trace = range_check_lookup_trace(values);
+    trace.get_row_mut(5)[0] = Felt252::from(16);
+
+    let proof_options = ProofOptions::default_test_options();
+
+    let proof = Prover::<RangeCheckLookupAIR<Stark252PrimeField>>::prove(
+        &trace,
+        &(),
+        &proof_options,
+        StoneProverTranscript::new(&[]),
+    )
+    .unwrap();
+    assert!(
+        !Verifier::<RangeCheckLookupAIR<Stark252PrimeField>>::verify(
+            &proof,
+            &(),
+            &proof_options,
+            StoneProverTranscript::new(&[])
+        )
+    );
+}
+
+#[test_log::test]
+fn test_prove_multi_round_lookup() {
+    let first_values = [3, 0, 15, 3, 7, 7, 7, 1, 12, 0, 9, 3, 14, 2, 6, 5]
+        .into_iter()
+        .map(Felt252::from)
+        .collect();
+    let second_values = (0..16).rev().map(Felt252::from).collect();
+    let trace = multi_round_lookup_trace(first_values, second_values);
+
+    let proof_options = ProofOptions::default_test_options();
+
+    let proof = Prover::<MultiRoundLookupAIR<Stark252PrimeField>>::prove(
+        &trace,
+        &(),
+        &proof_options,
+        StoneProverTranscript::new(&[]),
+    )
+    .unwrap();
+    assert_eq!(proof.lde_trace_aux_merkle_roots.len(), 2);
+    assert!(Verifier::<MultiRoundLookupAIR<Stark252PrimeField>>::verify(
+        &proof,
+        &(),
+        &proof_options,
+        StoneProverTranscript::new(&[])
+    ));
+}
+
+#[test_log::test]
+fn test_prove_multi_round_lookup_with_a_value_out_of_range_in_the_second_round_fails() {
+    let values: Vec<_> = (0..16).map(Felt252::from).collect();
+    let mut trace = multi_round_lookup_trace(values.clone(), values);
+    trace.get_row_mut(5)[3] = Felt252::from(16);
+
+    let proof_options = ProofOptions::default_test_options();
+
+    let proof = Prover::<MultiRoundLookupAIR<Stark252PrimeField>>::prove(
+        &trace,
+        &(),
+        &proof_options,
+        StoneProverTranscript::new(&[]),
+    )
+    .unwrap();
+    assert!(
+        !Verifier::<MultiRoundLookupAIR<Stark252PrimeField>>::verify(
+            &proof,
+            &(),
+            &proof_options,
+            StoneProverTranscript::new(&[])
+        )
+    );
+}
+
+#[test_log::test]
+fn test_verify_multi_round_lookup_with_the_rounds_swapped_fails() {
+    let values: Vec<_> = (0..16).map(Felt252::from).collect();
+    let trace = multi_round_lookup_trace(values.clone(), values);
+
+    let proof_options = ProofOptions::default_test_options();
+
+    let mut proof = Prover::<MultiRoundLookupAIR<Stark252PrimeField>>::prove(
+        &trace,
+        &(),
+        &proof_options,
+        StoneProverTranscript::new(&[]),
+    )
+    .unwrap();
+    proof.lde_trace_aux_merkle_roots.swap(0, 1);
+    for opening in proof.deep_poly_openings.iter_mut() {
+        opening.aux_trace_polys.swap(0, 1);
+    }
+    assert!(
+        !Verifier::<MultiRoundLookupAIR<Stark252PrimeField>>::verify(
+            &proof,
+            &(),
+            &proof_options,
+            StoneProverTranscript::new(&[])
+        )
+    );
+}
+
+#[test_log::test]
+fn test_prove_range_check_with_preprocessed_table() {
+    let proof_options = ProofOptions::default_test_options();
+    let table = range_check_table(16);
+    let preprocessed =
+        Prover::<PreprocessedRangeCheckAIR<Stark252PrimeField>>::preprocess(&table, &proof_options);
+
+    // The same preprocessed table is reused by every proof.
+    for values in [[3, 0, 15, 3, 7, 7, 7, 1, 12, 0, 9, 3, 14, 2, 6, 5], [1; 16]] {
+        let trace = preprocessed_range_check_trace(values.into_iter().map(Felt252::from).collect());
+        let proof =
+            Prover::<PreprocessedRangeCheckAIR<Stark252PrimeField>>::prove_with_preprocessed_trace(
+                &trace,
+                &preprocessed,
+                &(),
+                &proof_options,
+                StoneProverTranscript::new(&[]),
+            )
+            .unwrap();
+        assert!(
+            Verifier::<PreprocessedRangeCheckAIR<Stark252PrimeField>>::verify_with_preprocessed_commitment(
+                &proof,
+                Some(&preprocessed.commitment()),
+                &(),
+                &proof_options,
+                StoneProverTranscript::new(&[])
+            )
+        );
+    }
+}
+
+#[test_log::test]
+fn test_prove_range_check_with_another_preprocessed_table_fails() {
+    let proof_options = ProofOptions::default_test_options();
+    // The prover commits to a table shifted by one, where the value 16 is in range.
+    let values = (1..=16).map(Felt252::from).collect();
+    let trace = TraceTable::from_columns_main(vec![values, vec![Felt252::one(); 16]], 1);
+    let mut table = range_check_table(16);
+    for row in 0..16 {
+        table.get_row_mut(row)[0] += Felt252::one();
+    }
+    let preprocessed =
+        Prover::<PreprocessedRangeCheckAIR<Stark252PrimeField>>::preprocess(&table, &proof_options);
+    let honest_preprocessed = Prover::<PreprocessedRangeCheckAIR<Stark252PrimeField>>::preprocess(
+        &range_check_table(16),
+        &proof_options,
+    );
+
+    let proof =
+        Prover::<PreprocessedRangeCheckAIR<Stark252PrimeField>>::prove_with_preprocessed_trace(
+            &trace,
+            &preprocessed,
+            &(),
+            &proof_options,
+            StoneProverTranscript::new(&[]),
+        )
+        .unwrap();
+    assert!(
+        !Verifier::<PreprocessedRangeCheckAIR<Stark252PrimeField>>::verify_with_preprocessed_commitment(
+            &proof,
+            Some(&honest_preprocessed.commitment()),
+            &(),
+            &proof_options,
+            StoneProverTranscript::new(&[])
+        )
+    );
+    assert!(
+        !Verifier::<PreprocessedRangeCheckAIR<Stark252PrimeField>>::verify(
+            &proof,
+            &(),
+            &proof_options,
+            StoneProverTranscript::new(&[])
+        )
+    );
+}
+
+#[test_log::test]
+fn test_prove_without_the_preprocessed_trace_of_the_air_fails() {
+    let proof_options = ProofOptions::default_test_options();
+    let trace = preprocessed_range_check_trace((0..16).map(Felt252::from).collect());
+
+    assert!(
+        Prover::<PreprocessedRangeCheckAIR<Stark252PrimeField>>::prove(
+            &trace,
+            &(),
+            &proof_options,
+            StoneProverTranscript::new(&[]),
+        )
+        .is_err()
+    );
+
+    let preprocessed = Prover::<PreprocessedRangeCheckAIR<Stark252PrimeField>>::preprocess(
+        &range_check_table(8),
+        &proof_options,
+    );
+    assert!(
+        Prover::<PreprocessedRangeCheckAIR<Stark252PrimeField>>::prove_with_preprocessed_trace(
+            &trace,
+            &preprocessed,
+            &(),
+            &proof_options,
+            StoneProverTranscript::new(&[]),
+        )
+        .is_err()
+    );
+}
+
 #[test_log::test]
 fn test_prove_dummy() {
     let trace_length = 16;
@@ -271,3 +932,278 @@ fn test_prove_bit_flags() {
         StoneProverTranscript::new(&[]),
     ));
 }
+
+#[test_log::test]
+fn test_proofs_are_the_same_with_each_lde_layout() {
+    let bit_flags_trace = bit_flags::bit_prefix_flag_trace(32);
+    let rap_trace = fibonacci_rap_trace([Felt252::from(1), Felt252::from(1)], 16);
+    let rap_pub_inputs = FibonacciRAPPublicInputs {
+        steps: 16,
+        a0: Felt252::one(),
+        a1: Felt252::one(),
+    };
+
+    let proofs: Vec<_> = [LDELayout::Natural, LDELayout::Coset, LDELayout::Streaming]
+        .into_iter()
+        .map(|lde_layout| {
+            let proof_options = ProofOptions {
+                lde_layout,
+                ..ProofOptions::default_test_options()
+            };
+            let bit_flags_proof = Prover::<BitFlagsAIR>::prove(
+                &bit_flags_trace,
+                &(),
+                &proof_options,
+                StoneProverTranscript::new(&[]),
+            )
+            .unwrap();
+            let rap_proof = Prover::<FibonacciRAP<Stark252PrimeField>>::prove(
+                &rap_trace,
+                &rap_pub_inputs,
+                &proof_options,
+                StoneProverTranscript::new(&[]),
+            )
+            .unwrap();
+
+            let mut bytes = Vec::new();
+            bit_flags_proof.write_to(&mut bytes).unwrap();
+            rap_proof.write_to(&mut bytes).unwrap();
+            bytes
+        })
+        .collect();
+
+    assert_eq!(proofs[0], proofs[1]);
+    assert_eq!(proofs[0], proofs[2]);
+}
+
+#[test_log::test]
+fn test_streaming_proofs_with_preprocessed_columns_and_zk_are_the_same() {
+    let table = range_check_table(16);
+    let range_check_trace =
+        preprocessed_range_check_trace((0..16u64).rev().map(Felt252::from).collect());
+    let rap_trace = fibonacci_rap_trace([Felt252::from(1), Felt252::from(1)], 120);
+    let rap_pub_inputs = FibonacciRAPPublicInputs {
+        steps: 120,
+        a0: Felt252::one(),
+        a1: Felt252::one(),
+    };
+
+    let proofs: Vec<_> = [LDELayout::Natural, LDELayout::Streaming]
+        .into_iter()
+        .map(|lde_layout| {
+            let proof_options = ProofOptions {
+                lde_layout,
+                ..ProofOptions::default_test_options()
+            };
+            let preprocessed = Prover::<PreprocessedRangeCheckAIR<Stark252PrimeField>>::preprocess(
+                &table,
+                &proof_options,
+            );
+            let range_check_proof =
+                Prover::<PreprocessedRangeCheckAIR<Stark252PrimeField>>::prove_with_preprocessed_trace(
+                    &range_check_trace,
+                    &preprocessed,
+                    &(),
+                    &proof_options,
+                    StoneProverTranscript::new(&[]),
+                )
+                .unwrap();
+            let zk_proof_options = ProofOptions {
+                lde_layout,
+                ..zk_test_options()
+            };
+            let rap_proof = Prover::<FibonacciRAP<Stark252PrimeField>>::prove_with_random_source(
+                &rap_trace,
+                &rap_pub_inputs,
+                &zk_proof_options,
+                &SeededRandomSource::new(0),
+                StoneProverTranscript::new(&[]),
+            )
+            .unwrap();
+            assert!(Verifier::<FibonacciRAP<Stark252PrimeField>>::verify(
+                &rap_proof,
+                &rap_pub_inputs,
+                &zk_proof_options,
+                StoneProverTranscript::new(&[]),
+            ));
+
+            let mut bytes = Vec::new();
+            range_check_proof.write_to(&mut bytes).unwrap();
+            rap_proof.write_to(&mut bytes).unwrap();
+            bytes
+        })
+        .collect();
+
+    assert_eq!(proofs[0], proofs[1]);
+}
+
+fn zk_test_options() -> ProofOptions {
+    ProofOptions {
+        zk: true,
+        ..ProofOptions::default_test_options()
+    }
+}
+
+#[test_log::test]
+fn test_prove_fib_zk() {
+    let trace = simple_fibonacci::fibonacci_trace([Felt252::from(1), Felt252::from(1)], 64);
+
+    let proof_options = zk_test_options();
+
+    let pub_inputs = FibonacciPublicInputs {
+        a0: Felt252::one(),
+        a1: Felt252::one(),
+    };
+
+    let proof = Prover::<FibonacciAIR<Stark252PrimeField>>::prove(
+        &trace,
+        &pub_inputs,
+        &proof_options,
+        StoneProverTranscript::new(&[]),
+    )
+    .unwrap();
+    assert!(proof
+        .deep_poly_openings
+        .iter()
+        .all(|opening| !opening.main_trace_polys.salt.is_empty()));
+    assert!(Verifier::<FibonacciAIR<Stark252PrimeField>>::verify(
+        &proof,
+        &pub_inputs,
+        &proof_options,
+        StoneProverTranscript::new(&[]),
+    ));
+}
+
+#[test_log::test]
+fn test_prove_fib_zk_with_different_randomness_gives_different_openings() {
+    let trace = simple_fibonacci::fibonacci_trace([Felt252::from(1), Felt252::from(1)], 64);
+
+    let proof_options = zk_test_options();
+
+    let pub_inputs = FibonacciPublicInputs {
+        a0: Felt252::one(),
+        a1: Felt252::one(),
+    };
+
+    let proofs: Vec<_> = [0, 1]
+        .into_iter()
+        .map(|seed| {
+            Prover::<FibonacciAIR<Stark252PrimeField>>::prove_with_random_source(
+                &trace,
+                &pub_inputs,
+                &proof_options,
+                &SeededRandomSource::new(seed),
+                StoneProverTranscript::new(&[]),
+            )
+            .unwrap()
+        })
+        .collect();
+    assert_ne!(
+        proofs[0].lde_trace_main_merkle_root,
+        proofs[1].lde_trace_main_merkle_root
+    );
+    assert_ne!(
+        proofs[0].trace_ood_evaluations.data,
+        proofs[1].trace_ood_evaluations.data
+    );
+    for proof in &proofs {
+        assert!(Verifier::<FibonacciAIR<Stark252PrimeField>>::verify(
+            proof,
+            &pub_inputs,
+            &proof_options,
+            StoneProverTranscript::new(&[]),
+        ));
+    }
+}
+
+#[test_log::test]
+fn test_prove_rap_fib_zk() {
+    let steps = 120;
+    let trace = fibonacci_rap_trace([Felt252::from(1), Felt252::from(1)], steps);
+
+    let proof_options = zk_test_options();
+
+    let pub_inputs = FibonacciRAPPublicInputs {
+        steps,
+        a0: Felt252::one(),
+        a1: Felt252::one(),
+    };
+
+    let proof = Prover::<FibonacciRAP<Stark252PrimeField>>::prove(
+        &trace,
+        &pub_inputs,
+        &proof_options,
+        StoneProverTranscript::new(&[]),
+    )
+    .unwrap();
+    assert!(Verifier::<FibonacciRAP<Stark252PrimeField>>::verify(
+        &proof,
+        &pub_inputs,
+        &proof_options,
+        StoneProverTranscript::new(&[])
+    ));
+}
+
+#[test_log::test]
+fn test_verify_fib_zk_with_a_wrong_salt_fails() {
+    let trace = simple_fibonacci::fibonacci_trace([Felt252::from(1), Felt252::from(1)], 64);
+
+    let proof_options = zk_test_options();
+
+    let pub_inputs = FibonacciPublicInputs {
+        a0: Felt252::one(),
+        a1: Felt252::one(),
+    };
+
+    let proof = Prover::<FibonacciAIR<Stark252PrimeField>>::prove(
+        &trace,
+        &pub_inputs,
+        &proof_options,
+        StoneProverTranscript::new(&[]),
+    )
+    .unwrap();
+
+    let mut wrong_main_trace_salt = proof.clone();
+    wrong_main_trace_salt.deep_poly_openings[0]
+        .main_trace_polys
+        .salt[0] += Felt252::one();
+    let mut wrong_composition_poly_salt = proof.clone();
+    wrong_composition_poly_salt.deep_poly_openings[0]
+        .composition_poly
+        .salt_sym[0] += Felt252::one();
+    let mut wrong_fri_salt = proof;
+    wrong_fri_salt.query_list[0].layers_salts[0][0] += Felt252::one();
+
+    for proof in [
+        wrong_main_trace_salt,
+        wrong_composition_poly_salt,
+        wrong_fri_salt,
+    ] {
+        assert!(!Verifier::<FibonacciAIR<Stark252PrimeField>>::verify(
+            &proof,
+            &pub_inputs,
+            &proof_options,
+            StoneProverTranscript::new(&[]),
+        ));
+    }
+}
+
+#[test_log::test]
+fn test_prove_fib_zk_with_a_short_trace_fails() {
+    let trace = simple_fibonacci::fibonacci_trace([Felt252::from(1), Felt252::from(1)], 16);
+
+    let proof_options = zk_test_options();
+
+    let pub_inputs = FibonacciPublicInputs {
+        a0: Felt252::one(),
+        a1: Felt252::one(),
+    };
+
+    let result = Prover::<FibonacciAIR<Stark252PrimeField>>::prove(
+        &trace,
+        &pub_inputs,
+        &proof_options,
+        StoneProverTranscript::new(&[]),
+    );
+    assert!(matches!(result, Err(ProvingError::WrongParameter(_))));
+}
@@ -6,8 +6,17 @@ use lambdaworks_math::{
     field::{element::FieldElement, traits::IsFFTField},
     polynomial::Polynomial,
 };
+#[cfg(feature = "proptest")]
+use proptest::{
+    arbitrary::Arbitrary,
+    collection,
+    prelude::any,
+    strategy::{BoxedStrategy, Strategy},
+};
 #[cfg(feature = "parallel")]
 use rayon::prelude::{IntoParallelRefIterator, ParallelIterator};
+#[cfg(feature = "wasm")]
+use wasm_bindgen::prelude::wasm_bindgen;
 
 /// A two-dimensional representation of an execution trace of the STARK
 /// protocol.
@@ -184,6 +193,82 @@ impl<F: IsField> TraceTable<F> {
         }
     }
 }
+
+/// Shape of the traces generated by the [`Arbitrary`] implementation of [`TraceTable`].
+#[cfg(feature = "proptest")]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TraceTableShape {
+    pub num_main_columns: usize,
+    pub num_aux_columns: usize,
+    pub step_size: usize,
+    /// Range of the base 2 logarithm of the number of rows.
+    pub log_num_rows: core::ops::RangeInclusive<u32>,
+}
+
+#[cfg(feature = "proptest")]
+impl Default for TraceTableShape {
+    fn default() -> Self {
+        Self {
+            num_main_columns: 1,
+            num_aux_columns: 0,
+            step_size: 1,
+            log_num_rows: 1..=6,
+        }
+    }
+}
+
+/// Generates traces of the given shape filled with random elements. They don't satisfy the
+/// constraints of any AIR in particular.
+#[cfg(feature = "proptest")]
+impl<F: IsField + 'static> Arbitrary for TraceTable<F>
+where
+    FieldElement<F>: Arbitrary,
+{
+    type Parameters = TraceTableShape;
+
+    fn arbitrary_with(shape: Self::Parameters) -> Self::Strategy {
+        let num_columns = shape.num_main_columns + shape.num_aux_columns;
+        shape
+            .log_num_rows
+            .clone()
+            .prop_flat_map(move |log_num_rows| {
+                collection::vec(any::<FieldElement<F>>(), num_columns << log_num_rows)
+            })
+            .prop_map(move |data| {
+                TraceTable::new(
+                    data,
+                    shape.num_main_columns,
+                    shape.num_aux_columns,
+                    shape.step_size,
+                )
+            })
+            .boxed()
+    }
+
+    type Strategy = BoxedStrategy<Self>;
+}
+/// The order in which an [`LDETraceTable`] stores the rows of the LDE, chosen with the
+/// `lde_layout` of the [`ProofOptions`](crate::proof::options::ProofOptions). All of them give the
+/// same proofs.
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum LDELayout {
+    /// The rows in the order of the LDE domain. The rows of a frame are `blowup_factor` rows
+    /// apart.
+    #[default]
+    Natural,
+    /// The rows of each coset of the trace domain one after the other: the rows `i`,
+    /// `i + blowup_factor`, `i + 2 * blowup_factor`... are stored next to each other, so the
+    /// rows of a frame are contiguous.
+    Coset,
+    /// The prover doesn't keep the LDE: it evaluates the trace polynomials on one coset of the
+    /// trace domain at a time to commit to them and to evaluate the constraints, and again on the
+    /// cosets of the rows it opens. It holds the evaluations of a coset, as many as the trace,
+    /// instead of `blowup_factor` times as many. The tables of each coset have their rows in
+    /// order.
+    Streaming,
+}
+
 pub struct LDETraceTable<F, E>
 where
     E: IsField,
@@ -193,6 +278,7 @@ where
     pub(crate) aux_table: Table<E>,
     pub(crate) lde_step_size: usize,
     pub(crate) blowup_factor: usize,
+    pub(crate) layout: LDELayout,
 }
 
 impl<F, E> LDETraceTable<F, E>
@@ -216,6 +302,7 @@ where
             aux_table,
             lde_step_size,
             blowup_factor,
+            layout: LDELayout::Natural,
         }
     }
 
@@ -225,15 +312,71 @@ where
         trace_step_size: usize,
         blowup_factor: usize,
     ) -> Self {
-        let main_table = Table::from_columns(main_columns);
-        let aux_table = Table::from_columns(aux_columns);
-        let lde_step_size = trace_step_size * blowup_factor;
+        Self::from_columns_with_layout(
+            main_columns,
+            aux_columns,
+            trace_step_size,
+            blowup_factor,
+            LDELayout::Natural,
+        )
+    }
 
-        Self {
-            main_table,
-            aux_table,
-            lde_step_size,
+    /// Creates the table of the LDE given by its columns, storing its rows in `layout`.
+    pub fn from_columns_with_layout(
+        main_columns: Vec<Vec<FieldElement<F>>>,
+        aux_columns: Vec<Vec<FieldElement<E>>>,
+        trace_step_size: usize,
+        blowup_factor: usize,
+        layout: LDELayout,
+    ) -> Self {
+        let mut table = Self {
+            main_table: Table::new(Vec::new(), 0),
+            aux_table: Table::new(Vec::new(), 0),
+            lde_step_size: trace_step_size * blowup_factor,
             blowup_factor,
+            layout,
+        };
+        table.main_table = table.table_from_columns(main_columns);
+        table.aux_table = table.table_from_columns(aux_columns);
+        table
+    }
+
+    /// The table of `columns` with its rows in the layout of this one.
+    fn table_from_columns<T: IsField>(&self, columns: Vec<Vec<FieldElement<T>>>) -> Table<T> {
+        if self.layout != LDELayout::Coset || columns.is_empty() {
+            return Table::from_columns(columns);
+        }
+        let height = columns[0].len();
+        let data = (0..height)
+            .flat_map(|index| {
+                let row = self.row_at(index, height);
+                columns.iter().map(move |column| column[row].clone())
+            })
+            .collect();
+        Table::new(data, columns.len())
+    }
+
+    /// The index where the row `row` is stored. The number of rows and the blowup factor are
+    /// powers of two, so the rows of a coset are indexed with shifts.
+    pub(crate) fn storage_index(&self, row: usize) -> usize {
+        match self.layout {
+            LDELayout::Natural | LDELayout::Streaming => row,
+            LDELayout::Coset => {
+                let log_blowup_factor = self.blowup_factor.trailing_zeros();
+                let log_coset_size = self.num_rows().trailing_zeros() - log_blowup_factor;
+                ((row & (self.blowup_factor - 1)) << log_coset_size) | (row >> log_blowup_factor)
+            }
+        }
+    }
+
+    /// The row stored at `index`, in a table of `num_rows` rows.
+    fn row_at(&self, index: usize, num_rows: usize) -> usize {
+        match self.layout {
+            LDELayout::Natural | LDELayout::Streaming => index,
+            LDELayout::Coset => {
+                let coset_size = num_rows / self.blowup_factor;
+                (index % coset_size) * self.blowup_factor + index / coset_size
+            }
         }
     }
 
@@ -246,19 +389,19 @@ where
     }
 
     pub fn get_main_row(&self, row_idx: usize) -> &[FieldElement<F>] {
-        self.main_table.get_row(row_idx)
+        self.main_table.get_row(self.storage_index(row_idx))
     }
 
     pub fn get_aux_row(&self, row_idx: usize) -> &[FieldElement<E>] {
-        self.aux_table.get_row(row_idx)
+        self.aux_table.get_row(self.storage_index(row_idx))
     }
 
     pub fn get_main(&self, row: usize, col: usize) -> &FieldElement<F> {
-        self.main_table.get(row, col)
+        self.main_table.get(self.storage_index(row), col)
     }
 
     pub fn get_aux(&self, row: usize, col: usize) -> &FieldElement<E> {
-        self.aux_table.get(row, col)
+        self.aux_table.get(self.storage_index(row), col)
     }
 
     pub fn num_steps(&self) -> usize {
@@ -349,7 +492,7 @@ pub fn columns2rows<F: IsField>(columns: Vec<Vec<FieldElement<F>>>) -> Vec<Vec<F
 
 #[cfg(test)]
 mod test {
-    use super::TraceTable;
+    use super::{LDELayout, LDETraceTable, TraceTable};
     use lambdaworks_math::field::{element::FieldElement, fields::u64_prime_field::F17};
     type FE = FieldElement<F17>;
 
@@ -363,4 +506,46 @@ mod test {
 
         assert_eq!(res_cols, vec![col_1, col_2]);
     }
+
+    #[test]
+    fn lde_tables_read_the_same_rows_in_each_layout() {
+        let main_columns: Vec<Vec<FE>> = (0..3u64)
+            .map(|col| (0..16u64).map(|row| FE::from(col * 16 + row)).collect())
+            .collect();
+        let aux_columns = vec![(0..16u64).map(FE::from).collect()];
+        let natural = LDETraceTable::from_columns(main_columns.clone(), aux_columns.clone(), 2, 4);
+        let coset = LDETraceTable::from_columns_with_layout(
+            main_columns,
+            aux_columns,
+            2,
+            4,
+            LDELayout::Coset,
+        );
+
+        for row in 0..16 {
+            assert_eq!(natural.get_main_row(row), coset.get_main_row(row));
+            assert_eq!(natural.get_aux_row(row), coset.get_aux_row(row));
+        }
+        // The coset of 1 has the rows 1, 5, 9 and 13, stored from the index 4.
+        assert_eq!(coset.main_table.get_row(6), natural.get_main_row(9));
+    }
+
+    #[cfg(feature = "proptest")]
+    proptest::proptest! {
+        #[test]
+        fn arbitrary_traces_have_the_requested_shape(
+            trace_table in proptest::prelude::any_with::<TraceTable<F17>>(super::TraceTableShape {
+                num_main_columns: 2,
+                num_aux_columns: 1,
+                step_size: 1,
+                log_num_rows: 2..=4,
+            })
+        ) {
+            proptest::prop_assert_eq!(trace_table.num_main_columns, 2);
+            proptest::prop_assert_eq!(trace_table.num_aux_columns, 1);
+            proptest::prop_assert_eq!(trace_table.table.width, 3);
+            proptest::prop_assert!(trace_table.n_rows().is_power_of_two());
+            proptest::prop_assert!((4..=16).contains(&trace_table.n_rows()));
+        }
+    }
 }
@@ -9,7 +9,10 @@ use lambdaworks_math::{
     polynomial::Polynomial,
 };
 
-use crate::{constraints::transition::TransitionConstraint, domain::Domain};
+use crate::{
+    constraints::{program::ConstraintProgram, transition::TransitionConstraint},
+    domain::Domain,
+};
 
 use super::{
     constraints::boundary::BoundaryConstraints, context::AirContext, frame::Frame,
@@ -47,12 +50,57 @@ pub trait AIR {
         Vec::new()
     }
 
+    /// Samples the challenges of the interaction round `round` of the RAP. By default, the AIR
+    /// has a single round with the challenges of [`Self::build_rap_challenges`].
+    fn build_rap_challenges_for_round(
+        &self,
+        round: usize,
+        transcript: &mut impl IsTranscript<Self::FieldExtension>,
+    ) -> Vec<FieldElement<Self::FieldExtension>> {
+        if round == 0 {
+            self.build_rap_challenges(transcript)
+        } else {
+            Vec::new()
+        }
+    }
+
+    /// Builds the auxiliary columns of the interaction round `round` of the RAP, given the
+    /// auxiliary columns of the previous rounds in `aux_trace` and the challenges sampled up to
+    /// this round. By default, the AIR has a single round with the columns of
+    /// [`Self::build_auxiliary_trace`].
+    fn build_auxiliary_trace_for_round(
+        &self,
+        round: usize,
+        main_trace: &TraceTable<Self::Field>,
+        _aux_trace: &TraceTable<Self::FieldExtension>,
+        rap_challenges: &[FieldElement<Self::FieldExtension>],
+    ) -> TraceTable<Self::FieldExtension> {
+        if round == 0 {
+            self.build_auxiliary_trace(main_trace, rap_challenges)
+        } else {
+            TraceTable::empty()
+        }
+    }
+
     fn trace_layout(&self) -> (usize, usize);
 
     fn num_auxiliary_rap_columns(&self) -> usize {
         self.trace_layout().1
     }
 
+    /// The number of auxiliary columns built in each interaction round of the RAP, which add up
+    /// to [`Self::num_auxiliary_rap_columns`]. The columns of each round with any are committed
+    /// before sampling the challenges of the next one.
+    fn num_auxiliary_rap_columns_per_round(&self) -> Vec<usize> {
+        vec![self.num_auxiliary_rap_columns()]
+    }
+
+    /// The number of preprocessed columns, which are the first columns of the main trace. See
+    /// [`crate::preprocessing`].
+    fn num_preprocessed_columns(&self) -> usize {
+        0
+    }
+
     fn composition_poly_degree_bound(&self) -> usize;
 
     /// The method called by the prover to evaluate the transitions corresponding to an evaluation frame.
@@ -73,6 +121,13 @@ pub trait AIR {
         evaluations
     }
 
+    /// The transition constraints as a [`ConstraintProgram`], which GPU backends interpret over
+    /// the whole LDE domain instead of calling [`Self::compute_transition_prover`] on each frame.
+    /// AIRs without one are evaluated in the CPU.
+    fn transition_program(&self) -> Option<ConstraintProgram<Self::FieldExtension>> {
+        None
+    }
+
     fn boundary_constraints(
         &self,
         rap_challenges: &[FieldElement<Self::FieldExtension>],
@@ -176,7 +231,9 @@ pub trait AIR {
             );
             zerofier_groups
                 .entry(zerofier_group_key)
-                .or_insert_with(|| c.zerofier_evaluations_on_extended_domain(domain));
+                .or_insert_with(|| {
+                    c.zerofier_evaluations_on_extended_domain(domain, Self::STEP_SIZE)
+                });
 
             let zerofier_evaluations = zerofier_groups.get(&zerofier_group_key).unwrap();
             evals[c.constraint_idx()].clone_from(zerofier_evaluations);
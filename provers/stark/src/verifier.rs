@@ -1,26 +1,27 @@
 use super::{
-    config::BatchedMerkleTreeBackend,
+    config::MerkleHash,
     domain::Domain,
-    fri::fri_decommit::FriDecommitment,
+    fri::{self, fri_decommit::FriDecommitment},
     grinding,
     proof::{options::ProofOptions, stark::StarkProof},
     traits::AIR,
 };
 use crate::{config::Commitment, proof::stark::DeepPolynomialOpening};
 use lambdaworks_crypto::{fiat_shamir::is_transcript::IsTranscript, merkle_tree::proof::Proof};
+#[cfg(feature = "instruments")]
+use lambdaworks_math::environment::Stopwatch;
 use lambdaworks_math::{
     fft::cpu::bit_reversing::reverse_index,
     field::{
         element::FieldElement,
         traits::{IsFFTField, IsField, IsSubFieldOf},
     },
+    polynomial::Polynomial,
     traits::AsBytes,
 };
 #[cfg(not(feature = "test_fiat_shamir"))]
 use log::error;
 use std::marker::PhantomData;
-#[cfg(feature = "instruments")]
-use std::time::Instant;
 
 /// A default STARK verifier implementing `IsStarkVerifier`.
 pub struct Verifier<A: AIR> {
@@ -47,7 +48,7 @@ where
     pub gammas: Vec<FieldElement<A::FieldExtension>>,
     /// The list of FRI commit phase folding challenges.
     pub zetas: Vec<FieldElement<A::FieldExtension>>,
-    /// The list of FRI query phase index challenges.
+    /// The list of FRI query phase index challenges, without repetitions.
     pub iotas: Vec<usize>,
     /// The challenges used to build the auxiliary trace.
     pub rap_challenges: Vec<FieldElement<A::FieldExtension>>,
@@ -60,21 +61,25 @@ pub type DeepPolynomialEvaluations<F> = (Vec<FieldElement<F>>, Vec<FieldElement<
 /// The functionality of a STARK verifier providing methods to run the STARK Verify protocol
 /// https://lambdaclass.github.io/lambdaworks/starks/protocol.html
 pub trait IsStarkVerifier<A: AIR> {
+    /// Samples the indexes of the FRI queries, without repetitions. See
+    /// [`fri::sample_query_indexes`].
     fn sample_query_indexes(
         number_of_queries: usize,
         domain: &Domain<A::Field>,
         transcript: &mut impl IsTranscript<A::FieldExtension>,
     ) -> Vec<usize> {
-        let domain_size = domain.lde_roots_of_unity_coset.len() as u64;
-        (0..number_of_queries)
-            .map(|_| (transcript.sample_u64(domain_size >> 1)) as usize)
-            .collect::<Vec<usize>>()
+        fri::sample_query_indexes(
+            number_of_queries,
+            domain.lde_roots_of_unity_coset.len(),
+            transcript,
+        )
     }
 
     /// Returns the list of challenges sent to the prover.
     fn step_1_replay_rounds_and_recover_challenges(
         air: &A,
         proof: &StarkProof<A::Field, A::FieldExtension>,
+        preprocessed_commitment: Option<&Commitment>,
         domain: &Domain<A::Field>,
         transcript: &mut impl IsTranscript<A::FieldExtension>,
     ) -> Challenges<A>
@@ -86,13 +91,27 @@ pub trait IsStarkVerifier<A: AIR> {
         // ==========|   Round 1   |==========
         // ===================================
 
+        if let Some(commitment) = preprocessed_commitment {
+            transcript.append_bytes(commitment);
+        }
+
         // <<<< Receive commitments:[tⱼ]
         transcript.append_bytes(&proof.lde_trace_main_merkle_root);
 
-        let rap_challenges = air.build_rap_challenges(transcript);
+        let mut rap_challenges = Vec::new();
+        let mut aux_roots = proof.lde_trace_aux_merkle_roots.iter();
+        for (round, num_columns) in air
+            .num_auxiliary_rap_columns_per_round()
+            .into_iter()
+            .enumerate()
+        {
+            rap_challenges.extend(air.build_rap_challenges_for_round(round, transcript));
 
-        if let Some(root) = proof.lde_trace_aux_merkle_root {
-            transcript.append_bytes(&root);
+            if num_columns > 0 {
+                if let Some(root) = aux_roots.next() {
+                    transcript.append_bytes(root);
+                }
+            }
         }
 
         // ===================================
@@ -141,7 +160,9 @@ pub trait IsStarkVerifier<A: AIR> {
         // ==========|   Round 4   |==========
         // ===================================
 
-        let n_terms_composition_poly = proof.composition_poly_parts_ood_evaluation.len();
+        // In zero-knowledge proofs, the random polynomial committed with the parts has a term too.
+        let n_terms_composition_poly =
+            proof.composition_poly_parts_ood_evaluation.len() + usize::from(air.options().zk);
         let n_terms_trace = air.context().transition_offsets.len() * air.context().trace_columns;
         let gamma = transcript.sample_field_element();
 
@@ -177,8 +198,10 @@ pub trait IsStarkVerifier<A: AIR> {
         // >>>> Send challenge 𝜁ₙ₋₁
         zetas.push(transcript.sample_field_element());
 
-        // <<<< Receive value: pₙ
-        transcript.append_field_element(&proof.fri_last_value);
+        // <<<< Receive coefficients: pₙ
+        for coefficient in proof.fri_last_layer_coefficients.iter() {
+            transcript.append_field_element(coefficient);
+        }
 
         // Receive grinding value
         let security_bits = air.context().proof_options.grinding_factor;
@@ -282,8 +305,12 @@ pub trait IsStarkVerifier<A: AIR> {
         let mut denominators =
             vec![FieldElement::<A::FieldExtension>::zero(); air.num_transition_constraints()];
         air.transition_constraints().iter().for_each(|c| {
-            denominators[c.constraint_idx()] =
-                c.evaluate_zerofier(&challenges.z, &domain.trace_primitive_root, trace_length);
+            denominators[c.constraint_idx()] = c.evaluate_zerofier(
+                &challenges.z,
+                &domain.trace_primitive_root,
+                trace_length,
+                A::STEP_SIZE,
+            );
         });
 
         let transition_c_i_evaluations_sum = itertools::izip!(
@@ -327,31 +354,39 @@ pub trait IsStarkVerifier<A: AIR> {
             );
 
         // verify FRI
-        let mut evaluation_point_inverse = challenges
+        let evaluation_points = challenges
             .iotas
             .iter()
             .map(|iota| Self::query_challenge_to_evaluation_point(*iota, domain))
             .collect::<Vec<FieldElement<A::Field>>>();
+        let mut evaluation_point_inverse = evaluation_points.clone();
         FieldElement::inplace_batch_inverse(&mut evaluation_point_inverse).unwrap();
 
+        let last_layer = Polynomial::new(&proof.fri_last_layer_coefficients);
+
         proof
             .query_list
             .iter()
             .zip(&challenges.iotas)
-            .zip(evaluation_point_inverse)
+            .zip(evaluation_points.iter().zip(evaluation_point_inverse))
             .enumerate()
-            .fold(true, |mut result, (i, ((proof_s, iota_s), eval))| {
-                result &= Self::verify_query_and_sym_openings(
-                    proof,
-                    &challenges.zetas,
-                    *iota_s,
-                    proof_s,
-                    eval,
-                    &deep_poly_evaluations[i],
-                    &deep_poly_evaluations_sym[i],
-                );
-                result
-            })
+            .fold(
+                true,
+                |mut result, (i, ((proof_s, iota_s), (eval_point, eval_inv)))| {
+                    result &= Self::verify_query_and_sym_openings(
+                        proof,
+                        &challenges.zetas,
+                        *iota_s,
+                        proof_s,
+                        eval_point,
+                        eval_inv,
+                        &last_layer,
+                        &deep_poly_evaluations[i],
+                        &deep_poly_evaluations_sym[i],
+                    );
+                    result
+                },
+            )
     }
 
     /// Returns the field element element of the domain `domain` corresponding to the given FRI query index challenge `iota`.
@@ -374,12 +409,15 @@ pub trait IsStarkVerifier<A: AIR> {
         .clone()
     }
 
-    /// Verifies the validity of the opening proof.
+    /// Verifies the validity of the opening proof of the leaf with `value`, followed by `salt`, in
+    /// a Merkle tree with hash `merkle_hash`.
     fn verify_opening<E>(
+        merkle_hash: MerkleHash,
         proof: &Proof<Commitment>,
         root: &Commitment,
         index: usize,
         value: &[FieldElement<E>],
+        salt: &[FieldElement<E>],
     ) -> bool
     where
         FieldElement<A::Field>: AsBytes + Sync + Send,
@@ -387,13 +425,14 @@ pub trait IsStarkVerifier<A: AIR> {
         E: IsField,
         A::Field: IsSubFieldOf<E>,
     {
-        proof.verify::<BatchedMerkleTreeBackend<E>>(root, index, &value.to_owned())
+        merkle_hash.verify(proof, root, index, &[value, salt].concat())
     }
 
     /// Verify opening Open(tⱼ(D_LDE), 𝜐) and Open(tⱼ(D_LDE), -𝜐) for all trace polynomials tⱼ,
     /// where 𝜐 and -𝜐 are the elements corresponding to the index challenge `iota`.
     fn verify_trace_openings(
         proof: &StarkProof<A::Field, A::FieldExtension>,
+        preprocessed_commitment: Option<&Commitment>,
         deep_poly_openings: &DeepPolynomialOpening<A::Field, A::FieldExtension>,
         iota: usize,
     ) -> bool
@@ -405,40 +444,73 @@ pub trait IsStarkVerifier<A: AIR> {
         let index_sym = iota * 2 + 1;
         let mut result = true;
 
+        match (
+            preprocessed_commitment,
+            &deep_poly_openings.preprocessed_trace_polys,
+        ) {
+            (Some(commitment), Some(preprocessed_trace_polys_opening)) => {
+                result &= Self::verify_opening::<A::Field>(
+                    proof.merkle_hash,
+                    &preprocessed_trace_polys_opening.proof,
+                    commitment,
+                    index,
+                    &preprocessed_trace_polys_opening.evaluations,
+                    &[],
+                );
+                result &= Self::verify_opening::<A::Field>(
+                    proof.merkle_hash,
+                    &preprocessed_trace_polys_opening.proof_sym,
+                    commitment,
+                    index_sym,
+                    &preprocessed_trace_polys_opening.evaluations_sym,
+                    &[],
+                );
+            }
+            (None, None) => {}
+            _ => result = false,
+        }
+
         result &= Self::verify_opening::<A::Field>(
+            proof.merkle_hash,
             &deep_poly_openings.main_trace_polys.proof,
             &proof.lde_trace_main_merkle_root,
             index,
             &deep_poly_openings.main_trace_polys.evaluations,
+            &deep_poly_openings.main_trace_polys.salt,
         );
         result &= Self::verify_opening::<A::Field>(
+            proof.merkle_hash,
             &deep_poly_openings.main_trace_polys.proof_sym,
             &proof.lde_trace_main_merkle_root,
             index_sym,
             &deep_poly_openings.main_trace_polys.evaluations_sym,
+            &deep_poly_openings.main_trace_polys.salt_sym,
         );
 
-        match (
-            proof.lde_trace_aux_merkle_root,
-            &deep_poly_openings.aux_trace_polys,
-        ) {
-            (None, Some(_)) => result = false,
-            (Some(_), None) => result = false,
-            (Some(aux_root), Some(aux_trace_polys_opening)) => {
-                result &= Self::verify_opening::<A::FieldExtension>(
-                    &aux_trace_polys_opening.proof,
-                    &aux_root,
-                    index,
-                    &aux_trace_polys_opening.evaluations,
-                );
-                result &= Self::verify_opening::<A::FieldExtension>(
-                    &aux_trace_polys_opening.proof_sym,
-                    &aux_root,
-                    index_sym,
-                    &aux_trace_polys_opening.evaluations_sym,
-                );
-            }
-            _ => {}
+        if proof.lde_trace_aux_merkle_roots.len() != deep_poly_openings.aux_trace_polys.len() {
+            return false;
+        }
+        for (aux_root, aux_trace_polys_opening) in proof
+            .lde_trace_aux_merkle_roots
+            .iter()
+            .zip(&deep_poly_openings.aux_trace_polys)
+        {
+            result &= Self::verify_opening::<A::FieldExtension>(
+                proof.merkle_hash,
+                &aux_trace_polys_opening.proof,
+                aux_root,
+                index,
+                &aux_trace_polys_opening.evaluations,
+                &aux_trace_polys_opening.salt,
+            );
+            result &= Self::verify_opening::<A::FieldExtension>(
+                proof.merkle_hash,
+                &aux_trace_polys_opening.proof_sym,
+                aux_root,
+                index_sym,
+                &aux_trace_polys_opening.evaluations_sym,
+                &aux_trace_polys_opening.salt_sym,
+            );
         }
 
         result
@@ -447,6 +519,7 @@ pub trait IsStarkVerifier<A: AIR> {
     /// Verify opening Open(Hᵢ(D_LDE), 𝜐) and Open(Hᵢ(D_LDE), -𝜐) for all parts Hᵢof the composition
     /// polynomial, where 𝜐 and -𝜐 are the elements corresponding to the index challenge `iota`.
     fn verify_composition_poly_opening(
+        merkle_hash: MerkleHash,
         deep_poly_openings: &DeepPolynomialOpening<A::Field, A::FieldExtension>,
         composition_poly_merkle_root: &Commitment,
         iota: &usize,
@@ -455,17 +528,16 @@ pub trait IsStarkVerifier<A: AIR> {
         FieldElement<A::Field>: AsBytes + Sync + Send,
         FieldElement<A::FieldExtension>: AsBytes + Sync + Send,
     {
-        let mut value = deep_poly_openings.composition_poly.evaluations.clone();
-        value.extend_from_slice(&deep_poly_openings.composition_poly.evaluations_sym);
-
-        deep_poly_openings
-            .composition_poly
-            .proof
-            .verify::<BatchedMerkleTreeBackend<A::FieldExtension>>(
-                composition_poly_merkle_root,
-                *iota,
-                &value,
-            )
+        // The evaluations at both points are in the same leaf, so they have the same salt.
+        let opening = &deep_poly_openings.composition_poly;
+        if opening.salt != opening.salt_sym {
+            return false;
+        }
+        let mut value = opening.evaluations.clone();
+        value.extend_from_slice(&opening.evaluations_sym);
+        value.extend_from_slice(&opening.salt);
+
+        merkle_hash.verify(&opening.proof, composition_poly_merkle_root, *iota, &value)
     }
 
     /// Verifies the validity of the purported values of the trace polynomials and the composition polynomial
@@ -473,6 +545,7 @@ pub trait IsStarkVerifier<A: AIR> {
     /// index challenges.
     fn step_4_verify_trace_and_composition_openings(
         proof: &StarkProof<A::Field, A::FieldExtension>,
+        preprocessed_commitment: Option<&Commitment>,
         challenges: &Challenges<A>,
     ) -> bool
     where
@@ -483,56 +556,42 @@ pub trait IsStarkVerifier<A: AIR> {
             true,
             |mut result, (iota_n, deep_poly_opening)| {
                 result &= Self::verify_composition_poly_opening(
+                    proof.merkle_hash,
                     deep_poly_opening,
                     &proof.composition_poly_root,
                     iota_n,
                 );
 
-                result &= Self::verify_trace_openings(proof, deep_poly_opening, *iota_n);
+                result &= Self::verify_trace_openings(
+                    proof,
+                    preprocessed_commitment,
+                    deep_poly_opening,
+                    *iota_n,
+                );
                 result
             },
         )
     }
 
-    /// Verifies the openings of a fold polynomial of an inner layer of FRI.
-    fn verify_fri_layer_openings(
-        merkle_root: &Commitment,
-        auth_path_sym: &Proof<Commitment>,
-        evaluation: &FieldElement<A::FieldExtension>,
-        evaluation_sym: &FieldElement<A::FieldExtension>,
-        iota: usize,
-    ) -> bool
-    where
-        FieldElement<A::Field>: AsBytes + Sync + Send,
-        FieldElement<A::FieldExtension>: AsBytes + Sync + Send,
-    {
-        let evaluations = if iota % 2 == 1 {
-            vec![evaluation_sym.clone(), evaluation.clone()]
-        } else {
-            vec![evaluation.clone(), evaluation_sym.clone()]
-        };
-
-        auth_path_sym.verify::<BatchedMerkleTreeBackend<A::FieldExtension>>(
-            merkle_root,
-            iota >> 1,
-            &evaluations,
-        )
-    }
-
     /// Verify a single FRI query
     /// `zetas`: the vector of all challenges sent by the verifier to the prover at the commit
     /// phase to fold polynomials.
     /// `iota`: the index challenge of this FRI query. This index uniquely determines two elements 𝜐 and -𝜐
     /// of the evaluation domain of FRI layer 0.
+    /// `evaluation_point`: the value 𝜐.
     /// `evaluation_point_inv`: precomputed value of 𝜐⁻¹.
+    /// `last_layer`: the last layer pₙ of FRI, whose coefficients are given by the prover.
     /// `deep_composition_evaluation`: precomputed value of p₀(𝜐), where p₀ is the deep composition polynomial.
     /// `deep_composition_evaluation_sym`: precomputed value of p₀(-𝜐), where p₀ is the deep composition polynomial.
+    #[allow(clippy::too_many_arguments)]
     fn verify_query_and_sym_openings(
         proof: &StarkProof<A::Field, A::FieldExtension>,
         zetas: &[FieldElement<A::FieldExtension>],
         iota: usize,
         fri_decommitment: &FriDecommitment<A::FieldExtension>,
+        evaluation_point: &FieldElement<A::Field>,
         evaluation_point_inv: FieldElement<A::Field>,
+        last_layer: &Polynomial<FieldElement<A::FieldExtension>>,
         deep_composition_evaluation: &FieldElement<A::FieldExtension>,
         deep_composition_evaluation_sym: &FieldElement<A::FieldExtension>,
     ) -> bool
@@ -540,65 +599,18 @@ pub trait IsStarkVerifier<A: AIR> {
         FieldElement<A::Field>: AsBytes + Sync + Send,
         FieldElement<A::FieldExtension>: AsBytes + Sync + Send,
     {
-        let fri_layers_merkle_roots = &proof.fri_layers_merkle_roots;
-        let evaluation_point_vec: Vec<FieldElement<A::Field>> =
-            core::iter::successors(Some(evaluation_point_inv.square()), |evaluation_point| {
-                Some(evaluation_point.square())
-            })
-            .take(fri_layers_merkle_roots.len())
-            .collect();
-
-        let p0_eval = deep_composition_evaluation;
-        let p0_eval_sym = deep_composition_evaluation_sym;
-
-        // Reconstruct p₁(𝜐²)
-        let mut v =
-            (p0_eval + p0_eval_sym) + evaluation_point_inv * &zetas[0] * (p0_eval - p0_eval_sym);
-        let mut index = iota;
-
-        // For each FRI layer, starting from the layer 1: use the proof to verify the validity of values pᵢ(−𝜐^(2ⁱ)) (given by the prover) and
-        // pᵢ(𝜐^(2ⁱ)) (computed on the previous iteration by the verifier). Then use them to obtain pᵢ₊₁(𝜐^(2ⁱ⁺¹)).
-        // Finally, check that the final value coincides with the given by the prover.
-        fri_layers_merkle_roots
-            .iter()
-            .enumerate()
-            .zip(&fri_decommitment.layers_auth_paths)
-            .zip(&fri_decommitment.layers_evaluations_sym)
-            .zip(evaluation_point_vec)
-            .fold(
-                true,
-                |result,
-                 (
-                    (((i, merkle_root), auth_path_sym), evaluation_sym),
-                    evaluation_point_inv,
-                )| {
-                    // Verify opening Open(pᵢ(Dₖ), −𝜐^(2ⁱ)) and Open(pᵢ(Dₖ), 𝜐^(2ⁱ)).
-                    // `v` is pᵢ(𝜐^(2ⁱ)).
-                    // `evaluation_sym` is pᵢ(−𝜐^(2ⁱ)).
-                    let openings_ok = Self::verify_fri_layer_openings(
-                        merkle_root,
-                        auth_path_sym,
-                        &v,
-                        evaluation_sym,
-                        index,
-                    );
-
-                    // Update `v` with next value pᵢ₊₁(𝜐^(2ⁱ⁺¹)).
-                    v = (&v + evaluation_sym) + evaluation_point_inv * &zetas[i + 1] * (&v - evaluation_sym);
-
-                    // Update index for next iteration. The index of the squares in the next layer
-                    // is obtained by halving the current index. This is due to the bit-reverse
-                    // ordering of the elements in the Merkle tree.
-                    index >>= 1;
-
-                    if i < fri_decommitment.layers_evaluations_sym.len() - 1 {
-                        result & openings_ok
-                    } else {
-                        // Check that final value is the given by the prover
-                        result & (v == proof.fri_last_value) & openings_ok
-                    }
-                },
-            )
+        fri::verify_query(
+            proof.merkle_hash,
+            &proof.fri_layers_merkle_roots,
+            last_layer,
+            zetas,
+            iota,
+            fri_decommitment,
+            evaluation_point,
+            &evaluation_point_inv,
+            deep_composition_evaluation,
+            deep_composition_evaluation_sym,
+        )
     }
 
     fn reconstruct_deep_composition_poly_evaluations_for_all_queries(
@@ -612,14 +624,14 @@ pub trait IsStarkVerifier<A: AIR> {
             let primitive_root =
                 &A::Field::get_primitive_root_of_unity(domain.root_order as u64).unwrap();
 
-            let mut evaluations: Vec<FieldElement<A::FieldExtension>> = proof.deep_poly_openings[i]
-                .main_trace_polys
-                .evaluations
-                .clone()
-                .into_iter()
-                .map(|x| x.to_extension())
+            let preprocessed_trace_polys = &proof.deep_poly_openings[i].preprocessed_trace_polys;
+            let mut evaluations: Vec<FieldElement<A::FieldExtension>> = preprocessed_trace_polys
+                .iter()
+                .flat_map(|opening| opening.evaluations.iter())
+                .chain(&proof.deep_poly_openings[i].main_trace_polys.evaluations)
+                .map(|x| x.clone().to_extension())
                 .collect();
-            if let Some(aux_trace_polys) = &proof.deep_poly_openings[i].aux_trace_polys {
+            for aux_trace_polys in &proof.deep_poly_openings[i].aux_trace_polys {
                 evaluations.extend_from_slice(&aux_trace_polys.evaluations);
             }
 
@@ -633,15 +645,14 @@ pub trait IsStarkVerifier<A: AIR> {
                 &proof.deep_poly_openings[i].composition_poly.evaluations,
             ));
 
-            let mut evaluations_sym: Vec<FieldElement<A::FieldExtension>> = proof
-                .deep_poly_openings[i]
-                .main_trace_polys
-                .evaluations_sym
-                .clone()
-                .into_iter()
-                .map(|x| x.to_extension())
-                .collect();
-            if let Some(aux_trace_polys) = &proof.deep_poly_openings[i].aux_trace_polys {
+            let mut evaluations_sym: Vec<FieldElement<A::FieldExtension>> =
+                preprocessed_trace_polys
+                    .iter()
+                    .flat_map(|opening| opening.evaluations_sym.iter())
+                    .chain(&proof.deep_poly_openings[i].main_trace_polys.evaluations_sym)
+                    .map(|x| x.clone().to_extension())
+                    .collect();
+            for aux_trace_polys in &proof.deep_poly_openings[i].aux_trace_polys {
                 evaluations_sym.extend_from_slice(&aux_trace_polys.evaluations_sym);
             }
 
@@ -686,7 +697,11 @@ pub trait IsStarkVerifier<A: AIR> {
                 trace_terms + trace_i
             });
 
-        let number_of_parts = lde_composition_poly_parts_evaluation.len();
+        // In zero-knowledge proofs, the evaluation of the random polynomial committed with the
+        // parts follows theirs.
+        let number_of_parts = proof.composition_poly_parts_ood_evaluation.len();
+        let (lde_composition_poly_parts_evaluation, randomizer_evaluation) =
+            lde_composition_poly_parts_evaluation.split_at(number_of_parts);
         let z_pow = &challenges.z.pow(number_of_parts);
 
         let denom_composition = (evaluation_point - z_pow).inv().unwrap();
@@ -697,6 +712,9 @@ pub trait IsStarkVerifier<A: AIR> {
             h_terms += h_i_term;
         }
         h_terms *= denom_composition;
+        for randomizer_upsilon in randomizer_evaluation {
+            h_terms += randomizer_upsilon * &challenges.gammas[number_of_parts];
+        }
 
         trace_term + h_terms
     }
@@ -707,32 +725,125 @@ pub trait IsStarkVerifier<A: AIR> {
         proof: &StarkProof<A::Field, A::FieldExtension>,
         pub_input: &A::PublicInputs,
         proof_options: &ProofOptions,
-        mut transcript: impl IsTranscript<A::FieldExtension>,
+        transcript: impl IsTranscript<A::FieldExtension>,
     ) -> bool
     where
         FieldElement<A::Field>: AsBytes + Sync + Send,
         FieldElement<A::FieldExtension>: AsBytes + Sync + Send,
     {
-        // Verify there are enough queries
-        if proof.query_list.len() < proof_options.fri_number_of_queries {
-            return false;
-        }
+        Self::verify_with_preprocessed_commitment(proof, None, pub_input, proof_options, transcript)
+    }
 
+    /// Like [`IsStarkVerifier::verify`], for an AIR with preprocessed columns committed to
+    /// `preprocessed_commitment`. See [`crate::preprocessing`].
+    fn verify_with_preprocessed_commitment(
+        proof: &StarkProof<A::Field, A::FieldExtension>,
+        preprocessed_commitment: Option<&Commitment>,
+        pub_input: &A::PublicInputs,
+        proof_options: &ProofOptions,
+        mut transcript: impl IsTranscript<A::FieldExtension>,
+    ) -> bool
+    where
+        FieldElement<A::Field>: AsBytes + Sync + Send,
+        FieldElement<A::FieldExtension>: AsBytes + Sync + Send,
+    {
         #[cfg(feature = "instruments")]
         println!("- Started step 1: Recover challenges");
         #[cfg(feature = "instruments")]
-        let timer1 = Instant::now();
+        let timer1 = Stopwatch::start();
 
         let air = A::new(proof.trace_length, pub_input, proof_options);
         let domain = Domain::new(&air);
 
+        if proof.merkle_hash != proof_options.merkle_hash {
+            error!("Merkle hash of the proof does not match the options");
+            return false;
+        }
+
+        let num_preprocessed_columns = air.num_preprocessed_columns();
+        if preprocessed_commitment.is_some() != (num_preprocessed_columns > 0) {
+            error!("Preprocessed commitment does not match the AIR");
+            return false;
+        }
+        let preprocessed_openings_are_valid = proof.deep_poly_openings.iter().all(|opening| {
+            opening
+                .preprocessed_trace_polys
+                .as_ref()
+                .map_or(0, |opening| opening.evaluations.len())
+                == num_preprocessed_columns
+        });
+        if !preprocessed_openings_are_valid {
+            error!("Openings of the preprocessed columns do not match the AIR");
+            return false;
+        }
+
+        // Each interaction round with columns has its commitment, and its openings hold the
+        // columns of that round.
+        let aux_columns_per_round: Vec<_> = air
+            .num_auxiliary_rap_columns_per_round()
+            .into_iter()
+            .filter(|num_columns| *num_columns > 0)
+            .collect();
+        let aux_openings_are_valid = proof.lde_trace_aux_merkle_roots.len()
+            == aux_columns_per_round.len()
+            && proof.deep_poly_openings.iter().all(|opening| {
+                opening.aux_trace_polys.len() == aux_columns_per_round.len()
+                    && opening
+                        .aux_trace_polys
+                        .iter()
+                        .zip(&aux_columns_per_round)
+                        .all(|(opening, num_columns)| opening.evaluations.len() == *num_columns)
+            });
+        if !aux_openings_are_valid {
+            error!("Auxiliary trace commitments do not match the interaction rounds of the AIR");
+            return false;
+        }
+
+        // Each opening of the composition polynomial has the evaluations of its parts, and in
+        // zero-knowledge proofs, of the random polynomial committed with them.
+        let num_composition_poly_columns =
+            proof.composition_poly_parts_ood_evaluation.len() + usize::from(proof_options.zk);
+        let composition_openings_are_valid = proof.deep_poly_openings.iter().all(|opening| {
+            opening.composition_poly.evaluations.len() == num_composition_poly_columns
+                && opening.composition_poly.evaluations_sym.len() == num_composition_poly_columns
+        });
+        if !composition_openings_are_valid {
+            error!("Openings of the composition polynomial do not match its parts");
+            return false;
+        }
+
+        // FRI stops at a last layer of the degree bound of the options, so the number of
+        // committed layers and of coefficients of the last one are fixed. In zero-knowledge
+        // proofs, the trace polynomials have twice the degree, so FRI folds once more.
+        let number_layers = domain.root_order as usize + usize::from(proof_options.zk);
+        let last_layer_degree_bound = proof_options.fri_last_layer_degree_bound;
+        let number_of_committed_layers =
+            fri::number_of_folds(number_layers, last_layer_degree_bound) - 1;
+        let fri_layers_are_valid = proof.fri_layers_merkle_roots.len()
+            == number_of_committed_layers
+            && proof.fri_last_layer_coefficients.len()
+                == fri::last_layer_len(number_layers, last_layer_degree_bound);
+        if !fri_layers_are_valid {
+            error!("FRI layers do not match the last layer degree bound of the options");
+            return false;
+        }
+
         let challenges = Self::step_1_replay_rounds_and_recover_challenges(
             &air,
             proof,
+            preprocessed_commitment,
             &domain,
             &mut transcript,
         );
 
+        // There is an opening for each query, without repetitions
+        if proof.query_list.len() != challenges.iotas.len()
+            || proof.deep_poly_openings.len() != challenges.iotas.len()
+        {
+            error!("Openings do not match the FRI queries");
+            return false;
+        }
+
         // verify grinding
         let security_bits = air.context().proof_options.grinding_factor;
         if security_bits > 0 {
@@ -754,7 +865,7 @@ pub trait IsStarkVerifier<A: AIR> {
         #[cfg(feature = "instruments")]
         println!("- Started step 2: Verify claimed polynomial");
         #[cfg(feature = "instruments")]
-        let timer2 = Instant::now();
+        let timer2 = Stopwatch::start();
 
         if !Self::step_2_verify_claimed_composition_polynomial(&air, proof, &domain, &challenges) {
             error!("Composition Polynomial verification failed");
@@ -769,7 +880,7 @@ pub trait IsStarkVerifier<A: AIR> {
 
         println!("- Started step 3: Verify FRI");
         #[cfg(feature = "instruments")]
-        let timer3 = Instant::now();
+        let timer3 = Stopwatch::start();
 
         if !Self::step_3_verify_fri(proof, &domain, &challenges) {
             error!("FRI verification failed");
@@ -784,10 +895,14 @@ pub trait IsStarkVerifier<A: AIR> {
         #[cfg(feature = "instruments")]
         println!("- Started step 4: Verify deep composition polynomial");
         #[cfg(feature = "instruments")]
-        let timer4 = Instant::now();
+        let timer4 = Stopwatch::start();
 
         #[allow(clippy::let_and_return)]
-        if !Self::step_4_verify_trace_and_composition_openings(proof, &challenges) {
+        if !Self::step_4_verify_trace_and_composition_openings(
+            proof,
+            preprocessed_commitment,
+            &challenges,
+        ) {
             error!("DEEP Composition Polynomial verification failed");
             return false;
         }
@@ -0,0 +1,225 @@
+use crate::{
+    config::MerkleHash,
+    examples::{
+        fibonacci_2_cols_shifted::{self, Fibonacci2ColsShifted},
+        fibonacci_2_columns::Fibonacci2ColsAIR,
+        fibonacci_rap::{FibonacciRAP, FibonacciRAPPublicInputs},
+        quadratic_air::{QuadraticAIR, QuadraticPublicInputs},
+        simple_fibonacci::{FibonacciAIR, FibonacciPublicInputs},
+    },
+    proof::{options::ProofOptions, stark::StarkProof},
+    trace::LDELayout,
+    traits::AIR,
+    transcript::StoneProverTranscript,
+    verifier::{IsStarkVerifier, Verifier},
+    Felt252, PrimeField,
+};
+use serde::{de::DeserializeOwned, Deserialize};
+use wasm_bindgen::prelude::*;
+
+#[wasm_bindgen]
+impl ProofOptions {
+    #[wasm_bindgen(constructor)]
+    pub fn new_js(
+        blowup_factor: u8,
+        fri_number_of_queries: usize,
+        coset_offset: u64,
+        grinding_factor: u8,
+    ) -> ProofOptions {
+        ProofOptions {
+            blowup_factor,
+            fri_number_of_queries,
+            coset_offset,
+            grinding_factor,
+            fri_last_layer_degree_bound: 1,
+            zk: false,
+            merkle_hash: MerkleHash::Keccak256,
+            lde_layout: LDELayout::Natural,
+        }
+    }
+}
+
+/// Verifies a STARK proof of the example AIR named `air`, which can be `fibonacci`,
+/// `fibonacci_2_columns`, `fibonacci_2_cols_shifted`, `fibonacci_rap` or `quadratic`.
+///
+/// `proof` is the [`StarkProof`] serialized with CBOR, and `public_inputs` a JSON object with the
+/// fields of the public inputs of the AIR, with field elements as hex strings. For example,
+/// `{"a0": "0x1", "a1": "0x1"}` for `fibonacci`. The proof must have been generated with a
+/// transcript seeded with no bytes, as the examples do.
+///
+/// Returns whether the proof is valid, or an error if the inputs can't be decoded.
+#[wasm_bindgen]
+pub fn verify_stark_proof(
+    air: &str,
+    proof: &[u8],
+    public_inputs: &str,
+    proof_options: &ProofOptions,
+) -> Result<bool, JsError> {
+    verify_serialized(air, proof, public_inputs, proof_options).map_err(|err| JsError::new(&err))
+}
+
+#[derive(Deserialize)]
+struct JsonFibonacciInputs {
+    a0: String,
+    a1: String,
+}
+
+#[derive(Deserialize)]
+struct JsonShiftedInputs {
+    claimed_value: String,
+    claimed_index: usize,
+}
+
+#[derive(Deserialize)]
+struct JsonRAPInputs {
+    steps: usize,
+    a0: String,
+    a1: String,
+}
+
+#[derive(Deserialize)]
+struct JsonQuadraticInputs {
+    a0: String,
+}
+
+fn verify_serialized(
+    air: &str,
+    proof: &[u8],
+    public_inputs: &str,
+    proof_options: &ProofOptions,
+) -> Result<bool, String> {
+    match air {
+        "fibonacci" => {
+            let inputs: JsonFibonacciInputs = parse_json(public_inputs)?;
+            let pub_inputs = FibonacciPublicInputs {
+                a0: parse_felt(&inputs.a0)?,
+                a1: parse_felt(&inputs.a1)?,
+            };
+            verify::<FibonacciAIR<PrimeField>>(proof, &pub_inputs, proof_options)
+        }
+        "fibonacci_2_columns" => {
+            let inputs: JsonFibonacciInputs = parse_json(public_inputs)?;
+            let pub_inputs = FibonacciPublicInputs {
+                a0: parse_felt(&inputs.a0)?,
+                a1: parse_felt(&inputs.a1)?,
+            };
+            verify::<Fibonacci2ColsAIR<PrimeField>>(proof, &pub_inputs, proof_options)
+        }
+        "fibonacci_2_cols_shifted" => {
+            let inputs: JsonShiftedInputs = parse_json(public_inputs)?;
+            let pub_inputs = fibonacci_2_cols_shifted::PublicInputs {
+                claimed_value: parse_felt(&inputs.claimed_value)?,
+                claimed_index: inputs.claimed_index,
+            };
+            verify::<Fibonacci2ColsShifted<PrimeField>>(proof, &pub_inputs, proof_options)
+        }
+        "fibonacci_rap" => {
+            let inputs: JsonRAPInputs = parse_json(public_inputs)?;
+            let pub_inputs = FibonacciRAPPublicInputs {
+                steps: inputs.steps,
+                a0: parse_felt(&inputs.a0)?,
+                a1: parse_felt(&inputs.a1)?,
+            };
+            verify::<FibonacciRAP<PrimeField>>(proof, &pub_inputs, proof_options)
+        }
+        "quadratic" => {
+            let inputs: JsonQuadraticInputs = parse_json(public_inputs)?;
+            let pub_inputs = QuadraticPublicInputs {
+                a0: parse_felt(&inputs.a0)?,
+            };
+            verify::<QuadraticAIR<PrimeField>>(proof, &pub_inputs, proof_options)
+        }
+        _ => Err(format!("unknown AIR {air}")),
+    }
+}
+
+fn verify<A>(
+    proof: &[u8],
+    pub_inputs: &A::PublicInputs,
+    proof_options: &ProofOptions,
+) -> Result<bool, String>
+where
+    A: AIR<Field = PrimeField, FieldExtension = PrimeField>,
+{
+    let proof: StarkProof<PrimeField, PrimeField> =
+        serde_cbor::from_slice(proof).map_err(|err| format!("invalid proof: {err}"))?;
+    Ok(Verifier::<A>::verify(
+        &proof,
+        pub_inputs,
+        proof_options,
+        StoneProverTranscript::new(&[]),
+    ))
+}
+
+fn parse_json<T: DeserializeOwned>(public_inputs: &str) -> Result<T, String> {
+    serde_json::from_str(public_inputs).map_err(|err| format!("invalid public inputs: {err}"))
+}
+
+fn parse_felt(hex: &str) -> Result<Felt252, String> {
+    Felt252::from_hex(hex).map_err(|_| format!("invalid field element {hex}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        examples::simple_fibonacci,
+        prover::{IsStarkProver, Prover},
+    };
+
+    fn fibonacci_proof() -> Vec<u8> {
+        let trace = simple_fibonacci::fibonacci_trace([Felt252::one(), Felt252::one()], 32);
+        let pub_inputs = FibonacciPublicInputs {
+            a0: Felt252::one(),
+            a1: Felt252::one(),
+        };
+        let proof = Prover::<FibonacciAIR<PrimeField>>::prove(
+            &trace,
+            &pub_inputs,
+            &ProofOptions::default_test_options(),
+            StoneProverTranscript::new(&[]),
+        )
+        .unwrap();
+        serde_cbor::to_vec(&proof).unwrap()
+    }
+
+    #[test]
+    fn serialized_fibonacci_proof_verifies() {
+        let proof = fibonacci_proof();
+        let options = ProofOptions::default_test_options();
+
+        assert_eq!(
+            verify_serialized(
+                "fibonacci",
+                &proof,
+                r#"{"a0": "0x1", "a1": "0x1"}"#,
+                &options
+            ),
+            Ok(true)
+        );
+        assert_eq!(
+            verify_serialized(
+                "fibonacci",
+                &proof,
+                r#"{"a0": "0x1", "a1": "0x2"}"#,
+                &options
+            ),
+            Ok(false)
+        );
+    }
+
+    #[test]
+    fn malformed_inputs_should_fail() {
+        let proof = fibonacci_proof();
+        let options = ProofOptions::default_test_options();
+        let pub_inputs = r#"{"a0": "0x1", "a1": "0x1"}"#;
+
+        assert!(verify_serialized("cairo", &proof, pub_inputs, &options).is_err());
+        assert!(verify_serialized("fibonacci", &proof[1..], pub_inputs, &options).is_err());
+        assert!(verify_serialized("fibonacci", &proof, r#"{"a0": "0x1"}"#, &options).is_err());
+        assert!(
+            verify_serialized("fibonacci", &proof, r#"{"a0": "0x1", "a1": "z"}"#, &options)
+                .is_err()
+        );
+    }
+}
@@ -0,0 +1,172 @@
+//! Zero-knowledge proofs, made with the `zk` option of
+//! [`ProofOptions`](crate::proof::options::ProofOptions).
+//!
+//! A proof reveals evaluations of the trace polynomials at the out-of-domain point and at the
+//! queries, and Merkle paths with the hashes of leaves that are not opened. To hide the witness:
+//!
+//! - Each polynomial `t` interpolating a column of the main or auxiliary trace, of length `n`,
+//!   is replaced by `t + Z r`, where `Z = X^n - 1` vanishes on the rows of the trace and `r` is
+//!   a random polynomial of degree less than `n`. It takes the same values on the trace, so the
+//!   constraints still hold, but any `n` evaluations outside of it are uniformly random. The
+//!   preprocessed columns are public, so they aren't blinded.
+//! - A random polynomial is committed with the parts of the composition polynomial and added to
+//!   the DEEP composition polynomial, so that the layers of FRI are random too.
+//! - Every Merkle leaf ends with a random salt, which is sent with its openings.
+//!
+//! Since the trace polynomials have degree less than `2n`, a constraint of degree `d` has degree
+//! `d n` more, so the composition polynomial is split in more parts, and FRI folds once more. The
+//! prover samples the randomness from an [`IsRandomSource`], and rejects options under which a
+//! proof would reveal more than `n` evaluations of a trace polynomial, directly or through the
+//! composition polynomial. The randomness is sampled in the base field, so the auxiliary trace
+//! can't be over a proper extension of it.
+
+use lambdaworks_math::{
+    environment::{random_field_element_of_bit_size, IsRandomSource},
+    field::{
+        element::FieldElement,
+        traits::{IsField, IsSubFieldOf},
+    },
+    polynomial::Polynomial,
+    traits::AsBytes,
+};
+
+use crate::{prover::ProvingError, traits::AIR};
+
+/// The number of bytes of randomness, at least, in the salt of a Merkle leaf.
+const SALT_BYTES: usize = 16;
+
+/// Checks that the AIR can be proven in zero-knowledge with its options.
+pub(crate) fn check_air<A: AIR>(air: &A) -> Result<(), ProvingError> {
+    let extension_degree = <A::Field as IsSubFieldOf<A::FieldExtension>>::to_subfield_vec(
+        FieldElement::<A::FieldExtension>::one().value().clone(),
+    )
+    .len();
+    if extension_degree != 1 {
+        return Err(ProvingError::WrongParameter(
+            "zero-knowledge proofs need the extension field to be the base field".to_string(),
+        ));
+    }
+
+    let number_of_parts = number_of_composition_poly_parts(air);
+    if (air.blowup_factor() as usize) < number_of_parts {
+        return Err(ProvingError::WrongParameter(format!(
+            "zero-knowledge proofs of this AIR need a blowup factor of at least {number_of_parts}"
+        )));
+    }
+
+    let revealed_evaluations = revealed_evaluations(air);
+    if revealed_evaluations > air.trace_length() {
+        return Err(ProvingError::WrongParameter(format!(
+            "zero-knowledge proofs with these options reveal {revealed_evaluations} evaluations of each trace polynomial, so they need at least as many rows"
+        )));
+    }
+    Ok(())
+}
+
+/// The number of parts the composition polynomial is split in: blinding the trace polynomials
+/// adds `d n` to the degree of the constraints of degree `d`, so it needs the maximum `d` more
+/// than proofs that aren't zero-knowledge.
+pub(crate) fn number_of_composition_poly_parts<A: AIR>(air: &A) -> usize {
+    let max_degree = air
+        .transition_constraints()
+        .iter()
+        .map(|constraint| constraint.degree())
+        .max()
+        .unwrap_or(1);
+    air.composition_poly_degree_bound() / air.trace_length() + max_degree
+}
+
+/// A bound on the number of evaluations of each trace polynomial that a proof reveals: the ones
+/// in the out-of-domain frame and at the queries and their symmetric points, directly, and the
+/// ones needed to evaluate the composition polynomial at all the points whose evaluations of its
+/// parts are revealed.
+fn revealed_evaluations<A: AIR>(air: &A) -> usize {
+    let frame_rows = air.context().transition_offsets.len() * A::STEP_SIZE;
+    let points = 2 * air.options().fri_number_of_queries + 1;
+    points * (number_of_composition_poly_parts(air) * frame_rows + 1)
+}
+
+/// Returns a random element of `F`, of the size of its byte representation.
+pub(crate) fn random_element<F: IsField>(source: &impl IsRandomSource) -> FieldElement<F>
+where
+    FieldElement<F>: AsBytes,
+{
+    let bit_size = FieldElement::<F>::zero().as_bytes().len() * 8;
+    random_field_element_of_bit_size(source, bit_size)
+}
+
+/// Returns a random polynomial with `len` coefficients.
+pub(crate) fn random_polynomial<F: IsField>(
+    len: usize,
+    source: &impl IsRandomSource,
+) -> Polynomial<FieldElement<F>>
+where
+    FieldElement<F>: AsBytes,
+{
+    let coefficients: Vec<_> = (0..len).map(|_| random_element(source)).collect();
+    Polynomial::new(&coefficients)
+}
+
+/// Returns the salt of a Merkle leaf of elements of `F`.
+pub(crate) fn random_salt<F: IsField>(source: &impl IsRandomSource) -> Vec<FieldElement<F>>
+where
+    FieldElement<F>: AsBytes,
+{
+    let element_bytes = FieldElement::<F>::zero().as_bytes().len().max(1);
+    (0..SALT_BYTES.div_ceil(element_bytes))
+        .map(|_| random_element(source))
+        .collect()
+}
+
+/// Returns `poly + (X^n - 1) r`, for a random polynomial `r` of degree less than `n`, where
+/// `n` is the length of the trace interpolated by `poly`.
+pub(crate) fn blind_trace_polynomial<S: IsSubFieldOf<E>, E: IsField>(
+    poly: &Polynomial<FieldElement<E>>,
+    trace_length: usize,
+    source: &impl IsRandomSource,
+) -> Polynomial<FieldElement<E>>
+where
+    FieldElement<S>: AsBytes,
+{
+    let r: Vec<FieldElement<E>> = (0..trace_length)
+        .map(|_| random_element::<S>(source).to_extension())
+        .collect();
+    let mut coefficients = poly.coefficients().to_vec();
+    coefficients.resize(trace_length, FieldElement::zero());
+    for (coefficient, r_i) in coefficients.iter_mut().zip(&r) {
+        *coefficient = &*coefficient - r_i;
+    }
+    coefficients.extend(r);
+    Polynomial::new(&coefficients)
+}
+
+#[cfg(test)]
+mod tests {
+    use lambdaworks_math::{
+        environment::SeededRandomSource,
+        field::fields::fft_friendly::stark_252_prime_field::Stark252PrimeField,
+    };
+
+    use super::*;
+
+    type FE = FieldElement<Stark252PrimeField>;
+
+    #[test]
+    fn blinded_polynomials_keep_their_values_on_the_trace() {
+        let values: Vec<FE> = (0..8u64).map(|i| FE::from(i * i + 1)).collect();
+        let poly = Polynomial::interpolate_fft::<Stark252PrimeField>(&values).unwrap();
+
+        let blinded = blind_trace_polynomial::<Stark252PrimeField, Stark252PrimeField>(
+            &poly,
+            8,
+            &SeededRandomSource::new(0),
+        );
+
+        assert_eq!(blinded.coeff_len(), 16);
+        let evaluations =
+            Polynomial::evaluate_fft::<Stark252PrimeField>(&blinded, 1, Some(16)).unwrap();
+        let trace_evaluations: Vec<_> = evaluations.into_iter().step_by(2).collect();
+        assert_eq!(trace_evaluations, values);
+        assert_ne!(blinded.evaluate(&FE::from(3)), poly.evaluate(&FE::from(3)));
+    }
+}
@@ -1,12 +1,17 @@
 use crate::utils::{
     matrix_lambda2winter, matrix_winter2lambda, vec_lambda2winter, vec_winter2lambda,
 };
+use lambdaworks_crypto::fiat_shamir::is_transcript::IsTranscript;
 use lambdaworks_math::field::element::FieldElement;
 use lambdaworks_math::field::traits::{IsFFTField, IsField, IsSubFieldOf};
 use lambdaworks_math::traits::ByteConversion;
 use miden_core::Felt;
 use stark_platinum_prover::{
-    constraints::boundary::{BoundaryConstraint, BoundaryConstraints},
+    constraints::{
+        boundary::{BoundaryConstraint, BoundaryConstraints},
+        transition::TransitionConstraint,
+    },
+    frame::Frame,
     traits::AIR,
 };
 use std::marker::PhantomData;
@@ -26,7 +31,58 @@ impl FromColumns<Felt, ()> for TraceTable<Felt> {
     }
 }
 
-#[derive(Clone)]
+/// Returns the Winterfell options equivalent to the lambdaworks `proof_options`.
+pub fn winterfell_proof_options(
+    proof_options: &stark_platinum_prover::proof::options::ProofOptions,
+) -> ProofOptions {
+    ProofOptions::new(
+        proof_options.fri_number_of_queries,
+        proof_options.blowup_factor as usize,
+        proof_options.grinding_factor as u32,
+        FieldExtension::None,
+        2,
+        0,
+    )
+}
+
+/// The zerofier of a transition constraint of a Winterfell AIR. Winterfell evaluates all the
+/// constraints at once, in [`AirAdapter::compute_transition_prover`] and
+/// [`AirAdapter::compute_transition_verifier`], so these are only used for their zerofiers.
+struct WinterfellTransitionConstraint<F, E> {
+    constraint_idx: usize,
+    degree: usize,
+    end_exemptions: usize,
+    phantom: PhantomData<(F, E)>,
+}
+
+impl<F, E> TransitionConstraint<F, E> for WinterfellTransitionConstraint<F, E>
+where
+    F: IsFFTField + IsSubFieldOf<E> + Send + Sync,
+    E: IsField + Send + Sync,
+{
+    fn degree(&self) -> usize {
+        self.degree
+    }
+
+    fn constraint_idx(&self) -> usize {
+        self.constraint_idx
+    }
+
+    fn end_exemptions(&self) -> usize {
+        self.end_exemptions
+    }
+
+    fn evaluate(
+        &self,
+        _frame: &Frame<F, E>,
+        _transition_evaluations: &mut [FieldElement<E>],
+        _periodic_values: &[FieldElement<F>],
+        _rap_challenges: &[FieldElement<E>],
+    ) {
+        unreachable!("the constraints of a Winterfell AIR are evaluated by the AirAdapter")
+    }
+}
+
 pub struct AirAdapter<A, T, FE, E, M>
 where
     FE: IsWinterfellFieldElement
@@ -34,8 +90,10 @@ where
         + ByteConversion
         + Unpin
         + IsFFTField
-        + IsSubFieldOf<E>,
-    E: IsField,
+        + IsSubFieldOf<E>
+        + Send
+        + Sync,
+    E: IsField + Send + Sync,
     A: Air<BaseField = FE>,
     A::PublicInputs: Clone,
     T: Trace<BaseField = FE> + Clone + FromColumns<FE, M>,
@@ -44,6 +102,7 @@ where
     winterfell_air: A,
     public_inputs: AirAdapterPublicInputs<A, M>,
     air_context: stark_platinum_prover::context::AirContext,
+    transition_constraints: Vec<Box<dyn TransitionConstraint<FE, E>>>,
     trace: PhantomData<T>,
     extension: PhantomData<E>,
 }
@@ -56,8 +115,11 @@ where
         + Unpin
         + IsFFTField
         + IsField<BaseType = FE>
-        + IsSubFieldOf<E>,
-    E: IsField<BaseType = E> + IsWinterfellFieldElement<BaseField = FE>,
+        + IsSubFieldOf<E>
+        + Send
+        + Sync
+        + 'static,
+    E: IsField<BaseType = E> + IsWinterfellFieldElement<BaseField = FE> + Send + Sync + 'static,
     A: Air<BaseField = FE> + Clone,
     A::PublicInputs: Clone,
     T: Trace<BaseField = FE> + Clone + FromColumns<FE, M>,
@@ -71,7 +133,10 @@ where
             columns.push(trace.get_column(i).to_owned());
         }
 
-        stark_platinum_prover::trace::TraceTable::from_columns(matrix_winter2lambda(&columns), 1)
+        stark_platinum_prover::trace::TraceTable::from_columns_main(
+            matrix_winter2lambda(&columns),
+            1,
+        )
     }
 }
 
@@ -83,8 +148,11 @@ where
         + Unpin
         + IsFFTField
         + IsField<BaseType = FE>
-        + IsSubFieldOf<E>,
-    E: IsField<BaseType = E> + IsWinterfellFieldElement<BaseField = FE>,
+        + IsSubFieldOf<E>
+        + Send
+        + Sync
+        + 'static,
+    E: IsField<BaseType = E> + IsWinterfellFieldElement<BaseField = FE> + Send + Sync + 'static,
     A: Air<BaseField = FE> + Clone,
     A::PublicInputs: Clone,
     T: Trace<BaseField = FE> + Clone + FromColumns<FE, M>,
@@ -92,7 +160,6 @@ where
 {
     type Field = FE;
     type FieldExtension = E;
-    type RAPChallenges = Vec<E>;
     type PublicInputs = AirAdapterPublicInputs<A, M>;
     const STEP_SIZE: usize = 1;
 
@@ -101,22 +168,27 @@ where
         pub_inputs: &Self::PublicInputs,
         lambda_proof_options: &stark_platinum_prover::proof::options::ProofOptions,
     ) -> Self {
-        let winter_proof_options = ProofOptions::new(
-            lambda_proof_options.fri_number_of_queries,
-            lambda_proof_options.blowup_factor as usize,
-            lambda_proof_options.grinding_factor as u32,
-            FieldExtension::None,
-            2,
-            0,
-        );
-
         let winterfell_air = A::new(
             pub_inputs.trace_info.clone(),
             pub_inputs.winterfell_public_inputs.clone(),
-            winter_proof_options,
+            winterfell_proof_options(lambda_proof_options),
         );
         let winterfell_context = winterfell_air.context();
 
+        // Winterfell splits the composition polynomial in one column per degree of its
+        // constraints above one, so this bounds the degree of each of them.
+        let degree = winterfell_context.num_constraint_composition_columns() + 1;
+        let transition_constraints = (0..winterfell_context.num_transition_constraints())
+            .map(|constraint_idx| {
+                Box::new(WinterfellTransitionConstraint {
+                    constraint_idx,
+                    degree,
+                    end_exemptions: pub_inputs.transition_exemptions[constraint_idx],
+                    phantom: PhantomData,
+                }) as Box<dyn TransitionConstraint<FE, E>>
+            })
+            .collect();
+
         let lambda_context = stark_platinum_prover::context::AirContext {
             proof_options: lambda_proof_options.clone(),
             transition_exemptions: pub_inputs.transition_exemptions.to_owned(),
@@ -129,6 +201,7 @@ where
             winterfell_air,
             public_inputs: pub_inputs.clone(),
             air_context: lambda_context,
+            transition_constraints,
             trace: PhantomData,
             extension: PhantomData,
         }
@@ -137,14 +210,14 @@ where
     fn build_auxiliary_trace(
         &self,
         main_trace: &stark_platinum_prover::trace::TraceTable<Self::Field>,
-        rap_challenges: &Self::RAPChallenges,
+        rap_challenges: &[FieldElement<Self::FieldExtension>],
     ) -> stark_platinum_prover::trace::TraceTable<Self::FieldExtension> {
         // We support at most a one-stage RAP. This covers most use cases.
         if let Some(winter_trace) = T::from_cols(
             matrix_lambda2winter(&main_trace.columns()),
             &self.pub_inputs().metadata,
         )
-        .build_aux_segment(&[], rap_challenges)
+        .build_aux_segment(&[], &vec_lambda2winter(rap_challenges))
         {
             let mut columns = Vec::new();
             for i in 0..winter_trace.num_cols() {
@@ -152,6 +225,7 @@ where
             }
             stark_platinum_prover::trace::TraceTable::<E>::from_columns(
                 matrix_winter2lambda(&columns),
+                0,
                 1,
             )
         } else {
@@ -161,17 +235,15 @@ where
 
     fn build_rap_challenges(
         &self,
-        transcript: &mut impl stark_platinum_prover::transcript::IsStarkTranscript<Self::FieldExtension>,
-    ) -> Self::RAPChallenges {
+        transcript: &mut impl IsTranscript<Self::FieldExtension>,
+    ) -> Vec<FieldElement<Self::FieldExtension>> {
         let trace_layout = self.winterfell_air.trace_layout();
         let num_segments = trace_layout.num_aux_segments();
 
         if num_segments == 1 {
-            let mut result = Vec::new();
-            for _ in 0..trace_layout.get_aux_segment_rand_elements(0) {
-                result.push(transcript.sample_field_element());
-            }
-            vec_lambda2winter(&result)
+            (0..trace_layout.get_aux_segment_rand_elements(0))
+                .map(|_| transcript.sample_field_element())
+                .collect()
         } else if num_segments == 0 {
             Vec::new()
         } else {
@@ -179,8 +251,12 @@ where
         }
     }
 
-    fn number_auxiliary_rap_columns(&self) -> usize {
-        self.winterfell_air.trace_layout().aux_trace_width()
+    fn trace_layout(&self) -> (usize, usize) {
+        let trace_layout = self.winterfell_air.trace_layout();
+        (
+            trace_layout.main_trace_width(),
+            trace_layout.aux_trace_width(),
+        )
     }
 
     fn composition_poly_degree_bound(&self) -> usize {
@@ -192,16 +268,16 @@ where
 
     fn compute_transition_prover(
         &self,
-        frame: &stark_platinum_prover::frame::Frame<Self::Field, Self::FieldExtension>,
+        frame: &Frame<Self::Field, Self::FieldExtension>,
         periodic_values: &[FieldElement<Self::Field>],
-        rap_challenges: &Self::RAPChallenges,
+        rap_challenges: &[FieldElement<Self::FieldExtension>],
     ) -> Vec<FieldElement<Self::FieldExtension>> {
         let first_step = frame.get_evaluation_step(0);
         let second_step = frame.get_evaluation_step(1);
 
         let main_frame = EvaluationFrame::from_rows(
-            vec_lambda2winter(first_step.get_row_main(0)),
-            vec_lambda2winter(second_step.get_row_main(0)),
+            vec_lambda2winter(first_step.data[0]),
+            vec_lambda2winter(second_step.data[0]),
         );
 
         let periodic_values = vec_lambda2winter(periodic_values);
@@ -227,14 +303,14 @@ where
 
         if self.winterfell_air.trace_layout().num_aux_segments() == 1 {
             let mut rand_elements = AuxTraceRandElements::new();
-            rand_elements.add_segment_elements(rap_challenges.clone());
+            rand_elements.add_segment_elements(vec_lambda2winter(rap_challenges));
 
             let first_step = frame.get_evaluation_step(0);
             let second_step = frame.get_evaluation_step(1);
 
             let aux_frame = EvaluationFrame::from_rows(
-                vec_lambda2winter(first_step.get_row_aux(0)),
-                vec_lambda2winter(second_step.get_row_aux(0)),
+                vec_lambda2winter(first_step.aux_data[0]),
+                vec_lambda2winter(second_step.aux_data[0]),
             );
 
             let mut aux_result = vec![
@@ -259,7 +335,7 @@ where
 
     fn boundary_constraints(
         &self,
-        rap_challenges: &Self::RAPChallenges,
+        rap_challenges: &[FieldElement<Self::FieldExtension>],
     ) -> stark_platinum_prover::constraints::boundary::BoundaryConstraints<E> {
         let mut result = Vec::new();
         for assertion in self.winterfell_air.get_assertions() {
@@ -272,7 +348,7 @@ where
         }
 
         let mut rand_elements = AuxTraceRandElements::new();
-        rand_elements.add_segment_elements(rap_challenges.clone());
+        rand_elements.add_segment_elements(vec_lambda2winter(rap_challenges));
 
         for assertion in self.winterfell_air.get_aux_assertions(&rand_elements) {
             assert!(assertion.is_single());
@@ -302,18 +378,24 @@ where
         matrix_winter2lambda(&self.winterfell_air.get_periodic_column_values())
     }
 
+    fn transition_constraints(
+        &self,
+    ) -> &Vec<Box<dyn TransitionConstraint<Self::Field, Self::FieldExtension>>> {
+        &self.transition_constraints
+    }
+
     fn compute_transition_verifier(
         &self,
-        frame: &stark_platinum_prover::frame::Frame<Self::FieldExtension, Self::FieldExtension>,
+        frame: &Frame<Self::FieldExtension, Self::FieldExtension>,
         periodic_values: &[FieldElement<Self::FieldExtension>],
-        rap_challenges: &Self::RAPChallenges,
+        rap_challenges: &[FieldElement<Self::FieldExtension>],
     ) -> Vec<FieldElement<Self::FieldExtension>> {
         let first_step = frame.get_evaluation_step(0);
         let second_step = frame.get_evaluation_step(1);
 
         let main_frame = EvaluationFrame::from_rows(
-            vec_lambda2winter(first_step.get_row_main(0)),
-            vec_lambda2winter(second_step.get_row_main(0)),
+            vec_lambda2winter(first_step.data[0]),
+            vec_lambda2winter(second_step.data[0]),
         );
 
         let periodic_values = vec_lambda2winter(periodic_values);
@@ -336,14 +418,14 @@ where
 
         if self.winterfell_air.trace_layout().num_aux_segments() == 1 {
             let mut rand_elements = AuxTraceRandElements::new();
-            rand_elements.add_segment_elements(rap_challenges.clone());
+            rand_elements.add_segment_elements(vec_lambda2winter(rap_challenges));
 
             let first_step = frame.get_evaluation_step(0);
             let second_step = frame.get_evaluation_step(1);
 
             let aux_frame = EvaluationFrame::from_rows(
-                vec_lambda2winter(first_step.get_row_aux(0)),
-                vec_lambda2winter(second_step.get_row_aux(0)),
+                vec_lambda2winter(first_step.aux_data[0]),
+                vec_lambda2winter(second_step.aux_data[0]),
             );
 
             let mut aux_result = vec![
@@ -1,7 +1,8 @@
+use lambdaworks_crypto::fiat_shamir::is_transcript::IsTranscript;
 use lambdaworks_math::{field::fields::winterfell::QuadFelt, traits::ByteConversion};
 use miden_core::Felt;
 use sha3::{Digest, Keccak256};
-use stark_platinum_prover::{fri::FieldElement, transcript::IsStarkTranscript};
+use stark_platinum_prover::fri::FieldElement;
 use winter_math::StarkField;
 
 pub mod air;
@@ -21,7 +22,7 @@ impl FeltTranscript {
     }
 }
 
-impl IsStarkTranscript<Felt> for FeltTranscript {
+impl IsTranscript<Felt> for FeltTranscript {
     fn append_field_element(&mut self, element: &FieldElement<Felt>) {
         self.append_bytes(&element.value().to_bytes_be());
     }
@@ -62,7 +63,7 @@ impl QuadFeltTranscript {
     }
 }
 
-impl IsStarkTranscript<QuadFelt> for QuadFeltTranscript {
+impl IsTranscript<QuadFelt> for QuadFeltTranscript {
     fn append_field_element(&mut self, element: &FieldElement<QuadFelt>) {
         self.append_bytes(&element.value().to_bytes_be());
     }
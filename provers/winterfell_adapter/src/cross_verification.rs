@@ -0,0 +1,187 @@
+//! Checks that a Winterfell AIR and its lambdaworks adapter accept the same traces, so that
+//! teams moving an AIR between the two libraries can validate that they prove the same
+//! statement.
+
+use miden_core::Felt;
+use stark_platinum_prover::{
+    proof::options::ProofOptions,
+    prover::{IsStarkProver, Prover},
+    verifier::{IsStarkVerifier, Verifier},
+};
+use winter_air::{Air, EvaluationFrame};
+use winter_math::FieldElement as IsWinterfellFieldElement;
+use winter_prover::{ColMatrix, Trace};
+
+use crate::adapter::{
+    air::{winterfell_proof_options, AirAdapter, FromColumns},
+    public_inputs::AirAdapterPublicInputs,
+    FeltTranscript,
+};
+
+/// Whether each library accepts a trace of an AIR.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CrossVerification {
+    /// The trace satisfies the constraints of the AIR, as Winterfell evaluates them.
+    pub winterfell: bool,
+    /// A lambdaworks proof of the trace, made through the [`AirAdapter`], verifies.
+    pub lambdaworks: bool,
+}
+
+impl CrossVerification {
+    pub fn agree(&self) -> bool {
+        self.winterfell == self.lambdaworks
+    }
+}
+
+/// Checks `trace` against the AIR `A` with both libraries. The AIR is instantiated with the
+/// Winterfell public inputs of `pub_inputs`, and the lambdaworks proof is made with
+/// `proof_options`.
+pub fn cross_verify<A, T, M>(
+    trace: &T,
+    pub_inputs: &AirAdapterPublicInputs<A, M>,
+    proof_options: &ProofOptions,
+) -> CrossVerification
+where
+    A: Air<BaseField = Felt> + Clone,
+    A::PublicInputs: Clone,
+    T: Trace<BaseField = Felt> + Clone + FromColumns<Felt, M>,
+    M: Clone,
+{
+    let air = A::new(
+        pub_inputs.trace_info.clone(),
+        pub_inputs.winterfell_public_inputs.clone(),
+        winterfell_proof_options(proof_options),
+    );
+    let winterfell = winterfell_accepts(&air, trace.main_segment());
+
+    let lambda_trace = AirAdapter::<A, T, Felt, Felt, M>::convert_winterfell_trace_table(
+        trace.main_segment().clone(),
+    );
+    let lambdaworks = Prover::<AirAdapter<A, T, Felt, Felt, M>>::prove(
+        &lambda_trace,
+        pub_inputs,
+        proof_options,
+        FeltTranscript::new(&[]),
+    )
+    .map(|proof| {
+        Verifier::<AirAdapter<A, T, Felt, Felt, M>>::verify(
+            &proof,
+            pub_inputs,
+            proof_options,
+            FeltTranscript::new(&[]),
+        )
+    })
+    .unwrap_or(false);
+
+    CrossVerification {
+        winterfell,
+        lambdaworks,
+    }
+}
+
+/// Returns whether the main segment of a trace satisfies the transition constraints and
+/// assertions of `air`, evaluating them with Winterfell. Auxiliary segments are not checked,
+/// since their random elements come from the prover's transcript.
+pub fn winterfell_accepts<A: Air<BaseField = Felt>>(air: &A, trace: &ColMatrix<Felt>) -> bool {
+    let trace_length = trace.num_rows();
+    let row = |step: usize| -> Vec<Felt> {
+        (0..trace.num_cols())
+            .map(|column| trace.get(column, step))
+            .collect()
+    };
+    let periodic_columns = air.get_periodic_column_values();
+    let num_constraints = air.context().num_main_transition_constraints();
+    let last_constrained_step = trace_length - air.context().num_transition_exemptions();
+
+    for step in 0..last_constrained_step {
+        let frame = EvaluationFrame::from_rows(row(step), row((step + 1) % trace_length));
+        let periodic_values: Vec<Felt> = periodic_columns
+            .iter()
+            .map(|column| column[step % column.len()])
+            .collect();
+        let mut result = vec![Felt::ZERO; num_constraints];
+        air.evaluate_transition(&frame, &periodic_values, &mut result);
+        if result.iter().any(|evaluation| *evaluation != Felt::ZERO) {
+            return false;
+        }
+    }
+
+    air.get_assertions().iter().all(|assertion| {
+        let mut satisfied = true;
+        assertion.apply(trace_length, |step, value| {
+            satisfied &= trace.get(assertion.column(), step) == value;
+        });
+        satisfied
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::examples::{
+        cubic::{self, Cubic},
+        fibonacci_2_terms::{self, FibAir2Terms},
+    };
+    use winter_air::TraceInfo;
+    use winter_prover::TraceTable;
+
+    fn fibonacci_inputs(result: Felt) -> AirAdapterPublicInputs<FibAir2Terms, ()> {
+        AirAdapterPublicInputs {
+            winterfell_public_inputs: result,
+            transition_exemptions: vec![1, 1],
+            transition_offsets: vec![0, 1],
+            trace_info: TraceInfo::new(2, 8),
+            metadata: (),
+        }
+    }
+
+    #[test]
+    fn both_libraries_accept_a_valid_fibonacci_trace() {
+        let trace = fibonacci_2_terms::build_trace(16);
+        let result = trace.get(1, 7);
+
+        let verification = cross_verify(
+            &trace,
+            &fibonacci_inputs(result),
+            &ProofOptions::default_test_options(),
+        );
+        assert!(verification.winterfell);
+        assert!(verification.lambdaworks);
+    }
+
+    #[test]
+    fn both_libraries_reject_a_wrong_fibonacci_result() {
+        let trace = fibonacci_2_terms::build_trace(16);
+        let result = trace.get(1, 7) + Felt::ONE;
+
+        let verification = cross_verify(
+            &trace,
+            &fibonacci_inputs(result),
+            &ProofOptions::default_test_options(),
+        );
+        assert!(!verification.winterfell);
+        assert!(verification.agree());
+    }
+
+    #[test]
+    fn both_libraries_reject_a_tampered_cubic_trace() {
+        let mut column = cubic::build_trace(16)
+            .main_segment()
+            .get_column(0)
+            .to_owned();
+        let result = column[15];
+        column[5] += Felt::ONE;
+        let trace = TraceTable::init(vec![column]);
+        let pub_inputs = AirAdapterPublicInputs::<Cubic, ()> {
+            winterfell_public_inputs: result,
+            transition_exemptions: vec![1],
+            transition_offsets: vec![0, 1],
+            trace_info: TraceInfo::new(1, 16),
+            metadata: (),
+        };
+
+        let verification = cross_verify(&trace, &pub_inputs, &ProofOptions::default_test_options());
+        assert!(!verification.winterfell);
+        assert!(verification.agree());
+    }
+}
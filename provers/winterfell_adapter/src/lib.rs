@@ -1,4 +1,5 @@
 pub mod adapter;
+pub mod cross_verification;
 pub mod examples;
 pub mod field_element;
 pub mod utils;